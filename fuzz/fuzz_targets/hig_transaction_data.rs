@@ -0,0 +1,41 @@
+//! Feeds arbitrary transaction data through the HIG's transaction processing path.
+//!
+//! Data that fails validation must be rejected with `InvalidTransactionData` and must not leave
+//! the transaction pending; no input may panic.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use hyperplane::{
+    hyper_ig::{node::HyperIGNode, HyperIG, HyperIGError},
+    types::{CLTransactionId, NetworkSpec, Transaction, TransactionId},
+    types::communication::cl_to_hig::TransactionData,
+};
+use tokio::sync::mpsc;
+
+fuzz_target!(|data: &str| {
+    let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+    runtime.block_on(async {
+        let (_sender_cl_to_hig, receiver_cl_to_hig) = mpsc::channel(1);
+        let (sender_hig_to_hs, _receiver_hig_to_hs) = mpsc::channel(16);
+        let network = NetworkSpec::default();
+        let chain_ids = network.chain_ids();
+        let mut hig_node = HyperIGNode::new(receiver_cl_to_hig, sender_hig_to_hs, chain_ids[0].clone(), 4, true);
+
+        let tx = Transaction {
+            id: TransactionId("cl-tx_fuzz:tx".to_string()),
+            chain_id: chain_ids[0].clone(),
+            constituent_chains: chain_ids.to_vec(),
+            data: data.to_string(),
+            cl_id: CLTransactionId("cl-tx_fuzz".to_string()),
+            expiry: None,
+        };
+        let result = hig_node.process_transaction(tx.clone()).await;
+
+        if !data.starts_with("STATUS_UPDATE") && TransactionData::validate(data).is_err() {
+            let error = result.expect_err("Malformed data was accepted");
+            assert!(matches!(error.downcast_ref::<HyperIGError>(), Some(HyperIGError::InvalidTransactionData { .. })));
+            assert!(!hig_node.get_pending_transactions().await.unwrap().contains(&tx.id));
+        }
+    });
+});
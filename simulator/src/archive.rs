@@ -0,0 +1,95 @@
+//! Archiving of the raw data of completed sweep points.
+//!
+//! Sweeps write dozens of small JSON files per run, which adds up to thousands of files per sweep.
+//! Once the sweep report has been generated, the raw data of each sweep point can be packed into a
+//! single `raw_data.tar.zst` (using the system `tar` with zstd support), leaving only the summaries
+//! of each run uncompressed.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::metadata::METADATA_FILE;
+
+// ------------------------------------------------------------------------------------------------
+// Constants
+// ------------------------------------------------------------------------------------------------
+
+/// Name of the archive written into the directory of each sweep point
+pub const ARCHIVE_FILE: &str = "raw_data.tar.zst";
+
+/// Files that stay uncompressed next to the archive
+const SUMMARY_FILES: [&str; 3] = ["simulation_stats.json", METADATA_FILE, "config.toml"];
+
+// ------------------------------------------------------------------------------------------------
+// Archiving
+// ------------------------------------------------------------------------------------------------
+
+/// Size of a sweep point's raw data before and after archiving
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ArchiveSummary {
+    pub files_archived: usize,
+    pub bytes_before: u64,
+    pub bytes_after: u64,
+}
+
+/// Packs the raw data of the runs of a sweep point into a zstd-compressed tarball
+///
+/// The archived files are only deleted once `tar` has succeeded, so a failure leaves the data untouched.
+///
+/// # Arguments
+/// * `sim_dir` - The directory of the sweep point (e.g. `simulator/results/<sweep>/data/sim_0`)
+pub fn archive_sweep_point(sim_dir: &str) -> Result<ArchiveSummary, String> {
+    let sim_path = Path::new(sim_dir);
+    let mut files = Vec::new();
+    for entry in fs::read_dir(sim_path).map_err(|e| format!("Failed to read {}: {}", sim_dir, e))? {
+        let path = entry.map_err(|e| e.to_string())?.path();
+        let is_run_dir = path.file_name().and_then(|name| name.to_str()).is_some_and(|name| name.starts_with("run_"));
+        if path.is_dir() && is_run_dir {
+            collect_raw_files(&path, &mut files)?;
+        }
+    }
+    if files.is_empty() {
+        return Ok(ArchiveSummary::default());
+    }
+
+    // Paths are passed relative to the sweep point through a file list, since there can be thousands
+    let relative: Vec<String> = files.iter()
+        .map(|file| file.strip_prefix(sim_path).map(|path| path.to_string_lossy().into_owned()).map_err(|e| e.to_string()))
+        .collect::<Result<_, _>>()?;
+    let file_list = sim_path.join("raw_data.files");
+    fs::write(&file_list, relative.join("\n")).map_err(|e| e.to_string())?;
+    let status = Command::new("tar")
+        .arg("--zstd")
+        .arg("-cf").arg(ARCHIVE_FILE)
+        .arg("-T").arg("raw_data.files")
+        .current_dir(sim_path)
+        .status();
+    let _ = fs::remove_file(&file_list);
+    match status {
+        Ok(status) if status.success() => {}
+        Ok(status) => return Err(format!("tar exited with {} while archiving {}", status, sim_dir)),
+        Err(e) => return Err(format!("Failed to run tar (zstd support is required): {}", e)),
+    }
+
+    let bytes_before = files.iter().map(|file| fs::metadata(file).map(|meta| meta.len()).unwrap_or(0)).sum();
+    for file in &files {
+        fs::remove_file(file).map_err(|e| format!("Failed to remove {}: {}", file.display(), e))?;
+    }
+    let bytes_after = fs::metadata(sim_path.join(ARCHIVE_FILE)).map_err(|e| e.to_string())?.len();
+
+    Ok(ArchiveSummary { files_archived: files.len(), bytes_before, bytes_after })
+}
+
+/// Collects all files below `dir` except the run summaries
+fn collect_raw_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), String> {
+    for entry in fs::read_dir(dir).map_err(|e| format!("Failed to read {}: {}", dir.display(), e))? {
+        let path = entry.map_err(|e| e.to_string())?.path();
+        if path.is_dir() {
+            collect_raw_files(&path, files)?;
+        } else if !path.file_name().and_then(|name| name.to_str()).is_some_and(|name| SUMMARY_FILES.contains(&name)) {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
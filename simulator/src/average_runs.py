@@ -8,6 +8,9 @@ from collections import defaultdict
 import numpy as np
 import shutil
 
+sys.path.insert(0, os.path.dirname(os.path.abspath(__file__)))
+from results_schema import SCHEMA_VERSION_KEY, RESULTS_SCHEMA_VERSION, migrate
+
 def load_metadata(results_dir):
     """Load metadata to get number of runs and parameters."""
     try:
@@ -28,14 +31,17 @@ def load_run_data(run_dir):
         print(f"Warning: No data directory found in {run_dir}")
         return run_data
     
-    # Load all JSON files in the data directory
+    # Load all JSON files in the data directory (the recorded workload is only used for replays)
     for filename in os.listdir(data_dir):
-        if filename.endswith('.json'):
+        if filename.endswith('.json') and filename != 'workload.json':
             filepath = os.path.join(data_dir, filename)
             try:
                 with open(filepath, 'r') as f:
                     data = json.load(f)
-                    run_data[filename] = data
+                # Stats written by older simulator versions are read in the current schema
+                if filename == 'simulation_stats.json':
+                    data = migrate(data)
+                run_data[filename] = data
             except Exception as e:
                 print(f"Warning: Could not load {filepath}: {e}")
     
@@ -87,7 +93,7 @@ def average_time_series_data(all_runs_data, key_name):
                 'height': height,
                 'percent': avg_value
             })
-        elif key_name == 'system_total_cpu':
+        elif key_name in ('system_total_cpu', 'worker_cpu', 'worker_cpu_max', 'runtime_busy'):
             averaged_data.append({
                 'height': height,
                 'percent': avg_value
@@ -127,7 +133,9 @@ def average_scalar_values(all_runs_data, key_path):
                 else:
                     current = 0
                     break
-            values.append(float(current))
+            # Values that do not apply to a run (null) are left out
+            if current is not None:
+                values.append(float(current))
     
     return np.mean(values) if values else 0.0
 
@@ -222,6 +230,7 @@ def create_averaged_data(results_dir):
         
         # Average simulation statistics
         avg_stats = {
+            SCHEMA_VERSION_KEY: RESULTS_SCHEMA_VERSION,
             'simulation_index': sim_index,
             'averaging_info': {
                 'num_runs': len(all_runs_data),
@@ -231,7 +240,36 @@ def create_averaged_data(results_dir):
             'results': {
                 'total_transactions': average_scalar_values(all_runs_data, ['results', 'total_transactions']),
                 'cat_transactions': average_scalar_values(all_runs_data, ['results', 'cat_transactions']),
-                'regular_transactions': average_scalar_values(all_runs_data, ['results', 'regular_transactions'])
+                'regular_transactions': average_scalar_values(all_runs_data, ['results', 'regular_transactions']),
+                'peak_memory_mb': average_scalar_values(all_runs_data, ['results', 'peak_memory_mb']),
+                'mean_cpu_percent': average_scalar_values(all_runs_data, ['results', 'mean_cpu_percent']),
+                'mean_worker_cpu_percent': average_scalar_values(all_runs_data, ['results', 'mean_worker_cpu_percent']),
+                'mean_hig_processing_ms': average_scalar_values(all_runs_data, ['results', 'mean_hig_processing_ms']),
+                'max_proposal_queue_depth': average_scalar_values(all_runs_data, ['results', 'max_proposal_queue_depth']),
+                'dropped_proposals': average_scalar_values(all_runs_data, ['results', 'dropped_proposals']),
+                'duplicate_transactions': average_scalar_values(all_runs_data, ['results', 'duplicate_transactions']),
+                'dependency_cycles': average_scalar_values(all_runs_data, ['results', 'dependency_cycles']),
+                'total_lock_wait_ms': average_scalar_values(all_runs_data, ['results', 'total_lock_wait_ms']),
+                'hottest_key_wait_share': average_scalar_values(all_runs_data, ['results', 'hottest_key_wait_share']),
+                'early_lock_releases': average_scalar_values(all_runs_data, ['results', 'early_lock_releases']),
+                'regular_tx_expirations': average_scalar_values(all_runs_data, ['results', 'regular_tx_expirations']),
+                'regular_tx_avg_latency_ms': average_scalar_values(all_runs_data, ['results', 'regular_tx_avg_latency_ms']),
+                'mean_block_utilization': average_scalar_values(all_runs_data, ['results', 'mean_block_utilization']),
+                'mean_block_byte_utilization': average_scalar_values(all_runs_data, ['results', 'mean_block_byte_utilization']),
+                'mean_block_serialized_bytes': average_scalar_values(all_runs_data, ['results', 'mean_block_serialized_bytes']),
+                'mean_cl_bytes_per_second': average_scalar_values(all_runs_data, ['results', 'mean_cl_bytes_per_second']),
+                'cats_awaiting_chain_1': average_scalar_values(all_runs_data, ['results', 'cats_awaiting_chain_1']),
+                'cats_awaiting_chain_2': average_scalar_values(all_runs_data, ['results', 'cats_awaiting_chain_2']),
+                'hs_proposals_chain_1': average_scalar_values(all_runs_data, ['results', 'hs_proposals_chain_1']),
+                'hs_proposals_chain_2': average_scalar_values(all_runs_data, ['results', 'hs_proposals_chain_2']),
+                'hs_decisions': average_scalar_values(all_runs_data, ['results', 'hs_decisions']),
+                'hs_decisions_per_block': average_scalar_values(all_runs_data, ['results', 'hs_decisions_per_block']),
+                'hs_decisions_per_block_max': average_scalar_values(all_runs_data, ['results', 'hs_decisions_per_block_max']),
+                'mean_proposal_spread_ms': average_scalar_values(all_runs_data, ['results', 'mean_proposal_spread_ms']),
+                'mean_pending_cat_age_ms': average_scalar_values(all_runs_data, ['results', 'mean_pending_cat_age_ms']),
+                'mean_hs_inclusion_to_decision_ms': average_scalar_values(all_runs_data, ['results', 'mean_hs_inclusion_to_decision_ms']),
+                'mean_hs_decision_to_submission_ms': average_scalar_values(all_runs_data, ['results', 'mean_hs_decision_to_submission_ms']),
+                'rate_anomaly_count': average_scalar_values(all_runs_data, ['results', 'rate_anomaly_count'])
             }
         }
         
@@ -257,6 +295,10 @@ def create_averaged_data(results_dir):
             ('cat_pending_resolving_transactions_chain_2.json', 'chain_2_cat_pending_resolving'),
             ('cat_pending_postponed_transactions_chain_1.json', 'chain_1_cat_pending_postponed'),
             ('cat_pending_postponed_transactions_chain_2.json', 'chain_2_cat_pending_postponed'),
+            ('cat_timed_out_transactions_chain_1.json', 'chain_1_cat_timed_out'),
+            ('cat_timed_out_transactions_chain_2.json', 'chain_2_cat_timed_out'),
+            ('cat_rejected_transactions_chain_1.json', 'chain_1_cat_rejected'),
+            ('cat_rejected_transactions_chain_2.json', 'chain_2_cat_rejected'),
             ('regular_pending_transactions_chain_1.json', 'chain_1_regular_pending'),
             ('regular_pending_transactions_chain_2.json', 'chain_2_regular_pending'),
             ('regular_success_transactions_chain_1.json', 'chain_1_regular_success'),
@@ -277,7 +319,27 @@ def create_averaged_data(results_dir):
             ('system_total_memory.json', 'system_total_memory'),
             ('system_cpu.json', 'system_cpu'),
             ('system_total_cpu.json', 'system_total_cpu'),
+            ('worker_cpu.json', 'worker_cpu'),
+            ('worker_cpu_max.json', 'worker_cpu_max'),
+            ('runtime_alive_tasks.json', 'runtime_alive_tasks'),
+            ('runtime_queue_depth.json', 'runtime_queue_depth'),
+            ('runtime_busy.json', 'runtime_busy'),
+            ('runtime_poll_latency.json', 'runtime_poll_latency'),
             ('loop_steps_without_tx_issuance.json', 'loop_steps_without_tx_issuance'),
+            ('chain_1_mempool_age_p50.json', 'chain_1_mempool_age_p50'),
+            ('chain_2_mempool_age_p50.json', 'chain_2_mempool_age_p50'),
+            ('chain_1_mempool_age_p99.json', 'chain_1_mempool_age_p99'),
+            ('chain_2_mempool_age_p99.json', 'chain_2_mempool_age_p99'),
+            ('chain_1_hig_lock_latency.json', 'chain_1_hig_lock_latency'),
+            ('chain_2_hig_lock_latency.json', 'chain_2_hig_lock_latency'),
+            ('chain_1_hig_execution_latency.json', 'chain_1_hig_execution_latency'),
+            ('chain_2_hig_execution_latency.json', 'chain_2_hig_execution_latency'),
+            ('chain_1_hig_queuing_latency.json', 'chain_1_hig_queuing_latency'),
+            ('chain_2_hig_queuing_latency.json', 'chain_2_hig_queuing_latency'),
+            ('chain_1_proposal_queue_depth.json', 'chain_1_proposal_queue_depth'),
+            ('chain_2_proposal_queue_depth.json', 'chain_2_proposal_queue_depth'),
+            ('chain_1_proposal_queue_age_latency.json', 'chain_1_proposal_queue_age_latency'),
+            ('chain_2_proposal_queue_age_latency.json', 'chain_2_proposal_queue_age_latency'),
         ]
         
         for filename, key_name in time_series_files:
@@ -0,0 +1,229 @@
+//! Balance exhaustion workloads.
+//!
+//! The highest accounts of a balance exhaustion run are sinks: they receive every transfer but never
+//! send, while the remaining source accounts send but never receive. The balances of the sources
+//! run down over the run, so more and more sends and CATs fail for lack of funds. Afterwards the
+//! run is split into windows of blocks, and each window reports how many regular transactions and
+//! CATs succeeded, failed, timed out or were rejected. The report also checks that the status
+//! counts of the HIGs add up, which is where bookkeeping errors show under high failure ratios.
+
+use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use crate::simulation_results::SimulationResults;
+
+// ------------------------------------------------------------------------------------------------
+// Constants
+// ------------------------------------------------------------------------------------------------
+
+/// Name of the balance exhaustion report (inside a run's data directory)
+pub const BALANCE_EXHAUSTION_FILE: &str = "balance_exhaustion.json";
+
+// ------------------------------------------------------------------------------------------------
+// Configuration
+// ------------------------------------------------------------------------------------------------
+
+/// How a balance exhaustion run splits the accounts and its report splits the run
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct BalanceExhaustion {
+    /// Number of highest accounts that only receive
+    pub sink_accounts: usize,
+    /// Blocks per window of the report
+    #[serde(default = "default_window_blocks")]
+    pub window_blocks: u64,
+}
+
+fn default_window_blocks() -> u64 {
+    50
+}
+
+impl BalanceExhaustion {
+    /// Checks that both sources and sinks remain and that the run has at least one window
+    ///
+    /// # Arguments
+    /// * `num_accounts` - Number of preloaded accounts
+    /// * `sim_total_block_number` - Number of blocks of the run
+    pub fn validate(&self, num_accounts: usize, sim_total_block_number: u64) -> Result<(), String> {
+        if self.sink_accounts == 0 || self.sink_accounts >= num_accounts {
+            return Err(format!("The sink accounts must be between 1 and {} (one less than the accounts), got {}", num_accounts - 1, self.sink_accounts));
+        }
+        if self.window_blocks == 0 || self.window_blocks > sim_total_block_number {
+            return Err(format!("The report window must be between 1 and {} blocks, got {}", sim_total_block_number, self.window_blocks));
+        }
+        Ok(())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Report
+// ------------------------------------------------------------------------------------------------
+
+/// Transactions that reached a final status during a window, over both chains
+#[derive(Debug, Clone, Serialize)]
+pub struct ExhaustionWindow {
+    /// First and last CL block height of the window
+    pub start_height: u64,
+    pub end_height: u64,
+    pub regular_successes: u64,
+    pub regular_failures: u64,
+    pub cat_successes: u64,
+    pub cat_failures: u64,
+    /// CAT failures of the window that timed out or were rejected by the HS
+    pub cat_timed_out: u64,
+    pub cat_rejected: u64,
+    /// Share of the regular transactions and CATs finalized in the window that failed
+    pub regular_failure_ratio: f64,
+    pub cat_failure_ratio: f64,
+}
+
+/// Status counts of a chain at the end of the run compared with the transactions submitted to it
+#[derive(Debug, Clone, Serialize)]
+pub struct StatusAccounting {
+    /// Chain (numbered from 1, as in chain-1)
+    pub chain: usize,
+    /// Transactions submitted to the chain
+    pub submitted: u64,
+    pub pending: u64,
+    pub successes: u64,
+    pub failures: u64,
+    /// CAT failures counted as timed out or rejected
+    pub cat_timed_out: u64,
+    pub cat_rejected: u64,
+    /// Problems of the counts (empty if they add up)
+    pub problems: Vec<String>,
+}
+
+/// The failure mix of a balance exhaustion run, window by window
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BalanceExhaustionReport {
+    pub windows: Vec<ExhaustionWindow>,
+    pub accounting: Vec<StatusAccounting>,
+    /// Source accounts whose final balance lies below the mean amount on any chain
+    pub drained_sources: usize,
+    pub source_accounts: usize,
+}
+
+impl BalanceExhaustionReport {
+    /// Splits the recorded time series of a run into windows and checks the final status counts
+    ///
+    /// Every regular transaction is submitted to both chains, and with two chains every CAT spans
+    /// both, so each chain should account for all transactions sent.
+    ///
+    /// # Arguments
+    /// * `results` - The results of the run
+    /// * `window_blocks` - Blocks per window
+    pub fn from_results(results: &SimulationResults, window_blocks: u64) -> Self {
+        let source_accounts = results.num_accounts - results.sink_accounts;
+        let mean_amount = results.amount_distribution.mean();
+        let drained_sources = (1..=source_accounts)
+            .filter(|account| [&results.chain_1_final_state, &results.chain_2_final_state].iter()
+                .any(|state| (balance(state, *account) as f64) < mean_amount))
+            .count();
+        let Some(first_height) = results.chain_1_pending.first().map(|(height, _)| *height) else {
+            return Self { drained_sources, source_accounts, ..Self::default() };
+        };
+        let last_height = results.chain_1_pending.last().map(|(height, _)| *height).unwrap_or(first_height);
+
+        let mut windows = Vec::new();
+        // The counts at the height before a window are the baseline of its growth
+        let mut start_height = first_height;
+        while start_height <= last_height {
+            let end_height = (start_height + window_blocks - 1).min(last_height);
+            let growth = |series: [&Vec<(u64, u64)>; 2]| series.iter()
+                .map(|series| count_at(series, end_height).saturating_sub(count_at(series, start_height.saturating_sub(1))))
+                .sum::<u64>();
+            let regular_successes = growth([&results.chain_1_regular_success, &results.chain_2_regular_success]);
+            let regular_failures = growth([&results.chain_1_regular_failure, &results.chain_2_regular_failure]);
+            let cat_successes = growth([&results.chain_1_cat_success, &results.chain_2_cat_success]);
+            let cat_failures = growth([&results.chain_1_cat_failure, &results.chain_2_cat_failure]);
+            windows.push(ExhaustionWindow {
+                start_height,
+                end_height,
+                regular_successes,
+                regular_failures,
+                cat_successes,
+                cat_failures,
+                cat_timed_out: growth([&results.chain_1_cat_timed_out, &results.chain_2_cat_timed_out]),
+                cat_rejected: growth([&results.chain_1_cat_rejected, &results.chain_2_cat_rejected]),
+                regular_failure_ratio: ratio(regular_failures, regular_successes + regular_failures),
+                cat_failure_ratio: ratio(cat_failures, cat_successes + cat_failures),
+            });
+            start_height = end_height + 1;
+        }
+
+        let submitted = results.regular_transactions + results.cat_transactions;
+        let accounting = [
+            (1, [&results.chain_1_pending, &results.chain_1_success, &results.chain_1_failure, &results.chain_1_cat_failure, &results.chain_1_cat_timed_out, &results.chain_1_cat_rejected]),
+            (2, [&results.chain_2_pending, &results.chain_2_success, &results.chain_2_failure, &results.chain_2_cat_failure, &results.chain_2_cat_timed_out, &results.chain_2_cat_rejected]),
+        ].into_iter().map(|(chain, [pending, successes, failures, cat_failures, cat_timed_out, cat_rejected])| {
+            StatusAccounting::new(chain, submitted,
+                [pending, successes, failures, cat_failures, cat_timed_out, cat_rejected].map(|series| count_at(series, last_height)))
+        }).collect();
+
+        Self { windows, accounting, drained_sources, source_accounts }
+    }
+
+    /// Whether the status counts of every chain add up
+    pub fn is_consistent(&self) -> bool {
+        self.accounting.iter().all(|accounting| accounting.problems.is_empty())
+    }
+
+    /// One line per window and per accounting problem for the simulation log
+    pub fn summary_lines(&self) -> Vec<String> {
+        let mut lines: Vec<String> = self.windows.iter().map(|window| format!(
+            "Heights {}-{}: regular {} ok / {} failed ({:.1}%), CATs {} ok / {} failed ({:.1}%, {} timed out, {} rejected)",
+            window.start_height, window.end_height,
+            window.regular_successes, window.regular_failures, window.regular_failure_ratio * 100.0,
+            window.cat_successes, window.cat_failures, window.cat_failure_ratio * 100.0,
+            window.cat_timed_out, window.cat_rejected,
+        )).collect();
+        if lines.is_empty() {
+            lines.push("No blocks were recorded".to_string());
+        }
+        lines.push(format!("Drained source accounts: {}/{}", self.drained_sources, self.source_accounts));
+        for accounting in &self.accounting {
+            for problem in &accounting.problems {
+                lines.push(format!("⚠️ chain-{}: {}", accounting.chain, problem));
+            }
+        }
+        lines
+    }
+}
+
+impl StatusAccounting {
+    /// Checks the final status counts of a chain
+    ///
+    /// # Arguments
+    /// * `chain` - Chain of the counts (numbered from 1)
+    /// * `submitted` - Transactions submitted to the chain
+    /// * `counts` - Pending, successful, failed, failed CAT, timed out CAT and rejected CAT counts
+    fn new(chain: usize, submitted: u64, [pending, successes, failures, cat_failures, cat_timed_out, cat_rejected]: [u64; 6]) -> Self {
+        let mut problems = Vec::new();
+        // Transactions still in the CL mempool have no status yet, so the counts may fall short
+        if pending + successes + failures > submitted {
+            problems.push(format!("{} pending, {} successful and {} failed transactions exceed the {} submitted", pending, successes, failures, submitted));
+        }
+        if cat_timed_out + cat_rejected > cat_failures {
+            problems.push(format!("{} timed out and {} rejected CATs exceed the {} failed CATs", cat_timed_out, cat_rejected, cat_failures));
+        }
+        Self { chain, submitted, pending, successes, failures, cat_timed_out, cat_rejected, problems }
+    }
+}
+
+/// The count of a time series at the last recorded height at or before `height`
+fn count_at(series: &[(u64, u64)], height: u64) -> u64 {
+    series.iter().take_while(|(recorded, _)| *recorded <= height).last().map(|(_, count)| *count).unwrap_or(0)
+}
+
+/// The balance of an account in a chain state (0 for accounts the chain does not know)
+fn balance(state: &HashMap<String, i64>, account: usize) -> i64 {
+    state.get(&account.to_string()).copied().unwrap_or(0)
+}
+
+/// `part / total`, or 0 for an empty total
+fn ratio(part: u64, total: u64) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        part as f64 / total as f64
+    }
+}
@@ -7,8 +7,58 @@ use simulator::{
 // ------------------------------------------------------------------------------------------------
 
 /// Main function that orchestrates the simulation setup and execution
-#[tokio::main]
-async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///
+/// Without arguments the interactive interface starts. `--validate-configs` checks the config files
+/// of all registered simulations against their declared schemas, `--config-docs` prints the
+/// declared parameters of all simulations as Markdown, `--experiment <file>` runs the
+/// experiment described in a YAML file, `--dry-run <scenario>` validates the config of a
+/// scenario and prints what it would simulate and how long it would take, without running it, and
+/// `--list-runs` and `--show-run <id or run directory>` query the index of completed runs.
+///
+/// `--worker-threads <n>` and `--pin <component>=<cores>` (repeatable) can be combined with any
+/// of these and configure the runtimes the simulations run on (see `simulator::runtime`).
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args: Vec<String> = std::env::args().collect();
+    let (runtime_config, remaining) = simulator::runtime::RuntimeConfig::from_args(&args[1..])?;
+    let runtime = runtime_config.build()?;
+    let args: Vec<String> = args.into_iter().take(1).chain(remaining).collect();
+    runtime.block_on(run(args))
+}
+
+/// Runs the simulator with the command line arguments left after the runtime options
+async fn run(args: Vec<String>) -> Result<(), Box<dyn std::error::Error>> {
+    match args.get(1).map(String::as_str) {
+        Some("--validate-configs") => return validate_configs().await,
+        Some("--config-docs") => {
+            let registry = simulator::simulation_registry::get_registry().await;
+            println!("{}", registry.lock().await.render_config_docs());
+            return Ok(());
+        }
+        Some("--experiment") => {
+            let path = args.get(2).ok_or("--experiment needs the path of an experiment file")?;
+            simulator::experiments::run_experiment(path).await?;
+            return Ok(());
+        }
+        Some("--dry-run") => {
+            let scenario = args.get(2).ok_or("--dry-run needs the name of a scenario")?;
+            let simulation_type = simulator::experiments::scenario_type(scenario)
+                .ok_or_else(|| format!("Unknown scenario '{}' (expected one of {})", scenario, simulator::experiments::scenario_names().join(", ")))?;
+            simulator::dry_run::print_dry_run(&simulation_type).await?;
+            return Ok(());
+        }
+        Some("--list-runs") => return list_runs(&args[2..]),
+        Some("--show-run") => {
+            let run = args.get(2).ok_or("--show-run needs the id or the directory of a run")?;
+            let index = simulator::run_index::load_index()?;
+            let entry = simulator::run_index::find_run(&index, run)
+                .ok_or_else(|| format!("No run '{}' in {}", run, simulator::run_index::RUN_INDEX_FILE))?;
+            println!("{}", simulator::run_index::format_run_details(entry, &index));
+            return Ok(());
+        }
+        Some(other) => return Err(format!("Unknown argument '{}' (expected --validate-configs, --config-docs, --experiment <file>, --dry-run <scenario>, --list-runs, --show-run <run>, --worker-threads <n> or --pin <component>=<cores>)", other).into()),
+        None => {}
+    }
+
     let mut interface = SimulatorInterface::new();
     
     if let Err(e) = interface.run_simple_simulation_async().await {
@@ -16,4 +66,63 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     }
     
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Checks the config files of all registered simulations and fails if any of them is invalid
+async fn validate_configs() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = simulator::simulation_registry::get_registry().await;
+    let mut invalid = 0;
+    for (name, report) in registry.lock().await.validate_all_configs() {
+        match report {
+            Ok(report) => {
+                println!("{}: {}", name, if report.is_valid() { "valid" } else { "invalid" });
+                for error in &report.errors {
+                    println!("  error: {}", error);
+                }
+                for warning in &report.warnings {
+                    println!("  warning: {}", warning);
+                }
+                if !report.is_valid() {
+                    invalid += 1;
+                }
+            }
+            Err(e) => {
+                println!("{}: {}", name, e);
+                invalid += 1;
+            }
+        }
+    }
+
+    if invalid > 0 {
+        return Err(format!("{} invalid configuration(s)", invalid).into());
+    }
+    Ok(())
+}
+
+/// Prints the runs of the index matching the filter options, the most recent first
+///
+/// Options: `--scenario <name>` (part of the results directory), `--config-hash <prefix>`,
+/// `--limit <n>` and `--all` (also runs whose directory was overwritten by a later run).
+fn list_runs(options: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    let mut filter = simulator::run_index::RunFilter { limit: simulator::run_index::DEFAULT_LIST_LIMIT, ..Default::default() };
+    let mut options = options.iter();
+    while let Some(option) = options.next() {
+        let mut value = || options.next().cloned().ok_or_else(|| format!("{} needs a value", option));
+        match option.as_str() {
+            "--scenario" => filter.scenario = Some(value()?),
+            "--config-hash" => filter.config_hash = Some(value()?),
+            "--limit" => filter.limit = value()?.parse().map_err(|e| format!("Invalid --limit: {}", e))?,
+            "--all" => filter.include_overwritten = true,
+            other => return Err(format!("Unknown option '{}' (expected --scenario <name>, --config-hash <prefix>, --limit <n> or --all)", other).into()),
+        }
+    }
+
+    let index = simulator::run_index::load_index()?;
+    let runs = simulator::run_index::list_runs(&index, &filter);
+    if runs.is_empty() {
+        println!("No matching runs in {}", simulator::run_index::RUN_INDEX_FILE);
+    } else {
+        println!("{}", simulator::run_index::format_run_table(&runs));
+    }
+    Ok(())
+}
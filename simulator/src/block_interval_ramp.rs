@@ -0,0 +1,302 @@
+//! Changes of the CL block interval during a run.
+//!
+//! A schedule changes the block interval of the CL once the run reaches given blocks, either
+//! listed explicitly or generated as a geometric ramp (e.g. halve the interval every 200 blocks).
+//! The simulation loop applies the due changes with `set_block_interval` while the nodes keep
+//! running. Afterwards the run is split into stages of constant block interval, and each stage
+//! reports how the pending counts, the HIG proposal queues and the HIG processing time developed,
+//! to show at which interval the HIGs and the HS stop keeping up.
+
+use std::sync::Arc;
+use std::time::Duration;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+use hyperplane::{
+    confirmation_layer::{node::ConfirmationLayerNode, ConfirmationLayer},
+    hyper_ig::SubblockTiming,
+    utils::logging,
+};
+use crate::simulation_results::SimulationResults;
+
+// ------------------------------------------------------------------------------------------------
+// Constants
+// ------------------------------------------------------------------------------------------------
+
+/// Name of the ramp report (inside a run's data directory)
+pub const BLOCK_INTERVAL_RAMP_FILE: &str = "block_interval_ramp.json";
+
+/// Smallest block interval a ramp may reach, in seconds
+const MIN_RAMP_BLOCK_INTERVAL: f64 = 0.001;
+
+// ------------------------------------------------------------------------------------------------
+// Schedule
+// ------------------------------------------------------------------------------------------------
+
+/// A change of the CL block interval once the simulation reaches a block
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct BlockIntervalChange {
+    /// Block at which the change is applied, counted from the start of the transaction submission
+    pub at_block: u64,
+    /// New block interval in seconds
+    pub block_interval: f64,
+}
+
+/// A geometric ramp of the block interval, or an explicit list of changes
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct BlockIntervalRamp {
+    /// Factor applied to the block interval at every step (0.5 = halve)
+    #[serde(default = "default_ramp_factor")]
+    pub factor: f64,
+    /// Blocks between two steps of the ramp
+    #[serde(default = "default_ramp_every_blocks")]
+    pub every_blocks: u64,
+    /// Block interval in seconds at which the ramp stops
+    #[serde(default)]
+    pub limit_block_interval: Option<f64>,
+    /// Explicit changes, used instead of the geometric ramp if not empty
+    #[serde(default)]
+    pub changes: Vec<BlockIntervalChange>,
+}
+
+fn default_ramp_factor() -> f64 {
+    0.5
+}
+
+fn default_ramp_every_blocks() -> u64 {
+    200
+}
+
+impl BlockIntervalRamp {
+    /// Checks that the ramp produces positive block intervals at blocks within the run
+    ///
+    /// # Arguments
+    /// * `sim_total_block_number` - Number of blocks of the run
+    pub fn validate(&self, sim_total_block_number: u64) -> Result<(), String> {
+        if self.changes.is_empty() {
+            if self.factor <= 0.0 || self.factor == 1.0 {
+                return Err(format!("The ramp factor must be positive and not 1, got {}", self.factor));
+            }
+            if self.every_blocks == 0 {
+                return Err("The ramp must step at least every block (every_blocks >= 1)".to_string());
+            }
+            if let Some(limit) = self.limit_block_interval {
+                if limit < MIN_RAMP_BLOCK_INTERVAL {
+                    return Err(format!("The ramp limit must be at least {} seconds, got {}", MIN_RAMP_BLOCK_INTERVAL, limit));
+                }
+            }
+        }
+        for change in &self.changes {
+            if change.block_interval < MIN_RAMP_BLOCK_INTERVAL {
+                return Err(format!("Block interval change at block {} must be at least {} seconds, got {}", change.at_block, MIN_RAMP_BLOCK_INTERVAL, change.block_interval));
+            }
+            if change.at_block >= sim_total_block_number {
+                return Err(format!("Block interval change at block {} is outside the run of {} blocks", change.at_block, sim_total_block_number));
+            }
+        }
+        Ok(())
+    }
+
+    /// The changes of the block interval during a run
+    ///
+    /// The geometric ramp multiplies the interval by `factor` every `every_blocks` blocks until it
+    /// reaches `limit_block_interval` (or the smallest supported interval) or the run ends.
+    ///
+    /// # Arguments
+    /// * `block_interval` - Block interval at the start of the run, in seconds
+    /// * `sim_total_block_number` - Number of blocks of the run
+    pub fn schedule(&self, block_interval: f64, sim_total_block_number: u64) -> Vec<BlockIntervalChange> {
+        if !self.changes.is_empty() {
+            let mut changes = self.changes.clone();
+            changes.sort_by_key(|change| change.at_block);
+            return changes;
+        }
+
+        let (lower, upper) = match self.limit_block_interval {
+            Some(limit) if self.factor < 1.0 => (limit, f64::INFINITY),
+            Some(limit) => (MIN_RAMP_BLOCK_INTERVAL, limit),
+            None => (MIN_RAMP_BLOCK_INTERVAL, f64::INFINITY),
+        };
+        let mut changes = Vec::new();
+        let mut interval = block_interval;
+        let mut at_block = self.every_blocks;
+        while at_block < sim_total_block_number {
+            let next = (interval * self.factor).clamp(lower, upper);
+            // Stop once the limit is reached (or lies behind the current interval)
+            if (next - interval) * (self.factor - 1.0) <= 0.0 {
+                break;
+            }
+            interval = next;
+            changes.push(BlockIntervalChange { at_block, block_interval: interval });
+            at_block += self.every_blocks;
+        }
+        changes
+    }
+}
+
+/// A change of the block interval applied to the CL during the run
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct AppliedBlockIntervalChange {
+    /// Block of the run at which the change was applied, counted from the start of the transaction submission
+    pub block: u64,
+    /// CL block height at which the change was applied
+    pub height: u64,
+    /// New block interval in seconds
+    pub block_interval: f64,
+}
+
+/// Configured block interval changes that have not been applied yet
+pub struct BlockIntervalSchedule {
+    /// Changes ordered by their block
+    changes: Vec<BlockIntervalChange>,
+    /// Index of the next change to apply
+    next: usize,
+}
+
+impl BlockIntervalSchedule {
+    /// Creates the schedule, keeping the config order of changes at the same block
+    pub fn new(changes: &[BlockIntervalChange]) -> Self {
+        let mut changes = changes.to_vec();
+        changes.sort_by_key(|change| change.at_block);
+        Self { changes, next: 0 }
+    }
+
+    /// Applies all changes due at or before `blocks_completed` to the CL
+    ///
+    /// # Arguments
+    /// * `blocks_completed` - Blocks since the start of the transaction submission
+    /// * `height` - Current CL block height
+    /// * `cl_node` - The CL node
+    ///
+    /// # Returns
+    ///
+    /// The changes that were applied
+    pub async fn apply_due(&mut self, blocks_completed: u64, height: u64, cl_node: &Arc<Mutex<ConfirmationLayerNode>>) -> Result<Vec<AppliedBlockIntervalChange>, String> {
+        let mut applied = Vec::new();
+        while let Some(change) = self.changes.get(self.next).filter(|change| change.at_block <= blocks_completed) {
+            cl_node.lock().await.set_block_interval(Duration::from_secs_f64(change.block_interval)).await.map_err(|e| e.to_string())?;
+            logging::log("SIMULATOR", &format!("Changed the CL block interval to {}s at block {}", change.block_interval, blocks_completed));
+            applied.push(AppliedBlockIntervalChange { block: blocks_completed, height, block_interval: change.block_interval });
+            self.next += 1;
+        }
+        Ok(applied)
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Report
+// ------------------------------------------------------------------------------------------------
+
+/// How the HIGs and the HS kept up during a stage of constant block interval
+#[derive(Debug, Clone, Serialize)]
+pub struct RampStage {
+    /// Block interval of the stage in seconds
+    pub block_interval: f64,
+    /// First and last CL block height of the stage
+    pub start_height: u64,
+    pub end_height: u64,
+    /// Change of the pending transaction counts over the stage, per chain
+    pub chain_1_pending_growth: i64,
+    pub chain_2_pending_growth: i64,
+    /// Change of the pending CAT counts over the stage, per chain
+    pub chain_1_cat_pending_growth: i64,
+    pub chain_2_cat_pending_growth: i64,
+    /// CATs that became successful during the stage, over both chains
+    pub cat_successes: u64,
+    /// Largest depth of a HIG's pending-proposal queue during the stage
+    pub max_proposal_queue_depth: u64,
+    /// Mean time the HIGs spent per subblock during the stage
+    pub mean_hig_processing_ms: f64,
+    /// Mean HIG time per subblock as a share of the block interval (1 = saturated)
+    pub hig_load: f64,
+    /// Whether the pending transactions grew by more than one block of transactions over the stage
+    pub falling_behind: bool,
+}
+
+/// The stages of constant block interval of a run
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BlockIntervalRampReport {
+    pub stages: Vec<RampStage>,
+}
+
+impl BlockIntervalRampReport {
+    /// Splits the recorded time series of a run at the applied block interval changes
+    pub fn from_results(results: &SimulationResults) -> Self {
+        let Some(first_height) = results.chain_1_pending.first().map(|(height, _)| *height) else { return Self::default() };
+        let last_height = results.chain_1_pending.last().map(|(height, _)| *height).unwrap_or(first_height);
+
+        let mut boundaries = vec![(first_height, results.block_interval)];
+        boundaries.extend(results.block_interval_changes.iter()
+            .filter(|change| change.height > first_height)
+            .map(|change| (change.height, change.block_interval)));
+
+        let stages = boundaries.iter().enumerate().map(|(index, &(start_height, block_interval))| {
+            let end_height = boundaries.get(index + 1).map(|(height, _)| height - 1).unwrap_or(last_height).max(start_height);
+            let growth = |series: &Vec<(u64, u64)>| count_at(series, end_height) as i64 - count_at(series, start_height) as i64;
+            let chain_1_pending_growth = growth(&results.chain_1_pending);
+            let chain_2_pending_growth = growth(&results.chain_2_pending);
+            let cat_successes = (growth(&results.chain_1_cat_success) + growth(&results.chain_2_cat_success)).max(0) as u64;
+            let max_proposal_queue_depth = results.chain_1_proposal_queue_depth.iter().chain(&results.chain_2_proposal_queue_depth)
+                .filter(|(height, _)| (start_height..=end_height).contains(height))
+                .map(|(_, depth)| *depth)
+                .max()
+                .unwrap_or(0);
+            let mean_hig_processing_ms = mean_processing_ms_between(&[&results.chain_1_subblock_timings, &results.chain_2_subblock_timings], start_height, end_height);
+            RampStage {
+                block_interval,
+                start_height,
+                end_height,
+                chain_1_pending_growth,
+                chain_2_pending_growth,
+                chain_1_cat_pending_growth: growth(&results.chain_1_cat_pending),
+                chain_2_cat_pending_growth: growth(&results.chain_2_cat_pending),
+                cat_successes,
+                max_proposal_queue_depth,
+                mean_hig_processing_ms,
+                hig_load: mean_hig_processing_ms / (block_interval * 1000.0),
+                falling_behind: chain_1_pending_growth.max(chain_2_pending_growth) > results.target_tpb as i64,
+            }
+        }).collect();
+
+        Self { stages }
+    }
+
+    /// The block interval of the first stage that fell behind
+    pub fn first_falling_behind(&self) -> Option<f64> {
+        self.stages.iter().find(|stage| stage.falling_behind).map(|stage| stage.block_interval)
+    }
+
+    /// One line per stage for the simulation log
+    pub fn summary_lines(&self) -> Vec<String> {
+        let mut lines: Vec<String> = self.stages.iter().map(|stage| format!(
+            "{}s (heights {}-{}): pending growth {}/{}, CAT pending growth {}/{}, {} CAT successes, max proposal queue {}, HIG load {:.1}%{}",
+            stage.block_interval, stage.start_height, stage.end_height,
+            stage.chain_1_pending_growth, stage.chain_2_pending_growth,
+            stage.chain_1_cat_pending_growth, stage.chain_2_cat_pending_growth,
+            stage.cat_successes, stage.max_proposal_queue_depth, stage.hig_load * 100.0,
+            if stage.falling_behind { " ⚠️ falling behind" } else { "" },
+        )).collect();
+        if lines.is_empty() {
+            lines.push("No blocks were recorded".to_string());
+        }
+        lines
+    }
+}
+
+/// The count of a time series at the last recorded height at or before `height`
+fn count_at(series: &[(u64, u64)], height: u64) -> u64 {
+    series.iter().take_while(|(recorded, _)| *recorded <= height).last().map(|(_, count)| *count).unwrap_or(0)
+}
+
+/// Mean time per subblock of the HIGs over the heights `start_height..=end_height`
+fn mean_processing_ms_between(timings: &[&Vec<(u64, SubblockTiming)>], start_height: u64, end_height: u64) -> f64 {
+    let durations: Vec<f64> = timings.iter()
+        .flat_map(|chain| chain.iter())
+        .filter(|(height, _)| (start_height..=end_height).contains(height))
+        .map(|(_, timing)| timing.total().as_secs_f64() * 1000.0)
+        .collect();
+    if durations.is_empty() {
+        0.0
+    } else {
+        durations.iter().sum::<f64>() / durations.len() as f64
+    }
+}
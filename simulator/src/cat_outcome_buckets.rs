@@ -0,0 +1,276 @@
+//! CAT outcomes by fanout and chain delay.
+//!
+//! A single CAT success rate hides why CATs fail: CATs spanning more chains wait for more
+//! proposals, and a CAT whose slowest chain took long to propose is more likely to time out.
+//! This report takes the outcome of every CAT over its chains and groups the CATs by their fanout
+//! and by the longest realized delay of their proposals, in blocks, so that the success and
+//! timeout rates of each group can be compared.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use serde::Serialize;
+use hyperplane::{
+    types::{CATId, ChainId, Transaction, TransactionStatus},
+    hyper_ig::node::HyperIGNode,
+    hyper_ig::HyperIG,
+};
+use crate::workload::RecordedSubmission;
+
+// ------------------------------------------------------------------------------------------------
+// Constants
+// ------------------------------------------------------------------------------------------------
+
+/// Name of the CAT outcome report (inside a run's data directory)
+pub const CAT_OUTCOME_BUCKETS_FILE: &str = "cat_outcome_buckets.json";
+
+/// Upper edges of the chain delay buckets in blocks (the last bucket is open-ended)
+pub const DELAY_BUCKET_EDGES_BLOCKS: [f64; 5] = [0.5, 1.0, 2.0, 5.0, 10.0];
+
+// ------------------------------------------------------------------------------------------------
+// Data Structures
+// ------------------------------------------------------------------------------------------------
+
+/// Outcome of a CAT over all of its chains
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CatOutcome {
+    /// Succeeded on every chain
+    Success,
+    /// Failed on a chain, e.g. for lack of funds or by a Failure decision of the HS
+    Failure,
+    /// Timed out on a chain
+    TimedOut,
+    /// Rejected on a chain because it depended on a pending transaction
+    Rejected,
+    /// Not yet finalized on a chain at the end of the run
+    Pending,
+}
+
+/// Outcome of one CAT together with its fanout and the delay of its slowest proposal
+#[derive(Debug, Clone)]
+pub struct CatOutcomeRecord {
+    pub cat_id: CATId,
+    /// Number of constituent chains of the CAT
+    pub fanout: usize,
+    /// Constituent chains of the CAT, in the order of their registration
+    pub chains: Vec<ChainId>,
+    pub outcome: CatOutcome,
+    /// Longest time a proposal for the CAT spent in a HIG's queue (None if no proposal was sent)
+    pub max_chain_delay: Option<Duration>,
+}
+
+/// Outcomes of the CATs of one fanout and chain delay bucket
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CatOutcomeBucket {
+    pub fanout: usize,
+    /// Range of the longest chain delay in blocks, e.g. `1-2`
+    pub delay_bucket: String,
+    pub count: u64,
+    pub success: u64,
+    pub failure: u64,
+    pub timed_out: u64,
+    pub rejected: u64,
+    pub pending: u64,
+    /// Share of the finalized CATs of the bucket that succeeded and that timed out
+    pub success_rate: f64,
+    pub timeout_rate: f64,
+}
+
+/// Outcomes of the CATs of a run
+#[derive(Debug, Clone, Default)]
+pub struct CatOutcomeReport {
+    pub cats: Vec<CatOutcomeRecord>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl CatOutcome {
+    /// Combines the statuses of a CAT on its chains: a timeout on any chain outweighs a
+    /// rejection, which outweighs other failures; pending chains leave the CAT pending unless it
+    /// failed elsewhere, and it only succeeded if it succeeded on every chain.
+    ///
+    /// # Arguments
+    /// * `statuses` - The status of the CAT on each chain (None if the chain has no status for it)
+    pub fn combine(statuses: &[Option<TransactionStatus>]) -> Self {
+        let any = |wanted: &[TransactionStatus]| statuses.iter().flatten().any(|status| wanted.contains(status));
+        if any(&[TransactionStatus::TimedOut]) {
+            CatOutcome::TimedOut
+        } else if any(&[TransactionStatus::Rejected]) {
+            CatOutcome::Rejected
+        } else if any(&[TransactionStatus::Failure, TransactionStatus::Expired]) {
+            CatOutcome::Failure
+        } else if statuses.is_empty() || statuses.iter().any(|status| *status != Some(TransactionStatus::Success)) {
+            CatOutcome::Pending
+        } else {
+            CatOutcome::Success
+        }
+    }
+}
+
+impl CatOutcomeBucket {
+    /// Counts a CAT in the bucket
+    fn add(&mut self, outcome: CatOutcome) {
+        self.count += 1;
+        match outcome {
+            CatOutcome::Success => self.success += 1,
+            CatOutcome::Failure => self.failure += 1,
+            CatOutcome::TimedOut => self.timed_out += 1,
+            CatOutcome::Rejected => self.rejected += 1,
+            CatOutcome::Pending => self.pending += 1,
+        }
+        let finalized = self.count - self.pending;
+        self.success_rate = ratio(self.success, finalized);
+        self.timeout_rate = ratio(self.timed_out, finalized);
+    }
+}
+
+impl CatOutcomeReport {
+    /// Groups the CATs by fanout and chain delay bucket, in ascending order of both
+    ///
+    /// # Arguments
+    /// * `block_interval` - The block interval in seconds, to express the delays in blocks
+    pub fn buckets(&self, block_interval: f64) -> Vec<CatOutcomeBucket> {
+        let mut buckets: BTreeMap<(usize, usize), CatOutcomeBucket> = BTreeMap::new();
+        for cat in &self.cats {
+            let index = delay_bucket_index(cat.max_chain_delay, block_interval);
+            buckets.entry((cat.fanout, index))
+                .or_insert_with(|| CatOutcomeBucket { fanout: cat.fanout, delay_bucket: delay_bucket_label(index), ..Default::default() })
+                .add(cat.outcome);
+        }
+        buckets.into_values().collect()
+    }
+
+    /// Returns the buckets and the outcome of every CAT as JSON (delays in blocks)
+    ///
+    /// # Arguments
+    /// * `block_interval` - The block interval in seconds
+    pub fn to_json(&self, block_interval: f64) -> serde_json::Value {
+        serde_json::json!({
+            "delay_bucket_edges_blocks": DELAY_BUCKET_EDGES_BLOCKS,
+            "buckets": self.buckets(block_interval),
+            "cats": self.cats.iter().map(|cat| serde_json::json!({
+                "cat_id": cat.cat_id.0.0,
+                "fanout": cat.fanout,
+                "outcome": cat.outcome,
+                "max_chain_delay_blocks": cat.max_chain_delay.map(|delay| to_blocks(delay, block_interval))
+            })).collect::<Vec<_>>()
+        })
+    }
+
+    /// Human-readable summary of the report, one line per bucket
+    ///
+    /// # Arguments
+    /// * `block_interval` - The block interval in seconds
+    pub fn summary_lines(&self, block_interval: f64) -> Vec<String> {
+        let buckets = self.buckets(block_interval);
+        if buckets.is_empty() {
+            return vec!["No CATs were submitted".to_string()];
+        }
+        buckets.iter().map(|bucket| format!(
+            "fanout {}, max chain delay {} blocks: {} CATs, {:.1}% success, {:.1}% timed out ({} rejected, {} failed, {} pending)",
+            bucket.fanout, bucket.delay_bucket, bucket.count, bucket.success_rate * 100.0, bucket.timeout_rate * 100.0,
+            bucket.rejected, bucket.failure, bucket.pending,
+        )).collect()
+    }
+}
+
+/// Index of the delay bucket of a CAT (after the delay buckets for CATs without proposals)
+fn delay_bucket_index(delay: Option<Duration>, block_interval: f64) -> usize {
+    match delay {
+        Some(delay) => {
+            let blocks = to_blocks(delay, block_interval);
+            DELAY_BUCKET_EDGES_BLOCKS.iter().position(|edge| blocks < *edge).unwrap_or(DELAY_BUCKET_EDGES_BLOCKS.len())
+        }
+        None => DELAY_BUCKET_EDGES_BLOCKS.len() + 1,
+    }
+}
+
+/// Label of a delay bucket, e.g. `<0.5`, `1-2` or `>=10`
+fn delay_bucket_label(index: usize) -> String {
+    let edges = DELAY_BUCKET_EDGES_BLOCKS;
+    match index {
+        0 => format!("<{}", edges[0]),
+        index if index < edges.len() => format!("{}-{}", edges[index - 1], edges[index]),
+        index if index == edges.len() => format!(">={}", edges[edges.len() - 1]),
+        _ => "no proposal".to_string(),
+    }
+}
+
+/// A delay in blocks (0 without a block interval)
+fn to_blocks(delay: Duration, block_interval: f64) -> f64 {
+    if block_interval > 0.0 { delay.as_secs_f64() / block_interval } else { 0.0 }
+}
+
+/// `part / total`, or 0 for an empty total
+fn ratio(part: u64, total: u64) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        part as f64 / total as f64
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Collection
+// ------------------------------------------------------------------------------------------------
+
+/// Collects the outcome, fanout and longest realized chain delay of every CAT of a run
+///
+/// The delay of a chain is the time the CAT's proposal spent in the queue of the chain's HIG;
+/// chains that did not send a proposal for the CAT do not count towards it.
+///
+/// # Arguments
+/// * `hig_nodes` - The HIG nodes, in the same order as `chains`
+/// * `chains` - The registered chains
+/// * `workload` - The submissions of the run
+pub async fn collect_cat_outcomes(
+    hig_nodes: &[Arc<Mutex<HyperIGNode>>],
+    chains: &[ChainId],
+    workload: &[RecordedSubmission],
+) -> CatOutcomeReport {
+    let mut delays: Vec<HashMap<CATId, Duration>> = Vec::new();
+    for hig_node in hig_nodes {
+        delays.push(hig_node.lock().await.get_realized_hs_message_delays_by_cat().await);
+    }
+
+    // Group the sub-transactions by CAT (the parts of a spread CAT share its id)
+    let mut cats: BTreeMap<String, Vec<&Transaction>> = BTreeMap::new();
+    for submission in workload.iter().filter(|submission| submission.is_cat) {
+        for cl_tx in &submission.cl_transactions {
+            cats.entry(cl_tx.id.0.clone()).or_default().extend(cl_tx.transactions.iter());
+        }
+    }
+
+    let mut report = CatOutcomeReport::default();
+    for transactions in cats.into_values() {
+        let Some(first) = transactions.first() else { continue };
+        let cat_id = CATId(first.cl_id.clone());
+
+        let mut statuses = Vec::new();
+        let mut max_chain_delay: Option<Duration> = None;
+        for tx in &transactions {
+            let Some(index) = chains.iter().position(|chain_id| *chain_id == tx.chain_id).filter(|index| *index < hig_nodes.len()) else {
+                statuses.push(None);
+                continue;
+            };
+            statuses.push(hig_nodes[index].lock().await.get_transaction_status(tx.id.clone()).await.ok());
+            if let Some(delay) = delays[index].get(&cat_id) {
+                max_chain_delay = Some(max_chain_delay.map_or(*delay, |max| max.max(*delay)));
+            }
+        }
+
+        let mut cat_chains: Vec<ChainId> = Vec::new();
+        for tx in &transactions {
+            if !cat_chains.contains(&tx.chain_id) {
+                cat_chains.push(tx.chain_id.clone());
+            }
+        }
+        cat_chains.sort_by_key(|chain_id| chains.iter().position(|registered| registered == chain_id).unwrap_or(usize::MAX));
+        report.cats.push(CatOutcomeRecord { cat_id, fanout: cat_chains.len(), chains: cat_chains, outcome: CatOutcome::combine(&statuses), max_chain_delay });
+    }
+    report
+}
@@ -0,0 +1,151 @@
+//! CAT connections and success rates between pairs of chains.
+//!
+//! The CAT outcome buckets show how the outcome of a CAT depends on its fanout, but not which
+//! chains it connected. With asymmetric chain delays, the CATs between a fast and a slow chain can
+//! fail far more often than those between two fast chains. This report counts, for every pair of
+//! registered chains, the CATs that spanned both chains and the share of them that succeeded, as
+//! an N×N matrix. The diagonal holds the CATs of each chain.
+
+use std::collections::HashMap;
+use serde::Serialize;
+use hyperplane::types::ChainId;
+use crate::cat_outcome_buckets::{CatOutcome, CatOutcomeReport};
+
+// ------------------------------------------------------------------------------------------------
+// Constants
+// ------------------------------------------------------------------------------------------------
+
+/// Name of the chain pair report (inside a run's data directory)
+pub const CHAIN_PAIR_MATRIX_FILE: &str = "chain_pair_matrix.json";
+
+/// Number of chain pairs with the lowest success rate printed with the results summary
+pub const CHAIN_PAIR_SUMMARY_LIMIT: usize = 10;
+
+// ------------------------------------------------------------------------------------------------
+// Data Structures
+// ------------------------------------------------------------------------------------------------
+
+/// Outcomes of the CATs that spanned one pair of chains (or one chain, on the diagonal)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct ChainPairCell {
+    pub cats: u64,
+    pub success: u64,
+    pub timed_out: u64,
+    pub pending: u64,
+}
+
+/// CAT connections between every pair of chains of a run
+#[derive(Debug, Clone, Default)]
+pub struct ChainPairMatrix {
+    /// The registered chains, in the order of the rows and columns
+    pub chains: Vec<ChainId>,
+    /// Configured delay of each chain in blocks, in the same order
+    pub chain_delays: Vec<f64>,
+    /// Cell `[i][j]` counts the CATs that spanned chains `i` and `j` (symmetric)
+    pub cells: Vec<Vec<ChainPairCell>>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl ChainPairCell {
+    /// Counts a CAT in the cell
+    fn add(&mut self, outcome: CatOutcome) {
+        self.cats += 1;
+        match outcome {
+            CatOutcome::Success => self.success += 1,
+            CatOutcome::TimedOut => self.timed_out += 1,
+            CatOutcome::Pending => self.pending += 1,
+            CatOutcome::Failure | CatOutcome::Rejected => {}
+        }
+    }
+
+    /// Share of the finalized CATs of the cell that succeeded (None without finalized CATs)
+    pub fn success_rate(&self) -> Option<f64> {
+        let finalized = self.cats - self.pending;
+        if finalized == 0 {
+            None
+        } else {
+            Some(self.success as f64 / finalized as f64)
+        }
+    }
+}
+
+impl ChainPairMatrix {
+    /// Counts the CATs of a run by the pairs of chains they spanned
+    ///
+    /// CATs on chains that are not registered are left out.
+    ///
+    /// # Arguments
+    /// * `outcomes` - The outcomes of the CATs of the run
+    /// * `chains` - The registered chains
+    /// * `chain_delays` - The configured delay of each chain in blocks, in the order of `chains`
+    pub fn from_outcomes(outcomes: &CatOutcomeReport, chains: &[ChainId], chain_delays: &[f64]) -> Self {
+        let index: HashMap<&ChainId, usize> = chains.iter().enumerate().map(|(i, chain_id)| (chain_id, i)).collect();
+        let mut cells = vec![vec![ChainPairCell::default(); chains.len()]; chains.len()];
+        for cat in &outcomes.cats {
+            let indices: Vec<usize> = cat.chains.iter().filter_map(|chain_id| index.get(chain_id).copied()).collect();
+            for &i in &indices {
+                for &j in &indices {
+                    cells[i][j].add(cat.outcome);
+                }
+            }
+        }
+        Self { chains: chains.to_vec(), chain_delays: chain_delays.to_vec(), cells }
+    }
+
+    /// The pairs of distinct chains that shared at least one CAT, lowest success rate first
+    pub fn pairs(&self) -> Vec<(usize, usize, ChainPairCell)> {
+        let mut pairs: Vec<(usize, usize, ChainPairCell)> = (0..self.chains.len())
+            .flat_map(|i| (i + 1..self.chains.len()).map(move |j| (i, j)))
+            .map(|(i, j)| (i, j, self.cells[i][j]))
+            .filter(|(_, _, cell)| cell.cats > 0)
+            .collect();
+        pairs.sort_by(|a, b| {
+            let rate = |cell: &ChainPairCell| cell.success_rate().unwrap_or(f64::INFINITY);
+            rate(&a.2).total_cmp(&rate(&b.2)).then(b.2.cats.cmp(&a.2.cats))
+        });
+        pairs
+    }
+
+    /// Returns the matrices of CAT counts and success rates and the list of pairs as JSON
+    pub fn to_json(&self) -> serde_json::Value {
+        let delay = |i: usize| self.chain_delays.get(i).copied().unwrap_or(0.0);
+        serde_json::json!({
+            "chains": self.chains.iter().map(|chain_id| chain_id.0.clone()).collect::<Vec<_>>(),
+            "chain_delays_blocks": self.chain_delays,
+            "cats": self.cells.iter().map(|row| row.iter().map(|cell| cell.cats).collect::<Vec<_>>()).collect::<Vec<_>>(),
+            "success_rates": self.cells.iter().map(|row| row.iter().map(|cell| cell.success_rate()).collect::<Vec<_>>()).collect::<Vec<_>>(),
+            "pairs": self.pairs().iter().map(|(i, j, cell)| serde_json::json!({
+                "chain_a": self.chains[*i].0,
+                "chain_b": self.chains[*j].0,
+                "delay_difference_blocks": (delay(*i) - delay(*j)).abs(),
+                "cats": cell.cats,
+                "success": cell.success,
+                "timed_out": cell.timed_out,
+                "pending": cell.pending,
+                "success_rate": cell.success_rate()
+            })).collect::<Vec<_>>()
+        })
+    }
+
+    /// Human-readable summary of the pairs with the lowest success rate, one line per pair
+    pub fn summary_lines(&self) -> Vec<String> {
+        let pairs = self.pairs();
+        if pairs.is_empty() {
+            return vec!["No CATs connected two chains".to_string()];
+        }
+        let mut lines: Vec<String> = pairs.iter().take(CHAIN_PAIR_SUMMARY_LIMIT).map(|(i, j, cell)| {
+            let success = cell.success_rate().map_or("no finalized CATs".to_string(), |rate| format!("{:.1}% success", rate * 100.0));
+            format!(
+                "{} <-> {}: {} CATs, {}, {} timed out, {} pending",
+                self.chains[*i].0, self.chains[*j].0, cell.cats, success, cell.timed_out, cell.pending,
+            )
+        }).collect();
+        if pairs.len() > CHAIN_PAIR_SUMMARY_LIMIT {
+            lines.push(format!("... {} more pairs in {}", pairs.len() - CHAIN_PAIR_SUMMARY_LIMIT, CHAIN_PAIR_MATRIX_FILE));
+        }
+        lines
+    }
+}
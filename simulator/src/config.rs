@@ -3,8 +3,17 @@
 //! Handles loading, validation, and access to simulation configuration files.
 
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use rand::Rng;
+use rand_distr::{Distribution, LogNormal};
 use std::time::Duration;
+use hyperplane::hyper_ig::{DelayDistribution, ProtocolViolationPolicy};
+use hyperplane::hyper_scheduler::DecisionLatency;
+use hyperplane::confirmation_layer::{BlockOrdering, BlockCapacity, BlockIntervalJitter};
+use hyperplane::types::{ChainId, ClockSkew, NetworkSpec};
+use crate::network::TopologyConfig;
+use crate::fault_orchestrator::{ScheduledFault, validate_fault_timeline};
+use crate::invariants::{BalanceInvariant, validate_invariants};
 use thiserror::Error;
 
 
@@ -43,16 +52,103 @@ pub struct Config {
 /// block production rates.
 #[derive(Debug, Deserialize, Clone)]
 pub struct NetworkConfig {
-    /// Number of chains in the multi-chain network
+    /// Number of chains in the multi-chain network (the simulator runs exactly 2)
     pub num_chains: usize,
+    /// IDs of the chains, in chain order (empty = chain-1 to chain-`num_chains`)
+    #[serde(default)]
+    pub chain_ids: Vec<String>,
     /// Delay in blocks for each chain (order corresponds to chain-1, chain-2, etc.)
     pub chain_delays: Vec<f64>,
+    /// Distribution of the delay of each proposal around its chain's delay (standard deviations in blocks)
+    #[serde(default)]
+    pub chain_delay_distribution: DelayDistribution,
     /// Block interval in seconds (time between block productions)
     pub block_interval: f64,
     /// Channel buffer size for high-performance communication
     /// Larger values handle higher TPS but use more memory
     #[serde(default = "default_channel_buffer_size")]
     pub channel_buffer_size: usize,
+    /// HS decision latency in blocks (time between the final proposal of a CAT and its status update)
+    #[serde(default)]
+    pub hs_decision_latency: DecisionLatency,
+    /// Per-link delay and jitter in blocks (CL -> HIG and HIG -> HS per chain, HS -> CL)
+    #[serde(default)]
+    pub topology: TopologyConfig,
+    /// Clock offset (in blocks) and drift of the HS and the HIGs relative to the CL
+    #[serde(default)]
+    pub clock_skew: ClockSkewConfig,
+    /// Order in which the CL includes pending transactions in a block
+    #[serde(default)]
+    pub cl_block_ordering: BlockOrdering,
+    /// Whether the CL holds back the parts of a CAT until all parts can be included at the same block height
+    #[serde(default)]
+    pub cl_align_cat_parts: bool,
+    /// Maximum number of CL transactions in a CL block (0 = unlimited)
+    #[serde(default)]
+    pub cl_block_max_transactions: u64,
+    /// Maximum total payload of the CL transactions in a CL block in bytes (0 = unlimited)
+    #[serde(default)]
+    pub cl_block_max_bytes: u64,
+    /// Jitter of the time between two CL blocks around the block interval (parameters in blocks)
+    #[serde(default)]
+    pub cl_block_interval_jitter: BlockIntervalJitter,
+    /// Whether the HIGs release the key locks of a CAT as soon as they propose Failure for it
+    #[serde(default)]
+    pub hig_release_locks_on_failure: bool,
+    /// Whether the HIGs process the status updates of a subblock before its other transactions
+    #[serde(default)]
+    pub hig_prioritize_status_updates: bool,
+    /// Delay in blocks before the HS submits the status update for each chain (order corresponds to chain-1, chain-2, etc.; empty = no delay)
+    #[serde(default)]
+    pub hs_status_update_delays: Vec<f64>,
+    /// Changes of the HIG parameters applied while the simulation runs (empty = parameters stay fixed)
+    #[serde(default)]
+    pub hig_parameter_schedule: Vec<HigParameterChange>,
+    /// Partitions of chains and crashes of HIGs at given blocks of the run (empty = no faults)
+    #[serde(default)]
+    pub fault_timeline: Vec<ScheduledFault>,
+    /// Address of a CL served by a separate process to run the workload against (unset = in-process nodes)
+    #[serde(default)]
+    pub cl_endpoint: Option<String>,
+}
+
+/// A change of HIG parameters applied once the simulation reaches a block.
+/// 
+/// Parameters that are not set keep their current value.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
+pub struct HigParameterChange {
+    /// Block at which the change is applied, counted from the start of the transaction submission
+    pub at_block: u64,
+    /// Chain whose HIG is changed (1 = chain-1, etc.; unset = all chains)
+    #[serde(default)]
+    pub chain: Option<usize>,
+    /// New delay in blocks for sending messages to the HS
+    #[serde(default)]
+    pub hs_message_delay: Option<f64>,
+    /// New lifetime in blocks of the CATs received from then on
+    #[serde(default)]
+    pub cat_lifetime_blocks: Option<u64>,
+    /// Whether CATs can depend on pending transactions from then on
+    #[serde(default)]
+    pub allow_cat_pending_dependencies: Option<bool>,
+}
+
+/// Clock skew of the nodes relative to the CL, which acts as the reference clock.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
+pub struct ClockSkewConfig {
+    /// Clock skew of each HIG (order corresponds to chain-1, chain-2, etc.; empty = no skew)
+    #[serde(default)]
+    pub hig: Vec<ClockSkew>,
+    /// Clock skew of the HS (only the drift applies)
+    #[serde(default)]
+    pub hs: ClockSkew,
+}
+
+impl ClockSkewConfig {
+    /// Returns the clock skew of the HIG of the chain at `chain_index`
+    pub fn hig(&self, chain_index: usize) -> ClockSkew {
+        self.hig.get(chain_index).copied().unwrap_or_default()
+    }
 }
 
 /// Configuration for account-related simulation parameters.
@@ -65,6 +161,45 @@ pub struct AccountConfig {
     pub initial_balance: i64,
     /// Number of accounts to create in the simulation
     pub num_accounts: usize,
+    /// Initial balances per chain, in the order of the chains. Chains without an entry
+    /// preload accounts 1..=num_accounts with the initial balance.
+    #[serde(default)]
+    pub chain_preloads: Vec<AccountPreload>,
+}
+
+impl AccountConfig {
+    /// Returns the initial (account, balance) pairs of each of the first `num_chains` chains
+    pub fn chain_balances(&self, num_chains: usize) -> Vec<Vec<(u32, u32)>> {
+        (0..num_chains)
+            .map(|chain_index| match self.chain_preloads.get(chain_index) {
+                Some(preload) => preload.balances(),
+                None => AccountPreload::Uniform {
+                    num_accounts: self.num_accounts as u32,
+                    balance: self.initial_balance as u32,
+                }.balances(),
+            })
+            .collect()
+    }
+}
+
+/// Initial account balances of one chain, applied to the chain's VM when the HIG is created.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AccountPreload {
+    /// Explicit (account, balance) pairs
+    List { balances: Vec<(u32, u32)> },
+    /// Accounts 1..=num_accounts, each with the same balance
+    Uniform { num_accounts: u32, balance: u32 },
+}
+
+impl AccountPreload {
+    /// Returns the (account, balance) pairs to preload
+    pub fn balances(&self) -> Vec<(u32, u32)> {
+        match self {
+            AccountPreload::List { balances } => balances.clone(),
+            AccountPreload::Uniform { num_accounts, balance } => (1..=*num_accounts).map(|account| (account, *balance)).collect(),
+        }
+    }
 }
 
 /// Configuration for transaction-related simulation parameters.
@@ -80,10 +215,121 @@ pub struct TransactionConfig {
     pub zipf_parameter: f64,
     /// Ratio of Cross-Chain Atomic Transactions (CATs) to regular transactions (0.0 = no CATs, 1.0 = all CATs)
     pub ratio_cats: f64,
+    /// CAT ratio of the transactions initiated by each chain, in the order of the chains (empty = `ratio_cats` for every chain).
+    /// When set, each transaction is initiated by a uniformly chosen chain, and its CATs include the initiating chain.
+    #[serde(default)]
+    pub chain_ratio_cats: Vec<f64>,
     /// Maximum number of blocks a CAT can remain pending before timing out
     pub cat_lifetime_blocks: u64,
     /// Whether CATs can depend on locked keys from pending transactions (affects transaction ordering)
     pub allow_cat_pending_dependencies: bool,
+    /// Whether CATs can depend on pending transactions on each chain, in the order of the chains
+    /// (chains without an entry use `allow_cat_pending_dependencies`)
+    #[serde(default)]
+    pub chain_allow_cat_pending_dependencies: Vec<bool>,
+    /// Distribution of the number of chains each generated CAT spans (defaults to all CATs spanning two chains)
+    #[serde(default = "default_cat_fanout")]
+    pub cat_fanout: Vec<CatFanoutWeight>,
+    /// How the constituent chains of a generated CAT are picked ("first" or "random")
+    #[serde(default)]
+    pub cat_chain_selection: CatChainSelection,
+    /// Maximum delay in blocks between the submissions of the parts of a CAT (0 = each CAT is submitted as one CL transaction)
+    #[serde(default)]
+    pub cat_part_spread_blocks: f64,
+    /// Number of blocks after submission at which a regular transaction expires if it is still pending (0 = never)
+    #[serde(default)]
+    pub regular_tx_ttl_blocks: u64,
+    /// Whether generated CATs are transfers (debit on the first constituent chain, credit on the second) instead of sends on every chain
+    #[serde(default)]
+    pub cat_transfers: bool,
+    /// Fraction of the generated CL transactions that are submitted a second time (0.0 = no duplicates)
+    #[serde(default)]
+    pub duplicate_ratio: f64,
+    /// Fraction of the duplicates that keep the id but carry a different payload (the others are exact copies)
+    #[serde(default)]
+    pub duplicate_variant_share: f64,
+    /// Blocks between the submission of a transaction and of its duplicate (0 = same block)
+    #[serde(default)]
+    pub duplicate_delay_blocks: u64,
+    /// Fraction of the generated transactions that are followed by a malformed CL transaction (0.0 = none)
+    #[serde(default)]
+    pub malformed_ratio: f64,
+    /// Distribution of the amounts of the generated sends and transfers (defaults to a fixed amount of 1)
+    #[serde(default)]
+    pub amount_distribution: AmountDistribution,
+}
+
+/// Weight of a single fanout (number of constituent chains) in the CAT fanout distribution.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct CatFanoutWeight {
+    /// Number of chains the CAT spans
+    pub chains: usize,
+    /// Relative weight of this fanout (weights do not need to sum to 1)
+    pub weight: f64,
+}
+
+/// Strategy for picking the constituent chains of a generated CAT.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum CatChainSelection {
+    /// Pick the first N registered chains (chain-1, chain-2, ...)
+    #[default]
+    First,
+    /// Pick N distinct registered chains uniformly at random
+    Random,
+}
+
+/// Distribution of the amounts of the generated transactions.
+///
+/// Whether a send succeeds depends on the amount compared to the balance of the sender, so the
+/// distribution controls the failure rate of a run. Amounts are drawn from the workload generator
+/// of the run, so they are reproduced by its seed.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AmountDistribution {
+    /// Every transaction moves the same amount
+    Fixed { amount: u32 },
+    /// Amount drawn uniformly from `min` to `max` (inclusive)
+    Uniform { min: u32, max: u32 },
+    /// Amount drawn from a log-normal distribution with the given mean whose logarithm has the
+    /// given standard deviation, rounded to at least 1
+    #[serde(rename = "lognormal")]
+    LogNormal { mean: f64, sigma: f64 },
+}
+
+impl Default for AmountDistribution {
+    fn default() -> Self {
+        AmountDistribution::Fixed { amount: 1 }
+    }
+}
+
+impl AmountDistribution {
+    /// Draws an amount from the distribution
+    ///
+    /// A fixed amount draws nothing from `rng`, so workloads generated before amounts were
+    /// configurable are reproduced by their seeds.
+    pub fn sample<R: Rng>(&self, rng: &mut R) -> u32 {
+        match *self {
+            AmountDistribution::Fixed { amount } => amount,
+            AmountDistribution::Uniform { min, max } => rng.gen_range(min..=max),
+            AmountDistribution::LogNormal { mean, sigma } => {
+                // Choose the location so that the mean of the distribution is the configured mean
+                match LogNormal::new(mean.ln() - sigma * sigma / 2.0, sigma) {
+                    Ok(log_normal) => log_normal.sample(rng).round().clamp(1.0, u32::MAX as f64) as u32,
+                    Err(_) => mean.round().max(1.0) as u32,
+                }
+            }
+        }
+    }
+
+    /// Mean amount of the distribution
+    pub fn mean(&self) -> f64 {
+        match *self {
+            AmountDistribution::Fixed { amount } => amount as f64,
+            AmountDistribution::Uniform { min, max } => (min as f64 + max as f64) / 2.0,
+            AmountDistribution::LogNormal { mean, .. } => mean,
+        }
+    }
 }
 
 /// Configuration for logging and output control.
@@ -95,6 +341,9 @@ pub struct LoggingConfig {
     /// Whether to log to file (true = write to file, false = no logging)
     #[serde(default = "default_log_to_file")]
     pub log_to_file: bool,
+    /// Whether the logs of each run are written to its results directory, one file per component
+    #[serde(default)]
+    pub capture_run_logs: bool,
 }
 
 /// Default value for log to file
@@ -102,6 +351,26 @@ fn default_log_to_file() -> bool {
     false
 }
 
+/// Default CAT fanout distribution: every CAT spans two chains
+pub fn default_cat_fanout() -> Vec<CatFanoutWeight> {
+    vec![CatFanoutWeight { chains: 2, weight: 1.0 }]
+}
+
+/// Default number of transactions per submission batch
+fn default_submission_batch_size() -> usize {
+    1
+}
+
+/// Default number of recent blocks the rate anomaly detector compares a block to
+fn default_rate_anomaly_window_blocks() -> usize {
+    20
+}
+
+/// Default deviation in standard deviations at which the rate anomaly detector flags a block
+fn default_rate_anomaly_sigma() -> f64 {
+    4.0
+}
+
 /// Default value for channel buffer size
 fn default_channel_buffer_size() -> usize {
     1000
@@ -111,6 +380,7 @@ impl Default for LoggingConfig {
     fn default() -> Self {
         Self {
             log_to_file: false,
+            capture_run_logs: false,
         }
     }
 }
@@ -119,9 +389,25 @@ impl Default for NetworkConfig {
     fn default() -> Self {
         Self {
             num_chains: 2,
+            chain_ids: Vec::new(),
             chain_delays: vec![0.0, 5.0],
+            chain_delay_distribution: DelayDistribution::default(),
             block_interval: 1.0,
             channel_buffer_size: default_channel_buffer_size(),
+            hs_decision_latency: DecisionLatency::default(),
+            topology: TopologyConfig::default(),
+            clock_skew: ClockSkewConfig::default(),
+            cl_block_ordering: BlockOrdering::default(),
+            cl_align_cat_parts: false,
+            cl_block_max_transactions: 0,
+            cl_block_max_bytes: 0,
+            cl_block_interval_jitter: BlockIntervalJitter::default(),
+            hig_release_locks_on_failure: false,
+            hig_prioritize_status_updates: false,
+            hs_status_update_delays: Vec::new(),
+            hig_parameter_schedule: Vec::new(),
+            fault_timeline: Vec::new(),
+            cl_endpoint: None,
         }
     }
 }
@@ -151,6 +437,9 @@ pub struct SimulationConfig {
     /// Step size for chain delay sweeps
     #[serde(default)]
     pub chain_delay_step: Option<f64>,
+    /// Step size of the spread of the chain delay distribution (for chain delay sweeps over the spread instead of the delay)
+    #[serde(default)]
+    pub chain_delay_spread_step: Option<f64>,
     /// Step size for block interval sweeps
     #[serde(default)]
     pub block_interval_step: Option<f64>,
@@ -160,6 +449,9 @@ pub struct SimulationConfig {
     /// Step size for total block number sweeps
     #[serde(default)]
     pub block_number_step: Option<u64>,
+    /// Step size for CL block capacity sweeps (in CL transactions per block)
+    #[serde(default)]
+    pub block_capacity_step: Option<u64>,
 
     /// Reference chain delay duration in seconds (for block interval constant time delay sweeps)
     #[serde(default)]
@@ -173,6 +465,52 @@ pub struct SimulationConfig {
     /// Constant number of CATs per block (for TPB sweeps with constant CATs per block)
     #[serde(default)]
     pub constants_cats_per_block: Option<f64>,
+    /// Ratios of chain delay to CAT lifetime (for CAT lifetime / delay ratio sweeps)
+    #[serde(default)]
+    pub delay_lifetime_ratios: Option<Vec<f64>>,
+    /// How HIGs handle protocol violations such as a Success update for a failed CAT ("ignore", "fail_run" or "escalate")
+    #[serde(default)]
+    pub protocol_violation_policy: ProtocolViolationPolicy,
+    /// Whether to write per-transaction and per-block records as Parquet files (requires the `parquet` feature)
+    #[serde(default)]
+    pub export_parquet: bool,
+    /// Number of transactions handed to the CL at once (1 = every transaction is submitted on its own)
+    #[serde(default = "default_submission_batch_size")]
+    pub submission_batch_size: usize,
+    /// Maximum number of transactions submitted per second (0 = unlimited)
+    #[serde(default)]
+    pub submission_rate_limit_tps: f64,
+    /// Master seed of the workload generator (drawn at random if unset); run N uses the seed plus N - 1
+    #[serde(default)]
+    pub seed: Option<u64>,
+    /// Whether to pack the raw data of each sweep point into a tar+zstd archive once the sweep report is written
+    #[serde(default)]
+    pub archive_raw_data: bool,
+    /// Interval in blocks at which the aggregated metrics of a run are flushed to its data directory (0 = never)
+    #[serde(default)]
+    pub flush_interval_blocks: u64,
+    /// Number of recent blocks the rate anomaly detector compares the TPS and pending counts of a block to
+    #[serde(default = "default_rate_anomaly_window_blocks")]
+    pub rate_anomaly_window_blocks: usize,
+    /// Deviation from the window mean, in standard deviations, at which a block is flagged (0 = disabled)
+    #[serde(default = "default_rate_anomaly_sigma")]
+    pub rate_anomaly_sigma: f64,
+    /// Invariants on the account balances, checked after every block; a violation fails the run
+    #[serde(default)]
+    pub invariants: Vec<BalanceInvariant>,
+    /// Number of blocks a HIG may fall behind the CL without advancing before the watchdog reports a stall (0 = disabled)
+    #[serde(default)]
+    pub watchdog_max_lag_blocks: u64,
+    /// Seconds the queues of a HIG may stay non-empty without progress before the watchdog reports a stall (0 = disabled)
+    #[serde(default)]
+    pub watchdog_queue_stall_secs: f64,
+    /// Whether a stall reported by the watchdog aborts the run
+    #[serde(default)]
+    pub watchdog_abort: bool,
+    /// Whether the nodes, the links and the submission loop run in virtual time on a `SimulatedClock`,
+    /// which advances as fast as the nodes keep up instead of in real time
+    #[serde(default)]
+    pub simulated_clock: bool,
 }
 
 impl Default for SimulationConfig {
@@ -184,13 +522,30 @@ impl Default for SimulationConfig {
             cat_lifetime_step: None,
             cat_ratio_step: None,
             chain_delay_step: None,
+            chain_delay_spread_step: None,
             block_interval_step: None,
             zipf_step: None,
             block_number_step: None,
+            block_capacity_step: None,
             reference_chain_delay_duration: None,
             reference_tps: None,
             target_tpb_multiplier_per_step: None,
             constants_cats_per_block: None,
+            delay_lifetime_ratios: None,
+            protocol_violation_policy: ProtocolViolationPolicy::default(),
+            export_parquet: false,
+            submission_batch_size: default_submission_batch_size(),
+            submission_rate_limit_tps: 0.0,
+            seed: None,
+            archive_raw_data: false,
+            flush_interval_blocks: 0,
+            rate_anomaly_window_blocks: default_rate_anomaly_window_blocks(),
+            rate_anomaly_sigma: default_rate_anomaly_sigma(),
+            invariants: Vec::new(),
+            watchdog_max_lag_blocks: 0,
+            watchdog_queue_stall_secs: 0.0,
+            watchdog_abort: false,
+            simulated_clock: false,
             sim_total_block_number: 1000, // Default value for sim_total_block_number
         }
     }
@@ -242,6 +597,9 @@ pub fn validate_common_fields(
     if account_config.num_accounts == 0 {
         return Err(ConfigError::ValidationError("Number of accounts must be positive".into()));
     }
+    if account_config.chain_preloads.len() > network_config.num_chains {
+        return Err(ConfigError::ValidationError(format!("Account preloads are configured for {} chains, but only {} chains exist", account_config.chain_preloads.len(), network_config.num_chains)));
+    }
     if transaction_config.target_tpb <= 0.0 {
         return Err(ConfigError::ValidationError("Target TPB must be positive".into()));
     }
@@ -254,22 +612,154 @@ pub fn validate_common_fields(
     if transaction_config.ratio_cats < 0.0 || transaction_config.ratio_cats > 1.0 {
         return Err(ConfigError::ValidationError("Ratio cats must be between 0 and 1".into()));
     }
+    if transaction_config.chain_ratio_cats.iter().any(|ratio| *ratio < 0.0 || *ratio > 1.0) {
+        return Err(ConfigError::ValidationError("Per-chain CAT ratios must be between 0 and 1".into()));
+    }
+    if transaction_config.chain_ratio_cats.len() > network_config.num_chains {
+        return Err(ConfigError::ValidationError(format!("CAT ratios are configured for {} chains, but only {} chains exist", transaction_config.chain_ratio_cats.len(), network_config.num_chains)));
+    }
+    if transaction_config.chain_allow_cat_pending_dependencies.len() > network_config.num_chains {
+        return Err(ConfigError::ValidationError(format!("CAT pending dependencies are configured for {} chains, but only {} chains exist", transaction_config.chain_allow_cat_pending_dependencies.len(), network_config.num_chains)));
+    }
     if transaction_config.cat_lifetime_blocks == 0 {
         return Err(ConfigError::ValidationError("CAT lifetime blocks must be positive".into()));
     }
+    if transaction_config.cat_fanout.is_empty() {
+        return Err(ConfigError::ValidationError("CAT fanout distribution must not be empty".into()));
+    }
+    for fanout in &transaction_config.cat_fanout {
+        if fanout.chains < 2 || fanout.chains > network_config.num_chains {
+            return Err(ConfigError::ValidationError(format!("CAT fanout must be between 2 and the number of chains ({}), got {}", network_config.num_chains, fanout.chains)));
+        }
+        if fanout.weight < 0.0 {
+            return Err(ConfigError::ValidationError("CAT fanout weights must be non-negative".into()));
+        }
+    }
+    if transaction_config.cat_fanout.iter().map(|f| f.weight).sum::<f64>() <= 0.0 {
+        return Err(ConfigError::ValidationError("CAT fanout weights must not all be zero".into()));
+    }
+    if transaction_config.cat_transfers && transaction_config.cat_fanout.iter().any(|f| f.chains != 2 && f.weight > 0.0) {
+        return Err(ConfigError::ValidationError("CAT transfers require a CAT fanout of 2 chains".into()));
+    }
+    if transaction_config.cat_part_spread_blocks < 0.0 {
+        return Err(ConfigError::ValidationError("CAT part spread must be non-negative".into()));
+    }
+    if !(0.0..=1.0).contains(&transaction_config.duplicate_ratio) {
+        return Err(ConfigError::ValidationError("Duplicate ratio must be between 0 and 1".into()));
+    }
+    if !(0.0..=1.0).contains(&transaction_config.duplicate_variant_share) {
+        return Err(ConfigError::ValidationError("Duplicate variant share must be between 0 and 1".into()));
+    }
+    if !(0.0..=1.0).contains(&transaction_config.malformed_ratio) {
+        return Err(ConfigError::ValidationError("Malformed ratio must be between 0 and 1".into()));
+    }
+    match transaction_config.amount_distribution {
+        AmountDistribution::Fixed { amount } if amount == 0 => {
+            return Err(ConfigError::ValidationError("Fixed transaction amount must be positive".into()));
+        }
+        AmountDistribution::Uniform { min, max } if min == 0 || min > max => {
+            return Err(ConfigError::ValidationError("Uniform transaction amounts need 0 < min <= max".into()));
+        }
+        AmountDistribution::LogNormal { mean, sigma } if !mean.is_finite() || mean < 1.0 || !sigma.is_finite() || sigma < 0.0 => {
+            return Err(ConfigError::ValidationError("Log-normal transaction amounts need a mean of at least 1 and a non-negative sigma".into()));
+        }
+        _ => {}
+    }
     if simulation_config.initialization_wait_blocks == 0 {
         return Err(ConfigError::ValidationError("Initialization wait blocks must be positive".into()));
     }
     if simulation_config.num_runs == 0 {
         return Err(ConfigError::ValidationError("Number of runs must be positive".into()));
     }
+    if simulation_config.submission_batch_size == 0 {
+        return Err(ConfigError::ValidationError("Submission batch size must be positive".into()));
+    }
+    if simulation_config.submission_rate_limit_tps < 0.0 {
+        return Err(ConfigError::ValidationError("Submission rate limit must be non-negative".into()));
+    }
+    if simulation_config.rate_anomaly_sigma < 0.0 {
+        return Err(ConfigError::ValidationError("Rate anomaly sigma must be non-negative".into()));
+    }
+    if simulation_config.rate_anomaly_sigma > 0.0 && simulation_config.rate_anomaly_window_blocks < 2 {
+        return Err(ConfigError::ValidationError("Rate anomaly window must span at least 2 blocks".into()));
+    }
+    if !simulation_config.watchdog_queue_stall_secs.is_finite() || simulation_config.watchdog_queue_stall_secs < 0.0 {
+        return Err(ConfigError::ValidationError("Watchdog queue stall seconds must be finite and non-negative".into()));
+    }
     // allow_cat_pending_dependencies is a boolean, so no validation needed
-    if network_config.num_chains == 0 {
-        return Err(ConfigError::ValidationError("Number of chains must be positive".into()));
+    // The nodes, the workload and the per-chain results are built for chain-1 and chain-2
+    if network_config.num_chains != 2 {
+        return Err(ConfigError::ValidationError(format!("The simulator runs exactly 2 chains, got {}", network_config.num_chains)));
     }
     if network_config.chain_delays.len() != network_config.num_chains {
         return Err(ConfigError::ValidationError("Number of chain delays must match number of chains".into()));
     }
+    if !network_config.chain_ids.is_empty() && network_config.chain_ids.len() != network_config.num_chains {
+        return Err(ConfigError::ValidationError(format!("{} chain IDs are configured, but the network has {} chains", network_config.chain_ids.len(), network_config.num_chains)));
+    }
+    network_config.network_spec()?;
+    match network_config.chain_delay_distribution {
+        DelayDistribution::Normal { std_dev } if std_dev < 0.0 => {
+            return Err(ConfigError::ValidationError("Standard deviation of the chain delay distribution must be non-negative".into()));
+        }
+        DelayDistribution::LogNormal { sigma } if sigma < 0.0 => {
+            return Err(ConfigError::ValidationError("Sigma of the chain delay distribution must be non-negative".into()));
+        }
+        DelayDistribution::Bimodal { tail_probability, tail_factor } if !(0.0..=1.0).contains(&tail_probability) || tail_factor < 0.0 => {
+            return Err(ConfigError::ValidationError("Tail probability of the chain delay distribution must be between 0 and 1 and its tail factor non-negative".into()));
+        }
+        _ => {}
+    }
+    match network_config.cl_block_interval_jitter {
+        BlockIntervalJitter::Uniform { max_deviation } if max_deviation < 0.0 => {
+            return Err(ConfigError::ValidationError("Maximum deviation of the CL block interval jitter must be non-negative".into()));
+        }
+        BlockIntervalJitter::Normal { std_dev } if std_dev < 0.0 => {
+            return Err(ConfigError::ValidationError("Standard deviation of the CL block interval jitter must be non-negative".into()));
+        }
+        BlockIntervalJitter::LateBlocks { probability, delay } if !(0.0..=1.0).contains(&probability) || delay < 0.0 => {
+            return Err(ConfigError::ValidationError("Probability of a late CL block must be between 0 and 1 and its delay non-negative".into()));
+        }
+        _ => {}
+    }
+    if !network_config.hs_status_update_delays.is_empty() && network_config.hs_status_update_delays.len() != network_config.num_chains {
+        return Err(ConfigError::ValidationError("Number of HS status update delays must match number of chains".into()));
+    }
+    if network_config.hs_status_update_delays.iter().any(|delay| *delay < 0.0) {
+        return Err(ConfigError::ValidationError("HS status update delays must be non-negative".into()));
+    }
+    for change in &network_config.hig_parameter_schedule {
+        if change.hs_message_delay.is_none() && change.cat_lifetime_blocks.is_none() && change.allow_cat_pending_dependencies.is_none() {
+            return Err(ConfigError::ValidationError(format!("HIG parameter change at block {} does not change any parameter", change.at_block)));
+        }
+        if change.at_block >= simulation_config.sim_total_block_number {
+            return Err(ConfigError::ValidationError(format!("HIG parameter change at block {} is after the end of the simulation ({} blocks)", change.at_block, simulation_config.sim_total_block_number)));
+        }
+        if change.chain.is_some_and(|chain| chain == 0 || chain > network_config.num_chains) {
+            return Err(ConfigError::ValidationError(format!("HIG parameter change at block {} targets chain {}, but only chains 1 to {} exist", change.at_block, change.chain.unwrap_or_default(), network_config.num_chains)));
+        }
+        if change.hs_message_delay.is_some_and(|delay| delay < 0.0) {
+            return Err(ConfigError::ValidationError("HS message delays in the HIG parameter schedule must be non-negative".into()));
+        }
+        if change.cat_lifetime_blocks == Some(0) {
+            return Err(ConfigError::ValidationError("CAT lifetimes in the HIG parameter schedule must be positive".into()));
+        }
+    }
+    validate_fault_timeline(&network_config.fault_timeline, network_config.num_chains, simulation_config.sim_total_block_number).map_err(ConfigError::ValidationError)?;
+    if network_config.cl_endpoint.as_ref().is_some_and(|endpoint| endpoint.trim().is_empty()) {
+        return Err(ConfigError::ValidationError("CL endpoint must not be empty".into()));
+    }
+    network_config.topology.validate(network_config.num_chains).map_err(ConfigError::ValidationError)?;
+    validate_invariants(&simulation_config.invariants, network_config.num_chains).map_err(ConfigError::ValidationError)?;
+    if !network_config.clock_skew.hig.is_empty() && network_config.clock_skew.hig.len() != network_config.num_chains {
+        return Err(ConfigError::ValidationError("Number of HIG clock skews must match number of chains".into()));
+    }
+    let clock_skews = network_config.clock_skew.hig.iter().chain(std::iter::once(&network_config.clock_skew.hs));
+    for skew in clock_skews {
+        if skew.drift <= -1.0 {
+            return Err(ConfigError::ValidationError("Clock drift must be greater than -1".into()));
+        }
+    }
     // No validation needed for u64 - it's always non-negative
     if network_config.block_interval <= 0.0 {
         return Err(ConfigError::ValidationError("Block interval must be positive".into()));
@@ -284,18 +774,62 @@ pub fn validate_common_fields(
 impl NetworkConfig {
     /// Generates a list of chain IDs for the network configuration.
     /// 
-    /// Returns a vector of strings in the format "chain-1", "chain-2", etc.
+    /// Returns the configured chain IDs, or strings in the format "chain-1", "chain-2", etc.
     /// based on the number of chains configured.
     pub fn get_chain_ids(&self) -> Vec<String> {
-        (1..=self.num_chains)
-            .map(|i| format!("chain-{}", i))
+        match self.network_spec() {
+            Ok(network) => network.chain_ids().iter().map(|chain_id| chain_id.0.clone()).collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// Returns the chains the simulation runs: the configured chain IDs, or chain-1 to
+    /// chain-`num_chains` if none are configured
+    pub fn network_spec(&self) -> Result<NetworkSpec, ConfigError> {
+        let network = if self.chain_ids.is_empty() {
+            NetworkSpec::with_chain_count(self.num_chains)
+        } else {
+            NetworkSpec::new(self.chain_ids.iter().map(|chain_id| ChainId(chain_id.clone())).collect())
+        };
+        network.map_err(|e| ConfigError::ValidationError(format!("Invalid network: {}", e)))
+    }
+
+    /// Returns the limits on the contents of a CL block
+    pub fn cl_block_capacity(&self) -> BlockCapacity {
+        BlockCapacity {
+            max_transactions: self.cl_block_max_transactions,
+            max_bytes: self.cl_block_max_bytes,
+        }
+    }
+
+    /// Returns the jitter of the CL block interval in seconds
+    pub fn cl_block_interval_jitter_secs(&self) -> BlockIntervalJitter {
+        self.cl_block_interval_jitter.scaled(self.block_interval)
+    }
+}
+
+impl TransactionConfig {
+    /// Returns whether CATs can depend on pending transactions on each of the first `num_chains` chains
+    pub fn allow_cat_pending_dependencies_per_chain(&self, num_chains: usize) -> Vec<bool> {
+        (0..num_chains)
+            .map(|chain_index| self.chain_allow_cat_pending_dependencies.get(chain_index).copied().unwrap_or(self.allow_cat_pending_dependencies))
             .collect()
     }
 }
 
 impl Config {
-    /// Validates the configuration
+    /// Validates the configuration of a scenario that runs in-process nodes
+    ///
+    /// Only the simple simulation can run against an external CL, so a CL endpoint is rejected.
     pub fn validate(&self) -> Result<(), ConfigError> {
+        if let Some(cl_endpoint) = &self.network_config.cl_endpoint {
+            return Err(ConfigError::ValidationError(format!("CL endpoint {} is only supported by the simple simulation", cl_endpoint)));
+        }
+        self.validate_allowing_external_cl()
+    }
+
+    /// Validates the configuration of the simple simulation, which may set a CL endpoint
+    pub fn validate_allowing_external_cl(&self) -> Result<(), ConfigError> {
         validate_common_fields(&self.account_config, &self.transaction_config, &self.network_config, &self.simulation_config)
     }
 
@@ -0,0 +1,322 @@
+//! Declared structure of the simulation configuration files.
+//!
+//! Every registered simulation declares the parameters its config.toml is expected to contain
+//! (name, type, whether it is required, default and allowed range), so that configurations can
+//! be checked before a simulation is launched and the available parameters can be documented
+//! without reading the config structs.
+
+use std::fmt::Write;
+use std::fs;
+
+// ------------------------------------------------------------------------------------------------
+// Data Structures
+// ------------------------------------------------------------------------------------------------
+
+/// Type of a configuration parameter
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ParameterKind {
+    /// A number (integers are accepted as well)
+    Float,
+    /// A whole number
+    Integer,
+    /// `true` or `false`
+    Bool,
+    /// Free text, e.g. a path
+    String,
+    /// One of a fixed set of strings
+    Choice(&'static [&'static str]),
+    /// An array of numbers
+    FloatList,
+    /// An array of `true`/`false` values
+    BoolList,
+    /// An array of tables or strings, not checked further
+    List,
+    /// A table; its fields are checked if they are declared, and not checked otherwise
+    Table,
+}
+
+/// Declaration of a single configuration parameter
+#[derive(Debug, Clone, Copy)]
+pub struct ParameterSchema {
+    /// Location of the parameter, as `<table>.<field>`
+    pub path: &'static str,
+    /// Type of the parameter
+    pub kind: ParameterKind,
+    /// Whether the parameter must be set if its table is present
+    pub required: bool,
+    /// Default value for documentation (None for required parameters without default)
+    pub default: Option<&'static str>,
+    /// Smallest allowed value (inclusive) for numbers and the elements of number lists
+    pub min: Option<f64>,
+    /// Largest allowed value (inclusive) for numbers and the elements of number lists
+    pub max: Option<f64>,
+    /// Short description of the parameter
+    pub description: &'static str,
+}
+
+/// Declared structure of a simulation's configuration file
+#[derive(Debug, Clone, Copy)]
+pub struct ConfigSchema {
+    /// Configuration file the simulation reads
+    pub config_path: &'static str,
+    /// Parameter sections, e.g. the common parameters and the ones specific to a sweep
+    pub sections: &'static [&'static [ParameterSchema]],
+}
+
+/// Outcome of checking a configuration against its schema
+#[derive(Debug, Default)]
+pub struct SchemaReport {
+    /// Missing required parameters, wrong types and out-of-range values
+    pub errors: Vec<String>,
+    /// Parameters that are not declared and therefore ignored by the simulation
+    pub warnings: Vec<String>,
+}
+
+impl SchemaReport {
+    /// Whether the configuration can be used
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Common Parameters
+// ------------------------------------------------------------------------------------------------
+
+/// Builds the declaration of an optional parameter without default or range, which callers
+/// refine with struct update syntax
+pub const fn parameter(path: &'static str, kind: ParameterKind, description: &'static str) -> ParameterSchema {
+    ParameterSchema { path, kind, required: false, default: None, min: None, max: None, description }
+}
+
+/// Parameters of the `Config` struct shared by all simulations (see `crate::config`)
+pub const COMMON_PARAMETERS: &[ParameterSchema] = &[
+    ParameterSchema { required: true, ..parameter("network_config", ParameterKind::Table, "Network parameters") },
+    ParameterSchema { required: true, min: Some(2.0), max: Some(2.0), ..parameter("network_config.num_chains", ParameterKind::Integer, "Number of chains to simulate (exactly 2)") },
+    ParameterSchema { default: Some("chain-1 to chain-<num_chains>"), ..parameter("network_config.chain_ids", ParameterKind::List, "IDs of the chains, in chain order") },
+    ParameterSchema { required: true, min: Some(0.0), ..parameter("network_config.chain_delays", ParameterKind::FloatList, "Delay in blocks of each chain's HIG -> HS path, in chain order") },
+    ParameterSchema { default: Some("{ type = \"constant\" }"), ..parameter("network_config.chain_delay_distribution", ParameterKind::Table, "Distribution of the proposal delays around the chain delays (normal, lognormal or bimodal)") },
+    ParameterSchema { required: true, min: Some(0.0), ..parameter("network_config.block_interval", ParameterKind::Float, "Block interval in seconds (must be positive)") },
+    ParameterSchema { default: Some("1000"), min: Some(1.0), ..parameter("network_config.channel_buffer_size", ParameterKind::Integer, "Buffer size of the channels between the nodes") },
+    ParameterSchema { default: Some("{ type = \"zero\" }"), ..parameter("network_config.hs_decision_latency", ParameterKind::Table, "HS decision latency in blocks") },
+    ParameterSchema { default: Some("zero latency"), ..parameter("network_config.topology", ParameterKind::Table, "Per-link delay and jitter in blocks") },
+    ParameterSchema { default: Some("[]"), ..parameter("network_config.topology.cl_to_hig", ParameterKind::List, "Delay and jitter of each chain's CL -> HIG link, in chain order") },
+    ParameterSchema { default: Some("[]"), ..parameter("network_config.topology.hig_to_hs", ParameterKind::List, "Delay and jitter of each chain's HIG -> HS link, in chain order") },
+    ParameterSchema { default: Some("zero latency"), ..parameter("network_config.topology.hs_to_cl", ParameterKind::Table, "Delay and jitter of the HS -> CL link") },
+    ParameterSchema { default: Some("1.0"), min: Some(0.0), ..parameter("network_config.topology.cat_latency_multiplier", ParameterKind::Float, "Factor applied to the link latency of CAT proposals, status updates and subblocks with CATs") },
+    ParameterSchema { default: Some("1.0"), min: Some(0.0), ..parameter("network_config.topology.regular_latency_multiplier", ParameterKind::Float, "Factor applied to the link latency of subblocks with only regular transactions") },
+    ParameterSchema { default: Some("no skew"), ..parameter("network_config.clock_skew", ParameterKind::Table, "Clock offset and drift of the HIGs and the HS") },
+    ParameterSchema { default: Some("{ type = \"fifo\" }"), ..parameter("network_config.cl_block_ordering", ParameterKind::Table, "Order of the transactions within a CL block") },
+    ParameterSchema { default: Some("false"), ..parameter("network_config.cl_align_cat_parts", ParameterKind::Bool, "Hold back the parts of a CAT until all can be included at the same height") },
+    ParameterSchema { default: Some("0"), min: Some(0.0), ..parameter("network_config.cl_block_max_transactions", ParameterKind::Integer, "Maximum number of CL transactions per CL block (0 = unlimited)") },
+    ParameterSchema { default: Some("0"), min: Some(0.0), ..parameter("network_config.cl_block_max_bytes", ParameterKind::Integer, "Maximum payload of a CL block in bytes (0 = unlimited)") },
+    ParameterSchema { default: Some("{ type = \"none\" }"), ..parameter("network_config.cl_block_interval_jitter", ParameterKind::Table, "Jitter of the time between CL blocks in blocks (none, uniform, normal or late_blocks)") },
+    ParameterSchema { default: Some("false"), ..parameter("network_config.hig_release_locks_on_failure", ParameterKind::Bool, "Release the key locks of a CAT when the HIG proposes Failure") },
+    ParameterSchema { default: Some("false"), ..parameter("network_config.hig_prioritize_status_updates", ParameterKind::Bool, "Process the status updates of a subblock first") },
+    ParameterSchema { default: Some("[]"), min: Some(0.0), ..parameter("network_config.hs_status_update_delays", ParameterKind::FloatList, "Delay in blocks of the HS status updates, in chain order") },
+    ParameterSchema { default: Some("[]"), ..parameter("network_config.hig_parameter_schedule", ParameterKind::List, "Changes of the HIG parameters at given blocks of the run") },
+    ParameterSchema { default: Some("[]"), ..parameter("network_config.fault_timeline", ParameterKind::List, "Partitions of chains and crashes of HIGs at given blocks of the run") },
+    ParameterSchema { default: Some("in-process nodes"), ..parameter("network_config.cl_endpoint", ParameterKind::String, "Address of a CL served by a separate process (the shell with --cl-listen) to run the workload against, simple simulation only") },
+    ParameterSchema { required: true, ..parameter("account_config", ParameterKind::Table, "Account parameters") },
+    ParameterSchema { required: true, min: Some(0.0), ..parameter("account_config.initial_balance", ParameterKind::Integer, "Initial balance of each account") },
+    ParameterSchema { required: true, min: Some(1.0), ..parameter("account_config.num_accounts", ParameterKind::Integer, "Number of accounts") },
+    ParameterSchema { default: Some("[]"), ..parameter("account_config.chain_preloads", ParameterKind::List, "Initial balances per chain, in chain order") },
+    ParameterSchema { required: true, ..parameter("transaction_config", ParameterKind::Table, "Transaction parameters") },
+    ParameterSchema { required: true, min: Some(0.0), ..parameter("transaction_config.target_tpb", ParameterKind::Float, "Target transactions per block") },
+    ParameterSchema { required: true, min: Some(0.0), ..parameter("transaction_config.zipf_parameter", ParameterKind::Float, "Zipf parameter of the account selection (0 = uniform)") },
+    ParameterSchema { required: true, min: Some(0.0), max: Some(1.0), ..parameter("transaction_config.ratio_cats", ParameterKind::Float, "Ratio of CATs among all transactions") },
+    ParameterSchema { default: Some("[]"), min: Some(0.0), max: Some(1.0), ..parameter("transaction_config.chain_ratio_cats", ParameterKind::FloatList, "CAT ratio of the transactions initiated by each chain") },
+    ParameterSchema { required: true, min: Some(1.0), ..parameter("transaction_config.cat_lifetime_blocks", ParameterKind::Integer, "CAT lifetime in blocks") },
+    ParameterSchema { required: true, ..parameter("transaction_config.allow_cat_pending_dependencies", ParameterKind::Bool, "Allow CATs to depend on keys locked by pending transactions") },
+    ParameterSchema { default: Some("[]"), ..parameter("transaction_config.chain_allow_cat_pending_dependencies", ParameterKind::BoolList, "Allow CATs to depend on pending transactions, per chain") },
+    ParameterSchema { default: Some("[{ chains = 2, weight = 1.0 }]"), ..parameter("transaction_config.cat_fanout", ParameterKind::List, "Distribution of the number of chains a CAT spans") },
+    ParameterSchema { default: Some("\"first\""), ..parameter("transaction_config.cat_chain_selection", ParameterKind::Choice(&["first", "random"]), "How the constituent chains of a CAT are picked") },
+    ParameterSchema { default: Some("0.0"), min: Some(0.0), ..parameter("transaction_config.cat_part_spread_blocks", ParameterKind::Float, "Maximum delay between the parts of a CAT in blocks") },
+    ParameterSchema { default: Some("0"), min: Some(0.0), ..parameter("transaction_config.regular_tx_ttl_blocks", ParameterKind::Integer, "Blocks until blocked regular transactions expire (0 = never)") },
+    ParameterSchema { default: Some("false"), ..parameter("transaction_config.cat_transfers", ParameterKind::Bool, "Generate CATs as transfers instead of sends") },
+    ParameterSchema { default: Some("0.0"), min: Some(0.0), max: Some(1.0), ..parameter("transaction_config.duplicate_ratio", ParameterKind::Float, "Fraction of CL transactions submitted a second time") },
+    ParameterSchema { default: Some("0.0"), min: Some(0.0), max: Some(1.0), ..parameter("transaction_config.duplicate_variant_share", ParameterKind::Float, "Fraction of duplicates with the same id but a different payload") },
+    ParameterSchema { default: Some("0"), min: Some(0.0), ..parameter("transaction_config.duplicate_delay_blocks", ParameterKind::Integer, "Blocks between a transaction and its duplicate") },
+    ParameterSchema { default: Some("0.0"), min: Some(0.0), max: Some(1.0), ..parameter("transaction_config.malformed_ratio", ParameterKind::Float, "Fraction of transactions followed by a malformed one") },
+    ParameterSchema { default: Some("{ type = \"fixed\", amount = 1 }"), ..parameter("transaction_config.amount_distribution", ParameterKind::Table, "Distribution of the amounts of sends and transfers") },
+    ParameterSchema { required: true, ..parameter("transaction_config.amount_distribution.type", ParameterKind::Choice(&["fixed", "uniform", "lognormal"]), "Kind of the amount distribution") },
+    ParameterSchema { min: Some(1.0), ..parameter("transaction_config.amount_distribution.amount", ParameterKind::Integer, "Amount of every transaction (fixed)") },
+    ParameterSchema { min: Some(1.0), ..parameter("transaction_config.amount_distribution.min", ParameterKind::Integer, "Smallest amount (uniform)") },
+    ParameterSchema { min: Some(1.0), ..parameter("transaction_config.amount_distribution.max", ParameterKind::Integer, "Largest amount (uniform)") },
+    ParameterSchema { min: Some(1.0), ..parameter("transaction_config.amount_distribution.mean", ParameterKind::Float, "Mean amount (lognormal)") },
+    ParameterSchema { min: Some(0.0), ..parameter("transaction_config.amount_distribution.sigma", ParameterKind::Float, "Standard deviation of the logarithm of the amount (lognormal)") },
+    ParameterSchema { default: Some("defaults below"), ..parameter("simulation_config", ParameterKind::Table, "Simulation execution parameters") },
+    ParameterSchema { required: true, default: Some("10"), min: Some(0.0), ..parameter("simulation_config.initialization_wait_blocks", ParameterKind::Integer, "Blocks to wait before transaction submission starts") },
+    ParameterSchema { required: true, default: Some("1"), min: Some(1.0), ..parameter("simulation_config.num_runs", ParameterKind::Integer, "Number of runs to average") },
+    ParameterSchema { required: true, default: Some("1000"), min: Some(1.0), ..parameter("simulation_config.sim_total_block_number", ParameterKind::Integer, "Number of blocks to simulate") },
+    ParameterSchema { default: Some("\"ignore\""), ..parameter("simulation_config.protocol_violation_policy", ParameterKind::Choice(&["ignore", "fail_run", "escalate"]), "How HIGs handle protocol violations") },
+    ParameterSchema { default: Some("false"), ..parameter("simulation_config.export_parquet", ParameterKind::Bool, "Write per-transaction and per-block records as Parquet") },
+    ParameterSchema { default: Some("1"), min: Some(1.0), ..parameter("simulation_config.submission_batch_size", ParameterKind::Integer, "Transactions handed to the CL under a single lock") },
+    ParameterSchema { default: Some("0.0"), min: Some(0.0), ..parameter("simulation_config.submission_rate_limit_tps", ParameterKind::Float, "Submission rate limit in TPS (0 = unlimited)") },
+    ParameterSchema { default: Some("random"), min: Some(0.0), ..parameter("simulation_config.seed", ParameterKind::Integer, "Master seed of the workload generator") },
+    ParameterSchema { default: Some("false"), ..parameter("simulation_config.archive_raw_data", ParameterKind::Bool, "Archive the raw data of each sweep point") },
+    ParameterSchema { default: Some("0"), min: Some(0.0), ..parameter("simulation_config.flush_interval_blocks", ParameterKind::Integer, "Blocks between flushes of the partial results of a run (0 = never)") },
+    ParameterSchema { default: Some("20"), min: Some(2.0), ..parameter("simulation_config.rate_anomaly_window_blocks", ParameterKind::Integer, "Recent blocks the rate anomaly detector compares a block to") },
+    ParameterSchema { default: Some("4.0"), min: Some(0.0), ..parameter("simulation_config.rate_anomaly_sigma", ParameterKind::Float, "Deviation in standard deviations that flags a block (0 = disabled)") },
+    ParameterSchema { default: Some("[]"), ..parameter("simulation_config.invariants", ParameterKind::List, "Invariants on the account balances; a violation fails the run") },
+    ParameterSchema { default: Some("0"), min: Some(0.0), ..parameter("simulation_config.watchdog_max_lag_blocks", ParameterKind::Integer, "Blocks a HIG may fall behind the CL without advancing before a stall is reported (0 = disabled)") },
+    ParameterSchema { default: Some("0.0"), min: Some(0.0), ..parameter("simulation_config.watchdog_queue_stall_secs", ParameterKind::Float, "Seconds the queues of a HIG may stay non-empty without progress before a stall is reported (0 = disabled)") },
+    ParameterSchema { default: Some("false"), ..parameter("simulation_config.watchdog_abort", ParameterKind::Bool, "Abort the run when the watchdog reports a stall") },
+    ParameterSchema { default: Some("false"), ..parameter("simulation_config.simulated_clock", ParameterKind::Bool, "Run the nodes, the links and the submission loop in virtual time instead of real time") },
+    ParameterSchema { default: Some("defaults below"), ..parameter("logging_config", ParameterKind::Table, "Logging parameters") },
+    ParameterSchema { default: Some("false"), ..parameter("logging_config.log_to_file", ParameterKind::Bool, "Write the simulation log to a file") },
+    ParameterSchema { default: Some("false"), ..parameter("logging_config.capture_run_logs", ParameterKind::Bool, "Write the logs of each run to its results directory, one file per component") },
+];
+
+/// Looks up a common parameter by its path
+pub fn common_parameter(path: &str) -> Option<&'static ParameterSchema> {
+    COMMON_PARAMETERS.iter().find(|parameter| parameter.path == path)
+}
+
+/// Declares the number of simulations of a sweep and the sweep's own parameters
+///
+/// # Arguments
+/// * `path` - Path of the sweep's step (or other sweep-specific) parameter
+/// * `kind` - Type of the parameter
+/// * `description` - Short description of the parameter
+pub const fn sweep_parameter(path: &'static str, kind: ParameterKind, description: &'static str) -> ParameterSchema {
+    ParameterSchema { required: true, ..parameter(path, kind, description) }
+}
+
+/// Number of simulations of a sweep, required by every sweep
+pub const NUM_SIMULATIONS: ParameterSchema = ParameterSchema {
+    min: Some(1.0),
+    ..sweep_parameter("simulation_config.num_simulations", ParameterKind::Integer, "Number of simulations (parameter values) of the sweep")
+};
+
+// ------------------------------------------------------------------------------------------------
+// Validation
+// ------------------------------------------------------------------------------------------------
+
+impl ConfigSchema {
+    /// Iterates over all declared parameters
+    pub fn parameters(&self) -> impl Iterator<Item = &'static ParameterSchema> {
+        self.sections.iter().flat_map(|section| section.iter())
+    }
+
+    /// Reads the configuration file and checks it against the schema
+    pub fn validate_file(&self) -> Result<SchemaReport, String> {
+        let content = fs::read_to_string(self.config_path).map_err(|e| format!("Failed to read {}: {}", self.config_path, e))?;
+        let value: toml::Value = toml::from_str(&content).map_err(|e| format!("Failed to parse {}: {}", self.config_path, e))?;
+        Ok(self.validate(&value))
+    }
+
+    /// Checks a parsed configuration against the schema
+    pub fn validate(&self, config: &toml::Value) -> SchemaReport {
+        let mut report = SchemaReport::default();
+
+        for parameter in self.parameters() {
+            let (parent, _) = parameter.path.rsplit_once('.').unwrap_or(("", parameter.path));
+            match lookup(config, parameter.path) {
+                Some(value) => check_value(parameter, value, &mut report.errors),
+                // Parameters of optional tables are only required once the table is present
+                None if parameter.required && (parent.is_empty() || lookup(config, parent).is_some()) => {
+                    report.errors.push(format!("{} is required", parameter.path));
+                }
+                None => {}
+            }
+        }
+
+        self.find_undeclared(config, "", &mut report.warnings);
+        report
+    }
+
+    /// Collects the keys that are not declared, descending only into tables with declared fields
+    fn find_undeclared(&self, value: &toml::Value, prefix: &str, warnings: &mut Vec<String>) {
+        let Some(table) = value.as_table() else { return };
+        for (key, value) in table {
+            let path = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+            let has_fields = self.parameters().any(|parameter| parameter.path.starts_with(&format!("{}.", path)));
+            if has_fields {
+                self.find_undeclared(value, &path, warnings);
+            } else if !self.parameters().any(|parameter| parameter.path == path) {
+                warnings.push(format!("{} is not a known parameter and is ignored", path));
+            }
+        }
+    }
+
+    /// Renders the declared parameters as a Markdown table
+    pub fn render_markdown(&self, name: &str) -> String {
+        let mut markdown = String::new();
+        writeln!(markdown, "## {}\n\nConfiguration file: `{}`\n", name, self.config_path).unwrap();
+        writeln!(markdown, "| Parameter | Type | Required | Default | Range | Description |").unwrap();
+        writeln!(markdown, "|---|---|---|---|---|---|").unwrap();
+        for parameter in self.parameters() {
+            let range = match (parameter.min, parameter.max) {
+                (Some(min), Some(max)) => format!("{} to {}", min, max),
+                (Some(min), None) => format!(">= {}", min),
+                (None, Some(max)) => format!("<= {}", max),
+                (None, None) => String::new(),
+            };
+            writeln!(markdown, "| `{}` | {} | {} | {} | {} | {} |", parameter.path, kind_name(parameter.kind),
+                if parameter.required { "yes" } else { "no" }, parameter.default.map_or(String::new(), |d| format!("`{}`", d)),
+                range, parameter.description).unwrap();
+        }
+        markdown
+    }
+}
+
+/// Looks up the value at a dotted path
+fn lookup<'a>(config: &'a toml::Value, path: &str) -> Option<&'a toml::Value> {
+    path.split('.').try_fold(config, |value, key| value.get(key))
+}
+
+/// Checks the type and range of a value
+fn check_value(parameter: &ParameterSchema, value: &toml::Value, errors: &mut Vec<String>) {
+    let check_range = |number: f64, errors: &mut Vec<String>| {
+        if parameter.min.is_some_and(|min| number < min) || parameter.max.is_some_and(|max| number > max) {
+            errors.push(format!("{} = {} is outside of the allowed range ({} to {})", parameter.path, number,
+                parameter.min.map_or("-inf".to_string(), |min| min.to_string()), parameter.max.map_or("inf".to_string(), |max| max.to_string())));
+        }
+    };
+    let type_error = || format!("{} must be {}, found {}", parameter.path, kind_name(parameter.kind), value.type_str());
+
+    match (parameter.kind, value) {
+        (ParameterKind::Float, toml::Value::Float(number)) => check_range(*number, errors),
+        (ParameterKind::Float | ParameterKind::Integer, toml::Value::Integer(number)) => check_range(*number as f64, errors),
+        (ParameterKind::Bool, toml::Value::Boolean(_)) => {}
+        (ParameterKind::String, toml::Value::String(_)) => {}
+        (ParameterKind::Choice(choices), toml::Value::String(choice)) => {
+            if !choices.contains(&choice.as_str()) {
+                errors.push(format!("{} = \"{}\" must be one of {}", parameter.path, choice, choices.join(", ")));
+            }
+        }
+        (ParameterKind::FloatList, toml::Value::Array(elements)) => {
+            for element in elements {
+                match element {
+                    toml::Value::Float(number) => check_range(*number, errors),
+                    toml::Value::Integer(number) => check_range(*number as f64, errors),
+                    _ => errors.push(format!("{} must only contain numbers, found {}", parameter.path, element.type_str())),
+                }
+            }
+        }
+        (ParameterKind::BoolList, toml::Value::Array(elements)) => {
+            if let Some(element) = elements.iter().find(|element| !element.is_bool()) {
+                errors.push(format!("{} must only contain booleans, found {}", parameter.path, element.type_str()));
+            }
+        }
+        (ParameterKind::List, toml::Value::Array(_)) => {}
+        (ParameterKind::Table, toml::Value::Table(_)) => {}
+        _ => errors.push(type_error()),
+    }
+}
+
+/// Name of a parameter type for messages and documentation
+fn kind_name(kind: ParameterKind) -> String {
+    match kind {
+        ParameterKind::Float => "number".to_string(),
+        ParameterKind::Integer => "integer".to_string(),
+        ParameterKind::Bool => "boolean".to_string(),
+        ParameterKind::String => "string".to_string(),
+        ParameterKind::Choice(choices) => format!("one of {}", choices.iter().map(|c| format!("\"{}\"", c)).collect::<Vec<_>>().join(", ")),
+        ParameterKind::FloatList => "list of numbers".to_string(),
+        ParameterKind::BoolList => "list of booleans".to_string(),
+        ParameterKind::List => "list".to_string(),
+        ParameterKind::Table => "table".to_string(),
+    }
+}
@@ -0,0 +1,205 @@
+//! Analytic estimate of the key-conflict rate of a run.
+//!
+//! Every transaction sends from a uniformly chosen account to a Zipf-chosen account and locks
+//! both keys while it is pending. Given the number of accounts, the Zipf parameter, the CAT rate
+//! and the time a CAT keeps its keys locked, the probability that a new transaction finds one of
+//! its keys locked follows analytically. Comparing it to the share of transactions the HIGs
+//! reported as ever blocked flags runs in which the measured rate is far off, which points to
+//! bugs in the lock handling or in the workload generation.
+
+use serde::Serialize;
+use hyperplane::hyper_scheduler::DecisionLatency;
+use crate::simulation_results::SimulationResults;
+
+// ------------------------------------------------------------------------------------------------
+// Constants
+// ------------------------------------------------------------------------------------------------
+
+/// Name of the conflict rate report (inside a run's data directory)
+pub const CONFLICT_RATES_FILE: &str = "conflict_rates.json";
+
+/// Factor by which the measured conflict rate may differ from the expected one before it is flagged
+pub const CONFLICT_RATE_TOLERANCE_FACTOR: f64 = 2.0;
+
+/// Absolute difference of the rates below which a discrepancy is never flagged
+///
+/// Keeps runs with very few conflicts from being flagged for a handful of blocked transactions.
+pub const CONFLICT_RATE_MIN_DIFFERENCE: f64 = 0.05;
+
+// ------------------------------------------------------------------------------------------------
+// Data Structures
+// ------------------------------------------------------------------------------------------------
+
+/// Expected and measured conflict rate of one chain
+#[derive(Debug, Clone, Serialize)]
+pub struct ChainConflictRate {
+    pub chain: String,
+    /// Estimated number of blocks a CAT keeps its keys locked
+    pub lock_blocks: f64,
+    /// Estimated number of CATs pending on the chain at any time
+    pub pending_cats: f64,
+    /// Probability that a new transaction finds one of its keys locked
+    pub expected_rate: f64,
+    /// Share of the chain's transactions that were ever blocked (None if the chain saw no transactions)
+    pub measured_rate: Option<f64>,
+    pub measured_cat_rate: Option<f64>,
+    pub measured_regular_rate: Option<f64>,
+    /// Whether the measured rate is too far from the expected one
+    pub discrepancy: bool,
+}
+
+/// Expected and measured conflict rates of all chains of a run
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ConflictRateReport {
+    pub chains: Vec<ChainConflictRate>,
+}
+
+impl ConflictRateReport {
+    /// Estimates the conflict rates of both chains from the parameters of a run and compares
+    /// them to the blocked transactions the HIGs reported.
+    pub fn from_results(results: &SimulationResults) -> Self {
+        let breakdowns = [&results.chain_1_status_breakdown, &results.chain_2_status_breakdown];
+        let cats_per_block = results.target_tpb as f64 * mean_ratio_cats(results);
+        let max_chain_delay = results.chain_delays.iter().copied().fold(0.0, f64::max);
+
+        let chains = results.network.chain_ids().iter().zip(breakdowns).enumerate()
+            .map(|(index, (chain, breakdown))| {
+                let status_update_delay = results.hs_status_update_delays.get(index).copied().unwrap_or(0.0);
+                let lock_blocks = expected_lock_blocks(
+                    results.cat_lifetime, max_chain_delay, &results.hs_decision_latency, status_update_delay);
+                let pending_cats = cats_per_block * lock_blocks;
+                let expected_rate = expected_conflict_rate(results.num_accounts, results.zipf_parameter, pending_cats);
+                let measured_rate = blocked_share(&[breakdown.cats_ever_blocked, breakdown.regular_ever_blocked],
+                    &[breakdown.cats_never_blocked, breakdown.regular_never_blocked]);
+                ChainConflictRate {
+                    chain: chain.0.clone(),
+                    lock_blocks,
+                    pending_cats,
+                    expected_rate,
+                    measured_rate,
+                    measured_cat_rate: blocked_share(&[breakdown.cats_ever_blocked], &[breakdown.cats_never_blocked]),
+                    measured_regular_rate: blocked_share(&[breakdown.regular_ever_blocked], &[breakdown.regular_never_blocked]),
+                    discrepancy: measured_rate.map_or(false, |measured| is_discrepancy(expected_rate, measured)),
+                }
+            })
+            .collect();
+        Self { chains }
+    }
+
+    /// Returns true if the measured rate of any chain is too far from the expected one
+    pub fn has_discrepancies(&self) -> bool {
+        self.chains.iter().any(|chain| chain.discrepancy)
+    }
+
+    /// Human-readable summary of the report, one line per entry
+    pub fn summary_lines(&self) -> Vec<String> {
+        let percent = |rate: Option<f64>| rate.map_or("n/a".to_string(), |rate| format!("{:.1}%", rate * 100.0));
+        let mut lines = Vec::new();
+        for chain in &self.chains {
+            lines.push(format!(
+                "{}: expected {:.1}%, measured {} (CATs {}, regular {}) with {:.1} pending CATs locking for {:.1} blocks{}",
+                chain.chain,
+                chain.expected_rate * 100.0,
+                percent(chain.measured_rate),
+                percent(chain.measured_cat_rate),
+                percent(chain.measured_regular_rate),
+                chain.pending_cats,
+                chain.lock_blocks,
+                if chain.discrepancy { " - DISCREPANCY, possible bug" } else { "" },
+            ));
+        }
+        lines
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Estimation
+// ------------------------------------------------------------------------------------------------
+
+/// Estimates how long a CAT keeps its keys locked on a chain.
+///
+/// A CAT is pending on a chain from the moment the chain sees it until the chain sees its status
+/// update. The status can only be decided once the slowest chain proposed, the HS then takes its
+/// decision latency, and the update is included one block later and delayed by the chain's
+/// status update delay. The chain's own delay shifts both ends alike. A CAT never stays pending
+/// longer than its lifetime.
+///
+/// # Arguments
+/// * `cat_lifetime` - CAT lifetime in blocks
+/// * `max_chain_delay` - Largest chain delay in blocks
+/// * `hs_decision_latency` - HS decision latency in blocks
+/// * `status_update_delay` - Delay of the HS status updates to the chain in blocks
+pub fn expected_lock_blocks(cat_lifetime: u64, max_chain_delay: f64, hs_decision_latency: &DecisionLatency, status_update_delay: f64) -> f64 {
+    let resolution_blocks = max_chain_delay + mean_decision_latency(hs_decision_latency) + status_update_delay + 1.0;
+    resolution_blocks.min(cat_lifetime as f64)
+}
+
+/// Computes the probability that a new transaction finds at least one of its keys locked.
+///
+/// Senders are chosen uniformly and receivers by a Zipf distribution over the accounts 1..=N, as
+/// in the simulator's workload. Each of the pending CATs locks its sender and receiver, so an
+/// account k is locked with probability 1 - (1 - q(k))^L, where q(k) is the probability that a
+/// single transaction touches k and L the number of pending CATs. Transactions blocked behind a
+/// CAT hold further locks while they wait, which this first-order estimate ignores, so measured
+/// rates above the estimate are expected under heavy contention.
+///
+/// # Arguments
+/// * `num_accounts` - Number of accounts
+/// * `zipf_parameter` - Zipf parameter of the receiver selection (0 = uniform)
+/// * `pending_cats` - Number of CATs pending at any time
+pub fn expected_conflict_rate(num_accounts: usize, zipf_parameter: f64, pending_cats: f64) -> f64 {
+    if num_accounts == 0 || pending_cats <= 0.0 {
+        return 0.0;
+    }
+
+    let sender_probability = 1.0 / num_accounts as f64;
+    let weights: Vec<f64> = (1..=num_accounts).map(|k| (k as f64).powf(-zipf_parameter)).collect();
+    let total_weight: f64 = weights.iter().sum();
+
+    // Probability that a new transaction's sender and receiver are both unlocked
+    let (mut sender_free, mut receiver_free) = (0.0, 0.0);
+    for weight in &weights {
+        let receiver_probability = weight / total_weight;
+        let touched = 1.0 - (1.0 - sender_probability) * (1.0 - receiver_probability);
+        let unlocked = (1.0 - touched).powf(pending_cats);
+        sender_free += sender_probability * unlocked;
+        receiver_free += receiver_probability * unlocked;
+    }
+    (1.0 - sender_free * receiver_free).clamp(0.0, 1.0)
+}
+
+/// Returns true if the measured rate differs from the expected one by more than the tolerance,
+/// both relative and absolute
+pub fn is_discrepancy(expected: f64, measured: f64) -> bool {
+    if (measured - expected).abs() < CONFLICT_RATE_MIN_DIFFERENCE {
+        return false;
+    }
+    measured > expected * CONFLICT_RATE_TOLERANCE_FACTOR || measured < expected / CONFLICT_RATE_TOLERANCE_FACTOR
+}
+
+/// Mean of the HS decision latency distribution in blocks
+fn mean_decision_latency(latency: &DecisionLatency) -> f64 {
+    match *latency {
+        DecisionLatency::Zero => 0.0,
+        DecisionLatency::Constant { value } => value,
+        DecisionLatency::Uniform { min, max } => if max > min { (min + max) / 2.0 } else { min },
+        DecisionLatency::Exponential { mean } => mean.max(0.0),
+    }
+}
+
+/// Mean CAT ratio of the generated transactions, as every chain initiates equally many of them
+fn mean_ratio_cats(results: &SimulationResults) -> f64 {
+    if results.chain_ratio_cats.is_empty() {
+        results.ratio_cats
+    } else {
+        results.chain_ratio_cats.iter().sum::<f64>() / results.chain_ratio_cats.len() as f64
+    }
+}
+
+/// Share of the (pending, success, failure) counts in `blocked` among all counts (None if there are none)
+fn blocked_share(blocked: &[(u64, u64, u64)], unblocked: &[(u64, u64, u64)]) -> Option<f64> {
+    let total = |counts: &[(u64, u64, u64)]| counts.iter().map(|(pending, success, failure)| pending + success + failure).sum::<u64>();
+    let (blocked, unblocked) = (total(blocked), total(unblocked));
+    (blocked + unblocked > 0).then(|| blocked as f64 / (blocked + unblocked) as f64)
+}
+
@@ -0,0 +1,207 @@
+//! Distribution of the time the HS takes to resolve CATs.
+//!
+//! Measures, per CAT, the time from the inclusion of its CL transaction to the submission of its
+//! status update by the HS, split at the HS decision: the time until the decision is spent waiting
+//! for the proposals of the constituent chains (chain delays and HIG processing), the time after
+//! it is the scheduler's own overhead (its decision latency). Both parts are broken down by the
+//! fanout of the CATs, since CATs spanning more chains wait for more proposals.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use hyperplane::{
+    types::{CATId, ChainId, Transaction},
+    confirmation_layer::ConfirmationLayerNode,
+    hyper_scheduler::node::HyperSchedulerNode,
+};
+use crate::stats::{CatTimeHistogram, mean_duration_ms, percentile_ms};
+use crate::workload::RecordedSubmission;
+
+// ------------------------------------------------------------------------------------------------
+// Constants
+// ------------------------------------------------------------------------------------------------
+
+/// Name of the HS decision latency report (inside a run's data directory)
+pub const HS_DECISION_LATENCY_FILE: &str = "hs_decision_latency.json";
+
+// ------------------------------------------------------------------------------------------------
+// Data Structures
+// ------------------------------------------------------------------------------------------------
+
+/// Time the HS took to resolve one CAT
+#[derive(Debug, Clone)]
+pub struct CatDecisionLatency {
+    pub cat_id: CATId,
+    /// Number of constituent chains of the CAT
+    pub fanout: usize,
+    /// Time from the inclusion of the CAT's last part to the HS decision
+    pub inclusion_to_decision: Duration,
+    /// Time from the HS decision to the submission of the status update
+    pub decision_to_submission: Duration,
+}
+
+/// Time the HS took to resolve the CATs of a run
+#[derive(Debug, Clone, Default)]
+pub struct HsDecisionLatencyReport {
+    pub cats: Vec<CatDecisionLatency>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl CatDecisionLatency {
+    /// Time from the inclusion of the CAT's last part to the submission of the status update
+    pub fn total(&self) -> Duration {
+        self.inclusion_to_decision + self.decision_to_submission
+    }
+}
+
+impl HsDecisionLatencyReport {
+    /// Groups the CATs by fanout, in ascending order
+    pub fn by_fanout(&self) -> BTreeMap<usize, Vec<&CatDecisionLatency>> {
+        let mut groups: BTreeMap<usize, Vec<&CatDecisionLatency>> = BTreeMap::new();
+        for cat in &self.cats {
+            groups.entry(cat.fanout).or_default().push(cat);
+        }
+        groups
+    }
+
+    /// Mean time from the inclusion of the CATs to the HS decision (milliseconds)
+    pub fn mean_inclusion_to_decision_ms(&self) -> f64 {
+        mean_duration_ms(&self.cats.iter().map(|cat| cat.inclusion_to_decision).collect::<Vec<_>>())
+    }
+
+    /// Mean time from the HS decision to the submission of the status update (milliseconds)
+    pub fn mean_decision_to_submission_ms(&self) -> f64 {
+        mean_duration_ms(&self.cats.iter().map(|cat| cat.decision_to_submission).collect::<Vec<_>>())
+    }
+
+    /// Returns the distributions of all CATs and of each fanout as JSON (milliseconds)
+    pub fn to_json(&self) -> serde_json::Value {
+        let all: Vec<&CatDecisionLatency> = self.cats.iter().collect();
+        serde_json::json!({
+            "all": distributions_to_json(&all),
+            "by_fanout": self.by_fanout().into_iter().map(|(fanout, cats)| {
+                let mut entry = distributions_to_json(&cats);
+                entry["fanout"] = serde_json::json!(fanout);
+                entry
+            }).collect::<Vec<_>>(),
+            "cats": self.cats.iter().map(|cat| serde_json::json!({
+                "cat_id": cat.cat_id.0.0,
+                "fanout": cat.fanout,
+                "inclusion_to_decision_ms": cat.inclusion_to_decision.as_secs_f64() * 1000.0,
+                "decision_to_submission_ms": cat.decision_to_submission.as_secs_f64() * 1000.0,
+                "total_ms": cat.total().as_secs_f64() * 1000.0
+            })).collect::<Vec<_>>()
+        })
+    }
+
+    /// Human-readable summary of the report, one line per fanout
+    pub fn summary_lines(&self) -> Vec<String> {
+        let mut lines = vec![format!(
+            "All {} CATs: {:.1} ms waiting for proposals, {:.1} ms scheduler overhead (mean)",
+            self.cats.len(), self.mean_inclusion_to_decision_ms(), self.mean_decision_to_submission_ms(),
+        )];
+        for (fanout, cats) in self.by_fanout() {
+            let waits: Vec<Duration> = cats.iter().map(|cat| cat.inclusion_to_decision).collect();
+            let overheads: Vec<Duration> = cats.iter().map(|cat| cat.decision_to_submission).collect();
+            lines.push(format!(
+                "  fanout {}: {} CATs, {:.1} ms waiting for proposals, {:.1} ms scheduler overhead (mean), p99 total {:.1} ms",
+                fanout, cats.len(), mean_duration_ms(&waits), mean_duration_ms(&overheads),
+                percentile_ms(&cats.iter().map(|cat| cat.total()).collect::<Vec<_>>(), 99.0),
+            ));
+        }
+        lines
+    }
+}
+
+/// Summarizes the three durations of a group of CATs as JSON (milliseconds)
+fn distributions_to_json(cats: &[&CatDecisionLatency]) -> serde_json::Value {
+    let waits: Vec<Duration> = cats.iter().map(|cat| cat.inclusion_to_decision).collect();
+    let overheads: Vec<Duration> = cats.iter().map(|cat| cat.decision_to_submission).collect();
+    let totals: Vec<Duration> = cats.iter().map(|cat| cat.total()).collect();
+    serde_json::json!({
+        "count": cats.len(),
+        "inclusion_to_decision": distribution_to_json(&waits),
+        "decision_to_submission": distribution_to_json(&overheads),
+        "total": distribution_to_json(&totals)
+    })
+}
+
+/// Summarizes a list of durations with its mean, percentiles and histogram as JSON (milliseconds)
+fn distribution_to_json(durations: &[Duration]) -> serde_json::Value {
+    serde_json::json!({
+        "mean_ms": mean_duration_ms(durations),
+        "p50_ms": percentile_ms(durations, 50.0),
+        "p90_ms": percentile_ms(durations, 90.0),
+        "p99_ms": percentile_ms(durations, 99.0),
+        "max_ms": percentile_ms(durations, 100.0),
+        "histogram": CatTimeHistogram::from_durations(durations).to_json()
+    })
+}
+
+// ------------------------------------------------------------------------------------------------
+// Collection
+// ------------------------------------------------------------------------------------------------
+
+/// Collects the time the HS took to resolve each CAT of a run
+///
+/// CATs whose parts were not all included or which the HS did not send a status update for are
+/// skipped. A CAT that failed before its last part was included counts no time waiting for
+/// proposals. Must be called before the nodes are shut down, as shutting down clears the
+/// recorded times.
+///
+/// # Arguments
+/// * `cl_node` - The CL node
+/// * `hs_node` - The HS node
+/// * `workload` - The submissions of the run
+pub async fn collect_hs_decision_latencies(
+    cl_node: &Arc<Mutex<ConfirmationLayerNode>>,
+    hs_node: &Arc<Mutex<HyperSchedulerNode>>,
+    workload: &[RecordedSubmission],
+) -> HsDecisionLatencyReport {
+    // Group the sub-transactions by CAT (the parts of a spread CAT share its id)
+    let mut cats: BTreeMap<String, Vec<&Transaction>> = BTreeMap::new();
+    for submission in workload.iter().filter(|submission| submission.is_cat) {
+        for cl_tx in &submission.cl_transactions {
+            cats.entry(cl_tx.id.0.clone()).or_default().extend(cl_tx.transactions.iter());
+        }
+    }
+
+    let cl_clock = cl_node.lock().await.get_clock();
+    let mut report = HsDecisionLatencyReport::default();
+    for transactions in cats.into_values() {
+        let Some(first) = transactions.first() else { continue };
+        let cat_id = CATId(first.cl_id.clone());
+
+        // The CAT is complete once its last part is included
+        let mut included_at: Option<Duration> = None;
+        let mut all_included = true;
+        for tx in &transactions {
+            match cl_node.lock().await.get_inclusion_time(&tx.id).await {
+                Some(inclusion) => included_at = Some(included_at.map_or(inclusion.included_at, |at| at.max(inclusion.included_at))),
+                None => all_included = false,
+            }
+        }
+        let Some(included_at) = included_at.filter(|_| all_included) else { continue };
+        // The CL records clock times, the HS records instants
+        let included_at = cl_clock.instant_at(included_at);
+
+        let (decided_at, submitted_at) = {
+            let hs = hs_node.lock().await;
+            (hs.get_cat_decision_time(&cat_id).await, hs.get_status_update_submission_time(&cat_id).await)
+        };
+        let (Some(decided_at), Some(submitted_at)) = (decided_at, submitted_at) else { continue };
+
+        let chains: BTreeSet<&ChainId> = transactions.iter().map(|tx| &tx.chain_id).collect();
+        report.cats.push(CatDecisionLatency {
+            cat_id,
+            fanout: chains.len(),
+            inclusion_to_decision: decided_at.saturating_duration_since(included_at),
+            decision_to_submission: submitted_at.saturating_duration_since(decided_at),
+        });
+    }
+    report
+}
@@ -0,0 +1,212 @@
+//! Post-run state divergence analysis between chains.
+//!
+//! Compares the final status of every CAT on its constituent chains, together with the
+//! balances of the accounts it touched, and reports CATs that were applied on one chain but not another.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use serde::Serialize;
+use hyperplane::{
+    types::{CATId, ChainId, TransactionId, TransactionStatus},
+    hyper_ig::node::HyperIGNode,
+    hyper_ig::HyperIG,
+};
+
+// ------------------------------------------------------------------------------------------------
+// Data Structures
+// ------------------------------------------------------------------------------------------------
+
+/// A CAT submitted during the simulation
+#[derive(Debug, Clone)]
+pub struct SubmittedCat {
+    /// The id of the CAT
+    pub cat_id: CATId,
+    /// The sub-transaction of the CAT on each constituent chain
+    pub sub_transactions: Vec<(ChainId, TransactionId)>,
+    /// The accounts touched by the CAT
+    pub accounts: Vec<String>,
+}
+
+/// The balances of an account that differ between the constituent chains of a CAT
+#[derive(Debug, Clone, Serialize)]
+pub struct AccountDivergence {
+    pub account: String,
+    /// Balance per chain (missing if the account does not exist on that chain)
+    pub balances: BTreeMap<String, Option<i64>>,
+}
+
+/// A CAT that succeeded on some of its constituent chains and failed on others
+#[derive(Debug, Clone, Serialize)]
+pub struct CatDivergence {
+    pub cat_id: String,
+    /// Final status of the CAT per chain
+    pub statuses: BTreeMap<String, String>,
+    /// Touched accounts whose balances differ between the constituent chains
+    pub accounts: Vec<AccountDivergence>,
+}
+
+/// Result of the post-run state divergence analysis
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct StateDivergenceReport {
+    /// Number of CATs that were checked
+    pub cats_checked: u64,
+    /// Number of CATs that were still pending (or unknown) on at least one chain at the end of the run
+    pub cats_unresolved: u64,
+    /// Number of accounts touched by CATs whose balances differ between chains
+    pub divergent_accounts: u64,
+    /// CATs with asymmetric final statuses
+    pub divergent_cats: Vec<CatDivergence>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl StateDivergenceReport {
+    /// Returns true if no CAT was applied asymmetrically
+    pub fn is_consistent(&self) -> bool {
+        self.divergent_cats.is_empty()
+    }
+
+    /// Formats the report as human-readable lines for the results summary
+    pub fn summary_lines(&self) -> Vec<String> {
+        let mut lines = vec![
+            format!("CATs checked: {}", self.cats_checked),
+            format!("CATs unresolved at end of run: {}", self.cats_unresolved),
+            format!("Accounts with diverging balances: {}", self.divergent_accounts),
+            format!("Divergent CATs: {}", self.divergent_cats.len()),
+        ];
+        for divergence in &self.divergent_cats {
+            let statuses = divergence.statuses.iter()
+                .map(|(chain, status)| format!("{}={}", chain, status))
+                .collect::<Vec<_>>()
+                .join(", ");
+            lines.push(format!("  CAT {}: {}", divergence.cat_id, statuses));
+            for account in &divergence.accounts {
+                let balances = account.balances.iter()
+                    .map(|(chain, balance)| match balance {
+                        Some(balance) => format!("{}={}", chain, balance),
+                        None => format!("{}=missing", chain),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                lines.push(format!("    account {}: {}", account.account, balances));
+            }
+        }
+        lines
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Analysis
+// ------------------------------------------------------------------------------------------------
+
+/// Compares the final CAT statuses and balances across chains
+///
+/// A CAT diverges if it is final on all of its constituent chains, succeeded on at least one
+/// and did not succeed on another. CATs that are still pending on some chain are counted as unresolved.
+///
+/// # Arguments
+/// * `hig_nodes` - The HIG nodes, in the same order as `chains`
+/// * `chains` - The registered chains
+/// * `submitted_cats` - The CATs submitted during the run
+///
+/// # Returns
+/// The state divergence report
+pub async fn analyze_state_divergence(
+    hig_nodes: &[Arc<Mutex<HyperIGNode>>],
+    chains: &[ChainId],
+    submitted_cats: &[SubmittedCat],
+) -> Result<StateDivergenceReport, String> {
+    // Collect the final state of every chain
+    let mut chain_states: HashMap<ChainId, HashMap<String, i64>> = HashMap::new();
+    for (chain_id, hig_node) in chains.iter().zip(hig_nodes) {
+        let state = hig_node.lock().await.get_chain_state().await.map_err(|e| e.to_string())?;
+        chain_states.insert(chain_id.clone(), state);
+    }
+
+    let mut report = StateDivergenceReport::default();
+
+    // Accounts touched by CATs, with the chains they were touched on
+    let mut touched_accounts: HashMap<String, Vec<ChainId>> = HashMap::new();
+
+    for cat in submitted_cats {
+        report.cats_checked += 1;
+
+        let mut statuses = BTreeMap::new();
+        let mut any_success = false;
+        let mut any_not_success = false;
+        let mut unresolved = false;
+        for (chain_id, tx_id) in &cat.sub_transactions {
+            let Some(index) = chains.iter().position(|c| c == chain_id) else {
+                unresolved = true;
+                continue;
+            };
+            // The HIG may not have seen the transaction yet if it is still in the CL queue
+            let status = hig_nodes[index].lock().await.get_transaction_status(tx_id.clone()).await.ok();
+            match status {
+                Some(TransactionStatus::Success) => any_success = true,
+                Some(ref s) if s.is_final() => any_not_success = true,
+                _ => unresolved = true,
+            }
+            statuses.insert(chain_id.0.clone(), status.map_or("Unknown".to_string(), |s| format!("{:?}", s)));
+        }
+
+        let cat_chains: Vec<ChainId> = cat.sub_transactions.iter().map(|(chain_id, _)| chain_id.clone()).collect();
+        for account in &cat.accounts {
+            let entry = touched_accounts.entry(account.clone()).or_default();
+            for chain_id in &cat_chains {
+                if !entry.contains(chain_id) {
+                    entry.push(chain_id.clone());
+                }
+            }
+        }
+
+        if unresolved {
+            report.cats_unresolved += 1;
+            continue;
+        }
+        if !(any_success && any_not_success) {
+            continue;
+        }
+
+        let accounts = cat.accounts.iter()
+            .filter_map(|account| diverging_balances(&chain_states, &cat_chains, account))
+            .collect();
+        report.divergent_cats.push(CatDivergence {
+            cat_id: cat.cat_id.0.0.clone(),
+            statuses,
+            accounts,
+        });
+    }
+
+    report.divergent_accounts = touched_accounts.iter()
+        .filter(|(account, account_chains)| diverging_balances(&chain_states, account_chains, account).is_some())
+        .count() as u64;
+
+    Ok(report)
+}
+
+/// Returns the balances of an account on the given chains if they are not all equal
+fn diverging_balances(
+    chain_states: &HashMap<ChainId, HashMap<String, i64>>,
+    chains: &[ChainId],
+    account: &str,
+) -> Option<AccountDivergence> {
+    let balances: BTreeMap<String, Option<i64>> = chains.iter()
+        .map(|chain_id| {
+            let balance = chain_states.get(chain_id).and_then(|state| state.get(account).copied());
+            (chain_id.0.clone(), balance)
+        })
+        .collect();
+    let mut values = balances.values();
+    let first = values.next()?;
+    if values.all(|balance| balance == first) {
+        return None;
+    }
+    Some(AccountDivergence {
+        account: account.to_string(),
+        balances,
+    })
+}
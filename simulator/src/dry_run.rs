@@ -0,0 +1,148 @@
+//! Dry runs of simulations.
+//!
+//! A dry run loads and validates the configuration of a simulation, prints what it would simulate
+//! (simulation points, runs, blocks and transactions) with an estimate of its wall-clock time and
+//! exits without starting any node, so a typo in a sweep range is caught before it costs hours.
+//! Sweeps run their `run_fn` as usual: `SweepRunner::run` hands the configurations of its points to
+//! the dry run in scope and returns before creating any results.
+
+use std::cell::RefCell;
+use std::fmt;
+use std::time::Duration;
+
+use crate::config::Config;
+use crate::interface::SimulationType;
+
+// ------------------------------------------------------------------------------------------------
+// Data Structures
+// ------------------------------------------------------------------------------------------------
+
+tokio::task_local! {
+    /// Configurations of the simulation points recorded by the dry run in this scope
+    static DRY_RUN_CONFIGS: RefCell<Vec<Config>>;
+}
+
+/// What a simulation would run, derived from its configuration
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DryRunEstimate {
+    /// Number of simulation points (1 unless the simulation is a sweep)
+    pub simulations: usize,
+    /// Number of runs over all simulation points
+    pub runs: u64,
+    /// Blocks produced over all runs, including the initialization wait
+    pub blocks: u64,
+    /// Transactions submitted over all runs
+    pub transactions: u64,
+    /// CATs among the submitted transactions (expected value)
+    pub cats: u64,
+    /// Wall-clock time of all runs
+    pub wall_clock: Duration,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl DryRunEstimate {
+    /// Estimates the cost of simulating the given configurations
+    ///
+    /// The simulator runs in real time, so every block takes its configured block interval. The
+    /// initial block interval is used for runs with a block interval schedule.
+    pub fn from_configs(configs: &[Config]) -> Self {
+        configs.iter().fold(Self::default(), |mut estimate, config| {
+            let runs = u64::from(config.simulation_config.num_runs);
+            let blocks_per_run = config.simulation_config.initialization_wait_blocks + config.simulation_config.sim_total_block_number;
+            let transactions_per_run = config.transaction_config.target_tpb as u64 * config.simulation_config.sim_total_block_number;
+            estimate.simulations += 1;
+            estimate.runs += runs;
+            estimate.blocks += runs * blocks_per_run;
+            estimate.transactions += runs * transactions_per_run;
+            estimate.cats += (runs as f64 * transactions_per_run as f64 * config.transaction_config.ratio_cats).round() as u64;
+            estimate.wall_clock += Duration::from_secs_f64(config.network_config.block_interval * (runs * blocks_per_run) as f64);
+            estimate
+        })
+    }
+}
+
+impl fmt::Display for DryRunEstimate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let seconds = self.wall_clock.as_secs();
+        writeln!(f, "Simulation points: {}", self.simulations)?;
+        writeln!(f, "Runs: {}", self.runs)?;
+        writeln!(f, "Expected blocks: {}", self.blocks)?;
+        writeln!(f, "Transactions: {} ({} CATs)", self.transactions, self.cats)?;
+        write!(f, "Estimated wall-clock time (real time): {}h {:02}m {:02}s", seconds / 3600, seconds / 60 % 60, seconds % 60)
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Dry Run
+// ------------------------------------------------------------------------------------------------
+
+/// Whether a dry run is in scope, in which case sweeps only record their configurations
+pub fn is_active() -> bool {
+    DRY_RUN_CONFIGS.try_with(|_| ()).is_ok()
+}
+
+/// Records the configurations of the simulation points of a sweep for the dry run in scope
+pub fn record(configs: Vec<Config>) {
+    let _ = DRY_RUN_CONFIGS.try_with(|recorded| recorded.borrow_mut().extend(configs));
+}
+
+/// Validates the configuration of a simulation and estimates what it would run, without running it
+///
+/// # Arguments
+/// * `simulation_type` - The simulation to check
+///
+/// # Returns
+/// The schema warnings of the configuration and the estimate, or `None` for simulations whose
+/// configuration does not describe a workload (replay and compare)
+pub async fn dry_run(simulation_type: &SimulationType) -> Result<(Vec<String>, Option<DryRunEstimate>), String> {
+    let registry = crate::simulation_registry::get_registry().await;
+    let (warnings, config_path, run_future) = {
+        let registry_guard = registry.lock().await;
+        let simulation = registry_guard.get(simulation_type)
+            .ok_or_else(|| format!("Unknown simulation type: {:?}", simulation_type))?;
+        let warnings = registry_guard.validate_config(simulation_type)
+            .map_err(|e| format!("Invalid configuration: {}", e))?;
+        let config_path = simulation.config_schema.map(|schema| schema.config_path);
+        let run_future = simulation_type.is_sweep().then(|| (simulation.run_fn)());
+        (warnings, config_path, run_future)
+    };
+
+    if let Some(run_future) = run_future {
+        let configs = DRY_RUN_CONFIGS.scope(RefCell::new(Vec::new()), async {
+            run_future.await?;
+            Ok::<_, String>(DRY_RUN_CONFIGS.with(|recorded| recorded.take()))
+        }).await?;
+        return Ok((warnings, Some(DryRunEstimate::from_configs(&configs))));
+    }
+
+    let Some(config_path) = config_path else {
+        return Ok((warnings, None));
+    };
+    let content = std::fs::read_to_string(config_path).map_err(|e| format!("Failed to read {}: {}", config_path, e))?;
+    // Replay and compare read their own formats, which are covered by the schema check only
+    let Ok(config) = toml::from_str::<Config>(&content) else {
+        return Ok((warnings, None));
+    };
+    let validation = match simulation_type {
+        SimulationType::Simple => config.validate_allowing_external_cl(),
+        _ => config.validate(),
+    };
+    validation.map_err(|e| e.to_string())?;
+    Ok((warnings, Some(DryRunEstimate::from_configs(&[config]))))
+}
+
+/// Runs a dry run of a simulation and prints the warnings and the estimate
+pub async fn print_dry_run(simulation_type: &SimulationType) -> Result<(), String> {
+    let (warnings, estimate) = dry_run(simulation_type).await?;
+    for warning in warnings {
+        println!("Warning: {}", warning);
+    }
+    match estimate {
+        Some(estimate) => println!("Configuration is valid\n{}", estimate),
+        None => println!("Configuration is valid (no estimate, the configuration does not describe a workload)"),
+    }
+    Ok(())
+}
@@ -0,0 +1,114 @@
+//! Duplicate injection for the Hyperplane simulator.
+//!
+//! Re-submits a configurable fraction of the generated CL transactions, either as exact copies or
+//! as variants that keep the id but carry a different payload, so the deduplication of the CL and
+//! the HIGs is exercised under load. The duplicates are drawn from their own random generator, so
+//! the generated workload is the same with and without duplicates.
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use tokio::sync::Mutex;
+use hyperplane::{
+    confirmation_layer::{ConfirmationLayer, ConfirmationLayerError},
+    types::CLTransaction,
+    utils::logging,
+};
+
+/// Offset added to the run seed for the random generator of the duplicates
+const DUPLICATE_SEED_OFFSET: u64 = 0x5eed_d0b1;
+
+/// Re-submits a fraction of the generated CL transactions as duplicates
+pub struct DuplicateInjector {
+    /// Fraction of the generated CL transactions that are re-submitted
+    ratio: f64,
+    /// Fraction of the duplicates that keep the id but carry a different payload
+    variant_share: f64,
+    /// Blocks between the submission of a transaction and of its duplicate
+    delay_blocks: u64,
+    /// Random generator deciding which transactions are duplicated
+    rng: StdRng,
+    /// Duplicates waiting for their block (due block offset, whether it is a variant, transaction)
+    pending: VecDeque<(u64, bool, CLTransaction)>,
+    /// Number of duplicates submitted
+    injected: u64,
+    /// Number of the submitted duplicates that carried a different payload
+    variants: u64,
+}
+
+impl DuplicateInjector {
+    /// Creates the injector
+    ///
+    /// # Arguments
+    ///
+    /// * `ratio` - Fraction of the generated CL transactions that are re-submitted
+    /// * `variant_share` - Fraction of the duplicates that carry a different payload
+    /// * `delay_blocks` - Blocks between the submission of a transaction and of its duplicate
+    /// * `run_seed` - Seed of the run
+    pub fn new(ratio: f64, variant_share: f64, delay_blocks: u64, run_seed: u64) -> Self {
+        Self {
+            ratio,
+            variant_share,
+            delay_blocks,
+            rng: StdRng::seed_from_u64(run_seed.wrapping_add(DUPLICATE_SEED_OFFSET)),
+            pending: VecDeque::new(),
+            injected: 0,
+            variants: 0,
+        }
+    }
+
+    /// Schedules duplicates of a fraction of the CL transactions submitted at `block_offset`
+    pub fn schedule(&mut self, cl_transactions: &[CLTransaction], block_offset: u64) {
+        if self.ratio <= 0.0 {
+            return;
+        }
+        for cl_tx in cl_transactions {
+            if !self.rng.gen_bool(self.ratio) {
+                continue;
+            }
+            let is_variant = self.rng.gen_bool(self.variant_share);
+            let duplicate = if is_variant { payload_variant(cl_tx) } else { cl_tx.clone() };
+            self.pending.push_back((block_offset + self.delay_blocks, is_variant, duplicate));
+        }
+    }
+
+    /// Submits the duplicates that are due at or before `block_offset`
+    ///
+    /// A duplicate of a transaction the CL has already included is rejected, which is expected and
+    /// counted by the CL. Any other error of the CL is returned.
+    pub async fn submit_due<C: ConfirmationLayer>(&mut self, cl_node: &Arc<Mutex<C>>, block_offset: u64) -> Result<(), String> {
+        while let Some((_, is_variant, duplicate)) = self.pending.front().filter(|(due, _, _)| *due <= block_offset) {
+            let is_variant = *is_variant;
+            match cl_node.lock().await.submit_transaction(duplicate.clone()).await {
+                Ok(()) | Err(ConfirmationLayerError::TransactionAlreadyProcessed(_)) => {}
+                Err(e) => return Err(format!("Failed to submit duplicate of CL transaction {:?}: {}", duplicate.id, e)),
+            }
+            logging::log("SIMULATOR", &format!("Submitted {} of CL transaction {:?}",
+                if is_variant { "variant" } else { "duplicate" }, duplicate.id));
+            self.injected += 1;
+            if is_variant {
+                self.variants += 1;
+            }
+            self.pending.pop_front();
+        }
+        Ok(())
+    }
+
+    /// Returns the number of submitted duplicates and how many of them were variants
+    pub fn injected(&self) -> (u64, u64) {
+        (self.injected, self.variants)
+    }
+}
+
+/// Returns a copy of the CL transaction with the same ids whose amounts are increased by one
+fn payload_variant(cl_tx: &CLTransaction) -> CLTransaction {
+    let mut variant = cl_tx.clone();
+    for tx in &mut variant.transactions {
+        if let Some((command, amount)) = tx.data.rsplit_once(' ') {
+            if let Ok(amount) = amount.parse::<u32>() {
+                tx.data = format!("{} {}", command, amount.checked_add(1).unwrap_or(0));
+            }
+        }
+    }
+    variant
+}
@@ -0,0 +1,464 @@
+//! Multi-run experiments described in YAML files.
+//!
+//! An experiment lists registered scenarios and custom sweeps, the dependencies between them and
+//! a master seed they share. Entries run in file order, or up to `max_parallel` at a time once
+//! their dependencies succeeded, and the results of all entries are aggregated into one
+//! experiment-level report, so studies spanning several sweeps need no shell scripts around the
+//! simulator binary.
+
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::fs;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+use serde::Deserialize;
+use tokio::sync::{Semaphore, watch};
+use hyperplane::utils::logging;
+use crate::interface::SimulationType;
+use crate::metadata::MASTER_SEED_OVERRIDE;
+use crate::report::ResultsSummary;
+use crate::scenarios::sim_sweep_custom::simulation::{CustomSweep, load_custom_sweep, run_custom_sweep};
+
+// ------------------------------------------------------------------------------------------------
+// Constants
+// ------------------------------------------------------------------------------------------------
+
+/// Directory the experiment reports are written to, one subdirectory per experiment
+pub const EXPERIMENTS_DIR: &str = "simulator/results/experiments";
+
+/// Registered scenarios an experiment can run: name in the experiment file, simulation type and
+/// directory name of the results under `simulator/results`
+const SCENARIOS: &[(&str, SimulationType, &str)] = &[
+    ("simple", SimulationType::Simple, "sim_simple"),
+    ("sweep_block_capacity", SimulationType::SweepBlockCapacity, "sim_sweep_block_capacity"),
+    ("sweep_block_interval_all_scaled", SimulationType::SweepBlockIntervalAllScaled, "sim_sweep_block_interval_all_scaled"),
+    ("sweep_block_interval_constant_block_delay", SimulationType::SweepBlockIntervalConstantBlockDelay, "sim_sweep_block_interval_constant_block_delay"),
+    ("sweep_block_interval_constant_time_delay", SimulationType::SweepBlockIntervalConstantTimeDelay, "sim_sweep_block_interval_constant_time_delay"),
+    ("sweep_cat_lifetime", SimulationType::SweepCatLifetime, "sim_sweep_cat_lifetime"),
+    ("sweep_cat_lifetime_delay_ratio", SimulationType::SweepCatLifetimeDelayRatio, "sim_sweep_cat_lifetime_delay_ratio"),
+    ("sweep_cat_pending_dependencies", SimulationType::SweepCatPendingDependencies, "sim_sweep_cat_pending_dependencies"),
+    ("sweep_mixed_cat_pending_dependencies", SimulationType::SweepMixedCatPendingDependencies, "sim_sweep_mixed_cat_pending_dependencies"),
+    ("sweep_cat_ratio", SimulationType::SweepCatRatio, "sim_sweep_cat_ratio"),
+    ("sweep_chain_delay", SimulationType::SweepChainDelay, "sim_sweep_chain_delay"),
+    ("sweep_tpb_constant_cats_per_block", SimulationType::SweepCatRatioConstantCatsPerBlock, "sim_sweep_tpb_constant_cats_per_block"),
+    ("sweep_total_block_number", SimulationType::SweepTotalBlockNumber, "sim_sweep_total_block_number"),
+    ("sweep_zipf", SimulationType::SweepZipf, "sim_sweep_zipf"),
+    ("replay", SimulationType::Replay, "sim_replay"),
+    ("compare", SimulationType::Compare, "sim_compare"),
+    ("failover", SimulationType::Failover, "sim_failover"),
+    ("block_interval_ramp", SimulationType::BlockIntervalRamp, "sim_block_interval_ramp"),
+    ("balance_exhaustion", SimulationType::BalanceExhaustion, "sim_balance_exhaustion"),
+    ("sweep_hs_chain_count", SimulationType::SweepHsChainCount, "sim_sweep_hs_chain_count"),
+    ("sweep_custom", SimulationType::SweepCustom, "sim_sweep_custom"),
+];
+
+/// The simulation type of a registered scenario, by its name in experiment files
+pub fn scenario_type(name: &str) -> Option<SimulationType> {
+    SCENARIOS.iter().find(|(scenario, _, _)| *scenario == name).map(|(_, simulation_type, _)| simulation_type.clone())
+}
+
+/// Names of the registered scenarios
+pub fn scenario_names() -> Vec<&'static str> {
+    SCENARIOS.iter().map(|(name, _, _)| *name).collect()
+}
+
+// ------------------------------------------------------------------------------------------------
+// Data Structures
+// ------------------------------------------------------------------------------------------------
+
+/// An experiment file
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Experiment {
+    /// Name of the experiment, also the directory name of its report under `EXPERIMENTS_DIR`
+    pub name: String,
+    /// Master seed shared by all entries (each simulation's configured seed is used if unset)
+    #[serde(default)]
+    pub seed: Option<u64>,
+    /// Maximum number of entries run at the same time (1 = one after the other, in file order)
+    #[serde(default = "default_max_parallel")]
+    pub max_parallel: usize,
+    /// The scenarios and sweeps to run
+    pub entries: Vec<ExperimentEntry>,
+}
+
+/// A scenario or sweep of an experiment
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ExperimentEntry {
+    /// Name of the entry, referenced by `depends_on`
+    pub name: String,
+    /// A registered scenario, run with its own config.toml (one of `SCENARIOS`)
+    #[serde(default)]
+    pub scenario: Option<String>,
+    /// A custom sweep over one parameter of a base configuration
+    #[serde(default)]
+    pub sweep: Option<CustomSweep>,
+    /// Entries that must have succeeded before this one starts (must be declared earlier)
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+    /// Master seed of this entry, overriding the experiment's seed
+    #[serde(default)]
+    pub seed: Option<u64>,
+}
+
+fn default_max_parallel() -> usize {
+    1
+}
+
+/// Outcome of one entry
+struct EntryOutcome {
+    /// Name of the entry
+    name: String,
+    /// The entry's directory name under `simulator/results`
+    results_dir: String,
+    /// Master seed the entry ran with, if fixed by the experiment
+    seed: Option<u64>,
+    /// Why the entry did not run, if a dependency did not succeed
+    skipped: Option<String>,
+    /// Error returned by the simulation, if it failed
+    error: Option<String>,
+    /// Wall-clock time the entry took
+    duration: Duration,
+    /// Summary of the results the entry wrote, if they could be read
+    summary: Option<ResultsSummary>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Experiment {
+    /// Loads and validates an experiment file
+    pub fn load(path: &str) -> Result<Self, String> {
+        let content = fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+        let experiment: Experiment = serde_yaml::from_str(&content).map_err(|e| format!("Failed to parse {}: {}", path, e))?;
+        experiment.validate()?;
+        Ok(experiment)
+    }
+
+    /// Checks the names, dependencies and results directories of the entries and validates every sweep
+    pub fn validate(&self) -> Result<(), String> {
+        if !is_plain_name(&self.name) {
+            return Err(format!("Experiment name '{}' must be a plain directory name", self.name));
+        }
+        if self.entries.is_empty() {
+            return Err("The experiment needs at least one entry".to_string());
+        }
+        if self.max_parallel == 0 {
+            return Err("max_parallel must be at least 1".to_string());
+        }
+
+        let mut names = HashSet::new();
+        let mut results_dirs = HashSet::new();
+        for entry in &self.entries {
+            match (&entry.scenario, &entry.sweep) {
+                (Some(_), Some(_)) | (None, None) => {
+                    return Err(format!("Entry '{}' must set exactly one of 'scenario' and 'sweep'", entry.name));
+                }
+                (None, Some(sweep)) => {
+                    sweep.validate().map_err(|e| format!("Entry '{}': {}", entry.name, e))?;
+                }
+                (Some(_), None) => {}
+            }
+            // Dependencies must be declared earlier, which also rules out cycles
+            for dependency in &entry.depends_on {
+                if !names.contains(dependency.as_str()) {
+                    return Err(format!("Entry '{}' depends on '{}', which is not declared before it", entry.name, dependency));
+                }
+            }
+            if !names.insert(entry.name.as_str()) {
+                return Err(format!("Entry '{}' is declared twice", entry.name));
+            }
+            // Entries sharing a results directory would overwrite each other's results
+            let results_dir = entry.results_dir()?;
+            if !results_dirs.insert(results_dir.clone()) {
+                return Err(format!("Entry '{}' writes to simulator/results/{}, which another entry already writes to", entry.name, results_dir));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ExperimentEntry {
+    /// The simulation type of a registered scenario entry
+    fn simulation_type(&self) -> Option<SimulationType> {
+        scenario_type(self.scenario.as_deref()?)
+    }
+
+    /// Directory name of the entry's results under `simulator/results`
+    fn results_dir(&self) -> Result<String, String> {
+        if let Some(sweep) = &self.sweep {
+            return Ok(sweep.results_dir.clone());
+        }
+        let scenario = self.scenario.as_deref().unwrap_or_default();
+        match SCENARIOS.iter().find(|(name, _, _)| *name == scenario) {
+            // The custom sweep scenario writes to the directory configured in its config.toml
+            Some((_, SimulationType::SweepCustom, _)) => load_custom_sweep()
+                .map(|sweep| sweep.results_dir)
+                .map_err(|e| format!("Entry '{}': {}", self.name, e)),
+            Some((_, _, results_dir)) => Ok(results_dir.to_string()),
+            None => Err(format!("Entry '{}': unknown scenario '{}' (expected one of {})", self.name, scenario,
+                scenario_names().join(", "))),
+        }
+    }
+}
+
+impl EntryOutcome {
+    /// Status of the entry: skipped and failed entries and entries with anomalies are told apart
+    fn status(&self) -> &'static str {
+        match (&self.skipped, &self.error, &self.summary) {
+            (Some(_), _, _) => "skipped",
+            (None, Some(_), _) => "failed",
+            (None, None, Some(summary)) if !summary.anomalies.is_empty() => "anomalies",
+            (None, None, None) => "no results",
+            (None, None, Some(_)) => "passed",
+        }
+    }
+
+    /// Whether the entry ran without error, so entries depending on it may start
+    fn succeeded(&self) -> bool {
+        self.skipped.is_none() && self.error.is_none()
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Experiment Execution
+// ------------------------------------------------------------------------------------------------
+
+/// Runs the experiment described in a YAML file and writes its report
+///
+/// The configurations of all entries are validated before the first one starts. A failing entry
+/// does not stop the others, but the entries depending on it are skipped.
+///
+/// # Arguments
+/// * `path` - Path of the experiment file
+///
+/// # Returns
+/// The path of the written Markdown report
+pub async fn run_experiment(path: &str) -> Result<String, String> {
+    let experiment = Experiment::load(path)?;
+    let registry = crate::simulation_registry::get_registry().await;
+    for entry in &experiment.entries {
+        if let Some(simulation_type) = entry.simulation_type() {
+            let warnings = registry.lock().await.validate_config(&simulation_type)
+                .map_err(|e| format!("Entry '{}': invalid configuration: {}", entry.name, e))?;
+            for warning in warnings {
+                println!("Warning: entry '{}': {}", entry.name, warning);
+            }
+        }
+    }
+
+    let start_time = Instant::now();
+    logging::log("SIMULATOR", &format!("=== Starting Experiment {} ===", experiment.name));
+    logging::log("SIMULATOR", &format!("Running {} entries, at most {} at a time", experiment.entries.len(), experiment.max_parallel));
+
+    // Each entry publishes whether it succeeded; the entries depending on it wait for that
+    let (senders, receivers): (Vec<_>, Vec<_>) = experiment.entries.iter().map(|_| watch::channel(None::<bool>)).unzip();
+
+    // The simulation futures are not Send, so the entries share this task's thread (as in run_all_tests)
+    let semaphore = Rc::new(Semaphore::new(experiment.max_parallel));
+    let local = tokio::task::LocalSet::new();
+    let outcomes = local.run_until(async {
+        let handles: Vec<_> = experiment.entries.iter().cloned().zip(senders).enumerate()
+            .map(|(index, (entry, sender))| {
+                let semaphore = semaphore.clone();
+                let dependencies: Vec<(String, watch::Receiver<Option<bool>>)> = entry.depends_on.iter()
+                    .map(|dependency| {
+                        let position = experiment.entries.iter().position(|e| e.name == *dependency).expect("Dependencies are validated");
+                        (dependency.clone(), receivers[position].clone())
+                    })
+                    .collect();
+                let seed = entry.seed.or(experiment.seed);
+                let results_dir = entry.results_dir().expect("Results directories are validated");
+                let name = entry.name.clone();
+                let handle = tokio::task::spawn_local(async move {
+                    let mut outcome = EntryOutcome {
+                        name: entry.name.clone(),
+                        results_dir,
+                        seed,
+                        skipped: None,
+                        error: None,
+                        duration: Duration::ZERO,
+                        summary: None,
+                    };
+                    for (dependency, mut receiver) in dependencies {
+                        // A dependency that panicked drops its sender and counts as failed
+                        let succeeded = receiver.wait_for(|status| status.is_some()).await
+                            .is_ok_and(|status| *status == Some(true));
+                        if !succeeded {
+                            outcome.skipped = Some(format!("dependency '{}' did not succeed", dependency));
+                            sender.send_replace(Some(false));
+                            return outcome;
+                        }
+                    }
+
+                    let _permit = semaphore.acquire().await.expect("Experiment semaphore closed");
+                    println!("\n------------ {}. {} -----------", index + 1, entry.name);
+                    logging::log("SIMULATOR", &format!("------------ {}. {} -----------", index + 1, entry.name));
+
+                    let entry_start = Instant::now();
+                    outcome.error = run_entry(&entry, seed).await.err();
+                    outcome.duration = entry_start.elapsed();
+                    // A failed entry may have left the results of an earlier run behind
+                    if outcome.error.is_none() {
+                        outcome.summary = crate::report::summarize_results(&outcome.results_dir).ok();
+                    }
+                    sender.send_replace(Some(outcome.succeeded()));
+                    outcome
+                });
+                (name, handle)
+            })
+            .collect();
+
+        let mut outcomes = Vec::new();
+        for ((name, handle), entry) in handles.into_iter().zip(&experiment.entries) {
+            outcomes.push(handle.await.unwrap_or_else(|e| EntryOutcome {
+                results_dir: entry.results_dir().unwrap_or_default(),
+                seed: entry.seed.or(experiment.seed),
+                name,
+                skipped: None,
+                error: Some(format!("Entry panicked: {}", e)),
+                duration: Duration::ZERO,
+                summary: None,
+            }));
+        }
+        outcomes
+    }).await;
+
+    let total_time = start_time.elapsed();
+    let report_path = write_report(&experiment, path, &outcomes, total_time)?;
+    logging::log("SIMULATOR", &format!("Total execution time: {:.2?}", total_time));
+    println!("Experiment report written to {}", report_path);
+
+    let unsuccessful: Vec<&str> = outcomes.iter().filter(|o| !o.succeeded()).map(|o| o.name.as_str()).collect();
+    if !unsuccessful.is_empty() {
+        logging::log("SIMULATOR", &format!("=== {} of {} Entries Failed or Were Skipped ===", unsuccessful.len(), outcomes.len()));
+        return Err(format!("{} of {} entries failed or were skipped: {}", unsuccessful.len(), outcomes.len(), unsuccessful.join(", ")));
+    }
+
+    logging::log("SIMULATOR", &format!("=== Experiment {} Completed Successfully ===", experiment.name));
+    Ok(report_path)
+}
+
+/// Runs one entry, with the master seed of the experiment if one is set
+async fn run_entry(entry: &ExperimentEntry, seed: Option<u64>) -> Result<(), String> {
+    let run = async {
+        if let Some(sweep) = &entry.sweep {
+            return run_custom_sweep(sweep.clone(), &sweep.base_config).await.map_err(|e| e.to_string());
+        }
+        let simulation_type = entry.simulation_type()
+            .ok_or_else(|| format!("Entry '{}' has no scenario", entry.name))?;
+        let run_future = {
+            let registry = crate::simulation_registry::get_registry().await;
+            let registry_guard = registry.lock().await;
+            let config = registry_guard.get(&simulation_type)
+                .ok_or_else(|| format!("Unknown simulation type: {:?}", simulation_type))?;
+            (config.run_fn)()
+        };
+        run_future.await
+    };
+    match seed {
+        Some(seed) => MASTER_SEED_OVERRIDE.scope(seed, run).await,
+        None => run.await,
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Report
+// ------------------------------------------------------------------------------------------------
+
+/// Writes the status of all entries and their metrics side by side as Markdown and JSON, next to
+/// a copy of the experiment file
+///
+/// # Returns
+/// The path of the written Markdown report
+fn write_report(experiment: &Experiment, path: &str, outcomes: &[EntryOutcome], total_time: Duration) -> Result<String, String> {
+    let report_dir = format!("{}/{}", EXPERIMENTS_DIR, experiment.name);
+    fs::create_dir_all(&report_dir).map_err(|e| format!("Failed to create {}: {}", report_dir, e))?;
+    let experiment_copy = format!("{}/experiment.yaml", report_dir);
+    fs::copy(path, &experiment_copy).map_err(|e| format!("Failed to copy {} to {}: {}", path, experiment_copy, e))?;
+
+    let mut markdown = String::new();
+    writeln!(markdown, "# Experiment {}\n", experiment.name).unwrap();
+    writeln!(markdown, "{} entries, at most {} at a time, {:.2?} in total. Metrics are final values averaged over runs and simulations.\n",
+        outcomes.len(), experiment.max_parallel, total_time).unwrap();
+    writeln!(markdown, "| Entry | Results | Seed | Status | Duration | Runs |").unwrap();
+    writeln!(markdown, "|---|---|---:|---|---:|---:|").unwrap();
+    for outcome in outcomes {
+        let runs = outcome.summary.as_ref()
+            .map_or("-".to_string(), |summary| format!("{}/{}", summary.runs_found, summary.expected_runs));
+        let seed = outcome.seed.map_or("config".to_string(), |seed| seed.to_string());
+        writeln!(markdown, "| {} | {} | {} | {} | {:.1?} | {} |", outcome.name, outcome.results_dir, seed, outcome.status(), outcome.duration, runs).unwrap();
+    }
+
+    // Metrics of all entries side by side
+    let labels: Vec<&str> = outcomes.iter()
+        .find_map(|outcome| outcome.summary.as_ref())
+        .map(|summary| summary.metrics.iter().map(|(label, _)| *label).collect())
+        .unwrap_or_default();
+    if !labels.is_empty() {
+        write!(markdown, "\n## Metrics\n\n| Metric |").unwrap();
+        for outcome in outcomes {
+            write!(markdown, " {} |", outcome.name).unwrap();
+        }
+        writeln!(markdown, "\n|---|{}", "---:|".repeat(outcomes.len())).unwrap();
+        for label in &labels {
+            write!(markdown, "| {} |", label).unwrap();
+            for outcome in outcomes {
+                let value = outcome.summary.as_ref()
+                    .and_then(|summary| summary.metrics.iter().find(|(l, _)| l == label))
+                    .map_or("-".to_string(), |(_, value)| format!("{:.2}", value));
+                write!(markdown, " {} |", value).unwrap();
+            }
+            writeln!(markdown).unwrap();
+        }
+    }
+
+    // Skips, errors and anomalies below the tables
+    let notes: Vec<String> = outcomes.iter()
+        .flat_map(|outcome| {
+            outcome.skipped.iter().cloned()
+                .chain(outcome.error.iter().cloned())
+                .chain(outcome.summary.iter().flat_map(|summary| summary.anomalies.iter().cloned()))
+                .map(move |note| format!("- {}: {}", outcome.name, note))
+        })
+        .collect();
+    if !notes.is_empty() {
+        writeln!(markdown, "\n## Failures and anomalies\n\n{}", notes.join("\n")).unwrap();
+    }
+
+    let json = serde_json::json!({
+        "name": experiment.name,
+        "seed": experiment.seed,
+        "max_parallel": experiment.max_parallel,
+        "total_time_secs": total_time.as_secs_f64(),
+        "entries": outcomes.iter().map(|outcome| serde_json::json!({
+            "name": outcome.name,
+            "results_dir": outcome.results_dir,
+            "seed": outcome.seed,
+            "status": outcome.status(),
+            "skipped": outcome.skipped,
+            "error": outcome.error,
+            "duration_secs": outcome.duration.as_secs_f64(),
+            "simulations": outcome.summary.as_ref().map(|summary| summary.simulations),
+            "runs_found": outcome.summary.as_ref().map(|summary| summary.runs_found),
+            "expected_runs": outcome.summary.as_ref().map(|summary| summary.expected_runs),
+            "metrics": outcome.summary.as_ref().map(|summary| summary.metrics.iter()
+                .map(|(label, value)| (label.to_string(), serde_json::json!(value)))
+                .collect::<serde_json::Map<_, _>>()),
+            "anomalies": outcome.summary.as_ref().map(|summary| &summary.anomalies),
+        })).collect::<Vec<_>>()
+    });
+
+    let markdown_path = format!("{}/experiment.md", report_dir);
+    fs::write(&markdown_path, markdown).map_err(|e| format!("Failed to write {}: {}", markdown_path, e))?;
+    let json_path = format!("{}/experiment.json", report_dir);
+    fs::write(&json_path, serde_json::to_string_pretty(&json).unwrap()).map_err(|e| format!("Failed to write {}: {}", json_path, e))?;
+    Ok(markdown_path)
+}
+
+/// Whether a name can be used as a directory name
+fn is_plain_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
@@ -0,0 +1,229 @@
+//! Columnar export of per-transaction and per-block records.
+//!
+//! Large runs produce far more records than the JSON time series are suited for. With the
+//! `parquet` feature enabled, the records are written as Apache Parquet files that can be queried
+//! directly from DuckDB or Polars.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use hyperplane::{
+    types::ChainId,
+    hyper_ig::node::HyperIGNode,
+    hyper_ig::HyperIG,
+};
+use crate::workload::RecordedSubmission;
+use crate::SimulationResults;
+
+// ------------------------------------------------------------------------------------------------
+// Constants
+// ------------------------------------------------------------------------------------------------
+
+/// Name of the per-transaction Parquet file (inside a run's data directory)
+pub const TRANSACTIONS_FILE: &str = "transactions.parquet";
+
+/// Name of the per-block Parquet file (inside a run's data directory)
+pub const BLOCKS_FILE: &str = "blocks.parquet";
+
+// ------------------------------------------------------------------------------------------------
+// Data Structures
+// ------------------------------------------------------------------------------------------------
+
+/// Outcome of a single sub-transaction on one chain
+#[derive(Debug, Clone)]
+pub struct TransactionRecord {
+    /// Id of the sub-transaction
+    pub tx_id: String,
+    /// Id of the CL transaction it was submitted in
+    pub cl_id: String,
+    /// Whether the transaction is part of a CAT
+    pub is_cat: bool,
+    /// Block offset of the submission, relative to the first block of transaction submission
+    pub block_offset: u64,
+    /// The chain the sub-transaction was executed on
+    pub chain: String,
+    /// Final status at the end of the run ("Unknown" if the HIG never saw the transaction)
+    pub status: String,
+}
+
+/// Counters of one chain at one block height
+#[derive(Debug, Clone, Default)]
+pub struct BlockRecord {
+    pub height: u64,
+    pub chain: String,
+    pub pending: u64,
+    pub success: u64,
+    pub failure: u64,
+    pub cat_pending: u64,
+    pub cat_success: u64,
+    pub cat_failure: u64,
+    pub regular_pending: u64,
+    pub regular_success: u64,
+    pub regular_failure: u64,
+    pub locked_keys: u64,
+    pub tx_per_block: u64,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Record Collection
+// ------------------------------------------------------------------------------------------------
+
+/// Queries the final status of every submitted sub-transaction
+///
+/// # Arguments
+/// * `hig_nodes` - The HIG nodes, in the same order as `chains`
+/// * `chains` - The registered chains
+/// * `workload` - The submissions of the run
+pub async fn collect_transaction_records(
+    hig_nodes: &[Arc<Mutex<HyperIGNode>>],
+    chains: &[ChainId],
+    workload: &[RecordedSubmission],
+) -> Vec<TransactionRecord> {
+    let mut records = Vec::new();
+    for submission in workload {
+        for cl_transaction in &submission.cl_transactions {
+            for tx in &cl_transaction.transactions {
+                let status = match chains.iter().position(|chain_id| *chain_id == tx.chain_id) {
+                    Some(index) => hig_nodes[index].lock().await.get_transaction_status(tx.id.clone()).await
+                        .map_or("Unknown".to_string(), |status| format!("{:?}", status)),
+                    None => "Unknown".to_string(),
+                };
+                records.push(TransactionRecord {
+                    tx_id: tx.id.0.clone(),
+                    cl_id: cl_transaction.id.0.clone(),
+                    is_cat: submission.is_cat,
+                    block_offset: submission.block_offset,
+                    chain: tx.chain_id.0.clone(),
+                    status,
+                });
+            }
+        }
+    }
+    records
+}
+
+/// Joins the per-chain time series of a run into one record per chain and block height
+pub fn block_records(results: &SimulationResults) -> Vec<BlockRecord> {
+    let mut records: BTreeMap<(u64, usize), BlockRecord> = BTreeMap::new();
+
+    let chains: [[(&Vec<(u64, u64)>, fn(&mut BlockRecord) -> &mut u64); 11]; 2] = [
+        [
+            (&results.chain_1_pending, |r| &mut r.pending),
+            (&results.chain_1_success, |r| &mut r.success),
+            (&results.chain_1_failure, |r| &mut r.failure),
+            (&results.chain_1_cat_pending, |r| &mut r.cat_pending),
+            (&results.chain_1_cat_success, |r| &mut r.cat_success),
+            (&results.chain_1_cat_failure, |r| &mut r.cat_failure),
+            (&results.chain_1_regular_pending, |r| &mut r.regular_pending),
+            (&results.chain_1_regular_success, |r| &mut r.regular_success),
+            (&results.chain_1_regular_failure, |r| &mut r.regular_failure),
+            (&results.chain_1_locked_keys, |r| &mut r.locked_keys),
+            (&results.chain_1_tx_per_block, |r| &mut r.tx_per_block),
+        ],
+        [
+            (&results.chain_2_pending, |r| &mut r.pending),
+            (&results.chain_2_success, |r| &mut r.success),
+            (&results.chain_2_failure, |r| &mut r.failure),
+            (&results.chain_2_cat_pending, |r| &mut r.cat_pending),
+            (&results.chain_2_cat_success, |r| &mut r.cat_success),
+            (&results.chain_2_cat_failure, |r| &mut r.cat_failure),
+            (&results.chain_2_regular_pending, |r| &mut r.regular_pending),
+            (&results.chain_2_regular_success, |r| &mut r.regular_success),
+            (&results.chain_2_regular_failure, |r| &mut r.regular_failure),
+            (&results.chain_2_locked_keys, |r| &mut r.locked_keys),
+            (&results.chain_2_tx_per_block, |r| &mut r.tx_per_block),
+        ],
+    ];
+
+    for (chain_index, series_list) in chains.iter().enumerate() {
+        for (series, field) in series_list {
+            for &(height, count) in series.iter() {
+                let record = records.entry((height, chain_index)).or_insert_with(|| BlockRecord {
+                    height,
+                    chain: results.network.chain_ids()[chain_index].0.clone(),
+                    ..Default::default()
+                });
+                *field(record) = count;
+            }
+        }
+    }
+
+    records.into_values().collect()
+}
+
+// ------------------------------------------------------------------------------------------------
+// Parquet Output
+// ------------------------------------------------------------------------------------------------
+
+/// Writes the per-transaction and per-block records as Parquet files
+///
+/// # Arguments
+/// * `data_dir` - The data directory of the run
+/// * `transactions` - The per-transaction records
+/// * `blocks` - The per-block records
+///
+/// # Returns
+/// The paths of the written files
+#[cfg(feature = "parquet")]
+pub fn save_parquet(data_dir: &str, transactions: &[TransactionRecord], blocks: &[BlockRecord]) -> Result<Vec<String>, String> {
+    use arrow::array::{ArrayRef, BooleanArray, StringArray, UInt64Array};
+    use arrow::record_batch::RecordBatch;
+
+    let string_column = |values: Vec<&str>| Arc::new(StringArray::from(values)) as ArrayRef;
+    let u64_column = |values: Vec<u64>| Arc::new(UInt64Array::from(values)) as ArrayRef;
+
+    let transactions_batch = RecordBatch::try_from_iter(vec![
+        ("tx_id", string_column(transactions.iter().map(|r| r.tx_id.as_str()).collect())),
+        ("cl_id", string_column(transactions.iter().map(|r| r.cl_id.as_str()).collect())),
+        ("is_cat", Arc::new(BooleanArray::from(transactions.iter().map(|r| r.is_cat).collect::<Vec<_>>())) as ArrayRef),
+        ("block_offset", u64_column(transactions.iter().map(|r| r.block_offset).collect())),
+        ("chain", string_column(transactions.iter().map(|r| r.chain.as_str()).collect())),
+        ("status", string_column(transactions.iter().map(|r| r.status.as_str()).collect())),
+    ]).map_err(|e| e.to_string())?;
+
+    let blocks_batch = RecordBatch::try_from_iter(vec![
+        ("height", u64_column(blocks.iter().map(|r| r.height).collect())),
+        ("chain", string_column(blocks.iter().map(|r| r.chain.as_str()).collect())),
+        ("pending", u64_column(blocks.iter().map(|r| r.pending).collect())),
+        ("success", u64_column(blocks.iter().map(|r| r.success).collect())),
+        ("failure", u64_column(blocks.iter().map(|r| r.failure).collect())),
+        ("cat_pending", u64_column(blocks.iter().map(|r| r.cat_pending).collect())),
+        ("cat_success", u64_column(blocks.iter().map(|r| r.cat_success).collect())),
+        ("cat_failure", u64_column(blocks.iter().map(|r| r.cat_failure).collect())),
+        ("regular_pending", u64_column(blocks.iter().map(|r| r.regular_pending).collect())),
+        ("regular_success", u64_column(blocks.iter().map(|r| r.regular_success).collect())),
+        ("regular_failure", u64_column(blocks.iter().map(|r| r.regular_failure).collect())),
+        ("locked_keys", u64_column(blocks.iter().map(|r| r.locked_keys).collect())),
+        ("tx_per_block", u64_column(blocks.iter().map(|r| r.tx_per_block).collect())),
+    ]).map_err(|e| e.to_string())?;
+
+    let transactions_path = format!("{}/{}", data_dir, TRANSACTIONS_FILE);
+    let blocks_path = format!("{}/{}", data_dir, BLOCKS_FILE);
+    write_batch(&transactions_path, &transactions_batch)?;
+    write_batch(&blocks_path, &blocks_batch)?;
+    Ok(vec![transactions_path, blocks_path])
+}
+
+/// Writes the per-transaction and per-block records as Parquet files
+///
+/// Without the `parquet` feature this always fails, so a run configured for Parquet output
+/// reports that the simulator has to be rebuilt.
+#[cfg(not(feature = "parquet"))]
+pub fn save_parquet(_data_dir: &str, _transactions: &[TransactionRecord], _blocks: &[BlockRecord]) -> Result<Vec<String>, String> {
+    Err("the simulator was built without the `parquet` feature (rebuild with `--features parquet`)".to_string())
+}
+
+/// Writes a single record batch as a Snappy-compressed Parquet file
+#[cfg(feature = "parquet")]
+fn write_batch(path: &str, batch: &arrow::record_batch::RecordBatch) -> Result<(), String> {
+    use parquet::arrow::ArrowWriter;
+    use parquet::basic::Compression;
+    use parquet::file::properties::WriterProperties;
+
+    let file = std::fs::File::create(path).map_err(|e| format!("Failed to create {}: {}", path, e))?;
+    let properties = WriterProperties::builder().set_compression(Compression::SNAPPY).build();
+    let mut writer = ArrowWriter::try_new(file, batch.schema(), Some(properties)).map_err(|e| e.to_string())?;
+    writer.write(batch).map_err(|e| e.to_string())?;
+    writer.close().map_err(|e| e.to_string())?;
+    Ok(())
+}
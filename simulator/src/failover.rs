@@ -0,0 +1,462 @@
+//! Warm standby HIGs that take over from a crashed primary.
+//!
+//! Every chain gets a second HIG that is fed the same subblocks as its primary and processes them
+//! alongside it, but whose proposals are held back by a failover relay in front of the HS. A
+//! watchdog checks the primaries once per block; once a primary has been halted for the configured
+//! number of blocks, its standby is promoted. The relay then sends the held proposals of the
+//! standby for CATs the primary never proposed, forwards the standby's proposals from then on and
+//! drops any late proposal of the old primary. The relay forwards at most one proposal per CAT,
+//! so a proposal that still reaches the HS twice points to a bug in the failover itself.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use serde::Serialize;
+use tokio::sync::{Mutex, mpsc, watch};
+use tokio::task::JoinHandle;
+use hyperplane::{
+    types::{CATId, CATStatusUpdate, ChainId, CLTransaction, SubBlock},
+    confirmation_layer::{node::ConfirmationLayerNode, ConfirmationLayer},
+    hyper_ig::node::HyperIGNode,
+    hyper_scheduler::HyperSchedulerMetrics,
+    harness::{ChainOptions, Links},
+    utils::logging,
+};
+use crate::stats::mean_duration_ms;
+
+// ------------------------------------------------------------------------------------------------
+// Constants
+// ------------------------------------------------------------------------------------------------
+
+/// Name of the failover report (inside a run's data directory)
+pub const FAILOVER_FILE: &str = "failover.json";
+
+// ------------------------------------------------------------------------------------------------
+// Data Structures
+// ------------------------------------------------------------------------------------------------
+
+/// What the failover relay of a chain forwarded, held back and dropped
+#[derive(Debug, Clone, Default)]
+struct RelayCounters {
+    /// Proposals forwarded to the HS, from either HIG
+    forwarded: u64,
+    /// Time the replayed proposals of the standby were held back until it took over
+    replay_delays: Vec<Duration>,
+    /// Proposals for CATs that had already been forwarded
+    duplicates_suppressed: u64,
+    /// Proposals of the old primary that arrived after the standby took over
+    fenced_primary_proposals: u64,
+}
+
+/// The failover relay of one chain
+struct FailoverRelay {
+    /// Set to true to promote the standby
+    promote: Arc<watch::Sender<bool>>,
+    counters: Arc<std::sync::Mutex<RelayCounters>>,
+}
+
+/// Channels of the standbys created while the network is wired, by chain index
+#[derive(Default)]
+struct StandbyWiring {
+    /// Copies of the subblocks the primary receives
+    subblocks: HashMap<usize, mpsc::Receiver<SubBlock>>,
+    /// Senders for the proposals of the standby to its relay
+    proposals: HashMap<usize, mpsc::Sender<CATStatusUpdate>>,
+    relays: HashMap<usize, FailoverRelay>,
+}
+
+/// Links that give every chain a standby HIG behind a failover relay
+///
+/// Wraps the links of the network: the standby receives the subblocks after the CL -> HIG link
+/// of its chain, and the relay sits in front of the HIG -> HS link, so both HIGs see the same
+/// delays and partitions.
+pub struct FailoverLinks<L: Links> {
+    inner: L,
+    channel_buffer_size: usize,
+    wiring: Arc<std::sync::Mutex<StandbyWiring>>,
+}
+
+/// Handle to the standby channels of a network wired with `FailoverLinks`
+pub struct StandbyHandle {
+    wiring: Arc<std::sync::Mutex<StandbyWiring>>,
+}
+
+/// The standby HIGs of a network and their failover relays
+pub struct WarmStandbys {
+    /// The standby HIG nodes, in chain order
+    pub standby_nodes: Vec<Arc<Mutex<HyperIGNode>>>,
+    /// The chains, in chain order
+    chain_ids: Vec<ChainId>,
+    relays: Vec<FailoverRelay>,
+    /// CL height at which the watchdog first found each chain's primary halted
+    halted_at: Arc<std::sync::Mutex<HashMap<usize, u64>>>,
+    /// CL height at which each chain's standby took over
+    promoted_at: Arc<std::sync::Mutex<HashMap<usize, u64>>>,
+    watchdog: Option<JoinHandle<()>>,
+}
+
+/// Outcome of the failover of one chain
+#[derive(Debug, Clone, Serialize)]
+pub struct ChainFailover {
+    pub chain: String,
+    /// CL height at which the watchdog first found the primary halted, for the failover that took place
+    pub halt_detected_at_height: Option<u64>,
+    /// CL height at which the standby took over (None if the primary never failed)
+    pub promoted_at_height: Option<u64>,
+    /// Proposals forwarded to the HS, from either HIG
+    pub proposals_forwarded: u64,
+    /// Proposals of the standby for CATs the primary never proposed, sent when it took over
+    pub proposals_replayed: u64,
+    /// Mean and largest time the replayed proposals were held back (milliseconds)
+    pub mean_replay_delay_ms: f64,
+    pub max_replay_delay_ms: f64,
+    /// Proposals for CATs that had already been forwarded, kept from the HS
+    pub duplicates_suppressed: u64,
+    /// Proposals of the old primary that arrived after the standby took over
+    pub fenced_primary_proposals: u64,
+    /// Proposals the HS received from the chain
+    pub hs_proposals_received: u64,
+    /// Proposals the HS received beyond those the relay forwarded
+    pub double_proposals: u64,
+}
+
+/// Outcome of the failovers of all chains of a run
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct FailoverReport {
+    pub chains: Vec<ChainFailover>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Links
+// ------------------------------------------------------------------------------------------------
+
+impl<L: Links> FailoverLinks<L> {
+    /// Wraps the links of a network
+    ///
+    /// # Arguments
+    /// * `inner` - The links between the nodes
+    /// * `channel_buffer_size` - Buffer size of the channels of the standbys
+    ///
+    /// # Returns
+    /// The links and the handle to start the standbys with once the network is built
+    pub fn new(inner: L, channel_buffer_size: usize) -> (Self, StandbyHandle) {
+        let wiring = Arc::new(std::sync::Mutex::new(StandbyWiring::default()));
+        (Self { inner, channel_buffer_size, wiring: wiring.clone() }, StandbyHandle { wiring })
+    }
+}
+
+impl<L: Links> Links for FailoverLinks<L> {
+    fn cl_to_hig(&self, chain_index: usize, receiver: mpsc::Receiver<SubBlock>) -> mpsc::Receiver<SubBlock> {
+        let receiver = self.inner.cl_to_hig(chain_index, receiver);
+        let (primary, standby) = tee_link(receiver, self.channel_buffer_size);
+        self.wiring.lock().unwrap().subblocks.insert(chain_index, standby);
+        primary
+    }
+
+    fn hig_to_hs(&self, chain_index: usize, receiver: mpsc::Receiver<CATStatusUpdate>) -> mpsc::Receiver<CATStatusUpdate> {
+        let (standby_sender, standby_receiver) = mpsc::channel(self.channel_buffer_size);
+        let (sender_out, receiver_out) = mpsc::channel(self.channel_buffer_size);
+        let (promote, promoted) = watch::channel(false);
+        let counters = Arc::new(std::sync::Mutex::new(RelayCounters::default()));
+        tokio::spawn(run_relay(receiver, standby_receiver, promoted, sender_out, counters.clone()));
+
+        let mut wiring = self.wiring.lock().unwrap();
+        wiring.proposals.insert(chain_index, standby_sender);
+        wiring.relays.insert(chain_index, FailoverRelay { promote: Arc::new(promote), counters });
+        drop(wiring);
+        self.inner.hig_to_hs(chain_index, receiver_out)
+    }
+
+    fn hs_to_cl(&self, receiver: mpsc::Receiver<CLTransaction>) -> mpsc::Receiver<CLTransaction> {
+        self.inner.hs_to_cl(receiver)
+    }
+}
+
+/// Copies every message of a receiver to two receivers
+///
+/// Each copy is buffered without bound, so a receiver that stops reading (e.g. a halted HIG)
+/// does not hold up the other one.
+fn tee_link<T: Clone + Send + 'static>(mut receiver: mpsc::Receiver<T>, channel_buffer_size: usize) -> (mpsc::Receiver<T>, mpsc::Receiver<T>) {
+    let (sender_first, receiver_first) = unbounded_output(channel_buffer_size);
+    let (sender_second, receiver_second) = unbounded_output(channel_buffer_size);
+    tokio::spawn(async move {
+        while let Some(message) = receiver.recv().await {
+            let first = sender_first.send(message.clone());
+            let second = sender_second.send(message);
+            if first.is_err() && second.is_err() {
+                break;
+            }
+        }
+    });
+    (receiver_first, receiver_second)
+}
+
+/// Creates a channel whose messages are accepted without bound and delivered in order
+fn unbounded_output<T: Send + 'static>(channel_buffer_size: usize) -> (mpsc::UnboundedSender<T>, mpsc::Receiver<T>) {
+    let (sender_in, mut receiver_in) = mpsc::unbounded_channel::<T>();
+    let (sender_out, receiver_out) = mpsc::channel(channel_buffer_size);
+    tokio::spawn(async move {
+        while let Some(message) = receiver_in.recv().await {
+            if sender_out.send(message).await.is_err() {
+                break;
+            }
+        }
+    });
+    (sender_in, receiver_out)
+}
+
+/// Forwards the proposals of the active HIG of a chain to the HS
+///
+/// Until the standby is promoted, the primary's proposals are forwarded and the standby's are held
+/// back, unless the primary already proposed the CAT. On promotion the held proposals are sent, and
+/// from then on only the standby's proposals pass.
+async fn run_relay(
+    mut primary: mpsc::Receiver<CATStatusUpdate>,
+    mut standby: mpsc::Receiver<CATStatusUpdate>,
+    mut promoted: watch::Receiver<bool>,
+    sender: mpsc::Sender<CATStatusUpdate>,
+    counters: Arc<std::sync::Mutex<RelayCounters>>,
+) {
+    let mut forwarded: HashSet<CATId> = HashSet::new();
+    let mut held: Vec<(CATStatusUpdate, Instant)> = Vec::new();
+    let mut is_promoted = false;
+    let (mut primary_open, mut standby_open, mut promote_open) = (true, true, true);
+
+    loop {
+        let update = tokio::select! {
+            update = primary.recv(), if primary_open => match update {
+                Some(_) if is_promoted => {
+                    counters.lock().unwrap().fenced_primary_proposals += 1;
+                    continue;
+                }
+                Some(update) => {
+                    held.retain(|(held_update, _)| held_update.cat_id != update.cat_id);
+                    update
+                }
+                None => {
+                    primary_open = false;
+                    continue;
+                }
+            },
+            update = standby.recv(), if standby_open => match update {
+                Some(update) if is_promoted => update,
+                Some(update) => {
+                    if !forwarded.contains(&update.cat_id) {
+                        held.push((update, Instant::now()));
+                    }
+                    continue;
+                }
+                None => {
+                    standby_open = false;
+                    continue;
+                }
+            },
+            changed = promoted.changed(), if promote_open && !is_promoted => {
+                if changed.is_err() {
+                    promote_open = false;
+                    continue;
+                }
+                if !*promoted.borrow() {
+                    continue;
+                }
+                is_promoted = true;
+                for (update, held_since) in std::mem::take(&mut held) {
+                    counters.lock().unwrap().replay_delays.push(held_since.elapsed());
+                    if !forward(update, &mut forwarded, &sender, &counters).await {
+                        return;
+                    }
+                }
+                continue;
+            },
+            else => break,
+        };
+        if !forward(update, &mut forwarded, &sender, &counters).await {
+            return;
+        }
+    }
+}
+
+/// Forwards a proposal unless its CAT was already forwarded
+///
+/// # Returns
+/// False if the HS side of the relay has closed
+async fn forward(
+    update: CATStatusUpdate,
+    forwarded: &mut HashSet<CATId>,
+    sender: &mpsc::Sender<CATStatusUpdate>,
+    counters: &std::sync::Mutex<RelayCounters>,
+) -> bool {
+    if !forwarded.insert(update.cat_id.clone()) {
+        counters.lock().unwrap().duplicates_suppressed += 1;
+        return true;
+    }
+    counters.lock().unwrap().forwarded += 1;
+    sender.send(update).await.is_ok()
+}
+
+// ------------------------------------------------------------------------------------------------
+// Standbys
+// ------------------------------------------------------------------------------------------------
+
+impl StandbyHandle {
+    /// Creates and starts the standby HIG of every chain
+    ///
+    /// Must be called after the network was built with the `FailoverLinks` of this handle. The
+    /// standbys run on the clock of the CL, like the primaries.
+    ///
+    /// # Arguments
+    /// * `cl_node` - The CL node
+    /// * `chain_ids` - The chains, in chain order
+    /// * `options` - The settings of the chains' HIGs
+    /// * `account_balances` - The (account, balance) pairs to preload, one list per chain
+    pub async fn start_standbys(
+        self,
+        cl_node: &Arc<Mutex<ConfirmationLayerNode>>,
+        chain_ids: &[ChainId],
+        options: &ChainOptions,
+        account_balances: &[Vec<(u32, u32)>],
+    ) -> Result<WarmStandbys, String> {
+        let clock = cl_node.lock().await.get_clock();
+        let mut wiring = std::mem::take(&mut *self.wiring.lock().unwrap());
+
+        let mut standby_nodes = Vec::with_capacity(chain_ids.len());
+        let mut relays = Vec::with_capacity(chain_ids.len());
+        for (chain_index, chain_id) in chain_ids.iter().enumerate() {
+            let missing = || format!("Chain {} was not wired for a standby", chain_id.0);
+            let subblocks = wiring.subblocks.remove(&chain_index).ok_or_else(missing)?;
+            let proposals = wiring.proposals.remove(&chain_index).ok_or_else(missing)?;
+            relays.push(wiring.relays.remove(&chain_index).ok_or_else(missing)?);
+
+            let balances = account_balances.get(chain_index).unwrap_or(&options.account_balances);
+            let mut standby = HyperIGNode::new_with_account_balances(
+                subblocks,
+                proposals,
+                chain_id.clone(),
+                options.cat_lifetime,
+                options.allow_cat_pending_dependencies,
+                balances,
+            );
+            standby.set_clock(clock.clone());
+            let standby = Arc::new(Mutex::new(standby));
+            HyperIGNode::start(standby.clone()).await;
+            standby_nodes.push(standby);
+            logging::log("SIMULATOR", &format!("Started standby HIG for {}", chain_id.0));
+        }
+
+        Ok(WarmStandbys {
+            standby_nodes,
+            chain_ids: chain_ids.to_vec(),
+            relays,
+            halted_at: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            promoted_at: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            watchdog: None,
+        })
+    }
+}
+
+impl WarmStandbys {
+    /// Starts the watchdog that promotes the standby of a chain once its primary has been
+    /// halted for `detection_blocks` consecutive checks, one check per block
+    ///
+    /// # Arguments
+    /// * `primaries` - The primary HIG nodes, in chain order
+    /// * `cl_node` - The CL node, for the height of the promotion
+    /// * `block_interval` - Time between two checks
+    /// * `detection_blocks` - Number of checks a primary must fail before its standby takes over
+    pub fn spawn_watchdog(&mut self, primaries: Vec<Arc<Mutex<HyperIGNode>>>, cl_node: Arc<Mutex<ConfirmationLayerNode>>, block_interval: Duration, detection_blocks: u64) {
+        let promotes: Vec<Arc<watch::Sender<bool>>> = self.relays.iter().map(|relay| relay.promote.clone()).collect();
+        let chain_ids = self.chain_ids.clone();
+        let halted_at = self.halted_at.clone();
+        let promoted_at = self.promoted_at.clone();
+        self.watchdog = Some(tokio::spawn(async move {
+            let mut missed = vec![0u64; primaries.len()];
+            loop {
+                tokio::time::sleep(block_interval).await;
+                for (index, primary) in primaries.iter().enumerate() {
+                    if promoted_at.lock().unwrap().contains_key(&index) {
+                        continue;
+                    }
+                    missed[index] = if primary.lock().await.is_halted().await { missed[index] + 1 } else { 0 };
+                    if missed[index] == 0 {
+                        continue;
+                    }
+                    let height = cl_node.lock().await.get_current_block().await.unwrap_or_default();
+                    if missed[index] == 1 {
+                        halted_at.lock().unwrap().insert(index, height);
+                    }
+                    if missed[index] < detection_blocks.max(1) {
+                        continue;
+                    }
+                    promotes[index].send_replace(true);
+                    promoted_at.lock().unwrap().insert(index, height);
+                    logging::log("SIMULATOR", &format!("Block {}: standby HIG of {} took over after {} missed blocks", height, chain_ids[index].0, missed[index]));
+                }
+            }
+        }));
+    }
+
+    /// Stops the watchdog and reports the failovers of the run
+    ///
+    /// # Arguments
+    /// * `hs_metrics` - The metrics of the HS at the end of the run
+    pub fn report(&mut self, hs_metrics: &HyperSchedulerMetrics) -> FailoverReport {
+        if let Some(watchdog) = self.watchdog.take() {
+            watchdog.abort();
+        }
+        let promoted_at = self.promoted_at.lock().unwrap().clone();
+        let halted_at = self.halted_at.lock().unwrap().clone();
+        let chains = self.chain_ids.iter().zip(&self.relays).enumerate()
+            .map(|(index, (chain_id, relay))| {
+                let counters = relay.counters.lock().unwrap().clone();
+                let hs_proposals_received = hs_metrics.proposals_received.get(chain_id).copied().unwrap_or(0);
+                ChainFailover {
+                    chain: chain_id.0.clone(),
+                    halt_detected_at_height: promoted_at.get(&index).and(halted_at.get(&index).copied()),
+                    promoted_at_height: promoted_at.get(&index).copied(),
+                    proposals_forwarded: counters.forwarded,
+                    proposals_replayed: counters.replay_delays.len() as u64,
+                    mean_replay_delay_ms: mean_duration_ms(&counters.replay_delays),
+                    max_replay_delay_ms: counters.replay_delays.iter().max().map_or(0.0, |delay| delay.as_secs_f64() * 1000.0),
+                    duplicates_suppressed: counters.duplicates_suppressed,
+                    fenced_primary_proposals: counters.fenced_primary_proposals,
+                    hs_proposals_received,
+                    double_proposals: hs_proposals_received.saturating_sub(counters.forwarded),
+                }
+            })
+            .collect();
+        FailoverReport { chains }
+    }
+
+    /// Shuts down the standby HIG nodes
+    pub async fn shutdown(&mut self) {
+        if let Some(watchdog) = self.watchdog.take() {
+            watchdog.abort();
+        }
+        for standby in &self.standby_nodes {
+            HyperIGNode::shutdown(standby.clone()).await;
+        }
+    }
+}
+
+impl FailoverReport {
+    /// Returns true if the HS received more proposals from a chain than its relay forwarded
+    pub fn has_double_proposals(&self) -> bool {
+        self.chains.iter().any(|chain| chain.double_proposals > 0)
+    }
+
+    /// Human-readable summary of the report, one line per chain
+    pub fn summary_lines(&self) -> Vec<String> {
+        self.chains.iter().map(|chain| {
+            let takeover = match (chain.halt_detected_at_height, chain.promoted_at_height) {
+                (Some(halted), Some(promoted)) => format!("primary found halted at block {}, standby took over at block {}", halted, promoted),
+                _ => "primary stayed active".to_string(),
+            };
+            format!(
+                "{}: {}, {} proposals forwarded, {} replayed (mean delay {:.1} ms, max {:.1} ms), {} duplicates suppressed, {} fenced, {} double proposals at the HS",
+                chain.chain, takeover, chain.proposals_forwarded, chain.proposals_replayed,
+                chain.mean_replay_delay_ms, chain.max_replay_delay_ms,
+                chain.duplicates_suppressed, chain.fenced_primary_proposals, chain.double_proposals,
+            )
+        }).collect()
+    }
+}
@@ -0,0 +1,224 @@
+//! Scheduled faults for the Hyperplane simulator.
+//!
+//! Scenario configs declare a timeline of fault events (partition a chain, heal it, crash or
+//! recover its HIG). The orchestrator executes each event once the run reaches its block, so
+//! failure stories are configured instead of coded into dedicated scenarios. A crashed HIG loses
+//! the subblocks delivered while it is down; when it recovers, it replays them from the CL before
+//! resuming, so the chain ends in the same state as without the crash.
+
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use std::sync::Arc;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+use hyperplane::{
+    confirmation_layer::ConfirmationLayerNode,
+    hyper_ig::node::HyperIGNode,
+    utils::logging,
+};
+use crate::network::NetworkPartitions;
+
+// ------------------------------------------------------------------------------------------------
+// Fault Timeline
+// ------------------------------------------------------------------------------------------------
+
+/// A fault event of a chain (chains are numbered from 1, as in chain-1)
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum FaultEvent {
+    /// Holds back the messages between the chain and the rest of the network (CL -> HIG and HIG -> HS)
+    Partition { chain: usize },
+    /// Heals the partition of the chain (unset = all chains) and delivers the held messages
+    Heal {
+        #[serde(default)]
+        chain: Option<usize>,
+    },
+    /// Crashes the HIG of the chain, which stops processing subblocks and sending proposals and
+    /// loses the subblocks delivered until it recovers
+    Crash { chain: usize },
+    /// Restarts the crashed HIG of the chain from the state it had and replays the subblocks it
+    /// missed from the CL
+    Recover { chain: usize },
+}
+
+impl fmt::Display for FaultEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FaultEvent::Partition { chain } => write!(f, "partition chain-{}", chain),
+            FaultEvent::Heal { chain: Some(chain) } => write!(f, "heal chain-{}", chain),
+            FaultEvent::Heal { chain: None } => write!(f, "heal all chains"),
+            FaultEvent::Crash { chain } => write!(f, "crash HIG-{}", chain),
+            FaultEvent::Recover { chain } => write!(f, "recover HIG-{}", chain),
+        }
+    }
+}
+
+/// A fault event of the timeline
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+pub struct ScheduledFault {
+    /// Block at which the event happens, counted from the start of the transaction submission
+    pub at_block: u64,
+    /// The event
+    #[serde(flatten)]
+    pub event: FaultEvent,
+}
+
+/// A fault event executed during the run
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct ExecutedFault {
+    /// Block of the run at which the event was executed, counted from the start of the transaction submission
+    pub block: u64,
+    /// The event
+    #[serde(flatten)]
+    pub event: FaultEvent,
+    /// Subblocks the HIG lost while crashed and replayed from the CL (only for `recover`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lost_subblocks: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replayed_subblocks: Option<u64>,
+}
+
+/// Checks that every event of the timeline is within the run and refers to an existing chain
+///
+/// # Arguments
+/// * `timeline` - The fault timeline
+/// * `num_chains` - Number of chains in the network
+/// * `sim_total_block_number` - Number of blocks of the run
+pub fn validate_fault_timeline(timeline: &[ScheduledFault], num_chains: usize, sim_total_block_number: u64) -> Result<(), String> {
+    let partitioned_chains = partitioned_chains(timeline);
+    for fault in timeline {
+        if fault.at_block >= sim_total_block_number {
+            return Err(format!("Fault '{}' at block {} is after the end of the simulation ({} blocks)", fault.event, fault.at_block, sim_total_block_number));
+        }
+        let chain = match fault.event {
+            FaultEvent::Partition { chain } | FaultEvent::Crash { chain } | FaultEvent::Recover { chain } => chain,
+            FaultEvent::Heal { chain: Some(chain) } => {
+                if !partitioned_chains.contains(&chain) {
+                    return Err(format!("Fault '{}' at block {} heals a chain that is never partitioned", fault.event, fault.at_block));
+                }
+                chain
+            }
+            FaultEvent::Heal { chain: None } => continue,
+        };
+        if chain == 0 || chain > num_chains {
+            return Err(format!("Fault '{}' at block {} targets chain {}, but only chains 1 to {} exist", fault.event, fault.at_block, chain, num_chains));
+        }
+    }
+    Ok(())
+}
+
+/// Returns the chains that are partitioned at some point of the timeline
+fn partitioned_chains(timeline: &[ScheduledFault]) -> BTreeSet<usize> {
+    timeline.iter()
+        .filter_map(|fault| match fault.event {
+            FaultEvent::Partition { chain } => Some(chain),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Creates the partition gates for the chains the timeline partitions
+///
+/// Chains that are never partitioned get no gate, so their links carry no extra relay.
+pub fn network_partitions(timeline: &[ScheduledFault]) -> NetworkPartitions {
+    NetworkPartitions::new(partitioned_chains(timeline).into_iter().map(|chain| chain - 1))
+}
+
+// ------------------------------------------------------------------------------------------------
+// Orchestrator
+// ------------------------------------------------------------------------------------------------
+
+/// Executes the fault timeline during a run
+pub struct FaultOrchestrator {
+    /// Events ordered by their block
+    timeline: Vec<ScheduledFault>,
+    /// Index of the next event to execute
+    next: usize,
+    /// Partition gates of the chains
+    partitions: NetworkPartitions,
+    /// The HIG nodes, in chain order
+    hig_nodes: Vec<Arc<Mutex<HyperIGNode>>>,
+    /// The CL node, which recovering HIGs replay their missed subblocks from
+    cl_node: Arc<Mutex<ConfirmationLayerNode>>,
+    /// Subblocks each crashed HIG had lost before its current crash, by chain
+    lost_before_crash: HashMap<usize, u64>,
+}
+
+impl FaultOrchestrator {
+    /// Creates the orchestrator, keeping the config order of events at the same block
+    ///
+    /// # Arguments
+    /// * `timeline` - The fault timeline
+    /// * `partitions` - Partition gates of the chains, as created by `network_partitions`
+    /// * `hig_nodes` - The HIG nodes, in chain order
+    /// * `cl_node` - The CL node
+    pub fn new(timeline: &[ScheduledFault], partitions: NetworkPartitions, hig_nodes: Vec<Arc<Mutex<HyperIGNode>>>, cl_node: Arc<Mutex<ConfirmationLayerNode>>) -> Self {
+        let mut timeline = timeline.to_vec();
+        timeline.sort_by_key(|fault| fault.at_block);
+        Self { timeline, next: 0, partitions, hig_nodes, cl_node, lost_before_crash: HashMap::new() }
+    }
+
+    /// Executes all events due at or before `blocks_completed`
+    ///
+    /// # Arguments
+    /// * `blocks_completed` - Blocks since the start of the transaction submission
+    ///
+    /// # Returns
+    /// The events that were executed
+    pub async fn execute_due(&mut self, blocks_completed: u64) -> Result<Vec<ExecutedFault>, String> {
+        let mut executed = Vec::new();
+        while let Some(fault) = self.timeline.get(self.next).filter(|fault| fault.at_block <= blocks_completed).copied() {
+            let recovery = self.execute(fault.event).await?;
+            match recovery {
+                Some((lost, replayed)) => logging::log("SIMULATOR", &format!("Block {}: {} ({} subblocks lost, {} replayed from the CL)",
+                    blocks_completed, fault.event, lost, replayed)),
+                None => logging::log("SIMULATOR", &format!("Block {}: {}", blocks_completed, fault.event)),
+            }
+            executed.push(ExecutedFault {
+                block: blocks_completed,
+                event: fault.event,
+                lost_subblocks: recovery.map(|(lost, _)| lost),
+                replayed_subblocks: recovery.map(|(_, replayed)| replayed),
+            });
+            self.next += 1;
+        }
+        Ok(executed)
+    }
+
+    /// Executes a single event
+    ///
+    /// # Returns
+    /// For a recovery, the subblocks the HIG lost while crashed and the subblocks it replayed
+    async fn execute(&mut self, event: FaultEvent) -> Result<Option<(u64, u64)>, String> {
+        match event {
+            FaultEvent::Partition { chain } => self.partitions.partition(chain - 1).map(|_| None),
+            FaultEvent::Heal { chain: Some(chain) } => self.partitions.heal(chain - 1).map(|_| None),
+            FaultEvent::Heal { chain: None } => {
+                self.partitions.heal_all();
+                Ok(None)
+            }
+            FaultEvent::Crash { chain } => {
+                let hig_node = self.hig_node(chain)?.clone();
+                let lost = hig_node.lock().await.get_lost_subblock_count().await;
+                self.lost_before_crash.insert(chain, lost);
+                hig_node.lock().await.crash().await;
+                Ok(None)
+            }
+            FaultEvent::Recover { chain } => {
+                let hig_node = self.hig_node(chain)?.clone();
+                let replayed = HyperIGNode::restart(&hig_node, &self.cl_node).await
+                    .map_err(|e| format!("Failed to recover HIG-{}: {}", chain, e))?;
+                let lost = hig_node.lock().await.get_lost_subblock_count().await
+                    .saturating_sub(self.lost_before_crash.remove(&chain).unwrap_or(0));
+                Ok(Some((lost, replayed)))
+            }
+        }
+    }
+
+    /// Returns the HIG node of the chain
+    fn hig_node(&self, chain: usize) -> Result<&Arc<Mutex<HyperIGNode>>, String> {
+        chain.checked_sub(1)
+            .and_then(|index| self.hig_nodes.get(index))
+            .ok_or_else(|| format!("No HIG for chain {} ({} chains)", chain, self.hig_nodes.len()))
+    }
+}
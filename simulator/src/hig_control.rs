@@ -0,0 +1,167 @@
+//! Control channel for changing HIG parameters while a simulation runs.
+//!
+//! The simulation loop sends the changes of the configured schedule once their block is reached,
+//! and a background task applies them to the running HIG nodes. The nodes keep processing
+//! subblocks throughout, so a change takes effect without restarting the run, e.g. to measure
+//! the step response to a spike of the HS message delay.
+
+use std::sync::Arc;
+use std::time::Duration;
+use serde::Serialize;
+use tokio::sync::{Mutex, mpsc};
+use tokio::task::JoinHandle;
+use hyperplane::{
+    hyper_ig::node::HyperIGNode,
+    types::{ChainId, NetworkSpec},
+    utils::logging,
+};
+use crate::config::HigParameterChange;
+
+// ------------------------------------------------------------------------------------------------
+// Control Channel
+// ------------------------------------------------------------------------------------------------
+
+/// A change of one parameter of a running HIG
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HigControlCommand {
+    /// Sets the delay for sending messages to the HS
+    SetHsMessageDelay(Duration),
+    /// Sets the lifetime in blocks of the CATs received from then on
+    SetCatLifetime(u64),
+    /// Sets whether CATs can depend on pending transactions
+    SetAllowCatPendingDependencies(bool),
+}
+
+impl HigControlCommand {
+    /// Applies the command to a HIG node
+    async fn apply(self, hig_node: &Arc<Mutex<HyperIGNode>>) {
+        match self {
+            HigControlCommand::SetHsMessageDelay(delay) => hig_node.lock().await.set_hs_message_delay(delay),
+            HigControlCommand::SetCatLifetime(cat_lifetime) => hig_node.lock().await.set_cat_lifetime(cat_lifetime).await,
+            HigControlCommand::SetAllowCatPendingDependencies(allow) => hig_node.lock().await.set_allow_cat_pending_dependencies(allow).await,
+        }
+    }
+}
+
+/// Channel to the task that applies control commands to the running HIG nodes
+pub struct HigControlChannel {
+    /// Sends (chain index, command) pairs to the control task
+    sender: mpsc::UnboundedSender<(usize, HigControlCommand)>,
+    /// The control task
+    task: JoinHandle<()>,
+    /// Chains of the HIG nodes the commands can be sent to, in chain order
+    chain_ids: Vec<ChainId>,
+}
+
+impl HigControlChannel {
+    /// Spawns the control task for the given HIG nodes
+    ///
+    /// # Arguments
+    ///
+    /// * `hig_nodes` - The HIG nodes, in chain order
+    /// * `network` - The chains of the network
+    pub fn spawn(hig_nodes: Vec<Arc<Mutex<HyperIGNode>>>, network: &NetworkSpec) -> Self {
+        let (sender, mut receiver) = mpsc::unbounded_channel::<(usize, HigControlCommand)>();
+        let chain_ids: Vec<ChainId> = network.chain_ids().iter().take(hig_nodes.len()).cloned().collect();
+        let task = tokio::spawn(async move {
+            while let Some((chain_index, command)) = receiver.recv().await {
+                command.apply(&hig_nodes[chain_index]).await;
+                logging::log("SIMULATOR", &format!("Applied {:?} to the HIG of chain {}", command, chain_index + 1));
+            }
+        });
+        Self { sender, task, chain_ids }
+    }
+
+    /// Sends a command to the HIG of the chain at `chain_index`
+    pub fn send(&self, chain_index: usize, command: HigControlCommand) -> Result<(), String> {
+        if chain_index >= self.chain_ids.len() {
+            return Err(format!("No HIG for chain {} ({} chains)", chain_index + 1, self.chain_ids.len()));
+        }
+        self.sender.send((chain_index, command)).map_err(|_| "HIG control task has stopped".to_string())
+    }
+
+    /// Closes the channel and waits until all sent commands have been applied
+    pub async fn close(self) -> Result<(), String> {
+        drop(self.sender);
+        self.task.await.map_err(|e| format!("HIG control task failed: {}", e))
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Parameter Schedule
+// ------------------------------------------------------------------------------------------------
+
+/// A parameter change sent to a HIG during the run
+#[derive(Debug, Clone, Serialize)]
+pub struct HigParameterUpdate {
+    /// Block of the run at which the change was sent, counted from the start of the transaction submission
+    pub block: u64,
+    /// Chain whose HIG was changed
+    pub chain: String,
+    /// Name of the parameter, as in the config
+    pub parameter: &'static str,
+    /// New value of the parameter
+    pub value: serde_json::Value,
+}
+
+/// Configured HIG parameter changes that have not been sent yet
+pub struct HigParameterSchedule {
+    /// Changes ordered by their block
+    changes: Vec<HigParameterChange>,
+    /// Index of the next change to send
+    next: usize,
+}
+
+impl HigParameterSchedule {
+    /// Creates the schedule, keeping the config order of changes at the same block
+    pub fn new(changes: &[HigParameterChange]) -> Self {
+        let mut changes = changes.to_vec();
+        changes.sort_by_key(|change| change.at_block);
+        Self { changes, next: 0 }
+    }
+
+    /// Sends all changes due at or before `blocks_completed` to the HIGs
+    ///
+    /// # Arguments
+    ///
+    /// * `blocks_completed` - Blocks since the start of the transaction submission
+    /// * `block_interval` - Block interval in seconds, to convert the HS message delay
+    /// * `channel` - The control channel to the HIGs
+    ///
+    /// # Returns
+    ///
+    /// The parameter updates that were sent
+    pub fn send_due(&mut self, blocks_completed: u64, block_interval: f64, channel: &HigControlChannel) -> Result<Vec<HigParameterUpdate>, String> {
+        let mut updates = Vec::new();
+        while let Some(change) = self.changes.get(self.next).filter(|change| change.at_block <= blocks_completed) {
+            let chain_indices: Vec<usize> = match change.chain {
+                Some(chain) => vec![chain - 1],
+                None => (0..channel.chain_ids.len()).collect(),
+            };
+            let mut commands = Vec::new();
+            if let Some(delay_blocks) = change.hs_message_delay {
+                commands.push(("hs_message_delay", serde_json::json!(delay_blocks), HigControlCommand::SetHsMessageDelay(Duration::from_secs_f64(block_interval * delay_blocks))));
+            }
+            if let Some(cat_lifetime) = change.cat_lifetime_blocks {
+                commands.push(("cat_lifetime_blocks", serde_json::json!(cat_lifetime), HigControlCommand::SetCatLifetime(cat_lifetime)));
+            }
+            if let Some(allow) = change.allow_cat_pending_dependencies {
+                commands.push(("allow_cat_pending_dependencies", serde_json::json!(allow), HigControlCommand::SetAllowCatPendingDependencies(allow)));
+            }
+            for chain_index in chain_indices {
+                for (parameter, value, command) in &commands {
+                    channel.send(chain_index, *command)?;
+                    logging::log("SIMULATOR", &format!("Block {}: setting {} of chain {} to {}", blocks_completed, parameter, chain_index + 1, value));
+                    updates.push(HigParameterUpdate {
+                        block: blocks_completed,
+                        chain: channel.chain_ids[chain_index].0.clone(),
+                        parameter: *parameter,
+                        value: value.clone(),
+                    });
+                }
+            }
+            self.next += 1;
+        }
+        Ok(updates)
+    }
+}
@@ -22,6 +22,10 @@ pub enum SimulationType {
     SweepCatRatioConstantCatsPerBlock,
     /// CAT pending dependencies sweep
     SweepCatPendingDependencies,
+    /// Sweep over strict and permissive chains for CAT pending dependencies
+    SweepMixedCatPendingDependencies,
+    /// CL block capacity parameter sweep
+    SweepBlockCapacity,
     /// Block interval sweep with all scaled (TPS scaled to maintain constant txs per block)
     SweepBlockIntervalAllScaled,
     /// Block interval sweep with constant time delay
@@ -30,12 +34,30 @@ pub enum SimulationType {
     SweepBlockIntervalConstantBlockDelay,
     /// CAT lifetime parameter sweep
     SweepCatLifetime,
+    /// CAT lifetime and chain delay ratio parameter sweep
+    SweepCatLifetimeDelayRatio,
     /// Total block number parameter sweep
     SweepTotalBlockNumber,
     /// Chain delay parameter sweep
     SweepChainDelay,
     /// Zipf distribution parameter sweep
     SweepZipf,
+    /// Replay a recorded run against the current build
+    Replay,
+    /// Compare two protocol variants on the same workload
+    Compare,
+    /// Warm standby HIGs taking over from crashed primaries
+    Failover,
+    /// CL block interval changing on a schedule during the run
+    BlockIntervalRamp,
+    /// Source accounts running out of balance, raising the failure rate over the run
+    BalanceExhaustion,
+    /// Throughput of the HS receive loop over the number of registered chains
+    SweepHsChainCount,
+    /// Sweep the parameter configured by the sweep wizard
+    SweepCustom,
+    /// Configure a custom sweep interactively
+    SweepWizard,
     /// Run all test scenarios
     RunAllTests,
     /// Regenerate all plots
@@ -54,24 +76,55 @@ impl SimulationType {
     pub fn from_input(input: &str) -> Option<Self> {
         match input.trim() {
             "1" => Some(SimulationType::Simple),
-            "2" => Some(SimulationType::SweepBlockIntervalAllScaled),
-            "3" => Some(SimulationType::SweepBlockIntervalConstantBlockDelay),
-            "4" => Some(SimulationType::SweepBlockIntervalConstantTimeDelay),
-            "5" => Some(SimulationType::SweepCatLifetime),
-            "6" => Some(SimulationType::SweepCatPendingDependencies),
-            "7" => Some(SimulationType::SweepCatRatio),
-            "8" => Some(SimulationType::SweepChainDelay),
-            "9" => Some(SimulationType::SweepCatRatioConstantCatsPerBlock),
-            "10" => Some(SimulationType::SweepTotalBlockNumber),
-            "11" => Some(SimulationType::SweepZipf),
-            "12" => Some(SimulationType::RunAllTests),
-            "13" => Some(SimulationType::RunMissingTests),
-            "14" => Some(SimulationType::RunAllPlots),
-            "15" => Some(SimulationType::ToggleDebug),
+            "2" => Some(SimulationType::SweepBlockCapacity),
+            "3" => Some(SimulationType::SweepBlockIntervalAllScaled),
+            "4" => Some(SimulationType::SweepBlockIntervalConstantBlockDelay),
+            "5" => Some(SimulationType::SweepBlockIntervalConstantTimeDelay),
+            "6" => Some(SimulationType::SweepCatLifetime),
+            "7" => Some(SimulationType::SweepCatLifetimeDelayRatio),
+            "8" => Some(SimulationType::SweepCatPendingDependencies),
+            "9" => Some(SimulationType::SweepMixedCatPendingDependencies),
+            "10" => Some(SimulationType::SweepCatRatio),
+            "11" => Some(SimulationType::SweepChainDelay),
+            "12" => Some(SimulationType::SweepCatRatioConstantCatsPerBlock),
+            "13" => Some(SimulationType::SweepTotalBlockNumber),
+            "14" => Some(SimulationType::SweepZipf),
+            "15" => Some(SimulationType::Replay),
+            "16" => Some(SimulationType::Compare),
+            "17" => Some(SimulationType::Failover),
+            "18" => Some(SimulationType::BlockIntervalRamp),
+            "19" => Some(SimulationType::BalanceExhaustion),
+            "20" => Some(SimulationType::SweepHsChainCount),
+            "21" => Some(SimulationType::SweepCustom),
+            "22" => Some(SimulationType::SweepWizard),
+            "23" => Some(SimulationType::RunAllTests),
+            "24" => Some(SimulationType::RunMissingTests),
+            "25" => Some(SimulationType::RunAllPlots),
+            "26" => Some(SimulationType::ToggleDebug),
             "0" => Some(SimulationType::Exit),
             _ => None,
         }
     }
+
+    /// Whether the simulation is a parameter sweep (run through `SweepRunner`)
+    pub fn is_sweep(&self) -> bool {
+        matches!(self,
+            SimulationType::SweepBlockCapacity |
+            SimulationType::SweepBlockIntervalAllScaled |
+            SimulationType::SweepBlockIntervalConstantBlockDelay |
+            SimulationType::SweepBlockIntervalConstantTimeDelay |
+            SimulationType::SweepCatLifetime |
+            SimulationType::SweepCatLifetimeDelayRatio |
+            SimulationType::SweepCatPendingDependencies |
+            SimulationType::SweepMixedCatPendingDependencies |
+            SimulationType::SweepCatRatio |
+            SimulationType::SweepCatRatioConstantCatsPerBlock |
+            SimulationType::SweepChainDelay |
+            SimulationType::SweepTotalBlockNumber |
+            SimulationType::SweepZipf |
+            SimulationType::SweepCustom
+        )
+    }
 }
 
 // ------------------------------------------------------------------------------------------------
@@ -94,7 +147,7 @@ impl SimulatorInterface {
     /// Returns the menu text for available simulation types
     pub fn get_menu_text(&self) -> String {
         let debug_status = if self.debug_mode { "ON" } else { "OFF" };
-        format!("Available simulation types:\n  1. Simple simulation\n  2. Sweep Block Interval (All Scaled)\n  3. Sweep Block Interval (Constant Block Delay)\n  4. Sweep Block Interval (Constant Time Delay)\n  5. Sweep CAT lifetime\n  6. Sweep CAT Pending Dependencies\n  7. Sweep CAT ratio\n  8. Sweep Chain Delay\n  9. Sweep TPB (constant CATs per block)\n 10. Sweep Total Block Number\n 11. Sweep Zipf distribution\n  ------------------------\n 12. Run All Tests\n 13. Run Missing Tests Only\n 14. Rerun All Plots Only\n 15. Toggle Debug Mode (currently {})\n  0. Exit", debug_status)
+        format!("Available simulation types:\n  1. Simple simulation\n  2. Sweep Block Capacity\n  3. Sweep Block Interval (All Scaled)\n  4. Sweep Block Interval (Constant Block Delay)\n  5. Sweep Block Interval (Constant Time Delay)\n  6. Sweep CAT lifetime\n  7. Sweep CAT lifetime / delay ratio\n  8. Sweep CAT Pending Dependencies\n  9. Sweep Mixed CAT Pending Dependencies\n 10. Sweep CAT ratio\n 11. Sweep Chain Delay\n 12. Sweep TPB (constant CATs per block)\n 13. Sweep Total Block Number\n 14. Sweep Zipf distribution\n 15. Replay recorded run\n 16. Compare protocol variants\n 17. HIG failover (warm standby)\n 18. Block interval ramp\n 19. Balance exhaustion\n 20. Sweep HS chain count\n 21. Sweep custom parameter\n 22. Sweep configuration wizard\n  ------------------------\n 23. Run All Tests\n 24. Run Missing Tests Only\n 25. Rerun All Plots Only\n 26. Toggle Debug Mode (currently {})\n  0. Exit", debug_status)
     }
 
     /// Displays the simulator menu
@@ -132,10 +185,13 @@ impl SimulatorInterface {
             "sweep_cat_ratio" => "simulator/results/sim_sweep_cat_ratio/data",
             "sweep_tpb_constant_cats_per_block" => "simulator/results/sim_sweep_tpb_constant_cats_per_block/data",
             "sweep_cat_pending_dependencies" => "simulator/results/sim_sweep_cat_pending_dependencies/data",
+            "sweep_mixed_cat_pending_dependencies" => "simulator/results/sim_sweep_mixed_cat_pending_dependencies/data",
+            "sweep_block_capacity" => "simulator/results/sim_sweep_block_capacity/data",
             "sweep_block_interval_constant_time_delay" => "simulator/results/sim_sweep_block_interval_constant_time_delay/data",
             "sweep_block_interval_constant_block_delay" => "simulator/results/sim_sweep_block_interval_constant_block_delay/data",
             "sweep_block_interval_all_scaled" => "simulator/results/sim_sweep_block_interval_all_scaled/data",
             "sweep_cat_lifetime" => "simulator/results/sim_sweep_cat_lifetime/data",
+            "sweep_cat_lifetime_delay_ratio" => "simulator/results/sim_sweep_cat_lifetime_delay_ratio/data",
             "sweep_total_block_number" => "simulator/results/sim_sweep_total_block_number/data",
             "sweep_chain_delay" => "simulator/results/sim_sweep_chain_delay/data",
             "sweep_zipf" => "simulator/results/sim_sweep_zipf/data",
@@ -152,10 +208,13 @@ impl SimulatorInterface {
             ("sweep_cat_ratio", "CAT Ratio Sweep"),
             ("sweep_tpb_constant_cats_per_block", "TPB with Constant CATs per Block Sweep"),
             ("sweep_cat_pending_dependencies", "CAT Pending Dependencies Sweep"),
+            ("sweep_mixed_cat_pending_dependencies", "Mixed CAT Pending Dependencies Sweep"),
+            ("sweep_block_capacity", "Block Capacity Sweep"),
             ("sweep_block_interval_constant_time_delay", "Block Interval (Constant Time Delay) Sweep"),
             ("sweep_block_interval_constant_block_delay", "Block Interval (Constant Block Delay) Sweep"),
             ("sweep_block_interval_all_scaled", "Block Interval (All Scaled) Sweep"),
             ("sweep_cat_lifetime", "CAT Lifetime Sweep"),
+            ("sweep_cat_lifetime_delay_ratio", "CAT Lifetime / Delay Ratio Sweep"),
             ("sweep_total_block_number", "Total Block Number Sweep"),
             ("sweep_chain_delay", "Chain Delay Sweep"),
             ("sweep_zipf", "Zipf Distribution Sweep"),
@@ -191,10 +250,13 @@ impl SimulatorInterface {
                 "sweep_cat_ratio" => SimulationType::SweepCatRatio,
                 "sweep_tpb_constant_cats_per_block" => SimulationType::SweepCatRatioConstantCatsPerBlock,
                 "sweep_cat_pending_dependencies" => SimulationType::SweepCatPendingDependencies,
+                "sweep_mixed_cat_pending_dependencies" => SimulationType::SweepMixedCatPendingDependencies,
+                "sweep_block_capacity" => SimulationType::SweepBlockCapacity,
                 "sweep_block_interval_constant_time_delay" => SimulationType::SweepBlockIntervalConstantTimeDelay,
                 "sweep_block_interval_constant_block_delay" => SimulationType::SweepBlockIntervalConstantBlockDelay,
                 "sweep_block_interval_all_scaled" => SimulationType::SweepBlockIntervalAllScaled,
                 "sweep_cat_lifetime" => SimulationType::SweepCatLifetime,
+                "sweep_cat_lifetime_delay_ratio" => SimulationType::SweepCatLifetimeDelayRatio,
                 "sweep_total_block_number" => SimulationType::SweepTotalBlockNumber,
                 "sweep_chain_delay" => SimulationType::SweepChainDelay,
                 "sweep_zipf" => SimulationType::SweepZipf,
@@ -207,6 +269,7 @@ impl SimulatorInterface {
             
             if let Some(config) = registry_guard.get(&simulation_type) {
                 // Run the simulation
+                self.check_config(&registry_guard, &simulation_type)?;
                 let run_future = (config.run_fn)();
                 if let Err(e) = run_future.await {
                     return Err(format!("Failed to run {}: {}", name, e));
@@ -234,14 +297,21 @@ impl SimulatorInterface {
     pub fn generate_plots(&self, simulation_type: &str) -> Result<(), String> {
         let script_path = match simulation_type {
             "simple" => "simulator/src/scenarios/sim_simple/plot_results.py",
+            "failover" => "simulator/src/scenarios/sim_failover/plot_results.py",
+            "block_interval_ramp" => "simulator/src/scenarios/sim_block_interval_ramp/plot_results.py",
+            "balance_exhaustion" => "simulator/src/scenarios/sim_balance_exhaustion/plot_results.py",
+            "sweep_hs_chain_count" => "simulator/src/scenarios/sim_sweep_hs_chain_count/plot_results.py",
 
             "sweep_cat_ratio" => "simulator/src/scenarios/sim_sweep_cat_ratio/plot_results.py",
             "sweep_tpb_constant_cats_per_block" => "simulator/src/scenarios/sim_sweep_tpb_constant_cats_per_block/plot_results.py",
             "sweep_cat_pending_dependencies" => "simulator/src/scenarios/sim_sweep_cat_pending_dependencies/plot_results.py",
+            "sweep_mixed_cat_pending_dependencies" => "simulator/src/scenarios/sim_sweep_mixed_cat_pending_dependencies/plot_results.py",
+            "sweep_block_capacity" => "simulator/src/scenarios/sim_sweep_block_capacity/plot_results.py",
             "sweep_block_interval_constant_time_delay" => "simulator/src/scenarios/sim_sweep_block_interval_constant_time_delay/plot_results.py",
             "sweep_block_interval_constant_block_delay" => "simulator/src/scenarios/sim_sweep_block_interval_constant_block_delay/plot_results.py",
             "sweep_block_interval_all_scaled" => "simulator/src/scenarios/sim_sweep_block_interval_all_scaled/plot_results.py",
             "sweep_cat_lifetime" => "simulator/src/scenarios/sim_sweep_cat_lifetime/plot_results.py",
+            "sweep_cat_lifetime_delay_ratio" => "simulator/src/scenarios/sim_sweep_cat_lifetime_delay_ratio/plot_results.py",
             "sweep_total_block_number" => "simulator/src/scenarios/sim_sweep_total_block_number/plot_results.py",
             "sweep_chain_delay" => "simulator/src/scenarios/sim_sweep_chain_delay/plot_results.py",
             "sweep_zipf" => "simulator/src/scenarios/sim_sweep_zipf/plot_results.py",
@@ -269,6 +339,13 @@ impl SimulatorInterface {
             return Err(format!("Plot generation failed with status: {}", status));
         }
 
+        // Link the generated plots from the sweep's Markdown summary
+        if simulation_type.starts_with("sweep_") {
+            if let Err(e) = crate::report::generate_sweep_markdown(&format!("sim_{}", simulation_type)) {
+                println!("Warning: failed to update the Markdown summary: {}", e);
+            }
+        }
+
         Ok(())
     }
 
@@ -302,27 +379,26 @@ impl SimulatorInterface {
                     self.toggle_debug_mode();
                     continue;
                 }
+                Some(SimulationType::SweepWizard) => {
+                    // Only leave the menu if the configured sweep was started
+                    if self.run_sweep_wizard().await? {
+                        println!("Custom sweep completed successfully!");
+                        break;
+                    }
+                    continue;
+                }
 
                 Some(simulation_type) => {
                     // Check if this is a sweep simulation
-                    let is_sweep = matches!(simulation_type, 
-                        SimulationType::SweepBlockIntervalAllScaled |
-                        SimulationType::SweepBlockIntervalConstantBlockDelay |
-                        SimulationType::SweepBlockIntervalConstantTimeDelay |
-                        SimulationType::SweepCatLifetime |
-                        SimulationType::SweepCatPendingDependencies |
-                        SimulationType::SweepCatRatio |
-                        SimulationType::SweepCatRatioConstantCatsPerBlock |
-                        SimulationType::SweepChainDelay |
-                        SimulationType::SweepTotalBlockNumber |
-                        SimulationType::SweepZipf
-                    );
+                    // The custom sweep writes to a configured directory, so its plots are not rerun from here
+                    let is_sweep = simulation_type.is_sweep() && simulation_type != SimulationType::SweepCustom;
                     
                     if is_sweep {
                         // Show sub-menu for sweep simulations
                         println!("\nSweep options:");
                         println!("  1) Run simulation");
                         println!("  2) Rerun plots");
+                        println!("  3) Dry run (validate the config and estimate the duration)");
                         print!("Select option: ");
                         io::stdout().flush().unwrap();
                         
@@ -338,6 +414,8 @@ impl SimulatorInterface {
                                 if let Some(config) = registry_guard.get(&simulation_type) {
                                     println!("Running {}...", config.name);
                                     
+                                    self.check_config(&registry_guard, &simulation_type)?;
+                                    
                                     let run_future = (config.run_fn)();
                                     if let Err(e) = run_future.await {
                                         return Err(e);
@@ -348,11 +426,14 @@ impl SimulatorInterface {
                                         println!("Generating plots...");
                                         let plot_type = match simulation_type {
                                             SimulationType::Simple => "simple",
+                                            SimulationType::SweepBlockCapacity => "sweep_block_capacity",
                                             SimulationType::SweepBlockIntervalAllScaled => "sweep_block_interval_all_scaled",
                                             SimulationType::SweepBlockIntervalConstantBlockDelay => "sweep_block_interval_constant_block_delay",
                                             SimulationType::SweepBlockIntervalConstantTimeDelay => "sweep_block_interval_constant_time_delay",
                                             SimulationType::SweepCatLifetime => "sweep_cat_lifetime",
+                                            SimulationType::SweepCatLifetimeDelayRatio => "sweep_cat_lifetime_delay_ratio",
                                             SimulationType::SweepCatPendingDependencies => "sweep_cat_pending_dependencies",
+                                            SimulationType::SweepMixedCatPendingDependencies => "sweep_mixed_cat_pending_dependencies",
                                             SimulationType::SweepCatRatio => "sweep_cat_ratio",
                                             SimulationType::SweepCatRatioConstantCatsPerBlock => "sweep_tpb_constant_cats_per_block",
                                             SimulationType::SweepChainDelay => "sweep_chain_delay",
@@ -374,11 +455,14 @@ impl SimulatorInterface {
                             "2" => {
                                 // Rerun plots
                                 let plot_type = match simulation_type {
+                                    SimulationType::SweepBlockCapacity => "sweep_block_capacity",
                                     SimulationType::SweepBlockIntervalAllScaled => "sweep_block_interval_all_scaled",
                                     SimulationType::SweepBlockIntervalConstantBlockDelay => "sweep_block_interval_constant_block_delay",
                                     SimulationType::SweepBlockIntervalConstantTimeDelay => "sweep_block_interval_constant_time_delay",
                                     SimulationType::SweepCatLifetime => "sweep_cat_lifetime",
+                                    SimulationType::SweepCatLifetimeDelayRatio => "sweep_cat_lifetime_delay_ratio",
                                     SimulationType::SweepCatPendingDependencies => "sweep_cat_pending_dependencies",
+                                    SimulationType::SweepMixedCatPendingDependencies => "sweep_mixed_cat_pending_dependencies",
                                     SimulationType::SweepCatRatio => "sweep_cat_ratio",
                                     SimulationType::SweepCatRatioConstantCatsPerBlock => "sweep_tpb_constant_cats_per_block",
                                     SimulationType::SweepChainDelay => "sweep_chain_delay",
@@ -392,9 +476,12 @@ impl SimulatorInterface {
                                 }
                                 println!("Plots rerun successfully!");
                             }
+                            "3" => {
+                                crate::dry_run::print_dry_run(&simulation_type).await?;
+                            }
 
                             _ => {
-                                println!("Invalid option. Please enter '1' or '2'.");
+                                println!("Invalid option. Please enter '1', '2' or '3'.");
                             }
                         }
                     } else {
@@ -405,6 +492,8 @@ impl SimulatorInterface {
                         if let Some(config) = registry_guard.get(&simulation_type) {
                             println!("Running {}...", config.name);
                             
+                            self.check_config(&registry_guard, &simulation_type)?;
+                            
                             let run_future = (config.run_fn)();
                             if let Err(e) = run_future.await {
                                 return Err(e);
@@ -415,6 +504,10 @@ impl SimulatorInterface {
                                 println!("Generating plots...");
                                 let plot_type = match simulation_type {
                                     SimulationType::Simple => "simple",
+                                    SimulationType::Failover => "failover",
+                                    SimulationType::BlockIntervalRamp => "block_interval_ramp",
+                                    SimulationType::BalanceExhaustion => "balance_exhaustion",
+                                    SimulationType::SweepHsChainCount => "sweep_hs_chain_count",
                                     _ => "unknown",
                                 };
                                 
@@ -440,19 +533,124 @@ impl SimulatorInterface {
         Ok(())
     }
 
+    /// Checks a simulation's configuration against its declared schema before it is launched,
+    /// printing parameters that are not declared and therefore ignored
+    fn check_config(&self, registry: &crate::simulation_registry::SimulationRegistry, simulation_type: &SimulationType) -> Result<(), String> {
+        let warnings = registry.validate_config(simulation_type)
+            .map_err(|e| format!("Invalid configuration: {}", e))?;
+        for warning in warnings {
+            println!("Warning: {}", warning);
+        }
+        Ok(())
+    }
+
+    /// Prompts for a value, returning the default if the input is empty
+    fn prompt(&self, question: &str, default: &str) -> String {
+        print!("{} [{}]: ", question, default);
+        io::stdout().flush().unwrap();
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input).expect("Failed to read input");
+        let input = input.trim();
+        if input.is_empty() { default.to_string() } else { input.to_string() }
+    }
+
+    /// Guides through the configuration of a custom sweep
+    ///
+    /// Asks for the parameter to sweep, its range, the number of runs and the results directory,
+    /// validates every simulation of the sweep, writes the custom sweep's config.toml and
+    /// optionally starts the sweep.
+    ///
+    /// # Returns
+    /// Whether the sweep was started
+    pub async fn run_sweep_wizard(&self) -> Result<bool, String> {
+        use crate::scenarios::sim_sweep_custom::simulation::{CustomSweep, SWEEPABLE_PARAMETERS, CONFIG_PATH, sweepable_parameter};
+
+        println!("\nSweepable parameters:");
+        let parameters: Vec<_> = SWEEPABLE_PARAMETERS.iter().filter_map(|path| sweepable_parameter(path)).collect();
+        for (index, parameter) in parameters.iter().enumerate() {
+            println!(" {:2}. {} ({})", index + 1, parameter.path, parameter.description);
+        }
+        let Some(parameter) = self.prompt("Parameter to sweep", "1").parse::<usize>().ok()
+            .and_then(|choice| choice.checked_sub(1))
+            .and_then(|index| parameters.get(index))
+        else {
+            println!("Invalid parameter, aborting the wizard.");
+            return Ok(false);
+        };
+
+        let parse_number = |answer: String, what: &str| -> Option<f64> {
+            let value = answer.parse::<f64>().ok();
+            if value.is_none() {
+                println!("Invalid {}, aborting the wizard.", what);
+            }
+            value
+        };
+        // Start from the parameter's default, or from the smallest allowed value for required parameters
+        let default_start = parameter.default.and_then(|default| default.parse::<f64>().ok()).or(parameter.min).unwrap_or(0.0);
+        let Some(start) = parse_number(self.prompt("First value", &default_start.to_string()), "first value") else { return Ok(false) };
+        let Some(end) = parse_number(self.prompt("Last value", &start.to_string()), "last value") else { return Ok(false) };
+        let Ok(num_simulations) = self.prompt("Number of simulations", "5").parse::<usize>() else {
+            println!("Invalid number of simulations, aborting the wizard.");
+            return Ok(false);
+        };
+        let Ok(num_runs) = self.prompt("Runs per simulation", "1").parse::<u32>() else {
+            println!("Invalid number of runs, aborting the wizard.");
+            return Ok(false);
+        };
+        let results_dir = self.prompt("Results directory under simulator/results", "sim_sweep_custom");
+        let base_config = self.prompt("Base configuration", "simulator/src/scenarios/sim_simple/config.toml");
+
+        let sweep = CustomSweep {
+            parameter: parameter.path.to_string(),
+            start,
+            step: if num_simulations > 1 { (end - start) / (num_simulations - 1) as f64 } else { 0.0 },
+            num_simulations,
+            num_runs,
+            results_dir,
+            base_config,
+        };
+        if let Err(e) = sweep.validate() {
+            println!("The sweep is not valid: {}", e);
+            return Ok(false);
+        }
+        println!("Values of {}: {:?}", sweep.parameter, sweep.parameter_values());
+        if Path::new(&format!("simulator/results/{}/data", sweep.results_dir)).exists() {
+            println!("Note: the results in simulator/results/{} will be overwritten.", sweep.results_dir);
+        }
+
+        sweep.save().map_err(|e| format!("Failed to write {}: {}", CONFIG_PATH, e))?;
+        println!("Sweep configuration written to {}", CONFIG_PATH);
+
+        if !self.prompt("Start the sweep now? (y/n)", "n").eq_ignore_ascii_case("y") {
+            println!("Run it later with 'Sweep custom parameter'.");
+            return Ok(false);
+        }
+        crate::scenarios::sim_sweep_custom::simulation::run_sweep_custom_simulation().await
+            .map_err(|e| format!("Custom sweep failed: {}", e))?;
+        Ok(true)
+    }
+
     /// Reruns all plot generation scripts
     pub fn rerun_all_plots(&self) -> Result<(), String> {
         let plot_scripts = [
             ("1. Simple Simulation", "sim_simple", "simulator/src/scenarios/sim_simple/plot_results.py"),
-            ("2. Sweep Block Interval (All Scaled)", "sweep_block_interval_all_scaled", "simulator/src/scenarios/sim_sweep_block_interval_all_scaled/plot_results.py"),
-            ("3. Sweep Block Interval (Constant Block Delay)", "sweep_block_interval_constant_block_delay", "simulator/src/scenarios/sim_sweep_block_interval_constant_block_delay/plot_results.py"),
-            ("4. Sweep Block Interval (Constant Time Delay)", "sweep_block_interval_constant_time_delay", "simulator/src/scenarios/sim_sweep_block_interval_constant_time_delay/plot_results.py"),
-            ("5. Sweep CAT Lifetime", "sweep_cat_lifetime", "simulator/src/scenarios/sim_sweep_cat_lifetime/plot_results.py"),
-            ("6. Sweep CAT Pending Dependencies", "sweep_cat_pending_dependencies", "simulator/src/scenarios/sim_sweep_cat_pending_dependencies/plot_results.py"),
-            ("7. Sweep CAT Ratio", "sweep_cat_ratio", "simulator/src/scenarios/sim_sweep_cat_ratio/plot_results.py"),
-            ("8. Sweep Chain Delay", "sweep_chain_delay", "simulator/src/scenarios/sim_sweep_chain_delay/plot_results.py"),
-            ("9. Sweep Total Block Number", "sweep_total_block_number", "simulator/src/scenarios/sim_sweep_total_block_number/plot_results.py"),
-            ("10. Sweep Zipf Distribution", "sweep_zipf", "simulator/src/scenarios/sim_sweep_zipf/plot_results.py"),
+            ("2. Sweep Block Capacity", "sweep_block_capacity", "simulator/src/scenarios/sim_sweep_block_capacity/plot_results.py"),
+            ("3. Sweep Block Interval (All Scaled)", "sweep_block_interval_all_scaled", "simulator/src/scenarios/sim_sweep_block_interval_all_scaled/plot_results.py"),
+            ("4. Sweep Block Interval (Constant Block Delay)", "sweep_block_interval_constant_block_delay", "simulator/src/scenarios/sim_sweep_block_interval_constant_block_delay/plot_results.py"),
+            ("5. Sweep Block Interval (Constant Time Delay)", "sweep_block_interval_constant_time_delay", "simulator/src/scenarios/sim_sweep_block_interval_constant_time_delay/plot_results.py"),
+            ("6. Sweep CAT Lifetime", "sweep_cat_lifetime", "simulator/src/scenarios/sim_sweep_cat_lifetime/plot_results.py"),
+            ("7. Sweep CAT Lifetime / Delay Ratio", "sweep_cat_lifetime_delay_ratio", "simulator/src/scenarios/sim_sweep_cat_lifetime_delay_ratio/plot_results.py"),
+            ("8. Sweep CAT Pending Dependencies", "sweep_cat_pending_dependencies", "simulator/src/scenarios/sim_sweep_cat_pending_dependencies/plot_results.py"),
+            ("9. Sweep Mixed CAT Pending Dependencies", "sweep_mixed_cat_pending_dependencies", "simulator/src/scenarios/sim_sweep_mixed_cat_pending_dependencies/plot_results.py"),
+            ("10. Sweep CAT Ratio", "sweep_cat_ratio", "simulator/src/scenarios/sim_sweep_cat_ratio/plot_results.py"),
+            ("11. Sweep Chain Delay", "sweep_chain_delay", "simulator/src/scenarios/sim_sweep_chain_delay/plot_results.py"),
+            ("12. Sweep Total Block Number", "sweep_total_block_number", "simulator/src/scenarios/sim_sweep_total_block_number/plot_results.py"),
+            ("13. Sweep Zipf Distribution", "sweep_zipf", "simulator/src/scenarios/sim_sweep_zipf/plot_results.py"),
+            ("17. HIG Failover", "sim_failover", "simulator/src/scenarios/sim_failover/plot_results.py"),
+            ("18. Block Interval Ramp", "sim_block_interval_ramp", "simulator/src/scenarios/sim_block_interval_ramp/plot_results.py"),
+            ("19. Balance Exhaustion", "sim_balance_exhaustion", "simulator/src/scenarios/sim_balance_exhaustion/plot_results.py"),
+            ("20. Sweep HS Chain Count", "sim_sweep_hs_chain_count", "simulator/src/scenarios/sim_sweep_hs_chain_count/plot_results.py"),
         ];
         
         for (title, _name, script) in &plot_scripts {
@@ -0,0 +1,185 @@
+//! Account balance invariants for the Hyperplane simulator.
+//!
+//! Scenario configs declare invariants on the account balances of the chains (e.g. the total
+//! supply of a chain stays constant, an account never goes negative). The checker evaluates them
+//! on the state of the HIGs after every block of the run and fails the run at its end if any of
+//! them was violated, naming the invariant and the block at which it first broke.
+
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use std::sync::Arc;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+use hyperplane::{
+    hyper_ig::node::HyperIGNode,
+    hyper_ig::HyperIG,
+    utils::logging,
+};
+
+// ------------------------------------------------------------------------------------------------
+// Invariants
+// ------------------------------------------------------------------------------------------------
+
+/// An invariant on the account balances of a chain (chains are numbered from 1, as in chain-1)
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BalanceInvariant {
+    /// The sum of all balances of the chain does not change from `after_block` on
+    ///
+    /// CAT transfers move funds between chains, so this only holds for workloads without them.
+    ConstantSupply {
+        chain: usize,
+        /// Block from which on the supply is constant, counted from the start of the transaction submission
+        #[serde(default)]
+        after_block: u64,
+    },
+    /// The balance of the account (unset = every account) never drops below zero
+    NonNegative {
+        chain: usize,
+        #[serde(default)]
+        account: Option<u32>,
+    },
+}
+
+impl BalanceInvariant {
+    /// The chain the invariant is declared on
+    pub fn chain(&self) -> usize {
+        match self {
+            BalanceInvariant::ConstantSupply { chain, .. } | BalanceInvariant::NonNegative { chain, .. } => *chain,
+        }
+    }
+}
+
+impl fmt::Display for BalanceInvariant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BalanceInvariant::ConstantSupply { chain, after_block } => write!(f, "constant supply of chain-{} after block {}", chain, after_block),
+            BalanceInvariant::NonNegative { chain, account: Some(account) } => write!(f, "non-negative balance of account {} on chain-{}", account, chain),
+            BalanceInvariant::NonNegative { chain, account: None } => write!(f, "non-negative balances on chain-{}", chain),
+        }
+    }
+}
+
+/// The first violation of an invariant during a run
+#[derive(Debug, Clone, Serialize)]
+pub struct InvariantViolation {
+    /// The violated invariant
+    pub invariant: BalanceInvariant,
+    /// Block of the run at which the violation was detected, counted from the start of the transaction submission
+    pub block: u64,
+    /// What was observed
+    pub details: String,
+}
+
+impl fmt::Display for InvariantViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} violated at block {}: {}", self.invariant, self.block, self.details)
+    }
+}
+
+/// Checks that every invariant refers to an existing chain
+///
+/// # Arguments
+/// * `invariants` - The declared invariants
+/// * `num_chains` - Number of chains in the network
+pub fn validate_invariants(invariants: &[BalanceInvariant], num_chains: usize) -> Result<(), String> {
+    for invariant in invariants {
+        let chain = invariant.chain();
+        if chain == 0 || chain > num_chains {
+            return Err(format!("Invariant '{}' targets chain {}, but only chains 1 to {} exist", invariant, chain, num_chains));
+        }
+    }
+    Ok(())
+}
+
+// ------------------------------------------------------------------------------------------------
+// Checker
+// ------------------------------------------------------------------------------------------------
+
+/// Evaluates the invariants on the chain states during a run
+pub struct InvariantChecker {
+    /// The declared invariants
+    invariants: Vec<BalanceInvariant>,
+    /// Total supply at the start of each constant supply invariant (by invariant index)
+    reference_supplies: HashMap<usize, i64>,
+    /// First violation of each violated invariant, in the order they were detected
+    violations: Vec<(usize, InvariantViolation)>,
+}
+
+impl InvariantChecker {
+    /// Creates the checker
+    ///
+    /// # Arguments
+    /// * `invariants` - The declared invariants
+    pub fn new(invariants: &[BalanceInvariant]) -> Self {
+        Self {
+            invariants: invariants.to_vec(),
+            reference_supplies: HashMap::new(),
+            violations: Vec::new(),
+        }
+    }
+
+    /// Evaluates the invariants on the current state of the chains
+    ///
+    /// Only the first violation of each invariant is recorded. Without invariants, the chain
+    /// states are not read.
+    ///
+    /// # Arguments
+    /// * `hig_nodes` - The HIG nodes, in chain order
+    /// * `block` - Block of the run, counted from the start of the transaction submission
+    pub async fn check(&mut self, hig_nodes: &[Arc<Mutex<HyperIGNode>>], block: u64) -> Result<(), String> {
+        let chains: BTreeSet<usize> = self.invariants.iter().map(BalanceInvariant::chain).collect();
+        let mut states: HashMap<usize, HashMap<String, i64>> = HashMap::new();
+        for chain in chains {
+            let hig_node = hig_nodes.get(chain - 1)
+                .ok_or_else(|| format!("Invariants target chain {}, but only {} HIGs are running", chain, hig_nodes.len()))?;
+            let state = hig_node.lock().await.get_chain_state().await.map_err(|e| e.to_string())?;
+            states.insert(chain, state);
+        }
+
+        for (index, invariant) in self.invariants.iter().enumerate() {
+            if self.violations.iter().any(|(violated, _)| *violated == index) {
+                continue;
+            }
+            let state = &states[&invariant.chain()];
+            let details = match invariant {
+                BalanceInvariant::ConstantSupply { after_block, .. } => {
+                    if block < *after_block {
+                        continue;
+                    }
+                    let supply: i64 = state.values().sum();
+                    let reference = *self.reference_supplies.entry(index).or_insert(supply);
+                    (supply != reference).then(|| format!("total supply changed from {} to {}", reference, supply))
+                }
+                BalanceInvariant::NonNegative { account: Some(account), .. } => {
+                    state.get(&account.to_string()).copied()
+                        .filter(|balance| *balance < 0)
+                        .map(|balance| format!("balance is {}", balance))
+                }
+                BalanceInvariant::NonNegative { account: None, .. } => {
+                    let mut negative: Vec<(&String, &i64)> = state.iter().filter(|(_, balance)| **balance < 0).collect();
+                    negative.sort();
+                    (!negative.is_empty()).then(|| {
+                        let accounts: Vec<String> = negative.iter().map(|(account, balance)| format!("{}={}", account, balance)).collect();
+                        format!("negative balances {}", accounts.join(", "))
+                    })
+                }
+            };
+            if let Some(details) = details {
+                let violation = InvariantViolation { invariant: invariant.clone(), block, details };
+                logging::log("SIMULATOR", &format!("Invariant {}", violation));
+                self.violations.push((index, violation));
+            }
+        }
+        Ok(())
+    }
+
+    /// Fails with a message listing every violated invariant
+    pub fn finish(&self) -> Result<(), String> {
+        if self.violations.is_empty() {
+            return Ok(());
+        }
+        let violations: Vec<String> = self.violations.iter().map(|(_, violation)| violation.to_string()).collect();
+        Err(format!("{} balance invariant(s) violated: {}", violations.len(), violations.join("; ")))
+    }
+}
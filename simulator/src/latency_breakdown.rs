@@ -0,0 +1,198 @@
+//! Breakdown of the end-to-end latency of every transaction into its phases.
+//!
+//! The mean latency of a run does not show which part of the protocol it is spent in. This report
+//! decomposes the latency of every finished transaction along the chain that finalized it last:
+//! the wait in the CL mempool, the delivery of the subblock to the HIG, the wait for locked keys
+//! and the execution, and for CATs the wait for the HS decision and the round trip of the status
+//! update. Averaging the phases per transaction kind shows which part of the protocol an
+//! optimization has to target.
+
+use serde::Serialize;
+use crate::timeline::{ChainTimeline, TransactionTimeline};
+
+// ------------------------------------------------------------------------------------------------
+// Constants
+// ------------------------------------------------------------------------------------------------
+
+/// Name of the latency breakdown report (inside a run's data directory)
+pub const LATENCY_BREAKDOWN_FILE: &str = "latency_breakdown.json";
+
+// ------------------------------------------------------------------------------------------------
+// Data Structures
+// ------------------------------------------------------------------------------------------------
+
+/// Time a transaction spent in each phase, in milliseconds
+///
+/// The phases add up to the latency of the transaction. For regular transactions the HS decision
+/// and the status update are zero; for CATs the execution ends when the proposal is queued.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct LatencyBreakdown {
+    pub mempool_wait_ms: f64,
+    pub subblock_delivery_ms: f64,
+    pub lock_wait_ms: f64,
+    pub execution_ms: f64,
+    pub hs_decision_ms: f64,
+    pub status_update_ms: f64,
+}
+
+/// Latency breakdown of one transaction
+#[derive(Debug, Clone, Serialize)]
+pub struct TransactionLatencyBreakdown {
+    pub cl_id: String,
+    pub is_cat: bool,
+    /// Chain whose finalization ended the transaction
+    pub critical_chain: String,
+    pub latency_ms: f64,
+    pub breakdown: LatencyBreakdown,
+}
+
+/// Latency breakdowns of the finished transactions of a run
+#[derive(Debug, Clone, Default)]
+pub struct LatencyBreakdownReport {
+    pub transactions: Vec<TransactionLatencyBreakdown>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl LatencyBreakdown {
+    /// Names of the phases, in the order of `values`
+    pub const PHASES: [&'static str; 6] = ["mempool wait", "subblock delivery", "lock wait", "execution", "HS decision", "status update"];
+
+    /// The phases in the order of `PHASES`
+    pub fn values(&self) -> [f64; 6] {
+        [self.mempool_wait_ms, self.subblock_delivery_ms, self.lock_wait_ms, self.execution_ms, self.hs_decision_ms, self.status_update_ms]
+    }
+
+    /// Sum of all phases
+    pub fn total_ms(&self) -> f64 {
+        self.values().iter().sum()
+    }
+
+    /// Decomposes the timeline of a transaction on the chain that finalized it
+    ///
+    /// Returns None if the transaction did not pass all phases on the chain. Phases are clamped
+    /// at zero, since the clocks of the nodes can be skewed against each other.
+    ///
+    /// # Arguments
+    /// * `chain` - The timeline on the chain, in milliseconds since the submission
+    /// * `decided_ms` - The time the HS decided the CAT (None for regular transactions)
+    fn from_timeline(chain: &ChainTimeline, decided_ms: Option<f64>) -> Option<Self> {
+        let included = chain.included_ms?;
+        let received = chain.received_ms?;
+        let finalized = chain.finalized_ms?;
+        let phase = |from: f64, to: f64| (to - from).max(0.0);
+        let mut breakdown = Self {
+            mempool_wait_ms: included.max(0.0),
+            subblock_delivery_ms: phase(included, received),
+            lock_wait_ms: chain.lock_wait_ms,
+            ..Self::default()
+        };
+        match (chain.proposed_ms, decided_ms) {
+            (Some(proposed), Some(decided)) => {
+                breakdown.execution_ms = phase(received + chain.lock_wait_ms, proposed);
+                breakdown.hs_decision_ms = phase(proposed, decided);
+                breakdown.status_update_ms = phase(decided.max(proposed), finalized);
+            }
+            // A CAT that failed locally is finalized without a decision of the HS
+            _ => breakdown.execution_ms = phase(received + chain.lock_wait_ms, finalized),
+        }
+        Some(breakdown)
+    }
+
+    /// Mean of each phase over a set of breakdowns
+    fn mean<'a>(breakdowns: impl Iterator<Item = &'a LatencyBreakdown>) -> (usize, Self) {
+        let mut sum = Self::default();
+        let mut count = 0;
+        for breakdown in breakdowns {
+            sum.mempool_wait_ms += breakdown.mempool_wait_ms;
+            sum.subblock_delivery_ms += breakdown.subblock_delivery_ms;
+            sum.lock_wait_ms += breakdown.lock_wait_ms;
+            sum.execution_ms += breakdown.execution_ms;
+            sum.hs_decision_ms += breakdown.hs_decision_ms;
+            sum.status_update_ms += breakdown.status_update_ms;
+            count += 1;
+        }
+        if count == 0 {
+            return (0, sum);
+        }
+        let n = count as f64;
+        (count, Self {
+            mempool_wait_ms: sum.mempool_wait_ms / n,
+            subblock_delivery_ms: sum.subblock_delivery_ms / n,
+            lock_wait_ms: sum.lock_wait_ms / n,
+            execution_ms: sum.execution_ms / n,
+            hs_decision_ms: sum.hs_decision_ms / n,
+            status_update_ms: sum.status_update_ms / n,
+        })
+    }
+}
+
+impl LatencyBreakdownReport {
+    /// Decomposes the latency of every finished transaction
+    ///
+    /// # Arguments
+    /// * `timelines` - The timelines of all CL transactions of the run
+    pub fn from_timelines(timelines: &[TransactionTimeline]) -> Self {
+        let transactions = timelines.iter()
+            .filter(|tx| tx.finished)
+            .filter_map(|tx| {
+                // The chain that finalized last determines the latency
+                let chain = tx.chains.iter().max_by(|a, b| a.finalized_ms.unwrap_or(0.0).total_cmp(&b.finalized_ms.unwrap_or(0.0)))?;
+                let breakdown = LatencyBreakdown::from_timeline(chain, tx.decided_ms)?;
+                Some(TransactionLatencyBreakdown {
+                    cl_id: tx.cl_id.clone(),
+                    is_cat: tx.is_cat,
+                    critical_chain: chain.chain.clone(),
+                    latency_ms: tx.latency_ms,
+                    breakdown,
+                })
+            })
+            .collect();
+        Self { transactions }
+    }
+
+    /// Number of transactions and mean breakdown of the regular transactions or the CATs
+    pub fn mean(&self, cats: bool) -> (usize, LatencyBreakdown) {
+        LatencyBreakdown::mean(self.transactions.iter().filter(|tx| tx.is_cat == cats).map(|tx| &tx.breakdown))
+    }
+
+    /// Returns the mean breakdowns as JSON, for the results of a run
+    pub fn means_json(&self) -> serde_json::Value {
+        let mean = |cats: bool| {
+            let (count, breakdown) = self.mean(cats);
+            let total = breakdown.total_ms();
+            let shares: serde_json::Map<String, serde_json::Value> = LatencyBreakdown::PHASES.iter().zip(breakdown.values())
+                .map(|(phase, ms)| (phase.to_string(), serde_json::json!(if total > 0.0 { ms / total } else { 0.0 })))
+                .collect();
+            serde_json::json!({ "count": count, "mean_ms": breakdown, "mean_total_ms": total, "shares": shares })
+        };
+        serde_json::json!({ "regular": mean(false), "cats": mean(true) })
+    }
+
+    /// Returns the mean breakdowns and the breakdown of every transaction as JSON
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "phases": LatencyBreakdown::PHASES,
+            "mean": self.means_json(),
+            "transactions": self.transactions
+        })
+    }
+
+    /// Human-readable summary of the mean breakdowns, one line per transaction kind
+    pub fn summary_lines(&self) -> Vec<String> {
+        [("Regular transactions", false), ("CATs", true)].into_iter().map(|(label, cats)| {
+            let (count, breakdown) = self.mean(cats);
+            if count == 0 {
+                return format!("{}: none finished", label);
+            }
+            let total = breakdown.total_ms();
+            let phases: Vec<String> = LatencyBreakdown::PHASES.iter().zip(breakdown.values())
+                .filter(|(_, ms)| *ms > 0.0)
+                .map(|(phase, ms)| format!("{} {:.1} ms ({:.0}%)", phase, ms, if total > 0.0 { ms / total * 100.0 } else { 0.0 }))
+                .collect();
+            format!("{} ({} finished, mean {:.1} ms): {}", label, count, total, phases.join(", "))
+        }).collect()
+    }
+}
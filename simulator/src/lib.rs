@@ -25,12 +25,18 @@ pub mod network;
 /// Configuration management, validation, and parameter handling
 pub mod config;
 
+/// Declared structure of the configuration files, for validation and documentation
+pub mod config_schema;
+
 /// Logging utilities for simulation output and debugging
 pub mod logging;
 
 /// Test node setup and management for simulation environment
 pub mod testnodes;
 
+/// Worker threads and core pinning of the runtimes the simulations run on
+pub mod runtime;
+
 /// Interactive interface system for simulation selection and execution
 pub mod interface;
 
@@ -40,6 +46,90 @@ pub mod scenarios;
 /// Performance statistics collection and analysis
 pub mod stats;
 
+/// Post-run state divergence analysis between chains
+pub mod divergence;
+
+/// Workload recording for replaying simulation runs
+pub mod workload;
+
+/// Batched and rate-limited submission of transactions to the CL
+pub mod submission;
+
+/// Control channel for changing HIG parameters during a run
+pub mod hig_control;
+
+/// Scheduled partitions of chains and crashes of HIGs during a run
+pub mod fault_orchestrator;
+
+/// Re-submission of generated CL transactions as duplicates during a run
+pub mod duplicates;
+
+/// Injection of malformed CL transactions during a run
+pub mod malformed;
+
+/// Account balance invariants checked after every block of a run
+pub mod invariants;
+
+/// Warm standby HIGs that take over proposing from a crashed primary
+pub mod failover;
+
+/// Changes of the CL block interval during a run and how the nodes keep up with them
+pub mod block_interval_ramp;
+
+/// Workloads that run the balances of the sending accounts down and how the failure mix evolves
+pub mod balance_exhaustion;
+
+/// Watchdog that detects stalled HIGs during a run
+pub mod watchdog;
+
+/// Report of the slowest transactions of a run with their timelines
+pub mod slowest;
+
+/// Timelines of the transactions of a run, joined from the CL, the HIGs and the HS
+pub mod timeline;
+
+/// Breakdown of the latency of every transaction into mempool wait, delivery, lock wait, execution and HS phases
+pub mod latency_breakdown;
+
+/// Analytic estimate of the key-conflict rate compared to the measured one
+pub mod conflicts;
+
+/// Distribution of the time the HS takes from a CAT's inclusion to its status update
+pub mod decision_latency;
+
+/// Success and timeout rates of the CATs by fanout and longest realized chain delay
+pub mod cat_outcome_buckets;
+
+/// Number of CATs and success rate between every pair of chains
+pub mod chain_pair_matrix;
+
+/// Per-block state roots of the chains and their comparison between replays
+pub mod state_roots;
+
+/// Schema version of the results files and migration of older versions
+pub mod results_schema;
+
+/// Provenance of simulation runs (build, config hash, seeds, times and host)
+pub mod metadata;
+
+/// Index of completed runs with their config hash and headline metrics
+pub mod run_index;
+
+/// Archiving of the raw data of completed sweep points
+pub mod archive;
+
+/// Self-contained HTML reports for sweep results
+pub mod report;
+
+/// Multi-run experiments described in YAML files
+pub mod experiments;
+
+/// Config validation and cost estimates without running a simulation
+pub mod dry_run;
+
+/// Parquet export of per-transaction and per-block records
+pub mod export;
+
 /// Central registry for all simulation types and configurations
 pub mod simulation_registry;
 
@@ -48,7 +138,7 @@ pub mod simulation_registry;
 // ------------------------------------------------------------------------------------------------
 
 // Core simulation components
-pub use run_simulation::run_simulation;
+pub use run_simulation::{run_simulation, run_simulation_with_replay};
 pub use simulation_results::SimulationResults;
 pub use interface::{SimulatorInterface, SimulationType};
 
@@ -66,16 +156,38 @@ pub use testnodes::*;
 // Simple simulation
 pub use scenarios::sim_simple::simulation::run_simple_simulation;
 
+// Replay simulation
+pub use scenarios::sim_replay::simulation::run_replay_simulation;
+
+// Comparison simulation
+pub use scenarios::sim_compare::simulation::run_compare_simulation;
+
+// Failover simulation
+pub use scenarios::sim_failover::simulation::run_failover_simulation;
+
+// Block interval ramp simulation
+pub use scenarios::sim_block_interval_ramp::simulation::run_block_interval_ramp_simulation;
+
+// Balance exhaustion simulation
+pub use scenarios::sim_balance_exhaustion::simulation::run_balance_exhaustion_simulation;
+
+// HS chain count sweep
+pub use scenarios::sim_sweep_hs_chain_count::simulation::run_sweep_hs_chain_count_simulation;
+
 // Sweep simulations
 pub use scenarios::sim_sweep_cat_ratio::simulation::run_sweep_cat_ratio_simulation;
 pub use scenarios::sim_sweep_zipf::simulation::run_sweep_zipf_simulation;
 pub use scenarios::sim_sweep_chain_delay::simulation::run_sweep_chain_delay;
 pub use scenarios::sim_sweep_total_block_number::simulation::run_sweep_total_block_number;
 pub use scenarios::sim_sweep_cat_lifetime::simulation::run_sweep_cat_lifetime_simulation;
+pub use scenarios::sim_sweep_cat_lifetime_delay_ratio::simulation::run_sweep_cat_lifetime_delay_ratio_simulation;
+pub use scenarios::sim_sweep_block_capacity::simulation::run_sweep_block_capacity_simulation;
 pub use scenarios::sim_sweep_block_interval_constant_block_delay::simulation::run_sweep_block_interval_constant_block_delay;
 pub use scenarios::sim_sweep_block_interval_constant_time_delay::simulation::run_sweep_block_interval_constant_time_delay;
 pub use scenarios::sim_sweep_block_interval_all_scaled::simulation::run_sweep_block_interval_all_scaled;
 pub use scenarios::sim_sweep_cat_pending_dependencies::simulation::run_sweep_cat_pending_dependencies_simulation;
+pub use scenarios::sim_sweep_mixed_cat_pending_dependencies::simulation::run_sweep_mixed_cat_pending_dependencies_simulation;
+pub use scenarios::sim_sweep_custom::simulation::run_sweep_custom_simulation;
 
 // Test orchestration
 pub use scenarios::run_all_tests; 
\ No newline at end of file
@@ -0,0 +1,156 @@
+//! Malformed transaction injection for the Hyperplane simulator.
+//!
+//! Submits malformed CL transactions (unknown prefixes, unknown commands, wrong chain lists) next
+//! to the generated workload, so the validation of the CL and the HIGs is exercised under load.
+//! Every malformed transaction is expected to be rejected; the outcomes are counted for the
+//! results instead of aborting the run. The injections are drawn from their own random
+//! generator, so the generated workload is the same with and without them.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use serde::Serialize;
+use tokio::sync::Mutex;
+use hyperplane::{
+    types::{TransactionId, Transaction, CLTransaction, CLTransactionId, ChainId},
+    confirmation_layer::ConfirmationLayer,
+    utils::logging,
+};
+
+/// Offset added to the run seed for the random generator of the malformed transactions
+const MALFORMED_SEED_OFFSET: u64 = 0xbad_da7a;
+
+/// Kind of malformation of an injected transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MalformedKind {
+    /// Data with a prefix other than REGULAR or CAT
+    BadPrefix,
+    /// Data with a known prefix but an unknown command
+    UnknownCommand,
+    /// A transaction for a chain outside its constituent chains, a CAT on a single chain, or an unregistered chain
+    WrongChainList,
+}
+
+impl MalformedKind {
+    const ALL: [MalformedKind; 3] = [MalformedKind::BadPrefix, MalformedKind::UnknownCommand, MalformedKind::WrongChainList];
+}
+
+impl fmt::Display for MalformedKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MalformedKind::BadPrefix => write!(f, "bad prefix"),
+            MalformedKind::UnknownCommand => write!(f, "unknown command"),
+            MalformedKind::WrongChainList => write!(f, "wrong chain list"),
+        }
+    }
+}
+
+/// Outcomes of the injected malformed transactions
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MalformedCounts {
+    /// Number of injected transactions per kind
+    pub injected: BTreeMap<MalformedKind, u64>,
+    /// Number of injected transactions the CL rejected at submission
+    pub rejected_by_cl: u64,
+    /// Number of injected transactions the CL accepted (these must be skipped by the HIGs)
+    pub accepted_by_cl: u64,
+}
+
+/// Submits malformed transactions for a fraction of the generated transactions
+pub struct MalformedInjector {
+    /// Fraction of the generated transactions that are followed by a malformed one
+    ratio: f64,
+    /// Random generator deciding when and how transactions are malformed
+    rng: StdRng,
+    /// Outcomes of the injected transactions
+    counts: MalformedCounts,
+}
+
+impl MalformedInjector {
+    /// Creates the injector
+    ///
+    /// # Arguments
+    ///
+    /// * `ratio` - Fraction of the generated transactions that are followed by a malformed one
+    /// * `run_seed` - Seed of the run
+    pub fn new(ratio: f64, run_seed: u64) -> Self {
+        Self {
+            ratio,
+            rng: StdRng::seed_from_u64(run_seed.wrapping_add(MALFORMED_SEED_OFFSET)),
+            counts: MalformedCounts::default(),
+        }
+    }
+
+    /// Submits a malformed transaction with the probability of the configured ratio
+    ///
+    /// # Arguments
+    ///
+    /// * `cl_node` - A reference to the confirmation layer node
+    /// * `chains` - The registered chains
+    /// * `from_account` - Sender account of the generated transaction
+    /// * `to_account` - Receiver account of the generated transaction
+    pub async fn maybe_submit<C: ConfirmationLayer>(
+        &mut self,
+        cl_node: &Arc<Mutex<C>>,
+        chains: &[ChainId],
+        from_account: usize,
+        to_account: usize,
+    ) {
+        if self.ratio <= 0.0 || !self.rng.gen_bool(self.ratio) {
+            return;
+        }
+        let kind = MalformedKind::ALL[self.rng.gen_range(0..MalformedKind::ALL.len())];
+        let injected: u64 = self.counts.injected.values().sum();
+        let cl_tx = self.build(kind, CLTransactionId(format!("cl-bad-tx_{}", injected)), chains, from_account, to_account);
+        *self.counts.injected.entry(kind).or_insert(0) += 1;
+
+        match cl_node.lock().await.submit_transaction(cl_tx.clone()).await {
+            Err(e) => {
+                self.counts.rejected_by_cl += 1;
+                logging::log("SIMULATOR", &format!("CL rejected malformed transaction {:?} ({}): {}", cl_tx.id, kind, e));
+            }
+            Ok(()) => {
+                self.counts.accepted_by_cl += 1;
+                logging::log("SIMULATOR", &format!("Warning: the CL accepted malformed transaction {:?} ({})", cl_tx.id, kind));
+            }
+        }
+    }
+
+    /// Returns the outcomes of the injected transactions
+    pub fn counts(&self) -> &MalformedCounts {
+        &self.counts
+    }
+
+    /// Builds a malformed CL transaction of the given kind, bypassing the validation of the constructors
+    fn build(&mut self, kind: MalformedKind, cl_id: CLTransactionId, chains: &[ChainId], from_account: usize, to_account: usize) -> CLTransaction {
+        let chain_1 = chains[0].clone();
+        let chain_2 = chains[1].clone();
+        let send = format!("send {} {} 1", from_account, to_account);
+        let (constituent_chains, tx_chains, data) = match kind {
+            MalformedKind::BadPrefix => (vec![chain_1.clone()], vec![chain_1], format!("BOGUS.{}", send)),
+            MalformedKind::UnknownCommand => (vec![chain_1.clone()], vec![chain_1], format!("REGULAR.mint {} 1", to_account)),
+            MalformedKind::WrongChainList => match self.rng.gen_range(0..3) {
+                // A transaction for a chain outside the constituent chains
+                0 => (vec![chain_1], vec![chain_2], format!("REGULAR.{}", send)),
+                // A CAT on a single chain
+                1 => (vec![chain_1.clone()], vec![chain_1], format!("CAT.{}", send)),
+                // A CAT on a chain that is not registered
+                _ => {
+                    let unknown = ChainId("chain-unknown".to_string());
+                    (vec![chain_1.clone(), unknown.clone()], vec![chain_1, unknown], format!("CAT.{}", send))
+                }
+            },
+        };
+        let transactions = tx_chains.into_iter().enumerate().map(|(index, chain_id)| Transaction {
+            id: TransactionId(format!("{:?}:tx{}", cl_id, index + 1)),
+            chain_id,
+            constituent_chains: constituent_chains.clone(),
+            data: data.clone(),
+            cl_id: cl_id.clone(),
+            expiry: None,
+        }).collect();
+        CLTransaction { id: cl_id, constituent_chains, transactions }
+    }
+}
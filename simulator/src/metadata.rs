@@ -0,0 +1,161 @@
+//! Provenance of simulation runs.
+//!
+//! Every run writes a `metadata.json` next to its data that records which build produced it, a
+//! hash of the simulated parameters, the seeds of the workload generator, when it ran, on which
+//! host and on which runtime threads, so result directories stay interpretable long after the run. Comparisons between runs
+//! check the config hashes first, since metrics of differently configured runs are not comparable.
+
+use std::fs;
+use std::process::Command;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sysinfo::System;
+use crate::runtime::RuntimeConfig;
+
+// ------------------------------------------------------------------------------------------------
+// Constants
+// ------------------------------------------------------------------------------------------------
+
+/// Name of the file (inside a run's data directory) that holds the metadata
+pub const METADATA_FILE: &str = "metadata.json";
+
+// ------------------------------------------------------------------------------------------------
+// Data Structures
+// ------------------------------------------------------------------------------------------------
+
+/// Host the run was executed on
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HostInfo {
+    pub hostname: Option<String>,
+    pub os: String,
+    pub arch: String,
+    pub cpus: usize,
+}
+
+/// Provenance of a single run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunMetadata {
+    /// Git commit of the crate (None outside a git checkout)
+    pub crate_commit: Option<String>,
+    /// Whether tracked files differed from the commit
+    pub crate_dirty: bool,
+    pub crate_version: String,
+    /// Hash of the simulated parameters (the `parameters` of `simulation_stats.json`)
+    pub config_hash: String,
+    /// Seed the workload generators of all runs are derived from
+    pub master_seed: u64,
+    /// Seed of the workload generator of this run
+    pub run_seed: u64,
+    /// Start and end of the run (RFC 3339, UTC)
+    pub started_at: String,
+    pub finished_at: String,
+    pub host: HostInfo,
+    /// Worker threads and core pinning of the runtimes the run executed on
+    #[serde(default)]
+    pub runtime: RuntimeConfig,
+}
+
+impl RunMetadata {
+    /// Collects the metadata of a run that finishes now
+    ///
+    /// # Arguments
+    /// * `parameters` - The simulated parameters, as saved in `simulation_stats.json`
+    /// * `master_seed` - The seed all runs are derived from
+    /// * `run_seed` - The seed of this run
+    /// * `started_at` - When the run started
+    pub fn collect(parameters: &serde_json::Value, master_seed: u64, run_seed: u64, started_at: DateTime<Utc>) -> Self {
+        Self {
+            crate_commit: git(&["rev-parse", "HEAD"]),
+            crate_dirty: git(&["status", "--porcelain", "--untracked-files=no"]).is_some_and(|status| !status.is_empty()),
+            crate_version: env!("CARGO_PKG_VERSION").to_string(),
+            config_hash: config_hash(parameters),
+            master_seed,
+            run_seed,
+            started_at: started_at.to_rfc3339(),
+            finished_at: Utc::now().to_rfc3339(),
+            host: HostInfo {
+                hostname: System::host_name(),
+                os: std::env::consts::OS.to_string(),
+                arch: std::env::consts::ARCH.to_string(),
+                cpus: std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1),
+            },
+            runtime: crate::runtime::current(),
+        }
+    }
+
+    /// Saves the metadata to the data directory of a run
+    pub fn save(&self, data_dir: &str) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        fs::write(format!("{}/{}", data_dir, METADATA_FILE), json).map_err(|e| e.to_string())
+    }
+
+    /// Loads the metadata from the data directory of a run (None for runs recorded before metadata was saved)
+    pub fn load(data_dir: &str) -> Result<Option<Self>, String> {
+        let path = format!("{}/{}", data_dir, METADATA_FILE);
+        match fs::read_to_string(&path) {
+            Ok(json) => serde_json::from_str(&json).map(Some).map_err(|e| format!("Failed to parse {}: {}", path, e)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(format!("Failed to read {}: {}", path, e)),
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Seeds and Hashes
+// ------------------------------------------------------------------------------------------------
+
+tokio::task_local! {
+    /// Master seed shared by the simulations an experiment runs in this scope, overriding their configured seeds
+    pub static MASTER_SEED_OVERRIDE: u64;
+}
+
+/// Master seed of a simulation: the seed of the enclosing experiment, else the configured seed, else a random one
+pub fn master_seed(configured: Option<u64>) -> u64 {
+    MASTER_SEED_OVERRIDE.try_with(|seed| *seed).ok().or(configured).unwrap_or_else(rand::random)
+}
+
+/// Seed of the given run (1-based), derived from the master seed so runs differ but stay reproducible
+pub fn run_seed(master_seed: u64, run: u32) -> u64 {
+    master_seed.wrapping_add(u64::from(run.saturating_sub(1)))
+}
+
+/// Hash of the simulated parameters
+///
+/// Uses 64-bit FNV-1a over the serialized parameters, so the hash is stable across builds and platforms.
+pub fn config_hash(parameters: &serde_json::Value) -> String {
+    let hash = parameters.to_string().bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    });
+    format!("{:016x}", hash)
+}
+
+/// Checks that two runs were simulated with the same parameters
+///
+/// Runs without metadata are accepted, since older runs did not record it.
+///
+/// # Arguments
+/// * `left_dir` - The data directory of the first run
+/// * `right_dir` - The data directory of the second run
+/// * `allow_mismatch` - Whether differing parameters are expected (only logged then)
+pub fn check_comparable(left_dir: &str, right_dir: &str, allow_mismatch: bool) -> Result<(), String> {
+    let (Some(left), Some(right)) = (RunMetadata::load(left_dir)?, RunMetadata::load(right_dir)?) else {
+        hyperplane::utils::logging::log("SIMULATOR", "Skipping config hash check: a run has no metadata");
+        return Ok(());
+    };
+    if left.config_hash == right.config_hash {
+        return Ok(());
+    }
+    let message = format!("Config hashes differ: {} has {}, {} has {}", left_dir, left.config_hash, right_dir, right.config_hash);
+    if allow_mismatch {
+        hyperplane::utils::logging::log("SIMULATOR", &format!("{} (allowed)", message));
+        Ok(())
+    } else {
+        Err(format!("Refusing to compare runs with different parameters. {}", message))
+    }
+}
+
+/// Runs git with the given arguments and returns its trimmed output (None if git fails)
+fn git(args: &[&str]) -> Option<String> {
+    let output = Command::new("git").args(args).output().ok()?;
+    output.status.success().then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
@@ -1,11 +1,16 @@
 //! Network setup and account initialization for the Hyperplane simulator.
 //! 
-//! Handles node creation, chain registration, and account funding verification.
+//! Handles node creation, chain registration, account funding verification and the simulated
+//! link layer (per-link delay and jitter between the nodes, and partitions of chains).
 
+use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::{mpsc, Mutex};
+use std::time::Duration;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use tokio::sync::{mpsc, watch, Mutex};
 use hyperplane::{
-    types::{ChainId, CLTransaction, SubBlock},
+    types::{ChainId, CATStatusUpdate, CLTransaction, Clock, SubBlock, Transaction},
     confirmation_layer::ConfirmationLayerNode,
 };
 
@@ -39,4 +44,309 @@ pub async fn create_network(num_nodes: usize, num_chains: usize, channel_buffer_
     nodes
 }
 
- 
\ No newline at end of file
+ 
+
+// ------------------------------------------------------------------------------------------------
+// Topology
+// ------------------------------------------------------------------------------------------------
+
+/// Delay and jitter of a single directed link (in blocks)
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Default)]
+pub struct LinkSpec {
+    /// Fixed delay added to every message (in blocks)
+    #[serde(default)]
+    pub delay: f64,
+    /// Maximum additional random delay per message (in blocks), drawn uniformly from [0, jitter]
+    #[serde(default)]
+    pub jitter: f64,
+}
+
+impl LinkSpec {
+    /// Returns true if the link delivers messages immediately
+    pub fn is_zero(&self) -> bool {
+        self.delay == 0.0 && self.jitter == 0.0
+    }
+}
+
+/// Per-link latency of the simulated network
+///
+/// The per-chain links are listed in chain order (chain-1, chain-2, etc.). Missing entries are
+/// zero-latency links. The HIG -> HS link latency adds to the chain delay (`chain_delays`).
+///
+/// The latency of every message is scaled by the multiplier of its path: messages on the CAT
+/// path (proposals, status updates and subblocks with a CAT or status update) by
+/// `cat_latency_multiplier`, subblocks with only regular transactions by `regular_latency_multiplier`.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct TopologyConfig {
+    /// CL -> HIG links (subblock delivery), one per chain
+    #[serde(default)]
+    pub cl_to_hig: Vec<LinkSpec>,
+    /// HIG -> HS links (CAT status proposals), one per chain
+    #[serde(default)]
+    pub hig_to_hs: Vec<LinkSpec>,
+    /// HS -> CL link (CAT status updates)
+    #[serde(default)]
+    pub hs_to_cl: LinkSpec,
+    /// Factor applied to the link latency of messages on the CAT path
+    #[serde(default = "default_latency_multiplier")]
+    pub cat_latency_multiplier: f64,
+    /// Factor applied to the link latency of subblocks with only regular transactions
+    #[serde(default = "default_latency_multiplier")]
+    pub regular_latency_multiplier: f64,
+}
+
+fn default_latency_multiplier() -> f64 {
+    1.0
+}
+
+impl Default for TopologyConfig {
+    fn default() -> Self {
+        Self {
+            cl_to_hig: Vec::new(),
+            hig_to_hs: Vec::new(),
+            hs_to_cl: LinkSpec::default(),
+            cat_latency_multiplier: default_latency_multiplier(),
+            regular_latency_multiplier: default_latency_multiplier(),
+        }
+    }
+}
+
+impl TopologyConfig {
+    /// Returns the CL -> HIG link of the chain at `chain_index`
+    pub fn cl_to_hig(&self, chain_index: usize) -> LinkSpec {
+        self.cl_to_hig.get(chain_index).copied().unwrap_or_default()
+    }
+
+    /// Returns the HIG -> HS link of the chain at `chain_index`
+    pub fn hig_to_hs(&self, chain_index: usize) -> LinkSpec {
+        self.hig_to_hs.get(chain_index).copied().unwrap_or_default()
+    }
+
+    /// Returns the latency multipliers of the CAT and the regular path
+    pub fn latency_multipliers(&self) -> LatencyMultipliers {
+        LatencyMultipliers { cat: self.cat_latency_multiplier, regular: self.regular_latency_multiplier }
+    }
+
+    /// Checks that every link and multiplier is non-negative and the per-chain links match the number of chains
+    pub fn validate(&self, num_chains: usize) -> Result<(), String> {
+        for (name, links) in [("cl_to_hig", &self.cl_to_hig), ("hig_to_hs", &self.hig_to_hs)] {
+            if !links.is_empty() && links.len() != num_chains {
+                return Err(format!("Topology {} must have one link per chain ({}), got {}", name, num_chains, links.len()));
+            }
+        }
+        let all_links = self.cl_to_hig.iter().chain(self.hig_to_hs.iter()).chain(std::iter::once(&self.hs_to_cl));
+        for link in all_links {
+            if link.delay < 0.0 || link.jitter < 0.0 {
+                return Err("Topology link delay and jitter must be non-negative".into());
+            }
+        }
+        if self.cat_latency_multiplier < 0.0 || self.regular_latency_multiplier < 0.0 {
+            return Err("Topology latency multipliers must be non-negative".into());
+        }
+        Ok(())
+    }
+}
+
+/// Factors applied to the link latency of a message depending on the path it belongs to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LatencyMultipliers {
+    /// Factor for messages on the CAT path
+    pub cat: f64,
+    /// Factor for messages with only regular transactions
+    pub regular: f64,
+}
+
+impl LatencyMultipliers {
+    /// Returns the factor for a message
+    pub fn for_message(&self, message: &impl TransactionPath) -> f64 {
+        if message.is_cat_path() { self.cat } else { self.regular }
+    }
+}
+
+/// Messages that can be attributed to the CAT or the regular transaction path
+pub trait TransactionPath {
+    /// Returns true if the message belongs to the resolution of a CAT
+    fn is_cat_path(&self) -> bool;
+}
+
+/// Returns true if the transaction is a CAT or a status update
+fn is_cat_transaction(tx: &Transaction) -> bool {
+    tx.data.starts_with("CAT") || tx.data.starts_with("STATUS_UPDATE")
+}
+
+impl TransactionPath for SubBlock {
+    /// A subblock is delivered as a whole, so a single CAT or status update puts it on the CAT path
+    fn is_cat_path(&self) -> bool {
+        self.transactions.iter().any(is_cat_transaction)
+    }
+}
+
+impl TransactionPath for CATStatusUpdate {
+    fn is_cat_path(&self) -> bool {
+        true
+    }
+}
+
+impl TransactionPath for CLTransaction {
+    fn is_cat_path(&self) -> bool {
+        self.transactions.iter().any(is_cat_transaction)
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Simulated Link Layer
+// ------------------------------------------------------------------------------------------------
+
+/// Inserts a simulated link in front of a receiver
+///
+/// Every message is delivered after the link's delay plus a random jitter, scaled by the latency
+/// multiplier of its path. Messages keep their order, so a message is never delivered before the
+/// one sent ahead of it. A zero-latency link returns the receiver unchanged.
+///
+/// # Arguments
+/// * `receiver` - The receiving end of the channel to delay
+/// * `link` - The delay and jitter of the link (in blocks)
+/// * `multipliers` - The latency multipliers of the CAT and the regular path
+/// * `block_interval` - The block interval used to convert blocks to time
+/// * `channel_buffer_size` - Buffer size of the delayed channel
+/// * `clock` - The clock the delays elapse on
+pub fn delayed_link<T: TransactionPath + Send + 'static>(
+    mut receiver: mpsc::Receiver<T>,
+    link: LinkSpec,
+    multipliers: LatencyMultipliers,
+    block_interval: Duration,
+    channel_buffer_size: usize,
+    clock: Arc<dyn Clock>,
+) -> mpsc::Receiver<T> {
+    if link.is_zero() {
+        return receiver;
+    }
+
+    let (sender_out, receiver_out) = mpsc::channel(channel_buffer_size);
+    let (sender_in_flight, mut receiver_in_flight) = mpsc::unbounded_channel::<(Duration, T)>();
+
+    // Stamp every message with its delivery time
+    let stamp_clock = clock.clone();
+    tokio::spawn(async move {
+        let mut last_delivery = stamp_clock.now();
+        while let Some(message) = receiver.recv().await {
+            let jitter = if link.jitter > 0.0 { rand::thread_rng().gen_range(0.0..=link.jitter) } else { 0.0 };
+            let latency = (link.delay + jitter) * multipliers.for_message(&message);
+            let delivery = (stamp_clock.now() + block_interval.mul_f64(latency)).max(last_delivery);
+            last_delivery = delivery;
+            if sender_in_flight.send((delivery, message)).is_err() {
+                break;
+            }
+        }
+    });
+
+    // Deliver the messages once their delivery time has come
+    tokio::spawn(async move {
+        while let Some((delivery, message)) = receiver_in_flight.recv().await {
+            clock.sleep_until(delivery).await;
+            if sender_out.send(message).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    receiver_out
+}
+
+// ------------------------------------------------------------------------------------------------
+// Network Partitions
+// ------------------------------------------------------------------------------------------------
+
+/// Partitions of the chains from the rest of the network, changed while the simulation runs
+///
+/// Only chains that were given a gate when the network was set up can be partitioned. While a
+/// chain is partitioned, the messages on its CL -> HIG and HIG -> HS links are held back; they are
+/// delivered in order once the partition heals.
+#[derive(Debug, Clone, Default)]
+pub struct NetworkPartitions {
+    /// Partition state of the gated chains by chain index (true = partitioned)
+    gates: Arc<HashMap<usize, watch::Sender<bool>>>,
+}
+
+impl NetworkPartitions {
+    /// Creates gates for the chains at the given indices, all initially connected
+    pub fn new(chain_indices: impl IntoIterator<Item = usize>) -> Self {
+        let gates = chain_indices.into_iter().map(|index| (index, watch::channel(false).0)).collect();
+        Self { gates: Arc::new(gates) }
+    }
+
+    /// Returns the gate of the chain at `chain_index`, if it can be partitioned
+    pub fn gate(&self, chain_index: usize) -> Option<watch::Receiver<bool>> {
+        self.gates.get(&chain_index).map(|gate| gate.subscribe())
+    }
+
+    /// Returns whether the chain at `chain_index` is currently partitioned
+    pub fn is_partitioned(&self, chain_index: usize) -> bool {
+        self.gates.get(&chain_index).is_some_and(|gate| *gate.borrow())
+    }
+
+    /// Partitions the chain at `chain_index` from the rest of the network
+    pub fn partition(&self, chain_index: usize) -> Result<(), String> {
+        self.set_partitioned(chain_index, true)
+    }
+
+    /// Heals the partition of the chain at `chain_index`
+    pub fn heal(&self, chain_index: usize) -> Result<(), String> {
+        self.set_partitioned(chain_index, false)
+    }
+
+    /// Heals the partitions of all chains
+    pub fn heal_all(&self) {
+        for gate in self.gates.values() {
+            gate.send_replace(false);
+        }
+    }
+
+    fn set_partitioned(&self, chain_index: usize, partitioned: bool) -> Result<(), String> {
+        let gate = self.gates.get(&chain_index)
+            .ok_or_else(|| format!("Chain {} was set up without a partition gate", chain_index + 1))?;
+        gate.send_replace(partitioned);
+        Ok(())
+    }
+}
+
+/// Inserts a partition gate in front of a receiver
+///
+/// Messages pass while the gate reports no partition. During a partition they are buffered
+/// without bound, so the sender is never blocked, and released in order when it heals.
+///
+/// # Arguments
+/// * `receiver` - The receiving end of the channel to gate
+/// * `gate` - The partition state of the link (true = partitioned)
+/// * `channel_buffer_size` - Buffer size of the gated channel
+pub fn gated_link<T: Send + 'static>(
+    mut receiver: mpsc::Receiver<T>,
+    mut gate: watch::Receiver<bool>,
+    channel_buffer_size: usize,
+) -> mpsc::Receiver<T> {
+    let (sender_out, receiver_out) = mpsc::channel(channel_buffer_size);
+    let (sender_held, mut receiver_held) = mpsc::unbounded_channel::<T>();
+
+    // Accept every message right away
+    tokio::spawn(async move {
+        while let Some(message) = receiver.recv().await {
+            if sender_held.send(message).is_err() {
+                break;
+            }
+        }
+    });
+
+    // Release the messages while the link is not partitioned
+    tokio::spawn(async move {
+        while let Some(message) = receiver_held.recv().await {
+            if gate.wait_for(|partitioned| !*partitioned).await.is_err() {
+                break;
+            }
+            if sender_out.send(message).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    receiver_out
+}
@@ -0,0 +1,661 @@
+//! HTML report generation for sweep results.
+//!
+//! Renders a self-contained HTML page per sweep (configuration summary, result tables, inline SVG
+//! charts and flagged anomalies) from the data a sweep writes to its results directory, so results
+//! can be shared without the plotting scripts. The same content is written as Markdown, linking
+//! the charts instead, to be pasted into issues and design docs. Comparisons of two protocol
+//! variants get a side-by-side table and bar charts instead, also exported as Markdown and JSON.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::fs;
+use crate::results_schema;
+
+// ------------------------------------------------------------------------------------------------
+// Constants
+// ------------------------------------------------------------------------------------------------
+
+/// Name of the report file inside a sweep's results directory
+pub const REPORT_FILE: &str = "report.html";
+
+/// Name of the Markdown summary inside a sweep's results directory
+pub const MARKDOWN_SUMMARY_FILE: &str = "results.md";
+
+/// Name of the Markdown comparison table inside a comparison's results directory
+pub const COMPARISON_MARKDOWN_FILE: &str = "comparison.md";
+
+/// Name of the JSON comparison table inside a comparison's results directory
+pub const COMPARISON_JSON_FILE: &str = "comparison.json";
+
+/// A column of the results table: label and the metrics (summed over both chains) it is built from
+struct ReportColumn {
+    label: &'static str,
+    metrics: &'static [&'static str],
+}
+
+/// Columns of the results table, each of which is also charted over the sweep
+const REPORT_COLUMNS: &[ReportColumn] = &[
+    ReportColumn { label: "Transactions sent", metrics: &["simulation_stats.json:results.total_transactions"] },
+    ReportColumn { label: "CAT success", metrics: &["cat_success_transactions_chain_1.json:chain_1_cat_success", "cat_success_transactions_chain_2.json:chain_2_cat_success"] },
+    ReportColumn { label: "CAT failure", metrics: &["cat_failure_transactions_chain_1.json:chain_1_cat_failure", "cat_failure_transactions_chain_2.json:chain_2_cat_failure"] },
+    ReportColumn { label: "CAT timed out", metrics: &["cat_timed_out_transactions_chain_1.json:chain_1_cat_timed_out", "cat_timed_out_transactions_chain_2.json:chain_2_cat_timed_out"] },
+    ReportColumn { label: "CAT pending at end", metrics: &["cat_pending_transactions_chain_1.json:chain_1_cat_pending", "cat_pending_transactions_chain_2.json:chain_2_cat_pending"] },
+    ReportColumn { label: "Regular success", metrics: &["regular_success_transactions_chain_1.json:chain_1_regular_success", "regular_success_transactions_chain_2.json:chain_2_regular_success"] },
+    ReportColumn { label: "Regular failure", metrics: &["regular_failure_transactions_chain_1.json:chain_1_regular_failure", "regular_failure_transactions_chain_2.json:chain_2_regular_failure"] },
+    ReportColumn { label: "Protocol violations", metrics: &["protocol_violations.json:chain_1_protocol_violations", "protocol_violations.json:chain_2_protocol_violations"] },
+    ReportColumn { label: "Diverging accounts", metrics: &["state_divergence.json:divergent_accounts"] },
+    ReportColumn { label: "Duplicates at HIGs", metrics: &["simulation_stats.json:results.duplicate_transactions"] },
+    ReportColumn { label: "Malformed accepted by CL", metrics: &["simulation_stats.json:results.malformed_accepted_by_cl"] },
+    ReportColumn { label: "Rate anomalies", metrics: &["simulation_stats.json:results.rate_anomaly_count"] },
+    ReportColumn { label: "Peak RSS (MB)", metrics: &["simulation_stats.json:results.peak_memory_mb"] },
+    ReportColumn { label: "Mean process CPU (%)", metrics: &["simulation_stats.json:results.mean_cpu_percent"] },
+    ReportColumn { label: "Mean worker CPU (%)", metrics: &["simulation_stats.json:results.mean_worker_cpu_percent"] },
+    ReportColumn { label: "Mean HIG time per subblock (ms)", metrics: &["simulation_stats.json:results.mean_hig_processing_ms"] },
+];
+
+// ------------------------------------------------------------------------------------------------
+// Data Structures
+// ------------------------------------------------------------------------------------------------
+
+/// Results of a scenario, averaged over its simulations
+pub struct ResultsSummary {
+    /// Number of simulations (parameter values) of the scenario
+    pub simulations: usize,
+    /// Number of runs found on disk, over all simulations
+    pub runs_found: usize,
+    /// Number of runs expected on disk, over all simulations
+    pub expected_runs: usize,
+    /// Label and value of each report column, averaged over the simulations
+    pub metrics: Vec<(&'static str, f64)>,
+    /// Anomalies detected in any of the simulations
+    pub anomalies: Vec<String>,
+}
+
+/// Averaged results of one simulation (parameter value) of a sweep
+struct SimulationSummary {
+    /// The parameter value, formatted for display
+    parameter: String,
+    /// Number of runs found on disk
+    runs_found: usize,
+    /// Value of each report column, averaged over the runs
+    values: Vec<f64>,
+    /// Anomalies detected for this simulation
+    anomalies: Vec<String>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Metric Collection
+// ------------------------------------------------------------------------------------------------
+
+/// Collects the final value of every metric saved in a run's data directory
+///
+/// Time series (`{"key": [{"height": .., "count": ..}]}`) contribute their last value, numeric
+/// fields contribute themselves, and nested objects contribute their numeric fields. Metrics are
+/// keyed by `file:key` (and `file:key.field` for nested objects). The simulation stats are
+/// migrated to the current results schema first, so runs written by older builds stay comparable.
+pub fn final_metric_values(data_dir: &str) -> Result<BTreeMap<String, f64>, String> {
+    let mut values = BTreeMap::new();
+    let entries = fs::read_dir(data_dir).map_err(|e| format!("Failed to read {}: {}", data_dir, e))?;
+
+    for entry in entries.flatten() {
+        let filename = entry.file_name().to_string_lossy().to_string();
+        if !filename.ends_with(".json") || filename == crate::workload::WORKLOAD_FILE {
+            continue;
+        }
+        let Ok(content) = fs::read_to_string(entry.path()) else { continue };
+        let Ok(mut value) = serde_json::from_str::<serde_json::Value>(&content) else { continue };
+        if filename == results_schema::SIMULATION_STATS_FILE {
+            value = results_schema::migrate(value)?;
+        }
+        let serde_json::Value::Object(object) = value else { continue };
+
+        for (key, value) in object {
+            if key == results_schema::SCHEMA_VERSION_KEY {
+                continue;
+            }
+            let metric = format!("{}:{}", filename, key);
+            match value {
+                serde_json::Value::Number(number) => {
+                    if let Some(number) = number.as_f64() {
+                        values.insert(metric, number);
+                    }
+                }
+                serde_json::Value::Array(series) => {
+                    let last_value = series.last()
+                        .and_then(|point| point.as_object())
+                        .and_then(|point| point.iter().find(|(field, _)| field.as_str() != "height").and_then(|(_, v)| v.as_f64()));
+                    if let Some(last_value) = last_value {
+                        values.insert(metric, last_value);
+                    }
+                }
+                serde_json::Value::Object(fields) => {
+                    for (field, value) in fields {
+                        if let Some(number) = value.as_f64() {
+                            values.insert(format!("{}.{}", metric, field), number);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    Ok(values)
+}
+
+/// Averages the report columns over all runs of one simulation and flags anomalies
+fn summarize_simulation(sim_dir: &str, parameter: String, expected_runs: usize) -> SimulationSummary {
+    let mut totals = vec![0.0; REPORT_COLUMNS.len()];
+    let mut runs_found = 0;
+    for run in 0..expected_runs {
+        let Ok(metrics) = final_metric_values(&format!("{}/run_{}/data", sim_dir, run)) else { continue };
+        runs_found += 1;
+        for (total, column) in totals.iter_mut().zip(REPORT_COLUMNS) {
+            *total += column.metrics.iter().filter_map(|metric| metrics.get(*metric)).sum::<f64>();
+        }
+    }
+    let values: Vec<f64> = totals.iter().map(|total| if runs_found > 0 { total / runs_found as f64 } else { 0.0 }).collect();
+
+    let column = |label: &str| REPORT_COLUMNS.iter().position(|c| c.label == label).map_or(0.0, |i| values[i]);
+    let mut anomalies = Vec::new();
+    if runs_found < expected_runs {
+        anomalies.push(format!("only {} of {} runs found", runs_found, expected_runs));
+    }
+    if column("Protocol violations") > 0.0 {
+        anomalies.push(format!("{} protocol violations", column("Protocol violations")));
+    }
+    if column("Diverging accounts") > 0.0 {
+        anomalies.push(format!("{} accounts with diverging balances between chains", column("Diverging accounts")));
+    }
+    if column("Duplicates at HIGs") > 0.0 {
+        anomalies.push(format!("{} duplicate transactions reached the HIGs", column("Duplicates at HIGs")));
+    }
+    if column("Malformed accepted by CL") > 0.0 {
+        anomalies.push(format!("{} malformed transactions accepted by the CL", column("Malformed accepted by CL")));
+    }
+    if column("Rate anomalies") > 0.0 {
+        anomalies.push(format!("{} stretches with TPS or pending counts outside their recent window (see rate_anomalies in simulation_stats.json)", column("Rate anomalies")));
+    }
+    if runs_found > 0 && column("CAT success") + column("Regular success") == 0.0 {
+        anomalies.push("no transaction succeeded".to_string());
+    }
+
+    SimulationSummary { parameter, runs_found, values, anomalies }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Report Generation
+// ------------------------------------------------------------------------------------------------
+
+/// A sweep's metadata, configuration and per-simulation summaries, read from its results directory
+struct SweepData {
+    parameter_name: String,
+    num_runs: usize,
+    config: String,
+    summaries: Vec<SimulationSummary>,
+}
+
+/// Reads the metadata and configuration of a sweep and summarizes each of its simulations
+fn load_sweep(base_dir: &str) -> Result<SweepData, String> {
+    let metadata_path = format!("{}/data/metadata.json", base_dir);
+    let metadata: serde_json::Value = fs::read_to_string(&metadata_path)
+        .map_err(|e| format!("Failed to read {}: {}", metadata_path, e))
+        .and_then(|content| serde_json::from_str(&content).map_err(|e| format!("Failed to parse {}: {}", metadata_path, e)))?;
+    let config = fs::read_to_string(format!("{}/data/config.toml", base_dir)).unwrap_or_default();
+
+    let parameter_name = metadata["parameter_name"].as_str().unwrap_or("parameter").to_string();
+    let num_runs = metadata["num_runs"].as_u64().unwrap_or(1) as usize;
+    let parameter_values = metadata["parameter_values"].as_array().cloned().unwrap_or_default();
+
+    let summaries: Vec<SimulationSummary> = parameter_values.iter().enumerate()
+        .map(|(sim_index, value)| {
+            let parameter = match value {
+                serde_json::Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            summarize_simulation(&format!("{}/data/sim_{}", base_dir, sim_index), parameter, num_runs)
+        })
+        .collect();
+
+    Ok(SweepData { parameter_name, num_runs, config, summaries })
+}
+
+/// Generates the HTML report and the Markdown summary of a sweep
+///
+/// # Arguments
+/// * `results_dir` - The sweep's directory name under `simulator/results`
+///
+/// # Returns
+/// The path of the written HTML report
+pub fn generate_sweep_report(results_dir: &str) -> Result<String, String> {
+    let base_dir = format!("simulator/results/{}", results_dir);
+    let sweep = load_sweep(&base_dir)?;
+
+    let html = render_report(results_dir, &sweep.parameter_name, sweep.num_runs, &sweep.config, &sweep.summaries);
+    let report_path = format!("{}/{}", base_dir, REPORT_FILE);
+    fs::write(&report_path, html).map_err(|e| format!("Failed to write {}: {}", report_path, e))?;
+
+    write_sweep_markdown(results_dir, &base_dir, &sweep)?;
+    Ok(report_path)
+}
+
+/// Generates the Markdown summary of a sweep
+///
+/// Called again once the plot script has run, so the summary links the generated plots.
+///
+/// # Arguments
+/// * `results_dir` - The sweep's directory name under `simulator/results`
+///
+/// # Returns
+/// The path of the written summary
+pub fn generate_sweep_markdown(results_dir: &str) -> Result<String, String> {
+    let base_dir = format!("simulator/results/{}", results_dir);
+    let sweep = load_sweep(&base_dir)?;
+    write_sweep_markdown(results_dir, &base_dir, &sweep)
+}
+
+/// Writes the Markdown summary of a sweep to its results directory
+fn write_sweep_markdown(results_dir: &str, base_dir: &str, sweep: &SweepData) -> Result<String, String> {
+    let markdown = render_markdown(results_dir, sweep, &chart_files(base_dir));
+    let markdown_path = format!("{}/{}", base_dir, MARKDOWN_SUMMARY_FILE);
+    fs::write(&markdown_path, markdown).map_err(|e| format!("Failed to write {}: {}", markdown_path, e))?;
+    Ok(markdown_path)
+}
+
+/// Summarizes the results of any scenario (simple simulation, sweep or comparison)
+///
+/// # Arguments
+/// * `results_dir` - The scenario's directory name under `simulator/results`
+pub fn summarize_results(results_dir: &str) -> Result<ResultsSummary, String> {
+    let base_dir = format!("simulator/results/{}", results_dir);
+    let metadata_path = format!("{}/data/metadata.json", base_dir);
+    let metadata: serde_json::Value = fs::read_to_string(&metadata_path)
+        .map_err(|e| format!("Failed to read {}: {}", metadata_path, e))
+        .and_then(|content| serde_json::from_str(&content).map_err(|e| format!("Failed to parse {}: {}", metadata_path, e)))?;
+
+    let num_runs = metadata["num_runs"].as_u64().unwrap_or(1) as usize;
+    // The simple simulation has no parameter values, only a number of simulations
+    let parameters: Vec<String> = match metadata["parameter_values"].as_array() {
+        Some(values) => values.iter().map(|value| value.as_str().map(str::to_string).unwrap_or_else(|| value.to_string())).collect(),
+        None => (0..metadata["num_simulations"].as_u64().unwrap_or(1)).map(|sim_index| format!("sim_{}", sim_index)).collect(),
+    };
+
+    let summaries: Vec<SimulationSummary> = parameters.into_iter().enumerate()
+        .map(|(sim_index, parameter)| summarize_simulation(&format!("{}/data/sim_{}", base_dir, sim_index), parameter, num_runs))
+        .collect();
+    let metrics = REPORT_COLUMNS.iter().enumerate()
+        .map(|(index, column)| {
+            let total: f64 = summaries.iter().map(|s| s.values[index]).sum();
+            (column.label, if summaries.is_empty() { 0.0 } else { total / summaries.len() as f64 })
+        })
+        .collect();
+
+    Ok(ResultsSummary {
+        simulations: summaries.len(),
+        runs_found: summaries.iter().map(|s| s.runs_found).sum(),
+        expected_runs: summaries.len() * num_runs,
+        metrics,
+        anomalies: summaries.iter()
+            .flat_map(|s| s.anomalies.iter().map(move |anomaly| format!("{}: {}", s.parameter, anomaly)))
+            .collect(),
+    })
+}
+
+/// Renders the complete HTML page
+fn render_report(results_dir: &str, parameter_name: &str, num_runs: usize, config: &str, summaries: &[SimulationSummary]) -> String {
+    let mut html = String::new();
+    let title = format!("Sweep report: {}", results_dir);
+
+    writeln!(html, "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>", escape(&title)).unwrap();
+    writeln!(html, "<style>body{{font-family:sans-serif;margin:2em;}}table{{border-collapse:collapse;}}td,th{{border:1px solid #ccc;padding:4px 8px;text-align:right;}}tr.anomaly td{{background:#fde2e2;}}pre{{background:#f4f4f4;padding:1em;}}.chart{{display:inline-block;margin:1em;}}</style>").unwrap();
+    writeln!(html, "</head>\n<body>\n<h1>{}</h1>", escape(&title)).unwrap();
+    writeln!(html, "<p>Swept parameter: <b>{}</b>, {} simulations, {} runs each. Generated {}.</p>",
+        escape(parameter_name), summaries.len(), num_runs, chrono::Local::now().format("%Y-%m-%d %H:%M:%S")).unwrap();
+
+    // Anomalies
+    writeln!(html, "<h2>Anomalies</h2>").unwrap();
+    let flagged: Vec<&SimulationSummary> = summaries.iter().filter(|s| !s.anomalies.is_empty()).collect();
+    if flagged.is_empty() {
+        writeln!(html, "<p>No anomalies detected.</p>").unwrap();
+    } else {
+        writeln!(html, "<ul>").unwrap();
+        for summary in flagged {
+            writeln!(html, "<li>{} = {}: {}</li>", escape(parameter_name), escape(&summary.parameter), escape(&summary.anomalies.join("; "))).unwrap();
+        }
+        writeln!(html, "</ul>").unwrap();
+    }
+
+    // Results table
+    writeln!(html, "<h2>Results (final values, averaged over runs)</h2>\n<table>\n<tr><th>{}</th><th>Runs</th>", escape(parameter_name)).unwrap();
+    for column in REPORT_COLUMNS {
+        write!(html, "<th>{}</th>", column.label).unwrap();
+    }
+    writeln!(html, "</tr>").unwrap();
+    for summary in summaries {
+        let class = if summary.anomalies.is_empty() { "" } else { " class=\"anomaly\"" };
+        write!(html, "<tr{}><td>{}</td><td>{}</td>", class, escape(&summary.parameter), summary.runs_found).unwrap();
+        for value in &summary.values {
+            write!(html, "<td>{}</td>", format_value(*value)).unwrap();
+        }
+        writeln!(html, "</tr>").unwrap();
+    }
+    writeln!(html, "</table>").unwrap();
+
+    // Charts
+    writeln!(html, "<h2>Charts</h2>").unwrap();
+    for (index, column) in REPORT_COLUMNS.iter().enumerate() {
+        let points: Vec<f64> = summaries.iter().map(|s| s.values[index]).collect();
+        let labels: Vec<&str> = summaries.iter().map(|s| s.parameter.as_str()).collect();
+        writeln!(html, "<div class=\"chart\">{}</div>", render_line_chart(column.label, parameter_name, &labels, &points)).unwrap();
+    }
+
+    // Configuration
+    writeln!(html, "<h2>Configuration</h2>\n<pre>{}</pre>", escape(config)).unwrap();
+    writeln!(html, "</body>\n</html>").unwrap();
+    html
+}
+
+/// Renders the Markdown summary of a sweep
+///
+/// Besides the anomalies, CAT timeouts and CATs still pending at the end of the run are listed as
+/// warnings, as they are expected in some sweeps but usually worth a look.
+fn render_markdown(results_dir: &str, sweep: &SweepData, charts: &[String]) -> String {
+    let mut markdown = String::new();
+    let value_of = |summary: &SimulationSummary, label: &str| REPORT_COLUMNS.iter().position(|c| c.label == label).map_or(0.0, |i| summary.values[i]);
+
+    writeln!(markdown, "# Sweep results: {}\n", results_dir).unwrap();
+    writeln!(markdown, "Swept parameter: **{}**, {} simulations, {} runs each. Generated {}.\n",
+        sweep.parameter_name, sweep.summaries.len(), sweep.num_runs, chrono::Local::now().format("%Y-%m-%d %H:%M:%S")).unwrap();
+
+    // Headline metrics per sweep point
+    writeln!(markdown, "## Results (final values, averaged over runs)\n").unwrap();
+    write!(markdown, "| {} | Runs |", markdown_cell(&sweep.parameter_name)).unwrap();
+    for column in REPORT_COLUMNS {
+        write!(markdown, " {} |", column.label).unwrap();
+    }
+    writeln!(markdown, "\n|---:|---:|{}", "---:|".repeat(REPORT_COLUMNS.len())).unwrap();
+    for summary in &sweep.summaries {
+        // Points with anomalies are marked in bold
+        let parameter = if summary.anomalies.is_empty() { markdown_cell(&summary.parameter) } else { format!("**{}**", markdown_cell(&summary.parameter)) };
+        write!(markdown, "| {} | {} |", parameter, summary.runs_found).unwrap();
+        for value in &summary.values {
+            write!(markdown, " {} |", format_value(*value)).unwrap();
+        }
+        writeln!(markdown).unwrap();
+    }
+
+    // Anomalies and warnings
+    writeln!(markdown, "\n## Anomalies\n").unwrap();
+    let mut notes = Vec::new();
+    for summary in &sweep.summaries {
+        let point = format!("{} = {}", sweep.parameter_name, summary.parameter);
+        notes.extend(summary.anomalies.iter().map(|anomaly| format!("- {}: {}", point, anomaly)));
+        if value_of(summary, "CAT timed out") > 0.0 {
+            notes.push(format!("- {}: warning: {} CATs timed out", point, format_value(value_of(summary, "CAT timed out"))));
+        }
+        if value_of(summary, "CAT pending at end") > 0.0 {
+            notes.push(format!("- {}: warning: {} CATs still pending at the end of the run", point, format_value(value_of(summary, "CAT pending at end"))));
+        }
+    }
+    if notes.is_empty() {
+        writeln!(markdown, "No anomalies detected.").unwrap();
+    } else {
+        writeln!(markdown, "{}", notes.join("\n")).unwrap();
+    }
+
+    // Charts, relative to the results directory
+    writeln!(markdown, "\n## Charts\n").unwrap();
+    writeln!(markdown, "- [Report with charts of all metrics]({})", REPORT_FILE).unwrap();
+    for chart in charts {
+        writeln!(markdown, "- [{}]({})", chart, chart).unwrap();
+    }
+    if charts.is_empty() {
+        writeln!(markdown, "\nThe plots are linked here once the plot script has run.").unwrap();
+    }
+
+    // Configuration
+    writeln!(markdown, "\n## Configuration\n\n```toml\n{}\n```", sweep.config.trim_end()).unwrap();
+    markdown
+}
+
+/// Lists the plots in the `figs` directory of a sweep, relative to its results directory
+fn chart_files(base_dir: &str) -> Vec<String> {
+    let mut charts = Vec::new();
+    let mut directories = vec![format!("{}/figs", base_dir)];
+    while let Some(directory) = directories.pop() {
+        let Ok(entries) = fs::read_dir(&directory) else { continue };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                directories.push(path.to_string_lossy().to_string());
+            } else if path.extension().is_some_and(|extension| extension == "png" || extension == "svg") {
+                if let Ok(relative) = path.strip_prefix(base_dir) {
+                    charts.push(relative.to_string_lossy().to_string());
+                }
+            }
+        }
+    }
+    charts.sort();
+    charts
+}
+
+// ------------------------------------------------------------------------------------------------
+// Comparison Report Generation
+// ------------------------------------------------------------------------------------------------
+
+/// Generates the side-by-side report of two protocol variants run on the same workload
+///
+/// The results directory is laid out like a sweep with two simulations, where `parameter_values`
+/// in `data/metadata.json` holds the variant names. Besides the HTML report, the comparison table
+/// is written as Markdown and JSON.
+///
+/// # Arguments
+/// * `results_dir` - The comparison's directory name under `simulator/results`
+///
+/// # Returns
+/// The path of the written report
+pub fn generate_comparison_report(results_dir: &str) -> Result<String, String> {
+    let base_dir = format!("simulator/results/{}", results_dir);
+    let metadata_path = format!("{}/data/metadata.json", base_dir);
+    let metadata: serde_json::Value = fs::read_to_string(&metadata_path)
+        .map_err(|e| format!("Failed to read {}: {}", metadata_path, e))
+        .and_then(|content| serde_json::from_str(&content).map_err(|e| format!("Failed to parse {}: {}", metadata_path, e)))?;
+    let config = fs::read_to_string(format!("{}/data/config.toml", base_dir)).unwrap_or_default();
+
+    let num_runs = metadata["num_runs"].as_u64().unwrap_or(1) as usize;
+    let variants: Vec<String> = metadata["parameter_values"].as_array().cloned().unwrap_or_default()
+        .iter()
+        .map(|value| value.as_str().map(str::to_string).unwrap_or_else(|| value.to_string()))
+        .collect();
+    let [variant_a, variant_b] = variants.as_slice() else {
+        return Err(format!("{} must list exactly two variants, found {}", metadata_path, variants.len()));
+    };
+
+    let a = summarize_simulation(&format!("{}/data/sim_0", base_dir), variant_a.clone(), num_runs);
+    let b = summarize_simulation(&format!("{}/data/sim_1", base_dir), variant_b.clone(), num_runs);
+
+    // Markdown table
+    let mut markdown = String::new();
+    writeln!(markdown, "| Metric | {} | {} | Delta | Change |", variant_a, variant_b).unwrap();
+    writeln!(markdown, "|---|---:|---:|---:|---:|").unwrap();
+    for (index, column) in REPORT_COLUMNS.iter().enumerate() {
+        let (value_a, value_b) = (a.values[index], b.values[index]);
+        writeln!(markdown, "| {} | {} | {} | {} | {} |", column.label, format_value(value_a), format_value(value_b),
+            format_delta(value_b - value_a), format_change(value_a, value_b)).unwrap();
+    }
+    let markdown_path = format!("{}/{}", base_dir, COMPARISON_MARKDOWN_FILE);
+    fs::write(&markdown_path, &markdown).map_err(|e| format!("Failed to write {}: {}", markdown_path, e))?;
+
+    // JSON table
+    let json = serde_json::json!({
+        "variants": [variant_a, variant_b],
+        "num_runs": num_runs,
+        "runs_found": [a.runs_found, b.runs_found],
+        "anomalies": { variant_a.as_str(): a.anomalies, variant_b.as_str(): b.anomalies },
+        "metrics": REPORT_COLUMNS.iter().enumerate().map(|(index, column)| {
+            let (value_a, value_b) = (a.values[index], b.values[index]);
+            serde_json::json!({
+                "metric": column.label,
+                variant_a.as_str(): value_a,
+                variant_b.as_str(): value_b,
+                "delta": value_b - value_a,
+                "relative_change": if value_a != 0.0 { Some((value_b - value_a) / value_a) } else { None }
+            })
+        }).collect::<Vec<_>>()
+    });
+    let json_path = format!("{}/{}", base_dir, COMPARISON_JSON_FILE);
+    fs::write(&json_path, serde_json::to_string_pretty(&json).unwrap()).map_err(|e| format!("Failed to write {}: {}", json_path, e))?;
+
+    let html = render_comparison(results_dir, num_runs, &config, &a, &b);
+    let report_path = format!("{}/{}", base_dir, REPORT_FILE);
+    fs::write(&report_path, html).map_err(|e| format!("Failed to write {}: {}", report_path, e))?;
+    Ok(report_path)
+}
+
+/// Renders the complete HTML page of a comparison
+fn render_comparison(results_dir: &str, num_runs: usize, config: &str, a: &SimulationSummary, b: &SimulationSummary) -> String {
+    let mut html = String::new();
+    let title = format!("Comparison report: {}", results_dir);
+
+    writeln!(html, "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>", escape(&title)).unwrap();
+    writeln!(html, "<style>body{{font-family:sans-serif;margin:2em;}}table{{border-collapse:collapse;}}td,th{{border:1px solid #ccc;padding:4px 8px;text-align:right;}}pre{{background:#f4f4f4;padding:1em;}}.chart{{display:inline-block;margin:1em;}}</style>").unwrap();
+    writeln!(html, "</head>\n<body>\n<h1>{}</h1>", escape(&title)).unwrap();
+    writeln!(html, "<p>Variants <b>{}</b> ({} runs found) and <b>{}</b> ({} runs found) on the same workload, {} runs each. Generated {}.</p>",
+        escape(&a.parameter), a.runs_found, escape(&b.parameter), b.runs_found, num_runs, chrono::Local::now().format("%Y-%m-%d %H:%M:%S")).unwrap();
+
+    // Anomalies
+    writeln!(html, "<h2>Anomalies</h2>").unwrap();
+    if a.anomalies.is_empty() && b.anomalies.is_empty() {
+        writeln!(html, "<p>No anomalies detected.</p>").unwrap();
+    } else {
+        writeln!(html, "<ul>").unwrap();
+        for summary in [a, b].into_iter().filter(|s| !s.anomalies.is_empty()) {
+            writeln!(html, "<li>{}: {}</li>", escape(&summary.parameter), escape(&summary.anomalies.join("; "))).unwrap();
+        }
+        writeln!(html, "</ul>").unwrap();
+    }
+
+    // Side-by-side table
+    writeln!(html, "<h2>Results (final values, averaged over runs)</h2>\n<table>\n<tr><th>Metric</th><th>{}</th><th>{}</th><th>Delta</th><th>Change</th></tr>",
+        escape(&a.parameter), escape(&b.parameter)).unwrap();
+    for (index, column) in REPORT_COLUMNS.iter().enumerate() {
+        let (value_a, value_b) = (a.values[index], b.values[index]);
+        writeln!(html, "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>", column.label, format_value(value_a),
+            format_value(value_b), format_delta(value_b - value_a), format_change(value_a, value_b)).unwrap();
+    }
+    writeln!(html, "</table>").unwrap();
+
+    // Charts
+    writeln!(html, "<h2>Charts</h2>").unwrap();
+    let labels = [a.parameter.as_str(), b.parameter.as_str()];
+    for (index, column) in REPORT_COLUMNS.iter().enumerate() {
+        writeln!(html, "<div class=\"chart\">{}</div>", render_bar_chart(column.label, &labels, &[a.values[index], b.values[index]])).unwrap();
+    }
+
+    // Configuration
+    writeln!(html, "<h2>Configuration</h2>\n<pre>{}</pre>", escape(config)).unwrap();
+    writeln!(html, "</body>\n</html>").unwrap();
+    html
+}
+
+/// Renders a bar chart of one value per variant as an inline SVG
+fn render_bar_chart(title: &str, labels: &[&str], values: &[f64]) -> String {
+    const WIDTH: f64 = 300.0;
+    const HEIGHT: f64 = 260.0;
+    const MARGIN: f64 = 45.0;
+    const COLORS: &[&str] = &["#1f77b4", "#ff7f0e"];
+
+    let max = values.iter().cloned().fold(0.0_f64, f64::max);
+    let y_max = if max > 0.0 { max } else { 1.0 };
+    let slot = (WIDTH - 2.0 * MARGIN) / values.len().max(1) as f64;
+
+    let mut svg = String::new();
+    write!(svg, "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">", w = WIDTH, h = HEIGHT).unwrap();
+    write!(svg, "<text x=\"{}\" y=\"18\" text-anchor=\"middle\" font-size=\"14\">{}</text>", WIDTH / 2.0, escape(title)).unwrap();
+    write!(svg, "<line x1=\"{m}\" y1=\"{b}\" x2=\"{r}\" y2=\"{b}\" stroke=\"black\"/><line x1=\"{m}\" y1=\"{m}\" x2=\"{m}\" y2=\"{b}\" stroke=\"black\"/>",
+        m = MARGIN, b = HEIGHT - MARGIN, r = WIDTH - MARGIN).unwrap();
+    write!(svg, "<text x=\"{}\" y=\"{}\" text-anchor=\"end\" font-size=\"10\">{}</text>", MARGIN - 4.0, MARGIN + 4.0, format_value(y_max)).unwrap();
+    write!(svg, "<text x=\"{}\" y=\"{}\" text-anchor=\"end\" font-size=\"10\">0</text>", MARGIN - 4.0, HEIGHT - MARGIN + 4.0).unwrap();
+
+    for (i, (value, label)) in values.iter().zip(labels).enumerate() {
+        let height = value.max(0.0) / y_max * (HEIGHT - 2.0 * MARGIN);
+        let x = MARGIN + i as f64 * slot + slot * 0.2;
+        write!(svg, "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" fill=\"{}\"><title>{}: {}</title></rect>",
+            x, HEIGHT - MARGIN - height, slot * 0.6, height, COLORS[i % COLORS.len()], escape(label), format_value(*value)).unwrap();
+        write!(svg, "<text x=\"{:.1}\" y=\"{}\" text-anchor=\"middle\" font-size=\"10\">{}</text>", x + slot * 0.3, HEIGHT - MARGIN + 14.0, escape(label)).unwrap();
+    }
+    svg.push_str("</svg>");
+    svg
+}
+
+/// Renders a line chart of one value per simulation as an inline SVG
+fn render_line_chart(title: &str, x_label: &str, labels: &[&str], values: &[f64]) -> String {
+    const WIDTH: f64 = 420.0;
+    const HEIGHT: f64 = 260.0;
+    const MARGIN: f64 = 45.0;
+
+    let max = values.iter().cloned().fold(0.0_f64, f64::max);
+    let y_max = if max > 0.0 { max } else { 1.0 };
+    let x_step = if values.len() > 1 { (WIDTH - 2.0 * MARGIN) / (values.len() - 1) as f64 } else { 0.0 };
+    let point = |i: usize, v: f64| (MARGIN + i as f64 * x_step, HEIGHT - MARGIN - v / y_max * (HEIGHT - 2.0 * MARGIN));
+
+    let mut svg = String::new();
+    write!(svg, "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">", w = WIDTH, h = HEIGHT).unwrap();
+    write!(svg, "<text x=\"{}\" y=\"18\" text-anchor=\"middle\" font-size=\"14\">{}</text>", WIDTH / 2.0, escape(title)).unwrap();
+    write!(svg, "<line x1=\"{m}\" y1=\"{b}\" x2=\"{r}\" y2=\"{b}\" stroke=\"black\"/><line x1=\"{m}\" y1=\"{m}\" x2=\"{m}\" y2=\"{b}\" stroke=\"black\"/>",
+        m = MARGIN, b = HEIGHT - MARGIN, r = WIDTH - MARGIN).unwrap();
+    write!(svg, "<text x=\"{}\" y=\"{}\" text-anchor=\"end\" font-size=\"10\">{}</text>", MARGIN - 4.0, MARGIN + 4.0, format_value(y_max)).unwrap();
+    write!(svg, "<text x=\"{}\" y=\"{}\" text-anchor=\"end\" font-size=\"10\">0</text>", MARGIN - 4.0, HEIGHT - MARGIN + 4.0).unwrap();
+    write!(svg, "<text x=\"{}\" y=\"{}\" text-anchor=\"middle\" font-size=\"11\">{}</text>", WIDTH / 2.0, HEIGHT - 8.0, escape(x_label)).unwrap();
+
+    let path: Vec<String> = values.iter().enumerate()
+        .map(|(i, v)| { let (x, y) = point(i, *v); format!("{:.1},{:.1}", x, y) })
+        .collect();
+    write!(svg, "<polyline fill=\"none\" stroke=\"#1f77b4\" stroke-width=\"2\" points=\"{}\"/>", path.join(" ")).unwrap();
+    for (i, (value, label)) in values.iter().zip(labels).enumerate() {
+        let (x, y) = point(i, *value);
+        write!(svg, "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"3\" fill=\"#1f77b4\"><title>{}: {}</title></circle>", x, y, escape(label), format_value(*value)).unwrap();
+    }
+    // Label the first and last parameter values on the x axis
+    if let (Some(first), Some(last)) = (labels.first(), labels.last()) {
+        write!(svg, "<text x=\"{}\" y=\"{}\" text-anchor=\"start\" font-size=\"10\">{}</text>", MARGIN, HEIGHT - MARGIN + 14.0, escape(first)).unwrap();
+        if labels.len() > 1 {
+            write!(svg, "<text x=\"{}\" y=\"{}\" text-anchor=\"end\" font-size=\"10\">{}</text>", WIDTH - MARGIN, HEIGHT - MARGIN + 14.0, escape(last)).unwrap();
+        }
+    }
+    svg.push_str("</svg>");
+    svg
+}
+
+/// Formats a value with at most two decimals
+fn format_value(value: f64) -> String {
+    if value.fract() == 0.0 {
+        format!("{}", value)
+    } else {
+        format!("{:.2}", value)
+    }
+}
+
+/// Formats a difference with an explicit sign
+fn format_delta(delta: f64) -> String {
+    if delta > 0.0 { format!("+{}", format_value(delta)) } else { format_value(delta) }
+}
+
+/// Formats the change of a value relative to a baseline as a percentage
+fn format_change(baseline: f64, value: f64) -> String {
+    if baseline == 0.0 {
+        if value == 0.0 { "0%".to_string() } else { "n/a".to_string() }
+    } else {
+        format!("{:+.1}%", (value - baseline) / baseline * 100.0)
+    }
+}
+
+/// Escapes the column separator for use in a Markdown table cell
+fn markdown_cell(text: &str) -> String {
+    text.replace('|', "\\|")
+}
+
+/// Escapes text for use in HTML
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
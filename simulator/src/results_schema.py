@@ -0,0 +1,100 @@
+#!/usr/bin/env python3
+"""
+Reader for the versioned simulation results files.
+
+Mirrors simulator/src/results_schema.rs: simulation_stats.json is stamped with the version of the
+results schema it was written with, and files of older versions are migrated step by step to the
+current one when they are loaded. Files without a stamp predate the versioning and are read as
+version 1. Keep RESULTS_SCHEMA_VERSION and the migrations in sync with the Rust module.
+"""
+
+import json
+
+# Key of the schema version at the top level of simulation_stats.json
+SCHEMA_VERSION_KEY = 'schema_version'
+
+# Version of the results schema written by the simulator
+RESULTS_SCHEMA_VERSION = 7
+
+# Version assumed for files that carry no stamp
+LEGACY_SCHEMA_VERSION = 1
+
+# Parameters that unversioned files may lack, with the value the run used in their absence
+V1_PARAMETER_DEFAULTS = {
+    'chain_delay_distribution': {'type': 'constant'},
+    'cat_latency_multiplier': 1.0,
+    'regular_latency_multiplier': 1.0,
+    'invariants': [],
+    'hig_parameter_schedule': [],
+    'fault_timeline': [],
+    'duplicate_ratio': 0.0,
+    'malformed_ratio': 0.0,
+}
+
+
+def schema_version(stats):
+    """Return the schema version the stats were written with (1 for files without a stamp)."""
+    return stats.get(SCHEMA_VERSION_KEY, LEGACY_SCHEMA_VERSION)
+
+
+def migrate_v1_to_v2(stats):
+    """Fill the parameters unversioned files did not record with the value the run used."""
+    parameters = stats.setdefault('parameters', {})
+    for key, default in V1_PARAMETER_DEFAULTS.items():
+        parameters.setdefault(key, default)
+    stats.setdefault('results', {})
+
+
+def migrate_v2_to_v3(stats):
+    """Add the block interval schedule, which runs before version 3 could not have."""
+    stats.setdefault('parameters', {}).setdefault('block_interval_schedule', [])
+
+
+def migrate_v3_to_v4(stats):
+    """Add the amount distribution, which was fixed at 1 before version 4."""
+    stats.setdefault('parameters', {}).setdefault('amount_distribution', {'type': 'fixed', 'amount': 1})
+
+
+def migrate_v4_to_v5(stats):
+    """Add the sink accounts, of which there were none before version 5."""
+    stats.setdefault('parameters', {}).setdefault('sink_accounts', 0)
+
+
+def migrate_v5_to_v6(stats):
+    """Add the CL block interval jitter, which was none before version 6."""
+    stats.setdefault('parameters', {}).setdefault('cl_block_interval_jitter', {'type': 'none'})
+
+
+def migrate_v6_to_v7(stats):
+    """Add the CL endpoint, as runs before version 7 always ran in-process nodes."""
+    stats.setdefault('parameters', {}).setdefault('cl_endpoint', None)
+
+
+# Migrations by the version they upgrade from
+MIGRATIONS = {
+    1: migrate_v1_to_v2,
+    2: migrate_v2_to_v3,
+    3: migrate_v3_to_v4,
+    4: migrate_v4_to_v5,
+    5: migrate_v5_to_v6,
+    6: migrate_v6_to_v7,
+}
+
+
+def migrate(stats):
+    """Migrate the stats of a run to the current schema version."""
+    version = schema_version(stats)
+    if version < LEGACY_SCHEMA_VERSION or version > RESULTS_SCHEMA_VERSION:
+        raise ValueError(f"Simulation stats have schema version {version}, but this reader supports "
+                         f"versions {LEGACY_SCHEMA_VERSION} to {RESULTS_SCHEMA_VERSION}")
+    while version < RESULTS_SCHEMA_VERSION:
+        MIGRATIONS[version](stats)
+        version += 1
+    stats[SCHEMA_VERSION_KEY] = RESULTS_SCHEMA_VERSION
+    return stats
+
+
+def load_simulation_stats(path):
+    """Load a simulation_stats.json, migrated to the current schema version."""
+    with open(path, 'r') as f:
+        return migrate(json.load(f))
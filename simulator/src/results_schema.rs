@@ -0,0 +1,162 @@
+//! Versioning of the simulation results files.
+//!
+//! Every run stamps its `simulation_stats.json` with the version of the results schema it was
+//! written with. Readers load the file through this module, which migrates older versions step by
+//! step to the current one, so analysis tooling keeps working on result directories written before
+//! `SimulationResults` gained fields. Files without a stamp predate the versioning and are read as
+//! version 1.
+
+use std::fs;
+
+// ------------------------------------------------------------------------------------------------
+// Constants
+// ------------------------------------------------------------------------------------------------
+
+/// Name of the file (inside a run's data directory) that holds the parameters and summary results
+pub const SIMULATION_STATS_FILE: &str = "simulation_stats.json";
+
+/// Key of the schema version at the top level of `simulation_stats.json`
+pub const SCHEMA_VERSION_KEY: &str = "schema_version";
+
+/// Version of the results schema written by this build
+///
+/// Bump it whenever a field of `simulation_stats.json` is renamed, removed or changes meaning, or
+/// a new parameter is added whose absence should read as its default, and add the migration
+/// from the previous version to `MIGRATIONS`.
+pub const RESULTS_SCHEMA_VERSION: u64 = 7;
+
+/// Version assumed for files that carry no stamp
+const LEGACY_SCHEMA_VERSION: u64 = 1;
+
+/// Migrations by the version they upgrade from (entry `i` upgrades version `i + 1` to `i + 2`)
+const MIGRATIONS: [fn(&mut serde_json::Value); (RESULTS_SCHEMA_VERSION - LEGACY_SCHEMA_VERSION) as usize] = [
+    migrate_v1_to_v2,
+    migrate_v2_to_v3,
+    migrate_v3_to_v4,
+    migrate_v4_to_v5,
+    migrate_v5_to_v6,
+    migrate_v6_to_v7,
+];
+
+/// Parameters that unversioned files may lack, with the value the run used in their absence
+fn v1_parameter_defaults() -> [(&'static str, serde_json::Value); 8] {
+    [
+        ("chain_delay_distribution", serde_json::json!({ "type": "constant" })),
+        ("cat_latency_multiplier", serde_json::json!(1.0)),
+        ("regular_latency_multiplier", serde_json::json!(1.0)),
+        ("invariants", serde_json::json!([])),
+        ("hig_parameter_schedule", serde_json::json!([])),
+        ("fault_timeline", serde_json::json!([])),
+        ("duplicate_ratio", serde_json::json!(0.0)),
+        ("malformed_ratio", serde_json::json!(0.0)),
+    ]
+}
+
+// ------------------------------------------------------------------------------------------------
+// Writing
+// ------------------------------------------------------------------------------------------------
+
+/// Stamps the stats of a run with the current schema version
+///
+/// # Arguments
+/// * `stats` - The content of `simulation_stats.json`
+pub fn stamp(stats: &mut serde_json::Value) {
+    if let Some(object) = stats.as_object_mut() {
+        object.insert(SCHEMA_VERSION_KEY.to_string(), serde_json::json!(RESULTS_SCHEMA_VERSION));
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Reading
+// ------------------------------------------------------------------------------------------------
+
+/// The schema version the stats were written with (1 for files without a stamp)
+pub fn schema_version(stats: &serde_json::Value) -> u64 {
+    stats.get(SCHEMA_VERSION_KEY).and_then(|version| version.as_u64()).unwrap_or(LEGACY_SCHEMA_VERSION)
+}
+
+/// Migrates the stats of a run to the current schema version
+///
+/// Fails for files written by a newer build, whose fields this build cannot interpret.
+///
+/// # Arguments
+/// * `stats` - The content of `simulation_stats.json`, in any supported version
+pub fn migrate(mut stats: serde_json::Value) -> Result<serde_json::Value, String> {
+    if !stats.is_object() {
+        return Err("Simulation stats are not a JSON object".to_string());
+    }
+    let version = schema_version(&stats);
+    if version == 0 || version > RESULTS_SCHEMA_VERSION {
+        return Err(format!(
+            "Simulation stats have schema version {}, but this build reads versions {} to {}",
+            version, LEGACY_SCHEMA_VERSION, RESULTS_SCHEMA_VERSION
+        ));
+    }
+    for migration in &MIGRATIONS[(version - LEGACY_SCHEMA_VERSION) as usize..] {
+        migration(&mut stats);
+    }
+    stamp(&mut stats);
+    Ok(stats)
+}
+
+/// Loads the `simulation_stats.json` of a run, migrated to the current schema version
+///
+/// # Arguments
+/// * `data_dir` - The data directory of the run
+pub fn load_simulation_stats(data_dir: &str) -> Result<serde_json::Value, String> {
+    let path = format!("{}/{}", data_dir, SIMULATION_STATS_FILE);
+    let content = fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let stats = serde_json::from_str(&content).map_err(|e| format!("Failed to parse {}: {}", path, e))?;
+    migrate(stats).map_err(|e| format!("{}: {}", path, e))
+}
+
+// ------------------------------------------------------------------------------------------------
+// Migrations
+// ------------------------------------------------------------------------------------------------
+
+/// Fills the parameters unversioned files did not record with the value the run used
+fn migrate_v1_to_v2(stats: &mut serde_json::Value) {
+    let object = stats.as_object_mut().expect("stats are checked to be an object");
+    let parameters = object.entry("parameters").or_insert_with(|| serde_json::json!({}));
+    if let Some(parameters) = parameters.as_object_mut() {
+        for (key, default) in v1_parameter_defaults() {
+            parameters.entry(key).or_insert(default);
+        }
+    }
+    object.entry("results").or_insert_with(|| serde_json::json!({}));
+}
+
+/// Adds the block interval schedule, which runs before version 3 could not have
+fn migrate_v2_to_v3(stats: &mut serde_json::Value) {
+    if let Some(parameters) = stats.get_mut("parameters").and_then(|parameters| parameters.as_object_mut()) {
+        parameters.entry("block_interval_schedule").or_insert_with(|| serde_json::json!([]));
+    }
+}
+
+/// Adds the amount distribution, which was fixed at 1 before version 4
+fn migrate_v3_to_v4(stats: &mut serde_json::Value) {
+    if let Some(parameters) = stats.get_mut("parameters").and_then(|parameters| parameters.as_object_mut()) {
+        parameters.entry("amount_distribution").or_insert_with(|| serde_json::json!({ "type": "fixed", "amount": 1 }));
+    }
+}
+
+/// Adds the sink accounts, of which there were none before version 5
+fn migrate_v4_to_v5(stats: &mut serde_json::Value) {
+    if let Some(parameters) = stats.get_mut("parameters").and_then(|parameters| parameters.as_object_mut()) {
+        parameters.entry("sink_accounts").or_insert_with(|| serde_json::json!(0));
+    }
+}
+
+/// Adds the CL block interval jitter, which was none before version 6
+fn migrate_v5_to_v6(stats: &mut serde_json::Value) {
+    if let Some(parameters) = stats.get_mut("parameters").and_then(|parameters| parameters.as_object_mut()) {
+        parameters.entry("cl_block_interval_jitter").or_insert_with(|| serde_json::json!({ "type": "none" }));
+    }
+}
+
+/// Adds the CL endpoint, as runs before version 7 always ran in-process nodes
+fn migrate_v6_to_v7(stats: &mut serde_json::Value) {
+    if let Some(parameters) = stats.get_mut("parameters").and_then(|parameters| parameters.as_object_mut()) {
+        parameters.entry("cl_endpoint").or_insert(serde_json::Value::Null);
+    }
+}
@@ -0,0 +1,256 @@
+//! Index of completed simulation runs.
+//!
+//! The results of a run end up nested as `simulator/results/<scenario>/data/sim_<i>/run_<j>`, and
+//! finding a particular run means walking these directories by hand. Every saved run therefore
+//! appends one line to `simulator/results/runs.jsonl` with where it was saved, its config hash,
+//! when it ran and its headline metrics. `--list-runs` and `--show-run` query the index. A run
+//! directory that is written again gets a new entry; the older entries of that directory are
+//! kept but marked as overwritten, since their data is gone.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use serde::{Deserialize, Serialize};
+
+use crate::metadata::{RunMetadata, METADATA_FILE};
+use crate::results_schema::SIMULATION_STATS_FILE;
+use crate::simulation_results::SimulationResults;
+
+// ------------------------------------------------------------------------------------------------
+// Constants
+// ------------------------------------------------------------------------------------------------
+
+/// The index of completed runs, one JSON object per line
+pub const RUN_INDEX_FILE: &str = "simulator/results/runs.jsonl";
+
+/// Number of runs `--list-runs` shows unless asked for more
+pub const DEFAULT_LIST_LIMIT: usize = 20;
+
+// ------------------------------------------------------------------------------------------------
+// Data Structures
+// ------------------------------------------------------------------------------------------------
+
+/// Headline metrics of a run, summed over both chains
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct HeadlineMetrics {
+    pub total_transactions: u64,
+    pub cat_transactions: u64,
+    /// Submission rate achieved during the run
+    pub achieved_tps: f64,
+    /// Final status counts of the CATs and regular transactions
+    pub cat_success: u64,
+    pub cat_failure: u64,
+    pub cat_pending: u64,
+    pub regular_success: u64,
+    pub regular_failure: u64,
+    pub regular_pending: u64,
+    pub peak_memory_mb: f64,
+}
+
+impl HeadlineMetrics {
+    /// Share of the finalized CATs that succeeded (0 without finalized CATs)
+    pub fn cat_success_ratio(&self) -> f64 {
+        let finalized = self.cat_success + self.cat_failure;
+        if finalized == 0 { 0.0 } else { self.cat_success as f64 / finalized as f64 }
+    }
+}
+
+/// A completed run in the index
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunIndexEntry {
+    /// Number of the run in the index, counted from 1
+    pub id: u64,
+    /// Directory the run was saved to
+    pub run_dir: String,
+    /// Results directory of the scenario (e.g. `sim_simple` or `sim_sweep_zipf`)
+    pub scenario: String,
+    pub config_hash: String,
+    pub master_seed: u64,
+    pub run_seed: u64,
+    /// Start and end of the run (RFC 3339, UTC)
+    pub started_at: String,
+    pub finished_at: String,
+    /// Git commit of the crate that produced the run
+    pub crate_commit: Option<String>,
+    pub metrics: HeadlineMetrics,
+}
+
+impl RunIndexEntry {
+    /// Builds the index entry of a run that was just saved
+    ///
+    /// # Arguments
+    /// * `id` - Number of the run in the index
+    /// * `run_dir` - Directory the run was saved to
+    /// * `metadata` - The metadata saved with the run
+    /// * `results` - The results of the run
+    pub fn new(id: u64, run_dir: &str, metadata: &RunMetadata, results: &SimulationResults) -> Self {
+        let last = |series: &Vec<(u64, u64)>| series.last().map(|(_, count)| *count).unwrap_or(0);
+        Self {
+            id,
+            run_dir: run_dir.to_string(),
+            scenario: scenario_of(run_dir),
+            config_hash: metadata.config_hash.clone(),
+            master_seed: metadata.master_seed,
+            run_seed: metadata.run_seed,
+            started_at: metadata.started_at.clone(),
+            finished_at: metadata.finished_at.clone(),
+            crate_commit: metadata.crate_commit.clone(),
+            metrics: HeadlineMetrics {
+                total_transactions: results.transactions_sent,
+                cat_transactions: results.cat_transactions,
+                achieved_tps: results.achieved_submission_tps,
+                cat_success: last(&results.chain_1_cat_success) + last(&results.chain_2_cat_success),
+                cat_failure: last(&results.chain_1_cat_failure) + last(&results.chain_2_cat_failure),
+                cat_pending: last(&results.chain_1_cat_pending) + last(&results.chain_2_cat_pending),
+                regular_success: last(&results.chain_1_regular_success) + last(&results.chain_2_regular_success),
+                regular_failure: last(&results.chain_1_regular_failure) + last(&results.chain_2_regular_failure),
+                regular_pending: last(&results.chain_1_regular_pending) + last(&results.chain_2_regular_pending),
+                peak_memory_mb: results.memory_usage.iter().map(|(_, bytes)| *bytes).max().unwrap_or(0) as f64 / (1024.0 * 1024.0),
+            },
+        }
+    }
+}
+
+/// Which runs `--list-runs` shows
+#[derive(Debug, Clone, Default)]
+pub struct RunFilter {
+    /// Only runs of scenarios whose results directory contains this
+    pub scenario: Option<String>,
+    /// Only runs whose config hash starts with this
+    pub config_hash: Option<String>,
+    /// Also runs whose directory was overwritten by a later run
+    pub include_overwritten: bool,
+    /// Maximum number of runs, the most recent first
+    pub limit: usize,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Recording
+// ------------------------------------------------------------------------------------------------
+
+/// Appends a saved run to the index
+///
+/// # Arguments
+/// * `run_dir` - Directory the run was saved to
+/// * `metadata` - The metadata saved with the run
+/// * `results` - The results of the run
+pub fn record_run(run_dir: &str, metadata: &RunMetadata, results: &SimulationResults) -> Result<RunIndexEntry, String> {
+    let id = load_index()?.last().map_or(1, |entry| entry.id + 1);
+    let entry = RunIndexEntry::new(id, run_dir, metadata, results);
+    let line = serde_json::to_string(&entry).map_err(|e| e.to_string())?;
+    if let Some(parent) = std::path::Path::new(RUN_INDEX_FILE).parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(RUN_INDEX_FILE)
+        .map_err(|e| format!("Failed to open {}: {}", RUN_INDEX_FILE, e))?;
+    // One write per line, so runs finishing at the same time do not interleave their entries
+    file.write_all(format!("{}\n", line).as_bytes()).map_err(|e| format!("Failed to append to {}: {}", RUN_INDEX_FILE, e))?;
+    Ok(entry)
+}
+
+// ------------------------------------------------------------------------------------------------
+// Queries
+// ------------------------------------------------------------------------------------------------
+
+/// Loads all entries of the index, in the order the runs finished (empty if there is no index yet)
+///
+/// Lines that cannot be parsed, e.g. of a run interrupted while appending, are skipped.
+pub fn load_index() -> Result<Vec<RunIndexEntry>, String> {
+    let content = match fs::read_to_string(RUN_INDEX_FILE) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("Failed to read {}: {}", RUN_INDEX_FILE, e)),
+    };
+    Ok(content.lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+/// Whether a later run was saved to the same directory as the entry
+pub fn is_overwritten(entry: &RunIndexEntry, index: &[RunIndexEntry]) -> bool {
+    index.iter().any(|other| other.run_dir == entry.run_dir && other.id > entry.id)
+}
+
+/// The runs of the index matching the filter, the most recent first
+pub fn list_runs(index: &[RunIndexEntry], filter: &RunFilter) -> Vec<RunIndexEntry> {
+    index.iter().rev()
+        .filter(|entry| filter.include_overwritten || !is_overwritten(entry, index))
+        .filter(|entry| filter.scenario.iter().all(|scenario| entry.scenario.contains(scenario.as_str())))
+        .filter(|entry| filter.config_hash.iter().all(|hash| entry.config_hash.starts_with(hash.as_str())))
+        .take(filter.limit)
+        .cloned()
+        .collect()
+}
+
+/// Finds a run by its id or by the directory it was saved to (the latest run saved there)
+pub fn find_run<'a>(index: &'a [RunIndexEntry], id_or_dir: &str) -> Option<&'a RunIndexEntry> {
+    let run_dir = id_or_dir.trim_end_matches('/');
+    match id_or_dir.parse::<u64>() {
+        Ok(id) => index.iter().find(|entry| entry.id == id),
+        Err(_) => index.iter().rev().find(|entry| entry.run_dir == run_dir),
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Output
+// ------------------------------------------------------------------------------------------------
+
+/// One table row per run for `--list-runs`
+pub fn format_run_table(runs: &[RunIndexEntry]) -> String {
+    let mut lines = vec![format!("{:>5}  {:<20}  {:<32}  {:<16}  {:>8}  {:>8}  {:>7}  {}",
+        "id", "finished (UTC)", "scenario", "config hash", "txs", "CATs ok", "TPS", "run directory")];
+    lines.extend(runs.iter().map(|entry| format!("{:>5}  {:<20}  {:<32}  {:<16}  {:>8}  {:>7.1}%  {:>7.1}  {}",
+        entry.id,
+        entry.finished_at.get(..19).unwrap_or(&entry.finished_at).replace('T', " "),
+        entry.scenario,
+        entry.config_hash,
+        entry.metrics.total_transactions,
+        entry.metrics.cat_success_ratio() * 100.0,
+        entry.metrics.achieved_tps,
+        entry.run_dir)));
+    lines.join("\n")
+}
+
+/// The details of a run for `--show-run`, with the files its data directory holds
+pub fn format_run_details(entry: &RunIndexEntry, index: &[RunIndexEntry]) -> String {
+    let metrics = &entry.metrics;
+    let mut lines = vec![
+        format!("Run {}{}", entry.id, if is_overwritten(entry, index) { " (overwritten by a later run)" } else { "" }),
+        format!("  Directory:     {}", entry.run_dir),
+        format!("  Scenario:      {}", entry.scenario),
+        format!("  Config hash:   {}", entry.config_hash),
+        format!("  Seeds:         master {}, run {}", entry.master_seed, entry.run_seed),
+        format!("  Ran:           {} to {}", entry.started_at, entry.finished_at),
+        format!("  Commit:        {}", entry.crate_commit.as_deref().unwrap_or("unknown")),
+        format!("  Transactions:  {} ({} CATs) at {:.1} TPS", metrics.total_transactions, metrics.cat_transactions, metrics.achieved_tps),
+        format!("  CATs:          {} success, {} failure, {} pending ({:.1}% of the finalized succeeded)",
+            metrics.cat_success, metrics.cat_failure, metrics.cat_pending, metrics.cat_success_ratio() * 100.0),
+        format!("  Regular:       {} success, {} failure, {} pending", metrics.regular_success, metrics.regular_failure, metrics.regular_pending),
+        format!("  Peak memory:   {:.1} MB", metrics.peak_memory_mb),
+    ];
+    let data_dir = format!("{}/data", entry.run_dir);
+    match fs::read_dir(&data_dir) {
+        Ok(dir) => {
+            let mut files: Vec<String> = dir.filter_map(|file| file.ok())
+                .map(|file| file.file_name().to_string_lossy().into_owned())
+                .collect();
+            files.sort();
+            lines.push(format!("  Files in {} ({}):", data_dir, files.len()));
+            // Stats and metadata first, as they summarize the run
+            for file in [SIMULATION_STATS_FILE, METADATA_FILE].iter().filter(|file| files.iter().any(|name| name == *file)) {
+                lines.push(format!("    {}", file));
+            }
+            lines.extend(files.iter()
+                .filter(|name| name.as_str() != SIMULATION_STATS_FILE && name.as_str() != METADATA_FILE)
+                .map(|name| format!("    {}", name)));
+        }
+        Err(_) => lines.push(format!("  {} no longer exists", data_dir)),
+    }
+    lines.join("\n")
+}
+
+/// The results directory of the scenario of a run, e.g. `sim_simple` for `simulator/results/sim_simple/data/sim_0/run_0`
+fn scenario_of(run_dir: &str) -> String {
+    let path = run_dir.strip_prefix("simulator/results/").unwrap_or(run_dir);
+    path.split("/data/").next().unwrap_or(path).to_string()
+}
@@ -8,17 +8,100 @@ use tokio::sync::Mutex;
 use tokio::time::sleep;
 use indicatif::{ProgressBar, ProgressStyle};
 use hyperplane::{
-    types::{TransactionId, Transaction, CLTransaction, CLTransactionId, ChainId},
-    confirmation_layer::{ConfirmationLayerNode, ConfirmationLayer},
+    types::{TransactionId, Transaction, CLTransaction, CLTransactionId, ChainId, CATId, CatBuilder, NetworkSpec, SubBlock},
+    confirmation_layer::{ConfirmationLayerNode, ConfirmationLayer, RemoteConfirmationLayer},
     hyper_ig::node::HyperIGNode,
-    hyper_ig::HyperIG,
+    hyper_scheduler::node::HyperSchedulerNode,
+    hyper_ig::{HyperIG, ProtocolViolationPolicy},
     utils::logging,
 };
 use crate::zipf_account_selection::AccountSelector;
-use rand::Rng;
+use crate::config::{Config, CatFanoutWeight, CatChainSelection};
+use crate::divergence::{SubmittedCat, analyze_state_divergence};
+use crate::workload::RecordedSubmission;
+use crate::state_roots::collect_state_roots;
+use crate::export::collect_transaction_records;
+use crate::cat_outcome_buckets::collect_cat_outcomes;
+use crate::chain_pair_matrix::ChainPairMatrix;
+use crate::stats::{RateAnomalyDetector, RuntimeMetricsSampler, collect_subblock_timings, collect_key_lock_waits, collect_realized_chain_delays};
+use crate::submission::SubmissionQueue;
+use crate::hig_control::{HigControlChannel, HigParameterSchedule};
+use crate::fault_orchestrator::FaultOrchestrator;
+use crate::block_interval_ramp::BlockIntervalSchedule;
+use crate::duplicates::DuplicateInjector;
+use crate::malformed::MalformedInjector;
+use crate::invariants::InvariantChecker;
+use crate::watchdog::{Watchdog, WatchdogConfig};
+use crate::network::NetworkPartitions;
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use rand::distributions::{Distribution, WeightedIndex};
 use crate::SimulationResults;
+use crate::simulation_results::StatusCountsBreakdown;
 use std::time::Instant;
 
+// ------------------------------------------------------------------------------------------------
+// Node Setup
+// ------------------------------------------------------------------------------------------------
+
+/// Sets up the nodes of a run and applies the HS, CL and HIG settings of its config
+///
+/// # Arguments
+///
+/// * `config` - The configuration of the run
+/// * `chain_delays` - The delays in blocks of the HIG -> HS paths, in chain order
+/// * `partitions` - The partition gates of the chains the fault timeline partitions
+/// * `network` - The chains of the run
+///
+/// # Returns
+///
+/// The HS node, the CL node and the HIG nodes of the first and the second chain
+pub async fn setup_configured_nodes(
+    config: &Config,
+    chain_delays: &[f64],
+    partitions: &NetworkPartitions,
+    network: &NetworkSpec,
+) -> (Arc<Mutex<HyperSchedulerNode>>, Arc<Mutex<ConfirmationLayerNode>>, Arc<Mutex<HyperIGNode>>, Arc<Mutex<HyperIGNode>>) {
+    let network_config = &config.network_config;
+    let (hs_node, cl_node, hig_node_1, hig_node_2, _start_block_height) = crate::testnodes::setup_test_nodes(
+        Duration::from_secs_f64(network_config.block_interval),
+        chain_delays,
+        &config.transaction_config.allow_cat_pending_dependencies_per_chain(network.chain_count()),
+        config.transaction_config.cat_lifetime_blocks,
+        &config.account_config.chain_balances(network.chain_count()),
+        network_config.channel_buffer_size,
+        &network_config.topology,
+        partitions,
+        network,
+        crate::testnodes::simulation_clock(&config.simulation_config),
+    ).await;
+
+    // Apply the HS decision latency (configured in blocks)
+    hs_node.lock().await.set_decision_latency(
+        network_config.hs_decision_latency.scaled(network_config.block_interval)
+    ).await;
+
+    // Apply the clock skews of the HS and the HIGs
+    crate::testnodes::apply_clock_skews(&hs_node, &[hig_node_1.clone(), hig_node_2.clone()], &network_config.clock_skew).await;
+
+    // Apply the per-chain delays of the HS status updates
+    crate::testnodes::apply_status_update_delays(&hs_node, network, &network_config.hs_status_update_delays, network_config.block_interval).await;
+
+    // Apply the CL block ordering, CAT alignment and block capacity
+    cl_node.lock().await.set_block_ordering(network_config.cl_block_ordering).await;
+    cl_node.lock().await.set_cat_alignment(network_config.cl_align_cat_parts).await;
+    cl_node.lock().await.set_block_capacity(network_config.cl_block_capacity()).await;
+    cl_node.lock().await.set_block_interval_jitter(network_config.cl_block_interval_jitter_secs()).await;
+
+    // Apply the HIG lock release policy for CATs proposing Failure and the status update priority
+    for hig_node in [&hig_node_1, &hig_node_2] {
+        hig_node.lock().await.set_release_locks_on_failure_proposal(network_config.hig_release_locks_on_failure).await;
+        hig_node.lock().await.set_prioritize_status_updates(network_config.hig_prioritize_status_updates).await;
+    }
+
+    (hs_node, cl_node, hig_node_1, hig_node_2)
+}
+
 // ------------------------------------------------------------------------------------------------
 // Main Simulation Function
 // ------------------------------------------------------------------------------------------------
@@ -53,90 +136,247 @@ pub async fn run_simulation_with_message(
     results: &mut SimulationResults,
     run_message: Option<String>,
 ) -> Result<(), String> {
-    run_simulation_with_message_and_retries(cl_node, hig_nodes, results, run_message, None).await
+    run_simulation_with_message_and_retries(cl_node, hig_nodes, NetworkPartitions::default(), results, run_message, None).await
 }
 
+/// Runs the simulation with generated transactions
+///
+/// `partitions` holds the partition gates of the chains the fault timeline partitions; they must
+/// be the gates the nodes were set up with.
 pub async fn run_simulation_with_message_and_retries(
     cl_node: Arc<Mutex<ConfirmationLayerNode>>,
     hig_nodes: Vec<Arc<Mutex<HyperIGNode>>>,
+    partitions: NetworkPartitions,
     results: &mut SimulationResults,
     run_message: Option<String>,
     retry_count: Option<usize>,
 ) -> Result<(), String> {
-    
-    // Get the current block at the start
-    let start_block = cl_node.lock().await.get_current_block().await.map_err(|e| e.to_string())?;
-    
-    let target_block = start_block + results.initialization_wait_blocks;
-    
-    logging::log("SIMULATOR", &format!("Starting at block {}, waiting until block {} for initialization and stable block production...", start_block, target_block));
-    
-    // Wait until we reach the target block
-    loop {
-        let current_block = cl_node.lock().await.get_current_block().await.map_err(|e| e.to_string())?;
-        if current_block >= target_block {
+    run_simulation_with_workload(cl_node, hig_nodes, partitions, results, run_message, retry_count, None).await
+}
+
+/// Runs the simulation, submitting a previously recorded workload instead of generating transactions
+///
+/// Each recorded submission is released at the same block offset (relative to the first block
+/// of transaction submission) as in the recorded run.
+///
+/// # Arguments
+///
+/// * `cl_node` - The confirmation layer node
+/// * `hig_nodes` - The HyperIG nodes, in chain order
+/// * `partitions` - The partition gates the nodes were set up with
+/// * `results` - The results to record into
+/// * `workload` - The recorded submissions, in submission order
+/// * `run_message` - Optional message shown next to the progress bar
+pub async fn run_simulation_with_replay(
+    cl_node: Arc<Mutex<ConfirmationLayerNode>>,
+    hig_nodes: Vec<Arc<Mutex<HyperIGNode>>>,
+    partitions: NetworkPartitions,
+    results: &mut SimulationResults,
+    workload: &[RecordedSubmission],
+    run_message: Option<String>,
+) -> Result<(), String> {
+    run_simulation_with_workload(cl_node, hig_nodes, partitions, results, run_message, None, Some(workload)).await
+}
+
+/// Runs the simulation with generated transactions against a CL served by a separate process
+///
+/// The process serves its CL with `cargo run --bin main -- --cl-listen <address>`. Only the CL is
+/// reachable over the connection, so the run records the CL side of the statistics (transactions
+/// per block, CL queue length, mempool ages, chain stats and the submission rate), and the results
+/// are saved without the HIG and HS series. The CATs are resolved by the HIGs and the HS of the
+/// serving process. The configured chains must be registered with the CL, and the accounts of the
+/// workload must be funded on them before the run.
+///
+/// # Arguments
+///
+/// * `cl_endpoint` - The address the CL is served on
+/// * `results` - The results to record into
+/// * `run_message` - Optional message shown next to the progress bar
+pub async fn run_simulation_against_external_cl(
+    cl_endpoint: &str,
+    results: &mut SimulationResults,
+    run_message: Option<String>,
+) -> Result<(), String> {
+    let client = RemoteConfirmationLayer::connect(cl_endpoint).await
+        .map_err(|e| format!("Failed to connect to the CL at {}: {}", cl_endpoint, e))?;
+    let cl_node = Arc::new(Mutex::new(client));
+
+    // The workload targets the configured chains, which the external network must have registered
+    let registered = cl_node.lock().await.get_registered_chains().await.map_err(|e| e.to_string())?;
+    let chains = results.network.chain_ids().to_vec();
+    if chains.len() < 2 {
+        return Err("A run against an external CL needs at least 2 chains".to_string());
+    }
+    if let Some(missing) = chains.iter().find(|chain_id| !registered.contains(chain_id)) {
+        return Err(format!("Chain {} is not registered with the CL at {}", missing.0, cl_endpoint));
+    }
+    let chain_id_1 = chains[0].clone();
+    let chain_id_2 = chains[1].clone();
+    logging::log("SIMULATOR", &format!("Connected to the CL at {} with chains {:?}", cl_endpoint, registered));
+
+    // Wait for initialization and stable block production
+    let initial_block = wait_for_initialization(&cl_node, results.initialization_wait_blocks).await?;
+    let final_simulation_block = initial_block + results.sim_total_block_number;
+    let progress_bar = block_progress_bar(results.sim_total_block_number, run_message, None);
+
+    // Initialize the workload generator, the account selectors and the submission path as for in-process nodes
+    let mut rng = StdRng::seed_from_u64(results.run_seed);
+    let (mut account_selector_sender, mut account_selector_receiver) = account_selectors(results);
+    let mut submission_queue = SubmissionQueue::new(results.submission_batch_size, results.submission_rate_limit_tps);
+    let mut duplicate_injector = DuplicateInjector::new(results.duplicate_ratio, results.duplicate_variant_share, results.duplicate_delay_blocks, results.run_seed);
+    let mut malformed_injector = MalformedInjector::new(results.malformed_ratio, results.run_seed);
+
+    // Poll at the block interval of the external CL, which may differ from the configured one
+    let block_interval = cl_node.lock().await.get_block_interval().await.map_err(|e| e.to_string())?;
+    let wait_interval = block_interval.div_f64(results.transaction_submission_frequency as f64);
+    let target_tps = results.target_tpb as f64 / block_interval.as_secs_f64();
+    let transactions_per_block = results.target_tpb as u64;
+
+    // ------- main simulation loop -------
+
+    let mut current_block = initial_block;
+    let mut block_counter = 0;
+    let mut last_flush_block = 0;
+    while current_block < final_simulation_block {
+        let new_block = cl_node.lock().await.get_current_block().await.map_err(|e| e.to_string())?;
+        if new_block >= final_simulation_block {
+            logging::log("SIMULATOR", &format!("Reached target block {}, stopping transaction processing", final_simulation_block));
             break;
         }
-        // Sleep for a short duration before checking again
-        sleep(Duration::from_millis(100)).await;
+
+        if new_block == current_block {
+            block_counter += 1;
+            sleep(wait_interval).await;
+            continue;
+        }
+
+        let height_delta = new_block - current_block;
+        results.loop_steps_without_tx_issuance.push((current_block, block_counter));
+        results.block_height_delta.push((current_block, height_delta));
+        block_counter = 0;
+
+        // Record the data the CL provides for this height
+        record_cl_block_data(&cl_node, results, new_block, &chain_id_1, &chain_id_2).await?;
+        current_block = new_block;
+        let blocks_completed = new_block - initial_block;
+        progress_bar.set_position(blocks_completed);
+
+        // Flush the metrics so far, so an aborted run still leaves partial results
+        if results.flush_interval_blocks > 0 && blocks_completed >= last_flush_block + results.flush_interval_blocks {
+            results.flush_partial(blocks_completed)?;
+            last_flush_block = blocks_completed;
+        }
+
+        // Release transactions for this block, compensating for missed blocks
+        release_transactions_for_block(
+            &cl_node,
+            &mut submission_queue,
+            &mut duplicate_injector,
+            &mut malformed_injector,
+            &mut rng,
+            &mut account_selector_sender,
+            &mut account_selector_receiver,
+            results,
+            &chains,
+            chain_id_1.clone(),
+            chain_id_2.clone(),
+            transactions_per_block,
+            blocks_completed,
+            height_delta,
+            current_block,
+        ).await?;
+        duplicate_injector.submit_due(&cl_node, blocks_completed).await?;
     }
+
+    // Record the intended and the achieved submission rate
+    submission_queue.flush(&cl_node).await?;
+    results.intended_submission_tps = target_tps;
+    results.achieved_submission_tps = submission_queue.achieved_tps();
+    results.submission_batches = submission_queue.batches();
+    logging::log("SIMULATOR", &format!("Submitted {} transactions to the CL at {} in {} batches, intended {:.2} TPS, achieved {:.2} TPS",
+        submission_queue.submitted(), cl_endpoint, submission_queue.batches(), target_tps, results.achieved_submission_tps));
+
+    // Collect the transactions the CL included per chain
+    for chain_id in &chains {
+        let stats = cl_node.lock().await.get_chain_stats(chain_id.clone()).await.map_err(|e| e.to_string())?;
+        results.cl_chain_stats.insert(chain_id.0.clone(), stats);
+    }
+
+    // Record the injected duplicates and malformed transactions; how the external network handled them is not reachable
+    (results.duplicates_injected, results.duplicate_variants_injected) = duplicate_injector.injected();
+    results.malformed_transactions = malformed_injector.counts().clone();
+
+    Ok(())
+}
+
+/// Runs the simulation with either generated transactions or a recorded workload
+async fn run_simulation_with_workload(
+    cl_node: Arc<Mutex<ConfirmationLayerNode>>,
+    hig_nodes: Vec<Arc<Mutex<HyperIGNode>>>,
+    partitions: NetworkPartitions,
+    results: &mut SimulationResults,
+    run_message: Option<String>,
+    retry_count: Option<usize>,
+    workload: Option<&[RecordedSubmission]>,
+) -> Result<(), String> {
     
-    // Get the current block after initialization
-    let initial_block = cl_node.lock().await.get_current_block().await.map_err(|e| e.to_string())?;
-    logging::log("SIMULATOR", &format!("Initialization complete, starting simulation at block {}", initial_block));
+    // Wait for initialization and stable block production
+    let initial_block = wait_for_initialization(&cl_node, results.initialization_wait_blocks).await?;
 
     // Record the start time for transaction sending (after initialization)
     let _transaction_spam_start_time = Instant::now();
 
-    // Initialize random number generator
-    let mut rng = rand::thread_rng();
+    // Initialize the workload generator from the seed of the run, so the metadata reproduces it
+    let mut rng = StdRng::seed_from_u64(results.run_seed);
     
-    // Initialize sender account selector with uniform distribution
-    let mut account_selector_sender = AccountSelector::new(results.num_accounts, 0.0);    
-    // Initialize receiver account selector with Zipf distribution
-    let mut account_selector_receiver = AccountSelector::new(results.num_accounts, results.zipf_parameter);
+    // Initialize the sender and receiver account selectors
+    let (mut account_selector_sender, mut account_selector_receiver) = account_selectors(results);
     
     // Calculate target block number for simulation termination
     let final_simulation_block = initial_block + results.sim_total_block_number;
     
     // Create progress bar for blocks
-    let progress_bar = ProgressBar::new(results.sim_total_block_number);
-    let template = if let Some(ref msg) = run_message {
-        let retry_suffix = if let Some(retries) = retry_count {
-            if retries > 0 {
-                format!(" [RETRY {}]", retries)
-            } else {
-                String::new()
-            }
-        } else {
-            String::new()
-        };
-        format!("[{{elapsed_precise}}] {{bar:40.cyan/blue}} Block {{pos}}/{{len}} ({{eta}}) {}{}", msg, retry_suffix)
-    } else {
-        let retry_suffix = if let Some(retries) = retry_count {
-            if retries > 0 {
-                format!(" [RETRY {}]", retries)
-            } else {
-                String::new()
-            }
-        } else {
-            String::new()
-        };
-        format!("[{{elapsed_precise}}] {{bar:40.cyan/blue}} Block {{pos}}/{{len}} ({{eta}}){}", retry_suffix)
-    };
-    progress_bar.set_style(ProgressStyle::default_bar()
-        .template(&template)
-        .unwrap()
-        .progress_chars("##-"));
-    
+    let progress_bar = block_progress_bar(results.sim_total_block_number, run_message, retry_count);
+
+    // Apply the configured protocol violation policy to all HIGs
+    for hig_node in &hig_nodes {
+        hig_node.lock().await.set_protocol_violation_policy(results.protocol_violation_policy).await;
+    }
 
     // Now set the actual chain delays for the main simulation
     logging::log("SIMULATOR", "Setting actual chain delays for main simulation...");
     for (i, delay_blocks) in results.chain_delays.iter().enumerate() {
         let delay_time = Duration::from_secs_f64(results.block_interval * *delay_blocks as f64);
-        hig_nodes[i].lock().await.set_hs_message_delay(delay_time);
+        let mut hig_node = hig_nodes[i].lock().await;
+        hig_node.set_hs_message_delay(delay_time);
+        hig_node.set_hs_message_delay_distribution(results.chain_delay_distribution.scaled(results.block_interval));
         logging::log("SIMULATOR", &format!("Set chain {} delay to {} blocks ({:?})", i + 1, delay_blocks, delay_time));
     }
+
+    // Start the control channel that changes the HIG parameters on the configured schedule
+    let hig_control = HigControlChannel::spawn(hig_nodes.clone(), &results.network);
+    let mut hig_parameter_schedule = HigParameterSchedule::new(&results.hig_parameter_schedule);
+    let updates = hig_parameter_schedule.send_due(0, results.block_interval, &hig_control)?;
+    results.hig_parameter_changes.extend(updates);
+
+    // Watch for HIGs that stall while the CL advances, skipping the chains the fault timeline partitions or crashes
+    let watchdog = Watchdog::spawn(
+        WatchdogConfig {
+            max_lag_blocks: results.watchdog_max_lag_blocks,
+            queue_stall_secs: results.watchdog_queue_stall_secs,
+            abort_on_stall: results.watchdog_abort,
+            check_interval: Duration::from_secs_f64(results.block_interval),
+        },
+        cl_node.clone(),
+        hig_nodes.clone(),
+        partitions.clone(),
+        initial_block,
+    );
+
+    // Execute the faults of the configured timeline
+    let mut fault_orchestrator = FaultOrchestrator::new(&results.fault_timeline, partitions, hig_nodes.clone(), cl_node.clone());
+    let faults = fault_orchestrator.execute_due(0).await?;
+    results.fault_events.extend(faults);
     
     // Track transaction amounts per chain by height. In the chain the tx is either pending, success, or failure.
     let mut current_block = initial_block;
@@ -146,6 +386,25 @@ pub async fn run_simulation_with_message_and_retries(
     let chain_id_1 = chains[0].clone();
     let chain_id_2 = chains[1].clone();
     
+    // Sample the tokio runtime relative to the start of the main simulation
+    let mut runtime_sampler = RuntimeMetricsSampler::new();
+
+    // Flags blocks at which the TPS or the pending counts leave their recent window
+    let mut anomaly_detector = RateAnomalyDetector::new(results.rate_anomaly_window_blocks, results.rate_anomaly_sigma);
+
+    // Checks the configured balance invariants after every block
+    let mut invariant_checker = InvariantChecker::new(&results.invariants);
+
+    // Block of the run at which the aggregated metrics were last flushed
+    let mut last_flush_block = 0;
+
+    // Changes the CL block interval on the configured schedule
+    let mut block_interval_schedule = BlockIntervalSchedule::new(&results.block_interval_schedule);
+    let mut block_interval = results.block_interval;
+
+    // The loop waits on the clock of the nodes, so it keeps pace with them in virtual time
+    let clock = cl_node.lock().await.get_clock();
+    
     // ------- main simulation loop -------
 
     // Calculate target TPS from target TPB and block interval
@@ -158,8 +417,26 @@ pub async fn run_simulation_with_message_and_retries(
     // a counter to track how many times we have entered the following block without releasing transactions
     let mut block_counter = 0;
 
+    // position of the next recorded submission when replaying a workload
+    let mut replay_cursor = 0;
+
+    // Queue that submits the transactions in batches, limited to the configured rate
+    let mut submission_queue = SubmissionQueue::new(results.submission_batch_size, results.submission_rate_limit_tps);
+
+    // Re-submits a fraction of the generated transactions as duplicates
+    let mut duplicate_injector = DuplicateInjector::new(results.duplicate_ratio, results.duplicate_variant_share, results.duplicate_delay_blocks, results.run_seed);
+
+    // Submits malformed transactions next to a fraction of the generated transactions
+    let mut malformed_injector = MalformedInjector::new(results.malformed_ratio, results.run_seed);
+
     // Main simulation loop - waits for new blocks and releases transactions in batches
     while current_block < final_simulation_block {
+        // Abort the run if the watchdog found a stalled HIG and is configured to abort
+        if let Some(stall) = watchdog.abort_reason() {
+            results.watchdog_stalls = watchdog.stop();
+            return Err(format!("Watchdog aborted the run: {}", stall));
+        }
+
         // Get current block height from CL
         let new_block = cl_node.lock().await.get_current_block().await.map_err(|e| e.to_string())?;
         
@@ -192,36 +469,160 @@ pub async fn run_simulation_with_message_and_retries(
                 new_block,
                 chain_id_1.clone(),
                 chain_id_2.clone(),
+                &mut runtime_sampler,
             ).await?;
+            detect_rate_anomalies(&mut anomaly_detector, results, new_block);
             
             current_block = new_block;
             
             // Update progress bar for new block
             let blocks_completed = new_block - initial_block;
             progress_bar.set_position(blocks_completed);
+
+            // Change the CL block interval if due at this block
+            let changes = block_interval_schedule.apply_due(blocks_completed, new_block, &cl_node).await?;
+            if let Some(change) = changes.last() {
+                block_interval = change.block_interval;
+            }
+            results.block_interval_changes.extend(changes);
+
+            // Change the HIG parameters that are due at this block
+            let updates = hig_parameter_schedule.send_due(blocks_completed, results.block_interval, &hig_control)?;
+            results.hig_parameter_changes.extend(updates);
+
+            // Execute the faults that are due at this block
+            let faults = fault_orchestrator.execute_due(blocks_completed).await?;
+            results.fault_events.extend(faults);
+
+            // Check the balance invariants on the state after the previous block
+            invariant_checker.check(&hig_nodes, blocks_completed).await?;
+
+            // Flush the metrics so far, so an aborted run still leaves partial results
+            if results.flush_interval_blocks > 0 && blocks_completed >= last_flush_block + results.flush_interval_blocks {
+                results.flush_partial(blocks_completed)?;
+                last_flush_block = blocks_completed;
+            }
             
-            // Release transactions for this block, compensating for missed blocks
-            release_transactions_for_block(
-                &cl_node,
-                &mut rng,
-                &mut account_selector_sender,
-                &mut account_selector_receiver,
-                results,
-                chain_id_1.clone(),
-                chain_id_2.clone(),
-                transactions_per_block,
-                new_block,
-                height_delta,
-            ).await?;
+            if let Some(workload) = workload {
+                // Replay the recorded submissions up to this block
+                replay_transactions_for_block(
+                    &cl_node,
+                    &mut submission_queue,
+                    results,
+                    workload,
+                    &mut replay_cursor,
+                    blocks_completed,
+                ).await?;
+            } else {
+                // Release transactions for this block, compensating for missed blocks
+                release_transactions_for_block(
+                    &cl_node,
+                    &mut submission_queue,
+                    &mut duplicate_injector,
+                    &mut malformed_injector,
+                    &mut rng,
+                    &mut account_selector_sender,
+                    &mut account_selector_receiver,
+                    results,
+                    &chains,
+                    chain_id_1.clone(),
+                    chain_id_2.clone(),
+                    transactions_per_block,
+                    blocks_completed,
+                    height_delta,
+                    current_block,
+                ).await?;
+
+                // Submit the duplicates that are due at this block
+                duplicate_injector.submit_due(&cl_node, blocks_completed).await?;
+            }
         } else {
             // increment the block counter
             block_counter += 1;
 
             // Wait in intervals based on transaction submission frequency
-            let wait_interval = Duration::from_secs_f64(results.block_interval / results.transaction_submission_frequency as f64);
-            tokio::time::sleep(wait_interval).await;
+            let wait_interval = Duration::from_secs_f64(block_interval / results.transaction_submission_frequency as f64);
+            clock.sleep(wait_interval).await;
         }
     }
+
+    // Wait until the HIGs have applied all parameter changes
+    hig_control.close().await?;
+
+    // Record the stalls the watchdog detected
+    results.watchdog_stalls = watchdog.stop();
+
+    // Record the stalls and surges the anomaly detector flagged
+    results.rate_anomalies = anomaly_detector.anomalies().to_vec();
+
+    // Record the intended and the achieved submission rate
+    submission_queue.flush(&cl_node).await?;
+    results.intended_submission_tps = target_tps;
+    results.achieved_submission_tps = submission_queue.achieved_tps();
+    results.submission_batches = submission_queue.batches();
+    logging::log("SIMULATOR", &format!("Submitted {} transactions in {} batches, intended {:.2} TPS, achieved {:.2} TPS",
+        submission_queue.submitted(), submission_queue.batches(), target_tps, results.achieved_submission_tps));
+
+    // Collect the transactions the CL included per chain and the utilization of its blocks
+    for chain_id in &chains {
+        let stats = cl_node.lock().await.get_chain_stats(chain_id.clone()).await.map_err(|e| e.to_string())?;
+        results.cl_chain_stats.insert(chain_id.0.clone(), stats);
+    }
+    results.cl_block_utilization = cl_node.lock().await.get_block_utilization().await;
+    results.cl_inter_block_times = cl_node.lock().await.get_inter_block_times().await;
+
+    // Check that the CL discarded the injected duplicates before they reached the HIGs
+    record_duplicate_counters(&cl_node, results, &duplicate_injector).await;
+
+    // Record how the malformed transactions were rejected
+    results.malformed_transactions = malformed_injector.counts().clone();
+    if results.malformed_transactions.accepted_by_cl > 0 {
+        logging::log("SIMULATOR", &format!("Warning: the CL accepted {} malformed transactions, of which the HIGs skipped {}",
+            results.malformed_transactions.accepted_by_cl, results.hig_malformed_transactions));
+    }
+
+    // Collect the final status counts split by CAT fanout and by lock contention
+    results.chain_1_status_breakdown = collect_status_breakdown(&hig_nodes[0]).await?;
+    results.chain_2_status_breakdown = collect_status_breakdown(&hig_nodes[1]).await?;
+ 
+    // Collect the state roots of both chains for every block of the run
+    results.chain_1_state_roots = collect_state_roots(&hig_nodes[0], initial_block, current_block).await;
+    results.chain_2_state_roots = collect_state_roots(&hig_nodes[1], initial_block, current_block).await;
+
+    // Record the final account balances of both chains
+    results.chain_1_final_state = hig_nodes[0].lock().await.get_chain_state().await.unwrap_or_default();
+    results.chain_2_final_state = hig_nodes[1].lock().await.get_chain_state().await.unwrap_or_default();
+
+    // Collect the time both HIGs spent on every subblock of the run
+    results.chain_1_subblock_timings = collect_subblock_timings(&hig_nodes[0], initial_block, current_block).await;
+    results.chain_2_subblock_timings = collect_subblock_timings(&hig_nodes[1], initial_block, current_block).await;
+
+    // Collect the time transactions waited for each locked key
+    results.chain_1_key_lock_waits = collect_key_lock_waits(&hig_nodes[0]).await;
+    results.chain_2_key_lock_waits = collect_key_lock_waits(&hig_nodes[1]).await;
+
+    // Collect the realized delays of the proposals of both HIGs
+    results.chain_1_realized_delays = collect_realized_chain_delays(&hig_nodes[0]).await;
+    results.chain_2_realized_delays = collect_realized_chain_delays(&hig_nodes[1]).await;
+
+    // Collect the outcome of every CAT with its fanout and longest realized chain delay
+    results.cat_outcomes = collect_cat_outcomes(&hig_nodes, &chains, &results.recorded_workload).await;
+    results.chain_pair_matrix = ChainPairMatrix::from_outcomes(&results.cat_outcomes, &chains, &results.chain_delays);
+
+    // Compare the final state of the chains for the submitted CATs
+    results.state_divergence = analyze_state_divergence(&hig_nodes, &chains, &results.submitted_cats).await?;
+    if !results.state_divergence.is_consistent() {
+        logging::log("SIMULATOR", &format!("State divergence detected for {} CATs", results.state_divergence.divergent_cats.len()));
+    }
+
+    // Check the balance invariants on the final state and fail the run if any was violated
+    invariant_checker.check(&hig_nodes, current_block - initial_block).await?;
+    invariant_checker.finish()?;
+
+    // Collect the final status of every sub-transaction for the Parquet export
+    if results.export_parquet {
+        results.transaction_records = collect_transaction_records(&hig_nodes, &chains, &results.recorded_workload).await;
+    }
  
     // Save results - removed for sweep simulations that handle their own saving
     // results.save().await?;
@@ -229,10 +630,102 @@ pub async fn run_simulation_with_message_and_retries(
     Ok(())
 }
 
+// ------------------------------------------------------------------------------------------------
+// Setup Functions
+// ------------------------------------------------------------------------------------------------
+
+/// Waits until the CL has produced `wait_blocks` blocks and returns the block the simulation starts at
+async fn wait_for_initialization<C: ConfirmationLayer>(cl_node: &Arc<Mutex<C>>, wait_blocks: u64) -> Result<u64, String> {
+    // Get the current block at the start
+    let start_block = cl_node.lock().await.get_current_block().await.map_err(|e| e.to_string())?;
+    
+    let target_block = start_block + wait_blocks;
+    
+    logging::log("SIMULATOR", &format!("Starting at block {}, waiting until block {} for initialization and stable block production...", start_block, target_block));
+    
+    // Wait until we reach the target block
+    loop {
+        let current_block = cl_node.lock().await.get_current_block().await.map_err(|e| e.to_string())?;
+        if current_block >= target_block {
+            break;
+        }
+        // Sleep for a short duration before checking again
+        sleep(Duration::from_millis(100)).await;
+    }
+    
+    // Get the current block after initialization
+    let initial_block = cl_node.lock().await.get_current_block().await.map_err(|e| e.to_string())?;
+    logging::log("SIMULATOR", &format!("Initialization complete, starting simulation at block {}", initial_block));
+    Ok(initial_block)
+}
+
+/// Creates the sender and the receiver account selector
+///
+/// Senders are drawn with a uniform distribution and receivers with a Zipf distribution; with
+/// sink accounts, senders are drawn below the sink accounts and receivers among them.
+fn account_selectors(results: &SimulationResults) -> (AccountSelector, AccountSelector) {
+    if results.sink_accounts > 0 {
+        let source_accounts = results.num_accounts - results.sink_accounts;
+        (AccountSelector::new(source_accounts, 0.0),
+         AccountSelector::in_range(source_accounts + 1, results.sink_accounts, results.zipf_parameter))
+    } else {
+        (AccountSelector::new(results.num_accounts, 0.0),
+         AccountSelector::new(results.num_accounts, results.zipf_parameter))
+    }
+}
+
+/// Creates the progress bar over the blocks of a run
+fn block_progress_bar(total_blocks: u64, run_message: Option<String>, retry_count: Option<usize>) -> ProgressBar {
+    let progress_bar = ProgressBar::new(total_blocks);
+    let retry_suffix = match retry_count {
+        Some(retries) if retries > 0 => format!(" [RETRY {}]", retries),
+        _ => String::new(),
+    };
+    let template = if let Some(ref msg) = run_message {
+        format!("[{{elapsed_precise}}] {{bar:40.cyan/blue}} Block {{pos}}/{{len}} ({{eta}}) {}{}", msg, retry_suffix)
+    } else {
+        format!("[{{elapsed_precise}}] {{bar:40.cyan/blue}} Block {{pos}}/{{len}} ({{eta}}){}", retry_suffix)
+    };
+    progress_bar.set_style(ProgressStyle::default_bar()
+        .template(&template)
+        .unwrap()
+        .progress_chars("##-"));
+    progress_bar
+}
+
 // ------------------------------------------------------------------------------------------------
 // Data Processing Functions
 // ------------------------------------------------------------------------------------------------
 
+/// Checks the TPS and the pending counts of both chains at a block against their recent window
+///
+/// Must be called after the data of the block was recorded. Every anomaly that starts at the
+/// block is logged.
+fn detect_rate_anomalies(detector: &mut RateAnomalyDetector, results: &SimulationResults, block_height: u64) {
+    let last = |series: &[(u64, u64)]| series.last().map_or(0.0, |(_, count)| *count as f64);
+    let transactions = last(&results.chain_1_tx_per_block) + last(&results.chain_2_tx_per_block);
+    let metrics = [
+        ("tps", transactions / results.block_interval),
+        ("chain_1_pending", last(&results.chain_1_pending)),
+        ("chain_2_pending", last(&results.chain_2_pending)),
+    ];
+    for (metric, value) in metrics {
+        if let Some(anomaly) = detector.observe(metric, block_height, value) {
+            logging::log("SIMULATOR", &format!("⚠️ RATE ANOMALY at block {}: {} = {:.1}, window mean {:.1} ± {:.1} ({:.1} sigma)",
+                block_height, metric, anomaly.value, anomaly.window_mean, anomaly.window_std, anomaly.peak_sigmas));
+        }
+    }
+}
+
+/// Collects the status counts of a HIG split by CAT fanout and by lock contention
+async fn collect_status_breakdown(hig_node: &Arc<Mutex<HyperIGNode>>) -> Result<StatusCountsBreakdown, String> {
+    let node = hig_node.lock().await;
+    let cats_by_fanout = node.get_transaction_status_counts_cats_by_fanout().await.map_err(|e| e.to_string())?;
+    let (cats_ever_blocked, cats_never_blocked) = node.get_transaction_status_counts_cats_by_blocked().await.map_err(|e| e.to_string())?;
+    let (regular_ever_blocked, regular_never_blocked) = node.get_transaction_status_counts_regular_by_blocked().await.map_err(|e| e.to_string())?;
+    Ok(StatusCountsBreakdown { cats_by_fanout, cats_ever_blocked, cats_never_blocked, regular_ever_blocked, regular_never_blocked })
+}
+
 /// Processes and records all data for a single block
 async fn process_block_data(
     cl_node: &Arc<Mutex<ConfirmationLayerNode>>,
@@ -241,6 +734,7 @@ async fn process_block_data(
     block_height: u64,
     chain_id_1: ChainId,
     chain_id_2: ChainId,
+    runtime_sampler: &mut RuntimeMetricsSampler,
 ) -> Result<(), String> {
     // Get CAT transaction status counts
     let (chain_1_cat_pending, chain_1_cat_success, chain_1_cat_failure) = hig_nodes[0].lock().await.get_transaction_status_counts_cats().await.map_err(|e| e.to_string())?;
@@ -250,6 +744,10 @@ async fn process_block_data(
     let (chain_1_cat_pending_resolving, chain_1_cat_pending_postponed) = hig_nodes[0].lock().await.get_cat_pending_detailed_counts().await.map_err(|e| e.to_string())?;
     let (chain_2_cat_pending_resolving, chain_2_cat_pending_postponed) = hig_nodes[1].lock().await.get_cat_pending_detailed_counts().await.map_err(|e| e.to_string())?;
     
+    // Get detailed CAT failure state counts
+    let (chain_1_cat_timed_out, chain_1_cat_rejected) = hig_nodes[0].lock().await.get_cat_failure_detailed_counts().await.map_err(|e| e.to_string())?;
+    let (chain_2_cat_timed_out, chain_2_cat_rejected) = hig_nodes[1].lock().await.get_cat_failure_detailed_counts().await.map_err(|e| e.to_string())?;
+    
     // Get regular transaction status counts
     let (chain_1_regular_pending, chain_1_regular_success, chain_1_regular_failure) = hig_nodes[0].lock().await.get_transaction_status_counts_regular().await.map_err(|e| e.to_string())?;
     let (chain_2_regular_pending, chain_2_regular_success, chain_2_regular_failure) = hig_nodes[1].lock().await.get_transaction_status_counts_regular().await.map_err(|e| e.to_string())?;
@@ -266,34 +764,14 @@ async fn process_block_data(
     let chain_2_success = chain_2_cat_success + chain_2_regular_success;
     let chain_2_failure = chain_2_cat_failure + chain_2_regular_failure;
     
-    // Subtract initialization transactions from success counts
-    // Each account gets one initialization credit transaction per chain
-    let init_tx_count = results.num_accounts as u64;
-    let chain_1_success_filtered = chain_1_success.saturating_sub(init_tx_count);
-    let chain_2_success_filtered = chain_2_success.saturating_sub(init_tx_count);
-    
-    // Get transactions per block for current block (only once per block)
-    // Filter out status update transactions as they are internal system messages, not user transactions
-    let chain_1_tx_per_block = cl_node.lock().await.get_subblock(chain_id_1.clone(), block_height).await
-        .map(|subblock| {
-            subblock.transactions.iter()
-                .filter(|tx| !tx.data.starts_with("STATUS_UPDATE"))
-                .count() as u64
-        })
-        .unwrap_or(0);
-    let chain_2_tx_per_block = cl_node.lock().await.get_subblock(chain_id_2.clone(), block_height).await
-        .map(|subblock| {
-            subblock.transactions.iter()
-                .filter(|tx| !tx.data.starts_with("STATUS_UPDATE"))
-                .count() as u64
-        })
-        .unwrap_or(0);
+    // Record the transactions per block, the CL queue length and the CL mempool ages
+    record_cl_block_data(cl_node, results, block_height, &chain_id_1, &chain_id_2).await?;
     
     // Record combined totals (for backward compatibility)
     results.chain_1_pending.push((block_height, chain_1_pending));    
     results.chain_2_pending.push((block_height, chain_2_pending));
-    results.chain_1_success.push((block_height, chain_1_success_filtered));
-    results.chain_2_success.push((block_height, chain_2_success_filtered));
+    results.chain_1_success.push((block_height, chain_1_success));
+    results.chain_2_success.push((block_height, chain_2_success));
     results.chain_1_failure.push((block_height, chain_1_failure));
     results.chain_2_failure.push((block_height, chain_2_failure));
     
@@ -311,6 +789,12 @@ async fn process_block_data(
     results.chain_1_cat_pending_postponed.push((block_height, chain_1_cat_pending_postponed));
     results.chain_2_cat_pending_postponed.push((block_height, chain_2_cat_pending_postponed));
     
+    // Record detailed CAT failure state data
+    results.chain_1_cat_timed_out.push((block_height, chain_1_cat_timed_out));
+    results.chain_2_cat_timed_out.push((block_height, chain_2_cat_timed_out));
+    results.chain_1_cat_rejected.push((block_height, chain_1_cat_rejected));
+    results.chain_2_cat_rejected.push((block_height, chain_2_cat_rejected));
+    
     // Record regular transaction data
     results.chain_1_regular_pending.push((block_height, chain_1_regular_pending));
     results.chain_2_regular_pending.push((block_height, chain_2_regular_pending));
@@ -323,10 +807,6 @@ async fn process_block_data(
     results.chain_1_locked_keys.push((block_height, chain_1_locked_keys));
     results.chain_2_locked_keys.push((block_height, chain_2_locked_keys));
     
-    // Record transactions per block data (excluding status updates)
-    results.chain_1_tx_per_block.push((block_height, chain_1_tx_per_block));
-    results.chain_2_tx_per_block.push((block_height, chain_2_tx_per_block));
-    
     // Record regular transaction timing metrics
     let chain_1_avg_latency = hig_nodes[0].lock().await.get_average_regular_tx_latency().await;
     let chain_2_avg_latency = hig_nodes[1].lock().await.get_average_regular_tx_latency().await;
@@ -342,17 +822,22 @@ async fn process_block_data(
     results.chain_1_regular_tx_finalized_count.push((block_height, chain_1_finalized_count));
     results.chain_2_regular_tx_finalized_count.push((block_height, chain_2_finalized_count));
     
-    // Log TPB information for debugging
-    let chain_1_total = cl_node.lock().await.get_subblock(chain_id_1.clone(), block_height).await
-        .map(|subblock| subblock.transactions.len() as u64)
-        .unwrap_or(0);
-    let chain_2_total = cl_node.lock().await.get_subblock(chain_id_2.clone(), block_height).await
-        .map(|subblock| subblock.transactions.len() as u64)
-        .unwrap_or(0);
-    
-    if chain_1_total != chain_1_tx_per_block || chain_2_total != chain_2_tx_per_block {
-        logging::log("SIMULATOR", &format!("Block {}: Chain-1 TPB: {} (filtered from {} total), Chain-2 TPB: {} (filtered from {} total)", 
-            block_height, chain_1_tx_per_block, chain_1_total, chain_2_tx_per_block, chain_2_total));
+    // Record protocol violations and abort the run if the policy requires it
+    let chain_1_protocol_violations = hig_nodes[0].lock().await.get_protocol_violation_count().await;
+    let chain_2_protocol_violations = hig_nodes[1].lock().await.get_protocol_violation_count().await;
+    results.chain_1_protocol_violations.push((block_height, chain_1_protocol_violations));
+    results.chain_2_protocol_violations.push((block_height, chain_2_protocol_violations));
+    if results.protocol_violation_policy == ProtocolViolationPolicy::FailRun {
+        let mut violations = Vec::with_capacity(hig_nodes.len());
+        for (hig_node, chain_id) in hig_nodes.iter().zip(results.network.chain_ids()) {
+            let count = hig_node.lock().await.get_protocol_violation_count().await;
+            if count > 0 {
+                violations.push(format!("{}: {}", chain_id.0, count));
+            }
+        }
+        if !violations.is_empty() {
+            return Err(format!("Protocol violation detected at block {} ({})", block_height, violations.join(", ")));
+        }
     }
     
     // Record memory usage for this block
@@ -371,11 +856,114 @@ async fn process_block_data(
     let total_cpu_usage = crate::SimulationResults::get_current_total_cpu_usage();
     results.total_cpu_usage.push((block_height, total_cpu_usage));
     
+    // Record runtime worker thread CPU usage for this block
+    let (worker_cpu_usage, worker_cpu_usage_max) = crate::SimulationResults::get_current_worker_cpu_usage();
+    results.worker_cpu_usage.push((block_height, worker_cpu_usage));
+    results.worker_cpu_usage_max.push((block_height, worker_cpu_usage_max));
+    
+    // Record tokio runtime scheduling state for this block
+    let runtime_sample = runtime_sampler.sample();
+    results.runtime_alive_tasks.push((block_height, runtime_sample.alive_tasks));
+    results.runtime_queue_depth.push((block_height, runtime_sample.queue_depth));
+    results.runtime_busy.push((block_height, runtime_sample.busy_percent));
+    results.runtime_poll_latency.push((block_height, runtime_sample.mean_poll_time_us));
+    
+    // Record the depth and the age of the oldest entry of the HIG pending-proposal queues
+    for (hig_node, depth_series, age_series) in [
+        (&hig_nodes[0], &mut results.chain_1_proposal_queue_depth, &mut results.chain_1_proposal_queue_age_latency),
+        (&hig_nodes[1], &mut results.chain_2_proposal_queue_depth, &mut results.chain_2_proposal_queue_age_latency),
+    ] {
+        let node = hig_node.lock().await;
+        depth_series.push((block_height, node.get_proposal_queue_depth().await));
+        age_series.push((block_height, node.get_proposal_queue_max_age().await.as_secs_f64() * 1000.0));
+    }
+    results.dropped_proposals = hig_nodes[0].lock().await.get_dropped_proposal_count().await
+        + hig_nodes[1].lock().await.get_dropped_proposal_count().await;
+    
+    // Record the ages of the pending CATs (blocks since each CAT was received)
+    results.chain_1_cat_age_histogram.push((block_height, hig_nodes[0].lock().await.get_pending_cat_ages().await));
+    results.chain_2_cat_age_histogram.push((block_height, hig_nodes[1].lock().await.get_pending_cat_ages().await));
+    
+    // Record the number of transactions the HIGs received more than once
+    results.duplicate_transactions = hig_nodes[0].lock().await.get_duplicate_transaction_count().await
+        + hig_nodes[1].lock().await.get_duplicate_transaction_count().await;
+    
+    // Record the number of CATs the HIGs received with the id of another CAT
+    results.cat_id_collisions = hig_nodes[0].lock().await.get_cat_id_collision_count().await
+        + hig_nodes[1].lock().await.get_cat_id_collision_count().await;
+    
+    // Record the number of malformed transactions the HIGs skipped
+    results.hig_malformed_transactions = hig_nodes[0].lock().await.get_malformed_transaction_count().await
+        + hig_nodes[1].lock().await.get_malformed_transaction_count().await;
+    
+    // Record the number of dependency cycles the HIGs detected
+    results.dependency_cycles = hig_nodes[0].lock().await.get_dependency_cycle_count().await
+        + hig_nodes[1].lock().await.get_dependency_cycle_count().await;
+    
+    // Record the number of CATs that released their locks on a Failure proposal
+    results.early_lock_releases = hig_nodes[0].lock().await.get_early_lock_release_count().await
+        + hig_nodes[1].lock().await.get_early_lock_release_count().await;
+    
+    // Record the number of regular transactions that expired while pending
+    results.regular_tx_expirations = hig_nodes[0].lock().await.get_expired_transaction_count().await
+        + hig_nodes[1].lock().await.get_expired_transaction_count().await;
+    
+    Ok(())
+}
+
+/// Records the data the CL provides for a single block
+///
+/// These are the transactions per block (excluding status updates), the CL queue length and the
+/// ages of the transactions waiting in the CL mempool, which are also available from an external CL.
+async fn record_cl_block_data<C: ConfirmationLayer>(
+    cl_node: &Arc<Mutex<C>>,
+    results: &mut SimulationResults,
+    block_height: u64,
+    chain_id_1: &ChainId,
+    chain_id_2: &ChainId,
+) -> Result<(), String> {
+    // Get transactions per block for current block (only once per block)
+    // Filter out status update transactions as they are internal system messages, not user transactions
+    let chain_1_subblock = cl_node.lock().await.get_subblock(chain_id_1.clone(), block_height).await.ok();
+    let chain_2_subblock = cl_node.lock().await.get_subblock(chain_id_2.clone(), block_height).await.ok();
+    let count_user_transactions = |subblock: &Option<SubBlock>| subblock.as_ref()
+        .map(|subblock| {
+            subblock.transactions.iter()
+                .filter(|tx| !tx.data.starts_with("STATUS_UPDATE"))
+                .count() as u64
+        })
+        .unwrap_or(0);
+    let chain_1_tx_per_block = count_user_transactions(&chain_1_subblock);
+    let chain_2_tx_per_block = count_user_transactions(&chain_2_subblock);
+    
+    // Record transactions per block data (excluding status updates)
+    results.chain_1_tx_per_block.push((block_height, chain_1_tx_per_block));
+    results.chain_2_tx_per_block.push((block_height, chain_2_tx_per_block));
+    
+    // Log TPB information for debugging
+    let chain_1_total = chain_1_subblock.map(|subblock| subblock.transactions.len() as u64).unwrap_or(0);
+    let chain_2_total = chain_2_subblock.map(|subblock| subblock.transactions.len() as u64).unwrap_or(0);
+    
+    if chain_1_total != chain_1_tx_per_block || chain_2_total != chain_2_tx_per_block {
+        logging::log("SIMULATOR", &format!("Block {}: Chain-1 TPB: {} (filtered from {} total), Chain-2 TPB: {} (filtered from {} total)", 
+            block_height, chain_1_tx_per_block, chain_1_total, chain_2_tx_per_block, chain_2_total));
+    }
+    
     // Record CL queue length for this block
     let cl_queue_length = cl_node.lock().await.get_pending_transactions().await
         .map_err(|e| format!("Failed to get CL queue length: {}", e))?;
     results.cl_queue_length.push((block_height, cl_queue_length as u64));
     
+    // Record the ages of the transactions waiting in the CL mempool for this block
+    let chain_1_mempool = cl_node.lock().await.get_mempool(chain_id_1.clone()).await
+        .map_err(|e| format!("Failed to get CL mempool for chain 1: {}", e))?;
+    let chain_2_mempool = cl_node.lock().await.get_mempool(chain_id_2.clone()).await
+        .map_err(|e| format!("Failed to get CL mempool for chain 2: {}", e))?;
+    results.chain_1_mempool_age_p50.push((block_height, chain_1_mempool.age_percentiles.p50));
+    results.chain_2_mempool_age_p50.push((block_height, chain_2_mempool.age_percentiles.p50));
+    results.chain_1_mempool_age_p99.push((block_height, chain_1_mempool.age_percentiles.p99));
+    results.chain_2_mempool_age_p99.push((block_height, chain_2_mempool.age_percentiles.p99));
+    
     Ok(())
 }
 
@@ -384,17 +972,22 @@ async fn process_block_data(
 // ------------------------------------------------------------------------------------------------
 
 /// Releases all transactions for a single block
-async fn release_transactions_for_block(
-    cl_node: &Arc<Mutex<ConfirmationLayerNode>>,
-    rng: &mut rand::rngs::ThreadRng,
+async fn release_transactions_for_block<C: ConfirmationLayer + 'static>(
+    cl_node: &Arc<Mutex<C>>,
+    submission_queue: &mut SubmissionQueue,
+    duplicate_injector: &mut DuplicateInjector,
+    malformed_injector: &mut MalformedInjector,
+    rng: &mut StdRng,
     account_selector_sender: &mut AccountSelector,
     account_selector_receiver: &mut AccountSelector,
     results: &mut SimulationResults,
+    chains: &[ChainId],
     chain_id_1: ChainId,
     chain_id_2: ChainId,
     transactions_per_block: u64,
-    _current_block: u64,
+    block_offset: u64,
     height_delta: u64,
+    current_block: u64,
 ) -> Result<(), String> {
     // Calculate total transactions to send (compensate for missed blocks)
     let total_transactions = transactions_per_block * height_delta;
@@ -410,14 +1003,23 @@ async fn release_transactions_for_block(
         // Record transaction in account statistics
         results.account_stats.record_transaction(from_account as u64, to_account as u64);
         
-        // Determine if this should be a CAT transaction based on configured ratio
-        let is_cat = rng.gen_bool(results.ratio_cats);
+        // Determine if this should be a CAT transaction based on configured ratio, either the
+        // global one or the one of a uniformly chosen initiating chain
+        let (initiating_chain, is_cat) = if results.chain_ratio_cats.is_empty() {
+            (None, rng.gen_bool(results.ratio_cats))
+        } else {
+            let chain_index = rng.gen_range(0..chains.len());
+            let ratio = results.chain_ratio_cats.get(chain_index).copied().unwrap_or(results.ratio_cats);
+            (Some(chains[chain_index].clone()), rng.gen_bool(ratio))
+        };
         
-        // Create transaction data
-        let tx_data = format!("{}.send {} {} 1", 
+        // Draw the amount and create the transaction data
+        let amount = results.amount_distribution.sample(rng);
+        let tx_data = format!("{}.send {} {} {}", 
             if is_cat { "CAT" } else { "REGULAR" },
             from_account,
-            to_account
+            to_account,
+            amount
         );
         
         // Create and submit transaction
@@ -426,106 +1028,311 @@ async fn release_transactions_for_block(
             results.transactions_sent
         ));
         
-        let (success, _) = if is_cat {
+        // CL transactions built for this transaction, recorded for replay
+        let mut cl_transactions = Vec::new();
+        // The chain that initiated the CAT, recorded for replay
+        let mut cat_initiator = None;
+        
+        if is_cat {
             results.cat_transactions += 1;
+            let mut cat_chains = select_cat_chains(rng, chains, &results.cat_fanout, results.cat_chain_selection)?;
+            if let Some(initiator) = &initiating_chain {
+                include_initiating_chain(&mut cat_chains, initiator, chains);
+            }
+            let initiator = initiating_chain.clone().unwrap_or_else(|| cat_chains[0].clone());
+            *results.cat_fanout_counts.entry(cat_chains.len()).or_insert(0) += 1;
+            *results.cat_initiations_per_chain.entry(initiator.0.clone()).or_insert(0) += 1;
+            cat_initiator = Some(initiator.clone());
+            let cl_tx = if results.cat_transfers {
+                // The initiating chain is the source of the transfer
+                let destination = cat_chains.iter().find(|chain_id| **chain_id != initiator).cloned()
+                    .ok_or_else(|| "A CAT transfer needs a second chain".to_string())?;
+                CatBuilder::transfer(initiator.clone(), destination, from_account as u32, to_account as u32, amount)
+                    .build(cl_id)
+            } else {
+                build_cat_transaction(cl_id, cat_chains, &tx_data)
+            }.map_err(|e| {
+                logging::log("SIMULATOR", &format!("Failed to create CAT CL transaction: {}", e));
+                e
+            })?;
             create_and_submit_cat_transaction(
                 cl_node,
-                cl_id,
-                chain_id_1.clone(),
-                chain_id_2.clone(),
+                submission_queue,
+                cl_tx,
                 tx_data.clone(),
+                &mut cl_transactions,
+                (results.cat_part_spread_blocks > 0.0)
+                    .then(|| Duration::from_secs_f64(results.cat_part_spread_blocks * results.block_interval)),
             ).await?
         } else {
             results.regular_transactions += 1;
             create_and_submit_regular_transaction(
                 cl_node,
+                submission_queue,
                 cl_id,
                 chain_id_1.clone(),
                 chain_id_2.clone(),
                 tx_data.clone(),
-            ).await?
+                &mut cl_transactions,
+                (results.regular_tx_ttl_blocks > 0).then(|| current_block + results.regular_tx_ttl_blocks),
+            ).await?;
+        }
+        
+        // Duplicate whole CL transactions only, as each part of a spread CAT is deduplicated separately
+        if !is_cat || results.cat_part_spread_blocks <= 0.0 {
+            duplicate_injector.schedule(&cl_transactions, block_offset);
+        }
+        
+        // Follow the transaction with a malformed one, which must be rejected
+        malformed_injector.maybe_submit(cl_node, chains, from_account, to_account).await;
+        
+        let submission = RecordedSubmission {
+            block_offset,
+            is_cat,
+            from_account,
+            to_account,
+            cl_transactions,
+            initiating_chain: cat_initiator,
         };
+        if is_cat {
+            record_submitted_cat(results, &submission);
+        }
+        results.recorded_workload.push(submission);
+
+        logging::log("SIMULATOR", &format!("Transaction {} released: {}", tx_index + 1, tx_data));
+        results.transactions_sent += 1;
+    }
+
+    // Submit the transactions of this block that did not fill a batch
+    submission_queue.flush(cl_node).await
+}
+
+/// Records the injected duplicates and the duplicates the CL discarded, and warns if the counters do not add up
+///
+/// Every injected duplicate is either discarded by the CL or still pending at the end of the run, and
+/// none of them may reach the HIGs.
+async fn record_duplicate_counters(
+    cl_node: &Arc<Mutex<ConfirmationLayerNode>>,
+    results: &mut SimulationResults,
+    duplicate_injector: &DuplicateInjector,
+) {
+    (results.duplicates_injected, results.duplicate_variants_injected) = duplicate_injector.injected();
+    let stats = cl_node.lock().await.get_duplicate_stats().await;
+    results.cl_duplicates_rejected = stats.rejected_submissions;
+    results.cl_duplicates_dropped = stats.dropped_pending;
+    if results.duplicates_injected == 0 {
+        return;
+    }
+    logging::log("SIMULATOR", &format!("Injected {} duplicates ({} variants), the CL rejected {} and dropped {}, the HIGs received {}",
+        results.duplicates_injected, results.duplicate_variants_injected, stats.rejected_submissions, stats.dropped_pending, results.duplicate_transactions));
+    if stats.total() > results.duplicates_injected {
+        logging::log("SIMULATOR", &format!("Warning: the CL discarded {} duplicates, but only {} were injected", stats.total(), results.duplicates_injected));
+    }
+    if results.duplicate_transactions > 0 {
+        logging::log("SIMULATOR", &format!("Warning: {} duplicate transactions reached the HIGs", results.duplicate_transactions));
+    }
+}
+
+/// Submits the recorded submissions that were released up to the given block offset
+///
+/// # Arguments
+///
+/// * `cl_node` - A reference to the confirmation layer node
+/// * `submission_queue` - The queue the transactions are submitted through
+/// * `results` - The results to record into
+/// * `workload` - The recorded submissions, in submission order
+/// * `replay_cursor` - Position of the next submission to replay
+/// * `block_offset` - The current block offset relative to the first block of transaction submission
+async fn replay_transactions_for_block(
+    cl_node: &Arc<Mutex<ConfirmationLayerNode>>,
+    submission_queue: &mut SubmissionQueue,
+    results: &mut SimulationResults,
+    workload: &[RecordedSubmission],
+    replay_cursor: &mut usize,
+    block_offset: u64,
+) -> Result<(), String> {
+    let start = *replay_cursor;
+    while *replay_cursor < workload.len() && workload[*replay_cursor].block_offset <= block_offset {
+        let submission = &workload[*replay_cursor];
+        *replay_cursor += 1;
 
-        if success {
-            logging::log("SIMULATOR", &format!("Transaction {} successful: {}", tx_index + 1, tx_data));
+        results.account_stats.record_transaction(submission.from_account as u64, submission.to_account as u64);
+        if submission.is_cat {
+            results.cat_transactions += 1;
+            if let Some(cl_tx) = submission.cl_transactions.first() {
+                *results.cat_fanout_counts.entry(cl_tx.constituent_chains.len()).or_insert(0) += 1;
+                let initiator = submission.initiating_chain.as_ref().or(cl_tx.constituent_chains.first());
+                if let Some(initiator) = initiator {
+                    *results.cat_initiations_per_chain.entry(initiator.0.clone()).or_insert(0) += 1;
+                }
+            }
+            record_submitted_cat(results, submission);
         } else {
-            logging::log("SIMULATOR", &format!("Transaction {} failed: {}", tx_index + 1, tx_data));
-            panic!("Transaction failed submitted to CL");
+            results.regular_transactions += 1;
         }
-        
+
+        submission_queue.submit(cl_node, submission.cl_transactions.clone()).await
+            .map_err(|e| format!("Failed to replay transaction: {}", e))?;
+
+        results.recorded_workload.push(submission.clone());
         results.transactions_sent += 1;
     }
-    
+
+    submission_queue.flush(cl_node).await
+        .map_err(|e| format!("Failed to replay transaction: {}", e))?;
+    logging::log("SIMULATOR", &format!("Replayed {} transactions up to block offset {}", *replay_cursor - start, block_offset));
     Ok(())
 }
 
+/// Records a submitted CAT for the post-run state divergence analysis
+fn record_submitted_cat(results: &mut SimulationResults, submission: &RecordedSubmission) {
+    for cl_tx in &submission.cl_transactions {
+        results.submitted_cats.push(SubmittedCat {
+            cat_id: CATId(cl_tx.id.clone()),
+            sub_transactions: cl_tx.transactions.iter()
+                .map(|tx| (tx.chain_id.clone(), tx.id.clone()))
+                .collect(),
+            accounts: vec![submission.from_account.to_string(), submission.to_account.to_string()],
+        });
+    }
+}
+
+/// Selects the constituent chains of a CAT according to the configured fanout distribution
+///
+/// # Arguments
+///
+/// * `rng` - The random number generator
+/// * `chains` - The registered chains, in registration order
+/// * `cat_fanout` - The fanout distribution (number of chains and relative weight)
+/// * `selection` - How the chains are picked among the registered chains
+fn select_cat_chains(
+    rng: &mut StdRng,
+    chains: &[ChainId],
+    cat_fanout: &[CatFanoutWeight],
+    selection: CatChainSelection,
+) -> Result<Vec<ChainId>, String> {
+    let weights = WeightedIndex::new(cat_fanout.iter().map(|f| f.weight))
+        .map_err(|e| format!("Invalid CAT fanout distribution: {}", e))?;
+    let fanout = cat_fanout[weights.sample(rng)].chains;
+    if fanout < 2 || fanout > chains.len() {
+        return Err(format!("CAT fanout {} is not possible with {} registered chains", fanout, chains.len()));
+    }
+    let selected = match selection {
+        CatChainSelection::First => chains[..fanout].to_vec(),
+        CatChainSelection::Random => {
+            // Keep registration order so the constituent chains are listed consistently
+            let mut indices: Vec<usize> = rand::seq::index::sample(rng, chains.len(), fanout).into_vec();
+            indices.sort_unstable();
+            indices.into_iter().map(|i| chains[i].clone()).collect()
+        }
+    };
+    Ok(selected)
+}
+
+/// Makes sure the initiating chain is one of the constituent chains of a CAT, replacing the last
+/// selected chain if needed and keeping the chains in registration order
+///
+/// # Arguments
+///
+/// * `cat_chains` - The selected constituent chains
+/// * `initiator` - The chain that initiates the CAT
+/// * `chains` - The registered chains, in registration order
+fn include_initiating_chain(cat_chains: &mut Vec<ChainId>, initiator: &ChainId, chains: &[ChainId]) {
+    if cat_chains.contains(initiator) {
+        return;
+    }
+    cat_chains.pop();
+    cat_chains.push(initiator.clone());
+    cat_chains.sort_by_key(|chain_id| chains.iter().position(|c| c == chain_id));
+}
+
 // ------------------------------------------------------------------------------------------------
 // Transaction Creation and Submission
 // ------------------------------------------------------------------------------------------------
 
-/// Creates and submits a CAT transaction
-/// 
+/// Builds a CAT that carries the same transaction data on each of its constituent chains
+///
 /// # Arguments
 ///
-/// * `cl_node` - A reference to the confirmation layer node
 /// * `cl_id` - A CLTransactionId, the ID of the CL transaction
-/// * `chain_id_1` - A ChainId, the ID of the first chain
-/// * `chain_id_2` - A ChainId, the ID of the second chain
-/// * `tx_data` - A String, the data of the transaction
-async fn create_and_submit_cat_transaction(
-    cl_node: &Arc<Mutex<ConfirmationLayerNode>>,
+/// * `cat_chains` - A Vec<ChainId>, the constituent chains of the CAT (one sub-transaction per chain)
+/// * `tx_data` - The data of the transaction
+fn build_cat_transaction(
     cl_id: CLTransactionId,
-    chain_id_1: ChainId,
-    chain_id_2: ChainId,
-    tx_data: String,
-) -> Result<(bool, String), String> {
-    // Create transactions for both chains
-    let tx1 = Transaction::new(
-        TransactionId(format!("{:?}:tx1", cl_id)),
-        chain_id_1.clone(),
-        vec![chain_id_1.clone(), chain_id_2.clone()],
-        tx_data.clone(),
-        cl_id.clone(),
-    ).map_err(|e| {
-        logging::log("SIMULATOR", &format!("Failed to create CAT-sub-transaction 1: {}", e));
-        e.to_string()
-    })?;
-
-    let tx2 = Transaction::new(
-        TransactionId(format!("{:?}:tx2", cl_id)),
-        chain_id_2.clone(),
-        vec![chain_id_1.clone(), chain_id_2.clone()],
-        tx_data.clone(),
-        cl_id.clone(),
-    ).map_err(|e| {
-        logging::log("SIMULATOR", &format!("Failed to create CAT-sub-transaction 2: {}", e));
-        e.to_string()
-    })?;
-
-    // Create the CL transaction
-    let cl_tx = CLTransaction::new(
-        cl_id.clone(),
-        vec![chain_id_1.clone(), chain_id_2.clone()],
-        vec![tx1, tx2],
-    ).map_err(|e| {
-        logging::log("SIMULATOR", &format!("Failed to create CAT CL transaction: {}", e));
-        e.to_string()
-    })?;
+    cat_chains: Vec<ChainId>,
+    tx_data: &str,
+) -> Result<CLTransaction, String> {
+    // Create one sub-transaction per constituent chain
+    let mut sub_txs = Vec::with_capacity(cat_chains.len());
+    for (i, chain_id) in cat_chains.iter().enumerate() {
+        let tx = Transaction::new(
+            cat_sub_transaction_id(&cl_id, i),
+            chain_id.clone(),
+            cat_chains.clone(),
+            tx_data.to_string(),
+            cl_id.clone(),
+        ).map_err(|e| format!("Failed to create CAT-sub-transaction {}: {}", i + 1, e))?;
+        sub_txs.push(tx);
+    }
+    CLTransaction::new(cl_id, cat_chains, sub_txs)
+}
 
+/// Submits a CAT transaction
+/// 
+/// # Arguments
+///
+/// * `cl_node` - A reference to the confirmation layer node
+/// * `submission_queue` - The queue the CAT is submitted through unless its parts are spread
+/// * `cl_tx` - The CL transaction of the CAT (one sub-transaction per constituent chain)
+/// * `tx_data` - A String, a description of the transaction for logging
+/// * `cl_transactions` - Collects the built CL transaction for the workload recording
+/// * `part_spread` - If set, each sub-transaction is submitted as a separate CAT part after a
+///   uniformly random delay of up to this duration, instead of submitting the CAT at once
+async fn create_and_submit_cat_transaction<C: ConfirmationLayer + 'static>(
+    cl_node: &Arc<Mutex<C>>,
+    submission_queue: &mut SubmissionQueue,
+    cl_tx: CLTransaction,
+    tx_data: String,
+    cl_transactions: &mut Vec<CLTransaction>,
+    part_spread: Option<Duration>,
+) -> Result<(), String> {
+    let cl_id = cl_tx.id.clone();
+    let cat_chains = cl_tx.constituent_chains.clone();
     logging::log("SIMULATOR", &format!("Created CAT CL transaction with ID: {:?}", cl_id));
+    cl_transactions.push(cl_tx.clone());
 
-    // Submit transaction to CL node
-    match cl_node.lock().await.submit_transaction(cl_tx.clone()).await {
-        Ok(_) => {
-            logging::log("SIMULATOR", &format!("CAT transaction submitted successfully: {}", tx_data));
-            Ok((true, tx_data))
-        }
-        Err(e) => {
-            logging::log("SIMULATOR", &format!("Failed to submit CAT CL transaction: {}", e));
-            logging::log("SIMULATOR", &format!("CAT transaction failed: {}", tx_data));
-            Ok((false, tx_data))
+    // Submit the parts of the CAT separately, each after its own random delay
+    if let Some(max_delay) = part_spread {
+        let mut rng = rand::thread_rng();
+        for tx in cl_tx.transactions {
+            let delay = max_delay.mul_f64(rng.gen::<f64>());
+            let cl_node = cl_node.clone();
+            let part = CLTransaction {
+                id: cl_id.clone(),
+                constituent_chains: cat_chains.clone(),
+                transactions: vec![tx],
+            };
+            tokio::spawn(async move {
+                sleep(delay).await;
+                if let Err(e) = cl_node.lock().await.submit_transaction(part.clone()).await {
+                    logging::log("SIMULATOR", &format!("Failed to submit part of CAT CL transaction {:?}: {}", part.id, e));
+                }
+            });
         }
+        logging::log("SIMULATOR", &format!("CAT transaction parts scheduled for submission: {}", tx_data));
+        return Ok(());
     }
+
+    // Queue the transaction for submission to the CL node
+    submission_queue.submit(cl_node, vec![cl_tx]).await.map_err(|e| {
+        logging::log("SIMULATOR", &format!("CAT transaction failed: {}", tx_data));
+        e
+    })
+}
+
+/// Returns the id of the sub-transaction of a CAT for its `index`-th constituent chain
+fn cat_sub_transaction_id(cl_id: &CLTransactionId, index: usize) -> TransactionId {
+    TransactionId(format!("{:?}:tx{}", cl_id, index + 1))
 }
 
 /// Creates and submits a regular transaction
@@ -533,16 +1340,22 @@ async fn create_and_submit_cat_transaction(
 /// # Arguments
 ///
 /// * `cl_node` - A reference to the confirmation layer node
+/// * `submission_queue` - The queue the transactions are submitted through
 /// * `cl_id` - A CLTransactionId, the ID of the CL transaction
 /// * `chain_id_1` - A ChainId, the ID of the first chain
 /// * `chain_id_2` - A ChainId, the ID of the second chain
-async fn create_and_submit_regular_transaction(
-    cl_node: &Arc<Mutex<ConfirmationLayerNode>>,
+/// * `cl_transactions` - Collects the built CL transactions for the workload recording
+/// * `expiry` - Block height after which the transactions expire if they are still pending
+async fn create_and_submit_regular_transaction<C: ConfirmationLayer>(
+    cl_node: &Arc<Mutex<C>>,
+    submission_queue: &mut SubmissionQueue,
     cl_id: CLTransactionId,
     chain_id_1: ChainId,
     chain_id_2: ChainId,
     tx_data: String,
-) -> Result<(bool, String), String> {
+    cl_transactions: &mut Vec<CLTransaction>,
+    expiry: Option<u64>,
+) -> Result<(), String> {
     // Create and submit CL transaction for chain-1
     let cl_id_1 = CLTransactionId(format!("{:?}_1", cl_id));
     let tx_1 = Transaction::new(
@@ -555,6 +1368,10 @@ async fn create_and_submit_regular_transaction(
         logging::log("SIMULATOR", &format!("Failed to create regular transaction for chain-1: {}", e));
         e.to_string()
     })?;
+    let tx_1 = match expiry {
+        Some(expiry) => tx_1.with_expiry(expiry),
+        None => tx_1,
+    };
 
     let cl_tx_1 = CLTransaction::new(
         cl_id_1.clone(),
@@ -577,6 +1394,10 @@ async fn create_and_submit_regular_transaction(
         logging::log("SIMULATOR", &format!("Failed to create regular transaction for chain-2: {}", e));
         e.to_string()
     })?;
+    let tx_2 = match expiry {
+        Some(expiry) => tx_2.with_expiry(expiry),
+        None => tx_2,
+    };
 
     let cl_tx_2 = CLTransaction::new(
         cl_id_2.clone(),
@@ -588,31 +1409,12 @@ async fn create_and_submit_regular_transaction(
     })?;
 
     logging::log("SIMULATOR", &format!("Created regular CL transactions with IDs: {:?}_1 and {:?}_2", cl_id, cl_id));
+    cl_transactions.push(cl_tx_1.clone());
+    cl_transactions.push(cl_tx_2.clone());
 
-    // Submit both transactions to CL node
-    let success1 = match cl_node.lock().await.submit_transaction(cl_tx_1.clone()).await {
-        Ok(_) => {
-            logging::log("SIMULATOR", &format!("Regular transaction submitted successfully to chain-1: {}", tx_data));
-            true
-        }
-        Err(e) => {
-            logging::log("SIMULATOR", &format!("Failed to submit regular transaction to CL node: {}", e));
-            logging::log("SIMULATOR", &format!("Regular transaction failed to submit: {}", tx_data));
-            false
-        }
-    };
-
-    let success2 = match cl_node.lock().await.submit_transaction(cl_tx_2.clone()).await {
-        Ok(_) => {
-            logging::log("SIMULATOR", &format!("Regular transaction submitted successfully: {}", tx_data));
-            true
-        }
-        Err(e) => {
-            logging::log("SIMULATOR", &format!("Failed to submit regular transaction to CL node: {}", e));
-            logging::log("SIMULATOR", &format!("Regular transaction failed to submit: {}", tx_data));
-            false
-        }
-    };
-
-    Ok((success1 && success2, tx_data))
-} 
\ No newline at end of file
+    // Queue both transactions for submission to the CL node
+    submission_queue.submit(cl_node, vec![cl_tx_1, cl_tx_2]).await.map_err(|e| {
+        logging::log("SIMULATOR", &format!("Regular transaction failed to submit: {}", tx_data));
+        e
+    })
+}
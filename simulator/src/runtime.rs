@@ -0,0 +1,233 @@
+//! Tokio runtimes the simulator runs on.
+//!
+//! By default all nodes of a simulation share one multi-threaded runtime, so at high chain counts
+//! the CL, the HS and every HIG compete for the same worker threads and a busy component can
+//! starve the others. `--worker-threads <n>` sets the number of worker threads of the main
+//! runtime, and `--pin <component>=<cores>` pins the worker threads of a component to the given
+//! cores (Linux only). A pinned CL, HS or HIG component gets a runtime of its own with one worker
+//! thread per core; `main` pins the workers of the main runtime, which runs the workload
+//! generator, the network links and the statistics. The settings of the process are recorded in
+//! the metadata of every run. They are not part of the config hash, since they change how fast a
+//! simulation runs but not what it simulates.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock};
+use serde::{Deserialize, Serialize};
+use tokio::runtime::{Builder, Runtime};
+use hyperplane::harness::ComponentRuntimes;
+use hyperplane::utils::logging;
+
+// ------------------------------------------------------------------------------------------------
+// Constants
+// ------------------------------------------------------------------------------------------------
+
+/// Components whose worker threads can be pinned
+pub const PINNABLE_COMPONENTS: [&str; 4] = ["main", "cl", "hs", "hig"];
+
+/// Runtime settings of this process, set once at startup
+static RUNTIME_CONFIG: OnceLock<RuntimeConfig> = OnceLock::new();
+
+/// Runtimes of the pinned components, kept for the lifetime of the process
+static COMPONENT_RUNTIMES: OnceLock<PinnedRuntimes> = OnceLock::new();
+
+// ------------------------------------------------------------------------------------------------
+// Data Structures
+// ------------------------------------------------------------------------------------------------
+
+/// Cores the worker threads of each component are pinned to (empty = not pinned)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CorePinning {
+    #[serde(default)]
+    pub main: Vec<usize>,
+    #[serde(default)]
+    pub cl: Vec<usize>,
+    #[serde(default)]
+    pub hs: Vec<usize>,
+    #[serde(default)]
+    pub hig: Vec<usize>,
+}
+
+/// Settings of the runtimes of the simulator
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuntimeConfig {
+    /// Worker threads of the main runtime (None = one per core, or one per pinned core of `main`)
+    pub worker_threads: Option<usize>,
+    #[serde(default)]
+    pub pinning: CorePinning,
+}
+
+/// The runtimes of the pinned CL, HS and HIG components
+struct PinnedRuntimes {
+    cl: Option<Runtime>,
+    hs: Option<Runtime>,
+    hig: Option<Runtime>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl CorePinning {
+    /// The cores of a component
+    fn cores_mut(&mut self, component: &str) -> Option<&mut Vec<usize>> {
+        match component {
+            "main" => Some(&mut self.main),
+            "cl" => Some(&mut self.cl),
+            "hs" => Some(&mut self.hs),
+            "hig" => Some(&mut self.hig),
+            _ => None,
+        }
+    }
+
+    /// Whether any component is pinned
+    pub fn is_pinned(&self) -> bool {
+        !(self.main.is_empty() && self.cl.is_empty() && self.hs.is_empty() && self.hig.is_empty())
+    }
+}
+
+impl RuntimeConfig {
+    /// Takes the runtime options out of the command line arguments
+    ///
+    /// Returns the runtime settings and the remaining arguments, in their order.
+    ///
+    /// # Arguments
+    /// * `args` - The command line arguments without the program name
+    pub fn from_args(args: &[String]) -> Result<(Self, Vec<String>), String> {
+        let mut config = Self::default();
+        let mut remaining = Vec::new();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--worker-threads" => {
+                    let value = args.next().ok_or("--worker-threads needs a number of threads")?;
+                    let threads = value.parse::<usize>().ok().filter(|threads| *threads > 0)
+                        .ok_or_else(|| format!("Invalid --worker-threads '{}': expected a positive number", value))?;
+                    config.worker_threads = Some(threads);
+                }
+                "--pin" => {
+                    let value = args.next().ok_or("--pin needs <component>=<cores>, e.g. hig=2-5")?;
+                    let (component, cores) = value.split_once('=')
+                        .ok_or_else(|| format!("Invalid --pin '{}': expected <component>=<cores>, e.g. hig=2-5", value))?;
+                    let slot = config.pinning.cores_mut(component.trim())
+                        .ok_or_else(|| format!("Invalid --pin '{}': unknown component '{}' (expected one of {})", value, component, PINNABLE_COMPONENTS.join(", ")))?;
+                    *slot = parse_cores(cores).map_err(|e| format!("Invalid --pin '{}': {}", value, e))?;
+                }
+                _ => remaining.push(arg.clone()),
+            }
+        }
+        Ok((config, remaining))
+    }
+
+    /// Checks that the pinned cores exist
+    ///
+    /// # Arguments
+    /// * `cpus` - The number of cores of the host
+    pub fn validate(&self, cpus: usize) -> Result<(), String> {
+        let pinning = &self.pinning;
+        for (component, cores) in PINNABLE_COMPONENTS.iter().zip([&pinning.main, &pinning.cl, &pinning.hs, &pinning.hig]) {
+            if let Some(core) = cores.iter().find(|core| **core >= cpus) {
+                return Err(format!("Cannot pin {} to core {}: the host has {} cores (0-{})", component, core, cpus, cpus.saturating_sub(1)));
+            }
+        }
+        if pinning.is_pinned() && !cfg!(target_os = "linux") {
+            return Err("Core pinning is only supported on Linux".to_string());
+        }
+        Ok(())
+    }
+
+    /// Builds the main runtime and the runtimes of the pinned components, and records the settings
+    /// for the metadata of the runs. Must be called once, before any simulation runs.
+    pub fn build(mut self) -> Result<Runtime, String> {
+        let cpus = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+        self.validate(cpus)?;
+        // Record the number of threads that actually run
+        let worker_threads = self.worker_threads
+            .unwrap_or(if self.pinning.main.is_empty() { cpus } else { self.pinning.main.len() });
+        self.worker_threads = Some(worker_threads);
+
+        let main = pinned_runtime(worker_threads, &self.pinning.main)
+            .map_err(|e| format!("Failed to build the main runtime: {}", e))?;
+        let component = |cores: &[usize]| -> Result<Option<Runtime>, String> {
+            if cores.is_empty() {
+                return Ok(None);
+            }
+            pinned_runtime(cores.len(), cores).map(Some).map_err(|e| format!("Failed to build a component runtime: {}", e))
+        };
+        let runtimes = PinnedRuntimes { cl: component(&self.pinning.cl)?, hs: component(&self.pinning.hs)?, hig: component(&self.pinning.hig)? };
+
+        if self.pinning.is_pinned() || self.worker_threads != Some(cpus) {
+            logging::log("SIMULATOR", &format!("Runtime: {} main worker threads, pinning {:?}", worker_threads, self.pinning));
+        }
+        RUNTIME_CONFIG.set(self).map_err(|_| "The simulator runtime was already built".to_string())?;
+        COMPONENT_RUNTIMES.set(runtimes).map_err(|_| "The simulator runtime was already built".to_string())?;
+        Ok(main)
+    }
+}
+
+/// The runtime settings of this process (the defaults if the runtime was not built by `RuntimeConfig::build`)
+pub fn current() -> RuntimeConfig {
+    RUNTIME_CONFIG.get().cloned().unwrap_or_default()
+}
+
+/// The runtimes the nodes of a simulation run on (the current runtime for unpinned components)
+pub fn component_runtimes() -> ComponentRuntimes {
+    match COMPONENT_RUNTIMES.get() {
+        Some(runtimes) => ComponentRuntimes {
+            cl: runtimes.cl.as_ref().map(|runtime| runtime.handle().clone()),
+            hs: runtimes.hs.as_ref().map(|runtime| runtime.handle().clone()),
+            hig: runtimes.hig.as_ref().map(|runtime| runtime.handle().clone()),
+        },
+        None => ComponentRuntimes::default(),
+    }
+}
+
+/// Parses a list of cores such as `0,2,4-7`
+fn parse_cores(spec: &str) -> Result<Vec<usize>, String> {
+    let mut cores = Vec::new();
+    for part in spec.split(',').map(str::trim) {
+        let invalid = || format!("invalid cores '{}': expected e.g. 0,2,4-7", part);
+        match part.split_once('-') {
+            Some((first, last)) => {
+                let first: usize = first.trim().parse().map_err(|_| invalid())?;
+                let last: usize = last.trim().parse().map_err(|_| invalid())?;
+                if first > last {
+                    return Err(invalid());
+                }
+                cores.extend(first..=last);
+            }
+            None => cores.push(part.parse().map_err(|_| invalid())?),
+        }
+    }
+    Ok(cores)
+}
+
+/// Builds a multi-threaded runtime whose workers are pinned to the given cores in turn (not pinned without cores)
+fn pinned_runtime(worker_threads: usize, cores: &[usize]) -> std::io::Result<Runtime> {
+    let mut builder = Builder::new_multi_thread();
+    builder.worker_threads(worker_threads).enable_all();
+    if !cores.is_empty() {
+        let cores = cores.to_vec();
+        let next = Arc::new(AtomicUsize::new(0));
+        // Blocking threads of the runtime start here too and share the cores of its workers
+        builder.on_thread_start(move || pin_current_thread(cores[next.fetch_add(1, Ordering::Relaxed) % cores.len()]));
+    }
+    builder.build()
+}
+
+/// Pins the calling thread to a core
+#[cfg(target_os = "linux")]
+fn pin_current_thread(core: usize) {
+    // SAFETY: the set is zero-initialized and only used for this call
+    let result = unsafe {
+        let mut set: libc::cpu_set_t = std::mem::zeroed();
+        libc::CPU_SET(core, &mut set);
+        libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &set)
+    };
+    if result != 0 {
+        logging::log("SIMULATOR", &format!("Failed to pin a worker thread to core {}: {}", core, std::io::Error::last_os_error()));
+    }
+}
+
+/// Pinning is rejected by `RuntimeConfig::validate` on other platforms
+#[cfg(not(target_os = "linux"))]
+fn pin_current_thread(_core: usize) {}
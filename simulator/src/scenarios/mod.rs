@@ -1,14 +1,24 @@
 pub mod sim_simple;
+pub mod sim_replay;
+pub mod sim_compare;
+pub mod sim_failover;
+pub mod sim_block_interval_ramp;
+pub mod sim_balance_exhaustion;
+pub mod sim_sweep_hs_chain_count;
 pub mod sim_sweep_cat_ratio;
 pub mod sim_sweep_tpb_constant_cats_per_block;
 pub mod sim_sweep_chain_delay;
 pub mod sim_sweep_total_block_number;
 pub mod sim_sweep_zipf;
 pub mod sim_sweep_cat_lifetime;
+pub mod sim_sweep_cat_lifetime_delay_ratio;
+pub mod sim_sweep_block_capacity;
 pub mod sim_sweep_block_interval_constant_block_delay;
 pub mod sim_sweep_block_interval_constant_time_delay;
 pub mod sim_sweep_block_interval_all_scaled;
 pub mod sim_sweep_cat_pending_dependencies;
+pub mod sim_sweep_mixed_cat_pending_dependencies;
+pub mod sim_sweep_custom;
 pub mod run_all_tests;
 pub mod sweep_runner; 
 pub mod sweep_macro;
@@ -15,6 +15,9 @@ from matplotlib.colors import LinearSegmentedColormap
 from typing import Dict, List, Tuple, Any, Optional
 from plot_utils_moving_average import apply_moving_average
 
+sys.path.insert(0, os.path.join(os.path.dirname(os.path.abspath(__file__)), '..'))
+from results_schema import load_simulation_stats
+
 # Global colormap setting - easily switch between different colormaps
 # Options: 'viridis', 'RdYlBu_r', 'plasma', 'inferno', 'magma', 'cividis'
 COLORMAP = 'viridis'  # Change this to switch colormaps globally
@@ -29,6 +32,7 @@ PARAM_DISPLAY_NAMES = {
     'block_interval': 'Block Interval (seconds)',
     'cat_ratio': 'CAT Ratio',
     'chain_delay': 'Chain Delay (blocks)',
+    'chain_delay_spread': 'Chain Delay Spread',
     'duration': 'Duration (blocks)',
     'cat_lifetime': 'CAT Lifetime (blocks)',
     'allow_cat_pending_dependencies': 'Allow CAT Pending Dependencies'
@@ -65,8 +69,7 @@ def load_sweep_data_from_run_average(results_dir_name: str, base_path: str = 'si
         # Load averaged stats for this simulation
         stats_file = f'{base_dir}/sim_{sim_index}/run_average/simulation_stats.json'
         if os.path.exists(stats_file):
-            with open(stats_file, 'r') as f:
-                stats = json.load(f)
+            stats = load_simulation_stats(stats_file)
             
             # Add to sweep summary
             sweep_summary['total_transactions'].append(stats['results']['total_transactions'])
@@ -99,6 +102,10 @@ def load_sweep_data_from_run_average(results_dir_name: str, base_path: str = 'si
                 ('cat_pending_resolving_transactions_chain_2.json', 'chain_2_cat_pending_resolving'),
                 ('cat_pending_postponed_transactions_chain_1.json', 'chain_1_cat_pending_postponed'),
                 ('cat_pending_postponed_transactions_chain_2.json', 'chain_2_cat_pending_postponed'),
+                ('cat_timed_out_transactions_chain_1.json', 'chain_1_cat_timed_out'),
+                ('cat_timed_out_transactions_chain_2.json', 'chain_2_cat_timed_out'),
+                ('cat_rejected_transactions_chain_1.json', 'chain_1_cat_rejected'),
+                ('cat_rejected_transactions_chain_2.json', 'chain_2_cat_rejected'),
                 ('regular_pending_transactions_chain_1.json', 'chain_1_regular_pending'),
                 ('regular_pending_transactions_chain_2.json', 'chain_2_regular_pending'),
                 ('regular_success_transactions_chain_1.json', 'chain_1_regular_success'),
@@ -257,8 +264,7 @@ def plot_transactions_overlay(
                 try:
                     stats_file = f'{results_dir}/data/sim_{i}/run_0/data/simulation_stats.json'
                     if os.path.exists(stats_file):
-                        with open(stats_file, 'r') as f:
-                            stats_data = json.load(f)
+                        stats_data = load_simulation_stats(stats_file)
                         block_interval = stats_data['parameters']['block_interval']  # in seconds
                 except (FileNotFoundError, json.JSONDecodeError, KeyError) as e:
                     print(f"Warning: Could not load block interval for simulation {i}: {e}")
@@ -1153,8 +1159,7 @@ def plot_sweep_transactions_per_block(data: Dict[str, Any], param_name: str, res
                 results_dir_name = results_dir.replace('simulator/results/', '')
                 # Use simulation_stats.json from the first simulation's run_average directory
                 stats_file = f'simulator/results/{results_dir_name}/data/sim_0/run_average/simulation_stats.json'
-                with open(stats_file, 'r') as f:
-                    stats_data = json.load(f)
+                stats_data = load_simulation_stats(stats_file)
                 target_tpb = stats_data['parameters']['target_tpb']
             except (FileNotFoundError, KeyError) as e:
                 print(f"Warning: Could not determine target_tpb from simulation stats: {e}")
@@ -1364,8 +1369,7 @@ def generate_individual_curves_plots(data: Dict[str, Any], param_name: str, resu
             try:
                 stats_file = f'{sim_data_dir}/run_average/simulation_stats.json'
                 if os.path.exists(stats_file):
-                    with open(stats_file, 'r') as f:
-                        stats_data = json.load(f)
+                    stats_data = load_simulation_stats(stats_file)
                     block_interval = stats_data['parameters']['block_interval']  # in seconds
             except (FileNotFoundError, json.JSONDecodeError, KeyError) as e:
                 print(f"Warning: Could not load block interval for simulation {sim_index}: {e}")
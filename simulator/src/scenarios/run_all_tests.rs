@@ -1,69 +1,225 @@
-use std::time::Instant;
+use std::fmt::Write;
+use std::fs;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
 use hyperplane::utils::logging;
+use tokio::sync::Semaphore;
 
-/// Runs all simulation tests sequentially and generates plots after each
+// ------------------------------------------------------------------------------------------------
+// Constants
+// ------------------------------------------------------------------------------------------------
+
+/// Maximum number of simulations run at the same time
+///
+/// The simulations run in real time, so running too many at once lets them compete for CPU and
+/// skews their timing-sensitive results.
+pub const MAX_CONCURRENT_TESTS: usize = 3;
+
+/// Directory the summary of all tests is written to
+pub const SUMMARY_DIR: &str = "simulator/results/run_all_tests";
+
+/// Report columns shown in the summary matrix
+const KEY_METRICS: &[&str] = &["CAT success", "CAT failure", "CAT timed out", "Regular success", "Protocol violations"];
+
+// ------------------------------------------------------------------------------------------------
+// Data Structures
+// ------------------------------------------------------------------------------------------------
+
+/// Entry point of a test: runs the simulation and its plot script
+type TestFn = fn() -> Pin<Box<dyn Future<Output = Result<(), crate::config::ConfigError>>>>;
+
+/// Outcome of one test
+struct TestOutcome {
+    /// Display name of the test
+    name: &'static str,
+    /// The test's directory name under `simulator/results`
+    results_dir: &'static str,
+    /// Error returned by the simulation, if it failed
+    error: Option<String>,
+    /// Wall-clock time the test took
+    duration: Duration,
+    /// Summary of the results the test wrote, if they could be read
+    summary: Option<crate::report::ResultsSummary>,
+}
+
+impl TestOutcome {
+    /// Status of the test: failed simulations and simulations with anomalies are told apart
+    fn status(&self) -> &'static str {
+        match (&self.error, &self.summary) {
+            (Some(_), _) => "failed",
+            (None, Some(summary)) if !summary.anomalies.is_empty() => "anomalies",
+            (None, None) => "no results",
+            (None, Some(_)) => "passed",
+        }
+    }
+
+    /// Value of a report column, if the results could be read
+    fn metric(&self, label: &str) -> Option<f64> {
+        self.summary.as_ref()
+            .and_then(|summary| summary.metrics.iter().find(|(l, _)| *l == label))
+            .map(|(_, value)| *value)
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Test Execution
+// ------------------------------------------------------------------------------------------------
+
+/// Runs all simulation tests with at most `MAX_CONCURRENT_TESTS` at a time and generates plots after each
 pub async fn run_all_tests() -> Result<(), crate::config::ConfigError> {
+    run_all_tests_with_concurrency(MAX_CONCURRENT_TESTS).await
+}
+
+/// Runs all simulation tests with at most `max_concurrent` at a time and generates plots after each
+///
+/// A failing test does not stop the others. Once all tests finished, a matrix of their status and
+/// key metrics is written to `summary.md` and `summary.json` in `SUMMARY_DIR`.
+pub async fn run_all_tests_with_concurrency(max_concurrent: usize) -> Result<(), crate::config::ConfigError> {
     let start_time = Instant::now();
-    
+    let tests: [(&'static str, &'static str, TestFn); 13] = [
+        ("Simple Simulation", "sim_simple", || Box::pin(crate::scenarios::sim_simple::simulation::run_with_plotting())),
+        ("Sweep Block Capacity", "sim_sweep_block_capacity", || Box::pin(crate::scenarios::sim_sweep_block_capacity::simulation::run_with_plotting())),
+        ("Sweep Block Interval (All Scaled)", "sim_sweep_block_interval_all_scaled", || Box::pin(crate::scenarios::sim_sweep_block_interval_all_scaled::simulation::run_with_plotting())),
+        ("Sweep Block Interval (Constant Block Delay)", "sim_sweep_block_interval_constant_block_delay", || Box::pin(crate::scenarios::sim_sweep_block_interval_constant_block_delay::simulation::run_with_plotting())),
+        ("Sweep Block Interval (Constant Time Delay)", "sim_sweep_block_interval_constant_time_delay", || Box::pin(crate::scenarios::sim_sweep_block_interval_constant_time_delay::simulation::run_with_plotting())),
+        ("Sweep CAT Lifetime", "sim_sweep_cat_lifetime", || Box::pin(crate::scenarios::sim_sweep_cat_lifetime::simulation::run_with_plotting())),
+        ("Sweep CAT Lifetime / Delay Ratio", "sim_sweep_cat_lifetime_delay_ratio", || Box::pin(crate::scenarios::sim_sweep_cat_lifetime_delay_ratio::simulation::run_with_plotting())),
+        ("Sweep CAT Pending Dependencies", "sim_sweep_cat_pending_dependencies", || Box::pin(crate::scenarios::sim_sweep_cat_pending_dependencies::simulation::run_with_plotting())),
+        ("Sweep Mixed CAT Pending Dependencies", "sim_sweep_mixed_cat_pending_dependencies", || Box::pin(crate::scenarios::sim_sweep_mixed_cat_pending_dependencies::simulation::run_with_plotting())),
+        ("Sweep CAT Ratio", "sim_sweep_cat_ratio", || Box::pin(crate::scenarios::sim_sweep_cat_ratio::simulation::run_with_plotting())),
+        ("Sweep Chain Delay", "sim_sweep_chain_delay", || Box::pin(crate::scenarios::sim_sweep_chain_delay::simulation::run_with_plotting())),
+        ("Sweep Total Block Number", "sim_sweep_total_block_number", || Box::pin(crate::scenarios::sim_sweep_total_block_number::simulation::run_with_plotting())),
+        ("Sweep Zipf Distribution", "sim_sweep_zipf", || Box::pin(crate::scenarios::sim_sweep_zipf::simulation::run_with_plotting())),
+    ];
+    let max_concurrent = max_concurrent.max(1);
+
     logging::log("SIMULATOR", "=== Starting All Tests Suite ===");
-    logging::log("SIMULATOR", "This will run all simulation types sequentially");
-    
-    // 1. Simple simulation
-    println!("\n------------ 1. Simple Simulation -----------");
-    logging::log("SIMULATOR", "------------ 1. Simple Simulation -----------");
-    crate::scenarios::sim_simple::simulation::run_with_plotting().await?;
-    
-    // 2. Block interval all scaled sweep
-    println!("\n------------ 2. Sweep Block Interval (All Scaled) -----------");
-    logging::log("SIMULATOR", "------------ 2. Sweep Block Interval (All Scaled) -----------");
-    crate::scenarios::sim_sweep_block_interval_all_scaled::simulation::run_with_plotting().await?;
-    
-    // 3. Block interval constant block delay sweep
-    println!("\n------------ 3. Sweep Block Interval (Constant Block Delay) -----------");
-    logging::log("SIMULATOR", "------------ 3. Sweep Block Interval (Constant Block Delay) -----------");
-    crate::scenarios::sim_sweep_block_interval_constant_block_delay::simulation::run_with_plotting().await?;
-    
-    // 4. Block interval constant time delay sweep
-    println!("\n------------ 4. Sweep Block Interval (Constant Time Delay) -----------");
-    logging::log("SIMULATOR", "------------ 4. Sweep Block Interval (Constant Time Delay) -----------");
-    crate::scenarios::sim_sweep_block_interval_constant_time_delay::simulation::run_with_plotting().await?;
-    
-    // 5. CAT lifetime sweep
-    println!("\n------------ 5. Sweep CAT Lifetime -----------");
-    logging::log("SIMULATOR", "------------ 5. Sweep CAT Lifetime -----------");
-    crate::scenarios::sim_sweep_cat_lifetime::simulation::run_with_plotting().await?;
-    
-    // 6. CAT pending dependencies sweep
-    println!("\n------------ 6. Sweep CAT Pending Dependencies -----------");
-    logging::log("SIMULATOR", "------------ 6. Sweep CAT Pending Dependencies -----------");
-    crate::scenarios::sim_sweep_cat_pending_dependencies::simulation::run_with_plotting().await?;
-    
-    // 7. CAT ratio sweep
-    println!("\n------------ 7. Sweep CAT Ratio -----------");
-    logging::log("SIMULATOR", "------------ 7. Sweep CAT Ratio -----------");
-    crate::scenarios::sim_sweep_cat_ratio::simulation::run_with_plotting().await?;
-    
-    // 8. Chain delay sweep
-    println!("\n------------ 8. Sweep Chain Delay -----------");
-    logging::log("SIMULATOR", "------------ 8. Sweep Chain Delay -----------");
-    crate::scenarios::sim_sweep_chain_delay::simulation::run_with_plotting().await?;
-    
-    // 9. Total block number sweep
-    println!("\n------------ 9. Sweep Total Block Number -----------");
-    logging::log("SIMULATOR", "------------ 9. Sweep Total Block Number -----------");
-    crate::scenarios::sim_sweep_total_block_number::simulation::run_with_plotting().await?;
-    
-    // 10. Zipf sweep
-    println!("\n------------ 10. Sweep Zipf Distribution -----------");
-    logging::log("SIMULATOR", "------------ 10. Sweep Zipf Distribution -----------");
-    crate::scenarios::sim_sweep_zipf::simulation::run_with_plotting().await?;
-    
+    logging::log("SIMULATOR", &format!("Running {} simulation types, at most {} at a time", tests.len(), max_concurrent));
+
+    // The simulation futures are not Send, so the tests share this task's thread; the nodes they
+    // build still spawn their tasks onto the runtime's worker threads
+    let semaphore = Rc::new(Semaphore::new(max_concurrent));
+    let local = tokio::task::LocalSet::new();
+    let outcomes = local.run_until(async {
+        let handles: Vec<_> = tests.iter().enumerate()
+            .map(|(index, &(name, results_dir, run))| {
+                let semaphore = semaphore.clone();
+                let handle = tokio::task::spawn_local(async move {
+                    let _permit = semaphore.acquire().await.expect("Test semaphore closed");
+                    println!("\n------------ {}. {} -----------", index + 1, name);
+                    logging::log("SIMULATOR", &format!("------------ {}. {} -----------", index + 1, name));
+
+                    let test_start = Instant::now();
+                    let error = run().await.err().map(|e| e.to_string());
+                    // A failed test may have left the results of an earlier run behind
+                    let summary = if error.is_none() { crate::report::summarize_results(results_dir).ok() } else { None };
+                    TestOutcome { name, results_dir, error, duration: test_start.elapsed(), summary }
+                });
+                (name, results_dir, handle)
+            })
+            .collect();
+
+        let mut outcomes = Vec::new();
+        for (name, results_dir, handle) in handles {
+            outcomes.push(handle.await.unwrap_or_else(|e| TestOutcome {
+                name,
+                results_dir,
+                error: Some(format!("Test panicked: {}", e)),
+                duration: Duration::ZERO,
+                summary: None,
+            }));
+        }
+        outcomes
+    }).await;
+
     let total_time = start_time.elapsed();
-    logging::log("SIMULATOR", "=== All Tests Completed Successfully ===");
+    let summary_path = write_summary(&outcomes, max_concurrent, total_time)
+        .map_err(crate::config::ConfigError::ValidationError)?;
+
+    let failed: Vec<&str> = outcomes.iter().filter(|o| o.error.is_some()).map(|o| o.name).collect();
     logging::log("SIMULATOR", &format!("Total execution time: {:.2?}", total_time));
-    
-    println!("All tests completed successfully!");
     println!("Total execution time: {:.2?}", total_time);
-    
+    println!("Test summary written to {}", summary_path);
+
+    if !failed.is_empty() {
+        logging::log("SIMULATOR", &format!("=== {} of {} Tests Failed ===", failed.len(), outcomes.len()));
+        return Err(crate::config::ConfigError::ValidationError(format!("{} of {} tests failed: {}", failed.len(), outcomes.len(), failed.join(", "))));
+    }
+
+    logging::log("SIMULATOR", "=== All Tests Completed Successfully ===");
+    println!("All tests completed successfully!");
+
     Ok(())
-} 
\ No newline at end of file
+}
+
+// ------------------------------------------------------------------------------------------------
+// Summary
+// ------------------------------------------------------------------------------------------------
+
+/// Writes the status and key metrics of all tests as a Markdown and a JSON matrix
+///
+/// # Returns
+/// The path of the written Markdown summary
+fn write_summary(outcomes: &[TestOutcome], max_concurrent: usize, total_time: Duration) -> Result<String, String> {
+    fs::create_dir_all(SUMMARY_DIR).map_err(|e| format!("Failed to create {}: {}", SUMMARY_DIR, e))?;
+
+    let mut markdown = String::new();
+    writeln!(markdown, "# Test summary\n").unwrap();
+    writeln!(markdown, "{} tests, at most {} at a time, {:.2?} in total. Metrics are final values averaged over runs and simulations.\n",
+        outcomes.len(), max_concurrent, total_time).unwrap();
+    write!(markdown, "| Test | Status | Duration | Runs |").unwrap();
+    for label in KEY_METRICS {
+        write!(markdown, " {} |", label).unwrap();
+    }
+    writeln!(markdown).unwrap();
+    writeln!(markdown, "|---|---|---:|---:|{}", "---:|".repeat(KEY_METRICS.len())).unwrap();
+    for outcome in outcomes {
+        let runs = outcome.summary.as_ref()
+            .map_or("-".to_string(), |summary| format!("{}/{}", summary.runs_found, summary.expected_runs));
+        write!(markdown, "| {} | {} | {:.1?} | {} |", outcome.name, outcome.status(), outcome.duration, runs).unwrap();
+        for label in KEY_METRICS {
+            let value = outcome.metric(label).map_or("-".to_string(), |value| format!("{:.2}", value));
+            write!(markdown, " {} |", value).unwrap();
+        }
+        writeln!(markdown).unwrap();
+    }
+
+    // Errors and anomalies below the matrix
+    let notes: Vec<String> = outcomes.iter()
+        .flat_map(|outcome| {
+            outcome.error.iter().cloned()
+                .chain(outcome.summary.iter().flat_map(|summary| summary.anomalies.iter().cloned()))
+                .map(move |note| format!("- {}: {}", outcome.name, note))
+        })
+        .collect();
+    if !notes.is_empty() {
+        writeln!(markdown, "\n## Failures and anomalies\n\n{}", notes.join("\n")).unwrap();
+    }
+
+    let json = serde_json::json!({
+        "max_concurrent": max_concurrent,
+        "total_time_secs": total_time.as_secs_f64(),
+        "tests": outcomes.iter().map(|outcome| serde_json::json!({
+            "name": outcome.name,
+            "results_dir": outcome.results_dir,
+            "status": outcome.status(),
+            "error": outcome.error,
+            "duration_secs": outcome.duration.as_secs_f64(),
+            "simulations": outcome.summary.as_ref().map(|summary| summary.simulations),
+            "runs_found": outcome.summary.as_ref().map(|summary| summary.runs_found),
+            "expected_runs": outcome.summary.as_ref().map(|summary| summary.expected_runs),
+            "metrics": outcome.summary.as_ref().map(|summary| summary.metrics.iter()
+                .map(|(label, value)| (label.to_string(), serde_json::json!(value)))
+                .collect::<serde_json::Map<_, _>>()),
+            "anomalies": outcome.summary.as_ref().map(|summary| &summary.anomalies),
+        })).collect::<Vec<_>>()
+    });
+
+    let markdown_path = format!("{}/summary.md", SUMMARY_DIR);
+    fs::write(&markdown_path, markdown).map_err(|e| format!("Failed to write {}: {}", markdown_path, e))?;
+    let json_path = format!("{}/summary.json", SUMMARY_DIR);
+    fs::write(&json_path, serde_json::to_string_pretty(&json).unwrap()).map_err(|e| format!("Failed to write {}: {}", json_path, e))?;
+    Ok(markdown_path)
+}
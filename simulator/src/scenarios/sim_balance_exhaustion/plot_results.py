@@ -0,0 +1,124 @@
+#!/usr/bin/env python3
+"""
+Plotting script for the Balance Exhaustion Simulation
+
+This script averages the runs and plots, window by window, the failure ratio of the regular
+transactions and CATs and how the finalized CATs split into successes, failures from the
+execution, timeouts and rejections. The window reports of all runs are summarized in
+data/exhaustion_summary.json.
+
+Usage:
+    python plot_results.py
+"""
+
+import sys
+import os
+import json
+import glob
+
+import numpy as np
+import matplotlib.pyplot as plt
+
+# Add the simulator source directory to the Python path to import the averaging script
+sys.path.insert(0, os.path.join(os.path.dirname(os.path.abspath(__file__)), '..', '..'))
+from average_runs import create_averaged_data
+
+RESULTS_DIR = 'simulator/results/sim_balance_exhaustion'
+
+# Window metrics averaged over the runs
+WINDOW_METRICS = ('regular_successes', 'regular_failures', 'cat_successes', 'cat_failures',
+                  'cat_timed_out', 'cat_rejected', 'regular_failure_ratio', 'cat_failure_ratio')
+
+
+def load_exhaustion_reports(results_dir: str) -> list:
+    """Load the balance exhaustion report of every run."""
+    reports = []
+    for path in sorted(glob.glob(f'{results_dir}/data/sim_0/run_*/data/balance_exhaustion.json')):
+        with open(path, 'r') as f:
+            reports.append(json.load(f))
+    return reports
+
+
+def summarize_windows(reports: list) -> list:
+    """Average the metrics of each window (by position in the run) over the runs."""
+    summary = []
+    for index in range(max(len(report['windows']) for report in reports)):
+        windows = [report['windows'][index] for report in reports if index < len(report['windows'])]
+        entry = {'window': index, 'runs': len(windows)}
+        for key in WINDOW_METRICS:
+            entry[key] = float(np.mean([window[key] for window in windows]))
+        summary.append(entry)
+    return summary
+
+
+def plot_failure_ratios(windows: list, results_dir: str) -> None:
+    """Plot the failure ratio of the regular transactions and CATs per window."""
+    positions = [window['window'] for window in windows]
+    fig, ax = plt.subplots(figsize=(12, 6))
+    ax.plot(positions, [window['regular_failure_ratio'] * 100.0 for window in windows], marker='o', label='regular')
+    ax.plot(positions, [window['cat_failure_ratio'] * 100.0 for window in windows], marker='s', label='CAT')
+    ax.set_xlabel('Window')
+    ax.set_ylabel('Failed (% of finalized)')
+    ax.set_ylim(0, 100)
+    ax.set_title('Failure Ratio per Window')
+    ax.grid(True, alpha=0.3)
+    ax.legend()
+
+    plt.tight_layout()
+    plt.savefig(f'{results_dir}/figs/failure_ratio_balance_exhaustion.png', dpi=300, bbox_inches='tight')
+    plt.close()
+
+
+def plot_cat_mix(windows: list, results_dir: str) -> None:
+    """Plot how the finalized CATs of each window split into successes and the kinds of failures."""
+    positions = np.array([window['window'] for window in windows])
+    successes = np.array([window['cat_successes'] for window in windows])
+    timed_out = np.array([window['cat_timed_out'] for window in windows])
+    rejected = np.array([window['cat_rejected'] for window in windows])
+    # Failures that neither timed out nor were rejected come from the execution on the chains
+    execution = np.maximum(np.array([window['cat_failures'] for window in windows]) - timed_out - rejected, 0)
+
+    fig, ax = plt.subplots(figsize=(12, 6))
+    bottom = np.zeros(len(windows))
+    for values, label, color in ((successes, 'success', 'tab:green'), (execution, 'failed', 'tab:red'),
+                                 (timed_out, 'timed out', 'tab:orange'), (rejected, 'rejected', 'tab:purple')):
+        ax.bar(positions, values, bottom=bottom, label=label, color=color)
+        bottom += values
+    ax.set_xlabel('Window')
+    ax.set_ylabel('Finalized CATs (both chains)')
+    ax.set_title('CAT Outcomes per Window')
+    ax.grid(True, alpha=0.3)
+    ax.legend()
+
+    plt.tight_layout()
+    plt.savefig(f'{results_dir}/figs/cat_mix_balance_exhaustion.png', dpi=300, bbox_inches='tight')
+    plt.close()
+
+
+def main():
+    """Main function to generate the plots of the balance exhaustion simulation."""
+    if not create_averaged_data(RESULTS_DIR):
+        print("Averaging failed!")
+        return
+
+    reports = load_exhaustion_reports(RESULTS_DIR)
+    if not reports or not any(report['windows'] for report in reports):
+        print("No balance exhaustion reports found. Skipping plot generation.")
+        return
+
+    windows = summarize_windows(reports)
+    inconsistent_runs = sum(1 for report in reports
+                            if any(accounting['problems'] for accounting in report['accounting']))
+    with open(f'{RESULTS_DIR}/data/exhaustion_summary.json', 'w') as f:
+        json.dump({'windows': windows, 'inconsistent_runs': inconsistent_runs}, f, indent=2)
+    if inconsistent_runs:
+        print(f"Warning: the status counts did not add up in {inconsistent_runs} run(s)")
+
+    os.makedirs(f'{RESULTS_DIR}/figs', exist_ok=True)
+    plot_failure_ratios(windows, RESULTS_DIR)
+    plot_cat_mix(windows, RESULTS_DIR)
+    print(f"Exhaustion summary written to {RESULTS_DIR}/data/exhaustion_summary.json and plots to {RESULTS_DIR}/figs/")
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,258 @@
+use std::fs;
+use std::time::Duration;
+
+use hyperplane::utils::logging;
+use serde::Deserialize;
+use crate::balance_exhaustion::{BalanceExhaustion, BalanceExhaustionReport, BALANCE_EXHAUSTION_FILE};
+use crate::config_schema::{ConfigSchema, ParameterKind, ParameterSchema, parameter};
+use toml;
+use serde_json;
+
+// ------------------------------------------------------------------------------------------------
+// Configuration Loading
+// ------------------------------------------------------------------------------------------------
+
+/// Layout of the exhaustion-specific part of the config.toml
+#[derive(Debug, Deserialize, Clone)]
+struct ExhaustionScenarioConfig {
+    exhaustion_config: BalanceExhaustion,
+}
+
+/// Parameters of the exhaustion config.toml beyond the common ones
+const EXHAUSTION_PARAMETERS: &[ParameterSchema] = &[
+    ParameterSchema { required: true, ..parameter("exhaustion_config", ParameterKind::Table, "Balance exhaustion parameters") },
+    ParameterSchema { required: true, min: Some(1.0), ..parameter("exhaustion_config.sink_accounts", ParameterKind::Integer, "Number of highest accounts that only receive") },
+    ParameterSchema { default: Some("50"), min: Some(1.0), ..parameter("exhaustion_config.window_blocks", ParameterKind::Integer, "Blocks per window of the failure mix report") },
+];
+
+/// Loads and validates the balance exhaustion simulation configuration from the TOML file.
+fn load_config() -> Result<(crate::config::Config, BalanceExhaustion), crate::config::ConfigError> {
+    let config_str = fs::read_to_string("simulator/src/scenarios/sim_balance_exhaustion/config.toml")?;
+    let config: crate::config::Config = toml::from_str(&config_str)?;
+    config.validate()?;
+    let exhaustion = toml::from_str::<ExhaustionScenarioConfig>(&config_str)?.exhaustion_config;
+    exhaustion.validate(config.account_config.num_accounts, config.simulation_config.sim_total_block_number)
+        .map_err(|e| crate::config::ConfigError::ValidationError(format!("Invalid exhaustion_config: {}", e)))?;
+    Ok((config, exhaustion))
+}
+
+// ------------------------------------------------------------------------------------------------
+// Simulation Entry Point
+// ------------------------------------------------------------------------------------------------
+
+/// Runs the balance exhaustion simulation
+///
+/// The workload sends only from the source accounts to the sink accounts, so the sources run out
+/// of funds and the failure rate of sends and CATs rises over the run. The failure mix of every
+/// window and the check of the final status counts are written to `balance_exhaustion.json` next
+/// to the results of each run.
+pub async fn run_balance_exhaustion_simulation() -> Result<(), crate::config::ConfigError> {
+    // Create results directory if it doesn't exist
+    fs::create_dir_all("simulator/results/sim_balance_exhaustion/data").expect("Failed to create data directory");
+    fs::create_dir_all("simulator/results/sim_balance_exhaustion/figs").expect("Failed to create figures directory");
+
+    // Load configuration
+    let (config, exhaustion) = load_config()?;
+
+    // Setup logging with configuration
+    setup_logging(&config);
+
+    let num_runs = config.simulation_config.num_runs;
+
+    // Write metadata.json for Python averaging script
+    let metadata = serde_json::json!({
+        "num_runs": num_runs,
+        "num_simulations": 1,
+        "parameters": {
+            "initial_balance": config.account_config.initial_balance,
+            "num_accounts": config.account_config.num_accounts,
+            "target_tpb": config.transaction_config.target_tpb,
+            "sim_total_block_number": config.simulation_config.sim_total_block_number,
+            "zipf_parameter": config.transaction_config.zipf_parameter,
+            "ratio_cats": config.transaction_config.ratio_cats,
+            "block_interval": config.network_config.block_interval,
+            "cat_lifetime_blocks": config.transaction_config.cat_lifetime_blocks,
+            "chain_delays": config.network_config.chain_delays,
+            "amount_distribution": config.transaction_config.amount_distribution,
+            "sink_accounts": exhaustion.sink_accounts,
+            "window_blocks": exhaustion.window_blocks,
+        }
+    });
+    std::fs::write("simulator/results/sim_balance_exhaustion/data/metadata.json",
+                   serde_json::to_string_pretty(&metadata).unwrap())
+        .expect("Failed to write metadata.json");
+
+    // Copy config.toml to data directory for reference
+    std::fs::copy("simulator/src/scenarios/sim_balance_exhaustion/config.toml",
+                  "simulator/results/sim_balance_exhaustion/data/config.toml")
+        .expect("Failed to copy config.toml");
+
+    println!("Running Balance Exhaustion Simulation");
+    logging::log("SIMULATOR", &format!("Sending from accounts 1-{} to the {} sink accounts above",
+        config.account_config.num_accounts - exhaustion.sink_accounts, exhaustion.sink_accounts));
+
+    // Draw the master seed unless the experiment or the configuration fixes it
+    let master_seed = crate::metadata::master_seed(config.simulation_config.seed);
+    logging::log("SIMULATOR", &format!("Master seed: {}", master_seed));
+
+    for run in 1..=num_runs {
+        logging::log("SIMULATOR", &format!("=== Starting Run {}/{} ===", run, num_runs));
+
+        let mut results = crate::scenarios::sim_simple::simulation::initialize_simulation_results(&config);
+        results.master_seed = master_seed;
+        results.run_seed = crate::metadata::run_seed(master_seed, run);
+        results.sink_accounts = exhaustion.sink_accounts;
+
+        // Flush the partial results of the run to the directory it is saved to
+        let run_dir = format!("simulator/results/sim_balance_exhaustion/data/sim_0/run_{}", run - 1);
+        results.flush_dir = Some(run_dir.clone());
+
+        // Capture the logs of the run in its directory, one file per component, if configured
+        let _log_capture = crate::scenarios::utils::capture_run_logs(&config.logging_config, &run_dir);
+
+        // Gate the links of the chains the fault timeline partitions
+        let partitions = crate::fault_orchestrator::network_partitions(&config.network_config.fault_timeline);
+
+        // Setup test nodes with preloaded accounts from config
+        let (hs_node, cl_node, hig_node_1, hig_node_2, _start_block_height) = crate::testnodes::setup_test_nodes(
+            Duration::from_secs_f64(config.network_config.block_interval),
+            &[0.0, 0.0], // Zero delays for funding
+            &config.transaction_config.allow_cat_pending_dependencies_per_chain(2),
+            config.transaction_config.cat_lifetime_blocks,
+            &config.account_config.chain_balances(2),
+            config.network_config.channel_buffer_size,
+            &config.network_config.topology,
+            &partitions,
+            &results.network,
+            crate::testnodes::simulation_clock(&config.simulation_config),
+        ).await;
+
+        // Apply the HS and CL settings as in the simple simulation
+        hs_node.lock().await.set_decision_latency(
+            config.network_config.hs_decision_latency.scaled(config.network_config.block_interval)
+        ).await;
+        crate::testnodes::apply_clock_skews(&hs_node, &[hig_node_1.clone(), hig_node_2.clone()], &config.network_config.clock_skew).await;
+        crate::testnodes::apply_status_update_delays(&hs_node, &results.network, &config.network_config.hs_status_update_delays, config.network_config.block_interval).await;
+        cl_node.lock().await.set_block_ordering(config.network_config.cl_block_ordering).await;
+        cl_node.lock().await.set_cat_alignment(config.network_config.cl_align_cat_parts).await;
+        cl_node.lock().await.set_block_capacity(config.network_config.cl_block_capacity()).await;
+        cl_node.lock().await.set_block_interval_jitter(config.network_config.cl_block_interval_jitter_secs()).await;
+        for hig_node in [&hig_node_1, &hig_node_2] {
+            hig_node.lock().await.set_release_locks_on_failure_proposal(config.network_config.hig_release_locks_on_failure).await;
+            hig_node.lock().await.set_prioritize_status_updates(config.network_config.hig_prioritize_status_updates).await;
+        }
+
+        // Run simulation; senders and receivers are drawn from the sources and the sinks
+        let run_message = format!("Run {}/{}", run, num_runs);
+        let simulation_result = crate::run_simulation::run_simulation_with_message_and_retries(
+            cl_node.clone(),
+            vec![hig_node_1.clone(), hig_node_2.clone()],
+            partitions,
+            &mut results,
+            Some(run_message),
+            None,
+        ).await;
+        if let Err(e) = simulation_result {
+            return Err(crate::config::ConfigError::ValidationError(format!(
+                "Balance exhaustion simulation failed during run {}/{}: {}", run, num_runs, e)));
+        }
+
+        // Record the HS state before the nodes are shut down
+        results.cats_awaiting_chain_1 = crate::stats::collect_pending_cats(&hs_node, results.network.chain_ids()[0].clone()).await;
+        results.cats_awaiting_chain_2 = crate::stats::collect_pending_cats(&hs_node, results.network.chain_ids()[1].clone()).await;
+        results.hs_metrics = crate::stats::collect_hs_metrics(&hs_node).await;
+        results.node_health = Some(crate::stats::collect_network_health(&cl_node, &hs_node, &results.network, &[hig_node_1.clone(), hig_node_2.clone()]).await);
+        let timelines = crate::timeline::collect_transaction_timelines(&cl_node, &[hig_node_1.clone(), hig_node_2.clone()], &hs_node, &results.recorded_workload).await;
+        results.latency_breakdown = crate::latency_breakdown::LatencyBreakdownReport::from_timelines(&timelines);
+        results.slowest_transactions = crate::slowest::SlowestTransactionsReport::slowest(timelines, crate::slowest::SLOWEST_TRANSACTIONS_LISTED);
+        results.hs_decision_latencies = crate::decision_latency::collect_hs_decision_latencies(&cl_node, &hs_node, &results.recorded_workload).await;
+
+        // Shutdown nodes between runs to prevent memory leak
+        if run < num_runs {
+            logging::log("SIMULATOR", "Shutting down nodes between runs to clear state...");
+            hyperplane::hyper_ig::node::HyperIGNode::shutdown(hig_node_1.clone()).await;
+            hyperplane::hyper_ig::node::HyperIGNode::shutdown(hig_node_2.clone()).await;
+            hyperplane::confirmation_layer::node::ConfirmationLayerNode::shutdown(cl_node.clone()).await;
+            hyperplane::hyper_scheduler::node::HyperSchedulerNode::shutdown(hs_node.clone()).await;
+            logging::log("SIMULATOR", "Node shutdown complete");
+        }
+
+        // Save this run's results and exhaustion report to its own directory
+        if let Err(e) = results.save_to_directory(&run_dir).await {
+            return Err(crate::config::ConfigError::ValidationError(format!(
+                "Balance exhaustion simulation failed to save results for run {}/{}: {}", run, num_runs, e)));
+        }
+        let exhaustion_report = BalanceExhaustionReport::from_results(&results, exhaustion.window_blocks);
+        let report_path = format!("{}/data/{}", run_dir, BALANCE_EXHAUSTION_FILE);
+        fs::write(&report_path, serde_json::to_string_pretty(&exhaustion_report).unwrap())?;
+
+        logging::log("SIMULATOR", "\n=== Balance Exhaustion ===");
+        for line in exhaustion_report.summary_lines() {
+            logging::log("SIMULATOR", &line);
+        }
+        logging::log("SIMULATOR", "==========================");
+        if !exhaustion_report.is_consistent() {
+            println!("Run {}: the status counts of the HIGs do not add up, see {}", run, report_path);
+        }
+
+        logging::log("SIMULATOR", &format!("=== Completed Run {}/{} ===", run, num_runs));
+    }
+
+    println!("Balance exhaustion simulation complete");
+    logging::log("SIMULATOR", "=== Balance Exhaustion Simulation Complete ===");
+
+    Ok(())
+}
+
+/// Runs the balance exhaustion simulation with automatic plotting
+pub async fn run_with_plotting() -> Result<(), crate::config::ConfigError> {
+    use crate::scenarios::utils::run_simulation_with_plotting;
+
+    run_simulation_with_plotting(
+        || run_balance_exhaustion_simulation(),
+        "Balance Exhaustion Simulation",
+        "simulator/src/scenarios/sim_balance_exhaustion/plot_results.py"
+    ).await
+}
+
+// ------------------------------------------------------------------------------------------------
+// Logging Setup
+// ------------------------------------------------------------------------------------------------
+
+/// Sets up logging with configuration
+fn setup_logging(config: &crate::config::Config) {
+    if config.logging_config.log_to_file {
+        let log_path = "simulator/results/sim_balance_exhaustion/simulation.log";
+        if let Err(e) = fs::remove_file(log_path) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                eprintln!("Error deleting log file: {}", e);
+            }
+        }
+        logging::init_logging_with_config(true, true, Some(log_path.to_string()));
+    } else {
+        logging::init_logging_with_config(false, false, None);
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Simulation Registration
+// ------------------------------------------------------------------------------------------------
+
+/// Register this simulation with the simulation registry.
+pub fn register() -> (crate::interface::SimulationType, crate::simulation_registry::SimulationConfig) {
+    use crate::interface::SimulationType;
+    use crate::simulation_registry::SimulationConfig;
+
+    (SimulationType::BalanceExhaustion, SimulationConfig {
+        name: "Balance Exhaustion Simulation",
+        run_fn: Box::new(|| Box::pin(async {
+            run_balance_exhaustion_simulation().await
+                .map_err(|e| format!("Balance exhaustion simulation failed: {}", e))
+        })),
+        plot_script: "simulator/src/scenarios/sim_balance_exhaustion/plot_results.py",
+        config_schema: Some(ConfigSchema {
+            config_path: "simulator/src/scenarios/sim_balance_exhaustion/config.toml",
+            sections: &[crate::config_schema::COMMON_PARAMETERS, EXHAUSTION_PARAMETERS],
+        }),
+    })
+}
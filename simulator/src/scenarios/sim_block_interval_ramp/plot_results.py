@@ -0,0 +1,147 @@
+#!/usr/bin/env python3
+"""
+Plotting script for the Block Interval Ramp Simulation
+
+This script averages the runs and plots the pending transactions of both chains per block, marking
+the blocks at which the CL block interval changed, and the HIG load and proposal queue depth of each
+stage of constant block interval. The stage reports of all runs are summarized in
+data/ramp_summary.json.
+
+Usage:
+    python plot_results.py
+"""
+
+import sys
+import os
+import json
+import glob
+
+import numpy as np
+import matplotlib.pyplot as plt
+
+# Add the simulator source directory to the Python path to import the averaging script
+sys.path.insert(0, os.path.join(os.path.dirname(os.path.abspath(__file__)), '..', '..'))
+from average_runs import create_averaged_data
+
+RESULTS_DIR = 'simulator/results/sim_block_interval_ramp'
+
+# Stage metrics averaged over the runs
+STAGE_METRICS = ('chain_1_pending_growth', 'chain_2_pending_growth', 'chain_1_cat_pending_growth',
+                 'chain_2_cat_pending_growth', 'cat_successes', 'max_proposal_queue_depth',
+                 'mean_hig_processing_ms', 'hig_load')
+
+
+def load_series(data_dir: str, filename: str, key: str) -> tuple:
+    """Return the heights and counts of a time series, or empty lists if it is missing."""
+    path = os.path.join(data_dir, filename)
+    if not os.path.exists(path):
+        return [], []
+    with open(path, 'r') as f:
+        series = json.load(f).get(key, [])
+    return [entry['height'] for entry in series], [entry['count'] for entry in series]
+
+
+def load_ramp_reports(results_dir: str) -> list:
+    """Load the ramp report of every run."""
+    reports = []
+    for path in sorted(glob.glob(f'{results_dir}/data/sim_0/run_*/data/block_interval_ramp.json')):
+        with open(path, 'r') as f:
+            reports.append(json.load(f))
+    return reports
+
+
+def summarize_stages(reports: list) -> list:
+    """Average the metrics of each stage (by position in the schedule) over the runs."""
+    summary = []
+    for index in range(max(len(report['stages']) for report in reports)):
+        stages = [report['stages'][index] for report in reports if index < len(report['stages'])]
+        entry = {'block_interval': stages[0]['block_interval'], 'runs': len(stages),
+                 'runs_falling_behind': sum(1 for stage in stages if stage['falling_behind'])}
+        for key in STAGE_METRICS:
+            entry[key] = float(np.mean([stage[key] for stage in stages]))
+        summary.append(entry)
+    return summary
+
+
+def interval_changes(results_dir: str) -> list:
+    """Return the heights and new block intervals of the changes applied in the first run."""
+    path = f'{results_dir}/data/sim_0/run_0/data/simulation_stats.json'
+    if not os.path.exists(path):
+        return []
+    with open(path, 'r') as f:
+        changes = json.load(f).get('results', {}).get('block_interval_changes', [])
+    return [(change['height'], change['block_interval']) for change in changes]
+
+
+def plot_pending(data_dir: str, changes: list, results_dir: str) -> None:
+    """Plot the pending transactions and CATs of both chains per block with the interval changes marked."""
+    fig, axes = plt.subplots(2, 1, figsize=(12, 9), sharex=True)
+    for ax, chain in zip(axes, (1, 2)):
+        heights, counts = load_series(data_dir, f'pending_transactions_chain_{chain}.json', f'chain_{chain}_pending')
+        if heights:
+            ax.plot(heights, counts, label='pending')
+        heights, counts = load_series(data_dir, f'cat_pending_transactions_chain_{chain}.json', f'chain_{chain}_cat_pending')
+        if heights:
+            ax.plot(heights, counts, label='CAT pending')
+        for height, block_interval in changes:
+            ax.axvline(height, color='gray', linestyle='--', alpha=0.7)
+            ax.text(height, ax.get_ylim()[1], f' {block_interval:g}s', va='top', fontsize=8, color='gray')
+        ax.set_ylabel('Transactions')
+        ax.set_title(f'Pending Transactions of chain-{chain}')
+        ax.grid(True, alpha=0.3)
+        ax.legend()
+    axes[-1].set_xlabel('Block Height')
+
+    plt.tight_layout()
+    plt.savefig(f'{results_dir}/figs/pending_block_interval_ramp.png', dpi=300, bbox_inches='tight')
+    plt.close()
+
+
+def plot_stages(stages: list, results_dir: str) -> None:
+    """Plot the HIG load and the largest proposal queue depth of each stage."""
+    labels = [f"{stage['block_interval']:g}s" for stage in stages]
+    positions = np.arange(len(stages))
+    fig, (ax_load, ax_queue) = plt.subplots(2, 1, figsize=(10, 8), sharex=True)
+
+    colors = ['tab:red' if stage['runs_falling_behind'] else 'tab:blue' for stage in stages]
+    ax_load.bar(positions, [stage['hig_load'] * 100.0 for stage in stages], color=colors)
+    ax_load.axhline(100.0, color='black', linestyle='--', alpha=0.5)
+    ax_load.set_ylabel('HIG time per subblock (% of block interval)')
+    ax_load.set_title('HIG Load per Stage (red = pending transactions piling up)')
+    ax_load.grid(True, alpha=0.3)
+
+    ax_queue.bar(positions, [stage['max_proposal_queue_depth'] for stage in stages], color=colors)
+    ax_queue.set_ylabel('Max proposal queue depth')
+    ax_queue.set_xlabel('Block interval of the stage')
+    ax_queue.set_xticks(positions)
+    ax_queue.set_xticklabels(labels)
+    ax_queue.grid(True, alpha=0.3)
+
+    plt.tight_layout()
+    plt.savefig(f'{results_dir}/figs/stages_block_interval_ramp.png', dpi=300, bbox_inches='tight')
+    plt.close()
+
+
+def main():
+    """Main function to generate the plots of the block interval ramp simulation."""
+    if not create_averaged_data(RESULTS_DIR):
+        print("Averaging failed!")
+        return
+
+    reports = load_ramp_reports(RESULTS_DIR)
+    if not reports or not any(report['stages'] for report in reports):
+        print("No ramp reports found. Skipping plot generation.")
+        return
+
+    stages = summarize_stages(reports)
+    with open(f'{RESULTS_DIR}/data/ramp_summary.json', 'w') as f:
+        json.dump({'stages': stages}, f, indent=2)
+
+    os.makedirs(f'{RESULTS_DIR}/figs', exist_ok=True)
+    plot_pending(f'{RESULTS_DIR}/data/sim_0/run_average', interval_changes(RESULTS_DIR), RESULTS_DIR)
+    plot_stages(stages, RESULTS_DIR)
+    print(f"Ramp summary written to {RESULTS_DIR}/data/ramp_summary.json and plots to {RESULTS_DIR}/figs/")
+
+
+if __name__ == "__main__":
+    main()
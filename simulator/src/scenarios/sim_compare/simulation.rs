@@ -0,0 +1,281 @@
+use std::fs;
+use std::time::Duration;
+
+use hyperplane::utils::logging;
+use serde::Deserialize;
+use crate::config_schema::{ConfigSchema, ParameterKind, ParameterSchema, parameter};
+use toml;
+use serde_json;
+
+// ------------------------------------------------------------------------------------------------
+// Configuration Loading
+// ------------------------------------------------------------------------------------------------
+
+/// A named protocol configuration: the base configuration with some of its values overridden
+#[derive(Debug, Deserialize, Clone)]
+struct Variant {
+    /// Name of the variant in the comparison table and charts
+    name: String,
+    /// Tables of the base configuration with the values to override (e.g. `transaction_config`)
+    #[serde(flatten)]
+    overrides: toml::Table,
+}
+
+/// Comparison-specific configuration
+#[derive(Debug, Deserialize, Clone)]
+struct CompareConfig {
+    /// Configuration both variants start from
+    #[serde(default = "default_base_config")]
+    base_config: String,
+    /// The baseline variant
+    variant_a: Variant,
+    /// The variant compared against the baseline
+    variant_b: Variant,
+}
+
+/// Parameters of the comparison config.toml
+const COMPARE_PARAMETERS: &[ParameterSchema] = &[
+    ParameterSchema { required: true, ..parameter("compare_config", ParameterKind::Table, "Comparison parameters") },
+    ParameterSchema { default: Some("simulator/src/scenarios/sim_simple/config.toml"), ..parameter("compare_config.base_config", ParameterKind::String, "Configuration both variants start from") },
+    ParameterSchema { required: true, ..parameter("compare_config.variant_a", ParameterKind::Table, "Name and overrides of the baseline variant") },
+    ParameterSchema { required: true, ..parameter("compare_config.variant_b", ParameterKind::Table, "Name and overrides of the compared variant") },
+];
+
+/// Layout of the comparison config.toml
+#[derive(Debug, Deserialize, Clone)]
+struct CompareScenarioConfig {
+    compare_config: CompareConfig,
+}
+
+fn default_base_config() -> String {
+    "simulator/src/scenarios/sim_simple/config.toml".to_string()
+}
+
+/// Loads the comparison configuration and builds the configuration of each variant.
+///
+/// Each variant's overrides are merged into the base configuration table by table, so only the
+/// values that differ between the variants have to be listed.
+fn load_config() -> Result<(CompareConfig, [(String, toml::Value, crate::config::Config); 2]), crate::config::ConfigError> {
+    let compare_str = fs::read_to_string("simulator/src/scenarios/sim_compare/config.toml")?;
+    let compare_config = toml::from_str::<CompareScenarioConfig>(&compare_str)?.compare_config;
+    if compare_config.variant_a.name == compare_config.variant_b.name {
+        return Err(crate::config::ConfigError::ValidationError("The two variants must have different names".to_string()));
+    }
+
+    let base: toml::Value = toml::from_str(&fs::read_to_string(&compare_config.base_config)?)?;
+    let build = |variant: &Variant| -> Result<(String, toml::Value, crate::config::Config), crate::config::ConfigError> {
+        let mut merged = base.clone();
+        if let toml::Value::Table(table) = &mut merged {
+            merge_tables(table, &variant.overrides);
+        }
+        let config = merged.clone().try_into::<crate::config::Config>()?;
+        config.validate()?;
+        Ok((variant.name.clone(), merged, config))
+    };
+    let variants = [build(&compare_config.variant_a)?, build(&compare_config.variant_b)?];
+
+    // Both variants must generate the same workload, which depends on these parameters
+    let (a, b) = (&variants[0].2, &variants[1].2);
+    if a.simulation_config.num_runs != b.simulation_config.num_runs || a.simulation_config.seed != b.simulation_config.seed {
+        return Err(crate::config::ConfigError::ValidationError("The variants must not override num_runs or seed".to_string()));
+    }
+    Ok((compare_config, variants))
+}
+
+/// Recursively merges the override tables into the base tables
+fn merge_tables(base: &mut toml::Table, overrides: &toml::Table) {
+    for (key, value) in overrides {
+        match (base.get_mut(key), value) {
+            (Some(toml::Value::Table(base_table)), toml::Value::Table(override_table)) => merge_tables(base_table, override_table),
+            _ => {
+                base.insert(key.clone(), value.clone());
+            }
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Simulation Entry Point
+// ------------------------------------------------------------------------------------------------
+
+/// Runs the comparison of two protocol variants
+///
+/// Every run executes variant A and then variant B with the same run seed, so both variants see
+/// the same generated workload. Variant A's runs are saved like the first simulation of a sweep
+/// (`data/sim_0`) and variant B's like the second (`data/sim_1`). The averaged results are written
+/// side by side to `comparison.md`, `comparison.json` and `report.html`.
+pub async fn run_compare_simulation() -> Result<(), crate::config::ConfigError> {
+    // Create results directory if it doesn't exist
+    fs::create_dir_all("simulator/results/sim_compare/data").expect("Failed to create data directory");
+
+    // Load configuration
+    let (compare_config, variants) = load_config()?;
+    let num_runs = variants[0].2.simulation_config.num_runs;
+
+    // Setup logging with configuration
+    setup_logging(&variants[0].2);
+
+    // Write metadata.json in the layout of a sweep over the variants
+    let metadata = serde_json::json!({
+        "num_runs": num_runs,
+        "num_simulations": 2,
+        "parameter_name": "variant",
+        "parameter_values": [variants[0].0, variants[1].0],
+        "base_config": compare_config.base_config,
+    });
+    fs::write("simulator/results/sim_compare/data/metadata.json", serde_json::to_string_pretty(&metadata).unwrap())
+        .expect("Failed to write metadata.json");
+
+    // Copy config.toml to data directory for reference, and save the configuration each variant ran with
+    fs::copy("simulator/src/scenarios/sim_compare/config.toml", "simulator/results/sim_compare/data/config.toml")
+        .expect("Failed to copy config.toml");
+    for (sim_index, (_, merged, _)) in variants.iter().enumerate() {
+        let sim_dir = format!("simulator/results/sim_compare/data/sim_{}", sim_index);
+        fs::create_dir_all(&sim_dir).expect("Failed to create simulation directory");
+        fs::write(format!("{}/config.toml", sim_dir), toml::to_string_pretty(merged).unwrap())
+            .expect("Failed to write variant config.toml");
+    }
+
+    println!("Comparing '{}' and '{}' over {} runs", variants[0].0, variants[1].0, num_runs);
+    logging::log("SIMULATOR", &format!("=== Comparing '{}' and '{}' over {} runs ===", variants[0].0, variants[1].0, num_runs));
+
+    // Draw the master seed shared by both variants unless the experiment or the configuration fixes it
+    let master_seed = crate::metadata::master_seed(variants[0].2.simulation_config.seed);
+    logging::log("SIMULATOR", &format!("Master seed: {}", master_seed));
+
+    for run in 1..=num_runs {
+        let mut workloads = Vec::new();
+        for (sim_index, (name, _, config)) in variants.iter().enumerate() {
+            logging::log("SIMULATOR", &format!("=== Starting Run {}/{} of '{}' ===", run, num_runs, name));
+            let run_dir = format!("simulator/results/sim_compare/data/sim_{}/run_{}", sim_index, run - 1);
+            // Capture the logs of the run in its directory, one file per component, if configured
+            let _log_capture = crate::scenarios::utils::capture_run_logs(&config.logging_config, &run_dir);
+            let results = run_variant(config, name, master_seed, run).await?;
+            results.save_to_directory(&run_dir).await.map_err(|e| crate::config::ConfigError::ValidationError(
+                format!("Comparison failed to save results of '{}' for run {}/{}: {}", name, run, num_runs, e)
+            ))?;
+            workloads.push(results.recorded_workload.iter().map(|s| (s.block_offset, s.is_cat, s.from_account, s.to_account)).collect::<Vec<_>>());
+        }
+
+        // The workload only depends on the seed, unless a variant overrides workload parameters
+        if workloads[0] != workloads[1] {
+            let warning = format!("Warning: the variants submitted different workloads in run {}/{}", run, num_runs);
+            logging::log("SIMULATOR", &warning);
+            println!("{}", warning);
+        }
+    }
+
+    // Compare the variants
+    let report_path = crate::report::generate_comparison_report("sim_compare")
+        .map_err(crate::config::ConfigError::ValidationError)?;
+    let table_path = format!("simulator/results/sim_compare/{}", crate::report::COMPARISON_MARKDOWN_FILE);
+    if let Ok(table) = fs::read_to_string(&table_path) {
+        println!("{}", table);
+    }
+    println!("Comparison report written to {}", report_path);
+
+    println!("Comparison simulation complete");
+    logging::log("SIMULATOR", "=== Comparison Simulation Complete ===");
+
+    Ok(())
+}
+
+/// Runs one run of a variant on freshly built nodes
+async fn run_variant(config: &crate::config::Config, name: &str, master_seed: u64, run: u32) -> Result<crate::SimulationResults, crate::config::ConfigError> {
+    let num_runs = config.simulation_config.num_runs;
+
+    // Initialize simulation results from configuration
+    let mut results = crate::scenarios::sim_simple::simulation::initialize_simulation_results(config);
+    results.master_seed = master_seed;
+    results.run_seed = crate::metadata::run_seed(master_seed, run);
+
+    // Gate the links of the chains the fault timeline partitions
+    let partitions = crate::fault_orchestrator::network_partitions(&config.network_config.fault_timeline);
+
+    // Setup test nodes with preloaded accounts from config and apply the HS, CL and HIG settings
+    let (hs_node, cl_node, hig_node_1, hig_node_2) = crate::run_simulation::setup_configured_nodes(
+        &config,
+        &[0.0, 0.0], // Zero delays for funding
+        &partitions,
+        &results.network,
+    ).await;
+
+    // Set the chain delays for the main simulation
+    hig_node_1.lock().await.set_hs_message_delay(Duration::from_secs_f64(config.network_config.block_interval * config.network_config.chain_delays[0]));
+    hig_node_2.lock().await.set_hs_message_delay(Duration::from_secs_f64(config.network_config.block_interval * config.network_config.chain_delays[1]));
+
+    // Run simulation
+    crate::run_simulation::run_simulation_with_message_and_retries(
+        cl_node.clone(),
+        vec![hig_node_1.clone(), hig_node_2.clone()],
+        partitions,
+        &mut results,
+        Some(format!("{} {}/{}", name, run, num_runs)),
+        None, // No retry count needed
+    ).await.map_err(|e| crate::config::ConfigError::ValidationError(
+        format!("Comparison failed during run {}/{} of '{}': {}", run, num_runs, name, e)
+    ))?;
+
+    // Record where the HS is still waiting on proposals, its metrics, the slowest transactions and the HS decision latencies before the nodes are shut down
+    results.cats_awaiting_chain_1 = crate::stats::collect_pending_cats(&hs_node, results.network.chain_ids()[0].clone()).await;
+    results.cats_awaiting_chain_2 = crate::stats::collect_pending_cats(&hs_node, results.network.chain_ids()[1].clone()).await;
+    results.hs_metrics = crate::stats::collect_hs_metrics(&hs_node).await;
+    results.node_health = Some(crate::stats::collect_network_health(&cl_node, &hs_node, &results.network, &[hig_node_1.clone(), hig_node_2.clone()]).await);
+    let timelines = crate::timeline::collect_transaction_timelines(&cl_node, &[hig_node_1.clone(), hig_node_2.clone()], &hs_node, &results.recorded_workload).await;
+    results.latency_breakdown = crate::latency_breakdown::LatencyBreakdownReport::from_timelines(&timelines);
+    results.slowest_transactions = crate::slowest::SlowestTransactionsReport::slowest(timelines, crate::slowest::SLOWEST_TRANSACTIONS_LISTED);
+    results.hs_decision_latencies = crate::decision_latency::collect_hs_decision_latencies(&cl_node, &hs_node, &results.recorded_workload).await;
+
+    // Shutdown nodes so the next variant starts from a clean state
+    hyperplane::hyper_ig::node::HyperIGNode::shutdown(hig_node_1.clone()).await;
+    hyperplane::hyper_ig::node::HyperIGNode::shutdown(hig_node_2.clone()).await;
+    hyperplane::confirmation_layer::node::ConfirmationLayerNode::shutdown(cl_node.clone()).await;
+    hyperplane::hyper_scheduler::node::HyperSchedulerNode::shutdown(hs_node.clone()).await;
+
+    Ok(results)
+}
+
+// ------------------------------------------------------------------------------------------------
+// Logging Setup
+// ------------------------------------------------------------------------------------------------
+
+/// Sets up logging with configuration
+fn setup_logging(config: &crate::config::Config) {
+    if config.logging_config.log_to_file {
+        let log_path = "simulator/results/sim_compare/simulation.log";
+        if let Err(e) = fs::remove_file(log_path) {
+            // Ignore error if file doesn't exist
+            if e.kind() != std::io::ErrorKind::NotFound {
+                eprintln!("Error deleting log file: {}", e);
+            }
+        }
+        logging::init_logging_with_config(true, true, Some(log_path.to_string()));
+    } else {
+        logging::init_logging_with_config(false, false, None);
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Simulation Registration
+// ------------------------------------------------------------------------------------------------
+
+/// Register this simulation with the simulation registry.
+///
+/// The comparison has no plot script; its charts are part of the generated report.
+pub fn register() -> (crate::interface::SimulationType, crate::simulation_registry::SimulationConfig) {
+    use crate::interface::SimulationType;
+    use crate::simulation_registry::SimulationConfig;
+
+    (SimulationType::Compare, SimulationConfig {
+        name: "Compare Protocol Variants",
+        run_fn: Box::new(|| Box::pin(async {
+            run_compare_simulation().await
+                .map_err(|e| format!("Comparison simulation failed: {}", e))
+        })),
+        plot_script: "",
+        config_schema: Some(ConfigSchema {
+            config_path: "simulator/src/scenarios/sim_compare/config.toml",
+            sections: &[COMPARE_PARAMETERS],
+        }),
+    })
+}
@@ -0,0 +1,122 @@
+#!/usr/bin/env python3
+"""
+Plotting script for the HIG Failover Simulation
+
+This script averages the runs and plots the CAT statuses of both chains per block, marking the
+blocks at which a crashed primary was detected and its standby took over. The failover reports
+of all runs are summarized in data/failover_summary.json.
+
+Usage:
+    python plot_results.py
+"""
+
+import sys
+import os
+import json
+import glob
+
+import numpy as np
+import matplotlib.pyplot as plt
+
+# Add the simulator source directory to the Python path to import the averaging script
+sys.path.insert(0, os.path.join(os.path.dirname(os.path.abspath(__file__)), '..', '..'))
+from average_runs import create_averaged_data
+
+RESULTS_DIR = 'simulator/results/sim_failover'
+
+# CAT statuses plotted per chain
+STATUSES = ('pending', 'success', 'failure')
+
+
+def load_series(data_dir: str, chain: int, status: str) -> tuple:
+    """Return the heights and counts of a CAT status time series, or empty lists if it is missing."""
+    path = os.path.join(data_dir, f'cat_{status}_transactions_chain_{chain}.json')
+    if not os.path.exists(path):
+        return [], []
+    with open(path, 'r') as f:
+        series = json.load(f).get(f'chain_{chain}_cat_{status}', [])
+    return [entry['height'] for entry in series], [entry['count'] for entry in series]
+
+
+def load_failover_reports(results_dir: str) -> list:
+    """Load the failover report of every run."""
+    reports = []
+    for path in sorted(glob.glob(f'{results_dir}/data/sim_0/run_*/data/failover.json')):
+        with open(path, 'r') as f:
+            reports.append(json.load(f))
+    return reports
+
+
+def summarize_failovers(reports: list) -> list:
+    """Average the failover outcome of each chain over the runs."""
+    summary = {}
+    for report in reports:
+        for chain in report['chains']:
+            entry = summary.setdefault(chain['chain'], {'chain': chain['chain'], 'runs': 0, 'failovers': 0,
+                                                        'halt_detected_at_height': [], 'promoted_at_height': [],
+                                                        'proposals_replayed': [], 'mean_replay_delay_ms': [],
+                                                        'fenced_primary_proposals': [], 'double_proposals': 0})
+            entry['runs'] += 1
+            entry['double_proposals'] += chain['double_proposals']
+            if chain['promoted_at_height'] is None:
+                continue
+            entry['failovers'] += 1
+            for key in ('halt_detected_at_height', 'promoted_at_height', 'proposals_replayed',
+                        'mean_replay_delay_ms', 'fenced_primary_proposals'):
+                entry[key].append(chain[key])
+
+    for entry in summary.values():
+        for key in ('halt_detected_at_height', 'promoted_at_height', 'proposals_replayed',
+                    'mean_replay_delay_ms', 'fenced_primary_proposals'):
+            entry[key] = float(np.mean(entry[key])) if entry[key] else None
+    return list(summary.values())
+
+
+def plot_cat_statuses(data_dir: str, failovers: list, results_dir: str) -> None:
+    """Plot the CAT statuses of both chains per block with the failover of each chain marked."""
+    fig, axes = plt.subplots(2, 1, figsize=(12, 9), sharex=True)
+    for ax, chain in zip(axes, (1, 2)):
+        for status in STATUSES:
+            heights, counts = load_series(data_dir, chain, status)
+            if heights:
+                ax.plot(heights, counts, label=status)
+        for failover in failovers:
+            if failover['promoted_at_height'] is None:
+                continue
+            ax.axvline(failover['halt_detected_at_height'], color='red', linestyle='--', alpha=0.7,
+                       label=f"{failover['chain']} primary halted")
+            ax.axvline(failover['promoted_at_height'], color='green', linestyle='--', alpha=0.7,
+                       label=f"{failover['chain']} standby took over")
+        ax.set_ylabel('CATs')
+        ax.set_title(f'CAT Statuses of chain-{chain} (as seen by its primary HIG)')
+        ax.grid(True, alpha=0.3)
+        ax.legend()
+    axes[-1].set_xlabel('Block Height')
+
+    plt.tight_layout()
+    plt.savefig(f'{results_dir}/figs/cat_statuses_failover.png', dpi=300, bbox_inches='tight')
+    plt.close()
+
+
+def main():
+    """Main function to generate the plots of the failover simulation."""
+    if not create_averaged_data(RESULTS_DIR):
+        print("Averaging failed!")
+        return
+
+    reports = load_failover_reports(RESULTS_DIR)
+    if not reports:
+        print("No failover reports found. Skipping plot generation.")
+        return
+
+    failovers = summarize_failovers(reports)
+    with open(f'{RESULTS_DIR}/data/failover_summary.json', 'w') as f:
+        json.dump({'chains': failovers}, f, indent=2)
+
+    os.makedirs(f'{RESULTS_DIR}/figs', exist_ok=True)
+    plot_cat_statuses(f'{RESULTS_DIR}/data/sim_0/run_average', failovers, RESULTS_DIR)
+    print(f"Failover summary written to {RESULTS_DIR}/data/failover_summary.json and plots to {RESULTS_DIR}/figs/")
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,293 @@
+use std::fs;
+use std::time::Duration;
+
+use hyperplane::utils::logging;
+use serde::Deserialize;
+use crate::config_schema::{ConfigSchema, ParameterKind, ParameterSchema, parameter};
+use crate::fault_orchestrator::FaultEvent;
+use toml;
+use serde_json;
+
+// ------------------------------------------------------------------------------------------------
+// Configuration Loading
+// ------------------------------------------------------------------------------------------------
+
+/// Failover-specific configuration
+#[derive(Debug, Deserialize, Clone)]
+struct FailoverConfig {
+    /// Number of consecutive blocks a primary must be halted before its standby takes over
+    #[serde(default = "default_detection_blocks")]
+    detection_blocks: u64,
+}
+
+/// Layout of the failover-specific part of the config.toml
+#[derive(Debug, Deserialize, Clone)]
+struct FailoverScenarioConfig {
+    failover_config: FailoverConfig,
+}
+
+/// Parameters of the failover config.toml beyond the common ones
+const FAILOVER_PARAMETERS: &[ParameterSchema] = &[
+    ParameterSchema { required: true, ..parameter("failover_config", ParameterKind::Table, "Failover parameters") },
+    ParameterSchema { default: Some("2"), min: Some(1.0), ..parameter("failover_config.detection_blocks", ParameterKind::Integer, "Consecutive blocks a primary must be halted before its standby takes over") },
+];
+
+fn default_detection_blocks() -> u64 {
+    2
+}
+
+/// Loads and validates the failover simulation configuration from the TOML file.
+///
+/// The fault timeline must crash at least one primary HIG, otherwise there is no failover to
+/// observe.
+fn load_config() -> Result<(crate::config::Config, FailoverConfig), crate::config::ConfigError> {
+    let config_str = fs::read_to_string("simulator/src/scenarios/sim_failover/config.toml")?;
+    let config: crate::config::Config = toml::from_str(&config_str)?;
+    config.validate()?;
+    let failover_config = toml::from_str::<FailoverScenarioConfig>(&config_str)?.failover_config;
+
+    if !config.network_config.fault_timeline.iter().any(|fault| matches!(fault.event, FaultEvent::Crash { .. })) {
+        return Err(crate::config::ConfigError::ValidationError(
+            "The failover simulation needs a crash event in network_config.fault_timeline".to_string()));
+    }
+    if failover_config.detection_blocks == 0 {
+        return Err(crate::config::ConfigError::ValidationError(
+            "failover_config.detection_blocks must be at least 1".to_string()));
+    }
+    Ok((config, failover_config))
+}
+
+// ------------------------------------------------------------------------------------------------
+// Simulation Entry Point
+// ------------------------------------------------------------------------------------------------
+
+/// Runs the failover simulation
+///
+/// Every chain runs a primary HIG and a warm standby fed the same subblocks. The fault timeline
+/// crashes primaries; the watchdog promotes a chain's standby once its primary was halted for
+/// `detection_blocks` blocks. The outcome of the failovers is written to `failover.json` next to
+/// the results of each run.
+pub async fn run_failover_simulation() -> Result<(), crate::config::ConfigError> {
+    // Create results directory if it doesn't exist
+    fs::create_dir_all("simulator/results/sim_failover/data").expect("Failed to create data directory");
+    fs::create_dir_all("simulator/results/sim_failover/figs").expect("Failed to create figures directory");
+
+    // Load configuration
+    let (config, failover_config) = load_config()?;
+
+    // Setup logging with configuration
+    setup_logging(&config);
+
+    let num_runs = config.simulation_config.num_runs;
+
+    // Write metadata.json for Python averaging script
+    let metadata = serde_json::json!({
+        "num_runs": num_runs,
+        "num_simulations": 1,
+        "parameters": {
+            "initial_balance": config.account_config.initial_balance,
+            "num_accounts": config.account_config.num_accounts,
+            "target_tpb": config.transaction_config.target_tpb,
+            "sim_total_block_number": config.simulation_config.sim_total_block_number,
+            "zipf_parameter": config.transaction_config.zipf_parameter,
+            "ratio_cats": config.transaction_config.ratio_cats,
+            "block_interval": config.network_config.block_interval,
+            "cat_lifetime_blocks": config.transaction_config.cat_lifetime_blocks,
+            "chain_delays": config.network_config.chain_delays,
+            "detection_blocks": failover_config.detection_blocks,
+        }
+    });
+    std::fs::write("simulator/results/sim_failover/data/metadata.json",
+                   serde_json::to_string_pretty(&metadata).unwrap())
+        .expect("Failed to write metadata.json");
+
+    // Copy config.toml to data directory for reference
+    std::fs::copy("simulator/src/scenarios/sim_failover/config.toml",
+                  "simulator/results/sim_failover/data/config.toml")
+        .expect("Failed to copy config.toml");
+
+    println!("Running Failover Simulation");
+
+    // Draw the master seed unless the experiment or the configuration fixes it
+    let master_seed = crate::metadata::master_seed(config.simulation_config.seed);
+    logging::log("SIMULATOR", &format!("Master seed: {}", master_seed));
+
+    let mut runs_with_double_proposals = 0;
+    for run in 1..=num_runs {
+        logging::log("SIMULATOR", &format!("=== Starting Run {}/{} ===", run, num_runs));
+
+        let mut results = crate::scenarios::sim_simple::simulation::initialize_simulation_results(&config);
+        results.master_seed = master_seed;
+        results.run_seed = crate::metadata::run_seed(master_seed, run);
+
+        // Flush the partial results of the run to the directory it is saved to
+        let run_dir = format!("simulator/results/sim_failover/data/sim_0/run_{}", run - 1);
+        results.flush_dir = Some(run_dir.clone());
+
+        // Capture the logs of the run in its directory, one file per component, if configured
+        let _log_capture = crate::scenarios::utils::capture_run_logs(&config.logging_config, &run_dir);
+
+        // Gate the links of the chains the fault timeline partitions
+        let partitions = crate::fault_orchestrator::network_partitions(&config.network_config.fault_timeline);
+
+        // Setup test nodes with a warm standby per chain
+        let block_interval = Duration::from_secs_f64(config.network_config.block_interval);
+        let (hs_node, cl_node, hig_node_1, hig_node_2, _start_block_height, mut standbys) = crate::testnodes::setup_test_nodes_with_standbys(
+            block_interval,
+            &[0.0, 0.0], // Zero delays for funding
+            &config.transaction_config.allow_cat_pending_dependencies_per_chain(2),
+            config.transaction_config.cat_lifetime_blocks,
+            &config.account_config.chain_balances(2),
+            config.network_config.channel_buffer_size,
+            &config.network_config.topology,
+            &partitions,
+            &results.network,
+            crate::testnodes::simulation_clock(&config.simulation_config),
+        ).await;
+        let primaries = vec![hig_node_1.clone(), hig_node_2.clone()];
+
+        // Apply the HS and CL settings as in the simple simulation
+        hs_node.lock().await.set_decision_latency(
+            config.network_config.hs_decision_latency.scaled(config.network_config.block_interval)
+        ).await;
+        crate::testnodes::apply_status_update_delays(&hs_node, &results.network, &config.network_config.hs_status_update_delays, config.network_config.block_interval).await;
+        cl_node.lock().await.set_block_ordering(config.network_config.cl_block_ordering).await;
+        cl_node.lock().await.set_cat_alignment(config.network_config.cl_align_cat_parts).await;
+        cl_node.lock().await.set_block_capacity(config.network_config.cl_block_capacity()).await;
+        cl_node.lock().await.set_block_interval_jitter(config.network_config.cl_block_interval_jitter_secs()).await;
+
+        // A standby runs with the clock skew, delay and policies of its primary
+        crate::testnodes::apply_clock_skews(&hs_node, &primaries, &config.network_config.clock_skew).await;
+        crate::testnodes::apply_clock_skews(&hs_node, &standbys.standby_nodes, &config.network_config.clock_skew).await;
+        for (index, (primary, standby)) in primaries.iter().zip(&standbys.standby_nodes).enumerate() {
+            let delay = Duration::from_secs_f64(config.network_config.block_interval * config.network_config.chain_delays[index]);
+            for hig_node in [primary, standby] {
+                hig_node.lock().await.set_hs_message_delay(delay);
+                hig_node.lock().await.set_release_locks_on_failure_proposal(config.network_config.hig_release_locks_on_failure).await;
+                hig_node.lock().await.set_prioritize_status_updates(config.network_config.hig_prioritize_status_updates).await;
+                hig_node.lock().await.set_protocol_violation_policy(config.simulation_config.protocol_violation_policy).await;
+            }
+        }
+
+        // Promote a standby once its primary was halted for the configured number of blocks
+        standbys.spawn_watchdog(primaries.clone(), cl_node.clone(), block_interval, failover_config.detection_blocks);
+
+        // Run simulation; the fault timeline crashes the primaries only
+        let run_message = format!("Run {}/{}", run, num_runs);
+        let simulation_result = crate::run_simulation::run_simulation_with_message_and_retries(
+            cl_node.clone(),
+            primaries.clone(),
+            partitions,
+            &mut results,
+            Some(run_message),
+            None,
+        ).await;
+        if let Err(e) = simulation_result {
+            return Err(crate::config::ConfigError::ValidationError(format!(
+                "Failover simulation failed during run {}/{}: {}", run, num_runs, e)));
+        }
+
+        // Record the HS state and the outcome of the failovers before the nodes are shut down
+        results.cats_awaiting_chain_1 = crate::stats::collect_pending_cats(&hs_node, results.network.chain_ids()[0].clone()).await;
+        results.cats_awaiting_chain_2 = crate::stats::collect_pending_cats(&hs_node, results.network.chain_ids()[1].clone()).await;
+        results.hs_metrics = crate::stats::collect_hs_metrics(&hs_node).await;
+        results.node_health = Some(crate::stats::collect_network_health(&cl_node, &hs_node, &results.network, &primaries).await);
+        let timelines = crate::timeline::collect_transaction_timelines(&cl_node, &primaries, &hs_node, &results.recorded_workload).await;
+        results.latency_breakdown = crate::latency_breakdown::LatencyBreakdownReport::from_timelines(&timelines);
+        results.slowest_transactions = crate::slowest::SlowestTransactionsReport::slowest(timelines, crate::slowest::SLOWEST_TRANSACTIONS_LISTED);
+        results.hs_decision_latencies = crate::decision_latency::collect_hs_decision_latencies(&cl_node, &hs_node, &results.recorded_workload).await;
+        let failover_report = standbys.report(&results.hs_metrics);
+
+        // Shutdown nodes between runs to prevent memory leak
+        standbys.shutdown().await;
+        if run < num_runs {
+            logging::log("SIMULATOR", "Shutting down nodes between runs to clear state...");
+            hyperplane::hyper_ig::node::HyperIGNode::shutdown(hig_node_1.clone()).await;
+            hyperplane::hyper_ig::node::HyperIGNode::shutdown(hig_node_2.clone()).await;
+            hyperplane::confirmation_layer::node::ConfirmationLayerNode::shutdown(cl_node.clone()).await;
+            hyperplane::hyper_scheduler::node::HyperSchedulerNode::shutdown(hs_node.clone()).await;
+            logging::log("SIMULATOR", "Node shutdown complete");
+        }
+
+        // Save this run's results and failover report to its own directory
+        if let Err(e) = results.save_to_directory(&run_dir).await {
+            return Err(crate::config::ConfigError::ValidationError(format!(
+                "Failover simulation failed to save results for run {}/{}: {}", run, num_runs, e)));
+        }
+        let report_path = format!("{}/data/{}", run_dir, crate::failover::FAILOVER_FILE);
+        fs::write(&report_path, serde_json::to_string_pretty(&failover_report).unwrap())?;
+
+        logging::log("SIMULATOR", "\n=== Failover ===");
+        for line in failover_report.summary_lines() {
+            logging::log("SIMULATOR", &line);
+        }
+        logging::log("SIMULATOR", "==================");
+        if failover_report.has_double_proposals() {
+            runs_with_double_proposals += 1;
+            eprintln!("Warning: run {} delivered double proposals to the HS, see {}", run, report_path);
+        }
+
+        logging::log("SIMULATOR", &format!("=== Completed Run {}/{} ===", run, num_runs));
+    }
+
+    println!("Failover simulation complete");
+    logging::log("SIMULATOR", "=== Failover Simulation Complete ===");
+    if runs_with_double_proposals > 0 {
+        println!("{} of {} runs delivered double proposals to the HS", runs_with_double_proposals, num_runs);
+    }
+
+    Ok(())
+}
+
+/// Runs the failover simulation with automatic plotting
+pub async fn run_with_plotting() -> Result<(), crate::config::ConfigError> {
+    use crate::scenarios::utils::run_simulation_with_plotting;
+
+    run_simulation_with_plotting(
+        || run_failover_simulation(),
+        "Failover Simulation",
+        "simulator/src/scenarios/sim_failover/plot_results.py"
+    ).await
+}
+
+// ------------------------------------------------------------------------------------------------
+// Logging Setup
+// ------------------------------------------------------------------------------------------------
+
+/// Sets up logging with configuration
+fn setup_logging(config: &crate::config::Config) {
+    if config.logging_config.log_to_file {
+        let log_path = "simulator/results/sim_failover/simulation.log";
+        if let Err(e) = fs::remove_file(log_path) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                eprintln!("Error deleting log file: {}", e);
+            }
+        }
+        logging::init_logging_with_config(true, true, Some(log_path.to_string()));
+    } else {
+        logging::init_logging_with_config(false, false, None);
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Simulation Registration
+// ------------------------------------------------------------------------------------------------
+
+/// Register this simulation with the simulation registry.
+pub fn register() -> (crate::interface::SimulationType, crate::simulation_registry::SimulationConfig) {
+    use crate::interface::SimulationType;
+    use crate::simulation_registry::SimulationConfig;
+
+    (SimulationType::Failover, SimulationConfig {
+        name: "Failover Simulation",
+        run_fn: Box::new(|| Box::pin(async {
+            run_failover_simulation().await
+                .map_err(|e| format!("Failover simulation failed: {}", e))
+        })),
+        plot_script: "simulator/src/scenarios/sim_failover/plot_results.py",
+        config_schema: Some(ConfigSchema {
+            config_path: "simulator/src/scenarios/sim_failover/config.toml",
+            sections: &[crate::config_schema::COMMON_PARAMETERS, FAILOVER_PARAMETERS],
+        }),
+    })
+}
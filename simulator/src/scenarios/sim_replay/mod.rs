@@ -0,0 +1 @@
+pub mod simulation;
\ No newline at end of file
@@ -0,0 +1,337 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::time::Instant;
+
+use hyperplane::utils::logging;
+use serde::Deserialize;
+use crate::config_schema::{ConfigSchema, ParameterKind, ParameterSchema, parameter};
+use toml;
+use serde_json;
+
+// ------------------------------------------------------------------------------------------------
+// Configuration Loading
+// ------------------------------------------------------------------------------------------------
+
+/// Replay-specific configuration
+#[derive(Debug, Deserialize, Clone)]
+struct ReplayConfig {
+    /// Run directory of the recorded run (e.g. `simulator/results/sim_simple/data/sim_0/run_0`)
+    source_run_dir: String,
+    /// Configuration to build the nodes with (defaults to the config.toml copied next to the recorded run)
+    #[serde(default)]
+    source_config: Option<String>,
+    /// Whether to diff against a recorded run simulated with different parameters (e.g. with `source_config` set)
+    #[serde(default)]
+    allow_config_mismatch: bool,
+}
+
+/// Parameters of the replay config.toml
+const REPLAY_PARAMETERS: &[ParameterSchema] = &[
+    ParameterSchema { required: true, ..parameter("replay_config", ParameterKind::Table, "Replay parameters") },
+    ParameterSchema { required: true, ..parameter("replay_config.source_run_dir", ParameterKind::String, "Run directory of the recorded run") },
+    ParameterSchema { default: Some("config.toml next to the recorded run"), ..parameter("replay_config.source_config", ParameterKind::String, "Configuration to build the nodes with") },
+    ParameterSchema { default: Some("false"), ..parameter("replay_config.allow_config_mismatch", ParameterKind::Bool, "Diff against a recorded run with a different config hash") },
+];
+
+/// Layout of the replay config.toml
+#[derive(Debug, Deserialize, Clone)]
+struct ReplayScenarioConfig {
+    replay_config: ReplayConfig,
+}
+
+/// Loads the replay configuration and the configuration of the recorded run.
+///
+/// The recorded run's configuration is read from the config.toml that every scenario copies into
+/// its data directory, unless `source_config` points to another file.
+fn load_config() -> Result<(ReplayConfig, crate::config::Config), crate::config::ConfigError> {
+    let replay_str = fs::read_to_string("simulator/src/scenarios/sim_replay/config.toml")?;
+    let replay_config = toml::from_str::<ReplayScenarioConfig>(&replay_str)?.replay_config;
+
+    let source_config = replay_config.source_config.clone()
+        .unwrap_or_else(|| format!("{}/../../config.toml", replay_config.source_run_dir));
+    let config_str = fs::read_to_string(&source_config)?;
+    let config: crate::config::Config = toml::from_str(&config_str)?;
+    config.validate()?;
+    Ok((replay_config, config))
+}
+
+// ------------------------------------------------------------------------------------------------
+// Simulation Entry Point
+// ------------------------------------------------------------------------------------------------
+
+/// Runs the replay simulation
+///
+/// Feeds the submission schedule recorded in a previous run into freshly built nodes, so that
+/// protocol changes can be evaluated on identical inputs. The final values of all recorded metrics
+/// and the per-block state roots of the chains are diffed against the recorded run and written to
+/// `replay_diff.json`.
+pub async fn run_replay_simulation() -> Result<(), crate::config::ConfigError> {
+    // Create results directory if it doesn't exist
+    fs::create_dir_all("simulator/results/sim_replay/data").expect("Failed to create data directory");
+
+    // Load configuration and the recorded workload
+    let (replay_config, config) = load_config()?;
+    let workload = crate::workload::load_workload(&replay_config.source_run_dir)
+        .map_err(crate::config::ConfigError::ValidationError)?;
+
+    // Setup logging with configuration
+    setup_logging(&config);
+
+    println!("Replaying {} recorded transactions from {}", workload.len(), replay_config.source_run_dir);
+    logging::log("SIMULATOR", &format!("=== Replaying {} recorded transactions from {} ===", workload.len(), replay_config.source_run_dir));
+
+    // Initialize simulation results from configuration, keeping the seeds of the recorded run
+    let mut results = initialize_simulation_results(&config);
+    if let Some(recorded) = crate::metadata::RunMetadata::load(&format!("{}/data", replay_config.source_run_dir))
+        .map_err(crate::config::ConfigError::ValidationError)?
+    {
+        results.master_seed = recorded.master_seed;
+        results.run_seed = recorded.run_seed;
+    }
+
+    // Flush the partial results of the replay to the directory it is saved to
+    let run_dir = "simulator/results/sim_replay/data/sim_0/run_0";
+    results.flush_dir = Some(run_dir.to_string());
+
+    // Capture the logs of the replay in its directory, one file per component, if configured
+    let log_capture = crate::scenarios::utils::capture_run_logs(&config.logging_config, run_dir);
+
+    // Gate the links of the chains the fault timeline partitions
+    let partitions = crate::fault_orchestrator::network_partitions(&config.network_config.fault_timeline);
+
+    // Setup test nodes with preloaded accounts from config and apply the HS, CL and HIG settings
+    let (hs_node, cl_node, hig_node_1, hig_node_2) = crate::run_simulation::setup_configured_nodes(
+        &config,
+        &[0.0, 0.0], // Zero delays for funding
+        &partitions,
+        &results.network,
+    ).await;
+
+    // Run the replay
+    crate::run_simulation::run_simulation_with_replay(
+        cl_node.clone(),
+        vec![hig_node_1.clone(), hig_node_2.clone()],
+        partitions,
+        &mut results,
+        &workload,
+        Some("Replay".to_string()),
+    ).await.map_err(|e| crate::config::ConfigError::ValidationError(format!("Replay simulation failed: {}", e)))?;
+
+    // Record where the HS is still waiting on proposals, its metrics, the slowest transactions and the HS decision latencies
+    results.cats_awaiting_chain_1 = crate::stats::collect_pending_cats(&hs_node, results.network.chain_ids()[0].clone()).await;
+    results.cats_awaiting_chain_2 = crate::stats::collect_pending_cats(&hs_node, results.network.chain_ids()[1].clone()).await;
+    results.hs_metrics = crate::stats::collect_hs_metrics(&hs_node).await;
+    results.node_health = Some(crate::stats::collect_network_health(&cl_node, &hs_node, &results.network, &[hig_node_1.clone(), hig_node_2.clone()]).await);
+    let timelines = crate::timeline::collect_transaction_timelines(&cl_node, &[hig_node_1.clone(), hig_node_2.clone()], &hs_node, &results.recorded_workload).await;
+    results.latency_breakdown = crate::latency_breakdown::LatencyBreakdownReport::from_timelines(&timelines);
+    results.slowest_transactions = crate::slowest::SlowestTransactionsReport::slowest(timelines, crate::slowest::SLOWEST_TRANSACTIONS_LISTED);
+    results.hs_decision_latencies = crate::decision_latency::collect_hs_decision_latencies(&cl_node, &hs_node, &results.recorded_workload).await;
+
+    // Save the replayed run's results
+    results.save_to_directory(run_dir).await
+        .map_err(|e| crate::config::ConfigError::ValidationError(format!("Replay simulation failed to save results: {}", e)))?;
+    drop(log_capture);
+
+    // Diff the replayed run against the recorded run, unless their parameters differ unexpectedly
+    crate::metadata::check_comparable(&format!("{}/data", replay_config.source_run_dir), &format!("{}/data", run_dir), replay_config.allow_config_mismatch)
+        .map_err(crate::config::ConfigError::ValidationError)?;
+    let diff = diff_runs(&format!("{}/data", replay_config.source_run_dir), &format!("{}/data", run_dir))
+        .map_err(crate::config::ConfigError::ValidationError)?;
+    let changed: Vec<_> = diff.iter().filter(|(_, entry)| entry.delta != 0.0).collect();
+    // Runs recorded before state roots were saved have nothing to compare
+    let state_roots = crate::state_roots::compare_state_roots(&format!("{}/data", replay_config.source_run_dir), &format!("{}/data", run_dir))
+        .unwrap_or_else(|e| {
+            logging::log("SIMULATOR", &format!("Skipping state root comparison: {}", e));
+            Vec::new()
+        });
+    // Runs recorded before final states were saved have nothing to diff either
+    let final_states = crate::state_roots::compare_final_states(&format!("{}/data", replay_config.source_run_dir), &format!("{}/data", run_dir))
+        .unwrap_or_else(|e| {
+            logging::log("SIMULATOR", &format!("Skipping final state diff: {}", e));
+            Vec::new()
+        });
+
+    logging::log("SIMULATOR", "\n=== Replay Diff ===");
+    logging::log("SIMULATOR", &format!("Metrics compared: {}, changed: {}", diff.len(), changed.len()));
+    println!("Replay diff: {} metrics compared, {} changed", diff.len(), changed.len());
+    for (metric, entry) in &changed {
+        let line = format!("  {}: recorded={} replayed={} delta={:+}", metric, entry.recorded, entry.replayed, entry.delta);
+        logging::log("SIMULATOR", &line);
+        println!("{}", line);
+    }
+    for comparison in &state_roots {
+        let line = match comparison.first_divergent_block {
+            Some(block) => format!("  {}: roots diverge at block offset {} ({} blocks compared, final roots {})",
+                comparison.series, block, comparison.blocks_compared, if comparison.final_roots_match { "match" } else { "differ" }),
+            None => format!("  {}: roots identical ({} blocks compared)", comparison.series, comparison.blocks_compared),
+        };
+        logging::log("SIMULATOR", &line);
+        println!("{}", line);
+    }
+    for (chain, state_diff) in &final_states {
+        let line = format!("  {} final state: {}", chain, state_diff);
+        logging::log("SIMULATOR", &line);
+        println!("{}", line);
+    }
+    logging::log("SIMULATOR", "===================");
+
+    let diff_json = serde_json::json!({
+        "source_run_dir": replay_config.source_run_dir,
+        "state_roots": state_roots,
+        "final_states": final_states.iter().map(|(chain, state_diff)| {
+            serde_json::json!({
+                "chain": chain,
+                "accounts": state_diff.accounts
+            })
+        }).collect::<Vec<_>>(),
+        "metrics": diff.iter().map(|(metric, entry)| {
+            serde_json::json!({
+                "metric": metric,
+                "recorded": entry.recorded,
+                "replayed": entry.replayed,
+                "delta": entry.delta
+            })
+        }).collect::<Vec<_>>()
+    });
+    fs::write("simulator/results/sim_replay/data/replay_diff.json", serde_json::to_string_pretty(&diff_json).unwrap())
+        .expect("Failed to write replay_diff.json");
+
+    // Shutdown nodes
+    hyperplane::hyper_ig::node::HyperIGNode::shutdown(hig_node_1.clone()).await;
+    hyperplane::hyper_ig::node::HyperIGNode::shutdown(hig_node_2.clone()).await;
+    hyperplane::confirmation_layer::node::ConfirmationLayerNode::shutdown(cl_node.clone()).await;
+    hyperplane::hyper_scheduler::node::HyperSchedulerNode::shutdown(hs_node.clone()).await;
+
+    println!("Replay simulation complete");
+    logging::log("SIMULATOR", "=== Replay Simulation Complete ===");
+
+    Ok(())
+}
+
+// ------------------------------------------------------------------------------------------------
+// Result Diffing
+// ------------------------------------------------------------------------------------------------
+
+/// Final value of a metric in the recorded and the replayed run
+struct MetricDiff {
+    recorded: f64,
+    replayed: f64,
+    delta: f64,
+}
+
+/// Diffs the final values of all metrics that exist in both data directories
+fn diff_runs(recorded_dir: &str, replayed_dir: &str) -> Result<BTreeMap<String, MetricDiff>, String> {
+    let recorded = crate::report::final_metric_values(recorded_dir)?;
+    let replayed = crate::report::final_metric_values(replayed_dir)?;
+
+    Ok(recorded.into_iter()
+        .filter_map(|(metric, recorded)| {
+            replayed.get(&metric).map(|&replayed| (metric, MetricDiff { recorded, replayed, delta: replayed - recorded }))
+        })
+        .collect())
+}
+
+// ------------------------------------------------------------------------------------------------
+// Logging Setup
+// ------------------------------------------------------------------------------------------------
+
+/// Sets up logging with configuration
+fn setup_logging(config: &crate::config::Config) {
+    if config.logging_config.log_to_file {
+        let log_path = "simulator/results/sim_replay/simulation.log";
+        if let Err(e) = fs::remove_file(log_path) {
+            // Ignore error if file doesn't exist
+            if e.kind() != std::io::ErrorKind::NotFound {
+                eprintln!("Error deleting log file: {}", e);
+            }
+        }
+        logging::init_logging_with_config(true, true, Some(log_path.to_string()));
+    } else {
+        logging::init_logging_with_config(false, false, None);
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Results Initialization
+// ------------------------------------------------------------------------------------------------
+
+/// Initializes simulation results from the recorded run's configuration
+fn initialize_simulation_results(config: &crate::config::Config) -> crate::SimulationResults {
+    let mut results = crate::SimulationResults::default();
+    results.initial_balance = config.account_config.initial_balance.try_into().unwrap();
+    results.num_accounts = config.account_config.num_accounts.try_into().unwrap();
+    results.target_tpb = config.transaction_config.target_tpb as u64;
+    results.sim_total_block_number = config.simulation_config.sim_total_block_number.try_into().unwrap();
+    results.zipf_parameter = config.transaction_config.zipf_parameter;
+    results.ratio_cats = config.transaction_config.ratio_cats;
+    results.block_interval = config.network_config.block_interval;
+    results.network = config.network_config.network_spec().expect("The network is validated with the config");
+    results.cat_lifetime = config.transaction_config.cat_lifetime_blocks;
+    results.initialization_wait_blocks = config.simulation_config.initialization_wait_blocks;
+    results.chain_delays = config.network_config.chain_delays.clone();
+    results.chain_delay_distribution = config.network_config.chain_delay_distribution;
+    results.protocol_violation_policy = config.simulation_config.protocol_violation_policy;
+    results.export_parquet = config.simulation_config.export_parquet;
+    results.flush_interval_blocks = config.simulation_config.flush_interval_blocks;
+    results.watchdog_max_lag_blocks = config.simulation_config.watchdog_max_lag_blocks;
+    results.watchdog_queue_stall_secs = config.simulation_config.watchdog_queue_stall_secs;
+    results.watchdog_abort = config.simulation_config.watchdog_abort;
+    results.submission_batch_size = config.simulation_config.submission_batch_size;
+    results.submission_rate_limit_tps = config.simulation_config.submission_rate_limit_tps;
+    results.rate_anomaly_window_blocks = config.simulation_config.rate_anomaly_window_blocks;
+    results.rate_anomaly_sigma = config.simulation_config.rate_anomaly_sigma;
+    results.cat_fanout = config.transaction_config.cat_fanout.clone();
+    results.cat_chain_selection = config.transaction_config.cat_chain_selection;
+    results.hs_decision_latency = config.network_config.hs_decision_latency;
+    results.cl_block_ordering = config.network_config.cl_block_ordering;
+    results.cl_align_cat_parts = config.network_config.cl_align_cat_parts;
+    results.cl_block_max_transactions = config.network_config.cl_block_max_transactions;
+    results.cl_block_max_bytes = config.network_config.cl_block_max_bytes;
+    results.cl_block_interval_jitter = config.network_config.cl_block_interval_jitter;
+    results.hig_release_locks_on_failure = config.network_config.hig_release_locks_on_failure;
+    results.hig_prioritize_status_updates = config.network_config.hig_prioritize_status_updates;
+    results.hs_status_update_delays = config.network_config.hs_status_update_delays.clone();
+    results.cat_latency_multiplier = config.network_config.topology.cat_latency_multiplier;
+    results.regular_latency_multiplier = config.network_config.topology.regular_latency_multiplier;
+    results.hig_parameter_schedule = config.network_config.hig_parameter_schedule.clone();
+    results.fault_timeline = config.network_config.fault_timeline.clone();
+    results.invariants = config.simulation_config.invariants.clone();
+    results.cat_part_spread_blocks = config.transaction_config.cat_part_spread_blocks;
+    results.regular_tx_ttl_blocks = config.transaction_config.regular_tx_ttl_blocks;
+    results.cat_transfers = config.transaction_config.cat_transfers;
+    results.duplicate_ratio = config.transaction_config.duplicate_ratio;
+    results.duplicate_variant_share = config.transaction_config.duplicate_variant_share;
+    results.duplicate_delay_blocks = config.transaction_config.duplicate_delay_blocks;
+    results.malformed_ratio = config.transaction_config.malformed_ratio;
+    results.amount_distribution = config.transaction_config.amount_distribution;
+    results.chain_ratio_cats = config.transaction_config.chain_ratio_cats.clone();
+    results.chain_allow_cat_pending_dependencies = config.transaction_config.allow_cat_pending_dependencies_per_chain(2);
+    results.start_time = Instant::now();
+    results.started_at = chrono::Utc::now();
+    results
+}
+
+// ------------------------------------------------------------------------------------------------
+// Simulation Registration
+// ------------------------------------------------------------------------------------------------
+
+/// Register this simulation with the simulation registry.
+///
+/// The replay has no plot script; its output is the diff against the recorded run.
+pub fn register() -> (crate::interface::SimulationType, crate::simulation_registry::SimulationConfig) {
+    use crate::interface::SimulationType;
+    use crate::simulation_registry::SimulationConfig;
+
+    (SimulationType::Replay, SimulationConfig {
+        name: "Replay Recorded Run",
+        run_fn: Box::new(|| Box::pin(async {
+            run_replay_simulation().await
+                .map_err(|e| format!("Replay simulation failed: {}", e))
+        })),
+        plot_script: "",
+        config_schema: Some(ConfigSchema {
+            config_path: "simulator/src/scenarios/sim_replay/config.toml",
+            sections: &[REPLAY_PARAMETERS],
+        }),
+    })
+}
@@ -18,7 +18,7 @@ use serde_json;
 fn load_config() -> Result<crate::config::Config, crate::config::ConfigError> {
     let config_str = fs::read_to_string("simulator/src/scenarios/sim_simple/config.toml")?;
     let config: crate::config::Config = toml::from_str(&config_str)?;
-    config.validate()?;
+    config.validate_allowing_external_cl()?;
     Ok(config)
 }
 
@@ -79,27 +79,56 @@ pub async fn run_simple_simulation() -> Result<(), crate::config::ConfigError> {
     // Store results for all runs
     let mut all_results = Vec::new();
 
+    // Draw the master seed unless the experiment or the configuration fixes it
+    let master_seed = crate::metadata::master_seed(config.simulation_config.seed);
+    logging::log("SIMULATOR", &format!("Master seed: {}", master_seed));
+
     // Run the simulation multiple times
     for run in 1..=num_runs {
         logging::log("SIMULATOR", &format!("=== Starting Run {}/{} ===", run, num_runs));
         
         // Initialize simulation results from configuration
         let mut results = initialize_simulation_results(&config);
+        results.master_seed = master_seed;
+        results.run_seed = crate::metadata::run_seed(master_seed, run);
+
+        // Flush the partial results of the run to the directory it is saved to
+        let run_dir = format!("simulator/results/sim_simple/data/sim_0/run_{}", run - 1);
+        results.flush_dir = Some(run_dir.clone());
+
+        // Capture the logs of the run in its directory, one file per component, if configured
+        let _log_capture = crate::scenarios::utils::capture_run_logs(&config.logging_config, &run_dir);
+
+        // Run the workload against the CL of a separately running network instead of in-process nodes
+        if let Some(cl_endpoint) = &config.network_config.cl_endpoint {
+            let run_message = format!("Run {}/{}", run, num_runs);
+            crate::run_simulation::run_simulation_against_external_cl(cl_endpoint, &mut results, Some(run_message)).await
+                .map_err(|e| crate::config::ConfigError::ValidationError(format!(
+                    "Simple simulation failed during run {}/{} against the CL at {}: {}", run, num_runs, cl_endpoint, e
+                )))?;
+            results.save_to_directory(&run_dir).await
+                .map_err(|e| crate::config::ConfigError::ValidationError(format!(
+                    "Simple simulation failed to save results for run {}/{}: {}", run, num_runs, e
+                )))?;
+            all_results.push(results);
+            logging::log("SIMULATOR", &format!("=== Completed Run {}/{} ===", run, num_runs));
+            continue;
+        }
 
         logging::log("SIMULATOR", "Setting up test nodes with preloaded accounts...");
-        // Setup test nodes with preloaded accounts from config
-        let (hs_node, cl_node, hig_node_1, hig_node_2, _start_block_height) = crate::testnodes::setup_test_nodes(
-            Duration::from_secs_f64(config.network_config.block_interval),
+        // Gate the links of the chains the fault timeline partitions
+        let partitions = crate::fault_orchestrator::network_partitions(&config.network_config.fault_timeline);
+
+        // Setup test nodes with preloaded accounts from config and apply the HS, CL and HIG settings
+        let (hs_node, cl_node, hig_node_1, hig_node_2) = crate::run_simulation::setup_configured_nodes(
+            &config,
             &[0.0, 0.0], // Zero delays for funding
-            config.transaction_config.allow_cat_pending_dependencies,
-            config.transaction_config.cat_lifetime_blocks,
-            config.account_config.num_accounts.try_into().unwrap(), // Preload accounts from config
-            config.account_config.initial_balance.try_into().unwrap(), // Preload value from config
-            config.network_config.channel_buffer_size, // Channel buffer size from config
+            &partitions,
+            &results.network,
         ).await;
         
-        logging::log("SIMULATOR", &format!("Test nodes setup complete with {} accounts preloaded with {} tokens each", 
-            config.account_config.num_accounts, config.account_config.initial_balance));
+        logging::log("SIMULATOR", &format!("Test nodes setup complete with {} preloaded accounts per chain", 
+            config.account_config.chain_balances(2).iter().map(|balances| balances.len().to_string()).collect::<Vec<_>>().join("/")));
         
         // Query and log account balances to verify preloading
         logging::log("SIMULATOR", "=== Verifying Preloaded Account Balances ===");
@@ -150,6 +179,7 @@ pub async fn run_simple_simulation() -> Result<(), crate::config::ConfigError> {
         let simulation_result = crate::run_simulation::run_simulation_with_message_and_retries(
             cl_node.clone(),
             vec![hig_node_1.clone(), hig_node_2.clone()],
+            partitions,
             &mut results,
             Some(run_message),
             None, // No retry count needed
@@ -164,6 +194,16 @@ pub async fn run_simple_simulation() -> Result<(), crate::config::ConfigError> {
             return Err(crate::config::ConfigError::ValidationError(error_context));
         }
 
+        // Record where the HS is still waiting on proposals, its metrics, the slowest transactions and the HS decision latencies before the nodes are shut down
+        results.cats_awaiting_chain_1 = crate::stats::collect_pending_cats(&hs_node, results.network.chain_ids()[0].clone()).await;
+        results.cats_awaiting_chain_2 = crate::stats::collect_pending_cats(&hs_node, results.network.chain_ids()[1].clone()).await;
+        results.hs_metrics = crate::stats::collect_hs_metrics(&hs_node).await;
+        results.node_health = Some(crate::stats::collect_network_health(&cl_node, &hs_node, &results.network, &[hig_node_1.clone(), hig_node_2.clone()]).await);
+        let timelines = crate::timeline::collect_transaction_timelines(&cl_node, &[hig_node_1.clone(), hig_node_2.clone()], &hs_node, &results.recorded_workload).await;
+        results.latency_breakdown = crate::latency_breakdown::LatencyBreakdownReport::from_timelines(&timelines);
+        results.slowest_transactions = crate::slowest::SlowestTransactionsReport::slowest(timelines, crate::slowest::SLOWEST_TRANSACTIONS_LISTED);
+        results.hs_decision_latencies = crate::decision_latency::collect_hs_decision_latencies(&cl_node, &hs_node, &results.recorded_workload).await;
+
         // Shutdown nodes between runs to prevent memory leak
         if run < num_runs {
             logging::log("SIMULATOR", "Shutting down nodes between runs to clear state...");
@@ -182,7 +222,6 @@ pub async fn run_simple_simulation() -> Result<(), crate::config::ConfigError> {
         }
 
         // Save this run's results to its own directory
-        let run_dir = format!("simulator/results/sim_simple/data/sim_0/run_{}", run - 1);
         let save_result = results.save_to_directory(&run_dir).await;
         
         if let Err(e) = save_result {
@@ -217,9 +256,16 @@ pub async fn run_simple_simulation() -> Result<(), crate::config::ConfigError> {
 }
 
 /// Runs the simple simulation with automatic plotting
+///
+/// A run against an external CL is not plotted, as the plots need the HIG and HS series it does not record.
 pub async fn run_with_plotting() -> Result<(), crate::config::ConfigError> {
     use crate::scenarios::utils::run_simulation_with_plotting;
     
+    if let Some(cl_endpoint) = load_config()?.network_config.cl_endpoint {
+        logging::log("PLOT", &format!("Skipping the plots of the simple simulation, which ran against the CL at {} without HIG and HS series", cl_endpoint));
+        return run_simple_simulation().await;
+    }
+
     run_simulation_with_plotting(
         || run_simple_simulation(),
         "Simple Simulation",
@@ -264,7 +310,7 @@ fn setup_logging(config: &crate::config::Config) {
 // ------------------------------------------------------------------------------------------------
 
 /// Initializes simulation results from configuration
-fn initialize_simulation_results(config: &crate::config::Config) -> crate::SimulationResults {
+pub(crate) fn initialize_simulation_results(config: &crate::config::Config) -> crate::SimulationResults {
     let mut results = crate::SimulationResults::default();
     results.initial_balance = config.account_config.initial_balance.try_into().unwrap();
     results.num_accounts = config.account_config.num_accounts.try_into().unwrap();
@@ -273,10 +319,50 @@ fn initialize_simulation_results(config: &crate::config::Config) -> crate::Simul
     results.zipf_parameter = config.transaction_config.zipf_parameter;
     results.ratio_cats = config.transaction_config.ratio_cats;
     results.block_interval = config.network_config.block_interval;
+    results.network = config.network_config.network_spec().expect("The network is validated with the config");
     results.cat_lifetime = config.transaction_config.cat_lifetime_blocks;
     results.initialization_wait_blocks = config.simulation_config.initialization_wait_blocks;
     results.chain_delays = config.network_config.chain_delays.clone();
+    results.chain_delay_distribution = config.network_config.chain_delay_distribution;
+    results.protocol_violation_policy = config.simulation_config.protocol_violation_policy;
+    results.export_parquet = config.simulation_config.export_parquet;
+    results.flush_interval_blocks = config.simulation_config.flush_interval_blocks;
+    results.cl_endpoint = config.network_config.cl_endpoint.clone();
+    results.watchdog_max_lag_blocks = config.simulation_config.watchdog_max_lag_blocks;
+    results.watchdog_queue_stall_secs = config.simulation_config.watchdog_queue_stall_secs;
+    results.watchdog_abort = config.simulation_config.watchdog_abort;
+    results.submission_batch_size = config.simulation_config.submission_batch_size;
+    results.submission_rate_limit_tps = config.simulation_config.submission_rate_limit_tps;
+    results.rate_anomaly_window_blocks = config.simulation_config.rate_anomaly_window_blocks;
+    results.rate_anomaly_sigma = config.simulation_config.rate_anomaly_sigma;
+    results.cat_fanout = config.transaction_config.cat_fanout.clone();
+    results.cat_chain_selection = config.transaction_config.cat_chain_selection;
+    results.hs_decision_latency = config.network_config.hs_decision_latency;
+    results.cl_block_ordering = config.network_config.cl_block_ordering;
+    results.cl_align_cat_parts = config.network_config.cl_align_cat_parts;
+    results.cl_block_max_transactions = config.network_config.cl_block_max_transactions;
+    results.cl_block_max_bytes = config.network_config.cl_block_max_bytes;
+    results.cl_block_interval_jitter = config.network_config.cl_block_interval_jitter;
+    results.hig_release_locks_on_failure = config.network_config.hig_release_locks_on_failure;
+    results.hig_prioritize_status_updates = config.network_config.hig_prioritize_status_updates;
+    results.hs_status_update_delays = config.network_config.hs_status_update_delays.clone();
+    results.cat_latency_multiplier = config.network_config.topology.cat_latency_multiplier;
+    results.regular_latency_multiplier = config.network_config.topology.regular_latency_multiplier;
+    results.hig_parameter_schedule = config.network_config.hig_parameter_schedule.clone();
+    results.fault_timeline = config.network_config.fault_timeline.clone();
+    results.invariants = config.simulation_config.invariants.clone();
+    results.cat_part_spread_blocks = config.transaction_config.cat_part_spread_blocks;
+    results.regular_tx_ttl_blocks = config.transaction_config.regular_tx_ttl_blocks;
+    results.cat_transfers = config.transaction_config.cat_transfers;
+    results.duplicate_ratio = config.transaction_config.duplicate_ratio;
+    results.duplicate_variant_share = config.transaction_config.duplicate_variant_share;
+    results.duplicate_delay_blocks = config.transaction_config.duplicate_delay_blocks;
+    results.malformed_ratio = config.transaction_config.malformed_ratio;
+    results.amount_distribution = config.transaction_config.amount_distribution;
+    results.chain_ratio_cats = config.transaction_config.chain_ratio_cats.clone();
+    results.chain_allow_cat_pending_dependencies = config.transaction_config.allow_cat_pending_dependencies_per_chain(2);
     results.start_time = Instant::now();
+    results.started_at = chrono::Utc::now();
 
     // Log configuration
     let start_time = Local::now();
@@ -318,5 +404,9 @@ pub fn register() -> (crate::interface::SimulationType, crate::simulation_regist
                 .map_err(|e| format!("Simple simulation failed: {}", e))
         })),
         plot_script: "simulator/src/scenarios/sim_simple/plot_results.py",
+        config_schema: Some(crate::config_schema::ConfigSchema {
+            config_path: "simulator/src/scenarios/sim_simple/config.toml",
+            sections: &[crate::config_schema::COMMON_PARAMETERS],
+        }),
     })
 } 
\ No newline at end of file
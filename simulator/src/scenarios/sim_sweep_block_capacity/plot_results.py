@@ -0,0 +1,108 @@
+#!/usr/bin/env python3
+"""
+Plotting script for CL Block Capacity Sweep Simulation
+
+This script generates the generic sweep plots and, in addition, the latency of each
+simulation against the mean utilization of its CL blocks. The curves are written to
+data/latency_vs_utilization.json and figs/latency_vs_utilization.png.
+
+Usage:
+    python plot_results.py
+"""
+
+import sys
+import os
+import json
+
+import matplotlib.pyplot as plt
+
+# Add the scripts directory to the Python path to import plot_utils
+sys.path.insert(0, os.path.join(os.path.dirname(os.path.abspath(__file__)), '..'))
+from plot_utils import generate_all_plots
+
+
+def mean_series(data_dir: str, filename: str, key: str) -> float:
+    """Return the mean of a time series file, or 0 if it is missing or empty."""
+    path = os.path.join(data_dir, filename)
+    if not os.path.exists(path):
+        return 0.0
+    with open(path, 'r') as f:
+        series = json.load(f).get(key, [])
+    return sum(float(entry['count']) for entry in series) / len(series) if series else 0.0
+
+
+def build_curves(results_dir: str) -> list:
+    """Collect the block utilization and the latencies of every simulation from its run_average folder."""
+    with open(f'{results_dir}/data/metadata.json', 'r') as f:
+        metadata = json.load(f)
+
+    curves = []
+    for sim_index, block_capacity in enumerate(metadata['parameter_values']):
+        data_dir = f'{results_dir}/data/sim_{sim_index}/run_average'
+        stats_path = os.path.join(data_dir, 'simulation_stats.json')
+        if not os.path.exists(stats_path):
+            print(f"Warning: No averaged data found for simulation {sim_index}")
+            continue
+        with open(stats_path, 'r') as f:
+            results = json.load(f)['results']
+        curves.append({
+            'block_capacity': block_capacity,
+            'mean_block_utilization': results.get('mean_block_utilization') or 0.0,
+            'regular_tx_avg_latency_ms': results.get('regular_tx_avg_latency_ms') or 0.0,
+            'mean_pending_cat_age_ms': results.get('mean_pending_cat_age_ms') or 0.0,
+            'mempool_age_p50_blocks': (mean_series(data_dir, 'chain_1_mempool_age_p50.json', 'chain_1_mempool_age_p50')
+                                       + mean_series(data_dir, 'chain_2_mempool_age_p50.json', 'chain_2_mempool_age_p50')) / 2.0,
+        })
+    return sorted(curves, key=lambda entry: entry['mean_block_utilization'])
+
+
+def plot_curves(curves: list, results_dir: str) -> None:
+    """Plot the latencies against the mean block utilization, labelling each point with its capacity."""
+    utilization = [entry['mean_block_utilization'] * 100.0 for entry in curves]
+
+    fig, (ax_latency, ax_mempool) = plt.subplots(1, 2, figsize=(14, 6))
+    ax_latency.plot(utilization, [entry['regular_tx_avg_latency_ms'] for entry in curves], 'o-', label='Regular transactions')
+    ax_latency.plot(utilization, [entry['mean_pending_cat_age_ms'] for entry in curves], 's-', label='CATs (pending at the HS)')
+    ax_mempool.plot(utilization, [entry['mempool_age_p50_blocks'] for entry in curves], 'o-', color='tab:red')
+    for entry, x in zip(curves, utilization):
+        ax_mempool.annotate(str(entry['block_capacity']), (x, entry['mempool_age_p50_blocks']),
+                            textcoords='offset points', xytext=(0, 6), ha='center', fontsize=8)
+
+    ax_latency.set_ylabel('Mean Latency (ms)')
+    ax_latency.set_title('Latency vs Block Utilization')
+    ax_latency.legend()
+    ax_mempool.set_ylabel('Median Mempool Age (blocks)')
+    ax_mempool.set_title('Mempool Age vs Block Utilization (labels: block capacity)')
+    for ax in (ax_latency, ax_mempool):
+        ax.set_xlabel('Mean Block Utilization (%)')
+        ax.grid(True, alpha=0.3)
+
+    plt.tight_layout()
+    plt.savefig(f'{results_dir}/figs/latency_vs_utilization.png', dpi=300, bbox_inches='tight')
+    plt.close()
+
+
+def main():
+    """Main function to generate plots for the CL block capacity sweep simulation."""
+    param_name = 'block_capacity'
+    results_dir = 'simulator/results/sim_sweep_block_capacity'
+    sweep_type = 'Block Capacity'
+
+    # Generate all plots using the generic utility (this also averages the runs)
+    generate_all_plots(results_dir, param_name, sweep_type)
+
+    curves = build_curves(results_dir)
+    if not curves:
+        print("No data found for Block Capacity simulation. Skipping latency vs utilization plot.")
+        return
+
+    with open(f'{results_dir}/data/latency_vs_utilization.json', 'w') as f:
+        json.dump({'latency_vs_utilization': curves}, f, indent=2)
+
+    os.makedirs(f'{results_dir}/figs', exist_ok=True)
+    plot_curves(curves, results_dir)
+    print(f"Latency vs utilization written to {results_dir}/data/latency_vs_utilization.json and {results_dir}/figs/")
+
+
+if __name__ == "__main__":
+    main()
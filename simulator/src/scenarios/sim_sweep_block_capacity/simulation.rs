@@ -0,0 +1,155 @@
+use crate::scenarios::sweep_runner::{SweepRunner, create_modified_config, generate_u64_sequence};
+use crate::define_sweep_config;
+use crate::config::ValidateConfig;
+use crate::scenarios::utils::run_simulation_with_plotting;
+use serde::Deserialize;
+use crate::config_schema::{ConfigSchema, ParameterKind, ParameterSchema, COMMON_PARAMETERS, NUM_SIMULATIONS, sweep_parameter};
+
+// ------------------------------------------------------------------------------------------------
+// Sweep-Specific Parameter Struct
+// ------------------------------------------------------------------------------------------------
+
+/// Parameters specific to the CL block capacity sweep simulation.
+/// 
+/// This struct defines the parameters used to control the block capacity sweep.
+/// It contains only the parameters relevant to this specific sweep type.
+#[derive(Debug, Deserialize, Clone)]
+pub struct BlockCapacitySweepParameters {
+    /// Total number of simulation runs in the sweep (determines how many parameter values to test)
+    pub num_simulations: usize,
+    /// Step size for the block capacity (in CL transactions per block)
+    pub block_capacity_step: u64,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Sweep Configuration
+// ------------------------------------------------------------------------------------------------
+
+// Defines the sweep configuration for CL block capacity simulations.
+// 
+// This macro generates a complete sweep configuration setup including:
+// - A config struct with standard fields (network_config, account_config, transaction_config, sweep)
+// - Standard validation logic for common fields
+// - SweepConfigTrait implementation for integration with the generic SweepRunner
+// - A load_config() function that reads and validates the TOML configuration file
+define_sweep_config!(
+    "sim_sweep_block_capacity",
+    SweepBlockCapacityConfig,
+    validate_sweep_specific = |self_: &Self| {
+        // The sweep starts from a limited block, as an unlimited one has no utilization
+        if self_.network_config.cl_block_max_transactions == 0 {
+            return Err(crate::config::ConfigError::ValidationError("cl_block_max_transactions must be positive for the block capacity sweep".into()));
+        }
+        // Need block_capacity_step to generate the sequence of block capacities to test
+        if self_.simulation_config.block_capacity_step.unwrap_or(0) == 0 {
+            return Err(crate::config::ConfigError::ValidationError("Block capacity step must be positive".into()));
+        }
+        Ok(())
+    }
+);
+
+/// Parameters of the CL block capacity sweep
+const SWEEP_PARAMETERS: &[ParameterSchema] = &[
+    NUM_SIMULATIONS,
+    ParameterSchema { min: Some(1.0), ..sweep_parameter("simulation_config.block_capacity_step", ParameterKind::Integer, "Increment of the CL block capacity in CL transactions per block") },
+];
+
+// ------------------------------------------------------------------------------------------------
+// Simulation Runner
+// ------------------------------------------------------------------------------------------------
+
+/// Runs the sweep CL block capacity simulation
+/// 
+/// This simulation explores how the capacity of CL blocks affects latency. With a capacity
+/// below the offered load, transactions queue in the CL mempool and wait for later blocks;
+/// with a capacity above it, blocks are only partly used.
+/// 
+/// The sweep varies the maximum number of CL transactions per block starting from the
+/// configured cl_block_max_transactions value. The plotting script reports the latency of
+/// each simulation against the mean utilization of its blocks.
+pub async fn run_sweep_block_capacity_simulation() -> Result<(), crate::config::ConfigError> {
+    // Load sweep configuration to get parameter values
+    let sweep_config = load_config()?;
+    
+    // Calculate block capacities for each simulation using the helper function
+    // Creates a sequence starting from cl_block_max_transactions and stepping by block_capacity_step
+    let block_capacities = generate_u64_sequence(
+        sweep_config.network_config.cl_block_max_transactions,  // Start from the configured block capacity
+        sweep_config.simulation_config.block_capacity_step.unwrap(),
+        sweep_config.simulation_config.num_simulations.unwrap_or(1)
+    );
+
+    // Create the generic sweep runner that handles all the common functionality
+    let runner = SweepRunner::new(
+        "Block Capacity",              // Human-readable name for logging
+        "sim_sweep_block_capacity",    // Directory name for results
+        "block_capacity",              // Parameter name for JSON output
+        block_capacities,              // List of parameter values to test
+        // Function to load the sweep configuration
+        Box::new(|| {
+            load_config().map(|config| Box::new(config) as Box<dyn crate::scenarios::sweep_runner::SweepConfigTrait>)
+        }),
+        // Function to create a modified config for each simulation using the helper
+        Box::new(|sweep_config, block_capacity| {
+            create_modified_config(sweep_config, |base_config| {
+                crate::config::Config {
+                    network_config: crate::config::NetworkConfig {
+                        cl_block_max_transactions: block_capacity,  // This is the parameter we're varying
+                        ..base_config.network_config.clone()
+                    },
+                    account_config: base_config.account_config.clone(),
+                    transaction_config: base_config.transaction_config.clone(),
+                    simulation_config: base_config.simulation_config.clone(),
+                    logging_config: base_config.logging_config.clone(),
+                }
+            })
+        }),
+        // Function to save the combined results from all simulations
+        // Note: The latency and utilization curves are computed by the plotting code from the averaged runs
+        Box::new(|_results_dir, _all_results| {
+            Ok(())
+        }),
+    );
+
+    // Run the sweep - this handles all the simulation execution, logging, and result saving
+    runner.run().await
+}
+
+// ------------------------------------------------------------------------------------------------
+// Simulation Registration
+// ------------------------------------------------------------------------------------------------
+
+/// Register this simulation with the simulation registry.
+/// 
+/// This function provides the configuration needed to register the block capacity sweep
+/// with the main simulation registry.
+pub fn register() -> (crate::interface::SimulationType, crate::simulation_registry::SimulationConfig) {
+    use crate::interface::SimulationType;
+    use crate::simulation_registry::SimulationConfig;
+    
+    (SimulationType::SweepBlockCapacity, SimulationConfig {
+        name: "Block Capacity Sweep",
+        run_fn: Box::new(|| Box::pin(async {
+            run_sweep_block_capacity_simulation().await
+                .map_err(|e| format!("Block capacity sweep failed: {}", e))
+        })),
+        plot_script: "simulator/src/scenarios/sim_sweep_block_capacity/plot_results.py",
+        config_schema: Some(ConfigSchema {
+            config_path: "simulator/src/scenarios/sim_sweep_block_capacity/config.toml",
+            sections: &[COMMON_PARAMETERS, SWEEP_PARAMETERS],
+        }),
+    })
+}
+
+// ------------------------------------------------------------------------------------------------
+// Run with Plotting
+// ------------------------------------------------------------------------------------------------
+
+/// Runs the block capacity sweep simulation with automatic plotting.
+pub async fn run_with_plotting() -> Result<(), crate::config::ConfigError> {
+    run_simulation_with_plotting(
+        || run_sweep_block_capacity_simulation(),
+        "Block Capacity Sweep",
+        "simulator/src/scenarios/sim_sweep_block_capacity/plot_results.py"
+    ).await
+}
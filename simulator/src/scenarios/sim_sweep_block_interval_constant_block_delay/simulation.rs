@@ -3,6 +3,7 @@ use crate::define_sweep_config;
 use crate::config::ValidateConfig;
 use crate::scenarios::utils::run_simulation_with_plotting;
 use serde::Deserialize;
+use crate::config_schema::{ConfigSchema, ParameterKind, ParameterSchema, COMMON_PARAMETERS, NUM_SIMULATIONS, sweep_parameter};
 
 // ------------------------------------------------------------------------------------------------
 // Sweep-Specific Parameter Struct
@@ -43,6 +44,12 @@ define_sweep_config!(
     }
 );
 
+/// Parameters of the block interval sweep with constant block delays
+const SWEEP_PARAMETERS: &[ParameterSchema] = &[
+    NUM_SIMULATIONS,
+    ParameterSchema { min: Some(0.0), ..sweep_parameter("simulation_config.block_interval_step", ParameterKind::Float, "Increment of the block interval in seconds (must be positive)") },
+];
+
 // ------------------------------------------------------------------------------------------------
 // Simulation Runner
 // ------------------------------------------------------------------------------------------------
@@ -90,8 +97,22 @@ pub async fn run_sweep_block_interval_constant_block_delay() -> Result<(), crate
                             base_config.network_config.chain_delays[0],  // Keep first chain delay unchanged
                             base_config.network_config.chain_delays[1],  // Use the second chain delay for constant block delay
                         ],
+                        chain_delay_distribution: base_config.network_config.chain_delay_distribution,
                         block_interval: block_interval,                        // Apply the varied block interval
                         channel_buffer_size: base_config.network_config.channel_buffer_size,
+                        hs_decision_latency: base_config.network_config.hs_decision_latency,
+                        topology: base_config.network_config.topology.clone(),
+                        clock_skew: base_config.network_config.clock_skew.clone(),
+                        cl_block_ordering: base_config.network_config.cl_block_ordering,
+                        cl_align_cat_parts: base_config.network_config.cl_align_cat_parts,
+                        cl_block_max_transactions: base_config.network_config.cl_block_max_transactions,
+                        cl_block_max_bytes: base_config.network_config.cl_block_max_bytes,
+                        cl_block_interval_jitter: base_config.network_config.cl_block_interval_jitter,
+                        hig_release_locks_on_failure: base_config.network_config.hig_release_locks_on_failure,
+                        hig_prioritize_status_updates: base_config.network_config.hig_prioritize_status_updates,
+                        hs_status_update_delays: base_config.network_config.hs_status_update_delays.clone(),
+                        hig_parameter_schedule: base_config.network_config.hig_parameter_schedule.clone(),
+                        fault_timeline: base_config.network_config.fault_timeline.clone(),
                     },
                     account_config: base_config.account_config.clone(),
                     transaction_config: base_config.transaction_config.clone(),
@@ -130,6 +151,10 @@ pub fn register() -> (crate::interface::SimulationType, crate::simulation_regist
                 .map_err(|e| format!("Block interval constant block delay sweep failed: {}", e))
         })),
         plot_script: "simulator/src/scenarios/sim_sweep_block_interval_constant_block_delay/plot_results.py",
+        config_schema: Some(ConfigSchema {
+            config_path: "simulator/src/scenarios/sim_sweep_block_interval_constant_block_delay/config.toml",
+            sections: &[COMMON_PARAMETERS, SWEEP_PARAMETERS],
+        }),
     })
 }
 
@@ -3,6 +3,7 @@ use crate::define_sweep_config;
 use crate::config::ValidateConfig;
 use crate::scenarios::utils::run_simulation_with_plotting;
 use serde::Deserialize;
+use crate::config_schema::{ConfigSchema, ParameterKind, ParameterSchema, COMMON_PARAMETERS, NUM_SIMULATIONS, sweep_parameter};
 
 // ------------------------------------------------------------------------------------------------
 // Sweep-Specific Parameter Struct
@@ -49,6 +50,13 @@ define_sweep_config!(
     }
 );
 
+/// Parameters of the block interval sweep with constant time delays
+const SWEEP_PARAMETERS: &[ParameterSchema] = &[
+    NUM_SIMULATIONS,
+    ParameterSchema { min: Some(0.0), ..sweep_parameter("simulation_config.block_interval_step", ParameterKind::Float, "Increment of the block interval in seconds (must be positive)") },
+    ParameterSchema { min: Some(0.0), ..sweep_parameter("simulation_config.reference_chain_delay_duration", ParameterKind::Float, "Chain delay in seconds kept constant over the sweep (must be positive)") },
+];
+
 // ------------------------------------------------------------------------------------------------
 // Simulation Runner
 // ------------------------------------------------------------------------------------------------
@@ -111,8 +119,22 @@ pub async fn run_sweep_block_interval_constant_time_delay() -> Result<(), crate:
                             base_config.network_config.chain_delays[0],  // Keep first chain delay unchanged
                             delay_blocks as f64,  // Set second chain delay to maintain constant value
                         ],
+                        chain_delay_distribution: base_config.network_config.chain_delay_distribution,
                         block_interval: block_interval,  // Apply the varied block interval
                         channel_buffer_size: base_config.network_config.channel_buffer_size,
+                        hs_decision_latency: base_config.network_config.hs_decision_latency,
+                        topology: base_config.network_config.topology.clone(),
+                        clock_skew: base_config.network_config.clock_skew.clone(),
+                        cl_block_ordering: base_config.network_config.cl_block_ordering,
+                        cl_align_cat_parts: base_config.network_config.cl_align_cat_parts,
+                        cl_block_max_transactions: base_config.network_config.cl_block_max_transactions,
+                        cl_block_max_bytes: base_config.network_config.cl_block_max_bytes,
+                        cl_block_interval_jitter: base_config.network_config.cl_block_interval_jitter,
+                        hig_release_locks_on_failure: base_config.network_config.hig_release_locks_on_failure,
+                        hig_prioritize_status_updates: base_config.network_config.hig_prioritize_status_updates,
+                        hs_status_update_delays: base_config.network_config.hs_status_update_delays.clone(),
+                        hig_parameter_schedule: base_config.network_config.hig_parameter_schedule.clone(),
+                        fault_timeline: base_config.network_config.fault_timeline.clone(),
                     },
                     account_config: base_config.account_config.clone(),
                     transaction_config: crate::config::TransactionConfig {
@@ -121,6 +143,18 @@ pub async fn run_sweep_block_interval_constant_time_delay() -> Result<(), crate:
                         ratio_cats: base_config.transaction_config.ratio_cats,
                         cat_lifetime_blocks: base_config.transaction_config.cat_lifetime_blocks,
                         allow_cat_pending_dependencies: base_config.transaction_config.allow_cat_pending_dependencies,
+                        cat_fanout: base_config.transaction_config.cat_fanout.clone(),
+                        cat_chain_selection: base_config.transaction_config.cat_chain_selection,
+                        cat_part_spread_blocks: base_config.transaction_config.cat_part_spread_blocks,
+                        regular_tx_ttl_blocks: base_config.transaction_config.regular_tx_ttl_blocks,
+                        cat_transfers: base_config.transaction_config.cat_transfers,
+                        duplicate_ratio: base_config.transaction_config.duplicate_ratio,
+                        duplicate_variant_share: base_config.transaction_config.duplicate_variant_share,
+                        duplicate_delay_blocks: base_config.transaction_config.duplicate_delay_blocks,
+                        malformed_ratio: base_config.transaction_config.malformed_ratio,
+                        amount_distribution: base_config.transaction_config.amount_distribution,
+                        chain_ratio_cats: base_config.transaction_config.chain_ratio_cats.clone(),
+                        chain_allow_cat_pending_dependencies: base_config.transaction_config.chain_allow_cat_pending_dependencies.clone(),
                     },
                     simulation_config: base_config.simulation_config.clone(),
                     logging_config: base_config.logging_config.clone(),
@@ -157,6 +191,10 @@ pub fn register() -> (crate::interface::SimulationType, crate::simulation_regist
                 .map_err(|e| format!("Block interval constant time delay sweep failed: {}", e))
         })),
         plot_script: "simulator/src/scenarios/sim_sweep_block_interval_constant_time_delay/plot_results.py",
+        config_schema: Some(ConfigSchema {
+            config_path: "simulator/src/scenarios/sim_sweep_block_interval_constant_time_delay/config.toml",
+            sections: &[COMMON_PARAMETERS, SWEEP_PARAMETERS],
+        }),
     })
 }
 
@@ -2,6 +2,7 @@ use crate::scenarios::sweep_runner::{SweepRunner, create_modified_config, genera
 use crate::define_sweep_config;
 use crate::config::ValidateConfig;
 use crate::scenarios::utils::run_simulation_with_plotting;
+use crate::config_schema::{ConfigSchema, ParameterKind, ParameterSchema, COMMON_PARAMETERS, NUM_SIMULATIONS, sweep_parameter};
 
 // ------------------------------------------------------------------------------------------------
 // Sweep Configuration
@@ -26,6 +27,12 @@ define_sweep_config!(
     }
 );
 
+/// Parameters of the CAT lifetime sweep
+const SWEEP_PARAMETERS: &[ParameterSchema] = &[
+    NUM_SIMULATIONS,
+    ParameterSchema { min: Some(1.0), ..sweep_parameter("simulation_config.cat_lifetime_step", ParameterKind::Integer, "Increment of the CAT lifetime in blocks") },
+];
+
 // ------------------------------------------------------------------------------------------------
 // Simulation Runner
 // ------------------------------------------------------------------------------------------------
@@ -76,6 +83,18 @@ pub async fn run_sweep_cat_lifetime_simulation() -> Result<(), crate::config::Co
                         ratio_cats: base_config.transaction_config.ratio_cats,
                         cat_lifetime_blocks: cat_lifetime,  // This is the parameter we're varying
                         allow_cat_pending_dependencies: base_config.transaction_config.allow_cat_pending_dependencies,
+                        cat_fanout: base_config.transaction_config.cat_fanout.clone(),
+                        cat_chain_selection: base_config.transaction_config.cat_chain_selection,
+                        cat_part_spread_blocks: base_config.transaction_config.cat_part_spread_blocks,
+                        regular_tx_ttl_blocks: base_config.transaction_config.regular_tx_ttl_blocks,
+                        cat_transfers: base_config.transaction_config.cat_transfers,
+                        duplicate_ratio: base_config.transaction_config.duplicate_ratio,
+                        duplicate_variant_share: base_config.transaction_config.duplicate_variant_share,
+                        duplicate_delay_blocks: base_config.transaction_config.duplicate_delay_blocks,
+                        malformed_ratio: base_config.transaction_config.malformed_ratio,
+                        amount_distribution: base_config.transaction_config.amount_distribution,
+                        chain_ratio_cats: base_config.transaction_config.chain_ratio_cats.clone(),
+                        chain_allow_cat_pending_dependencies: base_config.transaction_config.chain_allow_cat_pending_dependencies.clone(),
                     },
                     simulation_config: base_config.simulation_config.clone(),
                     logging_config: base_config.logging_config.clone(),
@@ -112,6 +131,10 @@ pub fn register() -> (crate::interface::SimulationType, crate::simulation_regist
                 .map_err(|e| format!("CAT lifetime sweep failed: {}", e))
         })),
         plot_script: "simulator/src/scenarios/sim_sweep_cat_lifetime/plot_results.py",
+        config_schema: Some(ConfigSchema {
+            config_path: "simulator/src/scenarios/sim_sweep_cat_lifetime/config.toml",
+            sections: &[COMMON_PARAMETERS, SWEEP_PARAMETERS],
+        }),
     })
 }
 
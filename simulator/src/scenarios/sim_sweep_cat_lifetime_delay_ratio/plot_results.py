@@ -0,0 +1,107 @@
+#!/usr/bin/env python3
+"""
+Plotting script for CAT Lifetime / Chain Delay Ratio Sweep Simulation
+
+This script averages the runs of every grid point and reports the CAT timeout
+rate surface over (CAT lifetime, delay ratio). The surface is written to
+data/timeout_rate_surface.json and plotted as a heatmap.
+
+Usage:
+    python plot_results.py
+"""
+
+import sys
+import os
+import json
+
+import numpy as np
+import matplotlib.pyplot as plt
+
+# Add the simulator source directory to the Python path to import the averaging script
+sys.path.insert(0, os.path.join(os.path.dirname(os.path.abspath(__file__)), '..', '..'))
+from average_runs import create_averaged_data
+
+
+def last_count(data_dir: str, filename: str, key: str) -> float:
+    """Return the final count of a time series file, or 0 if it is missing or empty."""
+    path = os.path.join(data_dir, filename)
+    if not os.path.exists(path):
+        return 0.0
+    with open(path, 'r') as f:
+        series = json.load(f).get(key, [])
+    return float(series[-1]['count']) if series else 0.0
+
+
+def compute_timeout_rate(data_dir: str) -> dict:
+    """Compute the share of CAT sub-transactions that timed out across both chains."""
+    timed_out = 0.0
+    total = 0.0
+    for chain in (1, 2):
+        timed_out += last_count(data_dir, f'cat_timed_out_transactions_chain_{chain}.json', f'chain_{chain}_cat_timed_out')
+        for status in ('success', 'failure', 'pending'):
+            total += last_count(data_dir, f'cat_{status}_transactions_chain_{chain}.json', f'chain_{chain}_cat_{status}')
+    rate = timed_out / total if total > 0 else 0.0
+    return {'cat_timed_out': timed_out, 'cat_total': total, 'timeout_rate': rate}
+
+
+def build_surface(results_dir: str) -> list:
+    """Build the timeout rate surface from the run_average folder of every grid point."""
+    with open(f'{results_dir}/data/metadata.json', 'r') as f:
+        metadata = json.load(f)
+
+    surface = []
+    for sim_index, point in enumerate(metadata['parameter_values']):
+        data_dir = f'{results_dir}/data/sim_{sim_index}/run_average'
+        if not os.path.exists(data_dir):
+            print(f"Warning: No averaged data found for simulation {sim_index}")
+            continue
+        surface.append({**point, **compute_timeout_rate(data_dir)})
+    return surface
+
+
+def plot_surface(surface: list, results_dir: str) -> None:
+    """Plot the timeout rate surface as a heatmap over CAT lifetime and delay ratio."""
+    lifetimes = sorted({entry['cat_lifetime'] for entry in surface})
+    ratios = sorted({entry['delay_ratio'] for entry in surface})
+    grid = np.full((len(lifetimes), len(ratios)), np.nan)
+    for entry in surface:
+        grid[lifetimes.index(entry['cat_lifetime']), ratios.index(entry['delay_ratio'])] = entry['timeout_rate'] * 100.0
+
+    fig, ax = plt.subplots(figsize=(10, 6))
+    image = ax.imshow(grid, origin='lower', aspect='auto', cmap='viridis')
+    ax.set_xticks(range(len(ratios)))
+    ax.set_xticklabels([f'{ratio:g}' for ratio in ratios])
+    ax.set_yticks(range(len(lifetimes)))
+    ax.set_yticklabels([str(lifetime) for lifetime in lifetimes])
+    ax.set_xlabel('Chain Delay / CAT Lifetime')
+    ax.set_ylabel('CAT Lifetime (blocks)')
+    ax.set_title('CAT Timeout Rate (%)')
+    fig.colorbar(image, ax=ax, label='Timed out CATs (%)')
+    plt.tight_layout()
+    plt.savefig(f'{results_dir}/figs/timeout_rate_surface.png', dpi=300, bbox_inches='tight')
+    plt.close()
+
+
+def main():
+    """Main function to generate the timeout rate surface for the CAT lifetime / delay ratio sweep."""
+    results_dir = 'simulator/results/sim_sweep_cat_lifetime_delay_ratio'
+
+    if not create_averaged_data(results_dir):
+        print("Averaging failed!")
+        return
+
+    surface = build_surface(results_dir)
+    if not surface:
+        print("No data found for CAT Lifetime / Delay Ratio simulation. Skipping plot generation.")
+        return
+
+    with open(f'{results_dir}/data/timeout_rate_surface.json', 'w') as f:
+        json.dump({'timeout_rate_surface': surface}, f, indent=2)
+
+    os.makedirs(f'{results_dir}/figs', exist_ok=True)
+    plot_surface(surface, results_dir)
+    print(f"Timeout rate surface written to {results_dir}/data/timeout_rate_surface.json and {results_dir}/figs/")
+
+
+if __name__ == "__main__":
+    main()
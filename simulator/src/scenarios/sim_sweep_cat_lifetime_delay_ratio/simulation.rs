@@ -0,0 +1,226 @@
+use crate::scenarios::sweep_runner::{SweepRunner, create_modified_config, generate_u64_sequence};
+use crate::define_sweep_config;
+use crate::config::ValidateConfig;
+use crate::scenarios::utils::run_simulation_with_plotting;
+use serde::Serialize;
+use crate::config_schema::{ConfigSchema, ParameterKind, ParameterSchema, COMMON_PARAMETERS, NUM_SIMULATIONS, sweep_parameter};
+
+// ------------------------------------------------------------------------------------------------
+// Sweep Configuration
+// ------------------------------------------------------------------------------------------------
+
+// Defines the sweep configuration for CAT lifetime / chain delay ratio simulations.
+//
+// This macro generates a complete sweep configuration setup including:
+// - A config struct with standard fields (network_config, account_config, transaction_config, simulation_config)
+// - Standard validation logic for common fields
+// - SweepConfigTrait implementation for integration with the generic SweepRunner
+// - A load_config() function that reads and validates the TOML configuration file
+define_sweep_config!(
+    "sim_sweep_cat_lifetime_delay_ratio",
+    SweepCatLifetimeDelayRatioConfig,
+    validate_sweep_specific = |self_: &Self| {
+        // Need cat_lifetime_step to generate the sequence of CAT lifetimes to test
+        if self_.simulation_config.cat_lifetime_step.unwrap_or(0) == 0 {
+            return Err(crate::config::ConfigError::ValidationError("CAT lifetime step must be positive".into()));
+        }
+        // Need the ratios to derive the chain delay for each CAT lifetime
+        let ratios = self_.simulation_config.delay_lifetime_ratios.as_deref().unwrap_or(&[]);
+        if ratios.is_empty() {
+            return Err(crate::config::ConfigError::ValidationError("Delay lifetime ratios must not be empty".into()));
+        }
+        if ratios.iter().any(|ratio| !ratio.is_finite() || *ratio < 0.0) {
+            return Err(crate::config::ConfigError::ValidationError("Delay lifetime ratios must be non-negative".into()));
+        }
+        // The delay is applied to the second chain
+        if self_.network_config.num_chains < 2 {
+            return Err(crate::config::ConfigError::ValidationError("CAT lifetime / delay ratio sweep requires at least 2 chains".into()));
+        }
+        Ok(())
+    }
+);
+
+/// Parameters of the CAT lifetime to chain delay ratio sweep
+const SWEEP_PARAMETERS: &[ParameterSchema] = &[
+    NUM_SIMULATIONS,
+    ParameterSchema { min: Some(1.0), ..sweep_parameter("simulation_config.cat_lifetime_step", ParameterKind::Integer, "Increment of the CAT lifetime in blocks") },
+    ParameterSchema { min: Some(0.0), ..sweep_parameter("simulation_config.delay_lifetime_ratios", ParameterKind::FloatList, "Ratios of chain delay to CAT lifetime") },
+];
+
+// ------------------------------------------------------------------------------------------------
+// Sweep Parameter
+// ------------------------------------------------------------------------------------------------
+
+/// A single point of the CAT lifetime / chain delay grid.
+#[derive(Debug, Clone, Serialize)]
+pub struct LifetimeDelayPoint {
+    /// CAT lifetime in blocks
+    pub cat_lifetime: u64,
+    /// Ratio of the chain delay to the CAT lifetime
+    pub delay_ratio: f64,
+    /// Resulting delay of the second chain in blocks
+    pub chain_delay: f64,
+}
+
+/// Builds the sweep grid from the CAT lifetimes and delay ratios.
+///
+/// Points are ordered by CAT lifetime first, then by ratio, so consecutive
+/// simulations share the same lifetime.
+///
+/// # Arguments
+/// * `cat_lifetimes` - The CAT lifetimes to test (in blocks)
+/// * `ratios` - The chain delay to CAT lifetime ratios to test
+///
+/// # Returns
+/// The list of grid points, one per simulation
+pub fn build_grid(cat_lifetimes: &[u64], ratios: &[f64]) -> Vec<LifetimeDelayPoint> {
+    cat_lifetimes.iter()
+        .flat_map(|&cat_lifetime| ratios.iter().map(move |&delay_ratio| LifetimeDelayPoint {
+            cat_lifetime,
+            delay_ratio,
+            chain_delay: cat_lifetime as f64 * delay_ratio,
+        }))
+        .collect()
+}
+
+// ------------------------------------------------------------------------------------------------
+// Simulation Runner
+// ------------------------------------------------------------------------------------------------
+
+/// Runs the sweep CAT lifetime / chain delay ratio simulation
+///
+/// This simulation explores the boundary where the delay of a chain approaches
+/// the CAT lifetime. Instead of varying a single parameter, it varies the CAT
+/// lifetime and the delay of the second chain together, with the delay expressed
+/// as a ratio of the lifetime.
+///
+/// Each CAT lifetime in the sequence is combined with every configured ratio, so
+/// the sweep runs num_simulations x len(delay_lifetime_ratios) simulations. The
+/// plotting script reports the resulting CAT timeout rate surface.
+pub async fn run_sweep_cat_lifetime_delay_ratio_simulation() -> Result<(), crate::config::ConfigError> {
+    // Load sweep configuration to get parameter values
+    let sweep_config = load_config()?;
+
+    // Calculate CAT lifetimes starting from cat_lifetime_blocks and stepping by cat_lifetime_step
+    let cat_lifetimes = generate_u64_sequence(
+        sweep_config.transaction_config.cat_lifetime_blocks,
+        sweep_config.simulation_config.cat_lifetime_step.unwrap(),
+        sweep_config.simulation_config.num_simulations.unwrap_or(1)
+    );
+
+    // Combine every CAT lifetime with every delay ratio
+    let grid = build_grid(
+        &cat_lifetimes,
+        sweep_config.simulation_config.delay_lifetime_ratios.as_deref().unwrap()
+    );
+
+    // Create the generic sweep runner that handles all the common functionality
+    let runner = SweepRunner::new(
+        "CAT Lifetime / Delay Ratio",          // Human-readable name for logging
+        "sim_sweep_cat_lifetime_delay_ratio",  // Directory name for results
+        "lifetime_delay_point",                // Parameter name for JSON output
+        grid,                                  // List of parameter values to test
+        // Function to load the sweep configuration
+        Box::new(|| {
+            load_config().map(|config| Box::new(config) as Box<dyn crate::scenarios::sweep_runner::SweepConfigTrait>)
+        }),
+        // Function to create a modified config for each simulation using the helper
+        Box::new(|sweep_config, point| {
+            create_modified_config(sweep_config, |base_config| {
+                let mut chain_delays = base_config.network_config.chain_delays.clone();
+                chain_delays[1] = point.chain_delay;  // Apply delay to second chain in blocks
+                crate::config::Config {
+                    network_config: crate::config::NetworkConfig {
+                        num_chains: base_config.network_config.num_chains,
+                        chain_delays,
+                        chain_delay_distribution: base_config.network_config.chain_delay_distribution,
+                        block_interval: base_config.network_config.block_interval,
+                        channel_buffer_size: base_config.network_config.channel_buffer_size,
+                        hs_decision_latency: base_config.network_config.hs_decision_latency,
+                        topology: base_config.network_config.topology.clone(),
+                        clock_skew: base_config.network_config.clock_skew.clone(),
+                        cl_block_ordering: base_config.network_config.cl_block_ordering,
+                        cl_align_cat_parts: base_config.network_config.cl_align_cat_parts,
+                        cl_block_max_transactions: base_config.network_config.cl_block_max_transactions,
+                        cl_block_max_bytes: base_config.network_config.cl_block_max_bytes,
+                        cl_block_interval_jitter: base_config.network_config.cl_block_interval_jitter,
+                        hig_release_locks_on_failure: base_config.network_config.hig_release_locks_on_failure,
+                        hig_prioritize_status_updates: base_config.network_config.hig_prioritize_status_updates,
+                        hs_status_update_delays: base_config.network_config.hs_status_update_delays.clone(),
+                        hig_parameter_schedule: base_config.network_config.hig_parameter_schedule.clone(),
+                        fault_timeline: base_config.network_config.fault_timeline.clone(),
+                    },
+                    account_config: base_config.account_config.clone(),
+                    transaction_config: crate::config::TransactionConfig {
+                        target_tpb: base_config.transaction_config.target_tpb,
+                        zipf_parameter: base_config.transaction_config.zipf_parameter,
+                        ratio_cats: base_config.transaction_config.ratio_cats,
+                        cat_lifetime_blocks: point.cat_lifetime,  // Varied together with the chain delay
+                        allow_cat_pending_dependencies: base_config.transaction_config.allow_cat_pending_dependencies,
+                        cat_fanout: base_config.transaction_config.cat_fanout.clone(),
+                        cat_chain_selection: base_config.transaction_config.cat_chain_selection,
+                        cat_part_spread_blocks: base_config.transaction_config.cat_part_spread_blocks,
+                        regular_tx_ttl_blocks: base_config.transaction_config.regular_tx_ttl_blocks,
+                        cat_transfers: base_config.transaction_config.cat_transfers,
+                        duplicate_ratio: base_config.transaction_config.duplicate_ratio,
+                        duplicate_variant_share: base_config.transaction_config.duplicate_variant_share,
+                        duplicate_delay_blocks: base_config.transaction_config.duplicate_delay_blocks,
+                        malformed_ratio: base_config.transaction_config.malformed_ratio,
+                        amount_distribution: base_config.transaction_config.amount_distribution,
+                        chain_ratio_cats: base_config.transaction_config.chain_ratio_cats.clone(),
+                        chain_allow_cat_pending_dependencies: base_config.transaction_config.chain_allow_cat_pending_dependencies.clone(),
+                    },
+                    simulation_config: base_config.simulation_config.clone(),
+                    logging_config: base_config.logging_config.clone(),
+                }
+            })
+        }),
+        // Function to save the combined results from all simulations
+        // Note: The timeout rate surface is computed by the plotting code from the averaged runs
+        Box::new(|_results_dir, _all_results| {
+            Ok(())
+        }),
+    );
+
+    // Run the sweep - this handles all the simulation execution, logging, and result saving
+    runner.run().await
+}
+
+// ------------------------------------------------------------------------------------------------
+// Simulation Registration
+// ------------------------------------------------------------------------------------------------
+
+/// Register this simulation with the simulation registry.
+///
+/// This function provides the configuration needed to register the CAT lifetime / delay
+/// ratio sweep with the main simulation registry.
+pub fn register() -> (crate::interface::SimulationType, crate::simulation_registry::SimulationConfig) {
+    use crate::interface::SimulationType;
+    use crate::simulation_registry::SimulationConfig;
+
+    (SimulationType::SweepCatLifetimeDelayRatio, SimulationConfig {
+        name: "CAT Lifetime / Delay Ratio Sweep",
+        run_fn: Box::new(|| Box::pin(async {
+            run_sweep_cat_lifetime_delay_ratio_simulation().await
+                .map_err(|e| format!("CAT lifetime / delay ratio sweep failed: {}", e))
+        })),
+        plot_script: "simulator/src/scenarios/sim_sweep_cat_lifetime_delay_ratio/plot_results.py",
+        config_schema: Some(ConfigSchema {
+            config_path: "simulator/src/scenarios/sim_sweep_cat_lifetime_delay_ratio/config.toml",
+            sections: &[COMMON_PARAMETERS, SWEEP_PARAMETERS],
+        }),
+    })
+}
+
+// ------------------------------------------------------------------------------------------------
+// Run with Plotting
+// ------------------------------------------------------------------------------------------------
+
+/// Runs the CAT lifetime / delay ratio sweep simulation with automatic plotting.
+pub async fn run_with_plotting() -> Result<(), crate::config::ConfigError> {
+    run_simulation_with_plotting(
+        || run_sweep_cat_lifetime_delay_ratio_simulation(),
+        "CAT Lifetime / Delay Ratio Sweep",
+        "simulator/src/scenarios/sim_sweep_cat_lifetime_delay_ratio/plot_results.py"
+    ).await
+}
@@ -3,6 +3,7 @@ use crate::define_sweep_config;
 use crate::config::ValidateConfig;
 use crate::scenarios::utils::run_simulation_with_plotting;
 use serde::Deserialize;
+use crate::config_schema::{ConfigSchema, ParameterSchema, COMMON_PARAMETERS, NUM_SIMULATIONS};
 
 // ------------------------------------------------------------------------------------------------
 // Sweep-Specific Parameter Struct
@@ -41,6 +42,11 @@ define_sweep_config!(
     }
 );
 
+/// Parameters of the CAT pending dependencies sweep
+const SWEEP_PARAMETERS: &[ParameterSchema] = &[
+    ParameterSchema { min: Some(2.0), max: Some(2.0), ..NUM_SIMULATIONS },
+];
+
 // ------------------------------------------------------------------------------------------------
 // Simulation Runner
 // ------------------------------------------------------------------------------------------------
@@ -88,6 +94,18 @@ pub async fn run_sweep_cat_pending_dependencies_simulation() -> Result<(), crate
                         ratio_cats: base_config.transaction_config.ratio_cats,
                         cat_lifetime_blocks: base_config.transaction_config.cat_lifetime_blocks,
                         allow_cat_pending_dependencies: allow_cat_pending_dependencies,  // This is the parameter we're varying
+                        cat_fanout: base_config.transaction_config.cat_fanout.clone(),
+                        cat_chain_selection: base_config.transaction_config.cat_chain_selection,
+                        cat_part_spread_blocks: base_config.transaction_config.cat_part_spread_blocks,
+                        regular_tx_ttl_blocks: base_config.transaction_config.regular_tx_ttl_blocks,
+                        cat_transfers: base_config.transaction_config.cat_transfers,
+                        duplicate_ratio: base_config.transaction_config.duplicate_ratio,
+                        duplicate_variant_share: base_config.transaction_config.duplicate_variant_share,
+                        duplicate_delay_blocks: base_config.transaction_config.duplicate_delay_blocks,
+                        malformed_ratio: base_config.transaction_config.malformed_ratio,
+                        amount_distribution: base_config.transaction_config.amount_distribution,
+                        chain_ratio_cats: base_config.transaction_config.chain_ratio_cats.clone(),
+                        chain_allow_cat_pending_dependencies: Vec::new(),  // The swept value applies to every chain
                     },
                     simulation_config: base_config.simulation_config.clone(),
                     logging_config: base_config.logging_config.clone(),
@@ -124,6 +142,10 @@ pub fn register() -> (crate::interface::SimulationType, crate::simulation_regist
                 .map_err(|e| format!("CAT pending dependencies sweep failed: {}", e))
         })),
         plot_script: "simulator/src/scenarios/sim_sweep_cat_pending_dependencies/plot_results.py",
+        config_schema: Some(ConfigSchema {
+            config_path: "simulator/src/scenarios/sim_sweep_cat_pending_dependencies/config.toml",
+            sections: &[COMMON_PARAMETERS, SWEEP_PARAMETERS],
+        }),
     })
 }
 
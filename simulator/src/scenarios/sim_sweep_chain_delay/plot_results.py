@@ -8,6 +8,7 @@ plotting utilities to eliminate code duplication.
 
 import sys
 import os
+import json
 
 # Add the scripts directory to the Python path to import plot_utils
 sys.path.insert(0, os.path.join(os.path.dirname(os.path.abspath(__file__)), '..'))
@@ -16,9 +17,15 @@ from plot_utils import generate_all_plots
 def main():
     """Main function to generate plots for chain delay sweep simulation."""
     # Configuration for this specific sweep
-    param_name = 'chain_delay'
     results_dir = 'simulator/results/sim_sweep_chain_delay'
-    sweep_type = 'Chain Delay'
+    
+    # The sweep varies either the chain delay or the spread of the chain delay distribution
+    param_name = 'chain_delay'
+    metadata_file = f'{results_dir}/data/metadata.json'
+    if os.path.exists(metadata_file):
+        with open(metadata_file, 'r') as f:
+            param_name = json.load(f).get('parameter_name', param_name)
+    sweep_type = 'Chain Delay Spread' if param_name == 'chain_delay_spread' else 'Chain Delay'
     
     # Generate all plots using the generic utility
     # Data flow: run_average folders -> sweep_results_averaged.json -> plots
@@ -3,6 +3,8 @@ use crate::define_sweep_config;
 use crate::config::ValidateConfig;
 use crate::scenarios::utils::run_simulation_with_plotting;
 use serde::Deserialize;
+use hyperplane::hyper_ig::DelayDistribution;
+use crate::config_schema::{ConfigSchema, ParameterKind, ParameterSchema, COMMON_PARAMETERS, NUM_SIMULATIONS, parameter, sweep_parameter};
 
 // ------------------------------------------------------------------------------------------------
 // Sweep-Specific Parameter Struct
@@ -18,6 +20,31 @@ pub struct ChainDelaySweepParameters {
     pub num_simulations: usize,
     /// Step size for chain delay sweeps (in blocks, affects inter-chain communication timing)
     pub chain_delay_step: f64,
+    /// Step size of the spread of the chain delay distribution (sweeps the spread instead of the delay if set)
+    pub chain_delay_spread_step: Option<f64>,
+}
+
+/// Returns the parameter that controls the spread of a chain delay distribution
+///
+/// This is the standard deviation (in blocks) of a normal distribution, the sigma of a log-normal
+/// distribution and the tail probability of a bimodal distribution. A constant delay has no spread.
+pub fn delay_spread(distribution: &DelayDistribution) -> Option<f64> {
+    match *distribution {
+        DelayDistribution::Constant => None,
+        DelayDistribution::Normal { std_dev } => Some(std_dev),
+        DelayDistribution::LogNormal { sigma } => Some(sigma),
+        DelayDistribution::Bimodal { tail_probability, .. } => Some(tail_probability),
+    }
+}
+
+/// Returns the distribution with its spread parameter (see `delay_spread`) set to `spread`
+pub fn with_delay_spread(distribution: &DelayDistribution, spread: f64) -> DelayDistribution {
+    match *distribution {
+        DelayDistribution::Constant => DelayDistribution::Constant,
+        DelayDistribution::Normal { .. } => DelayDistribution::Normal { std_dev: spread },
+        DelayDistribution::LogNormal { .. } => DelayDistribution::LogNormal { sigma: spread },
+        DelayDistribution::Bimodal { tail_factor, .. } => DelayDistribution::Bimodal { tail_probability: spread, tail_factor },
+    }
 }
 
 // ------------------------------------------------------------------------------------------------
@@ -35,6 +62,16 @@ define_sweep_config!(
     "sim_sweep_chain_delay",
     SweepChainDelayConfig,
     validate_sweep_specific = |self_: &Self| {
+        // A spread sweep needs a distribution with a spread and a step to vary it by
+        if let Some(spread_step) = self_.simulation_config.chain_delay_spread_step {
+            if spread_step == 0.0 {
+                return Err(crate::config::ConfigError::ValidationError("Chain delay spread step must not be zero".into()));
+            }
+            if delay_spread(&self_.network_config.chain_delay_distribution).is_none() {
+                return Err(crate::config::ConfigError::ValidationError("A chain delay spread sweep needs a normal, lognormal or bimodal chain_delay_distribution".into()));
+            }
+            return Ok(());
+        }
         // Need chain_delay_step to generate the sequence of chain delays to test
         if self_.simulation_config.chain_delay_step.unwrap_or(0.0) == 0.0 {
             return Err(crate::config::ConfigError::ValidationError("Chain delay step must be positive".into()));
@@ -43,6 +80,13 @@ define_sweep_config!(
     }
 );
 
+/// Parameters of the chain delay sweep
+const SWEEP_PARAMETERS: &[ParameterSchema] = &[
+    NUM_SIMULATIONS,
+    sweep_parameter("simulation_config.chain_delay_step", ParameterKind::Float, "Increment of the chain delay in blocks (must not be zero)"),
+    parameter("simulation_config.chain_delay_spread_step", ParameterKind::Float, "Increment of the spread of the chain delay distribution; sweeps the spread instead of the delay if set"),
+];
+
 // ------------------------------------------------------------------------------------------------
 // Simulation Runner
 // ------------------------------------------------------------------------------------------------
@@ -55,34 +99,50 @@ define_sweep_config!(
 /// The sweep varies the delay of the second chain (HIG to HS) starting from
 /// the configured chain_delays[1] value, running multiple simulations to understand 
 /// how it affects transaction throughput, success rates, and overall system behavior.
+/// 
+/// If `chain_delay_spread_step` is set, the sweep keeps the chain delays and varies the spread
+/// of the chain delay distribution instead (see `delay_spread`), since tail delays drive timeouts.
 pub async fn run_sweep_chain_delay() -> Result<(), crate::config::ConfigError> {
     // Load sweep configuration to get parameter values
     // This reads the sweep settings from config_sweep_chain_delay.toml
     let sweep_config = load_config()?;
+    let spread_step = sweep_config.simulation_config.chain_delay_spread_step;
     
-    // Calculate chain delays for each simulation using the helper function
-    // Creates a sequence starting from the configured chain_delays[1] value
-    // Each value represents the delay from the HIG to HS in blocks
-    let chain_delays = generate_f64_sequence(
-        sweep_config.network_config.chain_delays[1],  // Start at configured chain_delays[1] value
-        sweep_config.simulation_config.chain_delay_step.unwrap(),
-        sweep_config.simulation_config.num_simulations.unwrap()
-    );
+    // Calculate the swept values for each simulation using the helper function
+    // Creates a sequence starting from the configured chain_delays[1] value (in blocks)
+    // or from the configured spread of the chain delay distribution
+    let (parameter_name, parameter_values) = match spread_step {
+        Some(spread_step) => ("chain_delay_spread", generate_f64_sequence(
+            delay_spread(&sweep_config.network_config.chain_delay_distribution).unwrap_or(0.0),
+            spread_step,
+            sweep_config.simulation_config.num_simulations.unwrap()
+        )),
+        None => ("chain_delay", generate_f64_sequence(
+            sweep_config.network_config.chain_delays[1],  // Start at configured chain_delays[1] value
+            sweep_config.simulation_config.chain_delay_step.unwrap(),
+            sweep_config.simulation_config.num_simulations.unwrap()
+        )),
+    };
 
     // Create the generic sweep runner that handles all the common functionality
     // This eliminates code duplication across different sweep types
     let runner = SweepRunner::new(
         "Chain Delay",                 // Human-readable name for logging
         "sim_sweep_chain_delay",       // Directory name for results
-        "chain_delay",                 // Parameter name for JSON output
-        chain_delays,                  // List of parameter values to test
+        parameter_name,                // Parameter name for JSON output
+        parameter_values,              // List of parameter values to test
         // Function to load the sweep configuration
         Box::new(|| {
             load_config().map(|config| Box::new(config) as Box<dyn crate::scenarios::sweep_runner::SweepConfigTrait>)
         }),
         // Function to create a modified config for each simulation using the helper
-        Box::new(|sweep_config, chain_delay| {
+        Box::new(move |sweep_config, value| {
             create_modified_config(sweep_config, |base_config| {
+                let base_delay_distribution = base_config.network_config.chain_delay_distribution;
+                let (chain_delay, chain_delay_distribution) = match spread_step {
+                    Some(_) => (base_config.network_config.chain_delays[1], with_delay_spread(&base_delay_distribution, value)),
+                    None => (value, base_delay_distribution),
+                };
                 crate::config::Config {
                     network_config: crate::config::NetworkConfig {
                         num_chains: base_config.network_config.num_chains,
@@ -90,8 +150,22 @@ pub async fn run_sweep_chain_delay() -> Result<(), crate::config::ConfigError> {
                             base_config.network_config.chain_delays[0],  // Keep first chain delay unchanged
                             chain_delay,                     // Apply delay to second chain in blocks
                         ],
+                        chain_delay_distribution,
                         block_interval: base_config.network_config.block_interval,
                         channel_buffer_size: base_config.network_config.channel_buffer_size,
+                        hs_decision_latency: base_config.network_config.hs_decision_latency,
+                        topology: base_config.network_config.topology.clone(),
+                        clock_skew: base_config.network_config.clock_skew.clone(),
+                        cl_block_ordering: base_config.network_config.cl_block_ordering,
+                        cl_align_cat_parts: base_config.network_config.cl_align_cat_parts,
+                        cl_block_max_transactions: base_config.network_config.cl_block_max_transactions,
+                        cl_block_max_bytes: base_config.network_config.cl_block_max_bytes,
+                        cl_block_interval_jitter: base_config.network_config.cl_block_interval_jitter,
+                        hig_release_locks_on_failure: base_config.network_config.hig_release_locks_on_failure,
+                        hig_prioritize_status_updates: base_config.network_config.hig_prioritize_status_updates,
+                        hs_status_update_delays: base_config.network_config.hs_status_update_delays.clone(),
+                        hig_parameter_schedule: base_config.network_config.hig_parameter_schedule.clone(),
+                        fault_timeline: base_config.network_config.fault_timeline.clone(),
                     },
                     account_config: base_config.account_config.clone(),
                     transaction_config: base_config.transaction_config.clone(),
@@ -130,6 +204,10 @@ pub fn register() -> (crate::interface::SimulationType, crate::simulation_regist
                 .map_err(|e| format!("Chain delay sweep failed: {}", e))
         })),
         plot_script: "simulator/src/scenarios/sim_sweep_chain_delay/plot_results.py",
+        config_schema: Some(ConfigSchema {
+            config_path: "simulator/src/scenarios/sim_sweep_chain_delay/config.toml",
+            sections: &[COMMON_PARAMETERS, SWEEP_PARAMETERS],
+        }),
     })
 }
 
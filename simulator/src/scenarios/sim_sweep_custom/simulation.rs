@@ -0,0 +1,273 @@
+use std::fs;
+
+use serde::{Deserialize, Serialize};
+use toml;
+
+use crate::config_schema::{ConfigSchema, ParameterKind, ParameterSchema, common_parameter, parameter};
+use crate::scenarios::sweep_runner::{SweepConfigTrait, SweepRunner};
+
+// ------------------------------------------------------------------------------------------------
+// Sweepable Parameters
+// ------------------------------------------------------------------------------------------------
+
+/// Path of the custom sweep's configuration file, written by the sweep wizard
+pub const CONFIG_PATH: &str = "simulator/src/scenarios/sim_sweep_custom/config.toml";
+
+/// Common parameters the custom sweep can vary (their types and ranges are declared in
+/// `crate::config_schema::COMMON_PARAMETERS`)
+pub const SWEEPABLE_PARAMETERS: &[&str] = &[
+    "network_config.block_interval",
+    "network_config.channel_buffer_size",
+    "network_config.topology.cat_latency_multiplier",
+    "network_config.topology.regular_latency_multiplier",
+    "account_config.initial_balance",
+    "account_config.num_accounts",
+    "transaction_config.target_tpb",
+    "transaction_config.zipf_parameter",
+    "transaction_config.ratio_cats",
+    "transaction_config.cat_lifetime_blocks",
+    "transaction_config.cat_part_spread_blocks",
+    "transaction_config.regular_tx_ttl_blocks",
+    "transaction_config.duplicate_ratio",
+    "transaction_config.malformed_ratio",
+    "transaction_config.amount_distribution.amount",
+    "transaction_config.amount_distribution.max",
+    "transaction_config.amount_distribution.mean",
+    "transaction_config.amount_distribution.sigma",
+    "simulation_config.sim_total_block_number",
+    "simulation_config.submission_batch_size",
+    "simulation_config.submission_rate_limit_tps",
+];
+
+/// Looks up the declaration of a sweepable parameter by its path
+pub fn sweepable_parameter(path: &str) -> Option<&'static ParameterSchema> {
+    if SWEEPABLE_PARAMETERS.contains(&path) { common_parameter(path) } else { None }
+}
+
+/// Parameters of the custom sweep's config.toml
+const CUSTOM_SWEEP_PARAMETERS: &[ParameterSchema] = &[
+    ParameterSchema { required: true, ..parameter("custom_sweep", ParameterKind::Table, "Custom sweep parameters") },
+    ParameterSchema { required: true, ..parameter("custom_sweep.parameter", ParameterKind::String, "The swept parameter, as <table>.<field>") },
+    ParameterSchema { required: true, ..parameter("custom_sweep.start", ParameterKind::Float, "Value of the first simulation") },
+    ParameterSchema { required: true, ..parameter("custom_sweep.step", ParameterKind::Float, "Increment between simulations") },
+    ParameterSchema { required: true, min: Some(1.0), ..parameter("custom_sweep.num_simulations", ParameterKind::Integer, "Number of simulations") },
+    ParameterSchema { required: true, min: Some(1.0), ..parameter("custom_sweep.num_runs", ParameterKind::Integer, "Number of runs per simulation") },
+    ParameterSchema { required: true, ..parameter("custom_sweep.results_dir", ParameterKind::String, "Directory name of the results under simulator/results") },
+    ParameterSchema { default: Some("simulator/src/scenarios/sim_simple/config.toml"), ..parameter("custom_sweep.base_config", ParameterKind::String, "Configuration the swept parameter is set in") },
+];
+
+// ------------------------------------------------------------------------------------------------
+// Configuration
+// ------------------------------------------------------------------------------------------------
+
+/// A one-dimensional sweep over any sweepable parameter of a base configuration
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct CustomSweep {
+    /// The swept parameter, as `<table>.<field>` (one of `SWEEPABLE_PARAMETERS`)
+    pub parameter: String,
+    /// Value of the first simulation
+    pub start: f64,
+    /// Increment between simulations
+    pub step: f64,
+    /// Number of simulations (parameter values)
+    pub num_simulations: usize,
+    /// Number of runs per simulation
+    pub num_runs: u32,
+    /// Directory name of the results under `simulator/results`
+    pub results_dir: String,
+    /// Configuration the swept parameter is set in
+    #[serde(default = "default_base_config")]
+    pub base_config: String,
+}
+
+/// Layout of the custom sweep's config.toml
+#[derive(Debug, Deserialize, Serialize, Clone)]
+struct CustomSweepScenarioConfig {
+    custom_sweep: CustomSweep,
+}
+
+fn default_base_config() -> String {
+    "simulator/src/scenarios/sim_simple/config.toml".to_string()
+}
+
+impl CustomSweep {
+    /// Values of the swept parameter, one per simulation
+    pub fn parameter_values(&self) -> Vec<f64> {
+        let integer = sweepable_parameter(&self.parameter).is_some_and(|parameter| parameter.kind == ParameterKind::Integer);
+        crate::scenarios::sweep_runner::generate_f64_sequence(self.start, self.step, self.num_simulations)
+            .into_iter()
+            .map(|value| if integer { value.round() } else { value })
+            .collect()
+    }
+
+    /// Validates the sweep and the configuration of every simulation
+    ///
+    /// # Returns
+    /// The configuration of each simulation
+    pub fn validate(&self) -> Result<Vec<crate::config::Config>, crate::config::ConfigError> {
+        let invalid = |message: String| crate::config::ConfigError::ValidationError(message);
+        let parameter = sweepable_parameter(&self.parameter)
+            .ok_or_else(|| invalid(format!("'{}' cannot be swept", self.parameter)))?;
+        if self.num_simulations == 0 {
+            return Err(invalid("The sweep needs at least one simulation".to_string()));
+        }
+        if self.num_simulations > 1 && self.step == 0.0 {
+            return Err(invalid("The step must not be zero".to_string()));
+        }
+        if self.num_runs == 0 {
+            return Err(invalid("Each simulation needs at least one run".to_string()));
+        }
+        if self.results_dir.is_empty() || !self.results_dir.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+            return Err(invalid(format!("Results directory '{}' must be a plain directory name", self.results_dir)));
+        }
+
+        let base = self.load_base()?;
+        // The fields of the amount distribution depend on its type, so the base configuration must set the swept one
+        if self.parameter.starts_with("transaction_config.amount_distribution.")
+            && self.parameter.split('.').try_fold(&base, |value, key| value.get(key)).is_none() {
+            return Err(invalid(format!("{} is not set in {}, so it does not apply to its amount distribution", self.parameter, self.base_config)));
+        }
+        self.parameter_values().into_iter()
+            .map(|value| {
+                if parameter.min.is_some_and(|min| value < min) || parameter.max.is_some_and(|max| value > max) {
+                    return Err(invalid(format!("{} = {} is outside of [{}, {}]", self.parameter, value,
+                        parameter.min.map_or("-inf".to_string(), |min| min.to_string()), parameter.max.map_or("inf".to_string(), |max| max.to_string()))));
+                }
+                let config = self.build_config(&base, value)?;
+                config.validate().map_err(|e| invalid(format!("{} = {}: {}", self.parameter, value, e)))?;
+                Ok(config)
+            })
+            .collect()
+    }
+
+    /// Loads the base configuration as a TOML table
+    fn load_base(&self) -> Result<toml::Value, crate::config::ConfigError> {
+        Ok(toml::from_str(&fs::read_to_string(&self.base_config)?)?)
+    }
+
+    /// Builds the configuration of the simulation with the given parameter value
+    fn build_config(&self, base: &toml::Value, value: f64) -> Result<crate::config::Config, crate::config::ConfigError> {
+        let integer = sweepable_parameter(&self.parameter).is_some_and(|parameter| parameter.kind == ParameterKind::Integer);
+        let mut config = base.clone();
+        set_value(&mut config, "simulation_config.num_runs", toml::Value::Integer(self.num_runs as i64));
+        set_value(&mut config, "simulation_config.num_simulations", toml::Value::Integer(self.num_simulations as i64));
+        set_value(&mut config, &self.parameter, if integer { toml::Value::Integer(value as i64) } else { toml::Value::Float(value) });
+        Ok(config.try_into::<crate::config::Config>()?)
+    }
+
+    /// Writes the sweep to the custom sweep's config.toml
+    pub fn save(&self) -> Result<(), crate::config::ConfigError> {
+        let content = toml::to_string(&CustomSweepScenarioConfig { custom_sweep: self.clone() })
+            .map_err(|e| crate::config::ConfigError::ValidationError(format!("Failed to serialize the sweep: {}", e)))?;
+        fs::write(CONFIG_PATH, format!("# Custom Sweep Configuration\n# Written by the sweep wizard; sweeps one parameter of the base configuration\n\n{}", content))?;
+        Ok(())
+    }
+}
+
+/// Sets a value at a dotted path, creating missing tables
+fn set_value(config: &mut toml::Value, path: &str, value: toml::Value) {
+    let mut current = config;
+    let mut keys = path.split('.').peekable();
+    while let Some(key) = keys.next() {
+        let Some(table) = current.as_table_mut() else { return };
+        if keys.peek().is_none() {
+            table.insert(key.to_string(), value);
+            return;
+        }
+        current = table.entry(key.to_string()).or_insert_with(|| toml::Value::Table(toml::Table::new()));
+    }
+}
+
+/// Loads the custom sweep from its config.toml
+pub fn load_custom_sweep() -> Result<CustomSweep, crate::config::ConfigError> {
+    let config_str = fs::read_to_string(CONFIG_PATH)?;
+    Ok(toml::from_str::<CustomSweepScenarioConfig>(&config_str)?.custom_sweep)
+}
+
+/// Sweep configuration handed to the sweep runner: the configuration of the first simulation
+struct CustomSweepConfig {
+    config: crate::config::Config,
+    num_simulations: usize,
+}
+
+impl SweepConfigTrait for CustomSweepConfig {
+    fn as_any(&self) -> &dyn std::any::Any { self }
+    fn get_num_simulations(&self) -> usize { self.num_simulations }
+    fn get_num_runs(&self) -> u32 { self.config.simulation_config.num_runs }
+    fn get_network_config(&self) -> &crate::config::NetworkConfig { &self.config.network_config }
+    fn get_account_config(&self) -> &crate::config::AccountConfig { &self.config.account_config }
+    fn get_transaction_config(&self) -> &crate::config::TransactionConfig { &self.config.transaction_config }
+    fn get_simulation_config(&self) -> Option<&crate::config::SimulationConfig> { Some(&self.config.simulation_config) }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Simulation Runner
+// ------------------------------------------------------------------------------------------------
+
+/// Runs the custom sweep
+///
+/// Sweeps the parameter configured in the custom sweep's config.toml (usually written by the sweep
+/// wizard) over a linear range, with every other value taken from the base configuration. Results
+/// are saved to `simulator/results/<results_dir>`.
+pub async fn run_sweep_custom_simulation() -> Result<(), crate::config::ConfigError> {
+    run_custom_sweep(load_custom_sweep()?, CONFIG_PATH).await
+}
+
+/// Runs a custom sweep
+///
+/// # Arguments
+/// * `sweep` - The sweep to run
+/// * `config_path` - Configuration file copied next to the results
+pub async fn run_custom_sweep(sweep: CustomSweep, config_path: &str) -> Result<(), crate::config::ConfigError> {
+    let configs = sweep.validate()?;
+    let base = sweep.load_base()?;
+    let first_config = configs[0].clone();
+    let num_simulations = sweep.num_simulations;
+    let modifier_sweep = sweep.clone();
+
+    let runner = SweepRunner::new(
+        &format!("Custom ({})", sweep.parameter),
+        &sweep.results_dir,
+        &sweep.parameter,
+        sweep.parameter_values(),
+        // Function to load the sweep configuration
+        Box::new(move || {
+            Ok(Box::new(CustomSweepConfig { config: first_config.clone(), num_simulations }) as Box<dyn SweepConfigTrait>)
+        }),
+        // Function to create the config of each simulation from the base configuration
+        Box::new(move |_sweep_config, value| {
+            modifier_sweep.build_config(&base, value).expect("Sweep points are validated before the sweep starts")
+        }),
+        // Function to save the combined results from all simulations
+        Box::new(|_results_dir, _all_results| {
+            Ok(())
+        }),
+    ).with_config_path(config_path);
+
+    runner.run().await
+}
+
+// ------------------------------------------------------------------------------------------------
+// Simulation Registration
+// ------------------------------------------------------------------------------------------------
+
+/// Register this simulation with the simulation registry.
+///
+/// The custom sweep has no plot script; its charts are part of the sweep report.
+pub fn register() -> (crate::interface::SimulationType, crate::simulation_registry::SimulationConfig) {
+    use crate::interface::SimulationType;
+    use crate::simulation_registry::SimulationConfig;
+
+    (SimulationType::SweepCustom, SimulationConfig {
+        name: "Custom Parameter Sweep",
+        run_fn: Box::new(|| Box::pin(async {
+            run_sweep_custom_simulation().await
+                .map_err(|e| format!("Custom sweep failed: {}", e))
+        })),
+        plot_script: "",
+        config_schema: Some(ConfigSchema {
+            config_path: CONFIG_PATH,
+            sections: &[CUSTOM_SWEEP_PARAMETERS],
+        }),
+    })
+}
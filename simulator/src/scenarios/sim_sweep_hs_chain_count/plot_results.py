@@ -0,0 +1,70 @@
+#!/usr/bin/env python3
+"""
+Plotting script for the HS Chain Count Sweep
+
+This script plots the proposal throughput and the decision latency of the HS receive loop over
+the number of registered chains, with the spread over the runs of every chain count.
+
+Usage:
+    python plot_results.py
+"""
+
+import os
+import json
+
+import numpy as np
+import matplotlib.pyplot as plt
+
+RESULTS_DIR = 'simulator/results/sim_sweep_hs_chain_count'
+
+
+def load_report(results_dir: str):
+    """Load the benchmark report, or None if the benchmark has not run."""
+    path = f'{results_dir}/data/hs_chain_count.json'
+    if not os.path.exists(path):
+        return None
+    with open(path, 'r') as f:
+        return json.load(f)
+
+
+def plot_metric(points: list, key: str, ylabel: str, title: str, filename: str, results_dir: str) -> None:
+    """Plot the mean of a run metric over the chain counts, with its minimum and maximum."""
+    chains = [point['num_chains'] for point in points]
+    values = [[run[key] for run in point['runs']] for point in points]
+    means = np.array([np.mean(runs) for runs in values])
+    errors = np.array([[mean - min(runs) for mean, runs in zip(means, values)],
+                       [max(runs) - mean for mean, runs in zip(means, values)]])
+
+    fig, ax = plt.subplots(figsize=(12, 6))
+    ax.errorbar(chains, means, yerr=errors, marker='o', capsize=4)
+    ax.set_xscale('log', base=2)
+    ax.set_xticks(chains)
+    ax.set_xticklabels([str(count) for count in chains])
+    ax.set_xlabel('Registered chains')
+    ax.set_ylabel(ylabel)
+    ax.set_title(title)
+    ax.grid(True, alpha=0.3)
+
+    plt.tight_layout()
+    plt.savefig(f'{results_dir}/figs/{filename}', dpi=300, bbox_inches='tight')
+    plt.close()
+
+
+def main():
+    """Main function to generate the plots of the HS chain count sweep."""
+    report = load_report(RESULTS_DIR)
+    if not report or not report['points']:
+        print("No HS chain count results found. Skipping plot generation.")
+        return
+
+    os.makedirs(f'{RESULTS_DIR}/figs', exist_ok=True)
+    points = report['points']
+    plot_metric(points, 'proposals_per_sec', 'Proposals per second', 'HS Throughput by Chain Count',
+                'throughput_hs_chain_count.png', RESULTS_DIR)
+    plot_metric(points, 'mean_decision_ms', 'Mean decision latency (ms)', 'HS Decision Latency by Chain Count',
+                'decision_latency_hs_chain_count.png', RESULTS_DIR)
+    print(f"Plots written to {RESULTS_DIR}/figs/")
+
+
+if __name__ == "__main__":
+    main()
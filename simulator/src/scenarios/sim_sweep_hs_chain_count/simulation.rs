@@ -0,0 +1,343 @@
+//! Benchmark of the receive loop of the HS over the number of registered chains.
+//!
+//! Only the HS runs: every chain gets its own channel, as the HIGs have in the other scenarios,
+//! and all chains propose for their CATs concurrently. For each chain count the proposal
+//! throughput and the time from the first proposal of a CAT to its decision are recorded, so a
+//! receive loop that slows down with many receivers shows up as a falling throughput curve.
+
+use std::collections::HashMap;
+use std::fs;
+use std::time::{Duration, Instant};
+
+use hyperplane::hyper_scheduler::node::HyperSchedulerNode;
+use hyperplane::types::{CATId, CATStatusLimited, CATStatusUpdate, ChainId, CLTransactionId, NetworkSpec};
+use hyperplane::utils::logging;
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+use crate::config_schema::{ConfigSchema, ParameterKind, ParameterSchema, parameter};
+use toml;
+use serde_json;
+
+// ------------------------------------------------------------------------------------------------
+// Constants
+// ------------------------------------------------------------------------------------------------
+
+/// Configuration file of the benchmark
+const CONFIG_PATH: &str = "simulator/src/scenarios/sim_sweep_hs_chain_count/config.toml";
+
+/// Directory the results are written to
+const RESULTS_DIR: &str = "simulator/results/sim_sweep_hs_chain_count";
+
+/// File the results are written to, under the data directory
+pub const HS_CHAIN_COUNT_FILE: &str = "hs_chain_count.json";
+
+/// Interval at which the HS is polled for the number of decided CATs
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+// ------------------------------------------------------------------------------------------------
+// Configuration Loading
+// ------------------------------------------------------------------------------------------------
+
+/// Parameters of the chain count benchmark
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct HsChainCountConfig {
+    /// Numbers of chains registered with the HS, one benchmark point each
+    pub chain_counts: Vec<usize>,
+    /// CATs led by each chain
+    pub cats_per_chain: usize,
+    /// Number of constituent chains of every CAT
+    pub cat_fanout: usize,
+    /// Buffer size of the channel of every chain to the HS
+    pub channel_buffer_size: usize,
+    /// Seconds to wait for all CATs of a run to be decided
+    pub timeout_secs: u64,
+    /// Number of runs per chain count
+    pub num_runs: u32,
+}
+
+impl HsChainCountConfig {
+    /// Checks that every chain count can hold a CAT of the configured fanout
+    pub fn validate(&self) -> Result<(), String> {
+        if self.chain_counts.is_empty() {
+            return Err("chain_counts must not be empty".to_string());
+        }
+        if self.cat_fanout < 2 {
+            return Err(format!("cat_fanout must be at least 2, got {}", self.cat_fanout));
+        }
+        if let Some(count) = self.chain_counts.iter().find(|count| **count < self.cat_fanout) {
+            return Err(format!("chain count {} is smaller than cat_fanout {}", count, self.cat_fanout));
+        }
+        if self.cats_per_chain == 0 || self.channel_buffer_size == 0 || self.timeout_secs == 0 || self.num_runs == 0 {
+            return Err("cats_per_chain, channel_buffer_size, timeout_secs and num_runs must be positive".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Layout of the config.toml
+#[derive(Debug, Deserialize, Clone)]
+struct HsChainCountScenarioConfig {
+    hs_chain_count_config: HsChainCountConfig,
+}
+
+/// Parameters of the config.toml
+const HS_CHAIN_COUNT_PARAMETERS: &[ParameterSchema] = &[
+    ParameterSchema { required: true, ..parameter("hs_chain_count_config", ParameterKind::Table, "HS chain count benchmark parameters") },
+    ParameterSchema { required: true, min: Some(2.0), ..parameter("hs_chain_count_config.chain_counts", ParameterKind::FloatList, "Numbers of chains registered with the HS") },
+    ParameterSchema { required: true, min: Some(1.0), ..parameter("hs_chain_count_config.cats_per_chain", ParameterKind::Integer, "CATs led by each chain") },
+    ParameterSchema { required: true, min: Some(2.0), ..parameter("hs_chain_count_config.cat_fanout", ParameterKind::Integer, "Number of constituent chains of every CAT") },
+    ParameterSchema { required: true, min: Some(1.0), ..parameter("hs_chain_count_config.channel_buffer_size", ParameterKind::Integer, "Buffer size of the channel of every chain to the HS") },
+    ParameterSchema { required: true, min: Some(1.0), ..parameter("hs_chain_count_config.timeout_secs", ParameterKind::Integer, "Seconds to wait for all CATs of a run to be decided") },
+    ParameterSchema { required: true, min: Some(1.0), ..parameter("hs_chain_count_config.num_runs", ParameterKind::Integer, "Number of runs per chain count") },
+];
+
+/// Loads and validates the benchmark configuration from the TOML file.
+fn load_config() -> Result<HsChainCountConfig, crate::config::ConfigError> {
+    let config_str = fs::read_to_string(CONFIG_PATH)?;
+    let config = toml::from_str::<HsChainCountScenarioConfig>(&config_str)?.hs_chain_count_config;
+    config.validate()
+        .map_err(|e| crate::config::ConfigError::ValidationError(format!("Invalid hs_chain_count_config: {}", e)))?;
+    Ok(config)
+}
+
+// ------------------------------------------------------------------------------------------------
+// Data Structures
+// ------------------------------------------------------------------------------------------------
+
+/// Measurements of one run
+#[derive(Debug, Clone, Serialize)]
+pub struct HsChainCountRun {
+    /// Proposals received by the HS
+    pub proposals: u64,
+    /// Time from the first proposal sent to the last CAT decided
+    pub elapsed_ms: f64,
+    /// Proposals processed per second
+    pub proposals_per_sec: f64,
+    /// Mean time from the first proposal of a CAT to its decision
+    pub mean_decision_ms: f64,
+    /// Longest time from the first proposal of a CAT to its decision
+    pub max_decision_ms: f64,
+}
+
+/// Measurements of one chain count, over all its runs
+#[derive(Debug, Clone, Serialize)]
+pub struct HsChainCountPoint {
+    pub num_chains: usize,
+    pub num_cats: usize,
+    pub runs: Vec<HsChainCountRun>,
+    /// Throughput averaged over the runs
+    pub mean_proposals_per_sec: f64,
+    /// Decision latency averaged over the runs
+    pub mean_decision_ms: f64,
+}
+
+/// Results of the benchmark
+#[derive(Debug, Clone, Serialize)]
+pub struct HsChainCountReport {
+    pub config: HsChainCountConfig,
+    pub points: Vec<HsChainCountPoint>,
+}
+
+impl HsChainCountReport {
+    /// Lines summarizing the throughput and latency of every chain count
+    pub fn summary_lines(&self) -> Vec<String> {
+        self.points.iter().map(|point| format!(
+            "{:>4} chains, {:>6} CATs: {:>10.0} proposals/s, mean decision {:>8.2} ms",
+            point.num_chains, point.num_cats, point.mean_proposals_per_sec, point.mean_decision_ms,
+        )).collect()
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Benchmark
+// ------------------------------------------------------------------------------------------------
+
+/// Lays out the CATs of a network: CAT `j` is led by chain `j % num_chains` and includes the
+/// next `cat_fanout - 1` chains, so every chain takes part in the same number of CATs
+fn cat_layout(network: &NetworkSpec, config: &HsChainCountConfig) -> Vec<(CATId, Vec<ChainId>)> {
+    let chains = network.chain_ids();
+    let chain = |index: usize| chains[index % chains.len()].clone();
+    (0..chains.len() * config.cats_per_chain)
+        .map(|j| {
+            let cat_id = CATId(CLTransactionId(format!("bench-cat-{}", j)));
+            let constituents = (0..config.cat_fanout).map(|offset| chain(j + offset)).collect();
+            (cat_id, constituents)
+        })
+        .collect()
+}
+
+/// Runs the benchmark once for a chain count
+///
+/// # Arguments
+/// * `num_chains` - Number of chains registered with the HS
+/// * `config` - Parameters of the benchmark
+async fn run_once(num_chains: usize, config: &HsChainCountConfig) -> Result<HsChainCountRun, String> {
+    // Drain the status updates so the HS never waits on a full channel to the CL
+    let (sender_to_cl, mut receiver_from_hs) = mpsc::channel(config.channel_buffer_size);
+    let drain = tokio::spawn(async move { while receiver_from_hs.recv().await.is_some() {} });
+    let mut hs_node = HyperSchedulerNode::new(sender_to_cl);
+
+    let network = NetworkSpec::with_chain_count(num_chains).map_err(|e| e.to_string())?;
+    let cats = cat_layout(&network, config);
+    let mut proposals_by_chain: HashMap<ChainId, Vec<CATStatusUpdate>> = HashMap::new();
+    for (cat_id, constituents) in &cats {
+        for chain_id in constituents {
+            proposals_by_chain.entry(chain_id.clone()).or_default().push(CATStatusUpdate {
+                cat_id: cat_id.clone(),
+                chain_id: chain_id.clone(),
+                status: CATStatusLimited::Success,
+                constituent_chains: constituents.clone(),
+                epoch: 0,
+                block_height: 0,
+            });
+        }
+    }
+
+    let mut senders = Vec::new();
+    for (chain_id, proposals) in proposals_by_chain {
+        let (sender, receiver) = mpsc::channel(config.channel_buffer_size);
+        hs_node.register_chain(chain_id, receiver).await.map_err(|e| e.to_string())?;
+        senders.push((sender, proposals));
+    }
+
+    // All chains propose at once; each records when it sent its proposal of every CAT
+    let start = Instant::now();
+    let tasks: Vec<_> = senders.into_iter().map(|(sender, proposals)| tokio::spawn(async move {
+        let mut sent_at = Vec::with_capacity(proposals.len());
+        for proposal in proposals {
+            let cat_id = proposal.cat_id.clone();
+            sender.send(proposal).await.map_err(|e| format!("Failed to send proposal: {}", e))?;
+            sent_at.push((cat_id, Instant::now()));
+        }
+        Ok::<_, String>(sent_at)
+    })).collect();
+
+    let mut first_proposal_at: HashMap<CATId, Instant> = HashMap::new();
+    for task in tasks {
+        for (cat_id, at) in task.await.map_err(|e| e.to_string())?? {
+            first_proposal_at.entry(cat_id).and_modify(|first| *first = (*first).min(at)).or_insert(at);
+        }
+    }
+
+    // Wait until the HS decided every CAT
+    let deadline = start + Duration::from_secs(config.timeout_secs);
+    loop {
+        let decisions = hs_node.get_metrics().await.decisions;
+        if decisions as usize >= cats.len() {
+            break;
+        }
+        if Instant::now() >= deadline {
+            return Err(format!("Only {} of {} CATs decided after {} s with {} chains",
+                decisions, cats.len(), config.timeout_secs, num_chains));
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+
+    let metrics = hs_node.get_metrics().await;
+    let proposals: u64 = metrics.proposals_received.values().sum();
+    let mut decision_times = Vec::with_capacity(cats.len());
+    let mut last_decision = start;
+    for (cat_id, _) in &cats {
+        let decided_at = hs_node.get_cat_decision_time(cat_id).await
+            .ok_or_else(|| format!("CAT {} has no decision time", cat_id.0 .0))?;
+        last_decision = last_decision.max(decided_at);
+        decision_times.push(decided_at.saturating_duration_since(first_proposal_at[cat_id]).as_secs_f64() * 1000.0);
+    }
+    drain.abort();
+
+    let elapsed = last_decision.duration_since(start).as_secs_f64();
+    Ok(HsChainCountRun {
+        proposals,
+        elapsed_ms: elapsed * 1000.0,
+        proposals_per_sec: if elapsed > 0.0 { proposals as f64 / elapsed } else { 0.0 },
+        mean_decision_ms: decision_times.iter().sum::<f64>() / decision_times.len() as f64,
+        max_decision_ms: decision_times.iter().cloned().fold(0.0, f64::max),
+    })
+}
+
+// ------------------------------------------------------------------------------------------------
+// Simulation Entry Point
+// ------------------------------------------------------------------------------------------------
+
+/// Runs the chain count sweep of the HS receive loop
+///
+/// Every chain count is benchmarked `num_runs` times on a fresh HS. The measurements are
+/// written to `hs_chain_count.json` in the data directory.
+pub async fn run_sweep_hs_chain_count_simulation() -> Result<(), crate::config::ConfigError> {
+    let data_dir = format!("{}/data", RESULTS_DIR);
+    fs::create_dir_all(&data_dir).expect("Failed to create data directory");
+    fs::create_dir_all(format!("{}/figs", RESULTS_DIR)).expect("Failed to create figures directory");
+
+    let config = load_config()?;
+    logging::init_logging_with_config(false, false, None);
+
+    // Copy config.toml to data directory for reference
+    fs::copy(CONFIG_PATH, format!("{}/config.toml", data_dir)).expect("Failed to copy config.toml");
+
+    println!("Running HS Chain Count Sweep");
+    let mut points = Vec::new();
+    for &num_chains in &config.chain_counts {
+        logging::log("SIMULATOR", &format!("=== Benchmarking the HS with {} chains ===", num_chains));
+        let mut runs = Vec::new();
+        for run in 1..=config.num_runs {
+            let result = run_once(num_chains, &config).await.map_err(|e| crate::config::ConfigError::ValidationError(
+                format!("HS chain count sweep failed with {} chains in run {}/{}: {}", num_chains, run, config.num_runs, e)))?;
+            logging::log("SIMULATOR", &format!("Run {}/{}: {:.0} proposals/s, mean decision {:.2} ms",
+                run, config.num_runs, result.proposals_per_sec, result.mean_decision_ms));
+            runs.push(result);
+        }
+        let mean = |values: Vec<f64>| values.iter().sum::<f64>() / values.len() as f64;
+        points.push(HsChainCountPoint {
+            num_chains,
+            num_cats: num_chains * config.cats_per_chain,
+            mean_proposals_per_sec: mean(runs.iter().map(|run| run.proposals_per_sec).collect()),
+            mean_decision_ms: mean(runs.iter().map(|run| run.mean_decision_ms).collect()),
+            runs,
+        });
+    }
+
+    let report = HsChainCountReport { config, points };
+    fs::write(format!("{}/{}", data_dir, HS_CHAIN_COUNT_FILE), serde_json::to_string_pretty(&report).unwrap())?;
+
+    println!("\n=== HS Chain Count Sweep ===");
+    for line in report.summary_lines() {
+        println!("{}", line);
+    }
+    println!("============================");
+
+    Ok(())
+}
+
+/// Runs the chain count sweep with automatic plotting
+pub async fn run_with_plotting() -> Result<(), crate::config::ConfigError> {
+    use crate::scenarios::utils::run_simulation_with_plotting;
+
+    run_simulation_with_plotting(
+        || run_sweep_hs_chain_count_simulation(),
+        "HS Chain Count Sweep",
+        "simulator/src/scenarios/sim_sweep_hs_chain_count/plot_results.py"
+    ).await
+}
+
+// ------------------------------------------------------------------------------------------------
+// Simulation Registration
+// ------------------------------------------------------------------------------------------------
+
+/// Register this simulation with the simulation registry.
+pub fn register() -> (crate::interface::SimulationType, crate::simulation_registry::SimulationConfig) {
+    use crate::interface::SimulationType;
+    use crate::simulation_registry::SimulationConfig;
+
+    (SimulationType::SweepHsChainCount, SimulationConfig {
+        name: "HS Chain Count Sweep",
+        run_fn: Box::new(|| Box::pin(async {
+            run_sweep_hs_chain_count_simulation().await
+                .map_err(|e| format!("HS chain count sweep failed: {}", e))
+        })),
+        plot_script: "simulator/src/scenarios/sim_sweep_hs_chain_count/plot_results.py",
+        config_schema: Some(ConfigSchema {
+            config_path: CONFIG_PATH,
+            sections: &[HS_CHAIN_COUNT_PARAMETERS],
+        }),
+    })
+}
@@ -0,0 +1,114 @@
+#!/usr/bin/env python3
+"""
+Plotting script for Mixed CAT Pending Dependencies Sweep Simulation
+
+This script averages the runs of every strict/permissive combination of the two chains
+and reports the final CAT outcomes of each chain. The outcomes are written to
+data/cat_outcomes.json and plotted as grouped bars.
+
+Usage:
+    python plot_results.py
+"""
+
+import sys
+import os
+import json
+
+import numpy as np
+import matplotlib.pyplot as plt
+
+# Add the simulator source directory to the Python path to import the averaging script
+sys.path.insert(0, os.path.join(os.path.dirname(os.path.abspath(__file__)), '..', '..'))
+from average_runs import create_averaged_data
+
+# Final CAT statuses reported per chain
+OUTCOMES = ('success', 'failure', 'timed_out', 'rejected', 'pending')
+
+
+def last_count(data_dir: str, filename: str, key: str) -> float:
+    """Return the final count of a time series file, or 0 if it is missing or empty."""
+    path = os.path.join(data_dir, filename)
+    if not os.path.exists(path):
+        return 0.0
+    with open(path, 'r') as f:
+        series = json.load(f).get(key, [])
+    return float(series[-1]['count']) if series else 0.0
+
+
+def chain_outcomes(data_dir: str, chain: int) -> dict:
+    """Collect the final CAT counts of a chain and the share of CATs that succeeded."""
+    counts = {
+        outcome: last_count(data_dir, f'cat_{outcome}_transactions_chain_{chain}.json', f'chain_{chain}_cat_{outcome}')
+        for outcome in OUTCOMES
+    }
+    total = sum(counts[outcome] for outcome in ('success', 'failure', 'pending'))
+    counts['success_rate'] = counts['success'] / total if total > 0 else 0.0
+    return counts
+
+
+def build_outcomes(results_dir: str) -> list:
+    """Build the per-chain CAT outcomes from the run_average folder of every combination."""
+    with open(f'{results_dir}/data/metadata.json', 'r') as f:
+        metadata = json.load(f)
+
+    outcomes = []
+    for sim_index, policies in enumerate(metadata['parameter_values']):
+        data_dir = f'{results_dir}/data/sim_{sim_index}/run_average'
+        if not os.path.exists(data_dir):
+            print(f"Warning: No averaged data found for simulation {sim_index}")
+            continue
+        outcomes.append({
+            **policies,
+            'chain_1': chain_outcomes(data_dir, 1),
+            'chain_2': chain_outcomes(data_dir, 2),
+        })
+    return outcomes
+
+
+def plot_outcomes(outcomes: list, results_dir: str) -> None:
+    """Plot the CAT success rate and the rejected CATs of each chain per combination."""
+    labels = [entry['label'] for entry in outcomes]
+    x = np.arange(len(labels))
+    width = 0.35
+
+    fig, (ax_rate, ax_rejected) = plt.subplots(1, 2, figsize=(14, 6))
+    for offset, chain in ((-width / 2, 1), (width / 2, 2)):
+        ax_rate.bar(x + offset, [entry[f'chain_{chain}']['success_rate'] * 100.0 for entry in outcomes], width, label=f'chain-{chain}')
+        ax_rejected.bar(x + offset, [entry[f'chain_{chain}']['rejected'] for entry in outcomes], width, label=f'chain-{chain}')
+
+    for ax, ylabel, title in ((ax_rate, 'Successful CATs (%)', 'CAT Success Rate'), (ax_rejected, 'Rejected CATs', 'Rejected CATs')):
+        ax.set_xticks(x)
+        ax.set_xticklabels(labels)
+        ax.set_xlabel('Chain-1 / Chain-2 Policy')
+        ax.set_ylabel(ylabel)
+        ax.set_title(title)
+        ax.legend()
+
+    plt.tight_layout()
+    plt.savefig(f'{results_dir}/figs/cat_outcomes.png', dpi=300, bbox_inches='tight')
+    plt.close()
+
+
+def main():
+    """Main function to generate the per-chain CAT outcomes for the mixed CAT pending dependencies sweep."""
+    results_dir = 'simulator/results/sim_sweep_mixed_cat_pending_dependencies'
+
+    if not create_averaged_data(results_dir):
+        print("Averaging failed!")
+        return
+
+    outcomes = build_outcomes(results_dir)
+    if not outcomes:
+        print("No data found for Mixed CAT Pending Dependencies simulation. Skipping plot generation.")
+        return
+
+    with open(f'{results_dir}/data/cat_outcomes.json', 'w') as f:
+        json.dump({'cat_outcomes': outcomes}, f, indent=2)
+
+    os.makedirs(f'{results_dir}/figs', exist_ok=True)
+    plot_outcomes(outcomes, results_dir)
+    print(f"CAT outcomes written to {results_dir}/data/cat_outcomes.json and {results_dir}/figs/")
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,175 @@
+use crate::scenarios::sweep_runner::{SweepRunner, create_modified_config};
+use crate::define_sweep_config;
+use crate::config::ValidateConfig;
+use crate::scenarios::utils::run_simulation_with_plotting;
+use serde::Serialize;
+use crate::config_schema::{ConfigSchema, ParameterSchema, COMMON_PARAMETERS, NUM_SIMULATIONS};
+
+// ------------------------------------------------------------------------------------------------
+// Sweep Configuration
+// ------------------------------------------------------------------------------------------------
+
+// Defines the sweep configuration for mixed CAT pending dependencies simulations.
+//
+// This macro generates a complete sweep configuration setup including:
+// - A config struct with standard fields (network_config, account_config, transaction_config, simulation_config)
+// - Standard validation logic for common fields
+// - SweepConfigTrait implementation for integration with the generic SweepRunner
+// - A load_config() function that reads and validates the TOML configuration file
+define_sweep_config!(
+    "sim_sweep_mixed_cat_pending_dependencies",
+    SweepMixedCatPendingDependenciesConfig,
+    validate_sweep_specific = |self_: &Self| {
+        // One simulation per strict/permissive combination of the two chains
+        if self_.simulation_config.num_simulations.unwrap_or(0) != 4 {
+            return Err(crate::config::ConfigError::ValidationError("Number of simulations must be exactly 4 for the mixed CAT pending dependencies sweep (one per combination of the two chains)".into()));
+        }
+        if self_.network_config.num_chains < 2 {
+            return Err(crate::config::ConfigError::ValidationError("Mixed CAT pending dependencies sweep requires at least 2 chains".into()));
+        }
+        Ok(())
+    }
+);
+
+/// Parameters of the mixed CAT pending dependencies sweep
+const SWEEP_PARAMETERS: &[ParameterSchema] = &[
+    ParameterSchema { min: Some(4.0), max: Some(4.0), ..NUM_SIMULATIONS },
+];
+
+// ------------------------------------------------------------------------------------------------
+// Sweep Parameter
+// ------------------------------------------------------------------------------------------------
+
+/// A combination of strict and permissive chains.
+#[derive(Debug, Clone, Serialize)]
+pub struct ChainDependencyPolicies {
+    /// Whether each chain allows CATs to depend on pending transactions, in chain order
+    pub chain_allow_cat_pending_dependencies: Vec<bool>,
+    /// Short description of the combination, e.g. "strict/permissive"
+    pub label: String,
+}
+
+/// Builds every strict/permissive combination of the first two chains.
+///
+/// The fully strict and fully permissive networks come first and last, so the mixed
+/// networks can be compared against both.
+///
+/// # Returns
+/// The list of combinations, one per simulation
+pub fn build_combinations() -> Vec<ChainDependencyPolicies> {
+    [[false, false], [false, true], [true, false], [true, true]].into_iter()
+        .map(|allow| ChainDependencyPolicies {
+            chain_allow_cat_pending_dependencies: allow.to_vec(),
+            label: allow.iter().map(|&allow| if allow { "permissive" } else { "strict" }).collect::<Vec<_>>().join("/"),
+        })
+        .collect()
+}
+
+// ------------------------------------------------------------------------------------------------
+// Simulation Runner
+// ------------------------------------------------------------------------------------------------
+
+/// Runs the sweep mixed CAT pending dependencies simulation
+///
+/// This simulation explores networks in which the chains disagree on whether CATs may
+/// depend on pending transactions. A CAT rejected by a strict chain fails on all of its
+/// chains, so strict chains also affect the CATs of permissive chains.
+///
+/// The sweep tests the four combinations of the two chains:
+/// - strict/strict and permissive/permissive: the uniform networks as a reference
+/// - strict/permissive and permissive/strict: the mixed networks
+///
+/// The plotting script reports the CAT outcomes of each chain per combination.
+pub async fn run_sweep_mixed_cat_pending_dependencies_simulation() -> Result<(), crate::config::ConfigError> {
+    // Load sweep configuration to validate it before the runner starts
+    let _sweep_config = load_config()?;
+
+    // Create the generic sweep runner that handles all the common functionality
+    let runner = SweepRunner::new(
+        "Mixed CAT Pending Dependencies",            // Human-readable name for logging
+        "sim_sweep_mixed_cat_pending_dependencies",  // Directory name for results
+        "chain_dependency_policies",                 // Parameter name for JSON output
+        build_combinations(),                        // List of parameter values to test
+        // Function to load the sweep configuration
+        Box::new(|| {
+            load_config().map(|config| Box::new(config) as Box<dyn crate::scenarios::sweep_runner::SweepConfigTrait>)
+        }),
+        // Function to create a modified config for each simulation using the helper
+        Box::new(|sweep_config, policies| {
+            create_modified_config(sweep_config, |base_config| {
+                crate::config::Config {
+                    network_config: base_config.network_config.clone(),
+                    account_config: base_config.account_config.clone(),
+                    transaction_config: crate::config::TransactionConfig {
+                        target_tpb: base_config.transaction_config.target_tpb,
+                        zipf_parameter: base_config.transaction_config.zipf_parameter,
+                        ratio_cats: base_config.transaction_config.ratio_cats,
+                        cat_lifetime_blocks: base_config.transaction_config.cat_lifetime_blocks,
+                        allow_cat_pending_dependencies: base_config.transaction_config.allow_cat_pending_dependencies,
+                        cat_fanout: base_config.transaction_config.cat_fanout.clone(),
+                        cat_chain_selection: base_config.transaction_config.cat_chain_selection,
+                        cat_part_spread_blocks: base_config.transaction_config.cat_part_spread_blocks,
+                        regular_tx_ttl_blocks: base_config.transaction_config.regular_tx_ttl_blocks,
+                        cat_transfers: base_config.transaction_config.cat_transfers,
+                        duplicate_ratio: base_config.transaction_config.duplicate_ratio,
+                        duplicate_variant_share: base_config.transaction_config.duplicate_variant_share,
+                        duplicate_delay_blocks: base_config.transaction_config.duplicate_delay_blocks,
+                        malformed_ratio: base_config.transaction_config.malformed_ratio,
+                        amount_distribution: base_config.transaction_config.amount_distribution,
+                        chain_ratio_cats: base_config.transaction_config.chain_ratio_cats.clone(),
+                        chain_allow_cat_pending_dependencies: policies.chain_allow_cat_pending_dependencies.clone(),  // This is the parameter we're varying
+                    },
+                    simulation_config: base_config.simulation_config.clone(),
+                    logging_config: base_config.logging_config.clone(),
+                }
+            })
+        }),
+        // Function to save the combined results from all simulations
+        // Note: The per-chain CAT outcomes are computed by the plotting code from the averaged runs
+        Box::new(|_results_dir, _all_results| {
+            Ok(())
+        }),
+    );
+
+    // Run the sweep - this handles all the simulation execution, logging, and result saving
+    runner.run().await
+}
+
+// ------------------------------------------------------------------------------------------------
+// Simulation Registration
+// ------------------------------------------------------------------------------------------------
+
+/// Register this simulation with the simulation registry.
+///
+/// This function provides the configuration needed to register the mixed CAT pending
+/// dependencies sweep with the main simulation registry.
+pub fn register() -> (crate::interface::SimulationType, crate::simulation_registry::SimulationConfig) {
+    use crate::interface::SimulationType;
+    use crate::simulation_registry::SimulationConfig;
+
+    (SimulationType::SweepMixedCatPendingDependencies, SimulationConfig {
+        name: "Mixed CAT Pending Dependencies Sweep",
+        run_fn: Box::new(|| Box::pin(async {
+            run_sweep_mixed_cat_pending_dependencies_simulation().await
+                .map_err(|e| format!("Mixed CAT pending dependencies sweep failed: {}", e))
+        })),
+        plot_script: "simulator/src/scenarios/sim_sweep_mixed_cat_pending_dependencies/plot_results.py",
+        config_schema: Some(ConfigSchema {
+            config_path: "simulator/src/scenarios/sim_sweep_mixed_cat_pending_dependencies/config.toml",
+            sections: &[COMMON_PARAMETERS, SWEEP_PARAMETERS],
+        }),
+    })
+}
+
+// ------------------------------------------------------------------------------------------------
+// Run with Plotting
+// ------------------------------------------------------------------------------------------------
+
+/// Runs the mixed CAT pending dependencies sweep simulation with automatic plotting.
+pub async fn run_with_plotting() -> Result<(), crate::config::ConfigError> {
+    run_simulation_with_plotting(
+        || run_sweep_mixed_cat_pending_dependencies_simulation(),
+        "Mixed CAT Pending Dependencies Sweep",
+        "simulator/src/scenarios/sim_sweep_mixed_cat_pending_dependencies/plot_results.py"
+    ).await
+}
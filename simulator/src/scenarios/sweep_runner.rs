@@ -2,7 +2,7 @@ use std::fs;
 use chrono::Local;
 use hyperplane::utils::logging;
 use hyperplane::hyper_ig::HyperIG;
-use std::time::{Duration, Instant};
+use std::time::Instant;
 use indicatif::{ProgressBar, ProgressStyle};
 use serde_json;
 
@@ -31,9 +31,11 @@ use serde_json;
 /// * `config_loader` - Function to load the sweep configuration
 /// * `config_modifier` - Function to create a modified config for each simulation
 /// * `result_saver` - Function to save combined results from all simulations
+/// * `config_path` - Configuration file copied next to the results (defaults to the scenario's config.toml)
 pub struct SweepRunner<T> {
     sweep_name: String,
     results_dir: String,
+    config_path: String,
     parameter_name: String,
     parameter_values: Vec<T>,
     config_loader: Box<dyn Fn() -> Result<Box<dyn SweepConfigTrait>, crate::config::ConfigError>>,
@@ -105,6 +107,7 @@ impl<T: std::fmt::Debug + Clone> SweepRunner<T> {
         Self {
             sweep_name: sweep_name.to_string(),
             results_dir: results_dir.to_string(),
+            config_path: format!("simulator/src/scenarios/{}/config.toml", results_dir),
             parameter_name: parameter_name.to_string(),
             parameter_values,
             config_loader,
@@ -113,6 +116,13 @@ impl<T: std::fmt::Debug + Clone> SweepRunner<T> {
         }
     }
 
+    /// Sets the configuration file copied next to the results, for sweeps whose results
+    /// directory is not named after their scenario directory.
+    pub fn with_config_path(mut self, config_path: &str) -> Self {
+        self.config_path = config_path.to_string();
+        self
+    }
+
     // ------------------------------------------------------------------------------------------------
     // Main Simulation Execution
     // ------------------------------------------------------------------------------------------------
@@ -134,6 +144,15 @@ impl<T: std::fmt::Debug + Clone> SweepRunner<T> {
     where
         T: serde::Serialize,
     {
+        // A dry run only needs the configurations of the simulation points
+        if crate::dry_run::is_active() {
+            let sweep_config = (self.config_loader)()?;
+            crate::dry_run::record(self.parameter_values.iter()
+                .map(|value| (self.config_modifier)(&sweep_config, value.clone()))
+                .collect());
+            return Ok(());
+        }
+
         // Create results directory if it doesn't exist
         self.create_directories();
         
@@ -147,30 +166,33 @@ impl<T: std::fmt::Debug + Clone> SweepRunner<T> {
         let first_config = (self.config_modifier)(&sweep_config, self.parameter_values[0].clone());
         self.setup_logging(&first_config);
 
+        // The parameter values are authoritative for the number of simulations, since
+        // multi-dimensional sweeps run more points than the configured num_simulations
+        let num_simulations = self.parameter_values.len();
+
         // Write metadata.json for Python averaging script
         let metadata_path = format!("simulator/results/{}/data/metadata.json", self.results_dir);
         let metadata = serde_json::json!({
             "num_runs": sweep_config.get_num_runs(),
-            "num_simulations": sweep_config.get_num_simulations(),
+            "num_simulations": num_simulations,
             "parameter_name": self.parameter_name,
             "parameter_values": self.parameter_values,
         });
         std::fs::write(&metadata_path, serde_json::to_string_pretty(&metadata).unwrap()).expect("Failed to write metadata.json");
         
         // Copy config.toml to data directory for reference
-        let config_source = format!("simulator/src/scenarios/{}/config.toml", self.results_dir);
         let config_dest = format!("simulator/results/{}/data/config.toml", self.results_dir);
-        std::fs::copy(&config_source, &config_dest)
+        std::fs::copy(&self.config_path, &config_dest)
             .expect("Failed to copy config.toml");
 
         // Log sweep start
-        self.log_sweep_start(&sweep_config);
+        self.log_sweep_start();
 
         // Display parameter values before progress bar
         println!("Parameter values to test: {:?}", self.parameter_values);
 
         // Create progress bar for sweep
-        let progress_bar = self.create_progress_bar(sweep_config.get_num_simulations());
+        let progress_bar = self.create_progress_bar(num_simulations);
 
         // Store results for each simulation
         let mut all_results = Vec::new();
@@ -178,9 +200,13 @@ impl<T: std::fmt::Debug + Clone> SweepRunner<T> {
         // Get number of runs from config
         let num_runs = sweep_config.get_num_runs();
 
+        // Draw the master seed unless the experiment or the configuration fixes it; every sweep point reuses the run seeds
+        let master_seed = crate::metadata::master_seed(first_config.simulation_config.seed);
+        logging::log("SIMULATOR", &format!("Master seed: {}", master_seed));
+
         // Run each simulation with different parameter value
         for (sim_index, param_value) in self.parameter_values.iter().enumerate() {
-            self.log_simulation_start(sim_index, sweep_config.get_num_simulations(), param_value);
+            self.log_simulation_start(sim_index, num_simulations, param_value);
 
             // Create a modified config with the current parameter value
             let sim_config = (self.config_modifier)(&sweep_config, param_value.clone());
@@ -202,20 +228,29 @@ impl<T: std::fmt::Debug + Clone> SweepRunner<T> {
 
                 // Initialize simulation results for this run
                 let mut results = self.initialize_simulation_results(&sim_config, sim_index, param_value);
+                results.master_seed = master_seed;
+                results.run_seed = crate::metadata::run_seed(master_seed, run);
 
-                // Setup test nodes with preloaded accounts from config
-                let (hs_node, cl_node, hig_node_1, hig_node_2, _start_block_height) = crate::testnodes::setup_test_nodes(
-                    Duration::from_secs_f64(sim_config.network_config.block_interval),
+                // Flush the partial results of the run to the directory it is saved to
+                let run_dir = format!("simulator/results/{}/data/sim_{}/run_{}", self.results_dir, sim_index, run - 1);
+                results.flush_dir = Some(run_dir.clone());
+
+                // Capture the logs of the run in its directory, one file per component, if configured
+                let _log_capture = crate::scenarios::utils::capture_run_logs(&sim_config.logging_config, &run_dir);
+
+                // Gate the links of the chains the fault timeline partitions
+                let partitions = crate::fault_orchestrator::network_partitions(&sim_config.network_config.fault_timeline);
+
+                // Setup test nodes with preloaded accounts from config and apply the HS, CL and HIG settings
+                let (hs_node, cl_node, hig_node_1, hig_node_2) = crate::run_simulation::setup_configured_nodes(
+                    &sim_config,
                     &sim_config.network_config.chain_delays,
-                    sim_config.transaction_config.allow_cat_pending_dependencies,
-                    sim_config.transaction_config.cat_lifetime_blocks,
-                    sim_config.account_config.num_accounts.try_into().unwrap(), // Preload accounts from config
-                    sim_config.account_config.initial_balance.try_into().unwrap(), // Preload value from config
-                    sim_config.network_config.channel_buffer_size, // Channel buffer size from config
+                    &partitions,
+                    &results.network,
                 ).await;
                 
-                logging::log("SIMULATOR", &format!("Test nodes setup complete with {} accounts preloaded with {} tokens each", 
-                    sim_config.account_config.num_accounts, sim_config.account_config.initial_balance));
+                logging::log("SIMULATOR", &format!("Test nodes setup complete with {} preloaded accounts per chain", 
+                    sim_config.account_config.chain_balances(2).iter().map(|balances| balances.len().to_string()).collect::<Vec<_>>().join("/")));
                 
                 // Query and log account balances to verify preloading (only for first run to avoid spam)
                 if run == 1 {
@@ -259,6 +294,7 @@ impl<T: std::fmt::Debug + Clone> SweepRunner<T> {
                 let simulation_result = crate::run_simulation::run_simulation_with_message_and_retries(
                     cl_node.clone(),
                     vec![hig_node_1.clone(), hig_node_2.clone()],
+                    partitions,
                     &mut results,
                     Some(run_message),
                     None, // No retry count needed
@@ -270,7 +306,7 @@ impl<T: std::fmt::Debug + Clone> SweepRunner<T> {
                         "Sweep '{}' failed during simulation {}/{} run {}/{} with {}: {:?}. Error: {}",
                         self.sweep_name,
                         sim_index + 1,
-                        sweep_config.get_num_simulations(),
+                        num_simulations,
                         run,
                         num_runs,
                         self.parameter_name,
@@ -280,6 +316,16 @@ impl<T: std::fmt::Debug + Clone> SweepRunner<T> {
                     return Err(crate::config::ConfigError::ValidationError(error_context));
                 }
 
+                // Record where the HS is still waiting on proposals, its metrics, the slowest transactions and the HS decision latencies before the nodes are shut down
+                results.cats_awaiting_chain_1 = crate::stats::collect_pending_cats(&hs_node, results.network.chain_ids()[0].clone()).await;
+                results.cats_awaiting_chain_2 = crate::stats::collect_pending_cats(&hs_node, results.network.chain_ids()[1].clone()).await;
+                results.hs_metrics = crate::stats::collect_hs_metrics(&hs_node).await;
+                results.node_health = Some(crate::stats::collect_network_health(&cl_node, &hs_node, &results.network, &[hig_node_1.clone(), hig_node_2.clone()]).await);
+                let timelines = crate::timeline::collect_transaction_timelines(&cl_node, &[hig_node_1.clone(), hig_node_2.clone()], &hs_node, &results.recorded_workload).await;
+                results.latency_breakdown = crate::latency_breakdown::LatencyBreakdownReport::from_timelines(&timelines);
+                results.slowest_transactions = crate::slowest::SlowestTransactionsReport::slowest(timelines, crate::slowest::SLOWEST_TRANSACTIONS_LISTED);
+                results.hs_decision_latencies = crate::decision_latency::collect_hs_decision_latencies(&cl_node, &hs_node, &results.recorded_workload).await;
+
                 // Shutdown nodes between runs to prevent state persistence
                 if run < num_runs {
                     logging::log("SIMULATOR", "Shutting down nodes between runs to clear state...");
@@ -298,7 +344,6 @@ impl<T: std::fmt::Debug + Clone> SweepRunner<T> {
                 }
 
                 // Save this run's results to its own directory
-                let run_dir = format!("simulator/results/{}/data/sim_{}/run_{}", self.results_dir, sim_index, run - 1);
                 let save_result = results.save_to_directory(&run_dir).await;
                 
                 if let Err(e) = save_result {
@@ -306,7 +351,7 @@ impl<T: std::fmt::Debug + Clone> SweepRunner<T> {
                         "Sweep '{}' failed to save results for simulation {}/{} run {}/{} with {}: {:?}. Error: {}",
                         self.sweep_name,
                         sim_index + 1,
-                        sweep_config.get_num_simulations(),
+                        num_simulations,
                         run,
                         num_runs,
                         self.parameter_name,
@@ -327,13 +372,13 @@ impl<T: std::fmt::Debug + Clone> SweepRunner<T> {
             
             // Update progress bar
             progress_bar.inc(1);
-            progress_bar.set_message(self.format_progress_message(sim_index, sweep_config.get_num_simulations(), param_value, None));
+            progress_bar.set_message(self.format_progress_message(sim_index, num_simulations, param_value, None));
         }
 
         // Finish progress bar with final state
         progress_bar.finish_with_message(self.format_progress_message(
-            sweep_config.get_num_simulations() - 1, 
-            sweep_config.get_num_simulations(), 
+            num_simulations - 1, 
+            num_simulations, 
             self.parameter_values.last().unwrap(),
             None
         ));
@@ -343,6 +388,30 @@ impl<T: std::fmt::Debug + Clone> SweepRunner<T> {
         // Save combined results
         (self.result_saver)(&self.results_dir, &all_results)?;
 
+        // Render the self-contained HTML report
+        match crate::report::generate_sweep_report(&self.results_dir) {
+            Ok(report_path) => {
+                println!("Report written to {}", report_path);
+                logging::log("SIMULATOR", &format!("Saved sweep report to {}", report_path));
+            }
+            Err(e) => logging::log("SIMULATOR", &format!("Failed to generate sweep report: {}", e)),
+        }
+
+        // Pack the raw data of every sweep point now that the report has aggregated it
+        if first_config.simulation_config.archive_raw_data {
+            for sim_index in 0..num_simulations {
+                let sim_dir = format!("simulator/results/{}/data/sim_{}", self.results_dir, sim_index);
+                match crate::archive::archive_sweep_point(&sim_dir) {
+                    Ok(summary) => logging::log("SIMULATOR", &format!("Archived {} files of {} ({} bytes -> {} bytes)",
+                        summary.files_archived, sim_dir, summary.bytes_before, summary.bytes_after)),
+                    Err(e) => {
+                        eprintln!("Warning: failed to archive {}: {}", sim_dir, e);
+                        logging::log("SIMULATOR", &format!("Failed to archive {}: {}", sim_dir, e));
+                    }
+                }
+            }
+        }
+
         logging::log("SIMULATOR", "=== Sweep Simulation Complete ===");
         logging::log("SIMULATOR", &format!("Total simulations completed: {}", all_results.len()));
 
@@ -425,13 +494,9 @@ impl<T: std::fmt::Debug + Clone> SweepRunner<T> {
     /// This method logs comprehensive information about the sweep including
     /// the sweep name, number of simulations, and all parameter values to be tested.
     /// This provides a clear record of what the sweep is testing.
-    /// 
-    /// # Arguments
-    /// 
-    /// * `sweep_config` - The sweep configuration containing simulation parameters
-    fn log_sweep_start(&self, sweep_config: &Box<dyn SweepConfigTrait>) {
+    fn log_sweep_start(&self) {
         logging::log("SIMULATOR", &format!("=== Sweep {} Simulation ===", self.sweep_name));
-        logging::log("SIMULATOR", &format!("Number of simulations: {}", sweep_config.get_num_simulations()));
+        logging::log("SIMULATOR", &format!("Number of simulations: {}", self.parameter_values.len()));
         logging::log("SIMULATOR", &format!("{} values: {:?}", self.parameter_name, self.parameter_values));
         logging::log("SIMULATOR", "================================");
     }
@@ -506,10 +571,49 @@ impl<T: std::fmt::Debug + Clone> SweepRunner<T> {
         results.zipf_parameter = config.transaction_config.zipf_parameter;
         results.ratio_cats = config.transaction_config.ratio_cats;
         results.block_interval = config.network_config.block_interval;
+        results.network = config.network_config.network_spec().expect("The network is validated with the config");
         results.cat_lifetime = config.transaction_config.cat_lifetime_blocks;
         results.initialization_wait_blocks = config.simulation_config.initialization_wait_blocks;
         results.chain_delays = config.network_config.chain_delays.clone();
+        results.chain_delay_distribution = config.network_config.chain_delay_distribution;
+        results.protocol_violation_policy = config.simulation_config.protocol_violation_policy;
+        results.export_parquet = config.simulation_config.export_parquet;
+        results.flush_interval_blocks = config.simulation_config.flush_interval_blocks;
+        results.watchdog_max_lag_blocks = config.simulation_config.watchdog_max_lag_blocks;
+        results.watchdog_queue_stall_secs = config.simulation_config.watchdog_queue_stall_secs;
+        results.watchdog_abort = config.simulation_config.watchdog_abort;
+        results.submission_batch_size = config.simulation_config.submission_batch_size;
+        results.submission_rate_limit_tps = config.simulation_config.submission_rate_limit_tps;
+        results.rate_anomaly_window_blocks = config.simulation_config.rate_anomaly_window_blocks;
+        results.rate_anomaly_sigma = config.simulation_config.rate_anomaly_sigma;
+        results.cat_fanout = config.transaction_config.cat_fanout.clone();
+        results.cat_chain_selection = config.transaction_config.cat_chain_selection;
+        results.hs_decision_latency = config.network_config.hs_decision_latency;
+        results.cl_block_ordering = config.network_config.cl_block_ordering;
+        results.cl_align_cat_parts = config.network_config.cl_align_cat_parts;
+        results.cl_block_max_transactions = config.network_config.cl_block_max_transactions;
+        results.cl_block_max_bytes = config.network_config.cl_block_max_bytes;
+        results.cl_block_interval_jitter = config.network_config.cl_block_interval_jitter;
+        results.hig_release_locks_on_failure = config.network_config.hig_release_locks_on_failure;
+        results.hig_prioritize_status_updates = config.network_config.hig_prioritize_status_updates;
+        results.hs_status_update_delays = config.network_config.hs_status_update_delays.clone();
+        results.cat_latency_multiplier = config.network_config.topology.cat_latency_multiplier;
+        results.regular_latency_multiplier = config.network_config.topology.regular_latency_multiplier;
+        results.hig_parameter_schedule = config.network_config.hig_parameter_schedule.clone();
+        results.fault_timeline = config.network_config.fault_timeline.clone();
+        results.invariants = config.simulation_config.invariants.clone();
+        results.cat_part_spread_blocks = config.transaction_config.cat_part_spread_blocks;
+        results.regular_tx_ttl_blocks = config.transaction_config.regular_tx_ttl_blocks;
+        results.cat_transfers = config.transaction_config.cat_transfers;
+        results.duplicate_ratio = config.transaction_config.duplicate_ratio;
+        results.duplicate_variant_share = config.transaction_config.duplicate_variant_share;
+        results.duplicate_delay_blocks = config.transaction_config.duplicate_delay_blocks;
+        results.malformed_ratio = config.transaction_config.malformed_ratio;
+        results.amount_distribution = config.transaction_config.amount_distribution;
+        results.chain_ratio_cats = config.transaction_config.chain_ratio_cats.clone();
+        results.chain_allow_cat_pending_dependencies = config.transaction_config.allow_cat_pending_dependencies_per_chain(2);
         results.start_time = Instant::now();
+        results.started_at = chrono::Utc::now();
 
         // Log configuration
         let start_time = Local::now();
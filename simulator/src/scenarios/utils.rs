@@ -1,6 +1,31 @@
 use std::process::Command;
 use hyperplane::utils::logging;
-use crate::config::ConfigError;
+use crate::config::{ConfigError, LoggingConfig};
+
+/// Directory (inside a run's directory) the logs of the run are captured in
+pub const RUN_LOGS_DIR: &str = "logs";
+
+/// Captures the logs of a run in its directory, one file per component, if the config asks for it
+///
+/// The capture lasts until the returned guard is dropped. A capture that cannot be started is
+/// reported and the run logs as configured.
+///
+/// # Arguments
+/// * `logging_config` - The logging configuration of the simulation
+/// * `run_dir` - The directory the run is saved to
+pub fn capture_run_logs(logging_config: &LoggingConfig, run_dir: &str) -> Option<logging::LogCaptureGuard> {
+    if !logging_config.capture_run_logs {
+        return None;
+    }
+    let logs_dir = format!("{}/{}", run_dir, RUN_LOGS_DIR);
+    match logging::capture_to_directory(&logs_dir) {
+        Ok(guard) => Some(guard),
+        Err(e) => {
+            eprintln!("Failed to capture the logs of the run in {}: {}", logs_dir, e);
+            None
+        }
+    }
+}
 
 /// Runs a simulation function with automatic plotting
 pub async fn run_simulation_with_plotting<F, Fut>(
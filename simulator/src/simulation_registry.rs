@@ -11,20 +11,31 @@ use tokio::sync::Mutex;
 
 use crate::scenarios::{
     sim_simple,
+    sim_replay,
+    sim_compare,
+    sim_failover,
+    sim_block_interval_ramp,
+    sim_balance_exhaustion,
+    sim_sweep_hs_chain_count,
+    sim_sweep_custom,
     sim_sweep_cat_ratio,
     sim_sweep_tpb_constant_cats_per_block,
     sim_sweep_zipf,
     sim_sweep_chain_delay,
     sim_sweep_total_block_number,
     sim_sweep_cat_lifetime,
+    sim_sweep_cat_lifetime_delay_ratio,
+    sim_sweep_block_capacity,
     sim_sweep_block_interval_constant_block_delay,
     sim_sweep_block_interval_constant_time_delay,
     sim_sweep_block_interval_all_scaled,
     sim_sweep_cat_pending_dependencies,
+    sim_sweep_mixed_cat_pending_dependencies,
     run_all_tests::run_all_tests,
 };
 
 use super::interface::SimulationType;
+use crate::config_schema::{ConfigSchema, SchemaReport};
 
 // ------------------------------------------------------------------------------------------------
 // Data Structures
@@ -36,10 +47,12 @@ use super::interface::SimulationType;
 /// - `name`: Human-readable name for the simulation
 /// - `run_fn`: Function that executes the simulation asynchronously
 /// - `plot_script`: Path to the Python script that generates plots from results
+/// - `config_schema`: Declared structure of the simulation's config file, if it reads one
 pub struct SimulationConfig {
     pub name: &'static str,
     pub run_fn: Box<dyn Fn() -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<(), String>>>> + Send + Sync>,
     pub plot_script: &'static str,
+    pub config_schema: Option<ConfigSchema>,
 }
 
 /// Registry that holds all simulation configurations
@@ -69,6 +82,9 @@ impl SimulationRegistry {
         let (sim_type, sim_config) = sim_simple::simulation::register();
         simulations.insert(sim_type, sim_config);
         
+        let (sim_type, sim_config) = sim_sweep_block_capacity::simulation::register();
+        simulations.insert(sim_type, sim_config);
+        
         let (sim_type, sim_config) = sim_sweep_block_interval_all_scaled::simulation::register();
         simulations.insert(sim_type, sim_config);
         
@@ -81,9 +97,15 @@ impl SimulationRegistry {
         let (sim_type, sim_config) = sim_sweep_cat_lifetime::simulation::register();
         simulations.insert(sim_type, sim_config);
         
+        let (sim_type, sim_config) = sim_sweep_cat_lifetime_delay_ratio::simulation::register();
+        simulations.insert(sim_type, sim_config);
+        
         let (sim_type, sim_config) = sim_sweep_cat_pending_dependencies::simulation::register();
         simulations.insert(sim_type, sim_config);
         
+        let (sim_type, sim_config) = sim_sweep_mixed_cat_pending_dependencies::simulation::register();
+        simulations.insert(sim_type, sim_config);
+        
         let (sim_type, sim_config) = sim_sweep_cat_ratio::simulation::register();
         simulations.insert(sim_type, sim_config);
         
@@ -99,6 +121,27 @@ impl SimulationRegistry {
         let (sim_type, sim_config) = sim_sweep_zipf::simulation::register();
         simulations.insert(sim_type, sim_config);
         
+        let (sim_type, sim_config) = sim_replay::simulation::register();
+        simulations.insert(sim_type, sim_config);
+        
+        let (sim_type, sim_config) = sim_compare::simulation::register();
+        simulations.insert(sim_type, sim_config);
+        
+        let (sim_type, sim_config) = sim_failover::simulation::register();
+        simulations.insert(sim_type, sim_config);
+        
+        let (sim_type, sim_config) = sim_block_interval_ramp::simulation::register();
+        simulations.insert(sim_type, sim_config);
+        
+        let (sim_type, sim_config) = sim_balance_exhaustion::simulation::register();
+        simulations.insert(sim_type, sim_config);
+        
+        let (sim_type, sim_config) = sim_sweep_hs_chain_count::simulation::register();
+        simulations.insert(sim_type, sim_config);
+        
+        let (sim_type, sim_config) = sim_sweep_custom::simulation::register();
+        simulations.insert(sim_type, sim_config);
+        
         // Register run all tests (still hardcoded since it doesn't have a register function)
         simulations.insert(SimulationType::RunAllTests, SimulationConfig {
             name: "All Tests",
@@ -107,6 +150,7 @@ impl SimulationRegistry {
                     .map_err(|e| format!("All tests failed: {}", e))
             })),
             plot_script: "", // No plot script for run all tests
+            config_schema: None, // Each test checks its own configuration
         });
         
         Self { simulations }
@@ -119,6 +163,41 @@ impl SimulationRegistry {
         self.simulations.get(simulation_type)
     }
     
+    /// Checks the configuration file of a simulation type against its declared schema.
+    /// 
+    /// Returns the warnings (parameters that are not declared) if the configuration is valid and
+    /// the errors otherwise. Simulations without a schema always pass.
+    pub fn validate_config(&self, simulation_type: &SimulationType) -> Result<Vec<String>, String> {
+        let Some(schema) = self.simulations.get(simulation_type).and_then(|config| config.config_schema) else {
+            return Ok(Vec::new());
+        };
+        let report = schema.validate_file()?;
+        if report.is_valid() {
+            Ok(report.warnings)
+        } else {
+            Err(report.errors.join("; "))
+        }
+    }
+    
+    /// Checks the configuration files of all simulations with a schema, ordered by name.
+    pub fn validate_all_configs(&self) -> Vec<(&'static str, Result<SchemaReport, String>)> {
+        let mut reports: Vec<_> = self.simulations.values()
+            .filter_map(|config| config.config_schema.map(|schema| (config.name, schema.validate_file())))
+            .collect();
+        reports.sort_by_key(|(name, _)| *name);
+        reports
+    }
+    
+    /// Renders the declared parameters of all simulations as Markdown, ordered by name.
+    pub fn render_config_docs(&self) -> String {
+        let mut configs: Vec<&SimulationConfig> = self.simulations.values().collect();
+        configs.sort_by_key(|config| config.name);
+        let sections: Vec<String> = configs.iter()
+            .filter_map(|config| config.config_schema.map(|schema| schema.render_markdown(config.name)))
+            .collect();
+        format!("# Simulation Parameters\n\n{}", sections.join("\n"))
+    }
+    
     /// Retrieves the plot script path for a specific simulation type.
     /// 
     /// Returns `None` if the simulation type is not registered or has no plot script.
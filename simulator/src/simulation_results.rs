@@ -2,13 +2,41 @@
 //! 
 //! Handles saving simulation data to JSON files for analysis.
 
-use std::time::Instant;
+use std::time::{Duration, Instant};
+use chrono::{DateTime, Utc};
 use std::fs;
 use serde_json;
 use crate::account_selection::AccountSelectionStats;
+use crate::config::{AmountDistribution, CatFanoutWeight, CatChainSelection, HigParameterChange, default_cat_fanout};
+use crate::hig_control::HigParameterUpdate;
+use crate::fault_orchestrator::{ScheduledFault, ExecutedFault};
+use crate::block_interval_ramp::{BlockIntervalChange, AppliedBlockIntervalChange};
+use crate::malformed::MalformedCounts;
+use crate::invariants::BalanceInvariant;
+use crate::watchdog::WatchdogStall;
+use crate::divergence::{SubmittedCat, StateDivergenceReport};
+use crate::slowest::{SlowestTransactionsReport, SLOWEST_TRANSACTIONS_FILE};
+use crate::latency_breakdown::{LatencyBreakdownReport, LATENCY_BREAKDOWN_FILE};
+use crate::conflicts::{ConflictRateReport, CONFLICT_RATES_FILE};
+use crate::decision_latency::{HsDecisionLatencyReport, HS_DECISION_LATENCY_FILE};
+use crate::cat_outcome_buckets::{CatOutcomeReport, CAT_OUTCOME_BUCKETS_FILE};
+use crate::chain_pair_matrix::{ChainPairMatrix, CHAIN_PAIR_MATRIX_FILE};
+use crate::workload::{RecordedSubmission, save_workload};
+use crate::state_roots::{STATE_ROOTS_FILE, FINAL_STATES_FILE};
+use crate::metadata::{RunMetadata, METADATA_FILE};
+use crate::results_schema;
+use crate::export::{TransactionRecord, block_records, save_parquet};
 use hyperplane::utils::logging;
+use hyperplane::hyper_ig::{DelayDistribution, ProtocolViolationPolicy, SubblockTiming, KeyLockWait};
+use hyperplane::confirmation_layer::{ChainStats, BlockUtilization, BlockIntervalJitter};
+use crate::stats::{ProcessingTimeHistogram, CatTimeHistogram, RateAnomaly, REALIZED_CHAIN_DELAYS_FILE, INTER_BLOCK_TIMES_FILE, key_lock_waits_to_json, inter_block_times_to_json, pending_cats_to_json, realized_chain_delays_to_json, mean_duration_ms, percentile_ms};
+use hyperplane::hyper_scheduler::{DecisionLatency, PendingCAT, HyperSchedulerMetrics};
+use hyperplane::confirmation_layer::BlockOrdering;
+use hyperplane::harness::NetworkHealth;
+use hyperplane::types::NetworkSpec;
 use sysinfo::System;
 use std::sync::Mutex;
+use std::collections::{BTreeMap, HashMap};
 use lazy_static::lazy_static;
 
 // ------------------------------------------------------------------------------------------------
@@ -17,16 +45,66 @@ use lazy_static::lazy_static;
 
 lazy_static! {
     static ref SYSTEM: Mutex<System> = Mutex::new(System::new_all());
+    /// CPU time (in clock ticks) of each runtime worker thread at the previous sample, keyed by thread id
+    static ref WORKER_CPU_SAMPLE: Mutex<Option<(Instant, HashMap<u32, u64>)>> = Mutex::new(None);
 }
 
+/// Name of the file (inside a run's data directory) the aggregated metrics are flushed to during a run
+pub const PARTIAL_RESULTS_FILE: &str = "partial_results.json";
+
+/// Name prefix of the tokio runtime worker threads (thread names are truncated to 15 characters)
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+const RUNTIME_WORKER_THREAD_PREFIX: &str = "tokio-runtime-w";
+
 fn get_system() -> &'static Mutex<System> {
     &SYSTEM
 }
 
+/// Reads the CPU time (user + system, in clock ticks) of every tokio runtime worker thread
+#[cfg(target_os = "linux")]
+fn read_worker_cpu_ticks() -> HashMap<u32, u64> {
+    let mut ticks = HashMap::new();
+    let Ok(entries) = fs::read_dir("/proc/self/task") else { return ticks };
+
+    for entry in entries.flatten() {
+        let Ok(tid) = entry.file_name().to_string_lossy().parse::<u32>() else { continue };
+        let Ok(stat) = fs::read_to_string(entry.path().join("stat")) else { continue };
+
+        // The thread name is enclosed in parentheses and may itself contain spaces
+        let (Some(name_start), Some(name_end)) = (stat.find('('), stat.rfind(')')) else { continue };
+        if !stat[name_start + 1..name_end].starts_with(RUNTIME_WORKER_THREAD_PREFIX) {
+            continue;
+        }
+
+        // utime and stime are the 14th and 15th fields, i.e. the 12th and 13th after the name
+        let fields: Vec<&str> = stat[name_end + 1..].split_whitespace().collect();
+        let utime = fields.get(11).and_then(|v| v.parse::<u64>().ok());
+        let stime = fields.get(12).and_then(|v| v.parse::<u64>().ok());
+        if let (Some(utime), Some(stime)) = (utime, stime) {
+            ticks.insert(tid, utime + stime);
+        }
+    }
+
+    ticks
+}
+
 // ------------------------------------------------------------------------------------------------
 // Data Structures
 // ------------------------------------------------------------------------------------------------
 
+/// Final status counts of one chain split by transaction origin, each as (pending, success, failure)
+#[derive(Debug, Clone, Default)]
+pub struct StatusCountsBreakdown {
+    /// CAT counts per number of constituent chains
+    pub cats_by_fanout: BTreeMap<usize, (u64, u64, u64)>,
+    /// CATs that found a key locked by a pending transaction at least once
+    pub cats_ever_blocked: (u64, u64, u64),
+    pub cats_never_blocked: (u64, u64, u64),
+    /// Regular transactions that waited on a locked key at least once
+    pub regular_ever_blocked: (u64, u64, u64),
+    pub regular_never_blocked: (u64, u64, u64),
+}
+
 /// Stores all simulation results and statistics
 #[derive(Debug, Clone)]
 pub struct SimulationResults {
@@ -46,7 +124,49 @@ pub struct SimulationResults {
     pub cat_lifetime: u64,
     pub initialization_wait_blocks: u64,
     pub transaction_submission_frequency: u64,  // How many times per block to check for transaction submission
+    pub network: NetworkSpec,  // Chains of the simulated network, in chain order
     pub chain_delays: Vec<f64>,  // Chain delays in blocks
+    pub chain_delay_distribution: DelayDistribution,  // Distribution of the proposal delays around the chain delays (blocks)
+    pub hs_decision_latency: DecisionLatency,  // HS decision latency in blocks
+    pub cl_block_ordering: BlockOrdering,  // Order of transactions within a CL block
+    pub cl_align_cat_parts: bool,  // Whether the CL aligns the parts of a CAT to the same block height
+    pub cl_block_max_transactions: u64,  // Maximum number of CL transactions per CL block (0 = unlimited)
+    pub cl_block_max_bytes: u64,  // Maximum payload of a CL block in bytes (0 = unlimited)
+    pub cl_block_interval_jitter: BlockIntervalJitter,  // Jitter of the time between CL blocks (blocks)
+    pub hig_release_locks_on_failure: bool,  // Whether the HIGs release the locks of a CAT when proposing Failure
+    pub hig_prioritize_status_updates: bool,  // Whether the HIGs process status updates first within a subblock
+    pub hs_status_update_delays: Vec<f64>,  // Delays of the HS status updates per chain in blocks
+    pub cat_latency_multiplier: f64,  // Factor applied to the link latency of messages on the CAT path
+    pub regular_latency_multiplier: f64,  // Factor applied to the link latency of subblocks with only regular transactions
+    pub hig_parameter_schedule: Vec<HigParameterChange>,  // Changes of the HIG parameters during the run
+    pub hig_parameter_changes: Vec<HigParameterUpdate>,  // HIG parameter changes sent during the run
+    pub fault_timeline: Vec<ScheduledFault>,  // Configured fault events of the run
+    pub block_interval_schedule: Vec<BlockIntervalChange>,  // Configured changes of the CL block interval during the run
+    pub fault_events: Vec<ExecutedFault>,  // Fault events executed during the run
+    pub block_interval_changes: Vec<AppliedBlockIntervalChange>,  // Changes of the CL block interval applied during the run
+    pub rate_anomaly_window_blocks: usize,  // Number of recent blocks the anomaly detector compares a block to
+    pub rate_anomaly_sigma: f64,  // Deviation in standard deviations that flags a block (0 = disabled)
+    pub invariants: Vec<BalanceInvariant>,  // Invariants on the account balances checked during the run
+    pub rate_anomalies: Vec<RateAnomaly>,  // Stretches of blocks at which the TPS or a pending count left its recent window
+    pub cat_part_spread_blocks: f64,  // Maximum delay between the submissions of the parts of a CAT in blocks
+    pub regular_tx_ttl_blocks: u64,  // Blocks after submission at which pending regular transactions expire (0 = never)
+    pub cat_transfers: bool,  // Whether generated CATs are cross-chain transfers
+    pub duplicate_ratio: f64,  // Fraction of the generated CL transactions submitted a second time
+    pub duplicate_variant_share: f64,  // Fraction of the duplicates with the same id but a different payload
+    pub duplicate_delay_blocks: u64,  // Blocks between the submission of a transaction and of its duplicate
+    pub malformed_ratio: f64,  // Fraction of the generated transactions followed by a malformed CL transaction
+    pub amount_distribution: AmountDistribution,  // Distribution of the amounts of the generated sends and transfers
+    pub sink_accounts: usize,  // Number of highest accounts that only receive (0 = every account sends and receives)
+    pub chain_ratio_cats: Vec<f64>,  // CAT ratio of the transactions initiated by each chain (empty = ratio_cats for all chains)
+    pub chain_allow_cat_pending_dependencies: Vec<bool>,  // Whether each chain lets CATs depend on pending transactions
+    pub submission_batch_size: usize,  // Number of transactions handed to the CL at once
+    pub submission_rate_limit_tps: f64,  // Maximum number of transactions submitted per second (0 = unlimited)
+    pub intended_submission_tps: f64,  // Submission rate implied by the target TPB
+    pub achieved_submission_tps: f64,  // Submission rate achieved during the run
+    pub submission_batches: u64,  // Number of batches the transactions were submitted in
+    pub protocol_violation_policy: ProtocolViolationPolicy,
+    pub cat_fanout: Vec<CatFanoutWeight>,  // Configured CAT fanout distribution
+    pub cat_chain_selection: CatChainSelection,
     
     // Chain data - Combined totals (for backward compatibility)
     pub chain_1_pending: Vec<(u64, u64)>,
@@ -70,6 +190,12 @@ pub struct SimulationResults {
     pub chain_1_cat_pending_postponed: Vec<(u64, u64)>,
     pub chain_2_cat_pending_postponed: Vec<(u64, u64)>,
     
+    // Chain data - Detailed CAT failure states (subsets of cat_failure)
+    pub chain_1_cat_timed_out: Vec<(u64, u64)>,
+    pub chain_2_cat_timed_out: Vec<(u64, u64)>,
+    pub chain_1_cat_rejected: Vec<(u64, u64)>,
+    pub chain_2_cat_rejected: Vec<(u64, u64)>,
+    
     // Chain data - Regular transactions
     pub chain_1_regular_pending: Vec<(u64, u64)>,
     pub chain_2_regular_pending: Vec<(u64, u64)>,
@@ -93,6 +219,14 @@ pub struct SimulationResults {
     // CPU usage tracking
     pub cpu_usage: Vec<(u64, f64)>, // (block_height, process_cpu_usage_percent)
     pub total_cpu_usage: Vec<(u64, f64)>, // (block_height, total_system_cpu_usage_percent)
+    pub worker_cpu_usage: Vec<(u64, f64)>, // (block_height, mean_runtime_worker_cpu_usage_percent)
+    pub worker_cpu_usage_max: Vec<(u64, f64)>, // (block_height, busiest_runtime_worker_cpu_usage_percent)
+    
+    // Tokio runtime tracking
+    pub runtime_alive_tasks: Vec<(u64, u64)>, // (block_height, alive_tasks)
+    pub runtime_queue_depth: Vec<(u64, u64)>, // (block_height, queued_tasks)
+    pub runtime_busy: Vec<(u64, f64)>, // (block_height, mean_worker_busy_percent)
+    pub runtime_poll_latency: Vec<(u64, f64)>, // (block_height, mean_poll_time_us)
     
     // Loop steps without transaction issuance tracking
     pub loop_steps_without_tx_issuance: Vec<(u64, u64)>, // (block_height, loop_steps_count)
@@ -103,6 +237,52 @@ pub struct SimulationResults {
     // CL queue length tracking
     pub cl_queue_length: Vec<(u64, u64)>, // (block_height, queue_length)
     
+    // CL mempool age tracking (inclusion delay, in blocks)
+    pub chain_1_mempool_age_p50: Vec<(u64, u64)>, // (block_height, median_age_blocks)
+    pub chain_2_mempool_age_p50: Vec<(u64, u64)>, // (block_height, median_age_blocks)
+    pub chain_1_mempool_age_p99: Vec<(u64, u64)>, // (block_height, p99_age_blocks)
+    pub chain_2_mempool_age_p99: Vec<(u64, u64)>, // (block_height, p99_age_blocks)
+    
+    // HIG pending-proposal queue tracking
+    pub chain_1_proposal_queue_depth: Vec<(u64, u64)>, // (block_height, queued_proposals)
+    pub chain_2_proposal_queue_depth: Vec<(u64, u64)>, // (block_height, queued_proposals)
+    pub chain_1_proposal_queue_age_latency: Vec<(u64, f64)>, // (block_height, oldest_proposal_age_ms)
+    pub chain_2_proposal_queue_age_latency: Vec<(u64, f64)>, // (block_height, oldest_proposal_age_ms)
+    pub dropped_proposals: u64,
+    
+    // Ages of the pending CATs (blocks since each CAT was received), to see how close CATs come to timing out
+    pub chain_1_cat_age_histogram: Vec<(u64, BTreeMap<u64, u64>)>, // (block_height, age_blocks -> pending_cats)
+    pub chain_2_cat_age_histogram: Vec<(u64, BTreeMap<u64, u64>)>, // (block_height, age_blocks -> pending_cats)
+    
+    // Transactions received more than once by the HIGs (indicates a CL bug)
+    pub duplicate_transactions: u64,
+    
+    // CATs the HIGs received with the id of another CAT
+    pub cat_id_collisions: u64,
+    
+    // Duplicates submitted by the generator, of which variants with a different payload
+    pub duplicates_injected: u64,
+    pub duplicate_variants_injected: u64,
+    
+    // Duplicates the CL rejected at submission and dropped from its mempool
+    pub cl_duplicates_rejected: u64,
+    pub cl_duplicates_dropped: u64,
+    
+    // Malformed transactions injected by the generator and their rejections by the CL
+    pub malformed_transactions: MalformedCounts,
+    
+    // Malformed transactions the HIGs skipped in subblocks
+    pub hig_malformed_transactions: u64,
+    
+    // Dependency cycles detected (and broken) by the HIGs
+    pub dependency_cycles: u64,
+    
+    // CATs whose locks the HIGs released on a Failure proposal
+    pub early_lock_releases: u64,
+    
+    // Regular transactions that expired while pending
+    pub regular_tx_expirations: u64,
+    
     // Regular transaction timing metrics
     pub chain_1_regular_tx_avg_latency: Vec<(u64, f64)>, // (block_height, average_latency_ms)
     pub chain_2_regular_tx_avg_latency: Vec<(u64, f64)>, // (block_height, average_latency_ms)
@@ -111,9 +291,96 @@ pub struct SimulationResults {
     pub chain_1_regular_tx_finalized_count: Vec<(u64, u64)>, // (block_height, finalized_count)
     pub chain_2_regular_tx_finalized_count: Vec<(u64, u64)>, // (block_height, finalized_count)
     
+    // Protocol violations (e.g. Success status update for a failed CAT)
+    pub chain_1_protocol_violations: Vec<(u64, u64)>, // (block_height, violation_count)
+    pub chain_2_protocol_violations: Vec<(u64, u64)>, // (block_height, violation_count)
+    
+    // Realized CAT fanout: number of constituent chains -> number of generated CATs
+    pub cat_fanout_counts: BTreeMap<usize, u64>,
+    // Number of generated CATs per initiating chain
+    pub cat_initiations_per_chain: BTreeMap<String, u64>,
+    // Transactions the CL included per chain, to show load imbalance between chains
+    pub cl_chain_stats: BTreeMap<String, ChainStats>,
+    // Contents of each CL block relative to its capacity
+    pub cl_block_utilization: Vec<BlockUtilization>,
+    // Height of each CL block and the time since the block before it
+    pub cl_inter_block_times: Vec<(u64, Duration)>,
+    // Final status counts split by CAT fanout and by lock contention
+    pub chain_1_status_breakdown: StatusCountsBreakdown,
+    pub chain_2_status_breakdown: StatusCountsBreakdown,
+    
+    // Submitted CATs and the post-run divergence analysis over them
+    pub submitted_cats: Vec<SubmittedCat>,
+    pub state_divergence: StateDivergenceReport,
+    
+    // State root of each chain after every block, for detecting nondeterminism between replays
+    pub chain_1_state_roots: Vec<(u64, String)>, // (block_height, state_root)
+    pub chain_2_state_roots: Vec<(u64, String)>, // (block_height, state_root)
+    // Account balances of each chain at the end of the run, for diffing replays per account
+    pub chain_1_final_state: HashMap<String, i64>,
+    pub chain_2_final_state: HashMap<String, i64>,
+    
+    // Time each HIG spent per subblock (lock wait, execution, proposal queuing)
+    pub chain_1_subblock_timings: Vec<(u64, SubblockTiming)>,
+    pub chain_2_subblock_timings: Vec<(u64, SubblockTiming)>,
+    
+    // Time transactions waited for each locked key, hottest first
+    pub chain_1_key_lock_waits: Vec<KeyLockWait>,
+    pub chain_2_key_lock_waits: Vec<KeyLockWait>,
+    
+    // Time each proposal of a HIG spent in its queue before it was sent to the HS, in send order
+    pub chain_1_realized_delays: Vec<Duration>,
+    pub chain_2_realized_delays: Vec<Duration>,
+    
+    // CATs the HS still waited on each chain's proposal for at the end of the run, longest waiting first
+    pub cats_awaiting_chain_1: Vec<PendingCAT>,
+    pub cats_awaiting_chain_2: Vec<PendingCAT>,
+    
+    // Internal processing metrics of the HS at the end of the run
+    pub hs_metrics: HyperSchedulerMetrics,
+    
+    // Health of all nodes at the end of the run (None until it was collected)
+    pub node_health: Option<NetworkHealth>,
+    
+    // Slowest regular transactions and CATs of the run with their timelines
+    pub slowest_transactions: SlowestTransactionsReport,
+    
+    // Phases of the latency of each finished transaction
+    pub latency_breakdown: LatencyBreakdownReport,
+    
+    // Time the HS took from the inclusion of each CAT to its status update, by fanout
+    pub hs_decision_latencies: HsDecisionLatencyReport,
+    
+    // Outcome of each CAT with its fanout and longest realized chain delay
+    pub cat_outcomes: CatOutcomeReport,
+    
+    // Number of CATs and success rate between every pair of chains
+    pub chain_pair_matrix: ChainPairMatrix,
+    
+    // Submission schedule of the run, for replaying it against other builds
+    pub recorded_workload: Vec<RecordedSubmission>,
+    
+    // Per-transaction records for the Parquet export (only collected if enabled)
+    pub export_parquet: bool,
+    pub transaction_records: Vec<TransactionRecord>,
+    
+    // Flushing of the aggregated metrics during the run (interval 0 or no directory = never)
+    pub flush_interval_blocks: u64,
+    pub flush_dir: Option<String>,  // Run directory the partial results are written to
+    pub cl_endpoint: Option<String>,  // Address of the external CL the run targeted (None = in-process nodes)
+    
+    // Stall watchdog of the run (thresholds of 0 = disabled) and the stalls it detected
+    pub watchdog_max_lag_blocks: u64,
+    pub watchdog_queue_stall_secs: f64,
+    pub watchdog_abort: bool,
+    pub watchdog_stalls: Vec<WatchdogStall>,
+    
     // Statistics
     pub account_stats: AccountSelectionStats,
     pub start_time: Instant,
+    pub started_at: DateTime<Utc>,  // Wall-clock start of the run, recorded in metadata.json
+    pub master_seed: u64,  // Seed the workload generators of all runs are derived from
+    pub run_seed: u64,  // Seed of the workload generator of this run
 }
 
 // ------------------------------------------------------------------------------------------------
@@ -136,7 +403,49 @@ impl Default for SimulationResults {
             cat_lifetime: 0,
             initialization_wait_blocks: 0,
             transaction_submission_frequency: 10,  // Default to 10 times per block
+            network: NetworkSpec::default(),
             chain_delays: Vec::new(),
+            chain_delay_distribution: DelayDistribution::default(),
+            protocol_violation_policy: ProtocolViolationPolicy::default(),
+            hs_decision_latency: DecisionLatency::default(),
+            cl_block_ordering: BlockOrdering::default(),
+            cl_align_cat_parts: false,
+            cl_block_max_transactions: 0,
+            cl_block_max_bytes: 0,
+            cl_block_interval_jitter: BlockIntervalJitter::default(),
+            hig_release_locks_on_failure: false,
+            hig_prioritize_status_updates: false,
+            hs_status_update_delays: Vec::new(),
+            cat_latency_multiplier: 1.0,
+            regular_latency_multiplier: 1.0,
+            hig_parameter_schedule: Vec::new(),
+            hig_parameter_changes: Vec::new(),
+            fault_timeline: Vec::new(),
+            block_interval_schedule: Vec::new(),
+            fault_events: Vec::new(),
+            block_interval_changes: Vec::new(),
+            rate_anomaly_window_blocks: 0,
+            rate_anomaly_sigma: 0.0,
+            invariants: Vec::new(),
+            rate_anomalies: Vec::new(),
+            cat_part_spread_blocks: 0.0,
+            regular_tx_ttl_blocks: 0,
+            cat_transfers: false,
+            duplicate_ratio: 0.0,
+            duplicate_variant_share: 0.0,
+            duplicate_delay_blocks: 0,
+            malformed_ratio: 0.0,
+            amount_distribution: AmountDistribution::default(),
+            sink_accounts: 0,
+            chain_ratio_cats: Vec::new(),
+            chain_allow_cat_pending_dependencies: Vec::new(),
+            submission_batch_size: 1,
+            submission_rate_limit_tps: 0.0,
+            intended_submission_tps: 0.0,
+            achieved_submission_tps: 0.0,
+            submission_batches: 0,
+            cat_fanout: default_cat_fanout(),
+            cat_chain_selection: CatChainSelection::default(),
             chain_1_pending: Vec::new(),
             chain_2_pending: Vec::new(),
             chain_1_success: Vec::new(),
@@ -153,6 +462,10 @@ impl Default for SimulationResults {
             chain_2_cat_pending_resolving: Vec::new(),
             chain_1_cat_pending_postponed: Vec::new(),
             chain_2_cat_pending_postponed: Vec::new(),
+            chain_1_cat_timed_out: Vec::new(),
+            chain_2_cat_timed_out: Vec::new(),
+            chain_1_cat_rejected: Vec::new(),
+            chain_2_cat_rejected: Vec::new(),
             chain_1_regular_pending: Vec::new(),
             chain_2_regular_pending: Vec::new(),
             chain_1_regular_success: Vec::new(),
@@ -167,17 +480,88 @@ impl Default for SimulationResults {
             total_memory: Vec::new(),
             cpu_usage: Vec::new(),
             total_cpu_usage: Vec::new(),
+            worker_cpu_usage: Vec::new(),
+            worker_cpu_usage_max: Vec::new(),
+            runtime_alive_tasks: Vec::new(),
+            runtime_queue_depth: Vec::new(),
+            runtime_busy: Vec::new(),
+            runtime_poll_latency: Vec::new(),
             loop_steps_without_tx_issuance: Vec::new(),
             block_height_delta: Vec::new(),
             cl_queue_length: Vec::new(),
+            chain_1_mempool_age_p50: Vec::new(),
+            chain_2_mempool_age_p50: Vec::new(),
+            chain_1_mempool_age_p99: Vec::new(),
+            chain_2_mempool_age_p99: Vec::new(),
+            chain_1_proposal_queue_depth: Vec::new(),
+            chain_2_proposal_queue_depth: Vec::new(),
+            chain_1_cat_age_histogram: Vec::new(),
+            chain_2_cat_age_histogram: Vec::new(),
+            chain_1_proposal_queue_age_latency: Vec::new(),
+            chain_2_proposal_queue_age_latency: Vec::new(),
+            dropped_proposals: 0,
+            duplicate_transactions: 0,
+            cat_id_collisions: 0,
+            duplicates_injected: 0,
+            duplicate_variants_injected: 0,
+            cl_duplicates_rejected: 0,
+            cl_duplicates_dropped: 0,
+            malformed_transactions: MalformedCounts::default(),
+            hig_malformed_transactions: 0,
+            dependency_cycles: 0,
+            early_lock_releases: 0,
+            regular_tx_expirations: 0,
             chain_1_regular_tx_avg_latency: Vec::new(),
             chain_2_regular_tx_avg_latency: Vec::new(),
             chain_1_regular_tx_max_latency: Vec::new(),
             chain_2_regular_tx_max_latency: Vec::new(),
             chain_1_regular_tx_finalized_count: Vec::new(),
             chain_2_regular_tx_finalized_count: Vec::new(),
+            chain_1_protocol_violations: Vec::new(),
+            chain_2_protocol_violations: Vec::new(),
+            cat_fanout_counts: BTreeMap::new(),
+            cat_initiations_per_chain: BTreeMap::new(),
+            cl_chain_stats: BTreeMap::new(),
+            cl_block_utilization: Vec::new(),
+            cl_inter_block_times: Vec::new(),
+            chain_1_status_breakdown: StatusCountsBreakdown::default(),
+            chain_2_status_breakdown: StatusCountsBreakdown::default(),
+            submitted_cats: Vec::new(),
+            state_divergence: StateDivergenceReport::default(),
+            chain_1_state_roots: Vec::new(),
+            chain_2_state_roots: Vec::new(),
+            chain_1_final_state: HashMap::new(),
+            chain_2_final_state: HashMap::new(),
+            chain_1_subblock_timings: Vec::new(),
+            chain_2_subblock_timings: Vec::new(),
+            chain_1_key_lock_waits: Vec::new(),
+            chain_2_key_lock_waits: Vec::new(),
+            chain_1_realized_delays: Vec::new(),
+            chain_2_realized_delays: Vec::new(),
+            cats_awaiting_chain_1: Vec::new(),
+            cats_awaiting_chain_2: Vec::new(),
+            hs_metrics: HyperSchedulerMetrics::default(),
+            node_health: None,
+            slowest_transactions: SlowestTransactionsReport::default(),
+            latency_breakdown: LatencyBreakdownReport::default(),
+            hs_decision_latencies: HsDecisionLatencyReport::default(),
+            cat_outcomes: CatOutcomeReport::default(),
+            chain_pair_matrix: ChainPairMatrix::default(),
+            recorded_workload: Vec::new(),
+            export_parquet: false,
+            transaction_records: Vec::new(),
+            flush_interval_blocks: 0,
+            flush_dir: None,
+            cl_endpoint: None,
+            watchdog_max_lag_blocks: 0,
+            watchdog_queue_stall_secs: 0.0,
+            watchdog_abort: false,
+            watchdog_stalls: Vec::new(),
             account_stats: AccountSelectionStats::new(),
             start_time: Instant::now(),
+            started_at: Utc::now(),
+            master_seed: 0,
+            run_seed: 0,
         }
     }
 }
@@ -306,48 +690,330 @@ impl SimulationResults {
         0.0
     }
 
+    /// Gets the CPU usage of the tokio runtime worker threads as percentages
+    ///
+    /// Compares the CPU time of every worker thread with the previous sample, so the first sample
+    /// of a process reports 0. Only supported on Linux; other platforms report 0.
+    ///
+    /// # Returns
+    /// The mean usage over all worker threads and the usage of the busiest worker thread
+    pub fn get_current_worker_cpu_usage() -> (f64, f64) {
+        #[cfg(target_os = "linux")]
+        {
+            let now = Instant::now();
+            let current_ticks = read_worker_cpu_ticks();
+            let ticks_per_second = unsafe { libc::sysconf(libc::_SC_CLK_TCK) } as f64;
+
+            if let Ok(mut sample) = WORKER_CPU_SAMPLE.lock() {
+                let usage = match sample.as_ref() {
+                    Some((sampled_at, previous_ticks)) if ticks_per_second > 0.0 && !current_ticks.is_empty() => {
+                        let elapsed = now.duration_since(*sampled_at).as_secs_f64();
+                        if elapsed > 0.0 {
+                            // Threads started since the previous sample have no baseline and count as idle
+                            let percentages: Vec<f64> = current_ticks.iter().map(|(tid, ticks)| {
+                                let delta = ticks.saturating_sub(previous_ticks.get(tid).copied().unwrap_or(*ticks));
+                                delta as f64 / ticks_per_second / elapsed * 100.0
+                            }).collect();
+                            let mean = percentages.iter().sum::<f64>() / percentages.len() as f64;
+                            let max = percentages.iter().cloned().fold(0.0, f64::max);
+                            (mean, max)
+                        } else {
+                            (0.0, 0.0)
+                        }
+                    }
+                    _ => (0.0, 0.0),
+                };
+                *sample = Some((now, current_ticks));
+                return usage;
+            }
+            (0.0, 0.0)
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            (0.0, 0.0)
+        }
+    }
+
+    /// The number of HS decisions at every block height of the run, including the blocks without decisions
+    fn hs_decisions_series(&self) -> Vec<(u64, u64)> {
+        let (Some((first, _)), Some((last, _))) = (self.cl_queue_length.first(), self.cl_queue_length.last()) else {
+            return Vec::new();
+        };
+        (*first..=*last)
+            .map(|height| (height, self.hs_metrics.decisions_per_block.get(&height).copied().unwrap_or(0)))
+            .collect()
+    }
+
+    /// The times between consecutive CL blocks
+    fn inter_block_gaps(&self) -> Vec<Duration> {
+        self.cl_inter_block_times.iter().map(|(_, gap)| *gap).collect()
+    }
+
+    /// The simulated parameters, as saved in `simulation_stats.json`
+    fn parameters_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "initial_balance": self.initial_balance,
+            "num_accounts": self.num_accounts,
+            "target_tpb": self.target_tpb,
+            "sim_total_block_number": self.sim_total_block_number,
+            "zipf_parameter": self.zipf_parameter,
+            "ratio_cats": self.ratio_cats,
+            "block_interval": self.block_interval,
+            "chain_delays": self.chain_delays.clone(),
+            "chain_delay_distribution": self.chain_delay_distribution,
+            "hs_decision_latency": self.hs_decision_latency,
+            "cl_block_ordering": self.cl_block_ordering,
+            "cl_align_cat_parts": self.cl_align_cat_parts,
+            "cl_block_max_transactions": self.cl_block_max_transactions,
+            "cl_block_max_bytes": self.cl_block_max_bytes,
+            "cl_block_interval_jitter": self.cl_block_interval_jitter,
+            "hig_release_locks_on_failure": self.hig_release_locks_on_failure,
+            "hig_prioritize_status_updates": self.hig_prioritize_status_updates,
+            "hs_status_update_delays": self.hs_status_update_delays.clone(),
+            "cat_latency_multiplier": self.cat_latency_multiplier,
+            "regular_latency_multiplier": self.regular_latency_multiplier,
+            "hig_parameter_schedule": self.hig_parameter_schedule.clone(),
+            "fault_timeline": self.fault_timeline.clone(),
+            "block_interval_schedule": self.block_interval_schedule.clone(),
+            "cat_part_spread_blocks": self.cat_part_spread_blocks,
+            "regular_tx_ttl_blocks": self.regular_tx_ttl_blocks,
+            "cat_transfers": self.cat_transfers,
+            "duplicate_ratio": self.duplicate_ratio,
+            "duplicate_variant_share": self.duplicate_variant_share,
+            "duplicate_delay_blocks": self.duplicate_delay_blocks,
+            "malformed_ratio": self.malformed_ratio,
+            "amount_distribution": self.amount_distribution,
+            "sink_accounts": self.sink_accounts,
+            "chain_ratio_cats": self.chain_ratio_cats.clone(),
+            "chain_allow_cat_pending_dependencies": self.chain_allow_cat_pending_dependencies.clone(),
+            "submission_batch_size": self.submission_batch_size,
+            "submission_rate_limit_tps": self.submission_rate_limit_tps,
+            "rate_anomaly_window_blocks": self.rate_anomaly_window_blocks,
+            "rate_anomaly_sigma": self.rate_anomaly_sigma,
+            "invariants": self.invariants.clone(),
+            "cl_endpoint": self.cl_endpoint.clone()
+        })
+    }
+
+    /// Writes the aggregated metrics of the run so far to the run directory
+    ///
+    /// The file is replaced atomically, so a run that crashes or is interrupted leaves the metrics
+    /// of its last flush intact. `save_to_directory` removes it once the complete results are saved.
+    /// Does nothing unless the runner set a flush directory.
+    ///
+    /// # Arguments
+    /// * `blocks_completed` - Blocks of the run so far, counted from the start of the transaction submission
+    pub fn flush_partial(&self, blocks_completed: u64) -> Result<(), String> {
+        let Some(base_dir) = &self.flush_dir else { return Ok(()) };
+        let last_count = |series: &Vec<(u64, u64)>| series.last().map(|(_, count)| *count).unwrap_or(0);
+        let mut partial = serde_json::json!({
+            "blocks_completed": blocks_completed,
+            "sim_total_block_number": self.sim_total_block_number,
+            "elapsed_secs": self.start_time.elapsed().as_secs_f64(),
+            "parameters": self.parameters_json(),
+            "results": {
+                "total_transactions": self.transactions_sent,
+                "cat_transactions": self.cat_transactions,
+                "regular_transactions": self.regular_transactions,
+                "chain_1_pending": last_count(&self.chain_1_pending),
+                "chain_1_success": last_count(&self.chain_1_success),
+                "chain_1_failure": last_count(&self.chain_1_failure),
+                "chain_2_pending": last_count(&self.chain_2_pending),
+                "chain_2_success": last_count(&self.chain_2_success),
+                "chain_2_failure": last_count(&self.chain_2_failure),
+                "chain_1_cat_pending": last_count(&self.chain_1_cat_pending),
+                "chain_1_cat_success": last_count(&self.chain_1_cat_success),
+                "chain_1_cat_failure": last_count(&self.chain_1_cat_failure),
+                "chain_2_cat_pending": last_count(&self.chain_2_cat_pending),
+                "chain_2_cat_success": last_count(&self.chain_2_cat_success),
+                "chain_2_cat_failure": last_count(&self.chain_2_cat_failure),
+                "peak_memory_mb": self.memory_usage.iter().map(|(_, bytes)| *bytes).max().unwrap_or(0) as f64 / (1024.0 * 1024.0),
+                "mean_cpu_percent": mean_percent(&self.cpu_usage),
+                "hig_parameter_changes": self.hig_parameter_changes.clone(),
+                "fault_events": self.fault_events.clone()
+            }
+        });
+        results_schema::stamp(&mut partial);
+
+        let data_dir = format!("{}/data", base_dir);
+        fs::create_dir_all(&data_dir).map_err(|e| format!("Failed to create {}: {}", data_dir, e))?;
+        let partial_file = format!("{}/{}", data_dir, PARTIAL_RESULTS_FILE);
+        let temp_file = format!("{}.tmp", partial_file);
+        fs::write(&temp_file, serde_json::to_string_pretty(&partial).expect("Failed to serialize partial results"))
+            .map_err(|e| format!("Failed to write {}: {}", temp_file, e))?;
+        fs::rename(&temp_file, &partial_file).map_err(|e| format!("Failed to replace {}: {}", partial_file, e))?;
+        logging::log("SIMULATOR", &format!("Flushed partial results after {} blocks to {}", blocks_completed, partial_file));
+        Ok(())
+    }
+
     /// Saves results to the default directory
     pub async fn save(&self) -> Result<(), String> {
         self.save_to_directory("simulator/results/sim_simple").await
     }
 
     /// Saves all simulation data to JSON files in the specified directory
+    ///
+    /// A run against an external CL only has the CL side of the statistics, so only that is saved,
+    /// see `save_cl_results_to_directory`.
     pub async fn save_to_directory(&self, base_dir: &str) -> Result<(), String> {
+        if let Some(cl_endpoint) = &self.cl_endpoint {
+            return self.save_cl_results_to_directory(base_dir, cl_endpoint);
+        }
+
         // Print final statistics
         logging::log("SIMULATOR", "\n=== Simulation Statistics ===");
         logging::log("SIMULATOR", &format!("Total Transactions: {}", self.transactions_sent));
         logging::log("SIMULATOR", &format!("CAT Transactions: {}", self.cat_transactions));
         logging::log("SIMULATOR", &format!("Regular Transactions: {}", self.regular_transactions));
         logging::log("SIMULATOR", &format!("Actual TPS: {:.2}", self.transactions_sent as f64 / self.start_time.elapsed().as_secs_f64()));
+        let inter_block_gaps = self.inter_block_gaps();
+        logging::log("SIMULATOR", &format!("Inter-block time: mean {:.1}ms, p50 {:.1}ms, p99 {:.1}ms, max {:.1}ms (interval {:.1}ms)",
+            mean_duration_ms(&inter_block_gaps), percentile_ms(&inter_block_gaps, 50.0), percentile_ms(&inter_block_gaps, 99.0),
+            percentile_ms(&inter_block_gaps, 100.0), self.block_interval * 1000.0));
+        logging::log("SIMULATOR", "===========================");
+        
+        // Print the state divergence report
+        logging::log("SIMULATOR", "\n=== State Divergence Report ===");
+        for line in self.state_divergence.summary_lines() {
+            logging::log("SIMULATOR", &line);
+        }
+        logging::log("SIMULATOR", "===============================");
+        
+        // Print the slowest transactions
+        logging::log("SIMULATOR", "\n=== Slowest Transactions ===");
+        for line in self.slowest_transactions.summary_lines() {
+            logging::log("SIMULATOR", &line);
+        }
+        logging::log("SIMULATOR", "============================");
+        
+        // Print where the time of the finished transactions went
+        logging::log("SIMULATOR", "\n=== Latency Breakdown ===");
+        for line in self.latency_breakdown.summary_lines() {
+            logging::log("SIMULATOR", &line);
+        }
+        logging::log("SIMULATOR", "=========================");
+        
+        // Print the time the HS took to resolve the CATs
+        logging::log("SIMULATOR", "\n=== HS Decision Latency ===");
+        for line in self.hs_decision_latencies.summary_lines() {
+            logging::log("SIMULATOR", &line);
+        }
         logging::log("SIMULATOR", "===========================");
         
+        // Print the CAT outcomes by fanout and chain delay
+        logging::log("SIMULATOR", "\n=== CAT Outcomes by Fanout and Chain Delay ===");
+        for line in self.cat_outcomes.summary_lines(self.block_interval) {
+            logging::log("SIMULATOR", &line);
+        }
+        logging::log("SIMULATOR", "==============================================");
+        
+        // Print the chain pairs with the lowest CAT success rate
+        logging::log("SIMULATOR", "\n=== CAT Success by Chain Pair ===");
+        for line in self.chain_pair_matrix.summary_lines() {
+            logging::log("SIMULATOR", &line);
+        }
+        logging::log("SIMULATOR", "=================================");
+        
+        // Compare the measured conflict rates to the analytic estimate
+        let conflict_rates = ConflictRateReport::from_results(self);
+        logging::log("SIMULATOR", "\n=== Conflict Rates ===");
+        for line in conflict_rates.summary_lines() {
+            logging::log("SIMULATOR", &line);
+        }
+        logging::log("SIMULATOR", "======================");
+        if conflict_rates.has_discrepancies() {
+            eprintln!("Warning: measured conflict rates differ from the analytic estimate, see {}", CONFLICT_RATES_FILE);
+        }
+        
         // Save statistics to JSON file
-        let stats = serde_json::json!({
-            "parameters": {
-                "initial_balance": self.initial_balance,
-                "num_accounts": self.num_accounts,
-                "target_tpb": self.target_tpb,
-                "sim_total_block_number": self.sim_total_block_number,
-                "zipf_parameter": self.zipf_parameter,
-                "ratio_cats": self.ratio_cats,
-                "block_interval": self.block_interval,
-                "chain_delays": self.chain_delays.clone()
-            },
+        let mut stats = serde_json::json!({
+            "parameters": self.parameters_json(),
             "results": {
                 "total_transactions": self.transactions_sent,
                 "cat_transactions": self.cat_transactions,
-                "regular_transactions": self.regular_transactions
+                "regular_transactions": self.regular_transactions,
+                "peak_memory_mb": self.memory_usage.iter().map(|(_, bytes)| *bytes).max().unwrap_or(0) as f64 / (1024.0 * 1024.0),
+                "mean_cpu_percent": mean_percent(&self.cpu_usage),
+                "mean_worker_cpu_percent": mean_percent(&self.worker_cpu_usage),
+                "mean_hig_processing_ms": mean_processing_ms(&[&self.chain_1_subblock_timings, &self.chain_2_subblock_timings]),
+                "max_pending_cat_age_blocks": self.chain_1_cat_age_histogram.iter().chain(&self.chain_2_cat_age_histogram).filter_map(|(_, ages)| ages.keys().next_back().copied()).max().unwrap_or(0),
+                "max_proposal_queue_depth": self.chain_1_proposal_queue_depth.iter().chain(&self.chain_2_proposal_queue_depth).map(|(_, depth)| *depth).max().unwrap_or(0),
+                "dropped_proposals": self.dropped_proposals,
+                "duplicate_transactions": self.duplicate_transactions,
+                "cat_id_collisions": self.cat_id_collisions,
+                "duplicates_injected": self.duplicates_injected,
+                "duplicate_variants_injected": self.duplicate_variants_injected,
+                "cl_duplicates_rejected": self.cl_duplicates_rejected,
+                "cl_duplicates_dropped": self.cl_duplicates_dropped,
+                "malformed_transactions": self.malformed_transactions.clone(),
+                "malformed_accepted_by_cl": self.malformed_transactions.accepted_by_cl,
+                "hig_malformed_transactions": self.hig_malformed_transactions,
+                "dependency_cycles": self.dependency_cycles,
+                "early_lock_releases": self.early_lock_releases,
+                "regular_tx_expirations": self.regular_tx_expirations,
+                "cat_initiations_per_chain": self.cat_initiations_per_chain.clone(),
+                "cl_chain_throughput": self.cl_chain_throughput_json(),
+                "mean_block_utilization": mean_utilization(&self.cl_block_utilization, BlockUtilization::transaction_utilization),
+                "mean_block_byte_utilization": mean_utilization(&self.cl_block_utilization, BlockUtilization::byte_utilization),
+                "mean_block_serialized_bytes": mean_serialized_bytes(&self.cl_block_utilization),
+                "mean_cl_bytes_per_second": if self.block_interval > 0.0 { mean_serialized_bytes(&self.cl_block_utilization) / self.block_interval } else { 0.0 },
+                "status_breakdown": {
+                    "chain_1": status_breakdown_to_json(&self.chain_1_status_breakdown),
+                    "chain_2": status_breakdown_to_json(&self.chain_2_status_breakdown)
+                },
+                "intended_submission_tps": self.intended_submission_tps,
+                "achieved_submission_tps": self.achieved_submission_tps,
+                "submission_batches": self.submission_batches,
+                "hig_parameter_changes": self.hig_parameter_changes.clone(),
+                "fault_events": self.fault_events.clone(),
+                "block_interval_changes": self.block_interval_changes.clone(),
+                "rate_anomaly_count": self.rate_anomalies.len(),
+                "rate_anomalies": self.rate_anomalies.clone(),
+                "watchdog_stall_count": self.watchdog_stalls.len(),
+                "watchdog_stalls": self.watchdog_stalls.clone(),
+                "regular_tx_avg_latency_ms": final_mean_latency_ms(&[&self.chain_1_regular_tx_avg_latency, &self.chain_2_regular_tx_avg_latency]),
+                "mean_latency_breakdown": self.latency_breakdown.means_json(),
+                "total_lock_wait_ms": total_lock_wait_ms(&[&self.chain_1_key_lock_waits, &self.chain_2_key_lock_waits]),
+                "hottest_key_wait_share": hottest_key_wait_share(&[&self.chain_1_key_lock_waits, &self.chain_2_key_lock_waits]),
+                "cats_awaiting_chain_1": self.cats_awaiting_chain_1.len(),
+                "cats_awaiting_chain_2": self.cats_awaiting_chain_2.len(),
+                "hs_proposals_chain_1": self.network.chain(1).and_then(|chain_id| self.hs_metrics.proposals_received.get(chain_id)).copied().unwrap_or(0),
+                "hs_proposals_chain_2": self.network.chain(2).and_then(|chain_id| self.hs_metrics.proposals_received.get(chain_id)).copied().unwrap_or(0),
+                "hs_decisions": self.hs_metrics.decisions,
+                "hs_decisions_per_block": mean_count(&self.hs_decisions_series()),
+                "hs_decisions_per_block_max": self.hs_decisions_series().iter().map(|(_, count)| *count).max().unwrap_or(0),
+                "mean_proposal_spread_ms": mean_duration_ms(&self.hs_metrics.proposal_spreads),
+                "mean_pending_cat_age_ms": mean_duration_ms(&self.hs_metrics.pending_cat_ages),
+                "mean_hs_inclusion_to_decision_ms": self.hs_decision_latencies.mean_inclusion_to_decision_ms(),
+                "mean_hs_decision_to_submission_ms": self.hs_decision_latencies.mean_decision_to_submission_ms(),
+                "chain_1_realized_delay_p99_ms": percentile_ms(&self.chain_1_realized_delays, 99.0),
+                "chain_2_realized_delay_p99_ms": percentile_ms(&self.chain_2_realized_delays, 99.0),
+                "mean_inter_block_time_ms": mean_duration_ms(&self.inter_block_gaps()),
+                "inter_block_time_p99_ms": percentile_ms(&self.inter_block_gaps(), 99.0)
             }
         });
 
         // Create results directories if they don't exist
         fs::create_dir_all(&format!("{}/data", base_dir)).expect("Failed to create results directory");
 
-        // Save simulation stats
-        let stats_file = format!("{}/data/simulation_stats.json", base_dir);
+        // Save simulation stats, stamped with the schema version they are written in
+        results_schema::stamp(&mut stats);
+        let stats_file = format!("{}/data/{}", base_dir, results_schema::SIMULATION_STATS_FILE);
         fs::write(&stats_file, serde_json::to_string_pretty(&stats).expect("Failed to serialize stats")).map_err(|e| e.to_string())?;
         logging::log("SIMULATOR", &format!("Saved simulation statistics to {}", stats_file));
 
+        // Save the provenance of the run
+        let metadata = RunMetadata::collect(&stats["parameters"], self.master_seed, self.run_seed, self.started_at);
+        metadata.save(&format!("{}/data", base_dir))?;
+        logging::log("SIMULATOR", &format!("Saved run metadata to {}/data/{}", base_dir, METADATA_FILE));
+
+        // Add the run to the index of completed runs; the results are complete without it
+        match crate::run_index::record_run(base_dir, &metadata, self) {
+            Ok(entry) => logging::log("SIMULATOR", &format!("Recorded run {} in {}", entry.id, crate::run_index::RUN_INDEX_FILE)),
+            Err(e) => eprintln!("Warning: failed to record the run in the run index: {}", e),
+        }
+
         // Save pending transactions data from chain 1
         let pending_txs_chain_1 = serde_json::json!({
             "chain_1_pending": self.chain_1_pending.iter().map(|(height, count)| {
@@ -567,6 +1233,56 @@ impl SimulationResults {
         fs::write(&cat_pending_postponed_file_chain_2, serde_json::to_string_pretty(&cat_pending_postponed_txs_chain_2).expect("Failed to serialize CAT pending postponed transactions")).map_err(|e| e.to_string())?;
         logging::log("SIMULATOR", &format!("Saved CAT pending postponed transactions data to {}", cat_pending_postponed_file_chain_2));
 
+        // Save detailed CAT failure states data from chain 1
+        let cat_timed_out_txs_chain_1 = serde_json::json!({
+            "chain_1_cat_timed_out": self.chain_1_cat_timed_out.iter().map(|(height, count)| {
+                serde_json::json!({
+                    "height": height,
+                    "count": count
+                })
+            }).collect::<Vec<_>>()
+        });
+        let cat_timed_out_file_chain_1 = format!("{}/data/cat_timed_out_transactions_chain_1.json", base_dir);
+        fs::write(&cat_timed_out_file_chain_1, serde_json::to_string_pretty(&cat_timed_out_txs_chain_1).expect("Failed to serialize CAT timed out transactions")).map_err(|e| e.to_string())?;
+        logging::log("SIMULATOR", &format!("Saved CAT timed out transactions data to {}", cat_timed_out_file_chain_1));
+
+        let cat_rejected_txs_chain_1 = serde_json::json!({
+            "chain_1_cat_rejected": self.chain_1_cat_rejected.iter().map(|(height, count)| {
+                serde_json::json!({
+                    "height": height,
+                    "count": count
+                })
+            }).collect::<Vec<_>>()
+        });
+        let cat_rejected_file_chain_1 = format!("{}/data/cat_rejected_transactions_chain_1.json", base_dir);
+        fs::write(&cat_rejected_file_chain_1, serde_json::to_string_pretty(&cat_rejected_txs_chain_1).expect("Failed to serialize CAT rejected transactions")).map_err(|e| e.to_string())?;
+        logging::log("SIMULATOR", &format!("Saved CAT rejected transactions data to {}", cat_rejected_file_chain_1));
+
+        // Save detailed CAT failure states data from chain 2
+        let cat_timed_out_txs_chain_2 = serde_json::json!({
+            "chain_2_cat_timed_out": self.chain_2_cat_timed_out.iter().map(|(height, count)| {
+                serde_json::json!({
+                    "height": height,
+                    "count": count
+                })
+            }).collect::<Vec<_>>()
+        });
+        let cat_timed_out_file_chain_2 = format!("{}/data/cat_timed_out_transactions_chain_2.json", base_dir);
+        fs::write(&cat_timed_out_file_chain_2, serde_json::to_string_pretty(&cat_timed_out_txs_chain_2).expect("Failed to serialize CAT timed out transactions")).map_err(|e| e.to_string())?;
+        logging::log("SIMULATOR", &format!("Saved CAT timed out transactions data to {}", cat_timed_out_file_chain_2));
+
+        let cat_rejected_txs_chain_2 = serde_json::json!({
+            "chain_2_cat_rejected": self.chain_2_cat_rejected.iter().map(|(height, count)| {
+                serde_json::json!({
+                    "height": height,
+                    "count": count
+                })
+            }).collect::<Vec<_>>()
+        });
+        let cat_rejected_file_chain_2 = format!("{}/data/cat_rejected_transactions_chain_2.json", base_dir);
+        fs::write(&cat_rejected_file_chain_2, serde_json::to_string_pretty(&cat_rejected_txs_chain_2).expect("Failed to serialize CAT rejected transactions")).map_err(|e| e.to_string())?;
+        logging::log("SIMULATOR", &format!("Saved CAT rejected transactions data to {}", cat_rejected_file_chain_2));
+
         // Save regular pending transactions data from chain 1
         let regular_pending_txs_chain_1 = serde_json::json!({
             "chain_1_regular_pending": self.chain_1_regular_pending.iter().map(|(height, count)| {
@@ -671,31 +1387,8 @@ impl SimulationResults {
         fs::write(&locked_keys_file_chain_2, serde_json::to_string_pretty(&locked_keys_chain_2).expect("Failed to serialize locked keys")).map_err(|e| e.to_string())?;
         logging::log("SIMULATOR", &format!("Saved locked keys data to {}", locked_keys_file_chain_2));
 
-        // Save transactions per block data from chain 1
-        let tx_per_block_chain_1 = serde_json::json!({
-            "chain_1_tx_per_block": self.chain_1_tx_per_block.iter().map(|(height, count)| {
-                serde_json::json!({
-                    "height": height,
-                    "count": count
-                })
-            }).collect::<Vec<_>>()
-        });
-        let tx_per_block_file_chain_1 = format!("{}/data/tx_per_block_chain_1.json", base_dir);
-        fs::write(&tx_per_block_file_chain_1, serde_json::to_string_pretty(&tx_per_block_chain_1).expect("Failed to serialize transactions per block")).map_err(|e| e.to_string())?;
-        logging::log("SIMULATOR", &format!("Saved transactions per block data to {}", tx_per_block_file_chain_1));
-
-        // Save transactions per block data from chain 2
-        let tx_per_block_chain_2 = serde_json::json!({
-            "chain_2_tx_per_block": self.chain_2_tx_per_block.iter().map(|(height, count)| {
-                serde_json::json!({
-                    "height": height,
-                    "count": count
-                })
-            }).collect::<Vec<_>>()
-        });
-        let tx_per_block_file_chain_2 = format!("{}/data/tx_per_block_chain_2.json", base_dir);
-        fs::write(&tx_per_block_file_chain_2, serde_json::to_string_pretty(&tx_per_block_chain_2).expect("Failed to serialize transactions per block")).map_err(|e| e.to_string())?;
-        logging::log("SIMULATOR", &format!("Saved transactions per block data to {}", tx_per_block_file_chain_2));
+        // Save the series the CL provides
+        self.save_cl_series(base_dir)?;
 
         // Save account selection data to files
         let (sender_json, receiver_json) = self.account_stats.to_json();
@@ -758,44 +1451,155 @@ impl SimulationResults {
         fs::write(&system_total_cpu_file, serde_json::to_string_pretty(&system_total_cpu_data).expect("Failed to serialize system total CPU")).map_err(|e| e.to_string())?;
         logging::log("SIMULATOR", &format!("Saved system total CPU data to {}", system_total_cpu_file));
 
-        // Save loop steps without transaction issuance data
-        let loop_steps_data = serde_json::json!({
-            "loop_steps_without_tx_issuance": self.loop_steps_without_tx_issuance.iter().map(|(height, count)| {
+        // Save runtime worker CPU usage data
+        let worker_cpu_data = serde_json::json!({
+            "worker_cpu": self.worker_cpu_usage.iter().map(|(height, percent)| {
                 serde_json::json!({
                     "height": height,
-                    "count": count
+                    "percent": percent
                 })
             }).collect::<Vec<_>>()
         });
-        let loop_steps_file = format!("{}/data/loop_steps_without_tx_issuance.json", base_dir);
-        fs::write(&loop_steps_file, serde_json::to_string_pretty(&loop_steps_data).expect("Failed to serialize loop steps data")).map_err(|e| e.to_string())?;
-        logging::log("SIMULATOR", &format!("Saved loop steps data to {}", loop_steps_file));
+        let worker_cpu_file = format!("{}/data/worker_cpu.json", base_dir);
+        fs::write(&worker_cpu_file, serde_json::to_string_pretty(&worker_cpu_data).expect("Failed to serialize worker CPU")).map_err(|e| e.to_string())?;
+        logging::log("SIMULATOR", &format!("Saved worker CPU data to {}", worker_cpu_file));
 
-        // Save block height delta data
-        let block_height_delta_data = serde_json::json!({
-            "block_height_delta": self.block_height_delta.iter().map(|(height, delta)| {
+        // Save busiest runtime worker CPU usage data
+        let worker_cpu_max_data = serde_json::json!({
+            "worker_cpu_max": self.worker_cpu_usage_max.iter().map(|(height, percent)| {
                 serde_json::json!({
                     "height": height,
-                    "delta": delta
+                    "percent": percent
                 })
             }).collect::<Vec<_>>()
         });
-        let block_height_delta_file = format!("{}/data/block_height_delta.json", base_dir);
-        fs::write(&block_height_delta_file, serde_json::to_string_pretty(&block_height_delta_data).expect("Failed to serialize block height delta data")).map_err(|e| e.to_string())?;
-        logging::log("SIMULATOR", &format!("Saved block height delta data to {}", block_height_delta_file));
+        let worker_cpu_max_file = format!("{}/data/worker_cpu_max.json", base_dir);
+        fs::write(&worker_cpu_max_file, serde_json::to_string_pretty(&worker_cpu_max_data).expect("Failed to serialize worker CPU max")).map_err(|e| e.to_string())?;
+        logging::log("SIMULATOR", &format!("Saved busiest worker CPU data to {}", worker_cpu_max_file));
 
-        // Save CL queue length data
-        let cl_queue_length_data = serde_json::json!({
-            "cl_queue_length": self.cl_queue_length.iter().map(|(height, count)| {
+        // Save tokio runtime data
+        let runtime_series: [(&str, Vec<serde_json::Value>); 4] = [
+            ("runtime_alive_tasks", self.runtime_alive_tasks.iter().map(|(height, count)| serde_json::json!({"height": height, "count": count})).collect()),
+            ("runtime_queue_depth", self.runtime_queue_depth.iter().map(|(height, count)| serde_json::json!({"height": height, "count": count})).collect()),
+            ("runtime_busy", self.runtime_busy.iter().map(|(height, percent)| serde_json::json!({"height": height, "percent": percent})).collect()),
+            ("runtime_poll_latency", self.runtime_poll_latency.iter().map(|(height, latency)| serde_json::json!({"height": height, "latency": latency})).collect()),
+        ];
+        for (key, series) in runtime_series {
+            let runtime_file = format!("{}/data/{}.json", base_dir, key);
+            fs::write(&runtime_file, serde_json::to_string_pretty(&serde_json::json!({ key: series })).expect("Failed to serialize runtime metrics")).map_err(|e| e.to_string())?;
+        }
+        logging::log("SIMULATOR", &format!("Saved tokio runtime data to {}/data/runtime_*.json", base_dir));
+
+        // Save HIG subblock processing time data (milliseconds)
+        let to_ms = |duration: std::time::Duration| duration.as_secs_f64() * 1000.0;
+        let hig_timing_series: [(&str, &Vec<(u64, SubblockTiming)>, fn(&SubblockTiming) -> std::time::Duration); 6] = [
+            ("chain_1_hig_lock_latency", &self.chain_1_subblock_timings, |t| t.lock_wait),
+            ("chain_2_hig_lock_latency", &self.chain_2_subblock_timings, |t| t.lock_wait),
+            ("chain_1_hig_execution_latency", &self.chain_1_subblock_timings, |t| t.execution),
+            ("chain_2_hig_execution_latency", &self.chain_2_subblock_timings, |t| t.execution),
+            ("chain_1_hig_queuing_latency", &self.chain_1_subblock_timings, |t| t.proposal_queuing),
+            ("chain_2_hig_queuing_latency", &self.chain_2_subblock_timings, |t| t.proposal_queuing),
+        ];
+        for (key, timings, part) in hig_timing_series {
+            let series: Vec<serde_json::Value> = timings.iter()
+                .map(|(height, timing)| serde_json::json!({"height": height, "latency": to_ms(part(timing))}))
+                .collect();
+            let hig_timing_file = format!("{}/data/{}.json", base_dir, key);
+            fs::write(&hig_timing_file, serde_json::to_string_pretty(&serde_json::json!({ key: series })).expect("Failed to serialize HIG processing time")).map_err(|e| e.to_string())?;
+        }
+        let hig_processing_histogram = serde_json::json!({
+            "chain_1": ProcessingTimeHistogram::from_timings(&self.chain_1_subblock_timings).to_json(),
+            "chain_2": ProcessingTimeHistogram::from_timings(&self.chain_2_subblock_timings).to_json()
+        });
+        let hig_processing_histogram_file = format!("{}/data/hig_processing_histogram.json", base_dir);
+        fs::write(&hig_processing_histogram_file, serde_json::to_string_pretty(&hig_processing_histogram).expect("Failed to serialize HIG processing histogram")).map_err(|e| e.to_string())?;
+        logging::log("SIMULATOR", &format!("Saved HIG processing time data to {}/data/chain_*_hig_*_latency.json and {}", base_dir, hig_processing_histogram_file));
+
+        // Save HIG pending-proposal queue data
+        let proposal_queue_depth_series: [(&str, &Vec<(u64, u64)>); 2] = [
+            ("chain_1_proposal_queue_depth", &self.chain_1_proposal_queue_depth),
+            ("chain_2_proposal_queue_depth", &self.chain_2_proposal_queue_depth),
+        ];
+        for (key, series) in proposal_queue_depth_series {
+            let series: Vec<serde_json::Value> = series.iter().map(|(height, count)| serde_json::json!({"height": height, "count": count})).collect();
+            let proposal_queue_file = format!("{}/data/{}.json", base_dir, key);
+            fs::write(&proposal_queue_file, serde_json::to_string_pretty(&serde_json::json!({ key: series })).expect("Failed to serialize proposal queue depth")).map_err(|e| e.to_string())?;
+        }
+        let proposal_queue_age_series: [(&str, &Vec<(u64, f64)>); 2] = [
+            ("chain_1_proposal_queue_age_latency", &self.chain_1_proposal_queue_age_latency),
+            ("chain_2_proposal_queue_age_latency", &self.chain_2_proposal_queue_age_latency),
+        ];
+        for (key, series) in proposal_queue_age_series {
+            let series: Vec<serde_json::Value> = series.iter().map(|(height, latency)| serde_json::json!({"height": height, "latency": latency})).collect();
+            let proposal_queue_file = format!("{}/data/{}.json", base_dir, key);
+            fs::write(&proposal_queue_file, serde_json::to_string_pretty(&serde_json::json!({ key: series })).expect("Failed to serialize proposal queue age")).map_err(|e| e.to_string())?;
+        }
+        logging::log("SIMULATOR", &format!("Saved proposal queue data to {}/data/chain_*_proposal_queue_*.json", base_dir));
+
+        // Save the ages of the pending CATs per block
+        let cat_age_series: [(&str, &Vec<(u64, BTreeMap<u64, u64>)>); 2] = [
+            ("chain_1_cat_age_histogram", &self.chain_1_cat_age_histogram),
+            ("chain_2_cat_age_histogram", &self.chain_2_cat_age_histogram),
+        ];
+        for (key, series) in cat_age_series {
+            let series: Vec<serde_json::Value> = series.iter().map(|(height, ages)| serde_json::json!({
+                "height": height,
+                "ages": ages.iter().map(|(age, count)| serde_json::json!({"age": age, "count": count})).collect::<Vec<_>>()
+            })).collect();
+            let cat_age_file = format!("{}/data/{}.json", base_dir, key);
+            fs::write(&cat_age_file, serde_json::to_string_pretty(&serde_json::json!({ key: series })).expect("Failed to serialize CAT age histogram")).map_err(|e| e.to_string())?;
+        }
+        logging::log("SIMULATOR", &format!("Saved pending CAT ages to {}/data/chain_*_cat_age_histogram.json", base_dir));
+
+        // Save the lock wait times of the hottest keys
+        let hottest_keys = serde_json::json!({
+            "chain_1": key_lock_waits_to_json(&self.chain_1_key_lock_waits),
+            "chain_2": key_lock_waits_to_json(&self.chain_2_key_lock_waits)
+        });
+        let hottest_keys_file = format!("{}/data/hottest_keys.json", base_dir);
+        fs::write(&hottest_keys_file, serde_json::to_string_pretty(&hottest_keys).expect("Failed to serialize hottest keys")).map_err(|e| e.to_string())?;
+        logging::log("SIMULATOR", &format!("Saved hottest keys to {}", hottest_keys_file));
+
+        // Save the CATs the HS still waited on each chain for
+        let pending_cats = serde_json::json!({
+            "chain_1": pending_cats_to_json(&self.cats_awaiting_chain_1),
+            "chain_2": pending_cats_to_json(&self.cats_awaiting_chain_2)
+        });
+        let pending_cats_file = format!("{}/data/pending_cats.json", base_dir);
+        fs::write(&pending_cats_file, serde_json::to_string_pretty(&pending_cats).expect("Failed to serialize pending CATs")).map_err(|e| e.to_string())?;
+        logging::log("SIMULATOR", &format!("Saved pending CATs to {}", pending_cats_file));
+
+        // Save the distributions of the HS proposal spreads and pending CAT ages
+        let hs_histograms = serde_json::json!({
+            "proposal_spread": CatTimeHistogram::from_durations(&self.hs_metrics.proposal_spreads).to_json(),
+            "pending_cat_age": CatTimeHistogram::from_durations(&self.hs_metrics.pending_cat_ages).to_json()
+        });
+        let hs_histograms_file = format!("{}/data/hs_histograms.json", base_dir);
+        fs::write(&hs_histograms_file, serde_json::to_string_pretty(&hs_histograms).expect("Failed to serialize HS histograms")).map_err(|e| e.to_string())?;
+        logging::log("SIMULATOR", &format!("Saved HS histograms to {}", hs_histograms_file));
+
+        // Save the HS decisions per block, which show bursts and stalls of the HS
+        let hs_decisions_data = serde_json::json!({
+            "hs_decisions_per_block": self.hs_decisions_series().iter().map(|(height, count)| {
                 serde_json::json!({
                     "height": height,
                     "count": count
                 })
             }).collect::<Vec<_>>()
         });
-        let cl_queue_length_file = format!("{}/data/cl_queue_length.json", base_dir);
-        fs::write(&cl_queue_length_file, serde_json::to_string_pretty(&cl_queue_length_data).expect("Failed to serialize CL queue length data")).map_err(|e| e.to_string())?;
-        logging::log("SIMULATOR", &format!("Saved CL queue length data to {}", cl_queue_length_file));
+        let hs_decisions_file = format!("{}/data/hs_decisions_per_block.json", base_dir);
+        fs::write(&hs_decisions_file, serde_json::to_string_pretty(&hs_decisions_data).expect("Failed to serialize HS decisions per block")).map_err(|e| e.to_string())?;
+        logging::log("SIMULATOR", &format!("Saved HS decisions per block to {}", hs_decisions_file));
+
+        // Save the health of the nodes at the end of the run, to diagnose runs that hang or stall
+        if let Some(node_health) = &self.node_health {
+            let node_health_file = format!("{}/data/node_health.json", base_dir);
+            fs::write(&node_health_file, serde_json::to_string_pretty(node_health).expect("Failed to serialize node health")).map_err(|e| e.to_string())?;
+            logging::log("SIMULATOR", &format!("Saved node health to {}", node_health_file));
+            for problem in node_health.problems() {
+                logging::log("SIMULATOR", &format!("Node health problem: {}", problem));
+            }
+        }
 
         // Save regular transaction timing metrics data from chain 1
         let regular_tx_avg_latency_chain_1 = serde_json::json!({
@@ -871,6 +1675,415 @@ impl SimulationResults {
         fs::write(&regular_tx_finalized_count_file_chain_2, serde_json::to_string_pretty(&regular_tx_finalized_count_chain_2).expect("Failed to serialize regular transaction finalized count")).map_err(|e| e.to_string())?;
         logging::log("SIMULATOR", &format!("Saved regular transaction finalized count data to {}", regular_tx_finalized_count_file_chain_2));
 
+        // Save protocol violation data from both chains
+        let protocol_violations = serde_json::json!({
+            "policy": format!("{:?}", self.protocol_violation_policy),
+            "chain_1_protocol_violations": self.chain_1_protocol_violations.iter().map(|(height, count)| {
+                serde_json::json!({
+                    "height": height,
+                    "count": count
+                })
+            }).collect::<Vec<_>>(),
+            "chain_2_protocol_violations": self.chain_2_protocol_violations.iter().map(|(height, count)| {
+                serde_json::json!({
+                    "height": height,
+                    "count": count
+                })
+            }).collect::<Vec<_>>()
+        });
+        let protocol_violations_file = format!("{}/data/protocol_violations.json", base_dir);
+        fs::write(&protocol_violations_file, serde_json::to_string_pretty(&protocol_violations).expect("Failed to serialize protocol violations")).map_err(|e| e.to_string())?;
+        logging::log("SIMULATOR", &format!("Saved protocol violation data to {}", protocol_violations_file));
+
+        // Save configured and realized CAT fanout
+        let cat_fanout = serde_json::json!({
+            "chain_selection": self.cat_chain_selection,
+            "configured": self.cat_fanout,
+            "realized": self.cat_fanout_counts.iter().map(|(chains, count)| {
+                serde_json::json!({
+                    "chains": chains,
+                    "count": count
+                })
+            }).collect::<Vec<_>>()
+        });
+        let cat_fanout_file = format!("{}/data/cat_fanout.json", base_dir);
+        fs::write(&cat_fanout_file, serde_json::to_string_pretty(&cat_fanout).expect("Failed to serialize CAT fanout")).map_err(|e| e.to_string())?;
+        logging::log("SIMULATOR", &format!("Saved CAT fanout data to {}", cat_fanout_file));
+
+        // Save state divergence report
+        let state_divergence_file = format!("{}/data/state_divergence.json", base_dir);
+        fs::write(&state_divergence_file, serde_json::to_string_pretty(&self.state_divergence).expect("Failed to serialize state divergence report")).map_err(|e| e.to_string())?;
+        logging::log("SIMULATOR", &format!("Saved state divergence report to {}", state_divergence_file));
+
+        // Save the slowest transactions with their timelines
+        let slowest_transactions_file = format!("{}/data/{}", base_dir, SLOWEST_TRANSACTIONS_FILE);
+        fs::write(&slowest_transactions_file, serde_json::to_string_pretty(&self.slowest_transactions).expect("Failed to serialize slowest transactions")).map_err(|e| e.to_string())?;
+        logging::log("SIMULATOR", &format!("Saved slowest transactions to {}", slowest_transactions_file));
+
+        // Save the latency breakdown of every finished transaction
+        let latency_breakdown_file = format!("{}/data/{}", base_dir, LATENCY_BREAKDOWN_FILE);
+        fs::write(&latency_breakdown_file, serde_json::to_string_pretty(&self.latency_breakdown.to_json()).expect("Failed to serialize the latency breakdown")).map_err(|e| e.to_string())?;
+        logging::log("SIMULATOR", &format!("Saved latency breakdown to {}", latency_breakdown_file));
+
+        // Save the HS decision latency distribution
+        let hs_decision_latency_file = format!("{}/data/{}", base_dir, HS_DECISION_LATENCY_FILE);
+        fs::write(&hs_decision_latency_file, serde_json::to_string_pretty(&self.hs_decision_latencies.to_json()).expect("Failed to serialize HS decision latencies")).map_err(|e| e.to_string())?;
+        logging::log("SIMULATOR", &format!("Saved HS decision latencies to {}", hs_decision_latency_file));
+
+        // Save the CAT outcomes by fanout and chain delay
+        let cat_outcome_buckets_file = format!("{}/data/{}", base_dir, CAT_OUTCOME_BUCKETS_FILE);
+        fs::write(&cat_outcome_buckets_file, serde_json::to_string_pretty(&self.cat_outcomes.to_json(self.block_interval)).expect("Failed to serialize CAT outcomes")).map_err(|e| e.to_string())?;
+        logging::log("SIMULATOR", &format!("Saved CAT outcomes by fanout and chain delay to {}", cat_outcome_buckets_file));
+
+        // Save the CAT connections between pairs of chains
+        let chain_pair_matrix_file = format!("{}/data/{}", base_dir, CHAIN_PAIR_MATRIX_FILE);
+        fs::write(&chain_pair_matrix_file, serde_json::to_string_pretty(&self.chain_pair_matrix.to_json()).expect("Failed to serialize the chain pair matrix")).map_err(|e| e.to_string())?;
+        logging::log("SIMULATOR", &format!("Saved CAT success by chain pair to {}", chain_pair_matrix_file));
+
+        // Save the realized chain delays
+        let realized_chain_delays = serde_json::json!({
+            "distribution": self.chain_delay_distribution,
+            "chain_1": realized_chain_delays_to_json(&self.chain_1_realized_delays, self.chain_delays.first().copied().unwrap_or(0.0), self.block_interval),
+            "chain_2": realized_chain_delays_to_json(&self.chain_2_realized_delays, self.chain_delays.get(1).copied().unwrap_or(0.0), self.block_interval)
+        });
+        let realized_chain_delays_file = format!("{}/data/{}", base_dir, REALIZED_CHAIN_DELAYS_FILE);
+        fs::write(&realized_chain_delays_file, serde_json::to_string_pretty(&realized_chain_delays).expect("Failed to serialize realized chain delays")).map_err(|e| e.to_string())?;
+        logging::log("SIMULATOR", &format!("Saved realized chain delays to {}", realized_chain_delays_file));
+
+        // Save the conflict rate estimate
+        let conflict_rates_file = format!("{}/data/{}", base_dir, CONFLICT_RATES_FILE);
+        fs::write(&conflict_rates_file, serde_json::to_string_pretty(&conflict_rates).expect("Failed to serialize conflict rates")).map_err(|e| e.to_string())?;
+        logging::log("SIMULATOR", &format!("Saved conflict rates to {}", conflict_rates_file));
+
+        // Save the state roots of both chains
+        let state_roots = serde_json::json!({
+            "chain_1_state_roots": self.chain_1_state_roots.iter().map(|(height, root)| {
+                serde_json::json!({
+                    "height": height,
+                    "root": root
+                })
+            }).collect::<Vec<_>>(),
+            "chain_2_state_roots": self.chain_2_state_roots.iter().map(|(height, root)| {
+                serde_json::json!({
+                    "height": height,
+                    "root": root
+                })
+            }).collect::<Vec<_>>()
+        });
+        let state_roots_file = format!("{}/data/{}", base_dir, STATE_ROOTS_FILE);
+        fs::write(&state_roots_file, serde_json::to_string_pretty(&state_roots).expect("Failed to serialize state roots")).map_err(|e| e.to_string())?;
+        logging::log("SIMULATOR", &format!("Saved state roots to {}", state_roots_file));
+
+        // Save the final account balances of both chains (sorted for stable diffs between runs)
+        let final_states = serde_json::json!({
+            "chain_1": self.chain_1_final_state.iter().collect::<BTreeMap<_, _>>(),
+            "chain_2": self.chain_2_final_state.iter().collect::<BTreeMap<_, _>>()
+        });
+        let final_states_file = format!("{}/data/{}", base_dir, FINAL_STATES_FILE);
+        fs::write(&final_states_file, serde_json::to_string_pretty(&final_states).expect("Failed to serialize final states")).map_err(|e| e.to_string())?;
+        logging::log("SIMULATOR", &format!("Saved final states to {}", final_states_file));
+
+        // Save the recorded workload
+        let workload_file = save_workload(&format!("{}/data", base_dir), &self.recorded_workload)?;
+        logging::log("SIMULATOR", &format!("Saved recorded workload to {}", workload_file));
+
+        // Save per-transaction and per-block records as Parquet
+        if self.export_parquet {
+            match save_parquet(&format!("{}/data", base_dir), &self.transaction_records, &block_records(self)) {
+                Ok(files) => logging::log("SIMULATOR", &format!("Saved Parquet records to {}", files.join(", "))),
+                Err(e) => {
+                    logging::log("SIMULATOR", &format!("Failed to save Parquet records: {}", e));
+                    eprintln!("Failed to save Parquet records: {}", e);
+                }
+            }
+        }
+
+        // The complete results supersede the metrics flushed during the run
+        let partial_file = format!("{}/data/{}", base_dir, PARTIAL_RESULTS_FILE);
+        if let Err(e) = fs::remove_file(&partial_file) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                return Err(format!("Failed to remove {}: {}", partial_file, e));
+            }
+        }
+
         Ok(())
     }
-} 
\ No newline at end of file
+
+    /// Saves the results of a run against an external CL to JSON files in the specified directory
+    ///
+    /// The HIGs and the HS of such a run are in another process, so instead of series that would
+    /// stay empty, only the statistics and series the CL provides are written. The stats carry the
+    /// CL endpoint among the parameters, which tells readers that the HIG and HS files are missing
+    /// on purpose.
+    ///
+    /// # Arguments
+    /// * `base_dir` - The directory to save to
+    /// * `cl_endpoint` - The address of the CL the run targeted
+    fn save_cl_results_to_directory(&self, base_dir: &str, cl_endpoint: &str) -> Result<(), String> {
+        logging::log("SIMULATOR", &format!("\n=== Simulation Statistics (CL at {}) ===", cl_endpoint));
+        logging::log("SIMULATOR", &format!("Total Transactions: {}", self.transactions_sent));
+        logging::log("SIMULATOR", &format!("Intended TPS: {:.2}, achieved TPS: {:.2}", self.intended_submission_tps, self.achieved_submission_tps));
+        logging::log("SIMULATOR", "HIG and HS statistics are not recorded for a run against an external CL");
+        logging::log("SIMULATOR", "===========================");
+
+        let mut stats = serde_json::json!({
+            "parameters": self.parameters_json(),
+            "results": {
+                "total_transactions": self.transactions_sent,
+                "cat_transactions": self.cat_transactions,
+                "regular_transactions": self.regular_transactions,
+                "duplicates_injected": self.duplicates_injected,
+                "duplicate_variants_injected": self.duplicate_variants_injected,
+                "malformed_transactions": self.malformed_transactions.clone(),
+                "cl_chain_throughput": self.cl_chain_throughput_json(),
+                "mean_block_utilization": mean_utilization(&self.cl_block_utilization, BlockUtilization::transaction_utilization),
+                "mean_block_byte_utilization": mean_utilization(&self.cl_block_utilization, BlockUtilization::byte_utilization),
+                "intended_submission_tps": self.intended_submission_tps,
+                "achieved_submission_tps": self.achieved_submission_tps,
+                "submission_batches": self.submission_batches,
+                "mean_inter_block_time_ms": mean_duration_ms(&self.inter_block_gaps()),
+                "inter_block_time_p99_ms": percentile_ms(&self.inter_block_gaps(), 99.0)
+            }
+        });
+
+        fs::create_dir_all(&format!("{}/data", base_dir)).map_err(|e| e.to_string())?;
+        results_schema::stamp(&mut stats);
+        let stats_file = format!("{}/data/{}", base_dir, results_schema::SIMULATION_STATS_FILE);
+        fs::write(&stats_file, serde_json::to_string_pretty(&stats).expect("Failed to serialize stats")).map_err(|e| e.to_string())?;
+        logging::log("SIMULATOR", &format!("Saved simulation statistics to {}", stats_file));
+
+        let metadata = RunMetadata::collect(&stats["parameters"], self.master_seed, self.run_seed, self.started_at);
+        metadata.save(&format!("{}/data", base_dir))?;
+        self.save_cl_series(base_dir)?;
+
+        // Save the recorded workload
+        let workload_file = save_workload(&format!("{}/data", base_dir), &self.recorded_workload)?;
+        logging::log("SIMULATOR", &format!("Saved recorded workload to {}", workload_file));
+
+        // The complete results supersede the metrics flushed during the run
+        let partial_file = format!("{}/data/{}", base_dir, PARTIAL_RESULTS_FILE);
+        if let Err(e) = fs::remove_file(&partial_file) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                return Err(format!("Failed to remove {}: {}", partial_file, e));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Saves the series recorded from the CL: transactions per block, CL throughput and block
+    /// utilization, loop steps, block height deltas, CL queue length, mempool ages and inter-block times
+    fn save_cl_series(&self, base_dir: &str) -> Result<(), String> {
+        // Save transactions per block data from chain 1
+        let tx_per_block_chain_1 = serde_json::json!({
+            "chain_1_tx_per_block": self.chain_1_tx_per_block.iter().map(|(height, count)| {
+                serde_json::json!({
+                    "height": height,
+                    "count": count
+                })
+            }).collect::<Vec<_>>()
+        });
+        let tx_per_block_file_chain_1 = format!("{}/data/tx_per_block_chain_1.json", base_dir);
+        fs::write(&tx_per_block_file_chain_1, serde_json::to_string_pretty(&tx_per_block_chain_1).expect("Failed to serialize transactions per block")).map_err(|e| e.to_string())?;
+        logging::log("SIMULATOR", &format!("Saved transactions per block data to {}", tx_per_block_file_chain_1));
+
+        // Save transactions per block data from chain 2
+        let tx_per_block_chain_2 = serde_json::json!({
+            "chain_2_tx_per_block": self.chain_2_tx_per_block.iter().map(|(height, count)| {
+                serde_json::json!({
+                    "height": height,
+                    "count": count
+                })
+            }).collect::<Vec<_>>()
+        });
+        let tx_per_block_file_chain_2 = format!("{}/data/tx_per_block_chain_2.json", base_dir);
+        fs::write(&tx_per_block_file_chain_2, serde_json::to_string_pretty(&tx_per_block_chain_2).expect("Failed to serialize transactions per block")).map_err(|e| e.to_string())?;
+        logging::log("SIMULATOR", &format!("Saved transactions per block data to {}", tx_per_block_file_chain_2));
+
+        // Save the transactions the CL included per chain and block
+        let cl_chain_stats: serde_json::Map<String, serde_json::Value> = self.cl_chain_stats.iter().map(|(chain, stats)| (chain.clone(), serde_json::json!(
+            stats.transactions_per_block.iter().map(|(height, count)| serde_json::json!({"height": height, "count": count})).collect::<Vec<_>>()
+        ))).collect();
+        let cl_chain_stats_file = format!("{}/data/cl_chain_transactions_per_block.json", base_dir);
+        fs::write(&cl_chain_stats_file, serde_json::to_string_pretty(&serde_json::json!({ "cl_chain_transactions_per_block": cl_chain_stats })).expect("Failed to serialize CL chain stats")).map_err(|e| e.to_string())?;
+        logging::log("SIMULATOR", &format!("Saved CL throughput per chain to {}", cl_chain_stats_file));
+
+        // Save the contents of each CL block relative to its capacity
+        let cl_block_utilization = serde_json::json!({
+            "cl_block_utilization": self.cl_block_utilization.iter().map(|block| {
+                serde_json::json!({
+                    "height": block.block_height,
+                    "transactions": block.transactions,
+                    "bytes": block.bytes,
+                    "serialized_bytes": block.serialized_bytes,
+                    "bytes_per_second": block.bytes_per_second(Duration::from_secs_f64(self.block_interval)),
+                    "transaction_utilization": block.transaction_utilization(),
+                    "byte_utilization": block.byte_utilization()
+                })
+            }).collect::<Vec<_>>()
+        });
+        let cl_block_utilization_file = format!("{}/data/cl_block_utilization.json", base_dir);
+        fs::write(&cl_block_utilization_file, serde_json::to_string_pretty(&cl_block_utilization).expect("Failed to serialize CL block utilization")).map_err(|e| e.to_string())?;
+        logging::log("SIMULATOR", &format!("Saved CL block utilization to {}", cl_block_utilization_file));
+
+        // Save loop steps without transaction issuance data
+        let loop_steps_data = serde_json::json!({
+            "loop_steps_without_tx_issuance": self.loop_steps_without_tx_issuance.iter().map(|(height, count)| {
+                serde_json::json!({
+                    "height": height,
+                    "count": count
+                })
+            }).collect::<Vec<_>>()
+        });
+        let loop_steps_file = format!("{}/data/loop_steps_without_tx_issuance.json", base_dir);
+        fs::write(&loop_steps_file, serde_json::to_string_pretty(&loop_steps_data).expect("Failed to serialize loop steps data")).map_err(|e| e.to_string())?;
+        logging::log("SIMULATOR", &format!("Saved loop steps data to {}", loop_steps_file));
+
+        // Save block height delta data
+        let block_height_delta_data = serde_json::json!({
+            "block_height_delta": self.block_height_delta.iter().map(|(height, delta)| {
+                serde_json::json!({
+                    "height": height,
+                    "delta": delta
+                })
+            }).collect::<Vec<_>>()
+        });
+        let block_height_delta_file = format!("{}/data/block_height_delta.json", base_dir);
+        fs::write(&block_height_delta_file, serde_json::to_string_pretty(&block_height_delta_data).expect("Failed to serialize block height delta data")).map_err(|e| e.to_string())?;
+        logging::log("SIMULATOR", &format!("Saved block height delta data to {}", block_height_delta_file));
+
+        // Save CL queue length data
+        let cl_queue_length_data = serde_json::json!({
+            "cl_queue_length": self.cl_queue_length.iter().map(|(height, count)| {
+                serde_json::json!({
+                    "height": height,
+                    "count": count
+                })
+            }).collect::<Vec<_>>()
+        });
+        let cl_queue_length_file = format!("{}/data/cl_queue_length.json", base_dir);
+        fs::write(&cl_queue_length_file, serde_json::to_string_pretty(&cl_queue_length_data).expect("Failed to serialize CL queue length data")).map_err(|e| e.to_string())?;
+        logging::log("SIMULATOR", &format!("Saved CL queue length data to {}", cl_queue_length_file));
+
+        // Save CL mempool age data
+        let mempool_age_series: [(&str, &Vec<(u64, u64)>); 4] = [
+            ("chain_1_mempool_age_p50", &self.chain_1_mempool_age_p50),
+            ("chain_2_mempool_age_p50", &self.chain_2_mempool_age_p50),
+            ("chain_1_mempool_age_p99", &self.chain_1_mempool_age_p99),
+            ("chain_2_mempool_age_p99", &self.chain_2_mempool_age_p99),
+        ];
+        for (key, series) in mempool_age_series {
+            let mempool_age_data = serde_json::json!({
+                key: series.iter().map(|(height, count)| {
+                    serde_json::json!({
+                        "height": height,
+                        "count": count
+                    })
+                }).collect::<Vec<_>>()
+            });
+            let mempool_age_file = format!("{}/data/{}.json", base_dir, key);
+            fs::write(&mempool_age_file, serde_json::to_string_pretty(&mempool_age_data).expect("Failed to serialize mempool age data")).map_err(|e| e.to_string())?;
+        }
+        logging::log("SIMULATOR", &format!("Saved CL mempool age data to {}/data/chain_*_mempool_age_*.json", base_dir));
+
+        // Save the times between CL blocks
+        let inter_block_times_file = format!("{}/data/{}", base_dir, INTER_BLOCK_TIMES_FILE);
+        let inter_block_times = inter_block_times_to_json(&self.cl_inter_block_times, self.block_interval);
+        fs::write(&inter_block_times_file, serde_json::to_string_pretty(&inter_block_times).expect("Failed to serialize inter-block times")).map_err(|e| e.to_string())?;
+        logging::log("SIMULATOR", &format!("Saved inter-block times to {}", inter_block_times_file));
+
+        Ok(())
+    }
+
+    /// Throughput of the CL per chain as JSON
+    fn cl_chain_throughput_json(&self) -> serde_json::Value {
+        self.cl_chain_stats.iter().map(|(chain, stats)| (chain.clone(), serde_json::json!({
+            "total_transactions": stats.total_transactions,
+            "status_updates": stats.status_updates,
+            "mean_transactions_per_block": stats.mean_transactions_per_block(),
+            "max_transactions_per_block": stats.max_transactions_per_block(),
+            "total_bytes": stats.total_bytes,
+            "mean_bytes_per_block": stats.mean_bytes_per_block()
+        }))).collect::<serde_json::Map<_, _>>().into()
+    }
+}
+
+/// Status counts breakdown as JSON, with every count as {pending, success, failure}
+fn status_breakdown_to_json(breakdown: &StatusCountsBreakdown) -> serde_json::Value {
+    let counts = |(pending, success, failure): (u64, u64, u64)| serde_json::json!({"pending": pending, "success": success, "failure": failure});
+    serde_json::json!({
+        "cats_by_fanout": breakdown.cats_by_fanout.iter().map(|(fanout, c)| (fanout.to_string(), counts(*c))).collect::<serde_json::Map<_, _>>(),
+        "cats_ever_blocked": counts(breakdown.cats_ever_blocked),
+        "cats_never_blocked": counts(breakdown.cats_never_blocked),
+        "regular_ever_blocked": counts(breakdown.regular_ever_blocked),
+        "regular_never_blocked": counts(breakdown.regular_never_blocked)
+    })
+}
+
+/// Mean utilization of the CL blocks (None if the capacity has no limit of that kind)
+fn mean_utilization(blocks: &[BlockUtilization], utilization: fn(&BlockUtilization) -> Option<f64>) -> Option<f64> {
+    let values: Vec<f64> = blocks.iter().filter_map(utilization).collect();
+    (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64)
+}
+
+/// Mean serialized size of the CL blocks in bytes (0 without blocks)
+fn mean_serialized_bytes(blocks: &[BlockUtilization]) -> f64 {
+    if blocks.is_empty() {
+        0.0
+    } else {
+        blocks.iter().map(|block| block.serialized_bytes as f64).sum::<f64>() / blocks.len() as f64
+    }
+}
+
+/// Mean of a per-block percentage series (0 for an empty series)
+fn mean_percent(series: &[(u64, f64)]) -> f64 {
+    if series.is_empty() {
+        0.0
+    } else {
+        series.iter().map(|(_, percent)| percent).sum::<f64>() / series.len() as f64
+    }
+}
+
+/// Mean of a per-block count series (0 for an empty series)
+fn mean_count(series: &[(u64, u64)]) -> f64 {
+    if series.is_empty() {
+        0.0
+    } else {
+        series.iter().map(|(_, count)| *count as f64).sum::<f64>() / series.len() as f64
+    }
+}
+
+/// Mean total time per subblock over the timings of all chains (milliseconds)
+fn mean_processing_ms(timings: &[&Vec<(u64, SubblockTiming)>]) -> f64 {
+    let count: usize = timings.iter().map(|chain| chain.len()).sum();
+    if count == 0 {
+        0.0
+    } else {
+        timings.iter().flat_map(|chain| chain.iter()).map(|(_, timing)| timing.total().as_secs_f64() * 1000.0).sum::<f64>() / count as f64
+    }
+}
+
+/// Total time transactions waited for locked keys over all chains (milliseconds)
+fn total_lock_wait_ms(key_lock_waits: &[&Vec<KeyLockWait>]) -> f64 {
+    key_lock_waits.iter().flat_map(|chain| chain.iter()).map(|key| key.total_wait.as_secs_f64() * 1000.0).sum()
+}
+
+/// Share of the total lock wait time spent on the hottest key of each chain (0 without waits)
+fn hottest_key_wait_share(key_lock_waits: &[&Vec<KeyLockWait>]) -> f64 {
+    let total = total_lock_wait_ms(key_lock_waits);
+    if total == 0.0 {
+        0.0
+    } else {
+        key_lock_waits.iter().filter_map(|chain| chain.first()).map(|key| key.total_wait.as_secs_f64() * 1000.0).sum::<f64>() / total
+    }
+}
+
+/// Mean of the final values of per-chain latency series (0 if no series has a value)
+fn final_mean_latency_ms(series: &[&Vec<(u64, f64)>]) -> f64 {
+    let finals: Vec<f64> = series.iter().filter_map(|chain| chain.last()).map(|(_, latency)| *latency).collect();
+    if finals.is_empty() {
+        0.0
+    } else {
+        finals.iter().sum::<f64>() / finals.len() as f64
+    }
+}
@@ -0,0 +1,128 @@
+//! Report of the slowest transactions of a run.
+//!
+//! Lists the slowest regular transactions and CATs of a run with their timelines and the phase
+//! they spent most of their time in, so long-tail latencies can be investigated without searching
+//! the logs.
+
+use serde::Serialize;
+use crate::timeline::TransactionTimeline;
+
+// ------------------------------------------------------------------------------------------------
+// Constants
+// ------------------------------------------------------------------------------------------------
+
+/// Number of regular transactions and of CATs listed in the slowest transactions report
+pub const SLOWEST_TRANSACTIONS_LISTED: usize = 10;
+
+/// Name of the slowest transactions report (inside a run's data directory)
+pub const SLOWEST_TRANSACTIONS_FILE: &str = "slowest_transactions.json";
+
+// ------------------------------------------------------------------------------------------------
+// Data Structures
+// ------------------------------------------------------------------------------------------------
+
+/// A CL transaction and where its time went
+#[derive(Debug, Clone, Serialize)]
+pub struct SlowTransaction {
+    #[serde(flatten)]
+    pub timeline: TransactionTimeline,
+    /// The phase the transaction spent the most time in
+    pub blocked_on: String,
+}
+
+/// The slowest regular transactions and CATs of a run, slowest first
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SlowestTransactionsReport {
+    pub regular: Vec<SlowTransaction>,
+    pub cats: Vec<SlowTransaction>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl SlowTransaction {
+    /// Finds the phase the transaction spent the most time in
+    ///
+    /// Phases that had not ended at the end of the run last until `latency_ms`.
+    ///
+    /// # Arguments
+    /// * `timeline` - The timeline of the transaction
+    fn longest_phase(timeline: &TransactionTimeline) -> String {
+        let end = timeline.latency_ms;
+        let mut phases: Vec<(String, f64)> = Vec::new();
+        for chain in &timeline.chains {
+            let Some(included) = chain.included_ms else {
+                phases.push(("CL mempool".to_string(), end));
+                continue;
+            };
+            phases.push(("CL mempool".to_string(), included));
+            let Some(received) = chain.received_ms else {
+                phases.push((format!("delivery to {}", chain.chain), end - included));
+                continue;
+            };
+            phases.push((format!("delivery to {}", chain.chain), received - included));
+
+            let mut locks = format!("locks on {}", chain.chain);
+            if !chain.blocked_keys.is_empty() {
+                locks.push_str(&format!(" (keys {})", chain.blocked_keys.join(", ")));
+            }
+            if !chain.blocking_txs.is_empty() {
+                locks.push_str(&format!(" held by {}", chain.blocking_txs.join(", ")));
+            }
+            phases.push((locks, chain.lock_wait_ms));
+
+            match (chain.proposed_ms, timeline.decided_ms) {
+                (Some(proposed), decided) => {
+                    phases.push(("HS decision".to_string(), decided.unwrap_or(end) - proposed));
+                    if let Some(decided) = decided {
+                        phases.push((format!("status update to {}", chain.chain), chain.finalized_ms.unwrap_or(end) - decided.max(proposed)));
+                    }
+                }
+                (None, _) => {
+                    let executed = chain.finalized_ms.unwrap_or(end) - received - chain.lock_wait_ms;
+                    phases.push((format!("execution on {}", chain.chain), executed));
+                }
+            }
+        }
+        phases.into_iter()
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map_or_else(|| "unknown".to_string(), |(phase, _)| phase)
+    }
+
+    /// Attributes the latency of a transaction to the phase it spent the most time in
+    pub fn new(timeline: TransactionTimeline) -> Self {
+        let blocked_on = Self::longest_phase(&timeline);
+        Self { timeline, blocked_on }
+    }
+}
+
+impl SlowestTransactionsReport {
+    /// Keeps the slowest regular transactions and CATs of a run
+    ///
+    /// # Arguments
+    /// * `timelines` - The timelines of all CL transactions of the run
+    /// * `count` - Number of regular transactions and of CATs to keep
+    pub fn slowest(timelines: Vec<TransactionTimeline>, count: usize) -> Self {
+        let (cats, regular): (Vec<_>, Vec<_>) = timelines.into_iter().partition(|timeline| timeline.is_cat);
+        let keep_slowest = |mut timelines: Vec<TransactionTimeline>| -> Vec<SlowTransaction> {
+            timelines.sort_by(|a, b| b.latency_ms.total_cmp(&a.latency_ms));
+            timelines.truncate(count);
+            timelines.into_iter().map(SlowTransaction::new).collect()
+        };
+        Self { regular: keep_slowest(regular), cats: keep_slowest(cats) }
+    }
+
+    /// Formats the report as human-readable lines for the results summary
+    pub fn summary_lines(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        for (label, transactions) in [("regular transactions", &self.regular), ("CATs", &self.cats)] {
+            lines.push(format!("Slowest {}: {}", label, transactions.len()));
+            for tx in transactions {
+                lines.push(format!("  {}: {:.1} ms{}, blocked on {}",
+                    tx.timeline.cl_id, tx.timeline.latency_ms, if tx.timeline.finished { "" } else { " (unfinished)" }, tx.blocked_on));
+            }
+        }
+        lines
+    }
+}
@@ -0,0 +1,141 @@
+//! Per-block state roots of the chains.
+//!
+//! Every HIG records the root of its account state after each subblock. Replays of the same
+//! workload must produce the same sequence of roots; the first block at which the roots of a
+//! recorded and a replayed run differ points to nondeterminism in the protocol. The final
+//! account balances of both runs are diffed per account to show how the states diverged.
+
+use std::collections::HashMap;
+use std::fs;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use serde::Serialize;
+use hyperplane::{
+    types::StateDiff,
+    hyper_ig::node::HyperIGNode,
+    hyper_ig::HyperIG,
+};
+
+// ------------------------------------------------------------------------------------------------
+// Constants
+// ------------------------------------------------------------------------------------------------
+
+/// Name of the file (inside a run's data directory) that holds the state roots
+pub const STATE_ROOTS_FILE: &str = "state_roots.json";
+
+/// Keys of the per-chain state root series in the state roots file
+const STATE_ROOT_SERIES: [&str; 2] = ["chain_1_state_roots", "chain_2_state_roots"];
+
+/// Name of the file (inside a run's data directory) that holds the final account balances
+pub const FINAL_STATES_FILE: &str = "final_states.json";
+
+/// Keys of the per-chain final states in the final states file
+const FINAL_STATE_KEYS: [&str; 2] = ["chain_1", "chain_2"];
+
+// ------------------------------------------------------------------------------------------------
+// Data Structures
+// ------------------------------------------------------------------------------------------------
+
+/// Comparison of the state roots of one chain between a recorded and a replayed run
+#[derive(Debug, Clone, Serialize)]
+pub struct StateRootComparison {
+    /// Key of the compared series (e.g. `chain_1_state_roots`)
+    pub series: String,
+    /// Number of blocks present in both runs
+    pub blocks_compared: usize,
+    /// Offset (relative to the first recorded block) of the first block whose roots differ
+    pub first_divergent_block: Option<usize>,
+    /// Whether the roots after the last block present in both runs match
+    pub final_roots_match: bool,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Collection
+// ------------------------------------------------------------------------------------------------
+
+/// Collects the state root of a chain for every block in `from..=to`
+///
+/// Blocks the HIG has not processed (yet) are skipped.
+///
+/// # Arguments
+/// * `hig_node` - The HIG node of the chain
+/// * `from` - The first block height
+/// * `to` - The last block height
+pub async fn collect_state_roots(hig_node: &Arc<Mutex<HyperIGNode>>, from: u64, to: u64) -> Vec<(u64, String)> {
+    let node = hig_node.lock().await;
+    let mut roots = Vec::new();
+    for height in from..=to {
+        if let Ok(root) = node.get_state_root(height).await {
+            roots.push((height, root.to_string()));
+        }
+    }
+    roots
+}
+
+// ------------------------------------------------------------------------------------------------
+// Comparison
+// ------------------------------------------------------------------------------------------------
+
+/// Loads the state root series of a run's data directory, in block order
+fn load_state_roots(data_dir: &str) -> Result<Vec<(String, Vec<String>)>, String> {
+    let path = format!("{}/{}", data_dir, STATE_ROOTS_FILE);
+    let json = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read state roots {}: {}", path, e))?;
+    let value: serde_json::Value = serde_json::from_str(&json)
+        .map_err(|e| format!("Failed to parse state roots {}: {}", path, e))?;
+
+    Ok(STATE_ROOT_SERIES.iter().map(|series| {
+        let roots = value[series].as_array()
+            .map(|points| points.iter().filter_map(|point| point["root"].as_str().map(str::to_string)).collect())
+            .unwrap_or_default();
+        (series.to_string(), roots)
+    }).collect())
+}
+
+/// Compares the state roots of a recorded and a replayed run block by block
+///
+/// The absolute block heights of two runs differ, so the roots are aligned by their offset from
+/// the first block of each run.
+///
+/// # Arguments
+/// * `recorded_dir` - The data directory of the recorded run
+/// * `replayed_dir` - The data directory of the replayed run
+pub fn compare_state_roots(recorded_dir: &str, replayed_dir: &str) -> Result<Vec<StateRootComparison>, String> {
+    let recorded = load_state_roots(recorded_dir)?;
+    let replayed = load_state_roots(replayed_dir)?;
+
+    Ok(recorded.into_iter().zip(replayed).map(|((series, recorded), (_, replayed))| {
+        let blocks_compared = recorded.len().min(replayed.len());
+        StateRootComparison {
+            series,
+            blocks_compared,
+            first_divergent_block: recorded.iter().zip(&replayed).position(|(a, b)| a != b),
+            final_roots_match: blocks_compared == 0 || recorded[blocks_compared - 1] == replayed[blocks_compared - 1],
+        }
+    }).collect())
+}
+
+/// Loads the final account balances of each chain from a run's data directory
+fn load_final_states(data_dir: &str) -> Result<Vec<(String, HashMap<String, i64>)>, String> {
+    let path = format!("{}/{}", data_dir, FINAL_STATES_FILE);
+    let json = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read final states {}: {}", path, e))?;
+    let mut states: HashMap<String, HashMap<String, i64>> = serde_json::from_str(&json)
+        .map_err(|e| format!("Failed to parse final states {}: {}", path, e))?;
+
+    Ok(FINAL_STATE_KEYS.iter().map(|chain| (chain.to_string(), states.remove(*chain).unwrap_or_default())).collect())
+}
+
+/// Diffs the final account balances of a recorded and a replayed run per chain
+///
+/// # Arguments
+/// * `recorded_dir` - The data directory of the recorded run
+/// * `replayed_dir` - The data directory of the replayed run
+pub fn compare_final_states(recorded_dir: &str, replayed_dir: &str) -> Result<Vec<(String, StateDiff)>, String> {
+    let recorded = load_final_states(recorded_dir)?;
+    let replayed = load_final_states(replayed_dir)?;
+
+    Ok(recorded.into_iter().zip(replayed).map(|((chain, recorded), (_, replayed))| {
+        (chain, StateDiff::between(&recorded, &replayed))
+    }).collect())
+}
@@ -1,9 +1,45 @@
 //! Transaction statistics tracking.
 //! 
-//! Tracks transaction counts, TPS, and cancellation rates during simulations.
+//! Tracks transaction counts, TPS, and cancellation rates during simulations, as well as the
+//! scheduling state of the tokio runtime the nodes run on, the time the HIGs spend per subblock,
+//! the time transactions wait for locked keys, the CATs the HS still waits on each chain for and the
+//! internal processing metrics of the HS and the realized delays of the HIG proposals. An online
+//! detector flags blocks at which the TPS or the pending counts deviate from their recent window, so
+//! transient stalls are annotated in the results.
 
-use std::time::Instant;
+use std::collections::{BTreeMap, VecDeque};
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
 use hyperplane::types::TransactionStatus;
+use hyperplane::hyper_ig::{HyperIG, KeyLockWait, SubblockTiming};
+use hyperplane::hyper_ig::node::HyperIGNode;
+use hyperplane::hyper_scheduler::{HyperScheduler, HyperSchedulerMetrics, PendingCAT};
+use hyperplane::hyper_scheduler::node::HyperSchedulerNode;
+use hyperplane::types::{ChainId, NetworkSpec};
+use hyperplane::confirmation_layer::ConfirmationLayerNode;
+use hyperplane::harness::NetworkHealth;
+
+/// Upper bounds of the buckets of the HIG processing-time histogram (milliseconds); a final bucket catches the rest
+pub const PROCESSING_TIME_BUCKETS_MS: [f64; 10] = [1.0, 2.0, 5.0, 10.0, 20.0, 50.0, 100.0, 200.0, 500.0, 1000.0];
+
+/// Number of keys listed per chain in the hottest keys file
+pub const HOTTEST_KEYS_LISTED: usize = 10;
+
+/// Share of the window mean below which the standard deviation of a window is not assumed to fall
+///
+/// Keeps a perfectly steady window from flagging every fluctuation by a single transaction.
+pub const RATE_ANOMALY_MIN_SIGMA_SHARE: f64 = 0.05;
+
+/// Name of the file (inside a run's data directory) that holds the realized chain delays
+pub const REALIZED_CHAIN_DELAYS_FILE: &str = "realized_chain_delays.json";
+
+/// Name of the file (inside a run's data directory) that holds the times between CL blocks
+pub const INTER_BLOCK_TIMES_FILE: &str = "inter_block_times.json";
+
+/// Upper bounds of the buckets of the HS proposal spread and pending CAT age histograms (milliseconds); a final bucket catches the rest
+pub const CAT_TIME_BUCKETS_MS: [f64; 10] = [10.0, 20.0, 50.0, 100.0, 200.0, 500.0, 1000.0, 2000.0, 5000.0, 10000.0];
 
 // ------------------------------------------------------------------------------------------------
 // Data Structures
@@ -23,6 +59,80 @@ pub struct SimulatorStats {
     transactions_per_block: Vec<usize>,
 }
 
+/// Scheduling state of the tokio runtime at one sample
+///
+/// Queue depth and poll latency are only available when the simulator is built with
+/// `RUSTFLAGS="--cfg tokio_unstable"`; otherwise they are reported as 0.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RuntimeMetricsSample {
+    /// Number of tasks alive on the runtime
+    pub alive_tasks: u64,
+    /// Number of tasks waiting in the global queue and the workers' local queues
+    pub queue_depth: u64,
+    /// Share of time the workers spent busy since the previous sample, averaged over the workers (percent)
+    pub busy_percent: f64,
+    /// Mean time the workers spent polling a task (microseconds)
+    pub mean_poll_time_us: f64,
+}
+
+/// Samples the metrics of the current tokio runtime
+///
+/// Keeps the workers' busy durations of the previous sample, so the busy share covers the time
+/// between two samples rather than the whole lifetime of the runtime.
+pub struct RuntimeMetricsSampler {
+    /// When the previous sample was taken
+    last_sample_time: Instant,
+    /// Total busy duration of each worker at the previous sample
+    last_busy_durations: Vec<Duration>,
+}
+
+/// Histogram of the total time a HIG spent per subblock
+#[derive(Debug, Clone)]
+pub struct ProcessingTimeHistogram {
+    /// Number of subblocks per bucket of `PROCESSING_TIME_BUCKETS_MS`, plus the overflow bucket
+    counts: [u64; PROCESSING_TIME_BUCKETS_MS.len() + 1],
+}
+
+/// Histogram of CAT durations measured by the HS (proposal spreads or pending ages)
+#[derive(Debug, Clone)]
+pub struct CatTimeHistogram {
+    /// Number of CATs per bucket of `CAT_TIME_BUCKETS_MS`, plus the overflow bucket
+    counts: [u64; CAT_TIME_BUCKETS_MS.len() + 1],
+}
+
+/// A stretch of blocks at which a metric deviated from its recent window
+#[derive(Debug, Clone, Serialize)]
+pub struct RateAnomaly {
+    /// Name of the metric (e.g. `tps` or `chain_1_pending`)
+    pub metric: String,
+    /// Block height at which the deviation started
+    pub height: u64,
+    /// Number of consecutive blocks the metric deviated
+    pub blocks: u64,
+    /// Value of the metric at the start of the deviation
+    pub value: f64,
+    /// Mean and standard deviation of the window before the deviation
+    pub window_mean: f64,
+    pub window_std: f64,
+    /// Largest deviation from the window mean, in standard deviations
+    pub peak_sigmas: f64,
+}
+
+/// Flags metrics that deviate by more than a number of standard deviations from their recent window
+///
+/// Every metric keeps the values of the last `window_blocks` blocks. A value further than
+/// `sigma_threshold` standard deviations from the window mean starts an anomaly, which lasts as
+/// long as the following values deviate as well. Deviating values still enter the window, so a
+/// lasting change of level becomes the new normal once the window has filled with it.
+pub struct RateAnomalyDetector {
+    window_blocks: usize,
+    sigma_threshold: f64,
+    windows: BTreeMap<String, VecDeque<f64>>,
+    /// Index into `anomalies` of the ongoing anomaly of each metric
+    ongoing: BTreeMap<String, usize>,
+    anomalies: Vec<RateAnomaly>,
+}
+
 // ------------------------------------------------------------------------------------------------
 // Implementations
 // ------------------------------------------------------------------------------------------------
@@ -42,7 +152,7 @@ impl SimulatorStats {
     /// Records a transaction and its status, incrementing appropriate counters
     pub fn record_transaction(&mut self, status: &TransactionStatus) {
         self.total_transactions += 1;
-        if matches!(status, TransactionStatus::Failure) {
+        if status.is_failure() {
             self.cancelled_transactions += 1;
         }
     }
@@ -88,4 +198,344 @@ impl SimulatorStats {
         println!("SIMULATOR: Cancellation Rate: {:.1}%", cancellation_rate);
         println!("SIMULATOR: Average TPS: {:.2}", tps);
     }
-} 
\ No newline at end of file
+}
+
+impl Default for RuntimeMetricsSampler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RuntimeMetricsSampler {
+    /// Creates a new sampler, using the current busy durations as baseline
+    pub fn new() -> Self {
+        let last_busy_durations = tokio::runtime::Handle::try_current()
+            .map(|handle| {
+                let metrics = handle.metrics();
+                (0..metrics.num_workers()).map(|worker| metrics.worker_total_busy_duration(worker)).collect()
+            })
+            .unwrap_or_default();
+        Self {
+            last_sample_time: Instant::now(),
+            last_busy_durations,
+        }
+    }
+
+    /// Takes a sample of the runtime metrics
+    ///
+    /// Returns an all-zero sample when called outside a tokio runtime.
+    pub fn sample(&mut self) -> RuntimeMetricsSample {
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            return RuntimeMetricsSample::default();
+        };
+        let metrics = handle.metrics();
+        let num_workers = metrics.num_workers();
+
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_sample_time).as_secs_f64();
+        let busy_durations: Vec<Duration> = (0..num_workers).map(|worker| metrics.worker_total_busy_duration(worker)).collect();
+        let busy_percent = if elapsed > 0.0 && num_workers > 0 {
+            let busy: f64 = busy_durations.iter().enumerate()
+                .map(|(worker, busy)| busy.saturating_sub(self.last_busy_durations.get(worker).copied().unwrap_or_default()).as_secs_f64())
+                .sum();
+            busy / elapsed / num_workers as f64 * 100.0
+        } else {
+            0.0
+        };
+        self.last_sample_time = now;
+        self.last_busy_durations = busy_durations;
+
+        #[cfg(tokio_unstable)]
+        let (local_queue_depth, mean_poll_time_us) = {
+            let local_queue_depth: usize = (0..num_workers).map(|worker| metrics.worker_local_queue_depth(worker)).sum();
+            let mean_poll_time_us = if num_workers > 0 {
+                (0..num_workers).map(|worker| metrics.worker_mean_poll_time(worker).as_secs_f64() * 1_000_000.0).sum::<f64>() / num_workers as f64
+            } else {
+                0.0
+            };
+            (local_queue_depth, mean_poll_time_us)
+        };
+        #[cfg(not(tokio_unstable))]
+        let (local_queue_depth, mean_poll_time_us) = (0, 0.0);
+
+        RuntimeMetricsSample {
+            alive_tasks: metrics.num_alive_tasks() as u64,
+            queue_depth: (metrics.global_queue_depth() + local_queue_depth) as u64,
+            busy_percent,
+            mean_poll_time_us,
+        }
+    }
+}
+
+impl RateAnomalyDetector {
+    /// Creates a detector
+    ///
+    /// # Arguments
+    /// * `window_blocks` - Number of recent blocks a value is compared to
+    /// * `sigma_threshold` - Deviation in standard deviations that flags a value (0 = disabled)
+    pub fn new(window_blocks: usize, sigma_threshold: f64) -> Self {
+        Self {
+            window_blocks,
+            sigma_threshold,
+            windows: BTreeMap::new(),
+            ongoing: BTreeMap::new(),
+            anomalies: Vec::new(),
+        }
+    }
+
+    /// Returns true if the detector flags anything at all
+    pub fn is_enabled(&self) -> bool {
+        self.sigma_threshold > 0.0 && self.window_blocks > 1
+    }
+
+    /// Records the value of a metric at a block and checks it against the metric's window
+    ///
+    /// # Returns
+    /// The anomaly if the value starts one
+    pub fn observe(&mut self, metric: &str, height: u64, value: f64) -> Option<&RateAnomaly> {
+        if !self.is_enabled() {
+            return None;
+        }
+        let window = self.windows.entry(metric.to_string()).or_default();
+        let deviation = (window.len() == self.window_blocks).then(|| {
+            let mean = window.iter().sum::<f64>() / window.len() as f64;
+            let variance = window.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / window.len() as f64;
+            let std = variance.sqrt().max(mean.abs() * RATE_ANOMALY_MIN_SIGMA_SHARE).max(f64::EPSILON);
+            (mean, std, (value - mean).abs() / std)
+        });
+        window.push_back(value);
+        if window.len() > self.window_blocks {
+            window.pop_front();
+        }
+
+        match deviation {
+            Some((mean, std, sigmas)) if sigmas > self.sigma_threshold => {
+                if let Some(&index) = self.ongoing.get(metric) {
+                    let anomaly = &mut self.anomalies[index];
+                    anomaly.blocks += 1;
+                    anomaly.peak_sigmas = anomaly.peak_sigmas.max(sigmas);
+                    return None;
+                }
+                self.ongoing.insert(metric.to_string(), self.anomalies.len());
+                self.anomalies.push(RateAnomaly {
+                    metric: metric.to_string(),
+                    height,
+                    blocks: 1,
+                    value,
+                    window_mean: mean,
+                    window_std: std,
+                    peak_sigmas: sigmas,
+                });
+                self.anomalies.last()
+            }
+            _ => {
+                self.ongoing.remove(metric);
+                None
+            }
+        }
+    }
+
+    /// The anomalies flagged so far, in the order they started
+    pub fn anomalies(&self) -> &[RateAnomaly] {
+        &self.anomalies
+    }
+}
+
+impl ProcessingTimeHistogram {
+    /// Builds the histogram over the total time of each subblock
+    pub fn from_timings(timings: &[(u64, SubblockTiming)]) -> Self {
+        let mut counts = [0; PROCESSING_TIME_BUCKETS_MS.len() + 1];
+        for (_, timing) in timings {
+            let ms = timing.total().as_secs_f64() * 1000.0;
+            let bucket = PROCESSING_TIME_BUCKETS_MS.iter().position(|&bound| ms <= bound).unwrap_or(PROCESSING_TIME_BUCKETS_MS.len());
+            counts[bucket] += 1;
+        }
+        Self { counts }
+    }
+
+    /// Returns the buckets as JSON (`le_ms` is null for the overflow bucket)
+    pub fn to_json(&self) -> Vec<serde_json::Value> {
+        self.counts.iter().enumerate().map(|(bucket, count)| {
+            serde_json::json!({
+                "le_ms": PROCESSING_TIME_BUCKETS_MS.get(bucket),
+                "count": count
+            })
+        }).collect()
+    }
+}
+
+impl CatTimeHistogram {
+    /// Builds the histogram over the given durations
+    pub fn from_durations(durations: &[Duration]) -> Self {
+        let mut counts = [0; CAT_TIME_BUCKETS_MS.len() + 1];
+        for duration in durations {
+            let ms = duration.as_secs_f64() * 1000.0;
+            let bucket = CAT_TIME_BUCKETS_MS.iter().position(|&bound| ms <= bound).unwrap_or(CAT_TIME_BUCKETS_MS.len());
+            counts[bucket] += 1;
+        }
+        Self { counts }
+    }
+
+    /// Returns the buckets as JSON (`le_ms` is null for the overflow bucket)
+    pub fn to_json(&self) -> Vec<serde_json::Value> {
+        self.counts.iter().enumerate().map(|(bucket, count)| {
+            serde_json::json!({
+                "le_ms": CAT_TIME_BUCKETS_MS.get(bucket),
+                "count": count
+            })
+        }).collect()
+    }
+}
+
+/// Collects the time a HIG spent on every subblock in `from..=to`
+///
+/// Blocks the HIG has not processed (yet) are skipped.
+///
+/// # Arguments
+/// * `hig_node` - The HIG node of the chain
+/// * `from` - The first block height
+/// * `to` - The last block height
+pub async fn collect_subblock_timings(hig_node: &Arc<Mutex<HyperIGNode>>, from: u64, to: u64) -> Vec<(u64, SubblockTiming)> {
+    let node = hig_node.lock().await;
+    let mut timings = Vec::new();
+    for height in from..=to {
+        if let Ok(Some(timing)) = node.get_subblock_timing(height).await {
+            timings.push((height, timing));
+        }
+    }
+    timings
+}
+
+/// Collects the lock wait times of all keys of a chain, hottest first
+///
+/// # Arguments
+/// * `hig_node` - The HIG node of the chain
+pub async fn collect_key_lock_waits(hig_node: &Arc<Mutex<HyperIGNode>>) -> Vec<KeyLockWait> {
+    hig_node.lock().await.get_hottest_keys(usize::MAX).await.unwrap_or_default()
+}
+
+/// Converts the lock wait times of the hottest keys to JSON (milliseconds)
+pub fn key_lock_waits_to_json(key_lock_waits: &[KeyLockWait]) -> serde_json::Value {
+    key_lock_waits.iter().take(HOTTEST_KEYS_LISTED).map(|key_lock_wait| serde_json::json!({
+        "key": key_lock_wait.key,
+        "total_wait_ms": key_lock_wait.total_wait.as_secs_f64() * 1000.0,
+        "max_wait_ms": key_lock_wait.max_wait.as_secs_f64() * 1000.0,
+        "blocked_count": key_lock_wait.blocked_count
+    })).collect()
+}
+
+/// Collects the realized delays of the proposals a HIG sent to the HS, in send order
+///
+/// # Arguments
+/// * `hig_node` - The HIG node of the chain
+pub async fn collect_realized_chain_delays(hig_node: &Arc<Mutex<HyperIGNode>>) -> Vec<Duration> {
+    hig_node.lock().await.get_realized_hs_message_delays().await
+}
+
+/// Summarizes the realized delays of a chain's proposals as JSON (blocks)
+///
+/// # Arguments
+/// * `delays` - The realized delays
+/// * `configured_blocks` - The configured chain delay in blocks
+/// * `block_interval` - The block interval in seconds
+pub fn realized_chain_delays_to_json(delays: &[Duration], configured_blocks: f64, block_interval: f64) -> serde_json::Value {
+    let to_blocks = |ms: f64| if block_interval > 0.0 { ms / 1000.0 / block_interval } else { 0.0 };
+    serde_json::json!({
+        "configured_blocks": configured_blocks,
+        "count": delays.len(),
+        "mean_blocks": to_blocks(mean_duration_ms(delays)),
+        "p50_blocks": to_blocks(percentile_ms(delays, 50.0)),
+        "p90_blocks": to_blocks(percentile_ms(delays, 90.0)),
+        "p99_blocks": to_blocks(percentile_ms(delays, 99.0)),
+        "max_blocks": to_blocks(percentile_ms(delays, 100.0)),
+        "delays_blocks": delays.iter().map(|delay| to_blocks(delay.as_secs_f64() * 1000.0)).collect::<Vec<_>>()
+    })
+}
+
+/// Summarizes the times between consecutive CL blocks as JSON (milliseconds)
+///
+/// # Arguments
+/// * `inter_block_times` - The height of each block and the time since the block before it
+/// * `block_interval` - The configured block interval in seconds
+pub fn inter_block_times_to_json(inter_block_times: &[(u64, Duration)], block_interval: f64) -> serde_json::Value {
+    let gaps: Vec<Duration> = inter_block_times.iter().map(|(_, gap)| *gap).collect();
+    serde_json::json!({
+        "block_interval_ms": block_interval * 1000.0,
+        "count": gaps.len(),
+        "mean_ms": mean_duration_ms(&gaps),
+        "min_ms": gaps.iter().min().map_or(0.0, |gap| gap.as_secs_f64() * 1000.0),
+        "p50_ms": percentile_ms(&gaps, 50.0),
+        "p90_ms": percentile_ms(&gaps, 90.0),
+        "p99_ms": percentile_ms(&gaps, 99.0),
+        "max_ms": percentile_ms(&gaps, 100.0),
+        "inter_block_times": inter_block_times.iter().map(|(height, gap)| serde_json::json!({
+            "height": height,
+            "ms": gap.as_secs_f64() * 1000.0
+        })).collect::<Vec<_>>()
+    })
+}
+
+/// Collects the CATs the HS still waits on a chain's proposal for, longest waiting first
+///
+/// # Arguments
+/// * `hs_node` - The HS node
+/// * `chain_id` - The chain the CATs wait on
+pub async fn collect_pending_cats(hs_node: &Arc<Mutex<HyperSchedulerNode>>, chain_id: ChainId) -> Vec<PendingCAT> {
+    hs_node.lock().await.get_pending_cats_for_chain(chain_id).await.unwrap_or_default()
+}
+
+/// Converts the CATs waiting on a chain to JSON (milliseconds)
+pub fn pending_cats_to_json(pending_cats: &[PendingCAT]) -> serde_json::Value {
+    pending_cats.iter().map(|pending_cat| serde_json::json!({
+        "cat_id": pending_cat.cat_id.0.0,
+        "proposed_by": pending_cat.proposed_by.iter().map(|chain_id| chain_id.0.clone()).collect::<Vec<_>>(),
+        "waiting_ms": pending_cat.waiting.as_secs_f64() * 1000.0
+    })).collect()
+}
+
+/// Collects the internal processing metrics of the HS
+///
+/// # Arguments
+/// * `hs_node` - The HS node
+pub async fn collect_hs_metrics(hs_node: &Arc<Mutex<HyperSchedulerNode>>) -> HyperSchedulerMetrics {
+    hs_node.lock().await.get_metrics().await
+}
+
+/// Collects the health of all nodes, e.g. to find the node a stalled run waits on
+///
+/// # Arguments
+/// * `cl_node` - The CL node
+/// * `hs_node` - The HS node
+/// * `network` - The chains of the network
+/// * `hig_nodes` - The HIG nodes, in the order of the chains
+pub async fn collect_network_health(
+    cl_node: &Arc<Mutex<ConfirmationLayerNode>>,
+    hs_node: &Arc<Mutex<HyperSchedulerNode>>,
+    network: &NetworkSpec,
+    hig_nodes: &[Arc<Mutex<HyperIGNode>>],
+) -> NetworkHealth {
+    let hig_nodes: Vec<(ChainId, Arc<Mutex<HyperIGNode>>)> = network.chain_ids().iter().cloned()
+        .zip(hig_nodes.iter().cloned())
+        .collect();
+    NetworkHealth::collect(cl_node, hs_node, &hig_nodes).await
+}
+
+/// Mean of a list of durations in milliseconds (0 for an empty list)
+pub fn mean_duration_ms(durations: &[Duration]) -> f64 {
+    if durations.is_empty() {
+        0.0
+    } else {
+        durations.iter().map(|duration| duration.as_secs_f64() * 1000.0).sum::<f64>() / durations.len() as f64
+    }
+}
+
+/// Nearest-rank percentile of a list of durations in milliseconds (0 for an empty list)
+pub fn percentile_ms(durations: &[Duration], percentile: f64) -> f64 {
+    if durations.is_empty() {
+        return 0.0;
+    }
+    let mut sorted = durations.to_vec();
+    sorted.sort();
+    let rank = ((percentile / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1].as_secs_f64() * 1000.0
+}
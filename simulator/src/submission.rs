@@ -0,0 +1,178 @@
+//! Batched and rate-limited submission of generated transactions to the CL.
+//!
+//! Transactions are queued and handed to the CL in batches under a single lock of the CL node,
+//! optionally throttled by a token bucket, so high-TPS runs are not bound by one await per
+//! transaction. The queue also tracks the submission rate it actually achieved.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+use tokio::time::sleep;
+use hyperplane::{
+    types::CLTransaction,
+    confirmation_layer::ConfirmationLayer,
+    utils::logging,
+};
+
+// ------------------------------------------------------------------------------------------------
+// Rate Limiting
+// ------------------------------------------------------------------------------------------------
+
+/// Token bucket that limits the number of transactions submitted per second
+#[derive(Debug)]
+pub struct TokenBucket {
+    /// Tokens added per second
+    rate: f64,
+    /// Maximum number of tokens in the bucket
+    capacity: f64,
+    /// Tokens currently in the bucket
+    tokens: f64,
+    /// Time of the last refill
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    /// Creates a full token bucket
+    ///
+    /// # Arguments
+    ///
+    /// * `rate` - Tokens added per second
+    /// * `capacity` - Maximum number of tokens in the bucket
+    pub fn new(rate: f64, capacity: f64) -> Self {
+        Self { rate, capacity, tokens: capacity, last_refill: Instant::now() }
+    }
+
+    /// Waits until `n` tokens are available and takes them from the bucket
+    ///
+    /// Requests for more tokens than the capacity wait for a full bucket and leave it in debt.
+    pub async fn acquire(&mut self, n: u64) {
+        let needed = (n as f64).min(self.capacity);
+        self.refill();
+        if self.tokens < needed {
+            sleep(Duration::from_secs_f64((needed - self.tokens) / self.rate)).await;
+            self.refill();
+        }
+        self.tokens -= n as f64;
+    }
+
+    /// Adds the tokens accumulated since the last refill
+    fn refill(&mut self) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.capacity);
+        self.last_refill = now;
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Submission Queue
+// ------------------------------------------------------------------------------------------------
+
+/// Queue of generated transactions that submits them to the CL in batches
+#[derive(Debug)]
+pub struct SubmissionQueue {
+    /// Number of transactions per batch
+    batch_size: usize,
+    /// Rate limiter (None = unlimited)
+    rate_limiter: Option<TokenBucket>,
+    /// CL transactions of the queued transactions, one entry per generated transaction
+    pending: Vec<Vec<CLTransaction>>,
+    /// Number of transactions submitted so far
+    submitted: u64,
+    /// Number of batches submitted so far
+    batches: u64,
+    /// Time the queue was created
+    start: Instant,
+    /// Time the last batch was submitted
+    last_submission: Option<Instant>,
+}
+
+impl SubmissionQueue {
+    /// Creates a new submission queue
+    ///
+    /// # Arguments
+    ///
+    /// * `batch_size` - Number of transactions per batch (1 = every transaction is submitted on its own)
+    /// * `rate_limit_tps` - Maximum number of transactions submitted per second (0 = unlimited)
+    pub fn new(batch_size: usize, rate_limit_tps: f64) -> Self {
+        let batch_size = batch_size.max(1);
+        // Allow bursts of one second of transactions, but at least one batch
+        let rate_limiter = (rate_limit_tps > 0.0)
+            .then(|| TokenBucket::new(rate_limit_tps, rate_limit_tps.max(batch_size as f64)));
+        Self {
+            batch_size,
+            rate_limiter,
+            pending: Vec::new(),
+            submitted: 0,
+            batches: 0,
+            start: Instant::now(),
+            last_submission: None,
+        }
+    }
+
+    /// Queues a generated transaction and submits the batch once it is full
+    ///
+    /// # Arguments
+    ///
+    /// * `cl_node` - A reference to the confirmation layer node
+    /// * `cl_transactions` - The CL transactions the generated transaction is submitted as
+    pub async fn submit<C: ConfirmationLayer>(
+        &mut self,
+        cl_node: &Arc<Mutex<C>>,
+        cl_transactions: Vec<CLTransaction>,
+    ) -> Result<(), String> {
+        self.pending.push(cl_transactions);
+        if self.pending.len() >= self.batch_size {
+            self.flush(cl_node).await?;
+        }
+        Ok(())
+    }
+
+    /// Submits all queued transactions
+    ///
+    /// # Arguments
+    ///
+    /// * `cl_node` - A reference to the confirmation layer node
+    pub async fn flush<C: ConfirmationLayer>(&mut self, cl_node: &Arc<Mutex<C>>) -> Result<(), String> {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+        let batch = std::mem::take(&mut self.pending);
+        if let Some(rate_limiter) = &mut self.rate_limiter {
+            rate_limiter.acquire(batch.len() as u64).await;
+        }
+
+        // Submit the whole batch under a single lock of the CL node
+        let mut cl_node = cl_node.lock().await;
+        for cl_tx in batch.iter().flatten() {
+            cl_node.submit_transaction(cl_tx.clone()).await
+                .map_err(|e| format!("Failed to submit CL transaction {}: {}", cl_tx.id, e))?;
+        }
+        drop(cl_node);
+
+        self.submitted += batch.len() as u64;
+        self.batches += 1;
+        self.last_submission = Some(Instant::now());
+        logging::log("SIMULATOR", &format!("Submitted batch of {} transactions ({} in total)", batch.len(), self.submitted));
+        Ok(())
+    }
+
+    /// Returns the number of transactions submitted so far
+    pub fn submitted(&self) -> u64 {
+        self.submitted
+    }
+
+    /// Returns the number of batches submitted so far
+    pub fn batches(&self) -> u64 {
+        self.batches
+    }
+
+    /// Returns the achieved submission rate in transactions per second, from the creation of the
+    /// queue until the last submitted batch
+    pub fn achieved_tps(&self) -> f64 {
+        match self.last_submission {
+            Some(last) if last > self.start => self.submitted as f64 / last.duration_since(self.start).as_secs_f64(),
+            _ => 0.0,
+        }
+    }
+}
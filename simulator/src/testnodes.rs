@@ -3,13 +3,15 @@
 //! Provides utilities for initializing and controlling simulated blockchain nodes.
 
 use hyperplane::{
-    types::ChainId,
+    types::{CATStatusUpdate, CLTransaction, Clock, NetworkSpec, SimulatedClock, SubBlock, SystemClock},
     confirmation_layer::node::ConfirmationLayerNode,
-    confirmation_layer::ConfirmationLayer,
     hyper_scheduler::node::HyperSchedulerNode,
     hyper_ig::node::HyperIGNode,
-    utils::logging,
+    harness::{NetworkBuilder, ChainOptions, Links},
 };
+use crate::network::{delayed_link, gated_link, NetworkPartitions, TopologyConfig};
+use crate::failover::{FailoverLinks, WarmStandbys};
+use crate::config::SimulationConfig;
 use tokio::time::Duration;
 use tokio::sync::mpsc;
 use std::sync::Arc;
@@ -19,6 +21,41 @@ use tokio::sync::Mutex;
 // Node Setup Functions
 // ------------------------------------------------------------------------------------------------
 
+/// Simulated network links of the configured topology
+struct SimulatedLinks {
+    topology: TopologyConfig,
+    partitions: NetworkPartitions,
+    block_interval: Duration,
+    channel_buffer_size: usize,
+    clock: Arc<dyn Clock>,
+}
+
+impl SimulatedLinks {
+    /// Gates a link of the chain at `chain_index` if the chain can be partitioned
+    fn gated<T: Send + 'static>(&self, chain_index: usize, receiver: mpsc::Receiver<T>) -> mpsc::Receiver<T> {
+        match self.partitions.gate(chain_index) {
+            Some(gate) => gated_link(receiver, gate, self.channel_buffer_size),
+            None => receiver,
+        }
+    }
+}
+
+impl Links for SimulatedLinks {
+    fn cl_to_hig(&self, chain_index: usize, receiver: mpsc::Receiver<SubBlock>) -> mpsc::Receiver<SubBlock> {
+        let receiver = delayed_link(receiver, self.topology.cl_to_hig(chain_index), self.topology.latency_multipliers(), self.block_interval, self.channel_buffer_size, self.clock.clone());
+        self.gated(chain_index, receiver)
+    }
+
+    fn hig_to_hs(&self, chain_index: usize, receiver: mpsc::Receiver<CATStatusUpdate>) -> mpsc::Receiver<CATStatusUpdate> {
+        let receiver = delayed_link(receiver, self.topology.hig_to_hs(chain_index), self.topology.latency_multipliers(), self.block_interval, self.channel_buffer_size, self.clock.clone());
+        self.gated(chain_index, receiver)
+    }
+
+    fn hs_to_cl(&self, receiver: mpsc::Receiver<CLTransaction>) -> mpsc::Receiver<CLTransaction> {
+        delayed_link(receiver, self.topology.hs_to_cl, self.topology.latency_multipliers(), self.block_interval, self.channel_buffer_size, self.clock.clone())
+    }
+}
+
 // Helper function to create test nodes with basic setup
 /// Returns a tuple of the nodes and the current block number at the end of the setup
 ///
@@ -26,79 +63,108 @@ use tokio::sync::Mutex;
 ///
 /// * `block_interval` - The block interval to use for the confirmation layer node
 /// * `chain_delays` - The delays to use for the hyperig nodes (in blocks)
-/// * `allow_cat_pending_dependencies` - Whether to allow CATs to depend on locked keys
+/// * `allow_cat_pending_dependencies` - Whether to allow CATs to depend on locked keys, one value per chain
 /// * `cat_lifetime_blocks` - The default lifetime for CATs in blocks
-/// * `num_accounts` - Number of accounts to preload (0 for no preloading)
-/// * `preload_value` - Value to preload each account with
+/// * `account_balances` - The (account, balance) pairs to preload, one list per chain
 /// * `channel_buffer_size` - Buffer size for communication channels
+/// * `topology` - Per-link delay and jitter between the nodes
+/// * `partitions` - Partition gates of the chains that can be partitioned during the run
+/// * `network` - The chains of the network, with at least two chains
+/// * `clock` - The clock the nodes and the links run on (see `simulation_clock`)
 ///
 /// # Returns
 ///
 /// * `hs_node` - The hyperscheduler node
 /// * `cl_node` - The confirmation layer node
-/// * `hig_node_1` - The hyperig node for chain-1
-/// * `hig_node_2` - The hyperig node for chain-2
+/// * `hig_node_1` - The hyperig node for the first chain
+/// * `hig_node_2` - The hyperig node for the second chain
 /// * `current_block` - The current block number at the end of the setup
 ///
-pub async fn setup_test_nodes(block_interval: Duration, chain_delays: &[f64], allow_cat_pending_dependencies: bool, cat_lifetime_blocks: u64, num_accounts: u32, preload_value: u32, channel_buffer_size: usize) 
+pub async fn setup_test_nodes(block_interval: Duration, chain_delays: &[f64], allow_cat_pending_dependencies: &[bool], cat_lifetime_blocks: u64, account_balances: &[Vec<(u32, u32)>], channel_buffer_size: usize, topology: &TopologyConfig, partitions: &NetworkPartitions, network: &NetworkSpec, clock: Arc<dyn Clock>) 
 -> (Arc<Mutex<HyperSchedulerNode>>, Arc<Mutex<ConfirmationLayerNode>>, Arc<Mutex<HyperIGNode>>, Arc<Mutex<HyperIGNode>>, u64) {
-    // Note: Logging should be initialized by the calling code before calling this function
+    let links = SimulatedLinks { topology: topology.clone(), partitions: partitions.clone(), block_interval, channel_buffer_size, clock: clock.clone() };
+    let options = chain_options(allow_cat_pending_dependencies, cat_lifetime_blocks, channel_buffer_size);
+    build_test_nodes(block_interval, chain_delays, allow_cat_pending_dependencies, &options, account_balances, network, links, clock).await
+}
 
-    // Create channels for communication
-    let (sender_hs_to_cl, receiver_hs_to_cl) = mpsc::channel(channel_buffer_size);
-    let (sender_hig1_to_hs, receiver_hig1_to_hs) = mpsc::channel(channel_buffer_size);
-    let (sender_hig2_to_hs, receiver_hig2_to_hs) = mpsc::channel(channel_buffer_size);
-    let (sender_cl_to_hig1, receiver_cl_to_hig1) = mpsc::channel(channel_buffer_size);
-    let (sender_cl_to_hig2, receiver_cl_to_hig2) = mpsc::channel(channel_buffer_size);
-    
-    // Create nodes with their channels
-    let hs_node = Arc::new(Mutex::new(HyperSchedulerNode::new(sender_hs_to_cl)));
-    let cl_node = Arc::new(Mutex::new(ConfirmationLayerNode::new_with_block_interval(
-        receiver_hs_to_cl,
-        block_interval,
-    ).expect("Failed to create confirmation node")));
-    let hig_node_1 = Arc::new(Mutex::new(HyperIGNode::new_with_preloaded_accounts(receiver_cl_to_hig1, sender_hig1_to_hs, ChainId("chain-1".to_string()), cat_lifetime_blocks, allow_cat_pending_dependencies, num_accounts, preload_value)));
-    let hig_node_2 = Arc::new(Mutex::new(HyperIGNode::new_with_preloaded_accounts(receiver_cl_to_hig2, sender_hig2_to_hs, ChainId("chain-2".to_string()), cat_lifetime_blocks, allow_cat_pending_dependencies, num_accounts, preload_value)));
-
-    // Start the nodes
-    HyperSchedulerNode::start(hs_node.clone()).await;
-    HyperIGNode::start(hig_node_1.clone()).await;
-    HyperIGNode::start(hig_node_2.clone()).await;
-    ConfirmationLayerNode::start(cl_node.clone()).await;
-
-    // Register chains in CL
-    let chain_id_1 = ChainId("chain-1".to_string());
-    let chain_id_2 = ChainId("chain-2".to_string());
-    {
-        let mut cl_node_guard = cl_node.lock().await;
-        cl_node_guard.register_chain(chain_id_1.clone(), sender_cl_to_hig1).await.expect("Failed to register chain");
-        cl_node_guard.register_chain(chain_id_2.clone(), sender_cl_to_hig2).await.expect("Failed to register chain");
+/// Sets up the test nodes like `setup_test_nodes`, and gives every chain a warm standby HIG
+///
+/// The standbys receive the same subblocks as the primary HIGs, but their proposals only reach
+/// the HS once they take over (see `crate::failover`). They start with the chain delays of the
+/// primaries and without a watchdog.
+///
+/// # Returns
+///
+/// The nodes as returned by `setup_test_nodes`, followed by the standbys
+pub async fn setup_test_nodes_with_standbys(block_interval: Duration, chain_delays: &[f64], allow_cat_pending_dependencies: &[bool], cat_lifetime_blocks: u64, account_balances: &[Vec<(u32, u32)>], channel_buffer_size: usize, topology: &TopologyConfig, partitions: &NetworkPartitions, network: &NetworkSpec, clock: Arc<dyn Clock>)
+-> (Arc<Mutex<HyperSchedulerNode>>, Arc<Mutex<ConfirmationLayerNode>>, Arc<Mutex<HyperIGNode>>, Arc<Mutex<HyperIGNode>>, u64, WarmStandbys) {
+    let links = SimulatedLinks { topology: topology.clone(), partitions: partitions.clone(), block_interval, channel_buffer_size, clock: clock.clone() };
+    let (links, standby_handle) = FailoverLinks::new(links, channel_buffer_size);
+    let options = chain_options(allow_cat_pending_dependencies, cat_lifetime_blocks, channel_buffer_size);
+    let (hs_node, cl_node, hig_node_1, hig_node_2, start_block) =
+        build_test_nodes(block_interval, chain_delays, allow_cat_pending_dependencies, &options, account_balances, network, links, clock).await;
+
+    let standbys = standby_handle.start_standbys(&cl_node, network.chain_ids(), &options, account_balances).await
+        .expect("Failed to start standby HIGs");
+    for (index, standby) in standbys.standby_nodes.iter().enumerate() {
+        let standby = standby.lock().await;
+        standby.set_allow_cat_pending_dependencies(allow_cat_pending_dependencies[index]).await;
+    }
+    for (standby, delay) in standbys.standby_nodes.iter().zip(chain_delays) {
+        standby.lock().await.set_hs_message_delay(Duration::from_secs_f64(block_interval.as_secs_f64() * delay));
     }
 
-    // Register chains in HS
-    {
-        let mut hs_node_guard = hs_node.lock().await;
-        hs_node_guard.register_chain(chain_id_1.clone(), receiver_hig1_to_hs).await.expect("Failed to register chain");
-        hs_node_guard.register_chain(chain_id_2.clone(), receiver_hig2_to_hs).await.expect("Failed to register chain");
+    (hs_node, cl_node, hig_node_1, hig_node_2, start_block, standbys)
+}
+
+/// Returns the clock the nodes of a run run on
+///
+/// With `simulated_clock` set this is a `SimulatedClock` that jumps to the next deadline whenever
+/// the nodes, the links and the submission loop wait on it, so a run takes as long as the nodes
+/// need to process it instead of its number of blocks times the block interval. Otherwise the
+/// nodes run in real time.
+///
+/// # Arguments
+///
+/// * `simulation_config` - The simulation settings of the run
+pub fn simulation_clock(simulation_config: &SimulationConfig) -> Arc<dyn Clock> {
+    if simulation_config.simulated_clock {
+        let clock = Arc::new(SimulatedClock::new());
+        // The driver stops once the nodes of the run dropped the clock
+        clock.spawn_auto_advance();
+        clock
+    } else {
+        Arc::new(SystemClock::new())
     }
+}
 
-    // Wait for block production to be ready
-    let mut attempts = 0;
-    while attempts < 10 {
-        if let Ok(interval) = cl_node.lock().await.get_block_interval().await {
-            if interval == block_interval {
-                break;
-            }
-        }
-        tokio::time::sleep(Duration::from_millis(100)).await;
-        logging::log("TEST", &format!("Waiting for block production to be ready.. attempt: {}", attempts));
-        attempts += 1;
+/// Settings shared by the HIGs of all chains
+fn chain_options(allow_cat_pending_dependencies: &[bool], cat_lifetime_blocks: u64, channel_buffer_size: usize) -> ChainOptions {
+    ChainOptions {
+        cat_lifetime: cat_lifetime_blocks,
+        allow_cat_pending_dependencies: allow_cat_pending_dependencies[0],
+        account_balances: Vec::new(),
+        channel_buffer_size,
     }
+}
 
-    // Wait a couple of blocks to ensure the block production is ready
-    tokio::time::sleep(block_interval * 2).await;
-    let current_block = cl_node.lock().await.get_current_block().await.unwrap();
-    logging::log("NODES SETUP", &format!("Nodes setup complete, current block: {}", current_block));
+/// Builds the network over the given links and applies the per-chain HIG settings
+async fn build_test_nodes(block_interval: Duration, chain_delays: &[f64], allow_cat_pending_dependencies: &[bool], options: &ChainOptions, account_balances: &[Vec<(u32, u32)>], network: &NetworkSpec, links: impl Links + 'static, clock: Arc<dyn Clock>)
+-> (Arc<Mutex<HyperSchedulerNode>>, Arc<Mutex<ConfirmationLayerNode>>, Arc<Mutex<HyperIGNode>>, Arc<Mutex<HyperIGNode>>, u64) {
+    // Note: Logging should be initialized by the calling code before calling this function
+    let network = NetworkBuilder::new(block_interval)
+        .network(network)
+        .chain_options(options.clone())
+        .account_balances(account_balances.to_vec())
+        .links(links)
+        .clock(clock)
+        .runtimes(crate::runtime::component_runtimes())
+        .build()
+        .await
+        .expect("Failed to set up test nodes");
+    let mut hig_nodes = network.hig_nodes.into_iter();
+    let hig_node_1 = hig_nodes.next().expect("Missing HIG node for the first chain");
+    let hig_node_2 = hig_nodes.next().expect("Missing HIG node for the second chain");
 
     // Set the provided delays (convert from blocks to time)
     let time_delay_1 = Duration::from_secs_f64(block_interval.as_secs_f64() * chain_delays[0]);
@@ -106,5 +172,38 @@ pub async fn setup_test_nodes(block_interval: Duration, chain_delays: &[f64], al
     hig_node_1.lock().await.set_hs_message_delay(time_delay_1);
     hig_node_2.lock().await.set_hs_message_delay(time_delay_2);
 
-    (hs_node, cl_node, hig_node_1, hig_node_2, current_block)
-} 
\ No newline at end of file
+    // Chains may differ in whether CATs can depend on pending transactions
+    hig_node_1.lock().await.set_allow_cat_pending_dependencies(allow_cat_pending_dependencies[0]).await;
+    hig_node_2.lock().await.set_allow_cat_pending_dependencies(allow_cat_pending_dependencies[1]).await;
+
+    (network.hs_node, network.cl_node, hig_node_1, hig_node_2, network.start_block)
+}
+
+/// Applies the configured clock skews to the HS and the HIG nodes
+///
+/// # Arguments
+///
+/// * `hs_node` - The hyperscheduler node
+/// * `hig_nodes` - The hyperig nodes, in chain order
+/// * `clock_skew` - The clock skew configuration
+pub async fn apply_clock_skews(hs_node: &Arc<Mutex<HyperSchedulerNode>>, hig_nodes: &[Arc<Mutex<HyperIGNode>>], clock_skew: &crate::config::ClockSkewConfig) {
+    hs_node.lock().await.set_clock_skew(clock_skew.hs).await;
+    for (index, hig_node) in hig_nodes.iter().enumerate() {
+        hig_node.lock().await.set_clock_skew(clock_skew.hig(index)).await;
+    }
+}
+
+/// Applies the configured per-chain delays of the HS status updates
+///
+/// # Arguments
+///
+/// * `hs_node` - The hyperscheduler node
+/// * `network` - The chains of the network
+/// * `delays` - The delays in blocks, in chain order (empty = no delay)
+/// * `block_interval` - The block interval in seconds
+pub async fn apply_status_update_delays(hs_node: &Arc<Mutex<HyperSchedulerNode>>, network: &NetworkSpec, delays: &[f64], block_interval: f64) {
+    let hs_node = hs_node.lock().await;
+    for (chain_id, delay) in network.chain_ids().iter().zip(delays) {
+        hs_node.set_status_update_delay(chain_id.clone(), Duration::from_secs_f64(block_interval * delay)).await;
+    }
+}
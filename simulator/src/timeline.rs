@@ -0,0 +1,174 @@
+//! Timelines of the transactions of a run.
+//!
+//! Joins the points in time recorded by the CL (submission, inclusion), the HIGs (receipt, lock
+//! waits, proposal, finalization) and the HS (decision) into one timeline per CL transaction. The
+//! reports on where the latency of a run goes are computed from these timelines.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::Mutex;
+use serde::Serialize;
+use hyperplane::{
+    types::{CATId, ChainId, Transaction},
+    confirmation_layer::{ConfirmationLayerNode, ConfirmationLayer},
+    hyper_ig::node::HyperIGNode,
+    hyper_ig::HyperIG,
+    hyper_scheduler::node::HyperSchedulerNode,
+};
+use crate::workload::RecordedSubmission;
+
+// ------------------------------------------------------------------------------------------------
+// Data Structures
+// ------------------------------------------------------------------------------------------------
+
+/// Timeline of a CL transaction on one of its chains, in milliseconds since its submission
+#[derive(Debug, Clone, Serialize)]
+pub struct ChainTimeline {
+    pub chain: String,
+    pub tx_id: String,
+    /// Status at the end of the run ("Unknown" if the HIG never saw the transaction)
+    pub status: String,
+    /// Height of the block that included the transaction
+    pub block_height: Option<u64>,
+    pub included_ms: Option<f64>,
+    pub received_ms: Option<f64>,
+    /// Total time the transaction waited for locked keys
+    pub lock_wait_ms: f64,
+    /// Keys the transaction waited for
+    pub blocked_keys: Vec<String>,
+    /// Transactions still holding the locks the transaction waited for at the end of the run
+    pub blocking_txs: Vec<String>,
+    /// Time the HIG queued its status proposal (CATs only)
+    pub proposed_ms: Option<f64>,
+    pub finalized_ms: Option<f64>,
+}
+
+/// Timeline of a CL transaction on all of its chains
+#[derive(Debug, Clone, Serialize)]
+pub struct TransactionTimeline {
+    pub cl_id: String,
+    pub is_cat: bool,
+    /// Time from submission to finalization on the last chain, or to the end of the run if unfinished
+    pub latency_ms: f64,
+    /// Whether the transaction reached a final status on all of its chains
+    pub finished: bool,
+    /// Time the HS decided the CAT (CATs only)
+    pub decided_ms: Option<f64>,
+    pub chains: Vec<ChainTimeline>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Collection
+// ------------------------------------------------------------------------------------------------
+
+/// Collects the timelines of all submitted CL transactions
+///
+/// Must be called before the nodes are shut down, as shutting down clears the recorded times.
+///
+/// # Arguments
+/// * `cl_node` - The CL node
+/// * `hig_nodes` - The HIG nodes, in the order the chains were registered
+/// * `hs_node` - The HS node
+/// * `workload` - The submissions of the run
+pub async fn collect_transaction_timelines(
+    cl_node: &Arc<Mutex<ConfirmationLayerNode>>,
+    hig_nodes: &[Arc<Mutex<HyperIGNode>>],
+    hs_node: &Arc<Mutex<HyperSchedulerNode>>,
+    workload: &[RecordedSubmission],
+) -> Vec<TransactionTimeline> {
+    let now = Instant::now();
+    let cl_clock = cl_node.lock().await.get_clock();
+    let chains: Vec<ChainId> = cl_node.lock().await.get_registered_chains().await.unwrap_or_default();
+
+    // Submission times of the transactions still waiting in the mempool
+    let mut mempool_submitted_at = HashMap::new();
+    for chain_id in &chains {
+        let Ok(mempool) = cl_node.lock().await.get_mempool(chain_id.clone()).await else { continue };
+        for entry in mempool.entries {
+            mempool_submitted_at.insert(entry.transaction.id, now - entry.age);
+        }
+    }
+
+    // Transactions that still wait for locks at the end of the run
+    let mut blocked = Vec::new();
+    for hig_node in hig_nodes {
+        blocked.push(hig_node.lock().await.get_all_blocked_transactions().await.unwrap_or_default());
+    }
+
+    // Group the sub-transactions by CL transaction (the parts of a spread CAT share its id)
+    let mut cl_transactions: BTreeMap<String, (bool, Vec<&Transaction>)> = BTreeMap::new();
+    for submission in workload {
+        for cl_tx in &submission.cl_transactions {
+            cl_transactions.entry(cl_tx.id.0.clone())
+                .or_insert_with(|| (submission.is_cat, Vec::new()))
+                .1
+                .extend(cl_tx.transactions.iter());
+        }
+    }
+
+    let mut timelines = Vec::new();
+    for (cl_id, (is_cat, transactions)) in cl_transactions {
+        let Some(first) = transactions.first() else { continue };
+
+        // The CL transaction was submitted when its first part entered the mempool
+        let mut submitted_at = mempool_submitted_at.get(&first.cl_id).copied();
+        let mut inclusions = Vec::new();
+        for tx in &transactions {
+            let inclusion = cl_node.lock().await.get_inclusion_time(&tx.id).await;
+            if let Some(inclusion) = inclusion {
+                let inclusion_submitted_at = cl_clock.instant_at(inclusion.submitted_at);
+                submitted_at = Some(submitted_at.map_or(inclusion_submitted_at, |at: Instant| at.min(inclusion_submitted_at)));
+            }
+            inclusions.push(inclusion);
+        }
+        let Some(origin) = submitted_at else { continue };
+        let ms = |at: Instant| at.saturating_duration_since(origin).as_secs_f64() * 1000.0;
+
+        let mut chain_timelines = Vec::new();
+        for (tx, inclusion) in transactions.iter().zip(inclusions) {
+            let hig_index = chains.iter().position(|chain_id| *chain_id == tx.chain_id).filter(|index| *index < hig_nodes.len());
+            let (status, timeline, blocked_tx) = match hig_index {
+                Some(index) => {
+                    let node = hig_nodes[index].lock().await;
+                    let status = node.get_transaction_status(tx.id.clone()).await
+                        .map_or("Unknown".to_string(), |status| format!("{:?}", status));
+                    (status, node.get_transaction_timeline(&tx.id).await, blocked[index].get(&tx.id).cloned())
+                }
+                None => ("Unknown".to_string(), None, None),
+            };
+            let blocked_tx = blocked_tx.unwrap_or_default();
+            chain_timelines.push(ChainTimeline {
+                chain: tx.chain_id.0.clone(),
+                tx_id: tx.id.0.clone(),
+                status,
+                block_height: inclusion.map(|inclusion| inclusion.block_height),
+                included_ms: inclusion.map(|inclusion| ms(cl_clock.instant_at(inclusion.included_at))),
+                received_ms: timeline.as_ref().map(|timeline| ms(timeline.received_at)),
+                lock_wait_ms: timeline.as_ref().map_or(0.0, |timeline| timeline.lock_wait.as_secs_f64() * 1000.0),
+                blocked_keys: if blocked_tx.keys.is_empty() {
+                    timeline.as_ref().map(|timeline| timeline.blocked_keys.clone()).unwrap_or_default()
+                } else {
+                    blocked_tx.keys
+                },
+                blocking_txs: blocked_tx.blocking_txs.iter().map(|tx_id| tx_id.0.clone()).collect(),
+                proposed_ms: timeline.as_ref().and_then(|timeline| timeline.proposed_at).map(ms),
+                finalized_ms: timeline.as_ref().and_then(|timeline| timeline.finalized_at).map(ms),
+            });
+        }
+
+        let decided_ms = if is_cat {
+            hs_node.lock().await.get_cat_decision_time(&CATId(first.cl_id.clone())).await.map(ms)
+        } else {
+            None
+        };
+        let finished = chain_timelines.iter().all(|chain| chain.finalized_ms.is_some());
+        let latency_ms = if finished {
+            chain_timelines.iter().filter_map(|chain| chain.finalized_ms).fold(0.0, f64::max)
+        } else {
+            ms(now)
+        };
+        timelines.push(TransactionTimeline { cl_id, is_cat, latency_ms, finished, decided_ms, chains: chain_timelines });
+    }
+    timelines
+}
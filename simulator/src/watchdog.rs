@@ -0,0 +1,250 @@
+//! Stall watchdog for simulation runs.
+//!
+//! A HIG that stops processing does not fail a run: the CL keeps producing blocks, the main loop
+//! keeps submitting and a sweep just stops making progress without a word. The watchdog runs next
+//! to the main loop, compares the height each HIG processed with the height of the CL and watches
+//! whether the queues of the HIGs drain. When a HIG stalls it logs the health of the CL and the
+//! HIGs and, if configured, makes the main loop abort the run.
+
+use std::fmt;
+use std::sync::{Arc, Mutex as StdMutex};
+use std::time::{Duration, Instant};
+use serde::Serialize;
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+use hyperplane::{
+    confirmation_layer::{ConfirmationLayerNode, ConfirmationLayer},
+    harness::HEALTH_TIMEOUT,
+    hyper_ig::{node::HyperIGNode, HyperIGHealth},
+    utils::logging,
+};
+use crate::network::NetworkPartitions;
+
+// ------------------------------------------------------------------------------------------------
+// Data Structures
+// ------------------------------------------------------------------------------------------------
+
+/// Thresholds of the watchdog (a threshold of 0 disables its check)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WatchdogConfig {
+    /// Number of blocks a HIG may fall behind the CL while it does not advance
+    pub max_lag_blocks: u64,
+    /// Seconds the queues of a HIG may stay non-empty without any progress
+    pub queue_stall_secs: f64,
+    /// Whether a stall aborts the run
+    pub abort_on_stall: bool,
+    /// Interval between two checks
+    pub check_interval: Duration,
+}
+
+impl WatchdogConfig {
+    /// Whether any check is enabled
+    pub fn is_enabled(&self) -> bool {
+        self.max_lag_blocks > 0 || self.queue_stall_secs > 0.0
+    }
+}
+
+/// What the watchdog found a HIG stuck on
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum StallKind {
+    /// The HIG fell more than `max_lag_blocks` behind the CL and did not advance since the last check
+    Lagging { cl_height: u64, hig_height: u64 },
+    /// The subblock and proposal queues of the HIG stayed non-empty without progress
+    QueueStalled { queued: usize, seconds: f64 },
+    /// The HIG did not answer within the health timeout, e.g. because a task holds its lock
+    Unresponsive { seconds: f64 },
+}
+
+/// A stall detected by the watchdog
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct WatchdogStall {
+    /// Block of the run at which the stall was detected, counted from the start of the transaction submission
+    pub block: u64,
+    /// Chain of the stalled HIG (numbered from 1, as in chain-1)
+    pub chain: usize,
+    /// What the HIG was stuck on
+    #[serde(flatten)]
+    pub kind: StallKind,
+}
+
+impl fmt::Display for WatchdogStall {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "block {}: HIG of chain-{} ", self.block, self.chain)?;
+        match self.kind {
+            StallKind::Lagging { cl_height, hig_height } =>
+                write!(f, "is stuck at height {}, {} blocks behind the CL at {}", hig_height, cl_height.saturating_sub(hig_height), cl_height),
+            StallKind::QueueStalled { queued, seconds } =>
+                write!(f, "has not drained its {} queued messages for {:.1}s", queued, seconds),
+            StallKind::Unresponsive { seconds } =>
+                write!(f, "has not responded for {:.1}s", seconds),
+        }
+    }
+}
+
+/// Progress of a HIG as seen at the previous checks
+#[derive(Debug, Clone, Copy)]
+struct HigProgress {
+    height: u64,
+    queued: usize,
+    /// Last time the HIG advanced, drained a message or had empty queues
+    since: Instant,
+    /// Whether the current stall was already reported
+    reported: bool,
+}
+
+/// The watchdog task of a run
+pub struct Watchdog {
+    config: WatchdogConfig,
+    /// Stalls detected so far
+    stalls: Arc<StdMutex<Vec<WatchdogStall>>>,
+    /// The watchdog task (None if all checks are disabled)
+    task: Option<JoinHandle<()>>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Watchdog
+// ------------------------------------------------------------------------------------------------
+
+impl Watchdog {
+    /// Spawns the watchdog for the HIGs of a run
+    ///
+    /// HIGs that are halted or partitioned by the fault timeline are expected to fall behind and are
+    /// not checked; their progress is tracked again once they recover.
+    ///
+    /// # Arguments
+    /// * `config` - The thresholds of the watchdog
+    /// * `cl_node` - The CL node
+    /// * `hig_nodes` - The HIG nodes, in chain order
+    /// * `partitions` - The partition gates the nodes were set up with
+    /// * `initial_block` - CL height at the start of the transaction submission
+    pub fn spawn(
+        config: WatchdogConfig,
+        cl_node: Arc<Mutex<ConfirmationLayerNode>>,
+        hig_nodes: Vec<Arc<Mutex<HyperIGNode>>>,
+        partitions: NetworkPartitions,
+        initial_block: u64,
+    ) -> Self {
+        let stalls = Arc::new(StdMutex::new(Vec::new()));
+        if !config.is_enabled() {
+            return Self { config, stalls, task: None };
+        }
+        let recorded = stalls.clone();
+        let task = tokio::spawn(async move {
+            let mut progress: Vec<Option<HigProgress>> = vec![None; hig_nodes.len()];
+            let mut interval = tokio::time::interval(config.check_interval);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                let cl_height = match tokio::time::timeout(HEALTH_TIMEOUT, async { cl_node.lock().await.get_current_block().await }).await {
+                    Ok(Ok(height)) => height,
+                    _ => {
+                        logging::log("SIMULATOR", &format!("CL did not report its height within {:?}", HEALTH_TIMEOUT));
+                        continue;
+                    }
+                };
+                let mut new_stalls = Vec::new();
+                for (chain_index, hig_node) in hig_nodes.iter().enumerate() {
+                    let health = tokio::time::timeout(HEALTH_TIMEOUT, async { hig_node.lock().await.health().await }).await.ok();
+                    if partitions.is_partitioned(chain_index) || health.as_ref().is_some_and(|health| health.halted) {
+                        progress[chain_index] = None;
+                        continue;
+                    }
+                    let block = cl_height.saturating_sub(initial_block);
+                    if let Some(kind) = check_hig(&config, &mut progress[chain_index], cl_height, health.as_ref()) {
+                        new_stalls.push((WatchdogStall { block, chain: chain_index + 1, kind }, health));
+                    }
+                }
+                if new_stalls.is_empty() {
+                    continue;
+                }
+                log_diagnostics(&cl_node, &new_stalls).await;
+                recorded.lock().unwrap().extend(new_stalls.into_iter().map(|(stall, _)| stall));
+            }
+        });
+        Self { config, stalls, task: Some(task) }
+    }
+
+    /// Returns the first stall if the watchdog is configured to abort the run on a stall
+    pub fn abort_reason(&self) -> Option<WatchdogStall> {
+        if !self.config.abort_on_stall {
+            return None;
+        }
+        self.stalls.lock().unwrap().first().copied()
+    }
+
+    /// Stops the watchdog and returns the stalls it detected
+    pub fn stop(self) -> Vec<WatchdogStall> {
+        std::mem::take(&mut *self.stalls.lock().unwrap())
+    }
+}
+
+impl Drop for Watchdog {
+    /// Stops the task, also when the run ends early with an error
+    fn drop(&mut self) {
+        if let Some(task) = &self.task {
+            task.abort();
+        }
+    }
+}
+
+/// Updates the progress of a HIG with its current health and returns the stall it is in, if it was not reported yet
+///
+/// # Arguments
+/// * `config` - The thresholds of the watchdog
+/// * `progress` - Progress of the HIG at the previous checks (None if it was not tracked)
+/// * `cl_height` - Current height of the CL
+/// * `health` - Current health of the HIG (None if it did not respond)
+fn check_hig(config: &WatchdogConfig, progress: &mut Option<HigProgress>, cl_height: u64, health: Option<&HyperIGHealth>) -> Option<StallKind> {
+    let now = Instant::now();
+    let Some(previous) = progress.as_mut() else {
+        // Start tracking, an unresponsive HIG counts as stuck at the height of the CL
+        let (height, queued) = health.map_or((cl_height, 0), |health| (health.current_block_height, queued_messages(health)));
+        *progress = Some(HigProgress { height, queued, since: now, reported: false });
+        return None;
+    };
+    let Some(health) = health else {
+        let stalled_for = now.duration_since(previous.since).as_secs_f64();
+        let lagging = config.max_lag_blocks > 0 && cl_height.saturating_sub(previous.height) > config.max_lag_blocks;
+        let stalled = config.queue_stall_secs > 0.0 && stalled_for >= config.queue_stall_secs;
+        if (lagging || stalled) && !previous.reported {
+            previous.reported = true;
+            return Some(StallKind::Unresponsive { seconds: stalled_for });
+        }
+        return None;
+    };
+
+    let height = health.current_block_height;
+    let queued = queued_messages(health);
+    let advanced = height > previous.height;
+    if advanced || queued < previous.queued || queued == 0 {
+        *previous = HigProgress { height, queued, since: now, reported: false };
+    }
+    let stalled_for = now.duration_since(previous.since).as_secs_f64();
+    // A HIG that catches up is not stuck, however far behind it is
+    let lag = cl_height.saturating_sub(height);
+    if config.max_lag_blocks > 0 && lag > config.max_lag_blocks && !advanced && !previous.reported {
+        previous.reported = true;
+        return Some(StallKind::Lagging { cl_height, hig_height: height });
+    }
+    if config.queue_stall_secs > 0.0 && queued > 0 && stalled_for >= config.queue_stall_secs && !previous.reported {
+        previous.reported = true;
+        return Some(StallKind::QueueStalled { queued, seconds: stalled_for });
+    }
+    None
+}
+
+/// Messages waiting at a HIG: subblocks in the channel from the CL and proposals in its queue
+fn queued_messages(health: &HyperIGHealth) -> usize {
+    health.cl_channel.map_or(0, |channel| channel.queued) + health.proposal_queue_depth
+}
+
+/// Logs the stalls with the health of the CL and the stalled HIGs
+async fn log_diagnostics(cl_node: &Arc<Mutex<ConfirmationLayerNode>>, stalls: &[(WatchdogStall, Option<HyperIGHealth>)]) {
+    let cl_health = tokio::time::timeout(HEALTH_TIMEOUT, async { cl_node.lock().await.health().await }).await.ok();
+    for (stall, health) in stalls {
+        logging::log("SIMULATOR", &format!("Stall detected: {}", stall));
+        logging::log("SIMULATOR", &format!("  HIG health: {:?}", health));
+    }
+    logging::log("SIMULATOR", &format!("  CL health: {:?}", cl_health));
+}
@@ -0,0 +1,70 @@
+//! Workload recording for replaying simulation runs.
+//!
+//! Records every CL transaction submitted during a run together with the block at which it was
+//! submitted, so the identical submission schedule can be fed into freshly built nodes.
+
+use std::fs;
+use serde::{Deserialize, Serialize};
+use hyperplane::types::{CLTransaction, ChainId};
+
+// ------------------------------------------------------------------------------------------------
+// Constants
+// ------------------------------------------------------------------------------------------------
+
+/// Name of the file (inside a run's data directory) that holds the recorded workload
+pub const WORKLOAD_FILE: &str = "workload.json";
+
+// ------------------------------------------------------------------------------------------------
+// Data Structures
+// ------------------------------------------------------------------------------------------------
+
+/// A single generated transaction and the CL transactions it was submitted as
+///
+/// A CAT is submitted as one CL transaction, a regular transaction as one CL transaction per chain.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedSubmission {
+    /// Block offset relative to the first block of transaction submission
+    pub block_offset: u64,
+    /// Whether the transaction is a CAT
+    pub is_cat: bool,
+    /// Sending account
+    pub from_account: usize,
+    /// Receiving account
+    pub to_account: usize,
+    /// The CL transactions submitted for this transaction
+    pub cl_transactions: Vec<CLTransaction>,
+    /// The chain that initiated the CAT (None for regular transactions and older recordings)
+    #[serde(default)]
+    pub initiating_chain: Option<ChainId>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Persistence
+// ------------------------------------------------------------------------------------------------
+
+/// Saves a recorded workload to a run's data directory
+///
+/// # Arguments
+/// * `data_dir` - The data directory of the run
+/// * `workload` - The recorded submissions, in submission order
+pub fn save_workload(data_dir: &str, workload: &[RecordedSubmission]) -> Result<String, String> {
+    let path = format!("{}/{}", data_dir, WORKLOAD_FILE);
+    let json = serde_json::to_string(workload).map_err(|e| e.to_string())?;
+    fs::write(&path, json).map_err(|e| e.to_string())?;
+    Ok(path)
+}
+
+/// Loads a recorded workload from a run directory
+///
+/// # Arguments
+/// * `run_dir` - The run directory (e.g. `simulator/results/sim_simple/data/sim_0/run_0`)
+///
+/// # Returns
+/// The recorded submissions, in submission order
+pub fn load_workload(run_dir: &str) -> Result<Vec<RecordedSubmission>, String> {
+    let path = format!("{}/data/{}", run_dir, WORKLOAD_FILE);
+    let json = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read recorded workload {}: {}", path, e))?;
+    serde_json::from_str(&json)
+        .map_err(|e| format!("Failed to parse recorded workload {}: {}", path, e))
+}
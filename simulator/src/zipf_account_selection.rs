@@ -16,6 +16,8 @@ pub struct AccountSelector {
     zipf: Zipf<f64>,
     zipf_parameter: f64,
     num_accounts: usize,
+    /// Number of accounts below the first selectable one
+    offset: usize,
 }
 
 // ------------------------------------------------------------------------------------------------
@@ -25,14 +27,22 @@ pub struct AccountSelector {
 impl AccountSelector {
     /// Creates a new account selector
     pub fn new(num_accounts: usize, zipf_parameter: f64) -> Self {
+        Self::in_range(1, num_accounts, zipf_parameter)
+    }
+
+    /// Creates an account selector over the accounts `first_account..first_account + num_accounts`
+    ///
+    /// The Zipf ranks start at `first_account`, so the lowest account of the range is the most popular.
+    pub fn in_range(first_account: usize, num_accounts: usize, zipf_parameter: f64) -> Self {
         logging::log("ACCOUNT_SELECTOR", &format!(
-            "Creating account selector with {} accounts and Zipf parameter {}",
-            num_accounts, zipf_parameter
+            "Creating account selector with {} accounts from account {} and Zipf parameter {}",
+            num_accounts, first_account, zipf_parameter
         ));
         Self {
             zipf: Zipf::new(num_accounts as u64, zipf_parameter).unwrap(),
             zipf_parameter,
             num_accounts,
+            offset: first_account - 1,
         }
     }
 
@@ -44,7 +54,7 @@ impl AccountSelector {
         } else {
             self.zipf.sample(rng) as usize
         };
-        selected
+        selected + self.offset
     }
 
     /// Returns the Zipf parameter used for account selection
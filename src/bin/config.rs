@@ -10,9 +10,21 @@ pub const CAT_MAX_LIFETIME_BLOCKS: u64 = 10;
 // Allow CATs to depend on pending transactions
 pub const ALLOW_CAT_PENDING_DEPENDENCIES: bool = false;
 
+// Number of chains (chain-1, chain-2, ...) created at startup unless `--chains` is given
+pub const DEFAULT_CHAIN_COUNT: usize = 3;
+
 // Channel buffer sizes for high-performance communication
 pub const CHANNEL_BUFFER_SIZE: usize = 1000;
 
+// Time `step` waits for the stepped block to be produced and processed by all HIGs
+pub const STEP_TIMEOUT: Duration = Duration::from_secs(10);
+
+// Control socket used by `--daemon` and hyperplane-ctl
+pub const DEFAULT_CONTROL_SOCKET: &str = "/tmp/hyperplane.sock";
+
+// Line that terminates the response to each command on the control socket
+pub const CONTROL_RESPONSE_END: &str = "<<END>>";
+
 // Dummy main function to satisfy Rust compiler for bin directory
 #[allow(dead_code)]
 fn main() {}
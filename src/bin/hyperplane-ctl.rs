@@ -0,0 +1,100 @@
+use tokio::io::{self, AsyncBufReadExt, AsyncWriteExt, BufReader, Lines};
+use tokio::net::UnixStream;
+use tokio::net::unix::{OwnedReadHalf, OwnedWriteHalf};
+use hyperplane::types::{ShellCommand, ShellCommandError};
+
+#[allow(dead_code)]
+mod config;
+
+// Client for a shell started with `--daemon`
+//
+// Usage:
+//   hyperplane-ctl [--socket <path>] <command...>   send a single command
+//   hyperplane-ctl [--socket <path>]                send one command per line from stdin
+//
+// Commands are checked with the shell's parser before they are sent, so a script with a typo
+// reports the line instead of sending it to the daemon.
+#[tokio::main]
+async fn main() {
+    let mut socket_path = config::DEFAULT_CONTROL_SOCKET.to_string();
+    let mut command_args = Vec::new();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--socket" {
+            match args.next() {
+                Some(path) => socket_path = path,
+                None => {
+                    eprintln!("Usage: hyperplane-ctl [--socket <path>] [command...]");
+                    std::process::exit(2);
+                }
+            }
+        } else {
+            command_args.push(arg);
+        }
+    }
+
+    let stream = match UnixStream::connect(&socket_path).await {
+        Ok(stream) => stream,
+        Err(e) => {
+            eprintln!("[ctl] Error: Failed to connect to {}: {}", socket_path, e);
+            std::process::exit(1);
+        }
+    };
+    let (reader, mut writer) = stream.into_split();
+    let mut responses = BufReader::new(reader).lines();
+
+    // Send the command given on the command line, or every line read from stdin
+    if !command_args.is_empty() {
+        // Arguments the calling shell kept together stay together
+        let args: Vec<String> = command_args.iter()
+            .map(|arg| if arg.contains(char::is_whitespace) { format!("\"{}\"", arg) } else { arg.clone() })
+            .collect();
+        match args.join(" ").parse::<ShellCommand>() {
+            Ok(command) => {
+                send_command(&command, &mut writer, &mut responses).await;
+            }
+            Err(e) => {
+                eprintln!("[ctl] Error: {}", e);
+                std::process::exit(2);
+            }
+        }
+        return;
+    }
+    let mut stdin_lines = BufReader::new(io::stdin()).lines();
+    let mut line_number = 0;
+    while let Ok(Some(line)) = stdin_lines.next_line().await {
+        line_number += 1;
+        let command = match line.parse::<ShellCommand>() {
+            Ok(command) => command,
+            Err(ShellCommandError::Empty) => continue,
+            Err(e) => {
+                eprintln!("[ctl] Error: line {}: {}", line_number, e);
+                continue;
+            }
+        };
+        if !send_command(&command, &mut writer, &mut responses).await {
+            break;
+        }
+    }
+}
+
+// Sends a command in its canonical form and prints the response until the end marker
+//
+// Returns false if the daemon closed the session.
+async fn send_command(command: &ShellCommand, writer: &mut OwnedWriteHalf, responses: &mut Lines<BufReader<OwnedReadHalf>>) -> bool {
+    if let Err(e) = writer.write_all(format!("{}\n", command).as_bytes()).await {
+        eprintln!("[ctl] Error: Failed to send command: {}", e);
+        std::process::exit(1);
+    }
+    loop {
+        match responses.next_line().await {
+            Ok(Some(line)) if line == config::CONTROL_RESPONSE_END => return true,
+            Ok(Some(line)) => println!("{}", line),
+            Ok(None) => return false,
+            Err(e) => {
+                eprintln!("[ctl] Error: Failed to read response: {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+}
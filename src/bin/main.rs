@@ -1,16 +1,18 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write as _;
 use std::sync::Arc;
 use tokio::sync::{mpsc, Mutex};
 use tokio::time::Duration;
-use tokio::io::{self, AsyncBufReadExt, BufReader};
+use tokio::io::{self, AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, UnixListener};
 use std::io::Write;
 use hyperplane::{
-    types::{ChainId, TransactionId, Transaction, CLTransaction, CATStatusUpdate, SubBlock, TransactionStatus, CLTransactionId},
-    confirmation_layer::{ConfirmationLayerNode, ConfirmationLayer, ConfirmationLayerError},
-    hyper_scheduler::node::HyperSchedulerNode,
+    types::{ChainId, TransactionId, Transaction, CLTransaction, CatBuilder, TransactionStatus, CLTransactionId, CATStatus, CATStatusLimited, SystemClock, NetworkSpec, ShellCommand, ShellCommandError},
+    confirmation_layer::{ConfirmationLayerNode, ConfirmationLayer, ConfirmationLayerError, remote},
+    hyper_scheduler::{node::HyperSchedulerNode, HyperScheduler},
     hyper_ig::node::HyperIGNode,
-    hyper_ig::HyperIG,
-    types::constants::{chain_1, chain_2, chain_3},
+    hyper_ig::{HyperIG, CatAuditEvent},
+    harness::{self, ChainOptions, ComponentRuntimes, DirectLinks},
 };
 
 mod config;
@@ -32,149 +34,250 @@ impl TransactionTracker {
     }
 }
 
-#[tokio::main]
-async fn main() {
-    // Initialize logging
-    hyperplane::utils::logging::init_logging();
-
-    println!("=== Hyperplane Shell ===");
-    println!("Type 'help' for commands.");
-    print!("> ");
-    std::io::stdout().flush().unwrap();
+// What to do after a command has been handled
+enum CommandOutcome {
+    // Keep reading commands
+    Continue,
+    // Close the current session (`exit` / `quit`)
+    Exit,
+    // Stop the daemon (`shutdown`); behaves like `exit` in the interactive shell
+    Shutdown,
+}
 
-    // Set up channel for HS <-> CL
-    let (sender_hs_to_cl, receiver_hs_to_cl) = mpsc::channel::<CLTransaction>(1000);
+// The nodes of the local network, shared by the interactive shell and all control socket sessions
+struct Shell {
+    cl_node: Arc<Mutex<ConfirmationLayerNode>>,
+    hs_node: Arc<Mutex<HyperSchedulerNode>>,
+    hig_nodes: Arc<Mutex<HashMap<ChainId, Arc<Mutex<HyperIGNode>>>>>,
+    transaction_tracker: Arc<Mutex<TransactionTracker>>,
+}
 
-    // Initialize nodes
-    let cl_node = Arc::new(Mutex::new(ConfirmationLayerNode::new_with_block_interval(receiver_hs_to_cl, config::BLOCK_TIME).unwrap()));
-    let hs_node = Arc::new(Mutex::new(HyperSchedulerNode::new(sender_hs_to_cl)));
+impl Shell {
+    // Handles a single command line and writes its output to `out`
+    async fn handle_command(&self, input: &str, out: &mut String) -> CommandOutcome {
+        let cl_node = &self.cl_node;
+        let hs_node = &self.hs_node;
+        let hig_nodes = &self.hig_nodes;
+        let transaction_tracker = &self.transaction_tracker;
 
-    // Store HIG nodes by chain_id
-    let hig_nodes: Arc<Mutex<HashMap<ChainId, Arc<Mutex<HyperIGNode>>>>> = Arc::new(Mutex::new(HashMap::new()));
-    
-    // Initialize transaction tracker
-    let transaction_tracker = Arc::new(Mutex::new(TransactionTracker::new()));
+        let command = match input.parse::<ShellCommand>() {
+            Ok(command) => command,
+            Err(ShellCommandError::Empty) => return CommandOutcome::Continue,
+            Err(e) => {
+                writeln!(out, "[shell] Error: {}", e).unwrap();
+                return CommandOutcome::Continue;
+            }
+        };
+        match command {
+            ShellCommand::Exit => {
+                writeln!(out, "Exiting shell.").unwrap();
+                return CommandOutcome::Exit;
+            }
+            ShellCommand::Shutdown => {
+                writeln!(out, "Shutting down.").unwrap();
+                return CommandOutcome::Shutdown;
+            }
+            ShellCommand::Help => {
+                writeln!(out, "Commands:").unwrap();
+                writeln!(out, "  add-chain <chain_id>").unwrap();
+                writeln!(out, "  send-tx <chain_id> <data>").unwrap();
+                writeln!(out, "  send-cat <chain_id1,chain_id2,...> <data> (or one command per chain: <data1>;<data2>;...)").unwrap();
+                writeln!(out, "  preview <chain_id> <data> (dry-run a transaction on a chain without changing state)").unwrap();
+                writeln!(out, "  balance <chain_id> <account> [--proof] (account balance, optionally with inclusion proof)").unwrap();
+                writeln!(out, "  blocked <chain_id> (blocked transactions with the transactions and keys they wait on)").unwrap();
+                writeln!(out, "  explain-cat <cat_id> (timeline of how a CAT was resolved: inclusion, proposals, HS decision, execution or timeout)").unwrap();
+                writeln!(out, "  dump-state <chain_id> <file> (write all account balances of a chain to a JSON file)").unwrap();
+                writeln!(out, "  load-balances <chain_id> <file> (preload account balances from a JSON file, e.g. one written by dump-state)").unwrap();
+                writeln!(out, "  set-delay <chain_id> <milliseconds>").unwrap();
+                writeln!(out, "  set-block-interval <milliseconds>").unwrap();
+                writeln!(out, "  pause (stop CL block production, submissions are still accepted)").unwrap();
+                writeln!(out, "  resume (resume CL block production)").unwrap();
+                writeln!(out, "  step (while paused: produce one CL block, wait for all HIGs to process it and show what changed)").unwrap();
+                writeln!(out, "  new-epoch (start a new HS epoch so a new run can reuse CAT IDs; late proposals of the earlier run still reach their own CATs)").unwrap();
+                writeln!(out, "  shutdown (daemon mode: stops the daemon)").unwrap();
+                writeln!(out, "  status").unwrap();
+                writeln!(out, "  health (running loops, queue depths, block heights and channel liveness of all nodes, to diagnose hangs)").unwrap();
+                writeln!(out, "  exit").unwrap();
+                writeln!(out, "\nArguments are separated by spaces; quote an argument with \" or ' if it contains spaces.").unwrap();
+                writeln!(out, "Transaction data is the rest of the line. Lines starting with # are ignored.").unwrap();
+                writeln!(out, "\nValid transaction data formats:").unwrap();
+                writeln!(out, "  Regular: credit <account> <amount>").unwrap();
+                writeln!(out, "  Regular: debit <account> <amount>").unwrap();
+                writeln!(out, "  Regular: send <from> <to> <amount>").unwrap();
+                writeln!(out, "  CAT: CAT.send <from> <to> <amount>").unwrap();
+                writeln!(out, "  CAT: CAT.credit <account> <amount>").unwrap();
+                writeln!(out, "  CAT: CAT.debit <account> <amount>").unwrap();
+                writeln!(out, "  CAT: transfer <from> <to> <amount> (debit on the first chain, credit on the second)").unwrap();
+                writeln!(out, "\nExamples:").unwrap();
+                writeln!(out, "  send-tx chain-1 credit 1 100").unwrap();
+                writeln!(out, "  send-tx chain-1 send 1 2 50").unwrap();
+                writeln!(out, "  send-cat chain-1,chain-2 CAT.send 1 2 50").unwrap();
+                writeln!(out, "  send-cat chain-1,chain-2 CAT.credit 1 100").unwrap();
+                writeln!(out, "  send-cat chain-1,chain-2 CAT.debit 1 50;CAT.credit 2 50").unwrap();
+                writeln!(out, "  send-cat chain-1,chain-2 transfer 1 2 50").unwrap();
+                writeln!(out, "  preview chain-1 send 1 2 50").unwrap();
+                writeln!(out, "  balance chain-1 1 --proof").unwrap();
+                writeln!(out, "  blocked chain-1").unwrap();
+                writeln!(out, "  explain-cat cl-tx_cat_1700000000000").unwrap();
+                writeln!(out, "  dump-state chain-1 state.json").unwrap();
+                writeln!(out, "  load-balances chain-2 state.json").unwrap();
+                writeln!(out, "  set-delay chain-1 200").unwrap();
+                writeln!(out, "  set-block-interval 500").unwrap();
+                writeln!(out, "  pause; send-tx chain-1 credit 1 100; step").unwrap();
+                writeln!(out, "\n⚠️  CONFIGURATION NOTE:").unwrap();
+                writeln!(out, "  Some settings (like CAT lifetime, allow_cat_pending_dependencies)").unwrap();
+                writeln!(out, "  must be changed in src/bin/config.rs and require restarting the shell.").unwrap();
+                writeln!(out, "  Check the config file for available options.").unwrap();
+                writeln!(out, " ").unwrap();
+            }
+            ShellCommand::SetDelay { chain_id, milliseconds: ms } => {
+                let hig_nodes_guard = hig_nodes.lock().await;
+                if let Some(node) = hig_nodes_guard.get(&chain_id) {
+                    node.lock().await.set_hs_message_delay(Duration::from_millis(ms));
+                    writeln!(out, "[shell] Set message delay for chain {} to {}ms", chain_id.0, ms).unwrap();
+                } else {
+                    writeln!(out, "[shell] Error: Chain {} not found", chain_id.0).unwrap();
+                }
+            }
+            ShellCommand::SetBlockInterval { milliseconds: ms } => {
+                let mut cl_node_guard = cl_node.lock().await;
+                if let Err(e) = cl_node_guard.set_block_interval(Duration::from_millis(ms)).await {
+                    writeln!(out, "[shell] Error: Failed to set block interval: {}", e).unwrap();
+                } else {
+                    writeln!(out, "[shell] Set CL block interval to {}ms", ms).unwrap();
+                }
+            }
+            ShellCommand::Pause => {
+                match cl_node.lock().await.pause().await {
+                    Ok(()) => writeln!(out, "[shell] CL block production paused").unwrap(),
+                    Err(e) => writeln!(out, "[shell] Error: Failed to pause CL: {}", e).unwrap(),
+                }
+            }
+            ShellCommand::Resume => {
+                match cl_node.lock().await.resume().await {
+                    Ok(()) => writeln!(out, "[shell] CL block production resumed").unwrap(),
+                    Err(e) => writeln!(out, "[shell] Error: Failed to resume CL: {}", e).unwrap(),
+                }
+            }
+            ShellCommand::Step => {
+                // Snapshot the state before the block to report what it changed
+                let chains: Vec<(ChainId, Arc<Mutex<HyperIGNode>>)> = {
+                    let mut chains: Vec<_> = hig_nodes.lock().await.iter()
+                        .map(|(id, node)| (id.clone(), node.clone()))
+                        .collect();
+                    chains.sort_by(|a, b| a.0.0.cmp(&b.0.0));
+                    chains
+                };
+                let mut states_before = HashMap::new();
+                for (chain_id, node) in &chains {
+                    states_before.insert(chain_id.clone(), node.lock().await.get_chain_state().await.unwrap_or_default());
+                }
 
-    // Start the nodes
-    ConfirmationLayerNode::start(cl_node.clone()).await;
-    HyperSchedulerNode::start(hs_node.clone()).await;
-
-    // Create 3 default chains
-    println!("[shell] Creating 3 default chains...");
-    let default_chains = [chain_1(), chain_2(), chain_3()];
-    for chain_id in default_chains {
-        println!("[shell] Adding chain: {}", chain_id.0);
-        // Channels for CL <-> HIG
-        let (sender_cl_to_hig, receiver_cl_to_hig) = mpsc::channel::<SubBlock>(config::CHANNEL_BUFFER_SIZE);
-        // Channels for HIG <-> HS
-        let (sender_hig_to_hs, receiver_hig_to_hs) = mpsc::channel::<CATStatusUpdate>(config::CHANNEL_BUFFER_SIZE);
-        // Create HIG node
-        let hig_node = Arc::new(Mutex::new(HyperIGNode::new(receiver_cl_to_hig, sender_hig_to_hs, chain_id.clone(), config::CAT_MAX_LIFETIME_BLOCKS, config::ALLOW_CAT_PENDING_DEPENDENCIES)));
-        // Register chain with CL
-        let mut cl_node_guard = cl_node.lock().await;
-        cl_node_guard.register_chain(chain_id.clone(), sender_cl_to_hig).await.expect("Failed to register chain with CL");
-        drop(cl_node_guard);
-        // Register chain with HS
-        let mut hs_node_guard = hs_node.lock().await;
-        hs_node_guard.register_chain(chain_id.clone(), receiver_hig_to_hs).await.expect("Failed to register chain with HS");
-        drop(hs_node_guard);
-        // Store HIG node
-        hig_nodes.lock().await.insert(chain_id.clone(), hig_node.clone());
-        // Start HIG node
-        HyperIGNode::start(hig_node).await;
-        println!("[shell] Chain {} registered successfully.", chain_id.0);
-    }
+                let block_height = match cl_node.lock().await.step().await {
+                    Ok(block_height) => block_height,
+                    Err(ConfirmationLayerError::NotPaused) => {
+                        writeln!(out, "[shell] Error: Block production is running, use 'pause' before stepping").unwrap();
+                        return CommandOutcome::Continue;
+                    }
+                    Err(e) => {
+                        writeln!(out, "[shell] Error: Failed to step: {}", e).unwrap();
+                        return CommandOutcome::Continue;
+                    }
+                };
 
-    // Start REPL
-    let stdin = BufReader::new(io::stdin());
-    let mut lines = stdin.lines();
-    while let Ok(Some(line)) = lines.next_line().await {
-        let input = line.trim();
-        if input == "exit" || input == "quit" {
-            println!("Exiting shell.");
-            break;
-        }
-        if input == "help" {
-            println!("Commands:");
-            println!("  add-chain <chain_id>");
-            println!("  send-tx <chain_id> <data>");
-            println!("  send-cat <chain_id1,chain_id2,...> <data>");
-            println!("  set-delay <chain_id> <milliseconds>");
-            println!("  set-block-interval <milliseconds>");
-            println!("  status");
-            println!("  exit");
-            println!("\nValid transaction data formats:");
-            println!("  Regular: credit <account> <amount>");
-            println!("  Regular: send <from> <to> <amount>");
-            println!("  CAT: CAT.send <from> <to> <amount>");
-            println!("  CAT: CAT.credit <account> <amount>");
-            println!("\nExamples:");
-            println!("  send-tx chain-1 credit 1 100");
-            println!("  send-tx chain-1 send 1 2 50");
-            println!("  send-cat chain-1,chain-2 CAT.send 1 2 50");
-            println!("  send-cat chain-1,chain-2 CAT.credit 1 100");
-            println!("  set-delay chain-1 200");
-            println!("  set-block-interval 500");
-            println!("\n⚠️  CONFIGURATION NOTE:");
-            println!("  Some settings (like CAT lifetime, allow_cat_pending_dependencies)");
-            println!("  must be changed in src/bin/config.rs and require restarting the shell.");
-            println!("  Check the config file for available options.");
-            println!(" ");
-            continue;
-        }
-        let mut parts = input.split_whitespace();
-        match parts.next() {
-            Some("set-delay") => {
-                if let (Some(chain_id_str), Some(ms_str)) = (parts.next(), parts.next()) {
-                    if let Ok(ms) = ms_str.parse::<u64>() {
-                        let chain_id = ChainId(chain_id_str.to_string());
-                        let hig_nodes_guard = hig_nodes.lock().await;
-                        if let Some(node) = hig_nodes_guard.get(&chain_id) {
-                            node.lock().await.set_hs_message_delay(Duration::from_millis(ms));
-                            println!("[shell] Set message delay for chain {} to {}ms", chain_id.0, ms);
-                        } else {
-                            println!("[shell] Error: Chain {} not found", chain_id.0);
+                // Wait until the block is produced and every HIG has finished processing its subblock
+                let deadline = tokio::time::Instant::now() + config::STEP_TIMEOUT;
+                let mut waiting_for: Vec<&(ChainId, Arc<Mutex<HyperIGNode>>)> = chains.iter().collect();
+                while !waiting_for.is_empty() && tokio::time::Instant::now() < deadline {
+                    let mut still_waiting = Vec::new();
+                    for chain in waiting_for {
+                        if !matches!(chain.1.lock().await.get_subblock_timing(block_height).await, Ok(Some(_))) {
+                            still_waiting.push(chain);
                         }
-                    } else {
-                        println!("[shell] Error: Invalid milliseconds value");
                     }
-                } else {
-                    println!("Usage: set-delay <chain_id> <milliseconds>");
+                    waiting_for = still_waiting;
+                    if !waiting_for.is_empty() {
+                        tokio::time::sleep(Duration::from_millis(10)).await;
+                    }
                 }
-            }
-            Some("set-block-interval") => {
-                if let Some(ms_str) = parts.next() {
-                    if let Ok(ms) = ms_str.parse::<u64>() {
-                        let mut cl_node_guard = cl_node.lock().await;
-                        if let Err(e) = cl_node_guard.set_block_interval(Duration::from_millis(ms)).await {
-                            println!("[shell] Error: Failed to set block interval: {}", e);
-                        } else {
-                            println!("[shell] Set CL block interval to {}ms", ms);
-                        }
-                    } else {
-                        println!("[shell] Error: Invalid milliseconds value");
+                if !waiting_for.is_empty() {
+                    let names: Vec<&str> = waiting_for.iter().map(|(chain_id, _)| chain_id.0.as_str()).collect();
+                    writeln!(out, "[shell] Error: Timed out waiting for block {} to be processed by {}", block_height, names.join(", ")).unwrap();
+                    return CommandOutcome::Continue;
+                }
+
+                let pending = cl_node.lock().await.get_pending_transactions().await.unwrap_or(0);
+                writeln!(out, "[shell] Produced block {} ({} CL transactions still pending)", block_height, pending).unwrap();
+                for (chain_id, node) in &chains {
+                    let included = cl_node.lock().await.get_subblock(chain_id.clone(), block_height).await
+                        .map(|subblock| subblock.transactions.len())
+                        .unwrap_or(0);
+                    let node = node.lock().await;
+                    let mut transitions: Vec<_> = node.get_status_transitions(block_height).await.unwrap_or_default().into_iter().collect();
+                    transitions.sort_by(|a, b| a.0.0.cmp(&b.0.0));
+                    let state_after = node.get_chain_state().await.unwrap_or_default();
+                    drop(node);
+
+                    // Accounts whose balance changed, sorted numerically
+                    let state_before = states_before.remove(chain_id).unwrap_or_default();
+                    let mut balance_changes: Vec<(u32, i64, i64)> = state_after.iter()
+                        .filter_map(|(account, &after)| {
+                            let before = state_before.get(account).copied().unwrap_or(0);
+                            if before == after {
+                                return None;
+                            }
+                            Some((account.parse::<u32>().ok()?, before, after))
+                        })
+                        .collect();
+                    balance_changes.sort();
+
+                    writeln!(out, "  {}: {} transactions included, {} status changes, {} balance changes",
+                        chain_id.0, included, transitions.len(), balance_changes.len()).unwrap();
+                    for (tx_id, status) in transitions {
+                        writeln!(out, "    {} -> {:?}", tx_id.0, status).unwrap();
+                    }
+                    for (account, before, after) in balance_changes {
+                        writeln!(out, "    account {}: {} -> {}", account, before, after).unwrap();
                     }
-                } else {
-                    println!("Usage: set-block-interval <milliseconds>");
                 }
             }
-            Some("status") => {
+            ShellCommand::NewEpoch => {
+                let epoch = hs_node.lock().await.advance_epoch().await;
+                // The HIGs tag the proposals for the CATs they receive from now on with the new epoch
+                for hig_node in hig_nodes.lock().await.values() {
+                    hig_node.lock().await.set_epoch(epoch).await;
+                }
+                writeln!(out, "[shell] HS is now in epoch {}", epoch).unwrap();
+            }
+            ShellCommand::Health => {
+                let chains: Vec<(ChainId, Arc<Mutex<HyperIGNode>>)> = {
+                    let mut chains: Vec<_> = hig_nodes.lock().await.iter()
+                        .map(|(id, node)| (id.clone(), node.clone()))
+                        .collect();
+                    chains.sort_by(|a, b| a.0.0.cmp(&b.0.0));
+                    chains
+                };
+                let health = harness::NetworkHealth::collect(cl_node, hs_node, &chains).await;
+                writeln!(out, "=== Node Health ===").unwrap();
+                writeln!(out, "{}", health).unwrap();
+            }
+            ShellCommand::Status => {
                 let chains = hig_nodes.lock().await;
                 let transactions = transaction_tracker.lock().await;
-                println!("=== System Status ===");
+                writeln!(out, "=== System Status ===").unwrap();
                 let mut chain_list: Vec<String> = chains.keys().map(|c| c.0.clone()).collect();
                 chain_list.sort();  // Sort chains alphabetically
-                println!("Registered chains: {}", chain_list.join(", "));
+                writeln!(out, "Registered chains: {}", chain_list.join(", ")).unwrap();
                 
                 // Display configuration information
-                println!("\nConfiguration:");
+                writeln!(out, "\nConfiguration:").unwrap();
                 
                 // Get block time from CL node
                 let block_time_ms = match cl_node.lock().await.get_block_interval().await {
                     Ok(interval) => interval.as_millis() as u64,
                     Err(_) => config::BLOCK_TIME_MILLISECONDS, // fallback to config
                 };
-                println!("  Block Time: {}ms", block_time_ms);
+                writeln!(out, "  Block Time: {}ms", block_time_ms).unwrap();
                 
                 // Get CAT timeout from one of the HIG nodes
                 let cat_timeout_blocks = if let Some((_, node)) = chains.iter().next() {
@@ -185,15 +288,17 @@ async fn main() {
                 } else {
                     config::CAT_MAX_LIFETIME_BLOCKS // fallback to config
                 };
-                println!("  CAT Max Lifetime: {} blocks ({}ms)", cat_timeout_blocks, cat_timeout_blocks * block_time_ms);
+                writeln!(out, "  CAT Max Lifetime: {} blocks ({}ms)", cat_timeout_blocks, cat_timeout_blocks * block_time_ms).unwrap();
                 
                 // Get CL block time and interval
                 let cl_block = cl_node.lock().await.get_current_block().await.unwrap();
                 let cl_interval = cl_node.lock().await.get_block_interval().await.unwrap();
-                println!("\nCL Block Height: {} (Interval: {}ms)", cl_block, cl_interval.as_millis());
+                let cl_paused = cl_node.lock().await.is_paused().await.unwrap_or(false);
+                writeln!(out, "\nCL Block Height: {} (Interval: {}ms){}", cl_block, cl_interval.as_millis(), if cl_paused { " [PAUSED]" } else { "" }).unwrap();
+                writeln!(out, "HS Epoch: {}", hs_node.lock().await.get_epoch().await).unwrap();
                 
                 // Show state for each chain
-                println!("\nChain States:");
+                writeln!(out, "\nChain States:").unwrap();
                 let mut chain_states: Vec<_> = chains.iter().collect();
                 chain_states.sort_by(|a, b| a.0.0.cmp(&b.0.0));  // Sort by chain ID
                 for (chain_id, node) in chain_states {
@@ -206,10 +311,10 @@ async fn main() {
                         .map(|(k, v)| format!("\"{}\": {}", k, v))
                         .collect::<Vec<_>>()
                         .join(", "));
-                    println!("  {}: {} (delay: {}ms)", chain_id.0, state_str, delay);
+                    writeln!(out, "  {}: {} (delay: {}ms)", chain_id.0, state_str, delay).unwrap();
                 }
                 
-                println!("\nTransaction Status:");
+                writeln!(out, "\nTransaction Status:").unwrap();
                 
                 // Collect all chain nodes and transaction IDs first
                 let chain_nodes: Vec<(ChainId, Arc<Mutex<HyperIGNode>>)> = chains.iter()
@@ -234,169 +339,512 @@ async fn main() {
                     } else {
                         &tx_id.0
                     };
-                    println!("  - {}:", cl_id);
+                    writeln!(out, "  - {}:", cl_id).unwrap();
                     // Process each chain
                     for (chain_id, node) in &chain_nodes {
                         let node = node.lock().await;
                         if let Ok(status) = node.get_resolution_status(tx_id.clone()).await {
                             if let Ok(data) = node.get_transaction_data(tx_id.clone()).await {
-                                println!("    {}: {:?} : {}", chain_id.0, status, data);
+                                writeln!(out, "    {}: {:?} : {}", chain_id.0, status, data).unwrap();
                             }
                         }
                     }
                 }
-                println!("===================");
-                println!(" ");
+                writeln!(out, "===================").unwrap();
+                writeln!(out, " ").unwrap();
             }
-            Some("add-chain") => {
-                if let Some(chain_id_str) = parts.next() {
-                    let chain_id = ChainId(chain_id_str.to_string());
-                    println!("[shell] Adding chain: {}", chain_id.0);
-                    // Channels for CL <-> HIG
-                    let (sender_cl_to_hig, receiver_cl_to_hig) = mpsc::channel::<SubBlock>(config::CHANNEL_BUFFER_SIZE);
-                    // Channels for HIG <-> HS
-                    let (sender_hig_to_hs, receiver_hig_to_hs) = mpsc::channel::<CATStatusUpdate>(config::CHANNEL_BUFFER_SIZE);
-                    // Create HIG node
-                    let hig_node = Arc::new(Mutex::new(HyperIGNode::new(receiver_cl_to_hig, sender_hig_to_hs, chain_id.clone(), config::CAT_MAX_LIFETIME_BLOCKS, config::ALLOW_CAT_PENDING_DEPENDENCIES)));
-                    // Register chain with CL
-                    let mut cl_node_guard = cl_node.lock().await;
-                    cl_node_guard.register_chain(chain_id.clone(), sender_cl_to_hig).await.expect("Failed to register chain with CL");
-                    drop(cl_node_guard);
-                    // Register chain with HS
-                    let mut hs_node_guard = hs_node.lock().await;
-                    hs_node_guard.register_chain(chain_id.clone(), receiver_hig_to_hs).await.expect("Failed to register chain with HS");
-                    drop(hs_node_guard);
-                    // Store HIG node
-                    hig_nodes.lock().await.insert(chain_id.clone(), hig_node.clone());
-                    // Start HIG node
-                    HyperIGNode::start(hig_node).await;
-                    println!("[shell] Chain {} registered successfully.", chain_id.0);
-                } else {
-                    println!("Usage: add-chain <chain_id>");
+            ShellCommand::AddChain { chain_id } => {
+                writeln!(out, "[shell] Adding chain: {}", chain_id.0).unwrap();
+                // Create the HIG node, register the chain with CL and HS and start the HIG
+                let chain_index = hig_nodes.lock().await.len();
+                let options = ChainOptions {
+                    cat_lifetime: config::CAT_MAX_LIFETIME_BLOCKS,
+                    allow_cat_pending_dependencies: config::ALLOW_CAT_PENDING_DEPENDENCIES,
+                    account_balances: Vec::new(),
+                    channel_buffer_size: config::CHANNEL_BUFFER_SIZE,
+                };
+                match harness::connect_chain(cl_node, hs_node, chain_id.clone(), chain_index, &options, &DirectLinks, &ComponentRuntimes::default()).await {
+                    Ok(hig_node) => {
+                        hig_node.lock().await.set_epoch(hs_node.lock().await.get_epoch().await).await;
+                        hig_nodes.lock().await.insert(chain_id.clone(), hig_node);
+                        writeln!(out, "[shell] Chain {} registered successfully.", chain_id.0).unwrap();
+                    }
+                    Err(e) => writeln!(out, "[shell] Error: Failed to add chain {}: {}", chain_id.0, e).unwrap(),
                 }
             }
-            Some("send-tx") => {
-                if let (Some(chain_id), Some(_data)) = (parts.next(), parts.next()) {
-                    // Get the rest of the input as the full data
-                    let data = input.split_once("send-tx").unwrap().1
-                        .split_once(chain_id).unwrap().1
-                        .trim_start();
-                    let data = data.trim_matches('"');  // Remove quotes if present
-                    // Generate unique transaction ID with timestamp
-                    let timestamp = std::time::SystemTime::now()
-                        .duration_since(std::time::UNIX_EPOCH)
-                        .unwrap()
-                        .as_millis();
-                    let cl_id = CLTransactionId(format!("cl-tx_{}", timestamp));
-                    let tx_id = TransactionId(format!("{}:tx", cl_id.0));
-                    println!("[shell] Sending tx to {}: {}", chain_id, data);
-                    match Transaction::new(
-                        tx_id.clone(),
-                        ChainId(chain_id.to_string()),
-                        vec![ChainId(chain_id.to_string())],
-                        format!("REGULAR.{}", data),  // Add REGULAR. prefix for regular transactions
-                        cl_id.clone(),
-                    ) {
-                        Ok(tx) => {
-                            match CLTransaction::new(
-                                cl_id.clone(),
-                                vec![ChainId(chain_id.to_string())],
-                                vec![tx],
-                            ) {
-                                Ok(cl_tx) => {
-                                    let mut cl_node_guard = cl_node.lock().await;
-                                    if let Err(e) = cl_node_guard.submit_transaction(cl_tx).await {
-                                        match e {
-                                            ConfirmationLayerError::TransactionAlreadyProcessed(id) => {
-                                                println!("[shell] Error: Transaction rejected - transaction {} has already been processed", id);
-                                            }
-                                            _ => {
-                                                println!("[shell] Error: Failed to submit transaction: {}", e);
-                                            }
+            ShellCommand::SendTx { chain_id, data } => {
+                // Generate unique transaction ID with timestamp
+                let timestamp = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap()
+                    .as_millis();
+                let cl_id = CLTransactionId(format!("cl-tx_{}", timestamp));
+                let tx_id = TransactionId(format!("{}:tx", cl_id.0));
+                writeln!(out, "[shell] Sending tx to {}: {}", chain_id.0, data).unwrap();
+                match Transaction::new(
+                    tx_id.clone(),
+                    chain_id.clone(),
+                    vec![chain_id.clone()],
+                    format!("REGULAR.{}", data),  // Add REGULAR. prefix for regular transactions
+                    cl_id.clone(),
+                ) {
+                    Ok(tx) => {
+                        match CLTransaction::new(
+                            cl_id.clone(),
+                            vec![chain_id],
+                            vec![tx],
+                        ) {
+                            Ok(cl_tx) => {
+                                let mut cl_node_guard = cl_node.lock().await;
+                                if let Err(e) = cl_node_guard.submit_transaction(cl_tx).await {
+                                    match e {
+                                        ConfirmationLayerError::TransactionAlreadyProcessed(id) => {
+                                            writeln!(out, "[shell] Error: Transaction rejected - transaction {} has already been processed", id).unwrap();
+                                        }
+                                        _ => {
+                                            writeln!(out, "[shell] Error: Failed to submit transaction: {}", e).unwrap();
                                         }
-                                    } else {
-                                        transaction_tracker.lock().await.add_transaction(tx_id.clone());
-                                        println!("[shell] Transaction sent successfully. ID: {}", tx_id.0);
                                     }
+                                } else {
+                                    transaction_tracker.lock().await.add_transaction(tx_id.clone());
+                                    writeln!(out, "[shell] Transaction sent successfully. ID: {}", tx_id.0).unwrap();
                                 }
-                                Err(e) => println!("[shell] Error: Failed to create CL transaction: {}", e),
                             }
+                            Err(e) => writeln!(out, "[shell] Error: Failed to create CL transaction: {}", e).unwrap(),
+                        }
+                    }
+                    Err(e) => writeln!(out, "[shell] Error: Failed to create transaction: {}", e).unwrap(),
+                }
+            }
+            ShellCommand::SendCat { chain_ids, data } => {
+                // Generate unique CAT ID with timestamp
+                let timestamp = std::time::SystemTime::now()
+                        .duration_since(std::time::UNIX_EPOCH)
+                        .unwrap()
+                        .as_millis();
+                let cl_id = CLTransactionId(format!("cl-tx_cat_{}", timestamp));
+                let chains: Vec<&str> = chain_ids.iter().map(|chain_id| chain_id.0.as_str()).collect();
+                writeln!(out, "[shell] Sending CAT to [{}]: {}", chains.join(","), data).unwrap();
+
+                let cl_tx = if let Some(args) = data.strip_prefix("transfer ") {
+                    // A transfer from the first to the second chain
+                    let amounts: Vec<u32> = args.split_whitespace().map(str::parse).collect::<Result<_, _>>().unwrap_or_default();
+                    match (chain_ids.as_slice(), amounts.as_slice()) {
+                        ([from_chain, to_chain], [from_acct, to_acct, amount]) => {
+                            CatBuilder::transfer(from_chain.clone(), to_chain.clone(), *from_acct, *to_acct, *amount).build(cl_id.clone())
+                        }
+                        _ => {
+                            writeln!(out, "Usage: send-cat <from_chain>,<to_chain> transfer <from> <to> <amount>").unwrap();
+                            return CommandOutcome::Continue;
                         }
-                        Err(e) => println!("[shell] Error: Failed to create transaction: {}", e),
                     }
                 } else {
-                    println!("Usage: send-tx <chain_id> <data>");
+                    // Either one command for all chains or one command per chain, separated by ';'
+                    let commands: Vec<&str> = data.split(';').map(str::trim).collect();
+                    let builder = CLTransaction::builder(cl_id.clone(), chain_ids.clone());
+                    let builder = if commands.len() == 1 {
+                        builder.command_for_all(commands[0])
+                    } else if commands.len() == chain_ids.len() {
+                        chain_ids.iter().zip(&commands).fold(builder, |builder, (chain_id, command)| builder.command(chain_id.clone(), *command))
+                    } else {
+                        writeln!(out, "[shell] Error: Expected 1 or {} commands, got {}", chain_ids.len(), commands.len()).unwrap();
+                        return CommandOutcome::Continue;
+                    };
+                    builder.build()
+                };
+
+                match cl_tx {
+                    Ok(cl_tx) => {
+                        let mut cl_node_guard = cl_node.lock().await;
+                        if let Err(e) = cl_node_guard.submit_transaction(cl_tx).await {
+                            writeln!(out, "[shell] Error: Failed to submit CAT transaction: {}", e).unwrap();
+                        } else {
+                            let tx_id = TransactionId(format!("{}:tx", cl_id.0));
+                            transaction_tracker.lock().await.add_transaction(tx_id);
+                            writeln!(out, "[shell] CAT transaction sent successfully. CL-ID: '{}'", cl_id.0).unwrap();
+                        }
+                    }
+                    Err(e) => writeln!(out, "[shell] Error: Failed to create CL transaction: {}", e).unwrap(),
                 }
             }
-            Some("send-cat") => {
-                if let (Some(chains), Some(_data)) = (parts.next(), parts.next()) {
-                    // Get the rest of the input as the full data
-                    let data = input.split_once("send-cat").unwrap().1
-                        .split_once(chains).unwrap().1
-                        .trim_start();
-                    let data = data.trim_matches('"');  // Remove quotes if present
-                    
-                    // Generate unique CAT ID with timestamp
-                    let timestamp = std::time::SystemTime::now()
-                            .duration_since(std::time::UNIX_EPOCH)
-                            .unwrap()
-                            .as_millis();
-                    let cl_id = CLTransactionId(format!("cl-tx_cat_{}", timestamp));
-                    // construct the transaction id
-                    let tx_id = TransactionId(format!("{}:tx", cl_id.0));
-                    println!("[shell] Sending CAT to [{}]: {}", chains, data);
-                    let chain_ids: Vec<ChainId> = chains.split(',').map(|c| ChainId(c.to_string())).collect();
-                    
-                    // Create a transaction for each chain
-                    let mut transactions = Vec::new();
-                    for chain_id in &chain_ids {
+            ShellCommand::Preview { chain_id, data } => {
+                // CATs are previewed as given, regular transactions get the REGULAR. prefix like in send-tx
+                let data = if data.starts_with("CAT.") { data } else { format!("REGULAR.{}", data) };
+                let node = hig_nodes.lock().await.get(&chain_id).cloned();
+                match node {
+                    Some(node) => {
+                        let cl_id = CLTransactionId("cl-tx_preview".to_string());
                         match Transaction::new(
-                            tx_id.clone(),
+                            TransactionId(format!("{}:tx", cl_id.0)),
                             chain_id.clone(),
-                            chain_ids.clone(),
-                            data.to_string(),  // Use the data as is, without adding REGULAR. prefix
-                            cl_id.clone(),
+                            vec![chain_id.clone()],
+                            data.clone(),
+                            cl_id,
                         ) {
-                            Ok(tx) => transactions.push(tx),
-                            Err(e) => {
-                                println!("[shell] Error: Failed to create transaction for chain {}: {}", chain_id.0, e);
-                                continue;
-                            }
+                            Ok(tx) => match node.lock().await.simulate_transaction(tx).await {
+                                Ok(preview) => {
+                                    writeln!(out, "[shell] Preview of '{}' on {}:", data, chain_id.0).unwrap();
+                                    writeln!(out, "  Keys: {:?}", preview.keys).unwrap();
+                                    if let Some(blocking_tx_id) = &preview.blocked_by {
+                                        writeln!(out, "  Blocked by: {}", blocking_tx_id.0).unwrap();
+                                    }
+                                    writeln!(out, "  Would succeed: {}", preview.would_succeed).unwrap();
+                                    writeln!(out, "  Expected status: {:?}", preview.expected_status).unwrap();
+                                }
+                                Err(e) => writeln!(out, "[shell] Error: Failed to simulate transaction: {}", e).unwrap(),
+                            },
+                            Err(e) => writeln!(out, "[shell] Error: Failed to create transaction: {}", e).unwrap(),
                         }
                     }
+                    None => writeln!(out, "[shell] Error: Chain {} not found", chain_id.0).unwrap(),
+                }
+            }
+            ShellCommand::Balance { chain_id, account, with_proof } => {
+                let node = hig_nodes.lock().await.get(&chain_id).cloned();
+                match node {
+                    Some(node) => match node.lock().await.get_account_balance(chain_id.clone(), account, with_proof).await {
+                        Ok(balance) => {
+                            writeln!(out, "[shell] Balance of account {} on {}: {}", account, chain_id.0, balance.balance).unwrap();
+                            writeln!(out, "  State root: {}", balance.state_root).unwrap();
+                            if let Some(proof) = &balance.proof {
+                                writeln!(out, "  Proof ({} steps, verifies: {}):", proof.path.len(), proof.verify(&balance.state_root)).unwrap();
+                                writeln!(out, "  {}", serde_json::to_string(proof).unwrap()).unwrap();
+                            } else if with_proof {
+                                writeln!(out, "  No proof: account does not exist").unwrap();
+                            }
+                        }
+                        Err(e) => writeln!(out, "[shell] Error: Failed to get balance: {}", e).unwrap(),
+                    },
+                    None => writeln!(out, "[shell] Error: Chain {} not found", chain_id.0).unwrap(),
+                }
+            }
+            ShellCommand::Blocked { chain_id } => {
+                let node = hig_nodes.lock().await.get(&chain_id).cloned();
+                match node {
+                    Some(node) => match node.lock().await.get_all_blocked_transactions().await {
+                        Ok(blocked) => {
+                            let mut blocked: Vec<_> = blocked.into_iter().collect();
+                            blocked.sort_by(|(a, _), (b, _)| a.0.cmp(&b.0));
+                            writeln!(out, "[shell] {} blocked transactions on {}", blocked.len(), chain_id.0).unwrap();
+                            for (tx_id, waits_on) in blocked {
+                                let blocking_txs: Vec<&str> = waits_on.blocking_txs.iter().map(|id| id.0.as_str()).collect();
+                                writeln!(out, "  {} <- {:?} (keys {:?})", tx_id.0, blocking_txs, waits_on.keys).unwrap();
+                            }
+                        }
+                        Err(e) => writeln!(out, "[shell] Error: Failed to get blocked transactions: {}", e).unwrap(),
+                    },
+                    None => writeln!(out, "[shell] Error: Chain {} not found", chain_id.0).unwrap(),
+                }
+            }
+            ShellCommand::ExplainCat { cat_id } => {
+                let chains: Vec<(ChainId, Arc<Mutex<HyperIGNode>>)> = {
+                    let mut chains: Vec<_> = hig_nodes.lock().await.iter()
+                        .map(|(id, node)| (id.clone(), node.clone()))
+                        .collect();
+                    chains.sort_by(|a, b| a.0.0.cmp(&b.0.0));
+                    chains
+                };
 
-                    // TODO check and explain the following again
-                    if !transactions.is_empty() {
-                        match CLTransaction::new(
-                            cl_id.clone(),
-                            chain_ids.clone(),
-                            transactions,
-                        ) {
-                            Ok(cl_tx) => {
-                                let mut cl_node_guard = cl_node.lock().await;
-                                if let Err(e) = cl_node_guard.submit_transaction(cl_tx).await {
-                                    println!("[shell] Error: Failed to submit CAT transaction: {}", e);
-                                } else {
-                                    let tx_id = TransactionId(format!("{}:tx", cl_id.0));
-                                    transaction_tracker.lock().await.add_transaction(tx_id);
-                                    println!("[shell] CAT transaction sent successfully. CL-ID: '{}'", cl_id.0);
+                // Collect the steps recorded by the CL, the HIGs and the HS, then order them in time
+                // (the CL records clock times, the HIGs and the HS record instants)
+                let cl_clock = cl_node.lock().await.get_clock();
+                let mut timeline: Vec<(std::time::Instant, String)> = Vec::new();
+                let mut outcomes = Vec::new();
+                for (chain_id, node) in &chains {
+                    let node = node.lock().await;
+                    for entry in node.get_cat_audit_trail(&cat_id).await {
+                        let prefix = format!("[{} @ block {}]", chain_id.0, entry.block_height);
+                        let text = match entry.event {
+                            CatAuditEvent::Received { tx_id } => {
+                                if let Some(inclusion) = cl_node.lock().await.get_inclusion_time(&tx_id).await {
+                                    timeline.push((cl_clock.instant_at(inclusion.submitted_at), format!("[CL] part for {} entered the mempool", chain_id.0)));
+                                    timeline.push((cl_clock.instant_at(inclusion.included_at), format!("[CL] part for {} included in block {}", chain_id.0, inclusion.block_height)));
                                 }
+                                let status = node.get_transaction_status(tx_id.clone()).await.ok();
+                                outcomes.push(format!("{}: {}", chain_id.0, status.map_or("unknown".to_string(), |status| format!("{:?}", status))));
+                                "HIG received the CAT".to_string()
                             }
-                            Err(e) => println!("[shell] Error: Failed to create CL transaction: {}", e),
-                        }
-                    } else {
-                        println!("[shell] Error: No valid transactions were created");
+                            CatAuditEvent::Rejected { blocked_by, key } => format!(
+                                "rejected the CAT: key '{}' is locked by pending transaction {} and CATs may not depend on pending transactions, so it proposes Failure",
+                                key, blocked_by.0),
+                            CatAuditEvent::Postponed { blocked_by, key } => format!(
+                                "postponed the CAT: key '{}' is locked by pending transaction {}, it is reprocessed once that transaction is final",
+                                key, blocked_by.0),
+                            CatAuditEvent::Proposed { status: CATStatus::Success } => "executing the CAT on the current state would succeed, so it proposes Success".to_string(),
+                            CatAuditEvent::Proposed { status: CATStatus::Failure } => "executing the CAT on the current state would fail, so it proposes Failure".to_string(),
+                            CatAuditEvent::Proposed { status: CATStatus::Pending } => "keeps the CAT pending without a proposal".to_string(),
+                            CatAuditEvent::ProposalSent { status, attempts } if attempts > 1 => format!("sent its {:?} proposal to the HS after {} attempts", status, attempts),
+                            CatAuditEvent::ProposalSent { status, .. } => format!("sent its {:?} proposal to the HS", status),
+                            CatAuditEvent::ProposalDropped { status, attempts } => format!("gave up sending its {:?} proposal after {} attempts", status, attempts),
+                            CatAuditEvent::StatusUpdate { status, applied: true } => format!(
+                                "received the HS status update {:?} and {} the CAT",
+                                status, if status == CATStatusLimited::Success { "executed" } else { "discarded" }),
+                            CatAuditEvent::StatusUpdate { status, applied: false } => format!(
+                                "ignored the HS status update {:?}: the CAT had already failed locally", status),
+                            CatAuditEvent::TimedOut { max_lifetime } => format!(
+                                "the CAT timed out: its lifetime ended at block {} before a status update arrived, so it failed", max_lifetime),
+                        };
+                        timeline.push((entry.at, format!("{} {}", prefix, text)));
+                    }
+                }
+                {
+                    let hs = hs_node.lock().await;
+                    for proposal in hs.get_cat_proposals(&cat_id).await {
+                        timeline.push((proposal.received_at, format!("[HS] received the {:?} proposal of {}", proposal.status, proposal.chain_id.0)));
                     }
+                    if let Some(decided_at) = hs.get_cat_decision_time(&cat_id).await {
+                        let status = hs.get_cat_status(cat_id.clone()).await.map_or("unknown".to_string(), |status| format!("{:?}", status));
+                        timeline.push((decided_at, format!("[HS] decided {}", status)));
+                    }
+                    if let Some(submitted_at) = hs.get_status_update_submission_time(&cat_id).await {
+                        timeline.push((submitted_at, "[HS] submitted the status update to the CL".to_string()));
+                    }
+                }
+
+                if timeline.is_empty() {
+                    writeln!(out, "[shell] Error: No record of CAT {}", cat_id.0).unwrap();
                 } else {
-                    println!("Usage: send-cat <chain_id1,chain_id2,...> <data>");
+                    // Steps recorded at the same time keep the order they were collected in
+                    timeline.sort_by_key(|(at, _)| *at);
+                    let start = timeline[0].0;
+                    writeln!(out, "[shell] Timeline of CAT {}:", cat_id.0).unwrap();
+                    for (at, text) in &timeline {
+                        writeln!(out, "  +{:>8.1} ms  {}", at.duration_since(start).as_secs_f64() * 1000.0, text).unwrap();
+                    }
+                    if !outcomes.is_empty() {
+                        writeln!(out, "  Status: {}", outcomes.join(", ")).unwrap();
+                    }
                 }
             }
-            Some(cmd) => {
-                println!("Unknown command: {}", cmd);
+            ShellCommand::DumpState { chain_id, file } => {
+                let node = hig_nodes.lock().await.get(&chain_id).cloned();
+                match node {
+                    Some(node) => match node.lock().await.get_chain_state().await {
+                        Ok(state) => {
+                            // Sort the accounts numerically so dumps of the same state are identical
+                            let accounts: BTreeMap<u32, i64> = state.into_iter()
+                                .filter_map(|(account, balance)| account.parse::<u32>().ok().map(|account| (account, balance)))
+                                .collect();
+                            match tokio::fs::write(&file, serde_json::to_string_pretty(&accounts).unwrap()).await {
+                                Ok(()) => writeln!(out, "[shell] Wrote {} accounts of {} to {}", accounts.len(), chain_id.0, file).unwrap(),
+                                Err(e) => writeln!(out, "[shell] Error: Failed to write {}: {}", file, e).unwrap(),
+                            }
+                        }
+                        Err(e) => writeln!(out, "[shell] Error: Failed to get chain state: {}", e).unwrap(),
+                    },
+                    None => writeln!(out, "[shell] Error: Chain {} not found", chain_id.0).unwrap(),
+                }
+            }
+            ShellCommand::LoadBalances { chain_id, file } => {
+                let node = hig_nodes.lock().await.get(&chain_id).cloned();
+                match node {
+                    Some(node) => match tokio::fs::read_to_string(&file).await {
+                        Ok(contents) => match serde_json::from_str::<BTreeMap<u32, u32>>(&contents) {
+                            Ok(balances) => {
+                                let balances: Vec<(u32, u32)> = balances.into_iter().collect();
+                                node.lock().await.preload_account_balances(&balances).await;
+                                writeln!(out, "[shell] Loaded {} account balances into {}", balances.len(), chain_id.0).unwrap();
+                            }
+                            Err(e) => writeln!(out, "[shell] Error: Invalid balances in {} (expected {{\"<account>\": <balance>, ...}}): {}", file, e).unwrap(),
+                        },
+                        Err(e) => writeln!(out, "[shell] Error: Failed to read {}: {}", file, e).unwrap(),
+                    },
+                    None => writeln!(out, "[shell] Error: Chain {} not found", chain_id.0).unwrap(),
+                }
+            }
+        }
+        CommandOutcome::Continue
+    }
+}
+
+// Parses `--daemon [socket_path]` from the command line
+fn daemon_socket_path() -> Option<String> {
+    let mut args = std::env::args().skip(1).peekable();
+    while let Some(arg) = args.next() {
+        if arg == "--daemon" {
+            return Some(args.next_if(|next| !next.starts_with("--")).unwrap_or_else(|| config::DEFAULT_CONTROL_SOCKET.to_string()));
+        }
+    }
+    None
+}
+
+// Parses `--cl-listen <address>` from the command line
+fn cl_listen_address() -> Result<Option<String>, String> {
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--cl-listen" {
+            return args.next().map(Some).ok_or_else(|| "--cl-listen needs an address, e.g. 127.0.0.1:9944".to_string());
+        }
+    }
+    Ok(None)
+}
+
+// Returns whether a flag without a value is on the command line
+fn has_flag(flag: &str) -> bool {
+    std::env::args().skip(1).any(|arg| arg == flag)
+}
+
+// Parses `--chains <count|chain_id,chain_id,...>` from the command line
+fn network_spec() -> Result<NetworkSpec, String> {
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--chains" {
+            let value = args.next().ok_or("--chains needs a chain count or a comma-separated list of chain IDs")?;
+            return value.parse().map_err(|e| format!("Invalid --chains '{}': {}", value, e));
+        }
+    }
+    NetworkSpec::with_chain_count(config::DEFAULT_CHAIN_COUNT).map_err(|e| e.to_string())
+}
+
+#[tokio::main]
+async fn main() {
+    // Initialize logging
+    hyperplane::utils::logging::init_logging();
+
+    let socket_path = daemon_socket_path();
+    let (network, cl_listen_address) = match (network_spec(), cl_listen_address()) {
+        (Ok(network), Ok(cl_listen_address)) => (network, cl_listen_address),
+        (Err(e), _) | (_, Err(e)) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    if socket_path.is_none() {
+        println!("=== Hyperplane Shell ===");
+        println!("Type 'help' for commands.");
+    } else {
+        println!("=== Hyperplane Shell (daemon) ===");
+    }
+
+    let shell = Arc::new(setup_network(&network).await);
+
+    // Serve the CL to simulators and tools in other processes
+    if let Some(address) = cl_listen_address {
+        let listener = match TcpListener::bind(&address).await {
+            Ok(listener) => listener,
+            Err(e) => {
+                eprintln!("Error: Failed to listen on {}: {}", address, e);
+                std::process::exit(1);
+            }
+        };
+        // Control calls and the status updates of an HS in another process must be enabled explicitly
+        let options = remote::ServeOptions {
+            allow_control: has_flag("--cl-allow-control"),
+            status_updates: if has_flag("--cl-remote-hs") {
+                Some(shell.hs_node.lock().await.get_sender_to_cl().await)
+            } else {
+                None
+            },
+        };
+        println!("[shell] Serving the CL on {}", address);
+        let cl_node = shell.cl_node.clone();
+        tokio::spawn(async move {
+            if let Err(e) = remote::serve(cl_node, listener, options).await {
+                println!("[shell] Error: Stopped serving the CL: {}", e);
             }
-            None => {}
+        });
+    }
+
+    match socket_path {
+        Some(socket_path) => run_daemon(shell, &socket_path).await,
+        None => run_repl(shell).await,
+    }
+}
+
+// Starts the CL and HS and registers the chains of the network spec
+async fn setup_network(network: &NetworkSpec) -> Shell {
+    // Start the CL and the HS, the interactive shell runs in real time
+    let (hs_node, cl_node) = harness::start_core_nodes(config::BLOCK_TIME, config::CHANNEL_BUFFER_SIZE, Arc::new(SystemClock::new()), &DirectLinks, &ComponentRuntimes::default()).await
+        .expect("Failed to start CL and HS");
+
+    // Store HIG nodes by chain_id
+    let hig_nodes: Arc<Mutex<HashMap<ChainId, Arc<Mutex<HyperIGNode>>>>> = Arc::new(Mutex::new(HashMap::new()));
+    
+    // Initialize transaction tracker
+    let transaction_tracker = Arc::new(Mutex::new(TransactionTracker::new()));
+
+    let shell = Shell { cl_node, hs_node, hig_nodes, transaction_tracker };
+
+    // Create the chains of the network spec
+    println!("[shell] Creating {} chains...", network.chain_count());
+    for chain_id in network.chain_ids() {
+        let mut out = String::new();
+        shell.handle_command(&ShellCommand::AddChain { chain_id: chain_id.clone() }.to_string(), &mut out).await;
+        print!("{}", out);
+    }
+    shell
+}
+
+// Reads commands from stdin
+async fn run_repl(shell: Arc<Shell>) {
+    print!("> ");
+    std::io::stdout().flush().unwrap();
+
+    let stdin = BufReader::new(io::stdin());
+    let mut lines = stdin.lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        let mut out = String::new();
+        let outcome = shell.handle_command(line.trim(), &mut out).await;
+        print!("{}", out);
+        if !matches!(outcome, CommandOutcome::Continue) {
+            break;
         }
         print!("> ");
         std::io::stdout().flush().unwrap();
     }
-} 
+}
+
+// Runs headless and accepts commands on a Unix domain socket
+//
+// Every connection is a session: each line is handled as a shell command and answered with the
+// command's output followed by a `config::CONTROL_RESPONSE_END` line. Any number of sessions can
+// be open at the same time.
+async fn run_daemon(shell: Arc<Shell>, socket_path: &str) {
+    // Remove a stale socket left behind by a previous daemon
+    let _ = std::fs::remove_file(socket_path);
+    let listener = UnixListener::bind(socket_path).expect("Failed to bind control socket");
+    println!("[shell] Listening for commands on {}", socket_path);
+
+    let (shutdown_sender, mut shutdown_receiver) = mpsc::channel::<()>(1);
+    loop {
+        tokio::select! {
+            accepted = listener.accept() => {
+                let stream = match accepted {
+                    Ok((stream, _)) => stream,
+                    Err(e) => {
+                        println!("[shell] Error: Failed to accept control connection: {}", e);
+                        continue;
+                    }
+                };
+                let shell = shell.clone();
+                let shutdown_sender = shutdown_sender.clone();
+                tokio::spawn(async move {
+                    let (reader, mut writer) = stream.into_split();
+                    let mut lines = BufReader::new(reader).lines();
+                    while let Ok(Some(line)) = lines.next_line().await {
+                        let mut out = String::new();
+                        let outcome = shell.handle_command(line.trim(), &mut out).await;
+                        out.push_str(config::CONTROL_RESPONSE_END);
+                        out.push('\n');
+                        if writer.write_all(out.as_bytes()).await.is_err() {
+                            break;
+                        }
+                        match outcome {
+                            CommandOutcome::Continue => {}
+                            CommandOutcome::Exit => break,
+                            CommandOutcome::Shutdown => {
+                                let _ = shutdown_sender.send(()).await;
+                                break;
+                            }
+                        }
+                    }
+                });
+            }
+            _ = shutdown_receiver.recv() => break,
+        }
+    }
+
+    let _ = std::fs::remove_file(socket_path);
+    println!("[shell] Daemon stopped.");
+}
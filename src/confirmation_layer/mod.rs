@@ -1,16 +1,21 @@
 use async_trait::async_trait;
 use std::time::Duration;
 use thiserror::Error;
-use crate::types::{ChainId, SubBlock, CLTransaction, CLTransactionId};
-use tokio::sync::mpsc; // Import the correct mpsc module
+use serde::{Deserialize, Serialize};
+use rand::Rng;
+use rand_distr::{Distribution, Normal};
+use crate::types::{ChainId, SubBlock, CLTransaction, CLTransactionId, ChainRegistrationEvent, ChannelHealth};
+use tokio::sync::{broadcast, mpsc}; // Import the correct mpsc module
 
 pub mod node;
+pub mod remote;
 pub use node::ConfirmationLayerNode;
+pub use remote::RemoteConfirmationLayer;
 
 #[cfg(test)]
 mod tests;
 
-#[derive(Debug, Error)]
+#[derive(Debug, Error, Serialize, Deserialize)]
 pub enum ConfirmationLayerError {
     #[error("Chain not found: {0}")]
     ChainNotFound(ChainId),
@@ -26,6 +31,292 @@ pub enum ConfirmationLayerError {
     SubBlockNotFound(ChainId, u64),
     #[error("Transaction already processed: {0}")]
     TransactionAlreadyProcessed(CLTransactionId),
+    #[error("Status updates can only be submitted by the Hyper Scheduler: {0}")]
+    StatusUpdateNotAllowed(CLTransactionId),
+    #[error("Malformed transaction {0}: {1}")]
+    MalformedTransaction(CLTransactionId, String),
+    #[error("Block production is not paused")]
+    NotPaused,
+    #[error("Transaction {0} is {1} bytes, above the maximum transaction size of {2} bytes")]
+    TransactionTooLarge(CLTransactionId, u64, u64),
+    #[error("Invalid block range: {0} to {1}")]
+    InvalidBlockRange(u64, u64),
+    #[error("{0} is not allowed for remote clients of this confirmation layer")]
+    RemoteCallNotAllowed(String),
+}
+
+/// Order in which the pending CL transactions are included in a block.
+/// 
+/// The order determines the order in which the HIGs execute the transactions of a subblock,
+/// and thereby which transactions contend for the same locks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BlockOrdering {
+    /// Order of arrival in the mempool
+    #[default]
+    Fifo,
+    /// Time at which the transactions entered the mempool, oldest first
+    SubmissionTimestamp,
+    /// Random order, seeded per block from the given seed and the block height
+    Random { seed: u64 },
+    /// CATs before regular transactions, each in order of arrival
+    CatFirst,
+}
+
+/// Deviation of the time between two blocks from the block interval.
+///
+/// Real CLs do not produce blocks at a perfect cadence. The gap before every block is drawn from
+/// the jitter around the block interval, truncated at zero, so the mean gap stays the block
+/// interval for the symmetric distributions. Values are in seconds.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BlockIntervalJitter {
+    /// Blocks are produced exactly every block interval
+    #[default]
+    None,
+    /// Gap drawn uniformly from the block interval ± `max_deviation`
+    Uniform { max_deviation: f64 },
+    /// Gap drawn from a normal distribution around the block interval with the given standard deviation
+    Normal { std_dev: f64 },
+    /// A fraction `probability` of the blocks comes `delay` late, the others on time
+    LateBlocks { probability: f64, delay: f64 },
+}
+
+impl BlockIntervalJitter {
+    /// Draws the gap before the next block.
+    ///
+    /// # Arguments
+    /// * `block_interval` - The block interval the jitter is applied to
+    ///
+    /// # Returns
+    /// The sampled gap (the block interval for invalid parameters)
+    pub fn sample(&self, block_interval: Duration) -> Duration {
+        let secs = block_interval.as_secs_f64();
+        let sampled = match *self {
+            BlockIntervalJitter::None => secs,
+            BlockIntervalJitter::Uniform { max_deviation } if max_deviation > 0.0 => {
+                secs + rand::thread_rng().gen_range(-max_deviation..=max_deviation)
+            }
+            BlockIntervalJitter::Uniform { .. } => secs,
+            BlockIntervalJitter::Normal { std_dev } => match Normal::new(secs, std_dev) {
+                Ok(normal) => normal.sample(&mut rand::thread_rng()),
+                Err(_) => secs,
+            },
+            BlockIntervalJitter::LateBlocks { probability, delay } => {
+                if rand::thread_rng().gen_bool(probability.clamp(0.0, 1.0)) { secs + delay } else { secs }
+            }
+        };
+        Duration::from_secs_f64(sampled.max(0.0))
+    }
+
+    /// Returns the jitter with its parameters in seconds multiplied by `factor`.
+    /// Used e.g. by the simulator to convert a jitter given in blocks into seconds.
+    ///
+    /// # Arguments
+    /// * `factor` - The scaling factor
+    pub fn scaled(&self, factor: f64) -> Self {
+        match *self {
+            BlockIntervalJitter::None => BlockIntervalJitter::None,
+            BlockIntervalJitter::Uniform { max_deviation } => BlockIntervalJitter::Uniform { max_deviation: max_deviation * factor },
+            BlockIntervalJitter::Normal { std_dev } => BlockIntervalJitter::Normal { std_dev: std_dev * factor },
+            BlockIntervalJitter::LateBlocks { probability, delay } => BlockIntervalJitter::LateBlocks { probability, delay: delay * factor },
+        }
+    }
+}
+
+/// A CL transaction waiting in the mempool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MempoolEntry {
+    /// The pending CL transaction
+    pub transaction: CLTransaction,
+    /// Block height at which the transaction entered the mempool
+    pub submitted_at_block: u64,
+    /// Number of blocks the transaction has been waiting
+    pub age_blocks: u64,
+    /// Time the transaction has been waiting, on the clock of the CL
+    pub age: Duration,
+    /// Size of the serialized transaction in bytes
+    pub serialized_size: u64,
+}
+
+/// Percentiles of the ages (in blocks) of the transactions in the mempool
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MempoolAgePercentiles {
+    pub p50: u64,
+    pub p90: u64,
+    pub p99: u64,
+    pub max: u64,
+}
+
+impl MempoolAgePercentiles {
+    /// Computes the nearest-rank percentiles of a set of ages
+    /// 
+    /// # Arguments
+    /// * `ages` - The ages in blocks, in any order
+    pub fn from_ages(ages: &[u64]) -> Self {
+        if ages.is_empty() {
+            return Self::default();
+        }
+        let mut sorted = ages.to_vec();
+        sorted.sort_unstable();
+        let percentile = |p: f64| sorted[((p * sorted.len() as f64).ceil() as usize).clamp(1, sorted.len()) - 1];
+        Self {
+            p50: percentile(0.50),
+            p90: percentile(0.90),
+            p99: percentile(0.99),
+            max: sorted[sorted.len() - 1],
+        }
+    }
+}
+
+/// Snapshot of the mempool of one chain
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Mempool {
+    /// Pending transactions that include the chain, in inclusion order
+    pub entries: Vec<MempoolEntry>,
+    /// Percentiles of the ages of the entries
+    pub age_percentiles: MempoolAgePercentiles,
+}
+
+/// Throughput of one chain in the CL
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainStats {
+    /// Number of transactions included in each block since the chain was registered, as (block height, count)
+    pub transactions_per_block: Vec<(u64, u64)>,
+    /// Total number of transactions included for the chain
+    pub total_transactions: u64,
+    /// Number of status updates among the included transactions
+    pub status_updates: u64,
+    /// Serialized size of the transactions included in each block, as (block height, bytes)
+    pub bytes_per_block: Vec<(u64, u64)>,
+    /// Total serialized size of the transactions included for the chain in bytes
+    pub total_bytes: u64,
+}
+
+impl ChainStats {
+    /// Returns the mean number of transactions included per block (zero before the first block)
+    pub fn mean_transactions_per_block(&self) -> f64 {
+        if self.transactions_per_block.is_empty() {
+            return 0.0;
+        }
+        self.total_transactions as f64 / self.transactions_per_block.len() as f64
+    }
+
+    /// Returns the largest number of transactions included in a single block
+    pub fn max_transactions_per_block(&self) -> u64 {
+        self.transactions_per_block.iter().map(|(_, count)| *count).max().unwrap_or(0)
+    }
+
+    /// Returns the mean serialized size in bytes of the transactions included per block (zero before the first block)
+    pub fn mean_bytes_per_block(&self) -> f64 {
+        if self.bytes_per_block.is_empty() {
+            return 0.0;
+        }
+        self.total_bytes as f64 / self.bytes_per_block.len() as f64
+    }
+}
+
+/// Limits on the contents of a CL block (0 = unlimited)
+/// 
+/// Transactions that do not fit into a block stay in the mempool for the next one. A block
+/// always takes at least one transaction, so a transaction larger than the byte limit is
+/// included alone instead of waiting forever.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockCapacity {
+    /// Maximum number of CL transactions in a block
+    pub max_transactions: u64,
+    /// Maximum total payload of the CL transactions in a block, in bytes
+    pub max_bytes: u64,
+}
+
+impl BlockCapacity {
+    /// Checks whether a block that already holds `transactions` CL transactions with `bytes`
+    /// bytes of payload can take another CL transaction of `size` bytes
+    pub fn fits(&self, transactions: u64, bytes: u64, size: u64) -> bool {
+        transactions == 0
+            || ((self.max_transactions == 0 || transactions < self.max_transactions)
+                && (self.max_bytes == 0 || bytes + size <= self.max_bytes))
+    }
+}
+
+/// Contents of a CL block relative to the capacity it was built with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockUtilization {
+    pub block_height: u64,
+    /// Number of CL transactions included in the block
+    pub transactions: u64,
+    /// Total payload of the included CL transactions in bytes
+    pub bytes: u64,
+    /// Total serialized size of the included CL transactions in bytes
+    pub serialized_bytes: u64,
+    /// Capacity of the block
+    pub capacity: BlockCapacity,
+}
+
+impl BlockUtilization {
+    /// Returns the share of the maximum number of transactions used (None without a limit)
+    pub fn transaction_utilization(&self) -> Option<f64> {
+        (self.capacity.max_transactions > 0).then(|| self.transactions as f64 / self.capacity.max_transactions as f64)
+    }
+
+    /// Returns the share of the maximum payload used (None without a limit)
+    pub fn byte_utilization(&self) -> Option<f64> {
+        (self.capacity.max_bytes > 0).then(|| self.bytes as f64 / self.capacity.max_bytes as f64)
+    }
+
+    /// Returns the serialized bytes included per second at the given block interval
+    pub fn bytes_per_second(&self, block_interval: Duration) -> f64 {
+        if block_interval.is_zero() {
+            return 0.0;
+        }
+        self.serialized_bytes as f64 / block_interval.as_secs_f64()
+    }
+}
+
+/// When a transaction entered the mempool and when it was included in a block.
+/// 
+/// Times are read from the clock of the CL (see `Clock::instant_at` to line them up with instants).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InclusionTime {
+    /// Clock time the CL transaction (or its first part, for CATs submitted in parts) entered the mempool
+    pub submitted_at: Duration,
+    /// Clock time the block including the transaction was produced
+    pub included_at: Duration,
+    /// Height of the block including the transaction
+    pub block_height: u64,
+}
+
+/// CL transactions the CL discarded because a transaction with the same id was already included
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DuplicateStats {
+    /// Submissions rejected because a transaction with the same id was already included
+    pub rejected_submissions: u64,
+    /// Pending transactions dropped because another transaction with the same id was included first
+    pub dropped_pending: u64,
+}
+
+impl DuplicateStats {
+    /// Returns the total number of discarded duplicates
+    pub fn total(&self) -> u64 {
+        self.rejected_submissions + self.dropped_pending
+    }
+}
+
+/// Snapshot of the liveness of the CL, to diagnose hangs
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ConfirmationLayerHealth {
+    /// Whether the block production loop is running
+    pub running: bool,
+    /// Whether block production is paused
+    pub paused: bool,
+    /// Height of the last produced block
+    pub current_block_height: u64,
+    /// Number of CL transactions in the mempool
+    pub pending_transactions: usize,
+    /// The channel from the HS (None once the node no longer holds it)
+    pub hs_channel: Option<ChannelHealth>,
+    /// The channels to the HIGs, in the order the chains were registered
+    pub hig_channels: Vec<(ChainId, ChannelHealth)>,
 }
 
 #[async_trait]
@@ -33,12 +324,27 @@ pub trait ConfirmationLayer: Send + Sync {
     /// Register a new chain with the confirmation layer
     async fn register_chain(&mut self, chain_id: ChainId, sender: mpsc::Sender<SubBlock>) -> Result<u64, ConfirmationLayerError>;
 
+    /// Deregister a chain; its subblocks are no longer produced
+    async fn deregister_chain(&mut self, chain_id: ChainId) -> Result<(), ConfirmationLayerError>;
+
+    /// Subscribe to chain registration and deregistration events
+    async fn subscribe_chain_events(&self) -> Result<broadcast::Receiver<ChainRegistrationEvent>, ConfirmationLayerError>;
+
     /// Get the current block ID
     async fn get_current_block(&self) -> Result<u64, ConfirmationLayerError>;
 
     /// Get the subBlock for a specific chain and block
     async fn get_subblock(&self, chain_id: ChainId, block_id: u64) -> Result<SubBlock, ConfirmationLayerError>;
 
+    /// Get the subBlocks of a chain for the blocks from `from_height` to `to_height` (inclusive)
+    /// Heights above the current block are left out.
+    async fn get_subblocks(&self, chain_id: ChainId, from_height: u64, to_height: u64) -> Result<Vec<SubBlock>, ConfirmationLayerError>;
+
+    /// Stream the subBlocks of a chain for the blocks from `from_height` to `to_height` (inclusive) in order
+    /// The history is read in batches, so block production continues while a long range is streamed.
+    /// The channel closes after the last subBlock or if the chain is deregistered.
+    async fn stream_subblocks(&self, chain_id: ChainId, from_height: u64, to_height: u64) -> Result<mpsc::Receiver<SubBlock>, ConfirmationLayerError>;
+
     /// Get all registered chains
     async fn get_registered_chains(&self) -> Result<Vec<ChainId>, ConfirmationLayerError>;
 
@@ -53,4 +359,23 @@ pub trait ConfirmationLayer: Send + Sync {
 
     /// Get the number of pending transactions
     async fn get_pending_transactions(&self) -> Result<usize, ConfirmationLayerError>;
+
+    /// Get the pending transactions that include a chain, with their queue ages
+    async fn get_mempool(&self, chain_id: ChainId) -> Result<Mempool, ConfirmationLayerError>;
+
+    /// Get the per-block and cumulative transaction counts of a chain (also after it was deregistered)
+    async fn get_chain_stats(&self, chain_id: ChainId) -> Result<ChainStats, ConfirmationLayerError>;
+
+    /// Stop block production; submissions are still accepted into the mempool
+    async fn pause(&mut self) -> Result<(), ConfirmationLayerError>;
+
+    /// Resume block production after a pause
+    async fn resume(&mut self) -> Result<(), ConfirmationLayerError>;
+
+    /// Check whether block production is paused
+    async fn is_paused(&self) -> Result<bool, ConfirmationLayerError>;
+
+    /// Produce exactly one block at the next block interval while paused
+    /// Returns the height the block will have; fails if block production is not paused.
+    async fn step(&mut self) -> Result<u64, ConfirmationLayerError>;
 }
\ No newline at end of file
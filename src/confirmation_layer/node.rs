@@ -1,7 +1,10 @@
 use tokio::time::Duration;
-use tokio::sync::mpsc;
-use crate::types::{Transaction, ChainId, CLTransaction, SubBlock, CLTransactionId};
-use super::{ConfirmationLayer, ConfirmationLayerError};
+use tokio::sync::{broadcast, mpsc};
+use tokio::task::JoinHandle;
+use crate::types::{Transaction, TransactionId, ChainId, CLTransaction, SubBlock, CLTransactionId, ChainRegistrationEvent, CHAIN_EVENTS_CAPACITY, ChannelHealth, Clock, SystemClock};
+use crate::types::communication::cl_to_hig::TransactionData;
+use super::{ConfirmationLayer, ConfirmationLayerError, ConfirmationLayerHealth, Mempool, MempoolEntry, MempoolAgePercentiles, BlockOrdering, BlockIntervalJitter, BlockCapacity, BlockUtilization, ChainStats, DuplicateStats, InclusionTime};
+use rand::{SeedableRng, seq::SliceRandom, rngs::StdRng};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use std::collections::{HashMap, HashSet};
@@ -15,8 +18,16 @@ pub struct ConfirmationLayerState {
     pub current_block_height: u64,
     /// Block interval
     pub block_interval: Duration,
+    /// Deviation of the time between two blocks from the block interval
+    pub block_interval_jitter: BlockIntervalJitter,
+    /// Clock time at which the last block was produced
+    pub last_block_at: Option<Duration>,
+    /// Time since the previous block of each produced block after the first, by block height
+    pub inter_block_times: Vec<(u64, Duration)>,
     /// Pending transactions
     pub pending_transactions: Vec<CLTransaction>,
+    /// Block height and clock time at which each pending transaction entered the mempool
+    pub pending_since: HashMap<CLTransactionId, (u64, Duration)>,
     /// Processed CL transactions
     pub processed_cltransactions: Vec<CLTransaction>,
     /// Set of processed CL transaction IDs
@@ -31,6 +42,177 @@ pub struct ConfirmationLayerState {
     pub blocks_transactions: HashMap<u64, Vec<(ChainId, Transaction)>>,
     /// Subblock to individual transactions mapping
     pub subblocks_transactions: HashMap<(ChainId, u64), Vec<Transaction>>,
+    /// Whether block production is paused
+    pub paused: bool,
+    /// Number of blocks still to be produced while paused, one per `step`
+    pub pending_steps: u64,
+    /// Order in which pending transactions are included in a block
+    pub block_ordering: BlockOrdering,
+    /// Whether parts of a CAT submitted as separate CL transactions are held back until all parts are pending
+    pub align_cat_parts: bool,
+    /// Chains already included for CATs of which only some parts have been included
+    pub partial_cat_chains: HashMap<CLTransactionId, HashSet<ChainId>>,
+    /// Transactions included per chain
+    pub chain_stats: HashMap<ChainId, ChainStats>,
+    /// Duplicates discarded at submission and from the mempool
+    pub duplicate_stats: DuplicateStats,
+    /// Number of malformed transactions rejected at submission
+    pub malformed_submissions: u64,
+    /// When each included transaction entered the mempool and was included
+    pub inclusion_times: HashMap<TransactionId, InclusionTime>,
+    /// Limits on the contents of a block
+    pub block_capacity: BlockCapacity,
+    /// Contents of each produced block relative to its capacity
+    pub block_utilization: Vec<BlockUtilization>,
+    /// Maximum serialized size of a submitted CL transaction in bytes (0 = unlimited)
+    pub max_transaction_size: u64,
+    /// Number of CL transactions rejected at submission for exceeding the maximum size
+    pub oversized_submissions: u64,
+}
+
+/// Number of subblocks a subblock stream reads per lock of the CL state (and its channel buffers)
+pub const SUBBLOCK_STREAM_BATCH: u64 = 64;
+
+impl ConfirmationLayerState {
+    /// Builds the subblock of a chain for a block from the transactions the block included
+    pub fn subblock(&self, chain_id: &ChainId, block_height: u64) -> SubBlock {
+        let transactions = self.blocks_transactions
+            .get(&block_height)
+            .map(|txs| txs.iter()
+                .filter(|(cid, _)| cid == chain_id)
+                .map(|(_, tx)| {
+                    let subblock_tx = Transaction::new(
+                        tx.id.clone(),
+                        tx.chain_id.clone(),
+                        tx.constituent_chains.clone(),
+                        tx.data.clone(),
+                        tx.cl_id.clone(),
+                    ).expect("Failed to create transaction");
+                    Transaction { expiry: tx.expiry, ..subblock_tx }
+                })
+                .collect())
+            .unwrap_or_default();
+
+        SubBlock {
+            chain_id: chain_id.clone(),
+            block_height,
+            transactions,
+        }
+    }
+
+    /// Checks a range of subblocks of a chain and returns its last height, capped at the current block
+    fn check_subblock_range(&self, chain_id: &ChainId, from_height: u64, to_height: u64) -> Result<u64, ConfirmationLayerError> {
+        if !self.registered_chains.contains(chain_id) {
+            return Err(ConfirmationLayerError::ChainNotFound(chain_id.clone()));
+        }
+        if from_height > to_height {
+            return Err(ConfirmationLayerError::InvalidBlockRange(from_height, to_height));
+        }
+        Ok(to_height.min(self.current_block_height))
+    }
+
+    /// Adds a transaction to the mempool, recording when it entered
+    /// 
+    /// # Arguments
+    /// * `transaction` - The transaction to add
+    /// * `now` - The time of the clock of the CL
+    pub fn push_pending(&mut self, transaction: CLTransaction, now: Duration) {
+        self.pending_since.entry(transaction.id.clone()).or_insert((self.current_block_height, now));
+        self.pending_transactions.push(transaction);
+    }
+
+    /// Checks whether a CL transaction, or the part of a CAT it carries, was already included in a block
+    pub fn is_processed(&self, cl_tx: &CLTransaction) -> bool {
+        if self.processed_cltransaction_ids.contains(&cl_tx.id) {
+            return true;
+        }
+        match self.partial_cat_chains.get(&cl_tx.id) {
+            Some(included) => cl_tx.transactions.iter().all(|tx| included.contains(&tx.chain_id)),
+            None => false,
+        }
+    }
+
+    /// Records the inclusion of a CL transaction; a CAT counts as processed once all its parts are included
+    fn mark_included(&mut self, cl_tx: &CLTransaction) {
+        if !is_cat_part(cl_tx) && !self.partial_cat_chains.contains_key(&cl_tx.id) {
+            self.processed_cltransaction_ids.insert(cl_tx.id.clone());
+            self.pending_since.remove(&cl_tx.id);
+            return;
+        }
+        let included = self.partial_cat_chains.entry(cl_tx.id.clone()).or_default();
+        included.extend(cl_tx.transactions.iter().map(|tx| tx.chain_id.clone()));
+        if cl_tx.constituent_chains.iter().all(|c| included.contains(c)) {
+            self.partial_cat_chains.remove(&cl_tx.id);
+            self.processed_cltransaction_ids.insert(cl_tx.id.clone());
+            self.pending_since.remove(&cl_tx.id);
+        }
+    }
+
+    /// Returns the ids of the CATs whose pending parts, together with the parts already included,
+    /// cover all constituent chains
+    fn complete_cat_parts(&self, pending: &[CLTransaction]) -> HashSet<CLTransactionId> {
+        let mut covered: HashMap<&CLTransactionId, HashSet<&ChainId>> = HashMap::new();
+        for cl_tx in pending.iter().filter(|cl_tx| is_cat_part(cl_tx)) {
+            let chains = covered.entry(&cl_tx.id).or_insert_with(|| {
+                self.partial_cat_chains.get(&cl_tx.id).map(|included| included.iter().collect()).unwrap_or_default()
+            });
+            chains.extend(cl_tx.transactions.iter().map(|tx| &tx.chain_id));
+        }
+        pending.iter()
+            .filter(|cl_tx| is_cat_part(cl_tx))
+            .filter(|cl_tx| covered.get(&cl_tx.id).is_some_and(|chains| cl_tx.constituent_chains.iter().all(|c| chains.contains(c))))
+            .map(|cl_tx| cl_tx.id.clone())
+            .collect()
+    }
+
+    /// Orders transactions for inclusion in the block at `block_height` according to the block ordering
+    fn order_for_block(&self, transactions: &mut [CLTransaction], block_height: u64) {
+        match self.block_ordering {
+            BlockOrdering::Fifo => {}
+            BlockOrdering::SubmissionTimestamp => {
+                // Transactions without a recorded submission keep their position relative to each other, after all others
+                transactions.sort_by_key(|cl_tx| self.pending_since.get(&cl_tx.id).map_or((1, None), |(_, time)| (0, Some(*time))));
+            }
+            BlockOrdering::Random { seed } => {
+                let mut rng = StdRng::seed_from_u64(seed ^ block_height);
+                transactions.shuffle(&mut rng);
+            }
+            BlockOrdering::CatFirst => {
+                transactions.sort_by_key(|cl_tx| cl_tx.constituent_chains.len() <= 1);
+            }
+        }
+    }
+}
+
+/// Payload size of a CL transaction in bytes, i.e. the total size of the data of its transactions
+fn payload_size(cl_tx: &CLTransaction) -> u64 {
+    cl_tx.transactions.iter().map(|tx| tx.data.len() as u64).sum()
+}
+
+/// Checks whether a CL transaction carries only some of the constituent transactions of a CAT
+fn is_cat_part(cl_tx: &CLTransaction) -> bool {
+    !cl_tx.constituent_chains.iter().all(|c| cl_tx.transactions.iter().any(|tx| &tx.chain_id == c))
+}
+
+/// Checks that a submitted CL transaction is well-formed: it has constituent chains and transactions,
+/// each transaction carries valid data for one of the constituent chains, and CATs span several chains
+fn check_well_formed(cl_tx: &CLTransaction) -> Result<(), String> {
+    if cl_tx.constituent_chains.is_empty() {
+        return Err("no constituent chains".to_string());
+    }
+    if cl_tx.transactions.is_empty() {
+        return Err("no transactions".to_string());
+    }
+    for tx in &cl_tx.transactions {
+        TransactionData::validate(&tx.data).map_err(|e| format!("{} ('{}')", e, tx.data))?;
+        if !cl_tx.constituent_chains.contains(&tx.chain_id) {
+            return Err(format!("transaction {} targets {}, which is not a constituent chain", tx.id, tx.chain_id));
+        }
+        if tx.data.starts_with("CAT") && cl_tx.constituent_chains.len() < 2 {
+            return Err(format!("CAT transaction {} has a single constituent chain", tx.id));
+        }
+    }
+    Ok(())
 }
 
 /// A simple node implementation of the ConfirmationLayer
@@ -41,6 +223,12 @@ pub struct ConfirmationLayerNode {
     receiver_hs_to_cl: Option<mpsc::Receiver<CLTransaction>>,
     /// Replace individual senders with a collection of senders
     pub senders_cl_to_hig: HashMap<String, mpsc::Sender<SubBlock>>, // Map chain ID to its channel
+    /// Sender of chain registration events
+    chain_events: broadcast::Sender<ChainRegistrationEvent>,
+    /// Clock that paces block production
+    clock: Arc<dyn Clock>,
+    /// The block production loop, once started
+    block_production: Option<JoinHandle<()>>,
 }
 
 impl ConfirmationLayerNode {
@@ -51,7 +239,11 @@ impl ConfirmationLayerNode {
                 registered_chains: Vec::new(),
                 current_block_height: 0,
                 block_interval: Duration::from_millis(100),
+                block_interval_jitter: BlockIntervalJitter::None,
+                last_block_at: None,
+                inter_block_times: Vec::new(),
                 pending_transactions: Vec::new(),
+                pending_since: HashMap::new(),
                 processed_cltransactions: Vec::new(),
                 processed_cltransaction_ids: HashSet::new(),
                 processed_transactions: Vec::new(),
@@ -59,9 +251,25 @@ impl ConfirmationLayerNode {
                 blocks_cltransactions: HashMap::new(),
                 blocks_transactions: HashMap::new(),
                 subblocks_transactions: HashMap::new(),
+                paused: false,
+                pending_steps: 0,
+                block_ordering: BlockOrdering::default(),
+                align_cat_parts: false,
+                partial_cat_chains: HashMap::new(),
+                chain_stats: HashMap::new(),
+                duplicate_stats: DuplicateStats::default(),
+                malformed_submissions: 0,
+                inclusion_times: HashMap::new(),
+                block_capacity: BlockCapacity::default(),
+                block_utilization: Vec::new(),
+                max_transaction_size: 0,
+                oversized_submissions: 0,
             })),
             receiver_hs_to_cl: Some(receiver_hs_to_cl),
             senders_cl_to_hig: HashMap::new(), // Initialize empty map for dynamic channels
+            chain_events: broadcast::channel(CHAIN_EVENTS_CAPACITY).0,
+            clock: Arc::new(SystemClock::new()),
+            block_production: None,
         }
     }
 
@@ -78,7 +286,11 @@ impl ConfirmationLayerNode {
                 registered_chains: Vec::new(),
                 current_block_height: 0,
                 block_interval: interval,
+                block_interval_jitter: BlockIntervalJitter::None,
+                last_block_at: None,
+                inter_block_times: Vec::new(),
                 pending_transactions: Vec::new(),
+                pending_since: HashMap::new(),
                 processed_cltransactions: Vec::new(),
                 processed_cltransaction_ids: HashSet::new(),
                 processed_transactions: Vec::new(),
@@ -86,12 +298,157 @@ impl ConfirmationLayerNode {
                 blocks_cltransactions: HashMap::new(),
                 blocks_transactions: HashMap::new(),
                 subblocks_transactions: HashMap::new(),
+                paused: false,
+                pending_steps: 0,
+                block_ordering: BlockOrdering::default(),
+                align_cat_parts: false,
+                partial_cat_chains: HashMap::new(),
+                chain_stats: HashMap::new(),
+                duplicate_stats: DuplicateStats::default(),
+                malformed_submissions: 0,
+                inclusion_times: HashMap::new(),
+                block_capacity: BlockCapacity::default(),
+                block_utilization: Vec::new(),
+                max_transaction_size: 0,
+                oversized_submissions: 0,
             })),
             receiver_hs_to_cl: Some(receiver_hs_to_cl),
             senders_cl_to_hig: HashMap::new(), // Initialize empty map for dynamic channels
+            chain_events: broadcast::channel(CHAIN_EVENTS_CAPACITY).0,
+            clock: Arc::new(SystemClock::new()),
+            block_production: None,
         })
     }
 
+    /// Sets the order in which pending transactions are included in a block.
+    /// 
+    /// # Arguments
+    /// * `ordering` - The block ordering policy
+    pub async fn set_block_ordering(&self, ordering: BlockOrdering) {
+        self.state.lock().await.block_ordering = ordering;
+    }
+
+    /// Gets the order in which pending transactions are included in a block.
+    pub async fn get_block_ordering(&self) -> BlockOrdering {
+        self.state.lock().await.block_ordering
+    }
+
+    /// Sets the deviation of the time between two blocks from the block interval (applies from the next block on).
+    /// 
+    /// # Arguments
+    /// * `jitter` - The distribution the gap before each block is drawn from
+    pub async fn set_block_interval_jitter(&self, jitter: BlockIntervalJitter) {
+        self.state.lock().await.block_interval_jitter = jitter;
+    }
+
+    /// Gets the deviation of the time between two blocks from the block interval.
+    pub async fn get_block_interval_jitter(&self) -> BlockIntervalJitter {
+        self.state.lock().await.block_interval_jitter
+    }
+
+    /// Gets the time that actually passed between each produced block and the previous one,
+    /// as (block height, time since the previous block), in block order.
+    pub async fn get_inter_block_times(&self) -> Vec<(u64, Duration)> {
+        self.state.lock().await.inter_block_times.clone()
+    }
+
+    /// Gets the number of duplicate CL transactions discarded at submission and from the mempool.
+    pub async fn get_duplicate_stats(&self) -> DuplicateStats {
+        self.state.lock().await.duplicate_stats
+    }
+
+    /// Gets when a transaction entered the mempool and when it was included in a block.
+    /// 
+    /// # Returns
+    /// The inclusion time, or None if the transaction has not been included
+    pub async fn get_inclusion_time(&self, tx_id: &TransactionId) -> Option<InclusionTime> {
+        self.state.lock().await.inclusion_times.get(tx_id).copied()
+    }
+
+    /// Gets the number of malformed CL transactions rejected at submission.
+    pub async fn get_malformed_submission_count(&self) -> u64 {
+        self.state.lock().await.malformed_submissions
+    }
+
+    /// Sets whether parts of a CAT submitted as separate CL transactions are held back until parts
+    /// for all constituent chains are pending, so that all parts are included at the same block height.
+    /// 
+    /// # Arguments
+    /// * `align` - Whether to align the parts of a CAT
+    pub async fn set_cat_alignment(&self, align: bool) {
+        self.state.lock().await.align_cat_parts = align;
+    }
+
+    /// Gets whether parts of a CAT are aligned to the same block height.
+    pub async fn get_cat_alignment(&self) -> bool {
+        self.state.lock().await.align_cat_parts
+    }
+
+    /// Sets the limits on the contents of a block (applies from the next block on).
+    /// 
+    /// # Arguments
+    /// * `capacity` - The maximum number of CL transactions and bytes per block (0 = unlimited)
+    pub async fn set_block_capacity(&self, capacity: BlockCapacity) {
+        self.state.lock().await.block_capacity = capacity;
+    }
+
+    /// Gets the limits on the contents of a block.
+    pub async fn get_block_capacity(&self) -> BlockCapacity {
+        self.state.lock().await.block_capacity
+    }
+
+    /// Gets the contents of every produced block relative to its capacity, in block order.
+    pub async fn get_block_utilization(&self) -> Vec<BlockUtilization> {
+        self.state.lock().await.block_utilization.clone()
+    }
+
+    /// Sets the maximum serialized size of a submitted CL transaction; larger submissions are rejected.
+    /// 
+    /// # Arguments
+    /// * `max_size` - The maximum size in bytes (0 = unlimited)
+    pub async fn set_max_transaction_size(&self, max_size: u64) {
+        self.state.lock().await.max_transaction_size = max_size;
+    }
+
+    /// Gets the maximum serialized size of a submitted CL transaction (0 = unlimited).
+    pub async fn get_max_transaction_size(&self) -> u64 {
+        self.state.lock().await.max_transaction_size
+    }
+
+    /// Gets the number of CL transactions rejected at submission for exceeding the maximum size.
+    pub async fn get_oversized_submission_count(&self) -> u64 {
+        self.state.lock().await.oversized_submissions
+    }
+
+    /// Sets the clock that paces block production (takes effect when block production starts).
+    /// 
+    /// # Arguments
+    /// * `clock` - The clock, e.g. a `SimulatedClock` for runs in virtual time
+    pub fn set_clock(&mut self, clock: Arc<dyn Clock>) {
+        self.clock = clock;
+    }
+
+    /// Gets the clock that paces block production.
+    pub fn get_clock(&self) -> Arc<dyn Clock> {
+        self.clock.clone()
+    }
+
+    /// Gets a snapshot of the liveness of the node: whether block production is running, the
+    /// mempool depth, the last block height and the state of the channels from the HS and to the HIGs.
+    pub async fn health(&self) -> ConfirmationLayerHealth {
+        let state = self.state.lock().await;
+        ConfirmationLayerHealth {
+            running: self.block_production.as_ref().is_some_and(|task| !task.is_finished()),
+            paused: state.paused,
+            current_block_height: state.current_block_height,
+            pending_transactions: state.pending_transactions.len(),
+            hs_channel: self.receiver_hs_to_cl.as_ref().map(ChannelHealth::of_receiver),
+            hig_channels: state.registered_chains.iter()
+                .filter_map(|chain_id| self.senders_cl_to_hig.get(&chain_id.0).map(|sender| (chain_id.clone(), ChannelHealth::of_sender(sender))))
+                .collect(),
+        }
+    }
+
     /// Register a new chain
     pub async fn register_chain(&mut self, chain_id: ChainId, sender: mpsc::Sender<SubBlock>) -> Result<u64, ConfirmationLayerError> {
         let mut state = self.state.lock().await;
@@ -108,15 +465,53 @@ impl ConfirmationLayerNode {
             state.registered_chains.push(chain_id.clone());
             log("CL", &format!("Chain '{}' added to registered_chains.", chain_id.0));
         }
+        state.chain_stats.entry(chain_id.clone()).or_default();
+
+        // Sending only fails if there are no subscribers
+        let _ = self.chain_events.send(ChainRegistrationEvent::Registered(chain_id));
 
         Ok(state.current_block_height)
     }
 
     /// Process messages and create blocks
     pub async fn process_messages_and_create_blocks(node: Arc<Mutex<Self>>) {
-        let mut interval = tokio::time::interval(node.lock().await.state.lock().await.block_interval);
+        let clock = node.lock().await.clock.clone();
+        // The first block is produced immediately, the following ones every block interval,
+        // each gap drawn from the jitter around the current block interval
+        let mut next_block_at = clock.now();
         loop {
-            interval.tick().await;
+            clock.sleep_until(next_block_at).await;
+            next_block_at += {
+                let node = node.lock().await;
+                let state = node.state.lock().await;
+                state.block_interval_jitter.sample(state.block_interval)
+            };
+
+            // While paused, transactions from the HS are accepted into the mempool but no block is produced,
+            // unless a single block was requested with `step`
+            let paused = {
+                let node = node.lock().await;
+                let mut state = node.state.lock().await;
+                if state.paused && state.pending_steps > 0 {
+                    state.pending_steps -= 1;
+                    false
+                } else {
+                    state.paused
+                }
+            };
+            if paused {
+                let mut state = node.lock().await;
+                while let Ok(transaction) = state.receiver_hs_to_cl.as_mut().unwrap().try_recv() {
+                    let mut inner_state = state.state.lock().await;
+                    let is_valid = transaction.constituent_chains.iter().all(|c| inner_state.registered_chains.contains(c))
+                        && !inner_state.is_processed(&transaction);
+                    if is_valid {
+                        inner_state.push_pending(transaction, clock.now());
+                    }
+                }
+                continue;
+            }
+
             log("BLOCK", &format!("═══════════════════════════════════════════════════════════════════════════════"));
             log("BLOCK", &format!("🎯 NEW BLOCK CREATED - Height: {} 🎯", node.lock().await.state.lock().await.current_block_height));
 
@@ -130,11 +525,10 @@ impl ConfirmationLayerNode {
                     let mut inner_state = state.state.lock().await;
                     // Check if all chains are registered and transaction hasn't been processed
                     let registered_chains = inner_state.registered_chains.clone();
-                    let processed_ids = inner_state.processed_cltransaction_ids.clone();
                     let is_valid = transaction.constituent_chains.iter().all(|c| registered_chains.contains(c)) 
-                        && !processed_ids.contains(&transaction.id);
+                        && !inner_state.is_processed(&transaction);
                     if is_valid {
-                        inner_state.pending_transactions.push(transaction);
+                        inner_state.push_pending(transaction, clock.now());
                     }
                 }
             }
@@ -145,26 +539,61 @@ impl ConfirmationLayerNode {
                 let mut inner_state = state.state.lock().await;
                 inner_state.current_block_height += 1;
                 let current_block_height = inner_state.current_block_height;
+
+                // Record the time since the previous block
+                let produced_at = clock.now();
+                if let Some(previous) = inner_state.last_block_at.replace(produced_at) {
+                    inner_state.inter_block_times.push((current_block_height, produced_at.saturating_sub(previous)));
+                }
                 
                 // Process pending transactions for this block
                 let mut processed_this_block = Vec::new();
                 let mut remaining = Vec::new();
                 let mut processed_cltransactions = Vec::new();
                 let registered_chains = inner_state.registered_chains.clone();
-                let processed_ids = inner_state.processed_cltransaction_ids.clone();
-                let pending_txs = inner_state.pending_transactions.drain(..).collect::<Vec<_>>();
+                let mut pending_txs = inner_state.pending_transactions.drain(..).collect::<Vec<_>>();
+                inner_state.order_for_block(&mut pending_txs, current_block_height);
+                
+                // With CAT alignment, parts of a CAT wait until parts for all constituent chains are pending
+                let complete_cats = if inner_state.align_cat_parts {
+                    Some(inner_state.complete_cat_parts(&pending_txs))
+                } else {
+                    None
+                };
+                
+                // Fill the block up to its capacity, the rest waits for the next block
+                let capacity = inner_state.block_capacity;
+                let (mut included_transactions, mut included_bytes, mut included_serialized_bytes) = (0u64, 0u64, 0u64);
                 
                 for cl_tx in pending_txs {
-                    // Check if all chains are registered and transaction hasn't been processed
-                    let is_valid = cl_tx.constituent_chains.iter().all(|c| registered_chains.contains(c)) 
-                        && !processed_ids.contains(&cl_tx.id);
-                    if is_valid {
+                    // Drop duplicates of transactions that were included in the meantime
+                    if inner_state.is_processed(&cl_tx) {
+                        log("CL", &format!("Dropping duplicate CL transaction {} from the mempool", cl_tx.id));
+                        inner_state.duplicate_stats.dropped_pending += 1;
+                        continue;
+                    }
+                    // Check if all chains are registered
+                    let is_valid = cl_tx.constituent_chains.iter().all(|c| registered_chains.contains(c));
+                    let is_ready = match &complete_cats {
+                        Some(complete_cats) => !is_cat_part(&cl_tx) || complete_cats.contains(&cl_tx.id),
+                        None => true,
+                    };
+                    let size = payload_size(&cl_tx);
+                    let fits = capacity.fits(included_transactions, included_bytes, size);
+                    if is_valid && is_ready && fits {
+                        included_transactions += 1;
+                        included_bytes += size;
+                        included_serialized_bytes += cl_tx.serialized_size();
+                        // Record when the transactions entered the mempool and were included
+                        let included_at = produced_at;
+                        let submitted_at = inner_state.pending_since.get(&cl_tx.id).map_or(included_at, |(_, at)| *at);
                         // Add to processed transactions for each transaction's this_chain_id
                         for tx in &cl_tx.transactions {
                             processed_this_block.push((tx.chain_id.clone(), tx.clone()));
+                            inner_state.inclusion_times.insert(tx.id.clone(), InclusionTime { submitted_at, included_at, block_height: current_block_height });
                         }
                         processed_cltransactions.push(cl_tx.clone());
-                        inner_state.processed_cltransaction_ids.insert(cl_tx.id.clone());
+                        inner_state.mark_included(&cl_tx);
                     } else {
                         remaining.push(cl_tx);
                     }
@@ -173,6 +602,13 @@ impl ConfirmationLayerNode {
                 
                 // Create a block
                 inner_state.blocks.push(current_block_height);
+                inner_state.block_utilization.push(BlockUtilization {
+                    block_height: current_block_height,
+                    transactions: included_transactions,
+                    bytes: included_bytes,
+                    serialized_bytes: included_serialized_bytes,
+                    capacity,
+                });
                 
                 // Store CL transactions for this block
                 inner_state.blocks_cltransactions.insert(current_block_height, processed_cltransactions.clone());
@@ -191,6 +627,11 @@ impl ConfirmationLayerNode {
             {
                 let state = node.lock().await;
                 for chain_id in &registered_chains {
+                    // Skip chains that were deregistered while the block was produced
+                    if !state.state.lock().await.registered_chains.contains(chain_id) {
+                        continue;
+                    }
+
                     let transactions = processed_this_block
                         .iter()
                         .filter(|(cid, _)| cid == chain_id)
@@ -203,11 +644,21 @@ impl ConfirmationLayerNode {
                         transactions: transactions.clone(),
                     };
 
-                    // Store transactions for this subblock
-                    state.state.lock().await.subblocks_transactions.insert(
-                        (chain_id.clone(), current_block_height),
-                        transactions
-                    );
+                    // Store transactions for this subblock and count them for the chain
+                    {
+                        let mut inner_state = state.state.lock().await;
+                        let chain_stats = inner_state.chain_stats.entry(chain_id.clone()).or_default();
+                        chain_stats.transactions_per_block.push((current_block_height, transactions.len() as u64));
+                        chain_stats.total_transactions += transactions.len() as u64;
+                        chain_stats.status_updates += transactions.iter().filter(|tx| tx.data.starts_with("STATUS_UPDATE")).count() as u64;
+                        let bytes = transactions.iter().map(Transaction::serialized_size).sum::<u64>();
+                        chain_stats.bytes_per_block.push((current_block_height, bytes));
+                        chain_stats.total_bytes += bytes;
+                        inner_state.subblocks_transactions.insert(
+                            (chain_id.clone(), current_block_height),
+                            transactions
+                        );
+                    }
 
                     // Send to the registered chain's HIG channel dynamically
                     if let Some(sender) = state.senders_cl_to_hig.get(&chain_id.0) {
@@ -226,7 +677,9 @@ impl ConfirmationLayerNode {
     /// Start the message processing and block production loop
     pub async fn start(node: Arc<Mutex<Self>>) {
         log("CL", "Starting block production");
-        tokio::spawn(async move { Self::process_messages_and_create_blocks(node).await });
+        let node_for_blocks = node.clone();
+        let block_production = tokio::spawn(async move { Self::process_messages_and_create_blocks(node_for_blocks).await });
+        node.lock().await.block_production = Some(block_production);
     }
 
     /// Shuts down the node by stopping background tasks and clearing state.
@@ -248,6 +701,7 @@ impl ConfirmationLayerNode {
             state.registered_chains.clear();
             state.current_block_height = 0;
             state.pending_transactions.clear();
+            state.pending_since.clear();
             state.processed_cltransactions.clear();
             state.processed_cltransaction_ids.clear();
             state.processed_transactions.clear();
@@ -255,6 +709,22 @@ impl ConfirmationLayerNode {
             state.blocks_cltransactions.clear();
             state.blocks_transactions.clear();
             state.subblocks_transactions.clear();
+            state.paused = false;
+            state.pending_steps = 0;
+            state.block_ordering = BlockOrdering::default();
+            state.block_interval_jitter = BlockIntervalJitter::None;
+            state.last_block_at = None;
+            state.inter_block_times.clear();
+            state.align_cat_parts = false;
+            state.partial_cat_chains.clear();
+            state.chain_stats.clear();
+            state.duplicate_stats = DuplicateStats::default();
+            state.malformed_submissions = 0;
+            state.inclusion_times.clear();
+            state.block_capacity = BlockCapacity::default();
+            state.block_utilization.clear();
+            state.max_transaction_size = 0;
+            state.oversized_submissions = 0;
         }
         
         log("CL", "ConfirmationLayer node shutdown complete");
@@ -278,11 +748,30 @@ impl ConfirmationLayer for ConfirmationLayerNode {
             }
         }
         
-        if state.processed_cltransaction_ids.contains(&transaction.id) {
+        if state.is_processed(&transaction) {
+            state.duplicate_stats.rejected_submissions += 1;
             return Err(ConfirmationLayerError::TransactionAlreadyProcessed(transaction.id.clone()));
         }
         
-        state.pending_transactions.push(transaction);
+        // Status updates only enter the CL through the HS channel
+        if transaction.transactions.iter().any(|tx| tx.data.starts_with("STATUS_UPDATE")) {
+            return Err(ConfirmationLayerError::StatusUpdateNotAllowed(transaction.id.clone()));
+        }
+        
+        // Malformed transactions never enter the mempool, so they cannot reach the HIGs
+        if let Err(reason) = check_well_formed(&transaction) {
+            state.malformed_submissions += 1;
+            return Err(ConfirmationLayerError::MalformedTransaction(transaction.id.clone(), reason));
+        }
+        
+        // Oversized transactions are rejected before they take up space in the mempool
+        let size = transaction.serialized_size();
+        if state.max_transaction_size > 0 && size > state.max_transaction_size {
+            state.oversized_submissions += 1;
+            return Err(ConfirmationLayerError::TransactionTooLarge(transaction.id.clone(), size, state.max_transaction_size));
+        }
+        
+        state.push_pending(transaction, self.clock.now());
         Ok(())
     }
 
@@ -293,25 +782,42 @@ impl ConfirmationLayer for ConfirmationLayerNode {
         }
 
         // Get transactions for this block, or return empty list if no transactions
-        let transactions = state.blocks_transactions
-            .get(&block_height)
-            .map(|txs| txs.iter()
-                .filter(|(cid, _)| cid == &chain_id)
-                .map(|(_, tx)| Transaction::new(
-                    tx.id.clone(),
-                    tx.chain_id.clone(),
-                    tx.constituent_chains.clone(),
-                    tx.data.clone(),
-                    tx.cl_id.clone(),
-                ).expect("Failed to create transaction"))
-                .collect())
-            .unwrap_or_default();
+        Ok(state.subblock(&chain_id, block_height))
+    }
 
-        Ok(SubBlock {
-            chain_id: chain_id.clone(),
-            block_height: block_height,
-            transactions,
-        })
+    async fn get_subblocks(&self, chain_id: ChainId, from_height: u64, to_height: u64) -> Result<Vec<SubBlock>, ConfirmationLayerError> {
+        let state = self.state.lock().await;
+        let to_height = state.check_subblock_range(&chain_id, from_height, to_height)?;
+        Ok((from_height..=to_height).map(|block_height| state.subblock(&chain_id, block_height)).collect())
+    }
+
+    async fn stream_subblocks(&self, chain_id: ChainId, from_height: u64, to_height: u64) -> Result<mpsc::Receiver<SubBlock>, ConfirmationLayerError> {
+        let to_height = self.state.lock().await.check_subblock_range(&chain_id, from_height, to_height)?;
+        let (sender, receiver) = mpsc::channel(SUBBLOCK_STREAM_BATCH as usize);
+        let state = self.state.clone();
+        tokio::spawn(async move {
+            let mut next_height = from_height;
+            while next_height <= to_height {
+                let batch_end = to_height.min(next_height + SUBBLOCK_STREAM_BATCH - 1);
+                // Build the batch under the lock and send it after releasing it
+                let batch = {
+                    let state = state.lock().await;
+                    if !state.registered_chains.contains(&chain_id) {
+                        log("CL", &format!("Stopping subblock stream of {}: chain deregistered", chain_id));
+                        return;
+                    }
+                    (next_height..=batch_end).map(|block_height| state.subblock(&chain_id, block_height)).collect::<Vec<_>>()
+                };
+                for subblock in batch {
+                    if sender.send(subblock).await.is_err() {
+                        // The receiver was dropped
+                        return;
+                    }
+                }
+                next_height = batch_end + 1;
+            }
+        });
+        Ok(receiver)
     }
 
     async fn get_current_block(&self) -> Result<u64, ConfirmationLayerError> {
@@ -353,14 +859,109 @@ impl ConfirmationLayer for ConfirmationLayerNode {
             state.registered_chains.push(chain_id.clone());
             log("CL", &format!("Chain {} added to registered_chains.", chain_id.0));
         }
+        state.chain_stats.entry(chain_id.clone()).or_default();
+
+        // Sending only fails if there are no subscribers
+        let _ = self.chain_events.send(ChainRegistrationEvent::Registered(chain_id));
 
         Ok(state.current_block_height)
     }
 
+    async fn deregister_chain(&mut self, chain_id: ChainId) -> Result<(), ConfirmationLayerError> {
+        let mut state = self.state.lock().await;
+
+        if self.senders_cl_to_hig.remove(&chain_id.0).is_none() {
+            return Err(ConfirmationLayerError::ChainNotFound(chain_id));
+        }
+        state.registered_chains.retain(|c| c != &chain_id);
+        log("CL", &format!("Chain {} deregistered.", chain_id.0));
+
+        // Sending only fails if there are no subscribers
+        let _ = self.chain_events.send(ChainRegistrationEvent::Deregistered(chain_id));
+
+        Ok(())
+    }
+
+    async fn subscribe_chain_events(&self) -> Result<broadcast::Receiver<ChainRegistrationEvent>, ConfirmationLayerError> {
+        Ok(self.chain_events.subscribe())
+    }
+
     async fn get_pending_transactions(&self) -> Result<usize, ConfirmationLayerError> {
         let state = self.state.lock().await;
         Ok(state.pending_transactions.len())
     }
+
+    async fn get_mempool(&self, chain_id: ChainId) -> Result<Mempool, ConfirmationLayerError> {
+        let state = self.state.lock().await;
+        if !state.registered_chains.contains(&chain_id) {
+            return Err(ConfirmationLayerError::ChainNotFound(chain_id));
+        }
+
+        let now = self.clock.now();
+        let entries: Vec<MempoolEntry> = state.pending_transactions.iter()
+            .filter(|cl_tx| cl_tx.constituent_chains.contains(&chain_id))
+            .map(|cl_tx| {
+                // Transactions added to the state directly count as submitted now
+                let (submitted_at_block, submitted_at) = state.pending_since.get(&cl_tx.id)
+                    .copied()
+                    .unwrap_or((state.current_block_height, now));
+                MempoolEntry {
+                    transaction: cl_tx.clone(),
+                    submitted_at_block,
+                    age_blocks: state.current_block_height.saturating_sub(submitted_at_block),
+                    age: now.saturating_sub(submitted_at),
+                    serialized_size: cl_tx.serialized_size(),
+                }
+            })
+            .collect();
+        let ages: Vec<u64> = entries.iter().map(|entry| entry.age_blocks).collect();
+
+        Ok(Mempool {
+            age_percentiles: MempoolAgePercentiles::from_ages(&ages),
+            entries,
+        })
+    }
+
+    async fn get_chain_stats(&self, chain_id: ChainId) -> Result<ChainStats, ConfirmationLayerError> {
+        let state = self.state.lock().await;
+        state.chain_stats.get(&chain_id)
+            .cloned()
+            .ok_or(ConfirmationLayerError::ChainNotFound(chain_id))
+    }
+
+    async fn pause(&mut self) -> Result<(), ConfirmationLayerError> {
+        let mut state = self.state.lock().await;
+        if !state.paused {
+            state.paused = true;
+            log("CL", &format!("Block production paused at height {}", state.current_block_height));
+        }
+        Ok(())
+    }
+
+    async fn resume(&mut self) -> Result<(), ConfirmationLayerError> {
+        let mut state = self.state.lock().await;
+        if state.paused {
+            state.paused = false;
+            log("CL", &format!("Block production resumed at height {} with {} pending transactions", state.current_block_height, state.pending_transactions.len()));
+        }
+        Ok(())
+    }
+
+    async fn is_paused(&self) -> Result<bool, ConfirmationLayerError> {
+        let state = self.state.lock().await;
+        Ok(state.paused)
+    }
+
+    async fn step(&mut self) -> Result<u64, ConfirmationLayerError> {
+        let mut state = self.state.lock().await;
+        if !state.paused {
+            return Err(ConfirmationLayerError::NotPaused);
+        }
+        state.pending_steps += 1;
+        let block_height = state.current_block_height + state.pending_steps;
+        log("CL", &format!("Stepping to block {} while paused", block_height));
+        Ok(block_height)
+    }
 }
 
 #[async_trait::async_trait]
@@ -375,6 +976,16 @@ impl ConfirmationLayer for Arc<Mutex<ConfirmationLayerNode>> {
         node.get_subblock(chain_id, block_id).await
     }
 
+    async fn get_subblocks(&self, chain_id: ChainId, from_height: u64, to_height: u64) -> Result<Vec<SubBlock>, ConfirmationLayerError> {
+        let node = self.lock().await;
+        node.get_subblocks(chain_id, from_height, to_height).await
+    }
+
+    async fn stream_subblocks(&self, chain_id: ChainId, from_height: u64, to_height: u64) -> Result<mpsc::Receiver<SubBlock>, ConfirmationLayerError> {
+        let node = self.lock().await;
+        node.stream_subblocks(chain_id, from_height, to_height).await
+    }
+
     async fn get_current_block(&self) -> Result<u64, ConfirmationLayerError> {
         let node = self.lock().await;
         node.get_current_block().await
@@ -400,8 +1011,47 @@ impl ConfirmationLayer for Arc<Mutex<ConfirmationLayerNode>> {
         node.register_chain(chain_id, sender).await
     }
 
+    async fn deregister_chain(&mut self, chain_id: ChainId) -> Result<(), ConfirmationLayerError> {
+        let mut node = self.lock().await;
+        node.deregister_chain(chain_id).await
+    }
+
+    async fn subscribe_chain_events(&self) -> Result<broadcast::Receiver<ChainRegistrationEvent>, ConfirmationLayerError> {
+        let node = self.lock().await;
+        node.subscribe_chain_events().await
+    }
+
     async fn get_pending_transactions(&self) -> Result<usize, ConfirmationLayerError> {
         let node = self.lock().await;
         node.get_pending_transactions().await
     }
-}
\ No newline at end of file
+    async fn get_mempool(&self, chain_id: ChainId) -> Result<Mempool, ConfirmationLayerError> {
+        let node = self.lock().await;
+        node.get_mempool(chain_id).await
+    }
+
+    async fn get_chain_stats(&self, chain_id: ChainId) -> Result<ChainStats, ConfirmationLayerError> {
+        let node = self.lock().await;
+        node.get_chain_stats(chain_id).await
+    }
+
+    async fn pause(&mut self) -> Result<(), ConfirmationLayerError> {
+        let mut node = self.lock().await;
+        node.pause().await
+    }
+
+    async fn resume(&mut self) -> Result<(), ConfirmationLayerError> {
+        let mut node = self.lock().await;
+        node.resume().await
+    }
+
+    async fn is_paused(&self) -> Result<bool, ConfirmationLayerError> {
+        let node = self.lock().await;
+        node.is_paused().await
+    }
+
+    async fn step(&mut self) -> Result<u64, ConfirmationLayerError> {
+        let mut node = self.lock().await;
+        node.step().await
+    }
+}
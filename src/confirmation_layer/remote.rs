@@ -0,0 +1,485 @@
+//! Access to a confirmation layer in another process over TCP.
+//!
+//! `serve` exposes a CL on a TCP listener and `RemoteConfirmationLayer` implements
+//! `ConfirmationLayer` on top of such a connection, so load generators and statistics written
+//! against the trait can target a network whose nodes run in separate processes or on another
+//! machine.
+//!
+//! Requests and responses are JSON objects, one per line. Calls that return a value are answered
+//! on a shared connection. Calls that return a stream (`register_chain`, `stream_subblocks` and
+//! `subscribe_chain_events`) open a connection of their own, which carries the items of the stream
+//! after the response until the stream ends.
+//!
+//! The protocol has neither TLS nor authentication, so `serve` only listens on loopback addresses;
+//! a CL on another machine is reached through a tunnel (e.g. `ssh -L`). Calls that change how the
+//! CL produces blocks or drop a chain, and status updates of an HS in another process, are refused
+//! unless the `ServeOptions` of the server allow them.
+
+use std::sync::Arc;
+use std::time::Duration;
+use async_trait::async_trait;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, Lines};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::sync::{broadcast, mpsc, Mutex};
+use crate::types::{ChainId, ChainRegistrationEvent, CLTransaction, SubBlock};
+use crate::types::communication::cl_to_hig::STATUS_UPDATE_CL_ID_SUFFIX;
+use crate::utils::logging::log;
+use super::{ChainStats, ConfirmationLayer, ConfirmationLayerError, Mempool};
+
+/// Number of stream items buffered on the receiving side of a stream
+const STREAM_BUFFER_SIZE: usize = 1000;
+
+// ------------------------------------------------------------------------------------------------
+// Protocol
+// ------------------------------------------------------------------------------------------------
+
+/// A call of a `ConfirmationLayer` method, sent by the client
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "method", rename_all = "snake_case")]
+pub enum RemoteRequest {
+    SubmitTransaction { transaction: CLTransaction },
+    /// A status update of an HS in another process, forwarded to the HS channel of the CL
+    SubmitStatusUpdate { transaction: CLTransaction },
+    GetSubblock { chain_id: ChainId, block_height: u64 },
+    GetSubblocks { chain_id: ChainId, from_height: u64, to_height: u64 },
+    GetCurrentBlock,
+    GetRegisteredChains,
+    SetBlockInterval { interval: Duration },
+    GetBlockInterval,
+    DeregisterChain { chain_id: ChainId },
+    GetPendingTransactions,
+    GetMempool { chain_id: ChainId },
+    GetChainStats { chain_id: ChainId },
+    Pause,
+    Resume,
+    IsPaused,
+    Step,
+    /// Answered with the registration height, followed by the subblocks of the chain
+    RegisterChain { chain_id: ChainId },
+    /// Answered with `Done`, followed by the subblocks of the range
+    StreamSubblocks { chain_id: ChainId, from_height: u64, to_height: u64 },
+    /// Answered with `Done`, followed by the chain registration events
+    SubscribeChainEvents,
+}
+
+impl RemoteRequest {
+    /// Returns the method name of a call that changes block production or drops a chain
+    fn control_method(&self) -> Option<&'static str> {
+        match self {
+            RemoteRequest::SetBlockInterval { .. } => Some("set_block_interval"),
+            RemoteRequest::DeregisterChain { .. } => Some("deregister_chain"),
+            RemoteRequest::Pause => Some("pause"),
+            RemoteRequest::Resume => Some("resume"),
+            RemoteRequest::Step => Some("step"),
+            _ => None,
+        }
+    }
+}
+
+/// A response to a request or an item of a stream, sent by the server
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RemoteResponse {
+    Done,
+    Height(u64),
+    SubBlock(SubBlock),
+    SubBlocks(Vec<SubBlock>),
+    Chains(Vec<ChainId>),
+    BlockInterval(Duration),
+    PendingTransactions(usize),
+    Mempool(Mempool),
+    ChainStats(ChainStats),
+    Paused(bool),
+    ChainEvent(ChainRegistrationEvent),
+    Error(ConfirmationLayerError),
+}
+
+/// Writes a message as a single line
+async fn write_message<T: Serialize>(writer: &mut OwnedWriteHalf, message: &T) -> Result<(), ConfirmationLayerError> {
+    let mut line = serde_json::to_string(message).map_err(|e| ConfirmationLayerError::Communication(e.to_string()))?;
+    line.push('\n');
+    writer.write_all(line.as_bytes()).await.map_err(|e| ConfirmationLayerError::Communication(e.to_string()))
+}
+
+/// Reads the message of the next line, None once the connection is closed
+async fn read_message<T: DeserializeOwned>(reader: &mut Lines<BufReader<OwnedReadHalf>>) -> Result<Option<T>, ConfirmationLayerError> {
+    match reader.next_line().await.map_err(|e| ConfirmationLayerError::Communication(e.to_string()))? {
+        Some(line) => serde_json::from_str(&line)
+            .map(Some)
+            .map_err(|e| ConfirmationLayerError::Communication(format!("Invalid message '{}': {}", line, e))),
+        None => Ok(None),
+    }
+}
+
+/// Returns whether a CL transaction has the form of an HS status update
+fn is_status_update(transaction: &CLTransaction) -> bool {
+    transaction.id.0.ends_with(STATUS_UPDATE_CL_ID_SUFFIX)
+        && !transaction.transactions.is_empty()
+        && transaction.transactions.iter().all(|tx| tx.data.starts_with("STATUS_UPDATE"))
+}
+
+// ------------------------------------------------------------------------------------------------
+// Server
+// ------------------------------------------------------------------------------------------------
+
+/// What the clients of a served confirmation layer may do besides submitting transactions,
+/// registering chains and reading the CL
+#[derive(Debug, Clone, Default)]
+pub struct ServeOptions {
+    /// Whether clients may pause, resume and step block production, change the block interval
+    /// and deregister chains
+    pub allow_control: bool,
+    /// The HS channel of the CL, which the status updates of an HS in another process are
+    /// forwarded to (None refuses them)
+    pub status_updates: Option<mpsc::Sender<CLTransaction>>,
+}
+
+/// Serves a confirmation layer to remote clients until the listener fails
+///
+/// Every connection is handled in a task of its own. A chain registered over a connection is
+/// deregistered once the connection is closed. The listener must be bound to a loopback address,
+/// since the connections are neither encrypted nor authenticated.
+///
+/// # Arguments
+/// * `cl` - The confirmation layer to serve, shared by all connections
+/// * `listener` - The listener to accept connections on
+/// * `options` - The calls the clients may make besides the default ones
+pub async fn serve<C>(cl: C, listener: TcpListener, options: ServeOptions) -> std::io::Result<()>
+where
+    C: ConfirmationLayer + Clone + 'static,
+{
+    let address = listener.local_addr()?;
+    if !address.ip().is_loopback() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("Refusing to serve the confirmation layer on {}: connections are not encrypted, so only loopback addresses are served", address),
+        ));
+    }
+    log("CL", &format!("Serving the confirmation layer on {} (control calls {}, remote HS status updates {})", address,
+        if options.allow_control { "allowed" } else { "refused" },
+        if options.status_updates.is_some() { "accepted" } else { "refused" }));
+    loop {
+        let (stream, peer) = listener.accept().await?;
+        let cl = cl.clone();
+        let options = options.clone();
+        tokio::spawn(async move {
+            if let Err(e) = handle_connection(cl, stream, options).await {
+                log("CL", &format!("Remote connection from {} closed: {}", peer, e));
+            }
+        });
+    }
+}
+
+/// Answers the requests of one connection until it is closed or turned into a stream
+async fn handle_connection<C: ConfirmationLayer>(mut cl: C, stream: TcpStream, options: ServeOptions) -> Result<(), ConfirmationLayerError> {
+    let (reader, mut writer) = stream.into_split();
+    let mut reader = BufReader::new(reader).lines();
+    while let Some(request) = read_message::<RemoteRequest>(&mut reader).await? {
+        if let Some(method) = request.control_method().filter(|_| !options.allow_control) {
+            write_message(&mut writer, &RemoteResponse::Error(ConfirmationLayerError::RemoteCallNotAllowed(method.to_string()))).await?;
+            continue;
+        }
+        let response = match request {
+            RemoteRequest::SubmitTransaction { transaction } => cl.submit_transaction(transaction).await.map(|()| RemoteResponse::Done),
+            RemoteRequest::SubmitStatusUpdate { transaction } => match &options.status_updates {
+                None => Err(ConfirmationLayerError::RemoteCallNotAllowed("submit_status_update".to_string())),
+                Some(_) if !is_status_update(&transaction) => Err(ConfirmationLayerError::MalformedTransaction(transaction.id.clone(), "not a status update".to_string())),
+                Some(sender) => sender.send(transaction).await
+                    .map(|()| RemoteResponse::Done)
+                    .map_err(|_| ConfirmationLayerError::Communication("The HS channel of the confirmation layer is closed".to_string())),
+            },
+            RemoteRequest::GetSubblock { chain_id, block_height } => cl.get_subblock(chain_id, block_height).await.map(RemoteResponse::SubBlock),
+            RemoteRequest::GetSubblocks { chain_id, from_height, to_height } => cl.get_subblocks(chain_id, from_height, to_height).await.map(RemoteResponse::SubBlocks),
+            RemoteRequest::GetCurrentBlock => cl.get_current_block().await.map(RemoteResponse::Height),
+            RemoteRequest::GetRegisteredChains => cl.get_registered_chains().await.map(RemoteResponse::Chains),
+            RemoteRequest::SetBlockInterval { interval } => cl.set_block_interval(interval).await.map(|()| RemoteResponse::Done),
+            RemoteRequest::GetBlockInterval => cl.get_block_interval().await.map(RemoteResponse::BlockInterval),
+            RemoteRequest::DeregisterChain { chain_id } => cl.deregister_chain(chain_id).await.map(|()| RemoteResponse::Done),
+            RemoteRequest::GetPendingTransactions => cl.get_pending_transactions().await.map(RemoteResponse::PendingTransactions),
+            RemoteRequest::GetMempool { chain_id } => cl.get_mempool(chain_id).await.map(RemoteResponse::Mempool),
+            RemoteRequest::GetChainStats { chain_id } => cl.get_chain_stats(chain_id).await.map(RemoteResponse::ChainStats),
+            RemoteRequest::Pause => cl.pause().await.map(|()| RemoteResponse::Done),
+            RemoteRequest::Resume => cl.resume().await.map(|()| RemoteResponse::Done),
+            RemoteRequest::IsPaused => cl.is_paused().await.map(RemoteResponse::Paused),
+            RemoteRequest::Step => cl.step().await.map(RemoteResponse::Height),
+            RemoteRequest::RegisterChain { chain_id } => {
+                let (sender, mut receiver) = mpsc::channel(STREAM_BUFFER_SIZE);
+                let height = match cl.register_chain(chain_id.clone(), sender).await {
+                    Ok(height) => height,
+                    Err(e) => {
+                        write_message(&mut writer, &RemoteResponse::Error(e)).await?;
+                        continue;
+                    }
+                };
+                log("CL", &format!("Registered chain {} for a remote HIG", chain_id));
+                let forwarded = async {
+                    write_message(&mut writer, &RemoteResponse::Height(height)).await?;
+                    while let Some(subblock) = receiver.recv().await {
+                        write_message(&mut writer, &RemoteResponse::SubBlock(subblock)).await?;
+                    }
+                    Ok::<(), ConfirmationLayerError>(())
+                }.await;
+                // The remote HIG is gone, so its chain no longer receives subblocks
+                let _ = cl.deregister_chain(chain_id).await;
+                return forwarded;
+            }
+            RemoteRequest::StreamSubblocks { chain_id, from_height, to_height } => {
+                let mut receiver = match cl.stream_subblocks(chain_id, from_height, to_height).await {
+                    Ok(receiver) => receiver,
+                    Err(e) => {
+                        write_message(&mut writer, &RemoteResponse::Error(e)).await?;
+                        continue;
+                    }
+                };
+                write_message(&mut writer, &RemoteResponse::Done).await?;
+                while let Some(subblock) = receiver.recv().await {
+                    write_message(&mut writer, &RemoteResponse::SubBlock(subblock)).await?;
+                }
+                return Ok(());
+            }
+            RemoteRequest::SubscribeChainEvents => {
+                let mut receiver = match cl.subscribe_chain_events().await {
+                    Ok(receiver) => receiver,
+                    Err(e) => {
+                        write_message(&mut writer, &RemoteResponse::Error(e)).await?;
+                        continue;
+                    }
+                };
+                write_message(&mut writer, &RemoteResponse::Done).await?;
+                loop {
+                    match receiver.recv().await {
+                        Ok(event) => write_message(&mut writer, &RemoteResponse::ChainEvent(event)).await?,
+                        Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                        Err(broadcast::error::RecvError::Closed) => return Ok(()),
+                    }
+                }
+            }
+        };
+        write_message(&mut writer, &response.unwrap_or_else(RemoteResponse::Error)).await?;
+    }
+    Ok(())
+}
+
+// ------------------------------------------------------------------------------------------------
+// Client
+// ------------------------------------------------------------------------------------------------
+
+/// A connection to a served confirmation layer
+struct Connection {
+    reader: Lines<BufReader<OwnedReadHalf>>,
+    writer: OwnedWriteHalf,
+}
+
+impl Connection {
+    async fn open(address: &str) -> Result<Self, ConfirmationLayerError> {
+        let stream = TcpStream::connect(address).await
+            .map_err(|e| ConfirmationLayerError::Communication(format!("Failed to connect to {}: {}", address, e)))?;
+        // Requests are small and answered one at a time, so they are not held back for batching
+        stream.set_nodelay(true).map_err(|e| ConfirmationLayerError::Communication(e.to_string()))?;
+        let (reader, writer) = stream.into_split();
+        Ok(Self { reader: BufReader::new(reader).lines(), writer })
+    }
+
+    /// Sends a request and reads its response
+    async fn request(&mut self, request: &RemoteRequest) -> Result<RemoteResponse, ConfirmationLayerError> {
+        write_message(&mut self.writer, request).await?;
+        read_message(&mut self.reader).await?
+            .ok_or_else(|| ConfirmationLayerError::Communication("Connection closed before the response".to_string()))
+    }
+}
+
+/// A confirmation layer in another process, reached through `serve`
+///
+/// Clones share the connection for calls that return a value. A connection that failed or whose
+/// call was cancelled is replaced by a new one on the next call.
+#[derive(Clone)]
+pub struct RemoteConfirmationLayer {
+    /// Address of the server, e.g. `127.0.0.1:9944`
+    address: String,
+    /// The connection for calls that return a value (None while a call uses it or after it failed)
+    connection: Arc<Mutex<Option<Connection>>>,
+}
+
+impl RemoteConfirmationLayer {
+    /// Connects to a served confirmation layer
+    ///
+    /// # Arguments
+    /// * `address` - Address of the server, e.g. `127.0.0.1:9944`
+    pub async fn connect(address: &str) -> Result<Self, ConfirmationLayerError> {
+        let connection = Connection::open(address).await?;
+        Ok(Self { address: address.to_string(), connection: Arc::new(Mutex::new(Some(connection))) })
+    }
+
+    /// Returns the address of the server
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    /// Submits a status update of an HS in this process, which the CL includes like the status
+    /// updates of its own HS
+    ///
+    /// # Arguments
+    /// * `transaction` - The status update, as built by the HS
+    pub async fn submit_status_update(&self, transaction: CLTransaction) -> Result<(), ConfirmationLayerError> {
+        self.call(RemoteRequest::SubmitStatusUpdate { transaction }, |r| match r { RemoteResponse::Done => Ok(()), r => Err(r) }).await
+    }
+
+    /// Returns a channel that submits the status updates sent to it to the served CL, in order
+    ///
+    /// Passed to `HyperSchedulerNode::new`, it lets an HS in this process resolve the CATs of the
+    /// served CL. An update the server refuses is logged and dropped.
+    pub fn status_update_sender(&self) -> mpsc::Sender<CLTransaction> {
+        let (sender, mut receiver) = mpsc::channel::<CLTransaction>(STREAM_BUFFER_SIZE);
+        let client = self.clone();
+        tokio::spawn(async move {
+            while let Some(transaction) = receiver.recv().await {
+                let id = transaction.id.clone();
+                if let Err(e) = client.submit_status_update(transaction).await {
+                    log("CL", &format!("Failed to submit status update {} to the CL at {}: {}", id.0, client.address, e));
+                }
+            }
+        });
+        sender
+    }
+
+    /// Calls a method on the shared connection
+    ///
+    /// # Arguments
+    /// * `request` - The call
+    /// * `extract` - Returns the value of the expected response, or gives back any other response
+    async fn call<T>(&self, request: RemoteRequest, extract: fn(RemoteResponse) -> Result<T, RemoteResponse>) -> Result<T, ConfirmationLayerError> {
+        let mut slot = self.connection.lock().await;
+        // The connection is only put back after a complete exchange, so a cancelled call cannot
+        // leave its response to the next call
+        let mut connection = match slot.take() {
+            Some(connection) => connection,
+            None => Connection::open(&self.address).await?,
+        };
+        let response = connection.request(&request).await?;
+        *slot = Some(connection);
+        match response {
+            RemoteResponse::Error(e) => Err(e),
+            response => extract(response).map_err(unexpected),
+        }
+    }
+
+    /// Opens a connection for a call that returns a stream and reads its response
+    async fn open_stream(&self, request: RemoteRequest) -> Result<(RemoteResponse, Connection), ConfirmationLayerError> {
+        let mut connection = Connection::open(&self.address).await?;
+        match connection.request(&request).await? {
+            RemoteResponse::Error(e) => Err(e),
+            response => Ok((response, connection)),
+        }
+    }
+}
+
+/// Error for a response that does not fit the request
+fn unexpected(response: RemoteResponse) -> ConfirmationLayerError {
+    ConfirmationLayerError::Communication(format!("Unexpected response: {:?}", response))
+}
+
+/// Forwards the subblocks of a stream connection until it or the receiver is closed
+fn forward_subblocks(mut connection: Connection, sender: mpsc::Sender<SubBlock>) {
+    tokio::spawn(async move {
+        while let Ok(Some(RemoteResponse::SubBlock(subblock))) = read_message(&mut connection.reader).await {
+            if sender.send(subblock).await.is_err() {
+                break;
+            }
+        }
+    });
+}
+
+#[async_trait]
+impl ConfirmationLayer for RemoteConfirmationLayer {
+    async fn register_chain(&mut self, chain_id: ChainId, sender: mpsc::Sender<SubBlock>) -> Result<u64, ConfirmationLayerError> {
+        match self.open_stream(RemoteRequest::RegisterChain { chain_id }).await? {
+            (RemoteResponse::Height(height), connection) => {
+                forward_subblocks(connection, sender);
+                Ok(height)
+            }
+            (response, _) => Err(unexpected(response)),
+        }
+    }
+
+    async fn deregister_chain(&mut self, chain_id: ChainId) -> Result<(), ConfirmationLayerError> {
+        self.call(RemoteRequest::DeregisterChain { chain_id }, |r| match r { RemoteResponse::Done => Ok(()), r => Err(r) }).await
+    }
+
+    async fn subscribe_chain_events(&self) -> Result<broadcast::Receiver<ChainRegistrationEvent>, ConfirmationLayerError> {
+        let (_, mut connection) = self.open_stream(RemoteRequest::SubscribeChainEvents).await?;
+        let (sender, receiver) = broadcast::channel(STREAM_BUFFER_SIZE);
+        tokio::spawn(async move {
+            while let Ok(Some(RemoteResponse::ChainEvent(event))) = read_message(&mut connection.reader).await {
+                if sender.send(event).is_err() {
+                    break;
+                }
+            }
+        });
+        Ok(receiver)
+    }
+
+    async fn get_current_block(&self) -> Result<u64, ConfirmationLayerError> {
+        self.call(RemoteRequest::GetCurrentBlock, |r| match r { RemoteResponse::Height(height) => Ok(height), r => Err(r) }).await
+    }
+
+    async fn get_subblock(&self, chain_id: ChainId, block_id: u64) -> Result<SubBlock, ConfirmationLayerError> {
+        self.call(RemoteRequest::GetSubblock { chain_id, block_height: block_id }, |r| match r { RemoteResponse::SubBlock(subblock) => Ok(subblock), r => Err(r) }).await
+    }
+
+    async fn get_subblocks(&self, chain_id: ChainId, from_height: u64, to_height: u64) -> Result<Vec<SubBlock>, ConfirmationLayerError> {
+        self.call(RemoteRequest::GetSubblocks { chain_id, from_height, to_height }, |r| match r { RemoteResponse::SubBlocks(subblocks) => Ok(subblocks), r => Err(r) }).await
+    }
+
+    async fn stream_subblocks(&self, chain_id: ChainId, from_height: u64, to_height: u64) -> Result<mpsc::Receiver<SubBlock>, ConfirmationLayerError> {
+        let (_, connection) = self.open_stream(RemoteRequest::StreamSubblocks { chain_id, from_height, to_height }).await?;
+        let (sender, receiver) = mpsc::channel(STREAM_BUFFER_SIZE);
+        forward_subblocks(connection, sender);
+        Ok(receiver)
+    }
+
+    async fn get_registered_chains(&self) -> Result<Vec<ChainId>, ConfirmationLayerError> {
+        self.call(RemoteRequest::GetRegisteredChains, |r| match r { RemoteResponse::Chains(chains) => Ok(chains), r => Err(r) }).await
+    }
+
+    async fn set_block_interval(&mut self, duration: Duration) -> Result<(), ConfirmationLayerError> {
+        self.call(RemoteRequest::SetBlockInterval { interval: duration }, |r| match r { RemoteResponse::Done => Ok(()), r => Err(r) }).await
+    }
+
+    async fn get_block_interval(&self) -> Result<Duration, ConfirmationLayerError> {
+        self.call(RemoteRequest::GetBlockInterval, |r| match r { RemoteResponse::BlockInterval(interval) => Ok(interval), r => Err(r) }).await
+    }
+
+    async fn submit_transaction(&mut self, transaction: CLTransaction) -> Result<(), ConfirmationLayerError> {
+        self.call(RemoteRequest::SubmitTransaction { transaction }, |r| match r { RemoteResponse::Done => Ok(()), r => Err(r) }).await
+    }
+
+    async fn get_pending_transactions(&self) -> Result<usize, ConfirmationLayerError> {
+        self.call(RemoteRequest::GetPendingTransactions, |r| match r { RemoteResponse::PendingTransactions(count) => Ok(count), r => Err(r) }).await
+    }
+
+    async fn get_mempool(&self, chain_id: ChainId) -> Result<Mempool, ConfirmationLayerError> {
+        self.call(RemoteRequest::GetMempool { chain_id }, |r| match r { RemoteResponse::Mempool(mempool) => Ok(mempool), r => Err(r) }).await
+    }
+
+    async fn get_chain_stats(&self, chain_id: ChainId) -> Result<ChainStats, ConfirmationLayerError> {
+        self.call(RemoteRequest::GetChainStats { chain_id }, |r| match r { RemoteResponse::ChainStats(stats) => Ok(stats), r => Err(r) }).await
+    }
+
+    async fn pause(&mut self) -> Result<(), ConfirmationLayerError> {
+        self.call(RemoteRequest::Pause, |r| match r { RemoteResponse::Done => Ok(()), r => Err(r) }).await
+    }
+
+    async fn resume(&mut self) -> Result<(), ConfirmationLayerError> {
+        self.call(RemoteRequest::Resume, |r| match r { RemoteResponse::Done => Ok(()), r => Err(r) }).await
+    }
+
+    async fn is_paused(&self) -> Result<bool, ConfirmationLayerError> {
+        self.call(RemoteRequest::IsPaused, |r| match r { RemoteResponse::Paused(paused) => Ok(paused), r => Err(r) }).await
+    }
+
+    async fn step(&mut self) -> Result<u64, ConfirmationLayerError> {
+        self.call(RemoteRequest::Step, |r| match r { RemoteResponse::Height(height) => Ok(height), r => Err(r) }).await
+    }
+}
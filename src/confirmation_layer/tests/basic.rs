@@ -1,6 +1,6 @@
 use tokio::time::{Duration, sleep};
 use crate::{
-    types::{TransactionId, ChainId, CLTransaction, Transaction, constants, CLTransactionId},
+    types::{TransactionId, ChainId, CLTransaction, CatBuilder, Transaction, NetworkSpec, CLTransactionId},
     confirmation_layer::{ConfirmationLayer, ConfirmationLayerError, node::ConfirmationLayerNode},
 };
 use std::sync::Arc;
@@ -29,8 +29,8 @@ async fn setup_cl_node_with_registration(block_interval: Duration) -> Arc<Mutex<
     let (sender_2, _receiver_2) = mpsc::channel(10);
 
     // Register the chains with their channels
-    cl_node.lock().await.register_chain(constants::chain_1(), sender_1).await.expect("Failed to register chain-1");
-    cl_node.lock().await.register_chain(constants::chain_2(), sender_2).await.expect("Failed to register chain-2");
+    cl_node.lock().await.register_chain(NetworkSpec::test_chain(1), sender_1).await.expect("Failed to register chain-1");
+    cl_node.lock().await.register_chain(NetworkSpec::test_chain(2), sender_2).await.expect("Failed to register chain-2");
 
     cl_node
 }
@@ -93,14 +93,14 @@ async fn test_transaction_submission() {
     let cl_id = CLTransactionId("cl-tx".to_string());
     let tx = Transaction::new(
         TransactionId(format!("{:?}:regular-tx", cl_id)),
-        constants::chain_1(),
-        vec![constants::chain_1()],
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1)],
         "REGULAR.credit 1 100".to_string(),
         cl_id.clone(),
     ).expect("Failed to create transaction");
     let cl_tx = CLTransaction::new(
         cl_id.clone(),
-        vec![constants::chain_1()],
+        vec![NetworkSpec::test_chain(1)],
         vec![tx],
     ).expect("Failed to create CL transaction");
     let result = cl_node.lock().await.submit_transaction(cl_tx).await;
@@ -122,7 +122,7 @@ async fn test_transaction_submission() {
     logging::log("TEST", "  Verifying transaction inclusion...");
     let mut found = false;
     for block_id in 1..=4 {
-        let subblock = cl_node.lock().await.get_subblock(constants::chain_1(), block_id)
+        let subblock = cl_node.lock().await.get_subblock(NetworkSpec::test_chain(1), block_id)
             .await
             .expect("Failed to get subblock");
         logging::log("TEST", &format!("  Subblock transactions for block {}: {:?}", block_id, subblock.transactions));
@@ -151,7 +151,7 @@ async fn test_chain_registration() {
     // Register a chain
     logging::log("TEST", "  Registering chain...");
     let (sender, _receiver) = mpsc::channel(10);
-    let result = cl_node.lock().await.register_chain(constants::chain_1(), sender).await;
+    let result = cl_node.lock().await.register_chain(NetworkSpec::test_chain(1), sender).await;
     assert!(result.is_ok(), "Failed to register chain");
     logging::log("TEST", "  Chain registered successfully");
 
@@ -159,21 +159,21 @@ async fn test_chain_registration() {
     logging::log("TEST", "  Verifying chain registration...");
     let chains = cl_node.lock().await.get_registered_chains().await.unwrap();
     assert_eq!(chains.len(), 1, "Should have exactly 1 registered chain");
-    assert_eq!(chains[0], constants::chain_1(), "Registered chain should match");
+    assert_eq!(chains[0], NetworkSpec::test_chain(1), "Registered chain should match");
     logging::log("TEST", "  Chain verification successful");
 
     // Try to register the same chain again
     logging::log("TEST", "  Attempting duplicate registration...");
     let (sender_again, _receiver_again) = mpsc::channel(10);
-    let result = cl_node.lock().await.register_chain(constants::chain_1(), sender_again).await;
+    let result = cl_node.lock().await.register_chain(NetworkSpec::test_chain(1), sender_again).await;
     assert!(matches!(result, Err(ConfirmationLayerError::ChainAlreadyRegistered(_))), 
         "Should not be able to register chain twice");
     logging::log("TEST", "  Duplicate registration correctly rejected");
 
     // Get subblock for the chain
     logging::log("TEST", "  Verifying subblock retrieval...");
-    let subblock = cl_node.lock().await.get_subblock(constants::chain_1(), 0).await.unwrap();
-    assert_eq!(subblock.chain_id, constants::chain_1(), "Subblock should be for registered chain");
+    let subblock = cl_node.lock().await.get_subblock(NetworkSpec::test_chain(1), 0).await.unwrap();
+    assert_eq!(subblock.chain_id, NetworkSpec::test_chain(1), "Subblock should be for registered chain");
     assert_eq!(subblock.block_height, 0, "Subblock should be for block 0");
     assert!(subblock.transactions.is_empty(), "Initial subblock should be empty");
     logging::log("TEST", "  Subblock retrieval successful");
@@ -210,9 +210,9 @@ async fn test_get_subblock() {
     
     // Get subblock for non-existent block
     let block_id = 999;
-    let subblock = cl_node.lock().await.get_subblock(constants::chain_1(), block_id).await.unwrap();
+    let subblock = cl_node.lock().await.get_subblock(NetworkSpec::test_chain(1), block_id).await.unwrap();
     assert_eq!(subblock.block_height, block_id);
-    assert_eq!(subblock.chain_id, constants::chain_1());
+    assert_eq!(subblock.chain_id, NetworkSpec::test_chain(1));
     assert!(subblock.transactions.is_empty());
 }
 
@@ -241,12 +241,12 @@ async fn test_get_registered_chains() {
 
     // Register a third chain
     let (sender, _receiver) = mpsc::channel(10);
-    cl_node.lock().await.register_chain(constants::chain_3(), sender).await.expect("Failed to register chain-3");
+    cl_node.lock().await.register_chain(NetworkSpec::test_chain(3), sender).await.expect("Failed to register chain-3");
 
     // Verify registered chain is returned
     let chains = cl_node.lock().await.get_registered_chains().await.unwrap();
     assert_eq!(chains.len(), 3);
-    assert!(chains.contains(&constants::chain_3()), "Chain-3 should be registered");
+    assert!(chains.contains(&NetworkSpec::test_chain(3)), "Chain-3 should be registered");
 }
 
 /// Tests get block interval functionality:
@@ -272,14 +272,14 @@ async fn test_submit_transaction_chain_not_registered() {
     let cl_id = CLTransactionId("cl-tx".to_string());
     let tx = Transaction::new(
         TransactionId(format!("{:?}:test-tx", cl_id)),
-        constants::chain_3(),
-        vec![constants::chain_3()],
+        NetworkSpec::test_chain(3),
+        vec![NetworkSpec::test_chain(3)],
         "REGULAR.credit 1 100".to_string(),
         cl_id.clone(),
     ).expect("Failed to create transaction");
     let cl_tx = CLTransaction::new(
         cl_id.clone(),
-        vec![constants::chain_3()],
+        vec![NetworkSpec::test_chain(3)],
         vec![tx],
     ).expect("Failed to create CL transaction");
     let result = cl_node.lock().await.submit_transaction(cl_tx).await;
@@ -297,21 +297,21 @@ async fn test_submit_cl_transaction_for_two_chains() {
     let cl_id = CLTransactionId("cl-tx".to_string());
     let tx1 = Transaction::new(
         TransactionId(format!("{:?}:multi-tx-1", cl_id)),
-        constants::chain_1(),
-        vec![constants::chain_1(), constants::chain_2()],
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)],
         "REGULAR.credit 1 100".to_string(),
         cl_id.clone(),
     ).expect("Failed to create transaction");
     let tx2 = Transaction::new(
         TransactionId(format!("{:?}:multi-tx-2", cl_id)),
-        constants::chain_2(),
-        vec![constants::chain_1(), constants::chain_2()],
+        NetworkSpec::test_chain(2),
+        vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)],
         "REGULAR.credit 1 100".to_string(),
         cl_id.clone(),
     ).expect("Failed to create transaction");
     let cl_tx = CLTransaction::new(
         cl_id.clone(),
-        vec![constants::chain_1(), constants::chain_2()],
+        vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)],
         vec![tx1, tx2],
     ).expect("Failed to create CL transaction");
     
@@ -329,10 +329,10 @@ async fn test_submit_cl_transaction_for_two_chains() {
     let mut found_chain2 = false;
 
     for block_id in 1..=4 {
-        let subblock1 = cl_node.lock().await.get_subblock(constants::chain_1(), block_id)
+        let subblock1 = cl_node.lock().await.get_subblock(NetworkSpec::test_chain(1), block_id)
             .await
             .expect("Failed to get subblock for chain 1");
-        let subblock2 = cl_node.lock().await.get_subblock(constants::chain_2(), block_id)
+        let subblock2 = cl_node.lock().await.get_subblock(NetworkSpec::test_chain(2), block_id)
             .await
             .expect("Failed to get subblock for chain 2");
 
@@ -426,14 +426,14 @@ async fn test_cl_transaction_id_tracking() {
     let cl_id = CLTransactionId("cl-tx".to_string());
     let tx = Transaction::new(
         TransactionId(format!("{:?}:test-tx", cl_id)),
-        constants::chain_1(),
-        vec![constants::chain_1()],
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1)],
         "REGULAR.credit 1 100".to_string(),
         cl_id.clone(),
     ).expect("Failed to create transaction");
     let cl_tx = CLTransaction::new(
         cl_id.clone(),
-        vec![constants::chain_1()],
+        vec![NetworkSpec::test_chain(1)],
         vec![tx],
     ).expect("Failed to create CL transaction");
     logging::log("TEST", &format!("  Created CL transaction with ID: {}", cl_tx.id.0));
@@ -458,3 +458,130 @@ async fn test_cl_transaction_id_tracking() {
 
     logging::log("TEST", "=== Test completed successfully ===\n");
 }
+
+/// Tests that status updates cannot be submitted through the public API:
+/// - Submit a CL transaction carrying STATUS_UPDATE data
+/// - Verify it is rejected, since only the HS may originate status updates
+#[tokio::test]
+async fn test_status_update_submission_rejected() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_status_update_submission_rejected ===");
+    let cl_node = setup_cl_node_with_registration(Duration::from_millis(100)).await;
+
+    // Create a spoofed status update that mimics the HS format
+    let cl_id = CLTransactionId("cl-cat.UPDATE".to_string());
+    let tx = Transaction::new(
+        TransactionId(format!("{}.{}", cl_id.0, NetworkSpec::test_chain(1).0)),
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1)],
+        "STATUS_UPDATE:Success.CAT_ID:cl-cat".to_string(),
+        cl_id.clone(),
+    ).expect("Failed to create transaction");
+    let cl_tx = CLTransaction::new(
+        cl_id.clone(),
+        vec![NetworkSpec::test_chain(1)],
+        vec![tx],
+    ).expect("Failed to create CL transaction");
+
+    // Submit the transaction
+    logging::log("TEST", "  Submitting spoofed status update...");
+    let result = cl_node.lock().await.submit_transaction(cl_tx).await;
+    assert!(matches!(result, Err(ConfirmationLayerError::StatusUpdateNotAllowed(_))), 
+        "Status update should be rejected when submitted through the API");
+    logging::log("TEST", "  Spoofed status update correctly rejected");
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
+
+/// Tests that the expiry of a transaction is kept when it is included in a subblock
+#[tokio::test]
+async fn test_subblock_keeps_transaction_expiry() {
+    logging::log("TEST", "\n=== Starting test_subblock_keeps_transaction_expiry ===");
+    let cl_node = setup_cl_node_with_registration(Duration::from_millis(100)).await;
+
+    let cl_id = CLTransactionId("cl-tx_expiring".to_string());
+    let tx = Transaction::new(
+        TransactionId(format!("{}:tx", cl_id.0)),
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1)],
+        "REGULAR.credit 1 100".to_string(),
+        cl_id.clone(),
+    ).expect("Failed to create transaction").with_expiry(42);
+    let cl_tx = CLTransaction::new(cl_id, vec![NetworkSpec::test_chain(1)], vec![tx]).expect("Failed to create CL transaction");
+    cl_node.lock().await.submit_transaction(cl_tx).await.expect("Failed to submit transaction");
+
+    sleep(Duration::from_millis(300)).await;
+    let current_block = cl_node.lock().await.get_current_block().await.expect("Failed to get current block");
+    let mut included = Vec::new();
+    for block_id in 1..=current_block {
+        let subblock = cl_node.lock().await.get_subblock(NetworkSpec::test_chain(1), block_id).await.expect("Failed to get subblock");
+        included.extend(subblock.transactions);
+    }
+    assert_eq!(included.len(), 1, "The transaction should be included exactly once");
+    assert_eq!(included[0].expiry, Some(42));
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
+
+/// Tests a CAT built with a different command per chain:
+/// - Each chain's subblock carries its own command under the same transaction ID
+/// - The builder rejects missing, duplicate, foreign and non-CAT commands
+#[tokio::test]
+async fn test_cat_with_per_chain_commands() {
+    logging::log("TEST", "\n=== Starting test_cat_with_per_chain_commands ===");
+    let cl_node = setup_cl_node_with_registration(Duration::from_millis(100)).await;
+    let chains = vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)];
+
+    let cl_id = CLTransactionId("cl-tx_transfer".to_string());
+    let cl_tx = CLTransaction::builder(cl_id.clone(), chains.clone())
+        .command(NetworkSpec::test_chain(1), "CAT.debit 1 50")
+        .command(NetworkSpec::test_chain(2), "CAT.credit 2 50")
+        .build()
+        .expect("Failed to build CL transaction");
+    assert_eq!(cl_tx.transactions.len(), 2);
+    cl_node.lock().await.submit_transaction(cl_tx).await.expect("Failed to submit transaction");
+
+    sleep(Duration::from_millis(300)).await;
+    let current_block = cl_node.lock().await.get_current_block().await.expect("Failed to get current block");
+    let mut chain_1_txs = Vec::new();
+    let mut chain_2_txs = Vec::new();
+    for block_id in 1..=current_block {
+        chain_1_txs.extend(cl_node.lock().await.get_subblock(NetworkSpec::test_chain(1), block_id).await.unwrap().transactions);
+        chain_2_txs.extend(cl_node.lock().await.get_subblock(NetworkSpec::test_chain(2), block_id).await.unwrap().transactions);
+    }
+    assert_eq!(chain_1_txs.len(), 1);
+    assert_eq!(chain_2_txs.len(), 1);
+    assert_eq!(chain_1_txs[0].data, "CAT.debit 1 50");
+    assert_eq!(chain_2_txs[0].data, "CAT.credit 2 50");
+    assert_eq!(chain_1_txs[0].id, chain_2_txs[0].id);
+
+    let builder = || CLTransaction::builder(CLTransactionId("cl-tx_invalid".to_string()), chains.clone());
+    assert!(builder().command(NetworkSpec::test_chain(1), "CAT.debit 1 50").build().is_err(), "A chain without a command should be rejected");
+    assert!(builder().command_for_all("CAT.credit 1 50").command(NetworkSpec::test_chain(2), "CAT.credit 2 50").build().is_err(),
+        "A chain with several commands should be rejected");
+    assert!(builder().command_for_all("CAT.credit 1 50").command(NetworkSpec::test_chain(3), "CAT.credit 2 50").build().is_err(),
+        "A command for a chain outside the constituent chains should be rejected");
+    assert!(builder().command_for_all("REGULAR.credit 1 50").build().is_err(), "Commands spanning several chains should be CATs");
+    assert!(builder().command_for_all("CAT.withdraw 1 50").build().is_err(), "Malformed commands should be rejected");
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
+
+/// Tests that a transfer CAT debits on the source chain and credits on the destination chain
+#[test]
+fn test_cat_builder_transfer() {
+    let cl_id = CLTransactionId("cl-tx_transfer".to_string());
+    let cl_tx = CatBuilder::transfer(NetworkSpec::test_chain(1), NetworkSpec::test_chain(2), 1, 2, 50)
+        .build(cl_id.clone())
+        .expect("Failed to build transfer");
+    assert_eq!(cl_tx.id, cl_id);
+    assert_eq!(cl_tx.constituent_chains, vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)]);
+    let commands: Vec<(ChainId, String)> = cl_tx.transactions.iter().map(|tx| (tx.chain_id.clone(), tx.data.clone())).collect();
+    assert_eq!(commands, vec![
+        (NetworkSpec::test_chain(1), "CAT.debit 1 50".to_string()),
+        (NetworkSpec::test_chain(2), "CAT.credit 2 50".to_string()),
+    ]);
+
+    let same_chain = CatBuilder::transfer(NetworkSpec::test_chain(1), NetworkSpec::test_chain(1), 1, 2, 50).build(cl_id);
+    assert!(same_chain.is_err(), "A transfer within one chain is not a CAT");
+}
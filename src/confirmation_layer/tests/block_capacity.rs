@@ -0,0 +1,99 @@
+use std::sync::Arc;
+use tokio::sync::{mpsc, Mutex};
+use tokio::time::{sleep, Duration};
+use crate::{
+    types::{TransactionId, CLTransaction, Transaction, NetworkSpec, CLTransactionId, SimulatedClock},
+    confirmation_layer::{ConfirmationLayer, BlockCapacity, node::ConfirmationLayerNode},
+};
+use hyperplane::utils::logging;
+
+/// Payload of the regular transactions submitted by the tests (20 bytes)
+const DATA: &str = "REGULAR.credit 1 100";
+
+/// Helper function to create a regular CL transaction for chain-1
+fn regular_transaction(id: &str) -> CLTransaction {
+    let cl_id = CLTransactionId(id.to_string());
+    let tx = Transaction::new(
+        TransactionId(format!("{:?}:{}", cl_id, NetworkSpec::test_chain(1).0)),
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1)],
+        DATA.to_string(),
+        cl_id.clone(),
+    ).expect("Failed to create transaction");
+    CLTransaction::new(cl_id, vec![NetworkSpec::test_chain(1)], vec![tx]).expect("Failed to create CL transaction")
+}
+
+/// Helper function that submits `count` transactions to a CL node with the given capacity and
+/// produces `blocks` blocks in simulated time
+async fn produce_blocks(capacity: BlockCapacity, count: usize, blocks: usize) -> Arc<Mutex<ConfirmationLayerNode>> {
+    let clock = Arc::new(SimulatedClock::new());
+    let (_sender_hs_to_cl, receiver_hs_to_cl) = mpsc::channel(100);
+    let mut cl_node = ConfirmationLayerNode::new_with_block_interval(receiver_hs_to_cl, Duration::from_millis(100))
+        .expect("Failed to create CL node");
+    cl_node.set_clock(clock.clone());
+    let cl_node = Arc::new(Mutex::new(cl_node));
+    let (sender_1, _receiver_1) = mpsc::channel(100);
+    cl_node.lock().await.register_chain(NetworkSpec::test_chain(1), sender_1).await.expect("Failed to register chain-1");
+    cl_node.lock().await.set_block_capacity(capacity).await;
+
+    for i in 0..count {
+        cl_node.lock().await.submit_transaction(regular_transaction(&format!("cl-tx_{}", i))).await.unwrap();
+    }
+    ConfirmationLayerNode::start(cl_node.clone()).await;
+    sleep(Duration::from_millis(200)).await;
+    for _ in 1..blocks {
+        clock.advance(Duration::from_millis(100));
+        sleep(Duration::from_millis(200)).await;
+    }
+    cl_node
+}
+
+/// Tests that a transaction limit spreads the mempool over several blocks:
+/// - Every block takes at most the configured number of transactions
+/// - The remaining transactions are included in the following blocks
+/// - The utilization of each block is recorded relative to the limit
+#[tokio::test]
+async fn test_block_capacity_limits_transactions() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_block_capacity_limits_transactions ===");
+
+    let capacity = BlockCapacity { max_transactions: 2, max_bytes: 0 };
+    let cl_node = produce_blocks(capacity, 5, 3).await;
+
+    let stats = cl_node.get_chain_stats(NetworkSpec::test_chain(1)).await.expect("Failed to get stats of chain-1");
+    assert_eq!(stats.transactions_per_block, vec![(1, 2), (2, 2), (3, 1)]);
+    assert_eq!(cl_node.get_pending_transactions().await.unwrap(), 0);
+
+    let utilization = cl_node.lock().await.get_block_utilization().await;
+    assert_eq!(utilization.iter().map(|block| block.transactions).collect::<Vec<_>>(), vec![2, 2, 1]);
+    assert_eq!(utilization.iter().map(|block| block.transaction_utilization()).collect::<Vec<_>>(), vec![Some(1.0), Some(1.0), Some(0.5)]);
+    assert_eq!(utilization[0].bytes, 2 * DATA.len() as u64);
+    assert_eq!(utilization[0].byte_utilization(), None, "Without a byte limit there is no byte utilization");
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
+
+/// Tests the byte limit of a block:
+/// - Transactions that would exceed the limit wait for the next block
+/// - A transaction larger than the limit is still included, alone in its block
+#[tokio::test]
+async fn test_block_capacity_limits_bytes() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_block_capacity_limits_bytes ===");
+
+    // Room for two transactions per block
+    let capacity = BlockCapacity { max_transactions: 0, max_bytes: 2 * DATA.len() as u64 + 1 };
+    let cl_node = produce_blocks(capacity, 3, 2).await;
+    let utilization = cl_node.lock().await.get_block_utilization().await;
+    assert_eq!(utilization.iter().map(|block| block.transactions).collect::<Vec<_>>(), vec![2, 1]);
+    assert_eq!(utilization[1].transaction_utilization(), None, "Without a transaction limit there is no transaction utilization");
+
+    // Every transaction exceeds the limit on its own
+    let capacity = BlockCapacity { max_transactions: 0, max_bytes: DATA.len() as u64 - 1 };
+    let cl_node = produce_blocks(capacity, 2, 2).await;
+    let utilization = cl_node.lock().await.get_block_utilization().await;
+    assert_eq!(utilization.iter().map(|block| block.transactions).collect::<Vec<_>>(), vec![1, 1]);
+    assert!(utilization[0].byte_utilization().unwrap() > 1.0);
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
@@ -0,0 +1,99 @@
+use tokio::time::Duration;
+use crate::{
+    types::{TransactionId, CLTransaction, Transaction, NetworkSpec, CLTransactionId, ChainId, SubBlock},
+    confirmation_layer::{ConfirmationLayer, ConfirmationLayerError, node::ConfirmationLayerNode},
+};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tokio::sync::mpsc;
+use hyperplane::utils::logging;
+
+/// Helper function to create the part of a CAT on chain-1 and chain-2 that carries the transaction for `chain_id`
+fn cat_part(id: &str, chain_id: ChainId) -> CLTransaction {
+    let cl_id = CLTransactionId(id.to_string());
+    let constituent_chains = vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)];
+    let tx = Transaction::new(
+        TransactionId(format!("{:?}:{}", cl_id, chain_id.0)),
+        chain_id,
+        constituent_chains.clone(),
+        "CAT.credit 1 100".to_string(),
+        cl_id.clone(),
+    ).expect("Failed to create transaction");
+    CLTransaction::new(cl_id, constituent_chains, vec![tx]).expect("Failed to create CL transaction")
+}
+
+/// Helper function to set up a CL node with chain-1 and chain-2 registered
+async fn setup_cl_node(align: bool) -> (Arc<Mutex<ConfirmationLayerNode>>, mpsc::Receiver<SubBlock>, mpsc::Receiver<SubBlock>) {
+    let (_sender_hs_to_cl, receiver_hs_to_cl) = mpsc::channel(100);
+    let cl_node = ConfirmationLayerNode::new_with_block_interval(receiver_hs_to_cl, Duration::from_millis(100))
+        .expect("Failed to create CL node");
+    let cl_node = Arc::new(Mutex::new(cl_node));
+    let (sender_1, receiver_1) = mpsc::channel(100);
+    let (sender_2, receiver_2) = mpsc::channel(100);
+    cl_node.lock().await.register_chain(NetworkSpec::test_chain(1), sender_1).await.expect("Failed to register chain-1");
+    cl_node.lock().await.register_chain(NetworkSpec::test_chain(2), sender_2).await.expect("Failed to register chain-2");
+    cl_node.lock().await.set_cat_alignment(align).await;
+    (cl_node, receiver_1, receiver_2)
+}
+
+/// Helper function that returns the next non-empty subblock of a chain
+async fn next_non_empty_subblock(receiver: &mut mpsc::Receiver<SubBlock>) -> SubBlock {
+    loop {
+        let subblock = tokio::time::timeout(Duration::from_secs(2), receiver.recv()).await
+            .expect("Timed out waiting for a subblock")
+            .expect("Channel closed");
+        if !subblock.transactions.is_empty() {
+            return subblock;
+        }
+    }
+}
+
+/// Tests that with CAT alignment, the parts of a CAT are held back until all parts are pending
+/// and are then included at the same block height
+#[tokio::test]
+async fn test_aligned_cat_parts_share_block_height() {
+    logging::log("TEST", "\n=== Starting test_aligned_cat_parts_share_block_height ===");
+    let (cl_node, mut receiver_1, mut receiver_2) = setup_cl_node(true).await;
+
+    // Submit the chain-1 part and let a few blocks pass
+    cl_node.lock().await.submit_transaction(cat_part("cat-1", NetworkSpec::test_chain(1))).await.expect("Failed to submit chain-1 part");
+    ConfirmationLayerNode::start(cl_node.clone()).await;
+    tokio::time::sleep(Duration::from_millis(350)).await;
+    while let Ok(subblock) = receiver_1.try_recv() {
+        assert!(subblock.transactions.is_empty(), "The chain-1 part should wait for the chain-2 part");
+    }
+    assert_eq!(cl_node.lock().await.get_pending_transactions().await.unwrap(), 1, "The chain-1 part should stay pending");
+
+    // Submit the chain-2 part
+    cl_node.lock().await.submit_transaction(cat_part("cat-1", NetworkSpec::test_chain(2))).await.expect("Failed to submit chain-2 part");
+    let subblock_1 = next_non_empty_subblock(&mut receiver_1).await;
+    let subblock_2 = next_non_empty_subblock(&mut receiver_2).await;
+    assert_eq!(subblock_1.block_height, subblock_2.block_height, "Both parts should be included at the same height");
+
+    // The CAT is processed once all parts are included
+    let result = cl_node.lock().await.submit_transaction(cat_part("cat-1", NetworkSpec::test_chain(1))).await;
+    assert!(matches!(result, Err(ConfirmationLayerError::TransactionAlreadyProcessed(_))), "A processed CAT should be rejected");
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
+
+/// Tests that without CAT alignment, each part of a CAT is included as soon as it is pending
+#[tokio::test]
+async fn test_unaligned_cat_parts_are_included_separately() {
+    logging::log("TEST", "\n=== Starting test_unaligned_cat_parts_are_included_separately ===");
+    let (cl_node, mut receiver_1, mut receiver_2) = setup_cl_node(false).await;
+
+    cl_node.lock().await.submit_transaction(cat_part("cat-1", NetworkSpec::test_chain(1))).await.expect("Failed to submit chain-1 part");
+    ConfirmationLayerNode::start(cl_node.clone()).await;
+    let subblock_1 = next_non_empty_subblock(&mut receiver_1).await;
+
+    // A duplicate of an included part is rejected, the missing part is accepted
+    let result = cl_node.lock().await.submit_transaction(cat_part("cat-1", NetworkSpec::test_chain(1))).await;
+    assert!(matches!(result, Err(ConfirmationLayerError::TransactionAlreadyProcessed(_))), "An included part should be rejected");
+    tokio::time::sleep(Duration::from_millis(150)).await;
+    cl_node.lock().await.submit_transaction(cat_part("cat-1", NetworkSpec::test_chain(2))).await.expect("Failed to submit chain-2 part");
+    let subblock_2 = next_non_empty_subblock(&mut receiver_2).await;
+    assert!(subblock_2.block_height > subblock_1.block_height, "The chain-2 part should be included in a later block");
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
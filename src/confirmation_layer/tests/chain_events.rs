@@ -0,0 +1,42 @@
+use crate::{
+    types::{NetworkSpec, ChainRegistrationEvent},
+    confirmation_layer::{ConfirmationLayer, ConfirmationLayerError, node::ConfirmationLayerNode},
+};
+use tokio::sync::mpsc;
+use hyperplane::utils::logging;
+
+/// Tests that chain registration and deregistration are broadcast to subscribers:
+/// - Registration emits a Registered event
+/// - Deregistration emits a Deregistered event and removes the chain
+/// - Deregistering an unknown chain fails without emitting an event
+#[tokio::test]
+async fn test_chain_registration_events() {
+    logging::log("TEST", "\n=== Starting test_chain_registration_events ===");
+    let (_sender_hs_to_cl, receiver_hs_to_cl) = mpsc::channel(100);
+    let mut cl_node = ConfirmationLayerNode::new(receiver_hs_to_cl);
+    let mut events = cl_node.subscribe_chain_events().await.expect("Failed to subscribe");
+
+    // Register chain-1
+    let (sender_1, _receiver_1) = mpsc::channel(10);
+    cl_node.register_chain(NetworkSpec::test_chain(1), sender_1).await.expect("Failed to register chain-1");
+    assert_eq!(events.try_recv().unwrap(), ChainRegistrationEvent::Registered(NetworkSpec::test_chain(1)));
+    logging::log("TEST", "  Registration event received");
+
+    // Deregister chain-1
+    cl_node.deregister_chain(NetworkSpec::test_chain(1)).await.expect("Failed to deregister chain-1");
+    assert_eq!(events.try_recv().unwrap(), ChainRegistrationEvent::Deregistered(NetworkSpec::test_chain(1)));
+    assert!(cl_node.get_registered_chains().await.unwrap().is_empty(), "chain-1 should no longer be registered");
+    logging::log("TEST", "  Deregistration event received");
+
+    // Deregister an unknown chain
+    let result = cl_node.deregister_chain(NetworkSpec::test_chain(2)).await;
+    assert!(matches!(result, Err(ConfirmationLayerError::ChainNotFound(_))), "Deregistering an unknown chain should fail");
+    assert!(events.try_recv().is_err(), "No event should be emitted for a failed deregistration");
+
+    // The chain can be registered again
+    let (sender_1, _receiver_1) = mpsc::channel(10);
+    cl_node.register_chain(NetworkSpec::test_chain(1), sender_1).await.expect("Failed to re-register chain-1");
+    assert_eq!(events.try_recv().unwrap(), ChainRegistrationEvent::Registered(NetworkSpec::test_chain(1)));
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
@@ -0,0 +1,72 @@
+use std::sync::Arc;
+use tokio::sync::{mpsc, Mutex};
+use tokio::time::{sleep, Duration};
+use crate::{
+    types::{TransactionId, CLTransaction, Transaction, NetworkSpec, CLTransactionId, ChainId, SimulatedClock},
+    confirmation_layer::{ConfirmationLayer, ConfirmationLayerError, node::ConfirmationLayerNode},
+};
+use hyperplane::utils::logging;
+
+/// Helper function to create a CL transaction with one transaction per chain
+fn cl_transaction(id: &str, chains: &[ChainId]) -> CLTransaction {
+    let cl_id = CLTransactionId(id.to_string());
+    let data = if chains.len() > 1 { "CAT.credit 1 100" } else { "REGULAR.credit 1 100" };
+    let transactions = chains.iter().map(|chain_id| Transaction::new(
+        TransactionId(format!("{:?}:{}", cl_id, chain_id.0)),
+        chain_id.clone(),
+        chains.to_vec(),
+        data.to_string(),
+        cl_id.clone(),
+    ).expect("Failed to create transaction")).collect();
+    CLTransaction::new(cl_id, chains.to_vec(), transactions).expect("Failed to create CL transaction")
+}
+
+/// Tests the per-chain throughput accounting:
+/// - Every block records the number of transactions included for each chain
+/// - Chains receiving more transactions show higher totals
+/// - Unknown chains are rejected
+#[tokio::test]
+async fn test_get_chain_stats() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_get_chain_stats ===");
+
+    let clock = Arc::new(SimulatedClock::new());
+    let (_sender_hs_to_cl, receiver_hs_to_cl) = mpsc::channel(100);
+    let mut cl_node = ConfirmationLayerNode::new_with_block_interval(receiver_hs_to_cl, Duration::from_millis(100))
+        .expect("Failed to create CL node");
+    cl_node.set_clock(clock.clone());
+    let cl_node = Arc::new(Mutex::new(cl_node));
+    let (sender_1, _receiver_1) = mpsc::channel(100);
+    let (sender_2, _receiver_2) = mpsc::channel(100);
+    cl_node.lock().await.register_chain(NetworkSpec::test_chain(1), sender_1).await.expect("Failed to register chain-1");
+    cl_node.lock().await.register_chain(NetworkSpec::test_chain(2), sender_2).await.expect("Failed to register chain-2");
+
+    // Both chains start without any transactions
+    let stats_1 = cl_node.get_chain_stats(NetworkSpec::test_chain(1)).await.expect("Failed to get stats of chain-1");
+    assert_eq!(stats_1.total_transactions, 0);
+    assert_eq!(stats_1.mean_transactions_per_block(), 0.0);
+
+    for i in 0..3 {
+        cl_node.lock().await.submit_transaction(cl_transaction(&format!("cl-tx_{}", i), &[NetworkSpec::test_chain(1)])).await.unwrap();
+    }
+    cl_node.lock().await.submit_transaction(cl_transaction("cl-tx_cat", &[NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)])).await.unwrap();
+    ConfirmationLayerNode::start(cl_node.clone()).await;
+    sleep(Duration::from_millis(200)).await;
+    clock.advance(Duration::from_millis(100));
+    sleep(Duration::from_millis(200)).await;
+
+    let stats_1 = cl_node.get_chain_stats(NetworkSpec::test_chain(1)).await.expect("Failed to get stats of chain-1");
+    let stats_2 = cl_node.get_chain_stats(NetworkSpec::test_chain(2)).await.expect("Failed to get stats of chain-2");
+    assert_eq!(stats_1.transactions_per_block, vec![(1, 4), (2, 0)]);
+    assert_eq!(stats_2.transactions_per_block, vec![(1, 1), (2, 0)]);
+    assert_eq!(stats_1.total_transactions, 4);
+    assert_eq!(stats_2.total_transactions, 1);
+    assert_eq!(stats_1.max_transactions_per_block(), 4);
+    assert_eq!(stats_1.mean_transactions_per_block(), 2.0);
+    assert_eq!(stats_1.status_updates, 0);
+
+    let result = cl_node.get_chain_stats(NetworkSpec::test_chain(3)).await;
+    assert!(matches!(result, Err(ConfirmationLayerError::ChainNotFound(_))), "Unknown chains should be rejected");
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
@@ -0,0 +1,36 @@
+use std::sync::Arc;
+use tokio::sync::{mpsc, Mutex};
+use tokio::time::{sleep, Duration};
+use crate::{
+    types::{NetworkSpec, SimulatedClock},
+    confirmation_layer::{ConfirmationLayer, node::ConfirmationLayerNode},
+};
+use hyperplane::utils::logging;
+
+/// Tests that block production follows the clock of the CL:
+/// - The first block is produced right away
+/// - No further blocks are produced while the simulated clock stands still
+/// - Advancing the clock by 2.5 block intervals produces two more blocks
+#[tokio::test]
+async fn test_block_production_on_simulated_clock() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_block_production_on_simulated_clock ===");
+
+    let clock = Arc::new(SimulatedClock::new());
+    let (_sender_hs_to_cl, receiver_hs_to_cl) = mpsc::channel(100);
+    let mut cl_node = ConfirmationLayerNode::new_with_block_interval(receiver_hs_to_cl, Duration::from_millis(100))
+        .expect("Failed to create CL node");
+    cl_node.set_clock(clock.clone());
+    let cl_node = Arc::new(Mutex::new(cl_node));
+    let (sender_1, _receiver_1) = mpsc::channel(100);
+    cl_node.lock().await.register_chain(NetworkSpec::test_chain(1), sender_1).await.expect("Failed to register chain-1");
+    ConfirmationLayerNode::start(cl_node.clone()).await;
+
+    // Real time passes, virtual time does not
+    sleep(Duration::from_millis(300)).await;
+    assert_eq!(cl_node.lock().await.get_current_block().await.unwrap(), 1);
+
+    clock.advance(Duration::from_millis(250));
+    sleep(Duration::from_millis(100)).await;
+    assert_eq!(cl_node.lock().await.get_current_block().await.unwrap(), 3);
+}
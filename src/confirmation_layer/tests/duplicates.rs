@@ -0,0 +1,75 @@
+use tokio::time::{Duration, sleep};
+use crate::{
+    types::{TransactionId, CLTransaction, Transaction, NetworkSpec, CLTransactionId},
+    confirmation_layer::{ConfirmationLayer, ConfirmationLayerError, DuplicateStats, node::ConfirmationLayerNode},
+};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tokio::sync::mpsc;
+use hyperplane::utils::logging;
+
+/// Helper function to set up a started CL node with chain-1 registered
+async fn setup_cl_node() -> (Arc<Mutex<ConfirmationLayerNode>>, mpsc::Receiver<crate::types::SubBlock>) {
+    let (_sender_hs_to_cl, receiver_hs_to_cl) = mpsc::channel(100);
+    let cl_node = ConfirmationLayerNode::new_with_block_interval(receiver_hs_to_cl, Duration::from_millis(100))
+        .expect("Failed to create CL node");
+    let cl_node = Arc::new(Mutex::new(cl_node));
+    let (sender_1, receiver_1) = mpsc::channel(100);
+    cl_node.lock().await.register_chain(NetworkSpec::test_chain(1), sender_1).await.expect("Failed to register chain-1");
+    ConfirmationLayerNode::start(cl_node.clone()).await;
+    (cl_node, receiver_1)
+}
+
+/// Helper function to create a regular CL transaction on chain-1 with the given payload
+fn regular_cl_transaction(id: &str, data: &str) -> CLTransaction {
+    let cl_id = CLTransactionId(id.to_string());
+    let tx = Transaction::new(
+        TransactionId(format!("{:?}:regular-tx", cl_id)),
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1)],
+        data.to_string(),
+        cl_id.clone(),
+    ).expect("Failed to create transaction");
+    CLTransaction::new(cl_id, vec![NetworkSpec::test_chain(1)], vec![tx]).expect("Failed to create CL transaction")
+}
+
+/// Tests that the CL includes only one transaction per id and counts the duplicates it discards:
+/// - Of two pending transactions with the same id, one is included and the other dropped
+/// - A transaction whose id was already included is rejected at submission
+#[tokio::test]
+async fn test_duplicates_are_discarded_and_counted() {
+    logging::log("TEST", "\n=== Starting test_duplicates_are_discarded_and_counted ===");
+    let (cl_node, mut receiver_1) = setup_cl_node().await;
+
+    // Submit an exact duplicate and a variant with a different payload for the same block
+    cl_node.lock().await.pause().await.expect("Failed to pause");
+    let original = regular_cl_transaction("cl-tx_dup", "REGULAR.credit 1 100");
+    cl_node.lock().await.submit_transaction(original.clone()).await.expect("Failed to submit the original");
+    cl_node.lock().await.submit_transaction(original.clone()).await.expect("A pending duplicate should be accepted");
+    cl_node.lock().await.submit_transaction(regular_cl_transaction("cl-tx_dup", "REGULAR.credit 1 999")).await
+        .expect("A pending variant should be accepted");
+    cl_node.lock().await.resume().await.expect("Failed to resume");
+
+    // Only the first one is included
+    let mut included = Vec::new();
+    while included.is_empty() {
+        let subblock = tokio::time::timeout(Duration::from_secs(1), receiver_1.recv()).await
+            .expect("Timed out waiting for a subblock")
+            .expect("Channel closed");
+        included.extend(subblock.transactions);
+    }
+    assert_eq!(included.len(), 1, "Only one transaction per id should be included");
+    assert_eq!(included[0].data, "REGULAR.credit 1 100");
+    assert_eq!(cl_node.lock().await.get_pending_transactions().await.unwrap(), 0, "The duplicates should leave the mempool");
+
+    // A replay of the included transaction is rejected
+    let result = cl_node.lock().await.submit_transaction(original).await;
+    assert!(matches!(result, Err(ConfirmationLayerError::TransactionAlreadyProcessed(_))));
+    sleep(Duration::from_millis(150)).await;
+
+    let stats = cl_node.lock().await.get_duplicate_stats().await;
+    assert_eq!(stats, DuplicateStats { rejected_submissions: 1, dropped_pending: 2 });
+    assert_eq!(stats.total(), 3);
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
@@ -0,0 +1,74 @@
+use std::sync::Arc;
+use tokio::sync::{mpsc, Mutex};
+use tokio::time::{sleep, Duration};
+use crate::{
+    types::{NetworkSpec, SimulatedClock},
+    confirmation_layer::{ConfirmationLayer, BlockIntervalJitter, node::ConfirmationLayerNode},
+};
+use hyperplane::utils::logging;
+
+/// Tests the gaps drawn from the block interval jitter:
+/// - Without jitter and for degenerate parameters the gap is the block interval
+/// - Uniform gaps stay within the deviation, normal gaps are truncated at zero
+/// - Late blocks come exactly the delay late
+/// - Scaling converts the parameters given in blocks into seconds
+#[test]
+fn test_block_interval_jitter_sample() {
+    let interval = Duration::from_millis(500);
+    assert_eq!(BlockIntervalJitter::None.sample(interval), interval);
+    assert_eq!(BlockIntervalJitter::Uniform { max_deviation: 0.0 }.sample(interval), interval);
+    assert_eq!(BlockIntervalJitter::Normal { std_dev: 0.0 }.sample(interval), interval);
+    assert_eq!(BlockIntervalJitter::LateBlocks { probability: 0.0, delay: 0.25 }.sample(interval), interval);
+    assert_eq!(BlockIntervalJitter::LateBlocks { probability: 1.0, delay: 0.25 }.sample(interval), Duration::from_millis(750));
+
+    for _ in 0..100 {
+        let gap = BlockIntervalJitter::Uniform { max_deviation: 0.1 }.sample(interval);
+        assert!(gap >= Duration::from_millis(400) && gap <= Duration::from_millis(600), "Gap {:?} outside the deviation", gap);
+        // A standard deviation far above the interval produces negative samples, which are truncated
+        let _ = BlockIntervalJitter::Normal { std_dev: 10.0 }.sample(interval);
+    }
+
+    assert_eq!(
+        BlockIntervalJitter::LateBlocks { probability: 0.1, delay: 2.0 }.scaled(0.5),
+        BlockIntervalJitter::LateBlocks { probability: 0.1, delay: 1.0 }
+    );
+    assert_eq!(BlockIntervalJitter::Normal { std_dev: 0.2 }.scaled(2.0), BlockIntervalJitter::Normal { std_dev: 0.4 });
+}
+
+/// Tests that block production follows the jittered gaps and records them:
+/// - With every block coming 50ms late, a 100ms interval produces a block every 150ms of virtual time
+/// - The inter-block times of all blocks after the first are recorded with their heights
+#[tokio::test]
+async fn test_block_production_with_jitter() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_block_production_with_jitter ===");
+
+    let clock = Arc::new(SimulatedClock::new());
+    let (_sender_hs_to_cl, receiver_hs_to_cl) = mpsc::channel(100);
+    let mut cl_node = ConfirmationLayerNode::new_with_block_interval(receiver_hs_to_cl, Duration::from_millis(100))
+        .expect("Failed to create CL node");
+    cl_node.set_clock(clock.clone());
+    cl_node.set_block_interval_jitter(BlockIntervalJitter::LateBlocks { probability: 1.0, delay: 0.05 }).await;
+    let cl_node = Arc::new(Mutex::new(cl_node));
+    let (sender_1, _receiver_1) = mpsc::channel(100);
+    cl_node.lock().await.register_chain(NetworkSpec::test_chain(1), sender_1).await.expect("Failed to register chain-1");
+    ConfirmationLayerNode::start(cl_node.clone()).await;
+    sleep(Duration::from_millis(100)).await;
+
+    // One interval without the delay is not enough for the next block
+    clock.advance(Duration::from_millis(100));
+    sleep(Duration::from_millis(100)).await;
+    assert_eq!(cl_node.lock().await.get_current_block().await.unwrap(), 1);
+
+    clock.advance(Duration::from_millis(50));
+    sleep(Duration::from_millis(100)).await;
+    clock.advance(Duration::from_millis(150));
+    sleep(Duration::from_millis(100)).await;
+    assert_eq!(cl_node.lock().await.get_current_block().await.unwrap(), 3);
+
+    assert_eq!(
+        cl_node.lock().await.get_inter_block_times().await,
+        vec![(2, Duration::from_millis(150)), (3, Duration::from_millis(150))]
+    );
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
@@ -0,0 +1,73 @@
+use tokio::time::Duration;
+use crate::{
+    types::{TransactionId, CLTransaction, Transaction, ChainId, NetworkSpec, CLTransactionId},
+    confirmation_layer::{ConfirmationLayer, ConfirmationLayerError, node::ConfirmationLayerNode},
+};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tokio::sync::mpsc;
+use hyperplane::utils::logging;
+
+/// Helper function to set up a CL node with chain-1 and chain-2 registered
+async fn setup_cl_node() -> Arc<Mutex<ConfirmationLayerNode>> {
+    let (_sender_hs_to_cl, receiver_hs_to_cl) = mpsc::channel(100);
+    let cl_node = ConfirmationLayerNode::new_with_block_interval(receiver_hs_to_cl, Duration::from_millis(100))
+        .expect("Failed to create CL node");
+    let cl_node = Arc::new(Mutex::new(cl_node));
+    let (sender_1, _receiver_1) = mpsc::channel(100);
+    let (sender_2, _receiver_2) = mpsc::channel(100);
+    cl_node.lock().await.register_chain(NetworkSpec::test_chain(1), sender_1).await.expect("Failed to register chain-1");
+    cl_node.lock().await.register_chain(NetworkSpec::test_chain(2), sender_2).await.expect("Failed to register chain-2");
+    cl_node
+}
+
+/// Helper function to create a CL transaction without validating its data or chains
+fn unchecked_cl_transaction(id: &str, constituent_chains: Vec<ChainId>, tx_chains: Vec<ChainId>, data: &str) -> CLTransaction {
+    let cl_id = CLTransactionId(id.to_string());
+    let transactions = tx_chains.into_iter().map(|chain_id| Transaction {
+        id: TransactionId(format!("{:?}:{}", cl_id, chain_id.0)),
+        chain_id,
+        constituent_chains: constituent_chains.clone(),
+        data: data.to_string(),
+        cl_id: cl_id.clone(),
+        expiry: None,
+    }).collect();
+    CLTransaction { id: cl_id, constituent_chains, transactions }
+}
+
+/// Tests that the CL rejects malformed transactions at submission and counts them:
+/// - Unknown prefixes and commands
+/// - Transactions for a chain that is not a constituent chain, and CATs on a single chain
+/// - CL transactions without chains or transactions
+#[tokio::test]
+async fn test_malformed_transactions_rejected() {
+    logging::log("TEST", "\n=== Starting test_malformed_transactions_rejected ===");
+    let cl_node = setup_cl_node().await;
+    let chain_1 = || vec![NetworkSpec::test_chain(1)];
+    let both = || vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)];
+
+    let malformed = vec![
+        unchecked_cl_transaction("cl-tx_prefix", NetworkSpec::test_chain(1), NetworkSpec::test_chain(1), "FOO.credit 1 100"),
+        unchecked_cl_transaction("cl-tx_command", NetworkSpec::test_chain(1), NetworkSpec::test_chain(1), "REGULAR.mint 1 100"),
+        unchecked_cl_transaction("cl-tx_overflow", NetworkSpec::test_chain(1), NetworkSpec::test_chain(1), "REGULAR.credit 1 99999999999"),
+        unchecked_cl_transaction("cl-tx_foreign", NetworkSpec::test_chain(1), vec![NetworkSpec::test_chain(2)], "REGULAR.credit 1 100"),
+        unchecked_cl_transaction("cl-tx_single_chain_cat", NetworkSpec::test_chain(1), NetworkSpec::test_chain(1), "CAT.credit 1 100"),
+        unchecked_cl_transaction("cl-tx_no_chains", vec![], vec![], "REGULAR.credit 1 100"),
+        unchecked_cl_transaction("cl-tx_no_transactions", both(), vec![], "CAT.credit 1 100"),
+    ];
+    let count = malformed.len() as u64;
+    for cl_tx in malformed {
+        let id = cl_tx.id.clone();
+        let result = cl_node.lock().await.submit_transaction(cl_tx).await;
+        assert!(matches!(result, Err(ConfirmationLayerError::MalformedTransaction(_, _))), "{:?} should be rejected as malformed, got {:?}", id, result);
+    }
+
+    // A well-formed CAT is still accepted
+    let cat = unchecked_cl_transaction("cl-tx_valid_cat", both(), both(), "CAT.credit 1 100");
+    cl_node.lock().await.submit_transaction(cat).await.expect("A well-formed CAT should be accepted");
+
+    assert_eq!(cl_node.lock().await.get_malformed_submission_count().await, count);
+    assert_eq!(cl_node.lock().await.get_pending_transactions().await.unwrap(), 1, "Only the well-formed CAT should be pending");
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
@@ -0,0 +1,150 @@
+use crate::{
+    types::{TransactionId, CLTransaction, Transaction, NetworkSpec, CLTransactionId, ChainId, SimulatedClock},
+    confirmation_layer::{ConfirmationLayer, ConfirmationLayerError, MempoolAgePercentiles, node::ConfirmationLayerNode},
+};
+use tokio::sync::mpsc;
+use tokio::time::Duration;
+use hyperplane::utils::logging;
+
+/// Helper function to create a CL transaction with one transaction per chain
+fn cl_transaction(id: &str, chains: &[ChainId]) -> CLTransaction {
+    let cl_id = CLTransactionId(id.to_string());
+    let data = if chains.len() > 1 { "CAT.credit 1 100" } else { "REGULAR.credit 1 100" };
+    let transactions = chains.iter().map(|chain_id| Transaction::new(
+        TransactionId(format!("{:?}:{}", cl_id, chain_id.0)),
+        chain_id.clone(),
+        chains.to_vec(),
+        data.to_string(),
+        cl_id.clone(),
+    ).expect("Failed to create transaction")).collect();
+    CLTransaction::new(cl_id, chains.to_vec(), transactions).expect("Failed to create CL transaction")
+}
+
+/// Tests the mempool inspection API:
+/// - Only transactions that include the chain are returned, in submission order
+/// - Ages are counted in blocks since submission
+/// - Unknown chains are rejected
+#[tokio::test]
+async fn test_get_mempool_with_ages() {
+    logging::log("TEST", "\n=== Starting test_get_mempool_with_ages ===");
+    // The node is not started, so block heights are advanced manually
+    let (_sender_hs_to_cl, receiver_hs_to_cl) = mpsc::channel(100);
+    let mut cl_node = ConfirmationLayerNode::new(receiver_hs_to_cl);
+    let (sender_1, _receiver_1) = mpsc::channel(10);
+    let (sender_2, _receiver_2) = mpsc::channel(10);
+    cl_node.register_chain(NetworkSpec::test_chain(1), sender_1).await.expect("Failed to register chain-1");
+    cl_node.register_chain(NetworkSpec::test_chain(2), sender_2).await.expect("Failed to register chain-2");
+
+    // Submit at block 0 and block 3
+    cl_node.submit_transaction(cl_transaction("cl-tx_old", &[NetworkSpec::test_chain(1)])).await.unwrap();
+    cl_node.submit_transaction(cl_transaction("cl-tx_cat", &[NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)])).await.unwrap();
+    cl_node.state.lock().await.current_block_height = 3;
+    cl_node.submit_transaction(cl_transaction("cl-tx_new", &[NetworkSpec::test_chain(1)])).await.unwrap();
+    cl_node.state.lock().await.current_block_height = 5;
+
+    let mempool_1 = cl_node.get_mempool(NetworkSpec::test_chain(1)).await.expect("Failed to get mempool of chain-1");
+    let ids: Vec<_> = mempool_1.entries.iter().map(|entry| entry.transaction.id.0.clone()).collect();
+    assert_eq!(ids, vec!["cl-tx_old", "cl-tx_cat", "cl-tx_new"], "chain-1 should see all three transactions in order");
+    let ages: Vec<_> = mempool_1.entries.iter().map(|entry| entry.age_blocks).collect();
+    assert_eq!(ages, vec![5, 5, 2], "Ages should be counted from the submission block");
+    assert_eq!(mempool_1.age_percentiles, MempoolAgePercentiles { p50: 5, p90: 5, p99: 5, max: 5 });
+
+    let mempool_2 = cl_node.get_mempool(NetworkSpec::test_chain(2)).await.expect("Failed to get mempool of chain-2");
+    assert_eq!(mempool_2.entries.len(), 1, "chain-2 should only see the CAT");
+    assert_eq!(mempool_2.entries[0].submitted_at_block, 0);
+
+    let result = cl_node.get_mempool(NetworkSpec::test_chain(3)).await;
+    assert!(matches!(result, Err(ConfirmationLayerError::ChainNotFound(_))), "Unknown chains should be rejected");
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
+
+/// Tests the nearest-rank percentiles of mempool ages
+#[test]
+fn test_mempool_age_percentiles() {
+    assert_eq!(MempoolAgePercentiles::from_ages(&[]), MempoolAgePercentiles::default());
+    let ages: Vec<u64> = (1..=100).rev().collect();
+    assert_eq!(MempoolAgePercentiles::from_ages(&ages), MempoolAgePercentiles { p50: 50, p90: 90, p99: 99, max: 100 });
+}
+
+/// Tests that the CL records when each transaction entered the mempool and was included
+#[tokio::test]
+async fn test_inclusion_times_recorded() {
+    logging::log("TEST", "\n=== Starting test_inclusion_times_recorded ===");
+    let (_sender_hs_to_cl, receiver_hs_to_cl) = mpsc::channel(100);
+    let cl_node = ConfirmationLayerNode::new_with_block_interval(receiver_hs_to_cl, std::time::Duration::from_millis(100))
+        .expect("Failed to create CL node");
+    let cl_node = std::sync::Arc::new(tokio::sync::Mutex::new(cl_node));
+    let (sender_1, mut receiver_1) = mpsc::channel(10);
+    let (sender_2, _receiver_2) = mpsc::channel(10);
+    cl_node.lock().await.register_chain(NetworkSpec::test_chain(1), sender_1).await.expect("Failed to register chain-1");
+    cl_node.lock().await.register_chain(NetworkSpec::test_chain(2), sender_2).await.expect("Failed to register chain-2");
+    ConfirmationLayerNode::start(cl_node.clone()).await;
+
+    let cat = cl_transaction("cl-tx_cat", &[NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)]);
+    assert!(cl_node.lock().await.get_inclusion_time(&cat.transactions[0].id).await.is_none());
+    cl_node.lock().await.submit_transaction(cat.clone()).await.unwrap();
+
+    // Wait for the subblock of chain-1 that includes the CAT
+    let subblock = loop {
+        let subblock = tokio::time::timeout(std::time::Duration::from_secs(1), receiver_1.recv()).await
+            .expect("Timed out waiting for a subblock")
+            .expect("Channel closed");
+        if !subblock.transactions.is_empty() {
+            break subblock;
+        }
+    };
+
+    for tx in &cat.transactions {
+        let inclusion = cl_node.lock().await.get_inclusion_time(&tx.id).await.expect("The transaction should have an inclusion time");
+        assert!(inclusion.included_at >= inclusion.submitted_at);
+        assert_eq!(inclusion.block_height, subblock.block_height);
+    }
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
+
+/// Tests that the mempool ages and the inclusion times are read from the clock of the CL:
+/// - The age of a pending transaction is the virtual time since its submission
+/// - Real time that passes while the simulated clock stands still does not count
+/// - The inclusion time is the clock time of the block that included the transaction
+#[tokio::test]
+async fn test_mempool_times_on_simulated_clock() {
+    logging::log("TEST", "\n=== Starting test_mempool_times_on_simulated_clock ===");
+    let clock = std::sync::Arc::new(SimulatedClock::new());
+    let (_sender_hs_to_cl, receiver_hs_to_cl) = mpsc::channel(100);
+    let mut cl_node = ConfirmationLayerNode::new_with_block_interval(receiver_hs_to_cl, Duration::from_millis(100))
+        .expect("Failed to create CL node");
+    cl_node.set_clock(clock.clone());
+    let (sender_1, mut receiver_1) = mpsc::channel(10);
+    cl_node.register_chain(NetworkSpec::test_chain(1), sender_1).await.expect("Failed to register chain-1");
+
+    let cl_tx = cl_transaction("cl-tx_1", &[NetworkSpec::test_chain(1)]);
+    cl_node.submit_transaction(cl_tx.clone()).await.unwrap();
+    clock.advance(Duration::from_millis(250));
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let mempool = cl_node.get_mempool(NetworkSpec::test_chain(1)).await.expect("Failed to get mempool of chain-1");
+    assert_eq!(mempool.entries[0].age, Duration::from_millis(250), "The age should only count virtual time");
+
+    // Advance the clock block by block until the transaction is included
+    let cl_node = std::sync::Arc::new(tokio::sync::Mutex::new(cl_node));
+    ConfirmationLayerNode::start(cl_node.clone()).await;
+    let subblock = loop {
+        match tokio::time::timeout(Duration::from_millis(100), receiver_1.recv()).await {
+            Ok(Some(subblock)) if !subblock.transactions.is_empty() => break subblock,
+            Ok(Some(_)) => {}
+            Ok(None) => panic!("Channel closed"),
+            Err(_) => clock.advance(Duration::from_millis(100)),
+        }
+    };
+
+    let inclusion = cl_node.lock().await.get_inclusion_time(&cl_tx.transactions[0].id).await
+        .expect("The transaction should have an inclusion time");
+    assert_eq!(inclusion.submitted_at, Duration::ZERO, "The transaction was submitted at the start of the clock");
+    assert!(inclusion.included_at >= Duration::from_millis(250));
+    assert!(inclusion.included_at <= clock.now());
+    assert_eq!(inclusion.block_height, subblock.block_height);
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
@@ -1,3 +1,17 @@
 #[cfg(test)]
 mod basic;
 mod shutdown;
+mod pause;
+mod chain_events;
+mod mempool;
+mod ordering;
+mod cat_alignment;
+mod clock;
+mod chain_stats;
+mod duplicates;
+mod malformed;
+mod block_capacity;
+mod transaction_size;
+mod subblock_range;
+mod jitter;
+mod remote;
@@ -0,0 +1,87 @@
+use tokio::time::Duration;
+use crate::{
+    types::{TransactionId, CLTransaction, Transaction, NetworkSpec, CLTransactionId, ChainId, SubBlock},
+    confirmation_layer::{BlockOrdering, ConfirmationLayer, node::ConfirmationLayerNode},
+};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tokio::sync::mpsc;
+use hyperplane::utils::logging;
+
+/// Helper function to create a CL transaction with one transaction per chain
+fn cl_transaction(id: &str, chains: &[ChainId]) -> CLTransaction {
+    let cl_id = CLTransactionId(id.to_string());
+    let data = if chains.len() > 1 { "CAT.credit 1 100" } else { "REGULAR.credit 1 100" };
+    let transactions = chains.iter().map(|chain_id| Transaction::new(
+        TransactionId(format!("{:?}:{}", cl_id, chain_id.0)),
+        chain_id.clone(),
+        chains.to_vec(),
+        data.to_string(),
+        cl_id.clone(),
+    ).expect("Failed to create transaction")).collect();
+    CLTransaction::new(cl_id, chains.to_vec(), transactions).expect("Failed to create CL transaction")
+}
+
+/// Helper function that submits the transactions before block production starts and returns the
+/// CL ids of the first chain-1 subblock, in execution order
+async fn first_block_order(ordering: BlockOrdering, transactions: Vec<CLTransaction>) -> Vec<String> {
+    let (_sender_hs_to_cl, receiver_hs_to_cl) = mpsc::channel(100);
+    let cl_node = ConfirmationLayerNode::new_with_block_interval(receiver_hs_to_cl, Duration::from_millis(50))
+        .expect("Failed to create CL node");
+    let cl_node = Arc::new(Mutex::new(cl_node));
+    let (sender_1, mut receiver_1) = mpsc::channel::<SubBlock>(10);
+    let (sender_2, _receiver_2) = mpsc::channel::<SubBlock>(10);
+    cl_node.lock().await.register_chain(NetworkSpec::test_chain(1), sender_1).await.expect("Failed to register chain-1");
+    cl_node.lock().await.register_chain(NetworkSpec::test_chain(2), sender_2).await.expect("Failed to register chain-2");
+    cl_node.lock().await.set_block_ordering(ordering).await;
+    for transaction in transactions {
+        cl_node.lock().await.submit_transaction(transaction).await.expect("Failed to submit transaction");
+    }
+
+    ConfirmationLayerNode::start(cl_node.clone()).await;
+    let subblock = tokio::time::timeout(Duration::from_secs(1), receiver_1.recv()).await
+        .expect("Timed out waiting for a subblock")
+        .expect("Channel closed");
+    subblock.transactions.iter().map(|tx| tx.cl_id.0.clone()).collect()
+}
+
+/// Tests that the CAT-first ordering includes CATs before regular transactions, each in order of arrival
+#[tokio::test]
+async fn test_cat_first_ordering() {
+    logging::log("TEST", "\n=== Starting test_cat_first_ordering ===");
+    let chain_1 = [NetworkSpec::test_chain(1)];
+    let both = [NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)];
+    let transactions = vec![
+        cl_transaction("regular-1", &chain_1),
+        cl_transaction("cat-1", &both),
+        cl_transaction("regular-2", &chain_1),
+        cl_transaction("cat-2", &both),
+    ];
+
+    let fifo = first_block_order(BlockOrdering::Fifo, transactions.clone()).await;
+    assert_eq!(fifo, vec!["regular-1", "cat-1", "regular-2", "cat-2"], "FIFO should keep the order of arrival");
+
+    let cat_first = first_block_order(BlockOrdering::CatFirst, transactions).await;
+    assert_eq!(cat_first, vec!["cat-1", "cat-2", "regular-1", "regular-2"], "CATs should be included first");
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
+
+/// Tests that the random ordering is reproducible for the same seed
+#[tokio::test]
+async fn test_random_ordering_is_seeded() {
+    logging::log("TEST", "\n=== Starting test_random_ordering_is_seeded ===");
+    let transactions: Vec<_> = (0..20).map(|i| cl_transaction(&format!("tx-{}", i), &[NetworkSpec::test_chain(1)])).collect();
+
+    let first = first_block_order(BlockOrdering::Random { seed: 7 }, transactions.clone()).await;
+    let second = first_block_order(BlockOrdering::Random { seed: 7 }, transactions.clone()).await;
+    assert_eq!(first, second, "The same seed should produce the same order");
+
+    let mut sorted = first.clone();
+    sorted.sort();
+    let mut expected: Vec<_> = transactions.iter().map(|tx| tx.id.0.clone()).collect();
+    expected.sort();
+    assert_eq!(sorted, expected, "All transactions should be included exactly once");
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
@@ -0,0 +1,133 @@
+use tokio::time::{Duration, sleep};
+use crate::{
+    types::{TransactionId, CLTransaction, Transaction, NetworkSpec, CLTransactionId},
+    confirmation_layer::{ConfirmationLayer, ConfirmationLayerError, node::ConfirmationLayerNode},
+};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tokio::sync::mpsc;
+use hyperplane::utils::logging;
+
+/// Helper function to set up a started CL node with chain-1 registered
+async fn setup_cl_node(block_interval: Duration) -> (Arc<Mutex<ConfirmationLayerNode>>, mpsc::Receiver<crate::types::SubBlock>) {
+    let (_sender_hs_to_cl, receiver_hs_to_cl) = mpsc::channel(100);
+    let cl_node = ConfirmationLayerNode::new_with_block_interval(receiver_hs_to_cl, block_interval)
+        .expect("Failed to create CL node");
+    let cl_node = Arc::new(Mutex::new(cl_node));
+    let (sender_1, receiver_1) = mpsc::channel(100);
+    cl_node.lock().await.register_chain(NetworkSpec::test_chain(1), sender_1).await.expect("Failed to register chain-1");
+    ConfirmationLayerNode::start(cl_node.clone()).await;
+    (cl_node, receiver_1)
+}
+
+/// Helper function to create a regular CL transaction on chain-1
+fn regular_cl_transaction(id: &str) -> CLTransaction {
+    let cl_id = CLTransactionId(id.to_string());
+    let tx = Transaction::new(
+        TransactionId(format!("{:?}:regular-tx", cl_id)),
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1)],
+        "REGULAR.credit 1 100".to_string(),
+        cl_id.clone(),
+    ).expect("Failed to create transaction");
+    CLTransaction::new(cl_id, vec![NetworkSpec::test_chain(1)], vec![tx]).expect("Failed to create CL transaction")
+}
+
+/// Tests pausing and resuming block production:
+/// - No blocks are produced while paused
+/// - Submissions are accepted into the mempool while paused
+/// - After resuming, the accumulated transactions are included in the next block
+#[tokio::test]
+async fn test_pause_and_resume() {
+    logging::log("TEST", "\n=== Starting test_pause_and_resume ===");
+    let (cl_node, mut receiver_1) = setup_cl_node(Duration::from_millis(100)).await;
+    sleep(Duration::from_millis(250)).await;
+
+    // Pause and verify that the block height stops advancing
+    logging::log("TEST", "  Pausing block production...");
+    cl_node.lock().await.pause().await.expect("Failed to pause");
+    assert!(cl_node.lock().await.is_paused().await.unwrap(), "CL should be paused");
+    // Let a block that was already in production complete
+    sleep(Duration::from_millis(50)).await;
+    let paused_height = cl_node.lock().await.get_current_block().await.unwrap();
+    while receiver_1.try_recv().is_ok() {}
+
+    // Submit while paused
+    for i in 0..3 {
+        cl_node.lock().await.submit_transaction(regular_cl_transaction(&format!("cl-tx_{}", i))).await
+            .expect("Submission should be accepted while paused");
+    }
+    sleep(Duration::from_millis(350)).await;
+    assert_eq!(cl_node.lock().await.get_current_block().await.unwrap(), paused_height, "No blocks should be produced while paused");
+    assert!(receiver_1.try_recv().is_err(), "No subblocks should be sent while paused");
+    assert_eq!(cl_node.lock().await.get_pending_transactions().await.unwrap(), 3, "Submissions should wait in the mempool");
+    logging::log("TEST", "  Block production paused, submissions queued");
+
+    // Resume and verify the queued transactions are included in the next block
+    logging::log("TEST", "  Resuming block production...");
+    cl_node.lock().await.resume().await.expect("Failed to resume");
+    assert!(!cl_node.lock().await.is_paused().await.unwrap(), "CL should not be paused");
+    let subblock = tokio::time::timeout(Duration::from_secs(1), receiver_1.recv()).await
+        .expect("Timed out waiting for a subblock")
+        .expect("Channel closed");
+    assert_eq!(subblock.block_height, paused_height + 1, "Block production should continue at the paused height");
+    assert_eq!(subblock.transactions.len(), 3, "The queued transactions should be included after resuming");
+    assert_eq!(cl_node.lock().await.get_pending_transactions().await.unwrap(), 0, "The mempool should be drained");
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
+
+/// Tests that pausing and resuming are idempotent and that shutdown clears the paused flag
+#[tokio::test]
+async fn test_pause_idempotent_and_reset_on_shutdown() {
+    logging::log("TEST", "\n=== Starting test_pause_idempotent_and_reset_on_shutdown ===");
+    let (cl_node, _receiver_1) = setup_cl_node(Duration::from_millis(100)).await;
+
+    cl_node.lock().await.resume().await.expect("Resuming a running CL should succeed");
+    cl_node.lock().await.pause().await.expect("Failed to pause");
+    cl_node.lock().await.pause().await.expect("Pausing twice should succeed");
+    assert!(cl_node.lock().await.is_paused().await.unwrap(), "CL should be paused");
+
+    ConfirmationLayerNode::shutdown(cl_node.clone()).await;
+    assert!(!cl_node.lock().await.is_paused().await.unwrap(), "Shutdown should clear the paused flag");
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
+
+/// Tests single-stepping block production:
+/// - Stepping is rejected while block production is running
+/// - Each step while paused produces exactly one block including the queued transactions
+/// - Block production stays paused after a step
+#[tokio::test]
+async fn test_step_while_paused() {
+    logging::log("TEST", "\n=== Starting test_step_while_paused ===");
+    let (cl_node, mut receiver_1) = setup_cl_node(Duration::from_millis(100)).await;
+
+    let result = cl_node.lock().await.step().await;
+    assert!(matches!(result, Err(ConfirmationLayerError::NotPaused)), "Stepping should require a paused CL");
+
+    cl_node.lock().await.pause().await.expect("Failed to pause");
+    // Let a block that was already in production complete
+    sleep(Duration::from_millis(50)).await;
+    let paused_height = cl_node.lock().await.get_current_block().await.unwrap();
+    while receiver_1.try_recv().is_ok() {}
+
+    cl_node.lock().await.submit_transaction(regular_cl_transaction("cl-tx_step")).await
+        .expect("Submission should be accepted while paused");
+    let block_height = cl_node.lock().await.step().await.expect("Failed to step");
+    assert_eq!(block_height, paused_height + 1, "The step should produce the next block");
+
+    let subblock = tokio::time::timeout(Duration::from_secs(1), receiver_1.recv()).await
+        .expect("Timed out waiting for a subblock")
+        .expect("Channel closed");
+    assert_eq!(subblock.block_height, block_height, "The subblock should belong to the stepped block");
+    assert_eq!(subblock.transactions.len(), 1, "The queued transaction should be included in the stepped block");
+
+    // No further blocks are produced after the step
+    sleep(Duration::from_millis(350)).await;
+    assert_eq!(cl_node.lock().await.get_current_block().await.unwrap(), block_height, "Only one block should be produced per step");
+    assert!(receiver_1.try_recv().is_err(), "No further subblocks should be sent");
+    assert!(cl_node.lock().await.is_paused().await.unwrap(), "CL should stay paused after a step");
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
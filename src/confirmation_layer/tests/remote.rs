@@ -0,0 +1,189 @@
+use std::sync::Arc;
+use tokio::net::TcpListener;
+use tokio::sync::{mpsc, Mutex};
+use tokio::time::{timeout, Duration};
+use crate::{
+    types::communication::cl_to_hig::STATUS_UPDATE_CL_ID_SUFFIX,
+    types::{TransactionId, CLTransaction, Transaction, NetworkSpec, CLTransactionId, ChainRegistrationEvent, SubBlock},
+    confirmation_layer::{ConfirmationLayer, ConfirmationLayerError, RemoteConfirmationLayer, node::ConfirmationLayerNode, remote::{self, ServeOptions}},
+};
+use hyperplane::utils::logging;
+
+/// Helper function: serves a started CL node on a free local port and connects a client to it
+///
+/// # Arguments
+/// * `block_interval` - The block interval of the CL
+/// * `allow_control` - Whether the clients may make control calls
+/// * `remote_hs` - Whether the status updates of a remote HS are forwarded to the HS channel of the CL
+async fn setup_remote_cl(block_interval: Duration, allow_control: bool, remote_hs: bool) -> (Arc<Mutex<ConfirmationLayerNode>>, RemoteConfirmationLayer) {
+    let (sender_hs_to_cl, receiver_hs_to_cl) = mpsc::channel(100);
+    let cl_node = ConfirmationLayerNode::new_with_block_interval(receiver_hs_to_cl, block_interval)
+        .expect("Failed to create CL node");
+    let cl_node = Arc::new(Mutex::new(cl_node));
+    ConfirmationLayerNode::start(cl_node.clone()).await;
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.expect("Failed to bind listener");
+    let address = listener.local_addr().expect("Failed to get listener address").to_string();
+    let options = ServeOptions { allow_control, status_updates: remote_hs.then_some(sender_hs_to_cl) };
+    tokio::spawn(remote::serve(cl_node.clone(), listener, options));
+    let client = RemoteConfirmationLayer::connect(&address).await.expect("Failed to connect to the served CL");
+    (cl_node, client)
+}
+
+/// Tests a CL served over TCP through the remote client:
+/// - A chain registered through the client receives its subblocks over the connection
+/// - A submitted transaction is included and shows up in the chain stats
+/// - Errors of the served CL reach the client with their variant
+/// - Closing the registration connection deregisters the chain
+#[tokio::test]
+async fn test_remote_confirmation_layer() {
+    logging::log("TEST", "\n=== Starting test_remote_confirmation_layer ===");
+    let (cl_node, mut client) = setup_remote_cl(Duration::from_millis(50), false, false).await;
+    let mut chain_events = client.subscribe_chain_events().await.expect("Failed to subscribe to chain events");
+
+    // Register a chain through the client
+    let (sender, mut receiver) = mpsc::channel(100);
+    client.register_chain(NetworkSpec::test_chain(1), sender).await.expect("Failed to register chain-1");
+    assert_eq!(client.get_registered_chains().await.unwrap(), vec![NetworkSpec::test_chain(1)]);
+    assert_eq!(
+        timeout(Duration::from_secs(1), chain_events.recv()).await.expect("No chain event").unwrap(),
+        ChainRegistrationEvent::Registered(NetworkSpec::test_chain(1))
+    );
+
+    // Submit a transaction and wait for the subblock that includes it
+    let cl_id = CLTransactionId("cl-tx_remote".to_string());
+    let tx = Transaction::new(
+        TransactionId("cl-tx_remote:tx".to_string()),
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1)],
+        "REGULAR.credit 1 100".to_string(),
+        cl_id.clone(),
+    ).expect("Failed to create transaction");
+    let cl_tx = CLTransaction::new(cl_id, vec![NetworkSpec::test_chain(1)], vec![tx.clone()]).expect("Failed to create CL transaction");
+    client.submit_transaction(cl_tx.clone()).await.expect("Failed to submit transaction");
+    let subblock = timeout(Duration::from_secs(1), async {
+        loop {
+            let subblock = receiver.recv().await.expect("Subblock stream closed");
+            if !subblock.transactions.is_empty() {
+                return subblock;
+            }
+        }
+    }).await.expect("No subblock with the transaction");
+    assert_eq!(subblock.transactions, vec![tx]);
+    assert_eq!(client.get_subblock(NetworkSpec::test_chain(1), subblock.block_height).await.unwrap().transactions, subblock.transactions);
+    assert_eq!(client.get_chain_stats(NetworkSpec::test_chain(1)).await.unwrap().total_transactions, 1);
+    assert!(client.get_current_block().await.unwrap() >= subblock.block_height);
+
+    // Errors keep their variant
+    assert!(matches!(client.submit_transaction(cl_tx).await, Err(ConfirmationLayerError::TransactionAlreadyProcessed(_))));
+    assert!(matches!(client.get_mempool(NetworkSpec::test_chain(2)).await, Err(ConfirmationLayerError::ChainNotFound(_))));
+
+    // Dropping the receiver closes the registration, which deregisters the chain
+    drop(receiver);
+    timeout(Duration::from_secs(1), async {
+        while !cl_node.lock().await.get_registered_chains().await.unwrap().is_empty() {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    }).await.expect("The chain should be deregistered once its connection is closed");
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
+
+/// Helper function: waits for the first subblock with transactions
+async fn next_nonempty_subblock(receiver: &mut mpsc::Receiver<SubBlock>) -> SubBlock {
+    timeout(Duration::from_secs(1), async {
+        loop {
+            let subblock = receiver.recv().await.expect("Subblock stream closed");
+            if !subblock.transactions.is_empty() {
+                return subblock;
+            }
+        }
+    }).await.expect("No subblock with transactions")
+}
+
+/// Helper function: creates the status update the HS submits for a CAT on chain-1
+fn status_update(cat_id: &str) -> CLTransaction {
+    let cl_id = CLTransactionId(format!("{}{}", cat_id, STATUS_UPDATE_CL_ID_SUFFIX));
+    let tx = Transaction::new(
+        TransactionId(format!("{}.{}", cl_id.0, NetworkSpec::test_chain(1).0)),
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1)],
+        format!("STATUS_UPDATE:Success.CAT_ID:{}", cat_id),
+        cl_id.clone(),
+    ).expect("Failed to create transaction");
+    CLTransaction::new(cl_id, vec![NetworkSpec::test_chain(1)], vec![tx]).expect("Failed to create CL transaction")
+}
+
+/// Tests that a served CL refuses what its options do not allow:
+/// - Control calls are refused by default, while reading the CL still works
+/// - Status updates of a remote HS are refused without an HS channel
+/// - Serving on an address that is not a loopback address fails
+#[tokio::test]
+async fn test_remote_calls_refused_by_default() {
+    logging::log("TEST", "\n=== Starting test_remote_calls_refused_by_default ===");
+    let (cl_node, mut client) = setup_remote_cl(Duration::from_millis(50), false, false).await;
+    let (sender, _receiver) = mpsc::channel(100);
+    client.register_chain(NetworkSpec::test_chain(1), sender).await.expect("Failed to register chain-1");
+
+    // Control calls are refused and leave the CL unchanged
+    assert!(matches!(client.pause().await, Err(ConfirmationLayerError::RemoteCallNotAllowed(_))));
+    assert!(matches!(client.step().await, Err(ConfirmationLayerError::RemoteCallNotAllowed(_))));
+    assert!(matches!(client.set_block_interval(Duration::from_millis(10)).await, Err(ConfirmationLayerError::RemoteCallNotAllowed(_))));
+    assert!(matches!(client.deregister_chain(NetworkSpec::test_chain(1)).await, Err(ConfirmationLayerError::RemoteCallNotAllowed(_))));
+    assert!(!client.is_paused().await.unwrap());
+    assert_eq!(client.get_block_interval().await.unwrap(), Duration::from_millis(50));
+    assert_eq!(cl_node.lock().await.get_registered_chains().await.unwrap(), vec![NetworkSpec::test_chain(1)]);
+
+    // Status updates are refused without an HS channel
+    assert!(matches!(client.submit_status_update(status_update("remote-cat")).await, Err(ConfirmationLayerError::RemoteCallNotAllowed(_))));
+
+    // The connections are not encrypted, so the CL is not served on other addresses
+    let listener = TcpListener::bind("0.0.0.0:0").await.expect("Failed to bind listener");
+    let result = timeout(Duration::from_secs(1), remote::serve(cl_node.clone(), listener, ServeOptions::default())).await
+        .expect("Serving on a non-loopback address should fail immediately");
+    assert_eq!(result.unwrap_err().kind(), std::io::ErrorKind::InvalidInput);
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
+
+/// Tests the calls a served CL's options enable:
+/// - Control calls reach the CL once allowed
+/// - A remote HS submits its status updates through the status update channel and the CL includes them
+/// - Transactions that are not status updates are refused on that path, and status updates are still refused as regular submissions
+#[tokio::test]
+async fn test_remote_control_and_status_updates() {
+    logging::log("TEST", "\n=== Starting test_remote_control_and_status_updates ===");
+    let (_cl_node, mut client) = setup_remote_cl(Duration::from_millis(50), true, true).await;
+    let (sender, mut receiver) = mpsc::channel(100);
+    client.register_chain(NetworkSpec::test_chain(1), sender).await.expect("Failed to register chain-1");
+
+    // Control calls are allowed
+    client.set_block_interval(Duration::from_millis(40)).await.expect("Failed to set the block interval");
+    assert_eq!(client.get_block_interval().await.unwrap(), Duration::from_millis(40));
+    client.pause().await.expect("Failed to pause");
+    assert!(client.is_paused().await.unwrap());
+    client.resume().await.expect("Failed to resume");
+
+    // A status update submitted as a regular transaction is still refused
+    assert!(matches!(client.submit_transaction(status_update("remote-cat")).await, Err(ConfirmationLayerError::StatusUpdateNotAllowed(_))));
+
+    // Only status updates are accepted from the remote HS
+    let cl_id = CLTransactionId("cl-tx_not_an_update".to_string());
+    let tx = Transaction::new(
+        TransactionId("cl-tx_not_an_update:tx".to_string()),
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1)],
+        "REGULAR.credit 1 100".to_string(),
+        cl_id.clone(),
+    ).expect("Failed to create transaction");
+    let cl_tx = CLTransaction::new(cl_id, vec![NetworkSpec::test_chain(1)], vec![tx]).expect("Failed to create CL transaction");
+    assert!(matches!(client.submit_status_update(cl_tx).await, Err(ConfirmationLayerError::MalformedTransaction(_, _))));
+
+    // The status update of the remote HS is included in the subblock of chain-1
+    let update = status_update("remote-cat");
+    client.status_update_sender().send(update.clone()).await.expect("Failed to send the status update");
+    let subblock = next_nonempty_subblock(&mut receiver).await;
+    assert_eq!(subblock.transactions, update.transactions);
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
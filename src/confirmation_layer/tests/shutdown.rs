@@ -2,7 +2,7 @@ use std::sync::Arc;
 use tokio::sync::Mutex;
 use tokio::sync::mpsc;
 use hyperplane::confirmation_layer::node::ConfirmationLayerNode;
-use hyperplane::types::{CLTransaction, CLTransactionId, Transaction, TransactionId, constants};
+use hyperplane::types::{CLTransaction, CLTransactionId, Transaction, TransactionId, NetworkSpec};
 use hyperplane::utils::logging;
 
 /// Tests that the CL node shutdown functionality works correctly:
@@ -31,27 +31,27 @@ async fn test_cl_node_shutdown() {
         let node_guard = node.lock().await;
         let mut state = node_guard.state.lock().await;
         
-        // Add some test data using constants
+        // Add some test data on the test chains
         state.pending_transactions.push(CLTransaction {
             id: CLTransactionId("test_cl_1".to_string()),
             transactions: vec![Transaction::new(
                 TransactionId("tx1".to_string()),
-                constants::chain_1(),
-                vec![constants::chain_1()],
+                NetworkSpec::test_chain(1),
+                vec![NetworkSpec::test_chain(1)],
                 "REGULAR.credit 1 100".to_string(),
                 CLTransactionId("test_cl_1".to_string())
             ).expect("Valid transaction")],
-            constituent_chains: vec![constants::chain_1()],
+            constituent_chains: vec![NetworkSpec::test_chain(1)],
         });
         state.current_block_height = 10;
-        state.registered_chains.push(constants::chain_1());
-        state.registered_chains.push(constants::chain_2());
+        state.registered_chains.push(NetworkSpec::test_chain(1));
+        state.registered_chains.push(NetworkSpec::test_chain(2));
         
         // Verify state exists
         assert!(!state.pending_transactions.is_empty());
         assert_eq!(state.current_block_height, 10);
-        assert!(state.registered_chains.contains(&constants::chain_1()));
-        assert!(state.registered_chains.contains(&constants::chain_2()));
+        assert!(state.registered_chains.contains(&NetworkSpec::test_chain(1)));
+        assert!(state.registered_chains.contains(&NetworkSpec::test_chain(2)));
         logging::log("TEST", "✓ Test state added and verified");
     }
     
@@ -104,14 +104,14 @@ async fn test_cl_node_shutdown_multiple_times() {
             id: CLTransactionId("test_cl_1".to_string()),
             transactions: vec![Transaction::new(
                 TransactionId("tx1".to_string()),
-                constants::chain_1(),
-                vec![constants::chain_1()],
+                NetworkSpec::test_chain(1),
+                vec![NetworkSpec::test_chain(1)],
                 "REGULAR.credit 1 100".to_string(),
                 CLTransactionId("test_cl_1".to_string())
             ).expect("Valid transaction")],
-            constituent_chains: vec![constants::chain_1()],
+            constituent_chains: vec![NetworkSpec::test_chain(1)],
         });
-        state.registered_chains.push(constants::chain_1());
+        state.registered_chains.push(NetworkSpec::test_chain(1));
         logging::log("TEST", "✓ Test state added");
     }
     
@@ -165,14 +165,14 @@ async fn test_cl_node_restart_after_shutdown() {
             id: CLTransactionId("test_cl_1".to_string()),
             transactions: vec![Transaction::new(
                 TransactionId("tx1".to_string()),
-                constants::chain_1(),
-                vec![constants::chain_1()],
+                NetworkSpec::test_chain(1),
+                vec![NetworkSpec::test_chain(1)],
                 "REGULAR.credit 1 100".to_string(),
                 CLTransactionId("test_cl_1".to_string())
             ).expect("Valid transaction")],
-            constituent_chains: vec![constants::chain_1()],
+            constituent_chains: vec![NetworkSpec::test_chain(1)],
         });
-        state.registered_chains.push(constants::chain_1());
+        state.registered_chains.push(NetworkSpec::test_chain(1));
         logging::log("TEST", "✓ Initial state added");
     }
     
@@ -203,14 +203,14 @@ async fn test_cl_node_restart_after_shutdown() {
             id: CLTransactionId("test_cl_2".to_string()),
             transactions: vec![Transaction::new(
                 TransactionId("tx2".to_string()),
-                constants::chain_2(),
-                vec![constants::chain_2()],
+                NetworkSpec::test_chain(2),
+                vec![NetworkSpec::test_chain(2)],
                 "REGULAR.credit 1 100".to_string(),
                 CLTransactionId("test_cl_2".to_string())
             ).expect("Valid transaction")],
-            constituent_chains: vec![constants::chain_2()],
+            constituent_chains: vec![NetworkSpec::test_chain(2)],
         });
-        state.registered_chains.push(constants::chain_2());
+        state.registered_chains.push(NetworkSpec::test_chain(2));
         logging::log("TEST", "✓ New state added after restart");
     }
     
@@ -224,13 +224,13 @@ async fn test_cl_node_restart_after_shutdown() {
         assert!(!state.pending_transactions.is_empty());
         let has_test2 = state.pending_transactions.iter().any(|tx| tx.id.0 == "test_cl_2");
         assert!(has_test2);
-        assert!(state.registered_chains.contains(&constants::chain_2()));
+        assert!(state.registered_chains.contains(&NetworkSpec::test_chain(2)));
         logging::log("TEST", "✓ New state exists");
         
         // Verify old state doesn't persist
         let has_test1 = state.pending_transactions.iter().any(|tx| tx.id.0 == "test_cl_1");
         assert!(!has_test1);
-        assert!(!state.registered_chains.contains(&constants::chain_1()));
+        assert!(!state.registered_chains.contains(&NetworkSpec::test_chain(1)));
         logging::log("TEST", "✓ Old state doesn't persist");
     }
     
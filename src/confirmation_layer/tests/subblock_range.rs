@@ -0,0 +1,102 @@
+use tokio::time::{Duration, sleep};
+use crate::{
+    types::{TransactionId, CLTransaction, Transaction, NetworkSpec, CLTransactionId, ChainId, SubBlock},
+    confirmation_layer::{ConfirmationLayer, ConfirmationLayerError, node::{ConfirmationLayerNode, SUBBLOCK_STREAM_BATCH}},
+};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tokio::sync::mpsc;
+use hyperplane::utils::logging;
+
+/// Helper function to set up a started CL node with chain-1 registered
+async fn setup_cl_node(block_interval: Duration) -> (Arc<Mutex<ConfirmationLayerNode>>, mpsc::Receiver<SubBlock>) {
+    let (_sender_hs_to_cl, receiver_hs_to_cl) = mpsc::channel(100);
+    let cl_node = ConfirmationLayerNode::new_with_block_interval(receiver_hs_to_cl, block_interval)
+        .expect("Failed to create CL node");
+    let cl_node = Arc::new(Mutex::new(cl_node));
+    let (sender_1, receiver_1) = mpsc::channel(1000);
+    cl_node.lock().await.register_chain(NetworkSpec::test_chain(1), sender_1).await.expect("Failed to register chain-1");
+    ConfirmationLayerNode::start(cl_node.clone()).await;
+    (cl_node, receiver_1)
+}
+
+/// Helper function to create a regular CL transaction on chain-1
+fn regular_cl_transaction(id: &str) -> CLTransaction {
+    let cl_id = CLTransactionId(id.to_string());
+    let tx = Transaction::new(
+        TransactionId(format!("{:?}:regular-tx", cl_id)),
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1)],
+        "REGULAR.credit 1 100".to_string(),
+        cl_id.clone(),
+    ).expect("Failed to create transaction");
+    CLTransaction::new(cl_id, vec![NetworkSpec::test_chain(1)], vec![tx]).expect("Failed to create CL transaction")
+}
+
+/// Helper function to produce blocks until the current block reaches `height`, then pause block production
+async fn produce_blocks_until(cl_node: &Arc<Mutex<ConfirmationLayerNode>>, height: u64) -> u64 {
+    while cl_node.lock().await.get_current_block().await.unwrap() < height {
+        sleep(Duration::from_millis(10)).await;
+    }
+    cl_node.lock().await.pause().await.expect("Failed to pause");
+    // Let a block that was already in production complete
+    sleep(Duration::from_millis(50)).await;
+    cl_node.lock().await.get_current_block().await.unwrap()
+}
+
+/// Tests range queries of subblocks:
+/// - Every subblock of the range matches the subblock of its height
+/// - Heights above the current block are left out
+/// - Reversed ranges and unknown chains are rejected
+#[tokio::test]
+async fn test_get_subblocks() {
+    logging::log("TEST", "\n=== Starting test_get_subblocks ===");
+    let (cl_node, _receiver_1) = setup_cl_node(Duration::from_millis(20)).await;
+    for i in 0..3 {
+        cl_node.lock().await.submit_transaction(regular_cl_transaction(&format!("cl-tx_{}", i))).await
+            .expect("Failed to submit transaction");
+    }
+    let current_block = produce_blocks_until(&cl_node, 5).await;
+
+    let subblocks = cl_node.lock().await.get_subblocks(NetworkSpec::test_chain(1), 1, current_block + 10).await
+        .expect("Failed to get subblocks");
+    assert_eq!(subblocks.len() as u64, current_block, "The range should end at the current block");
+    for (subblock, block_height) in subblocks.iter().zip(1..) {
+        let expected = cl_node.lock().await.get_subblock(NetworkSpec::test_chain(1), block_height).await.unwrap();
+        assert_eq!(subblock, &expected, "Subblock {} should match the single query", block_height);
+    }
+    assert_eq!(subblocks.iter().map(|subblock| subblock.transactions.len()).sum::<usize>(), 3, "All submitted transactions should be in the range");
+
+    let result = cl_node.lock().await.get_subblocks(NetworkSpec::test_chain(1), 4, 2).await;
+    assert!(matches!(result, Err(ConfirmationLayerError::InvalidBlockRange(4, 2))), "Reversed ranges should be rejected");
+    let result = cl_node.lock().await.get_subblocks(ChainId("chain-unknown".to_string()), 1, 2).await;
+    assert!(matches!(result, Err(ConfirmationLayerError::ChainNotFound(_))), "Unknown chains should be rejected");
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
+
+/// Tests streaming subblocks over more than one batch:
+/// - The stream yields the same subblocks as the range query, in order
+/// - The channel closes after the last subblock
+#[tokio::test]
+async fn test_stream_subblocks() {
+    logging::log("TEST", "\n=== Starting test_stream_subblocks ===");
+    let (cl_node, _receiver_1) = setup_cl_node(Duration::from_millis(5)).await;
+    cl_node.lock().await.submit_transaction(regular_cl_transaction("cl-tx_0")).await
+        .expect("Failed to submit transaction");
+    let current_block = produce_blocks_until(&cl_node, SUBBLOCK_STREAM_BATCH + 10).await;
+
+    let mut receiver = cl_node.lock().await.stream_subblocks(NetworkSpec::test_chain(1), 1, current_block).await
+        .expect("Failed to stream subblocks");
+    let mut streamed = Vec::new();
+    while let Some(subblock) = tokio::time::timeout(Duration::from_secs(1), receiver.recv()).await
+        .expect("Timed out waiting for the stream") {
+        streamed.push(subblock);
+    }
+
+    let expected = cl_node.lock().await.get_subblocks(NetworkSpec::test_chain(1), 1, current_block).await.unwrap();
+    assert_eq!(streamed.len() as u64, current_block, "Every block of the range should be streamed");
+    assert_eq!(streamed, expected, "The stream should match the range query");
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
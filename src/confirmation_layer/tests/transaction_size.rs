@@ -0,0 +1,104 @@
+use std::sync::Arc;
+use tokio::sync::{mpsc, Mutex};
+use tokio::time::{sleep, Duration};
+use crate::{
+    types::{TransactionId, CLTransaction, Transaction, NetworkSpec, CLTransactionId, SimulatedClock},
+    confirmation_layer::{ConfirmationLayer, ConfirmationLayerError, node::ConfirmationLayerNode},
+};
+use hyperplane::utils::logging;
+
+/// Helper function to create a regular CL transaction for chain-1 with the given amount
+fn regular_transaction(id: &str, amount: u64) -> CLTransaction {
+    let cl_id = CLTransactionId(id.to_string());
+    let tx = Transaction::new(
+        TransactionId(format!("{:?}:{}", cl_id, NetworkSpec::test_chain(1).0)),
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1)],
+        format!("REGULAR.credit 1 {}", amount),
+        cl_id.clone(),
+    ).expect("Failed to create transaction");
+    CLTransaction::new(cl_id, vec![NetworkSpec::test_chain(1)], vec![tx]).expect("Failed to create CL transaction")
+}
+
+/// Helper function to set up a CL node in simulated time with chain-1 registered
+async fn setup_cl_node(clock: Arc<SimulatedClock>) -> Arc<Mutex<ConfirmationLayerNode>> {
+    let (_sender_hs_to_cl, receiver_hs_to_cl) = mpsc::channel(100);
+    let mut cl_node = ConfirmationLayerNode::new_with_block_interval(receiver_hs_to_cl, Duration::from_millis(100))
+        .expect("Failed to create CL node");
+    cl_node.set_clock(clock);
+    let cl_node = Arc::new(Mutex::new(cl_node));
+    let (sender_1, _receiver_1) = mpsc::channel(100);
+    cl_node.lock().await.register_chain(NetworkSpec::test_chain(1), sender_1).await.expect("Failed to register chain-1");
+    cl_node
+}
+
+/// Tests the maximum transaction size:
+/// - Submissions above the maximum are rejected with their size and counted
+/// - Submissions up to the maximum enter the mempool, which reports their size
+/// - Without a maximum every size is accepted
+#[tokio::test]
+async fn test_oversized_transactions_rejected() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_oversized_transactions_rejected ===");
+    let cl_node = setup_cl_node(Arc::new(SimulatedClock::new())).await;
+
+    let small = regular_transaction("cl-tx_small", 1);
+    let large = regular_transaction("cl-tx_large", 1_000_000);
+    assert!(large.serialized_size() > small.serialized_size());
+    cl_node.lock().await.set_max_transaction_size(small.serialized_size()).await;
+    assert_eq!(cl_node.lock().await.get_max_transaction_size().await, small.serialized_size());
+
+    let result = cl_node.lock().await.submit_transaction(large.clone()).await;
+    match result {
+        Err(ConfirmationLayerError::TransactionTooLarge(id, size, max)) => {
+            assert_eq!(id, large.id);
+            assert_eq!(size, large.serialized_size());
+            assert_eq!(max, small.serialized_size());
+        }
+        other => panic!("The large transaction should be rejected as too large, got {:?}", other),
+    }
+    cl_node.lock().await.submit_transaction(small.clone()).await.expect("A transaction of the maximum size should be accepted");
+    assert_eq!(cl_node.lock().await.get_oversized_submission_count().await, 1);
+
+    let mempool = cl_node.get_mempool(NetworkSpec::test_chain(1)).await.unwrap();
+    assert_eq!(mempool.entries.len(), 1, "Only the small transaction should be pending");
+    assert_eq!(mempool.entries[0].serialized_size, small.serialized_size());
+
+    cl_node.lock().await.set_max_transaction_size(0).await;
+    cl_node.lock().await.submit_transaction(large).await.expect("Without a maximum every size should be accepted");
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
+
+/// Tests the byte throughput accounting:
+/// - Every block records the serialized size of its CL transactions
+/// - The chain stats record the serialized size of the transactions included per block
+#[tokio::test]
+async fn test_block_byte_throughput() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_block_byte_throughput ===");
+    let clock = Arc::new(SimulatedClock::new());
+    let cl_node = setup_cl_node(clock.clone()).await;
+
+    let transactions: Vec<CLTransaction> = (0..3).map(|i| regular_transaction(&format!("cl-tx_{}", i), 100)).collect();
+    for cl_tx in &transactions {
+        cl_node.lock().await.submit_transaction(cl_tx.clone()).await.unwrap();
+    }
+    ConfirmationLayerNode::start(cl_node.clone()).await;
+    sleep(Duration::from_millis(200)).await;
+    clock.advance(Duration::from_millis(100));
+    sleep(Duration::from_millis(200)).await;
+
+    let expected_bytes: u64 = transactions.iter().map(CLTransaction::serialized_size).sum();
+    let utilization = cl_node.lock().await.get_block_utilization().await;
+    assert_eq!(utilization.iter().map(|block| block.serialized_bytes).collect::<Vec<_>>(), vec![expected_bytes, 0]);
+    assert_eq!(utilization[0].bytes_per_second(Duration::from_secs(2)), expected_bytes as f64 / 2.0);
+
+    let stats = cl_node.get_chain_stats(NetworkSpec::test_chain(1)).await.unwrap();
+    let expected_chain_bytes: u64 = transactions.iter().flat_map(|cl_tx| &cl_tx.transactions).map(Transaction::serialized_size).sum();
+    assert_eq!(stats.bytes_per_block, vec![(1, expected_chain_bytes), (2, 0)]);
+    assert_eq!(stats.total_bytes, expected_chain_bytes);
+    assert_eq!(stats.mean_bytes_per_block(), expected_chain_bytes as f64 / 2.0);
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
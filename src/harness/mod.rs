@@ -0,0 +1,455 @@
+//! Shared wiring of CL, HS and HIG nodes.
+//!
+//! The integration tests, the simulator and the shell all build their node stacks through this
+//! module, so the channels between the nodes are set up in one place.
+
+use std::fmt;
+use std::future::Future;
+use std::sync::Arc;
+use serde::Serialize;
+use thiserror::Error;
+use tokio::runtime::Handle;
+use tokio::sync::{mpsc, Mutex};
+use tokio::time::Duration;
+use crate::{
+    confirmation_layer::{node::ConfirmationLayerNode, ConfirmationLayer, ConfirmationLayerError, ConfirmationLayerHealth},
+    hyper_ig::{node::HyperIGNode, HyperIGHealth},
+    hyper_scheduler::{node::HyperSchedulerNode, HyperSchedulerError, HyperSchedulerHealth},
+    types::{CATStatusUpdate, ChainId, ChannelHealth, CLTransaction, Clock, NetworkSpec, SubBlock, SystemClock},
+    utils::logging,
+};
+
+/// How long collecting the health of a network waits for each node before reporting it as unresponsive
+pub const HEALTH_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Errors that can occur while wiring the nodes
+#[derive(Debug, Error)]
+pub enum HarnessError {
+    #[error("CL error: {0}")]
+    ConfirmationLayer(#[from] ConfirmationLayerError),
+    #[error("HS error: {0}")]
+    HyperScheduler(#[from] HyperSchedulerError),
+}
+
+/// Hooks to insert something between the two ends of each channel, e.g. a simulated network link.
+/// Each hook receives the receiving end of a channel and returns the receiver the node reads from.
+pub trait Links: Send + Sync {
+    /// The link from the CL to the HIG of the chain at `chain_index`
+    fn cl_to_hig(&self, _chain_index: usize, receiver: mpsc::Receiver<SubBlock>) -> mpsc::Receiver<SubBlock> {
+        receiver
+    }
+
+    /// The link from the HIG of the chain at `chain_index` to the HS
+    fn hig_to_hs(&self, _chain_index: usize, receiver: mpsc::Receiver<CATStatusUpdate>) -> mpsc::Receiver<CATStatusUpdate> {
+        receiver
+    }
+
+    /// The link from the HS to the CL
+    fn hs_to_cl(&self, receiver: mpsc::Receiver<CLTransaction>) -> mpsc::Receiver<CLTransaction> {
+        receiver
+    }
+}
+
+/// Links that connect the nodes directly
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DirectLinks;
+
+impl Links for DirectLinks {}
+
+/// Runtimes the background tasks of each kind of node are spawned on
+///
+/// A node without a runtime runs on the runtime that starts it. Separate runtimes let a caller
+/// give each component its own worker threads, e.g. pinned to different cores. HIGs restarted
+/// later through `HyperIGNode::start` run on the runtime of the caller.
+#[derive(Debug, Clone, Default)]
+pub struct ComponentRuntimes {
+    pub cl: Option<Handle>,
+    pub hs: Option<Handle>,
+    pub hig: Option<Handle>,
+}
+
+/// Runs a future to completion on the given runtime, or in place without one
+async fn run_on<F>(runtime: Option<&Handle>, future: F) -> F::Output
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    match runtime {
+        Some(runtime) => runtime.spawn(future).await.expect("Task on a component runtime panicked"),
+        None => future.await,
+    }
+}
+
+/// Settings of the HIG of a chain
+#[derive(Debug, Clone)]
+pub struct ChainOptions {
+    /// The default lifetime for CATs in blocks
+    pub cat_lifetime: u64,
+    /// Whether CATs can depend on pending transactions
+    pub allow_cat_pending_dependencies: bool,
+    /// The (account, balance) pairs preloaded into the chain's VM
+    pub account_balances: Vec<(u32, u32)>,
+    /// Buffer size of the channels between the nodes
+    pub channel_buffer_size: usize,
+}
+
+impl Default for ChainOptions {
+    fn default() -> Self {
+        Self {
+            cat_lifetime: 4,
+            allow_cat_pending_dependencies: true,
+            account_balances: Vec::new(),
+            channel_buffer_size: 100,
+        }
+    }
+}
+
+/// Creates and starts a CL and an HS connected through the HS → CL link
+/// 
+/// # Arguments
+/// * `block_interval` - The block interval of the CL
+/// * `channel_buffer_size` - Buffer size of the HS → CL channel
+/// * `clock` - The clock both nodes run on (HIGs connected later run on the clock of the CL)
+/// * `links` - The links between the nodes
+/// * `runtimes` - The runtimes the CL and the HS run on
+pub async fn start_core_nodes(
+    block_interval: Duration,
+    channel_buffer_size: usize,
+    clock: Arc<dyn Clock>,
+    links: &dyn Links,
+    runtimes: &ComponentRuntimes,
+) -> Result<(Arc<Mutex<HyperSchedulerNode>>, Arc<Mutex<ConfirmationLayerNode>>), HarnessError> {
+    let (sender_hs_to_cl, receiver_hs_to_cl) = mpsc::channel(channel_buffer_size);
+    let receiver_hs_to_cl = links.hs_to_cl(receiver_hs_to_cl);
+
+    let mut hs_node = HyperSchedulerNode::new(sender_hs_to_cl);
+    hs_node.set_clock(clock.clone());
+    let mut cl_node = ConfirmationLayerNode::new_with_block_interval(receiver_hs_to_cl, block_interval)?;
+    cl_node.set_clock(clock);
+    let hs_node = Arc::new(Mutex::new(hs_node));
+    let cl_node = Arc::new(Mutex::new(cl_node));
+
+    run_on(runtimes.hs.as_ref(), HyperSchedulerNode::start(hs_node.clone())).await;
+    run_on(runtimes.cl.as_ref(), ConfirmationLayerNode::start(cl_node.clone())).await;
+    Ok((hs_node, cl_node))
+}
+
+/// Creates the HIG of a chain, registers the chain with the CL and the HS and starts the HIG.
+/// The HIG runs on the clock of the CL.
+/// 
+/// # Arguments
+/// * `cl_node` - The CL node
+/// * `hs_node` - The HS node
+/// * `chain_id` - The chain to connect
+/// * `chain_index` - The index of the chain, passed to the links
+/// * `options` - The settings of the chain's HIG
+/// * `links` - The links between the nodes
+/// * `runtimes` - The runtimes the HIG and the HS's receive loop for the chain run on
+pub async fn connect_chain(
+    cl_node: &Arc<Mutex<ConfirmationLayerNode>>,
+    hs_node: &Arc<Mutex<HyperSchedulerNode>>,
+    chain_id: ChainId,
+    chain_index: usize,
+    options: &ChainOptions,
+    links: &dyn Links,
+    runtimes: &ComponentRuntimes,
+) -> Result<Arc<Mutex<HyperIGNode>>, HarnessError> {
+    let (sender_cl_to_hig, receiver_cl_to_hig) = mpsc::channel(options.channel_buffer_size);
+    let (sender_hig_to_hs, receiver_hig_to_hs) = mpsc::channel(options.channel_buffer_size);
+    let receiver_cl_to_hig = links.cl_to_hig(chain_index, receiver_cl_to_hig);
+    let receiver_hig_to_hs = links.hig_to_hs(chain_index, receiver_hig_to_hs);
+
+    let mut hig_node = HyperIGNode::new_with_account_balances(
+        receiver_cl_to_hig,
+        sender_hig_to_hs,
+        chain_id.clone(),
+        options.cat_lifetime,
+        options.allow_cat_pending_dependencies,
+        &options.account_balances,
+    );
+    hig_node.set_clock(cl_node.lock().await.get_clock());
+    let hig_node = Arc::new(Mutex::new(hig_node));
+
+    cl_node.lock().await.register_chain(chain_id.clone(), sender_cl_to_hig).await?;
+    // The HS spawns its receive loop when the first chain registers
+    let hs = hs_node.clone();
+    run_on(runtimes.hs.as_ref(), async move { hs.lock().await.register_chain(chain_id, receiver_hig_to_hs).await }).await?;
+    run_on(runtimes.hig.as_ref(), HyperIGNode::start(hig_node.clone())).await;
+    Ok(hig_node)
+}
+
+/// A running CL, HS and one HIG per chain
+pub struct TestNetwork {
+    /// The HS node
+    pub hs_node: Arc<Mutex<HyperSchedulerNode>>,
+    /// The CL node
+    pub cl_node: Arc<Mutex<ConfirmationLayerNode>>,
+    /// The HIG nodes, in the order of the chains
+    pub hig_nodes: Vec<Arc<Mutex<HyperIGNode>>>,
+    /// The chains, in order
+    pub chain_ids: Vec<ChainId>,
+    /// The current block at the end of the setup
+    pub start_block: u64,
+}
+
+impl TestNetwork {
+    /// Collects the health of all nodes of the network
+    pub async fn health(&self) -> NetworkHealth {
+        let hig_nodes: Vec<(ChainId, Arc<Mutex<HyperIGNode>>)> = self.chain_ids.iter().cloned().zip(self.hig_nodes.iter().cloned()).collect();
+        NetworkHealth::collect(&self.cl_node, &self.hs_node, &hig_nodes).await
+    }
+}
+
+/// Health of all nodes of a network in one view, to diagnose hangs
+/// 
+/// A node that does not answer within `HEALTH_TIMEOUT`, e.g. because a task holds its lock,
+/// is reported as unresponsive instead of blocking the whole snapshot.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct NetworkHealth {
+    /// The CL (None if it was unresponsive)
+    pub cl: Option<ConfirmationLayerHealth>,
+    /// The HS (None if it was unresponsive)
+    pub hs: Option<HyperSchedulerHealth>,
+    /// The HIGs, in the order of the chains (None for unresponsive HIGs)
+    pub higs: Vec<(ChainId, Option<HyperIGHealth>)>,
+}
+
+impl NetworkHealth {
+    /// Collects the health of the given nodes
+    /// 
+    /// # Arguments
+    /// * `cl_node` - The CL node
+    /// * `hs_node` - The HS node
+    /// * `hig_nodes` - The HIG nodes with their chains
+    pub async fn collect(
+        cl_node: &Arc<Mutex<ConfirmationLayerNode>>,
+        hs_node: &Arc<Mutex<HyperSchedulerNode>>,
+        hig_nodes: &[(ChainId, Arc<Mutex<HyperIGNode>>)],
+    ) -> Self {
+        let cl = within_health_timeout(async { cl_node.lock().await.health().await }).await;
+        let hs = within_health_timeout(async { hs_node.lock().await.health().await }).await;
+        let mut higs = Vec::with_capacity(hig_nodes.len());
+        for (chain_id, hig_node) in hig_nodes {
+            higs.push((chain_id.clone(), within_health_timeout(async { hig_node.lock().await.health().await }).await));
+        }
+        Self { cl, hs, higs }
+    }
+
+    /// Lists what looks wrong: unresponsive nodes, loops that are not running, halted HIGs and closed channels
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        match &self.cl {
+            None => problems.push(format!("CL did not respond within {:?}", HEALTH_TIMEOUT)),
+            Some(cl) => {
+                if !cl.running {
+                    problems.push("CL block production is not running".to_string());
+                }
+                if cl.hs_channel.is_some_and(|channel| !channel.open) {
+                    problems.push("CL channel from the HS is closed".to_string());
+                }
+                for (chain_id, channel) in cl.hig_channels.iter().filter(|(_, channel)| !channel.open) {
+                    problems.push(format!("CL channel to the HIG of {} is closed ({} queued)", chain_id, channel.queued));
+                }
+            }
+        }
+        match &self.hs {
+            None => problems.push(format!("HS did not respond within {:?}", HEALTH_TIMEOUT)),
+            Some(hs) => {
+                if !hs.running {
+                    problems.push("HS receive loop is not running".to_string());
+                }
+                if hs.cl_channel.is_some_and(|channel| !channel.open) {
+                    problems.push("HS channel to the CL is closed".to_string());
+                }
+            }
+        }
+        for (chain_id, hig) in &self.higs {
+            let Some(hig) = hig else {
+                problems.push(format!("HIG of {} did not respond within {:?}", chain_id, HEALTH_TIMEOUT));
+                continue;
+            };
+            if !hig.message_loop_running {
+                problems.push(format!("HIG of {}: message loop is not running", chain_id));
+            }
+            if !hig.queue_processor_running {
+                problems.push(format!("HIG of {}: proposal queue processor is not running", chain_id));
+            }
+            if hig.halted {
+                problems.push(format!("HIG of {} is halted", chain_id));
+            }
+            if hig.cl_channel.is_some_and(|channel| !channel.open) {
+                problems.push(format!("HIG of {}: channel from the CL is closed", chain_id));
+            }
+            if hig.hs_channel.is_some_and(|channel| !channel.open) {
+                problems.push(format!("HIG of {}: channel to the HS is closed", chain_id));
+            }
+        }
+        problems
+    }
+
+    /// Checks whether no problems were found
+    pub fn is_healthy(&self) -> bool {
+        self.problems().is_empty()
+    }
+}
+
+impl fmt::Display for NetworkHealth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.cl {
+            None => writeln!(f, "CL: unresponsive")?,
+            Some(cl) => {
+                writeln!(f, "CL: {}{}, block {}, {} pending, HS channel {}",
+                    running(cl.running), if cl.paused { " [PAUSED]" } else { "" }, cl.current_block_height, cl.pending_transactions, channel(cl.hs_channel))?;
+                for (chain_id, hig_channel) in &cl.hig_channels {
+                    writeln!(f, "  channel to {}: {}", chain_id, channel(Some(*hig_channel)))?;
+                }
+            }
+        }
+        match &self.hs {
+            None => writeln!(f, "HS: unresponsive")?,
+            Some(hs) => writeln!(f, "HS: {}, {} chains, {} pending CATs, {} proposals, {} decisions, CL channel {}",
+                running(hs.running), hs.registered_chains, hs.pending_cats, hs.proposals_received, hs.decisions, channel(hs.cl_channel))?,
+        }
+        for (chain_id, hig) in &self.higs {
+            match hig {
+                None => writeln!(f, "HIG {}: unresponsive", chain_id)?,
+                Some(hig) => writeln!(f, "HIG {}: messages {}, proposals {}{}, block {}, {} pending, {} queued proposals, CL channel {}, HS channel {}",
+                    chain_id, running(hig.message_loop_running), running(hig.queue_processor_running), if hig.halted { " [HALTED]" } else { "" },
+                    hig.current_block_height, hig.pending_transactions, hig.proposal_queue_depth, channel(hig.cl_channel), channel(hig.hs_channel))?,
+            }
+        }
+        let problems = self.problems();
+        if problems.is_empty() {
+            write!(f, "No problems found")
+        } else {
+            write!(f, "Problems:\n  {}", problems.join("\n  "))
+        }
+    }
+}
+
+/// Waits for the health of a node, giving up after `HEALTH_TIMEOUT`
+async fn within_health_timeout<T>(health: impl Future<Output = T>) -> Option<T> {
+    tokio::time::timeout(HEALTH_TIMEOUT, health).await.ok()
+}
+
+/// Describes whether a loop is running
+fn running(running: bool) -> &'static str {
+    if running { "running" } else { "stopped" }
+}
+
+/// Describes the state of a channel
+fn channel(channel: Option<ChannelHealth>) -> String {
+    match channel {
+        Some(channel) => format!("{} ({} queued)", if channel.open { "open" } else { "closed" }, channel.queued),
+        None => "released".to_string(),
+    }
+}
+
+/// Builder for a [`TestNetwork`]
+pub struct NetworkBuilder {
+    block_interval: Duration,
+    chain_ids: Vec<ChainId>,
+    options: ChainOptions,
+    account_balances: Vec<Vec<(u32, u32)>>,
+    links: Box<dyn Links>,
+    clock: Arc<dyn Clock>,
+    runtimes: ComponentRuntimes,
+}
+
+impl NetworkBuilder {
+    /// Creates a builder for the chains of the default `NetworkSpec` (chain-1 and chain-2),
+    /// directly linked nodes, the default chain options and the system clock
+    /// 
+    /// # Arguments
+    /// * `block_interval` - The block interval of the CL
+    pub fn new(block_interval: Duration) -> Self {
+        Self {
+            block_interval,
+            chain_ids: NetworkSpec::default().chain_ids().to_vec(),
+            options: ChainOptions::default(),
+            account_balances: Vec::new(),
+            links: Box::new(DirectLinks),
+            clock: Arc::new(SystemClock::new()),
+            runtimes: ComponentRuntimes::default(),
+        }
+    }
+
+    /// Sets the chains of the network
+    pub fn chains(mut self, chain_ids: Vec<ChainId>) -> Self {
+        self.chain_ids = chain_ids;
+        self
+    }
+
+    /// Sets the chains of the network from a network spec
+    pub fn network(self, spec: &NetworkSpec) -> Self {
+        self.chains(spec.chain_ids().to_vec())
+    }
+
+    /// Sets the settings shared by the HIGs of all chains
+    pub fn chain_options(mut self, options: ChainOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Sets the preloaded (account, balance) pairs per chain, in the order of the chains.
+    /// Chains without an entry use the balances of the chain options.
+    pub fn account_balances(mut self, account_balances: Vec<Vec<(u32, u32)>>) -> Self {
+        self.account_balances = account_balances;
+        self
+    }
+
+    /// Sets the links between the nodes
+    pub fn links(mut self, links: impl Links + 'static) -> Self {
+        self.links = Box::new(links);
+        self
+    }
+
+    /// Sets the clock all nodes run on, e.g. a `SimulatedClock` for tests in virtual time
+    pub fn clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Sets the runtimes the nodes run on (by default all nodes run on the runtime that builds the network)
+    pub fn runtimes(mut self, runtimes: ComponentRuntimes) -> Self {
+        self.runtimes = runtimes;
+        self
+    }
+
+    /// Starts the nodes, connects all chains and waits until block production is running
+    pub async fn build(self) -> Result<TestNetwork, HarnessError> {
+        let (hs_node, cl_node) = start_core_nodes(self.block_interval, self.options.channel_buffer_size, self.clock.clone(), self.links.as_ref(), &self.runtimes).await?;
+
+        let mut hig_nodes = Vec::with_capacity(self.chain_ids.len());
+        for (chain_index, chain_id) in self.chain_ids.iter().enumerate() {
+            let mut options = self.options.clone();
+            if let Some(balances) = self.account_balances.get(chain_index) {
+                options.account_balances = balances.clone();
+            }
+            hig_nodes.push(connect_chain(&cl_node, &hs_node, chain_id.clone(), chain_index, &options, self.links.as_ref(), &self.runtimes).await?);
+        }
+
+        // Wait for block production to be ready
+        let mut attempts = 0;
+        while attempts < 10 {
+            if let Ok(interval) = cl_node.lock().await.get_block_interval().await {
+                if interval == self.block_interval {
+                    break;
+                }
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            logging::log("TEST", &format!("Waiting for block production to be ready.. attempt: {}", attempts));
+            attempts += 1;
+        }
+
+        // Wait a couple of blocks to ensure the block production is ready
+        self.clock.sleep(self.block_interval * 2).await;
+        let start_block = cl_node.lock().await.get_current_block().await?;
+        logging::log("NODES SETUP", &format!("Nodes setup complete, current block: {}", start_block));
+
+        Ok(TestNetwork { hs_node, cl_node, hig_nodes, chain_ids: self.chain_ids, start_block })
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,85 @@
+use tokio::time::Duration;
+use crate::{
+    harness::{NetworkBuilder, ChainOptions, ComponentRuntimes},
+    types::NetworkSpec,
+    confirmation_layer::ConfirmationLayer,
+    hyper_scheduler::HyperScheduler,
+    hyper_ig::HyperIG,
+};
+use hyperplane::utils::logging;
+
+/// Tests that the network builder wires and starts all nodes:
+/// - Every chain is registered with the CL and the HS
+/// - Every chain gets a HIG with its preloaded balances
+/// - Block production is running at the end of the setup
+#[tokio::test]
+async fn test_network_builder_connects_chains() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_network_builder_connects_chains ===");
+
+    let chains = vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2), NetworkSpec::test_chain(3)];
+    let network = NetworkBuilder::new(Duration::from_millis(50))
+        .chains(chains.clone())
+        .chain_options(ChainOptions { account_balances: vec![(1, 100)], ..ChainOptions::default() })
+        .account_balances(vec![vec![(1, 500)]])
+        .build()
+        .await
+        .expect("Failed to build network");
+
+    assert_eq!(network.hig_nodes.len(), 3);
+    assert_eq!(network.cl_node.lock().await.get_registered_chains().await.unwrap(), chains);
+    let mut hs_chains = network.hs_node.lock().await.get_registered_chains().await.unwrap();
+    hs_chains.sort_by(|a, b| a.0.cmp(&b.0));
+    assert_eq!(hs_chains, chains);
+
+    let state_1 = network.hig_nodes[0].lock().await.get_chain_state().await.unwrap();
+    let state_2 = network.hig_nodes[1].lock().await.get_chain_state().await.unwrap();
+    assert_eq!(state_1.get("1").copied(), Some(500), "Per-chain balances override the chain options");
+    assert_eq!(state_2.get("1").copied(), Some(100), "Chains without an entry use the chain options");
+
+    assert!(network.cl_node.lock().await.get_current_block().await.unwrap() >= network.start_block);
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
+
+/// Tests that the nodes run on the runtimes they are given:
+/// - The HIGs keep processing blocks produced on the CL runtime
+/// - Block production stops once the CL runtime is shut down, while the test's runtime keeps running
+#[tokio::test]
+async fn test_network_builder_runs_nodes_on_component_runtimes() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_network_builder_runs_nodes_on_component_runtimes ===");
+
+    let runtime = |name: &str| tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(1)
+        .thread_name(name)
+        .enable_all()
+        .build()
+        .expect("Failed to build runtime");
+    let (cl_runtime, hs_runtime, hig_runtime) = (runtime("cl"), runtime("hs"), runtime("hig"));
+    let block_interval = Duration::from_millis(20);
+    let network = NetworkBuilder::new(block_interval)
+        .runtimes(ComponentRuntimes {
+            cl: Some(cl_runtime.handle().clone()),
+            hs: Some(hs_runtime.handle().clone()),
+            hig: Some(hig_runtime.handle().clone()),
+        })
+        .build()
+        .await
+        .expect("Failed to build network");
+
+    tokio::time::sleep(block_interval * 5).await;
+    let hig_block = network.hig_nodes[0].lock().await.get_current_block_height().await.unwrap();
+    assert!(hig_block > network.start_block, "The HIG should process the blocks of the CL");
+
+    // Runtimes must not be dropped inside an async context
+    cl_runtime.shutdown_background();
+    tokio::time::sleep(block_interval * 2).await;
+    let block = network.cl_node.lock().await.get_current_block().await.unwrap();
+    tokio::time::sleep(block_interval * 5).await;
+    assert_eq!(network.cl_node.lock().await.get_current_block().await.unwrap(), block, "Block production should stop with its runtime");
+
+    hs_runtime.shutdown_background();
+    hig_runtime.shutdown_background();
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
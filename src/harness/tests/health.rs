@@ -0,0 +1,74 @@
+use tokio::time::Duration;
+use crate::{
+    harness::NetworkBuilder,
+    types::NetworkSpec,
+};
+use hyperplane::utils::logging;
+
+/// Tests the health of a freshly built network:
+/// - All loops are running and all channels are open
+/// - Every HIG is reported in the order of the chains
+/// - The CL reports its block height and one channel per chain
+#[tokio::test]
+async fn test_network_health_of_running_network() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_network_health_of_running_network ===");
+
+    let chains = vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)];
+    let network = NetworkBuilder::new(Duration::from_millis(50))
+        .chains(chains.clone())
+        .build()
+        .await
+        .expect("Failed to build network");
+
+    let health = network.health().await;
+    assert!(health.is_healthy(), "Unexpected problems: {:?}", health.problems());
+
+    let cl = health.cl.as_ref().expect("The CL should respond");
+    assert!(cl.running);
+    assert!(cl.current_block_height >= network.start_block);
+    assert_eq!(cl.hig_channels.iter().map(|(chain_id, _)| chain_id.clone()).collect::<Vec<_>>(), chains);
+    assert!(cl.hig_channels.iter().all(|(_, channel)| channel.open));
+
+    let hs = health.hs.as_ref().expect("The HS should respond");
+    assert!(hs.running);
+    assert_eq!(hs.registered_chains, 2);
+
+    assert_eq!(health.higs.iter().map(|(chain_id, _)| chain_id.clone()).collect::<Vec<_>>(), chains);
+    for (_, hig) in &health.higs {
+        let hig = hig.as_ref().expect("Every HIG should respond");
+        assert!(hig.message_loop_running && hig.queue_processor_running);
+        assert!(!hig.halted);
+        assert!(hig.cl_channel.is_some_and(|channel| channel.open));
+    }
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
+
+/// Tests that the health reports what blocks the network:
+/// - A halted HIG is listed as a problem
+/// - A node whose lock is held is reported as unresponsive instead of blocking the snapshot
+#[tokio::test]
+async fn test_network_health_reports_problems() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_network_health_reports_problems ===");
+
+    let network = NetworkBuilder::new(Duration::from_millis(50))
+        .build()
+        .await
+        .expect("Failed to build network");
+
+    network.hig_nodes[1].lock().await.set_halted(true).await;
+    let health = network.health().await;
+    assert_eq!(health.problems(), vec![format!("HIG of {} is halted", NetworkSpec::test_chain(2))]);
+    assert!(health.to_string().contains("[HALTED]"));
+
+    let hs_guard = network.hs_node.lock().await;
+    let health = network.health().await;
+    drop(hs_guard);
+    assert!(health.hs.is_none(), "The HS should be unresponsive while its lock is held");
+    assert!(health.problems().iter().any(|problem| problem.starts_with("HS did not respond")));
+    assert!(health.to_string().contains("HS: unresponsive"));
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
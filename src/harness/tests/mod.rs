@@ -0,0 +1,4 @@
+#[cfg(test)]
+mod basic;
+#[cfg(test)]
+mod health;
@@ -0,0 +1,369 @@
+//! A scriptable stand-in for a HIG.
+//!
+//! `MockHyperIG` implements the `HyperIG` trait without a VM, locks or dependencies. It sends
+//! canned CAT status proposals to the HS, either scripted at fixed times after `run_script` or as
+//! the response to processed CATs, and can be told to misbehave, so HS tests and scheduler-policy
+//! sweeps do not need full HIG nodes.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use async_trait::async_trait;
+use tokio::sync::{mpsc, Mutex};
+use tokio::task::JoinHandle;
+use tokio::time::{sleep, Duration};
+use crate::types::{
+    AccountBalance, CATId, CATStatusLimited, CATStatusUpdate, ChainId, StateRoot, SubBlock, Transaction,
+    TransactionId, TransactionStatus,
+};
+use crate::types::communication::cl_to_hig::STATUS_UPDATE_PATTERN;
+use super::{BlockedTransaction, HyperIG, HyperIGError, KeyLockWait, SubblockTiming, TransactionPreview};
+
+/// Misbehavior applied to every proposal a mock HIG sends
+#[derive(Debug, Clone, PartialEq)]
+pub enum Misbehavior {
+    /// Proposals are never sent
+    DropProposals,
+    /// Every proposal is sent twice
+    DuplicateProposals,
+    /// Success and Failure are swapped
+    FlipStatus,
+    /// Proposals claim to come from another chain
+    SpoofChainId(ChainId),
+    /// Proposals list these constituent chains instead of the CAT's
+    WrongConstituentChains(Vec<ChainId>),
+    /// Proposals are sent after this additional delay
+    Delay(Duration),
+}
+
+/// A proposal sent at a fixed time after the script starts
+#[derive(Debug, Clone)]
+pub struct ScriptedProposal {
+    /// Time after the start of the script
+    pub at: Duration,
+    /// The CAT the proposal is for
+    pub cat_id: CATId,
+    /// The proposed status
+    pub status: CATStatusLimited,
+    /// The constituent chains of the CAT
+    pub constituent_chains: Vec<ChainId>,
+}
+
+/// A HIG that sends canned proposals instead of executing transactions
+pub struct MockHyperIG {
+    /// The chain this mock stands in for
+    chain_id: ChainId,
+    /// Channel to the HS
+    sender_to_hs: mpsc::Sender<CATStatusUpdate>,
+    /// Proposals sent by `run_script`
+    script: Vec<ScriptedProposal>,
+    /// Proposal for processed CATs without a specific response
+    default_proposal: CATStatusLimited,
+    /// Proposals for specific processed CATs
+    responses: HashMap<CATId, CATStatusLimited>,
+    /// Misbehavior applied to every proposal
+    misbehaviors: Vec<Misbehavior>,
+    /// Processed transactions and their statuses
+    transactions: HashMap<TransactionId, (Transaction, TransactionStatus)>,
+    /// Proposals handed to the HS channel, after applying the misbehavior
+    sent_proposals: Arc<Mutex<Vec<CATStatusUpdate>>>,
+    /// Height of the last processed subblock
+    current_block_height: u64,
+}
+
+impl MockHyperIG {
+    /// Creates a mock HIG that proposes Success for every processed CAT
+    /// 
+    /// # Arguments
+    /// * `chain_id` - The chain the mock stands in for
+    /// * `sender_to_hs` - Channel to the HS
+    pub fn new(chain_id: ChainId, sender_to_hs: mpsc::Sender<CATStatusUpdate>) -> Self {
+        Self {
+            chain_id,
+            sender_to_hs,
+            script: Vec::new(),
+            default_proposal: CATStatusLimited::Success,
+            responses: HashMap::new(),
+            misbehaviors: Vec::new(),
+            transactions: HashMap::new(),
+            sent_proposals: Arc::new(Mutex::new(Vec::new())),
+            current_block_height: 0,
+        }
+    }
+
+    /// Schedules a proposal at a fixed time after the start of the script
+    pub fn propose_at(mut self, at: Duration, cat_id: CATId, status: CATStatusLimited, constituent_chains: Vec<ChainId>) -> Self {
+        self.script.push(ScriptedProposal { at, cat_id, status, constituent_chains });
+        self
+    }
+
+    /// Sets the proposal for processed CATs without a specific response
+    pub fn with_default_proposal(mut self, status: CATStatusLimited) -> Self {
+        self.default_proposal = status;
+        self
+    }
+
+    /// Sets the proposal for a specific processed CAT
+    pub fn respond_with(mut self, cat_id: CATId, status: CATStatusLimited) -> Self {
+        self.responses.insert(cat_id, status);
+        self
+    }
+
+    /// Adds a misbehavior applied to every proposal
+    pub fn with_misbehavior(mut self, misbehavior: Misbehavior) -> Self {
+        self.misbehaviors.push(misbehavior);
+        self
+    }
+
+    /// Sends the scripted proposals, each at its time after now
+    /// 
+    /// # Returns
+    /// The handle of the task sending the proposals
+    pub fn run_script(&self) -> JoinHandle<()> {
+        let mut script = self.script.clone();
+        script.sort_by_key(|proposal| proposal.at);
+        let sender = self.sender_to_hs.clone();
+        let sent_proposals = self.sent_proposals.clone();
+        let updates: Vec<(Duration, Vec<(Duration, CATStatusUpdate)>)> = script.into_iter()
+            .map(|proposal| (proposal.at, self.apply_misbehavior(proposal.cat_id, proposal.status, proposal.constituent_chains)))
+            .collect();
+        tokio::spawn(async move {
+            let start = tokio::time::Instant::now();
+            for (at, updates) in updates {
+                tokio::time::sleep_until(start + at).await;
+                for (delay, update) in updates {
+                    deliver(sender.clone(), sent_proposals.clone(), delay, update).await;
+                }
+            }
+        })
+    }
+
+    /// Returns the proposals handed to the HS channel so far
+    pub async fn get_sent_proposals(&self) -> Vec<CATStatusUpdate> {
+        self.sent_proposals.lock().await.clone()
+    }
+
+    /// Applies the misbehavior to a proposal
+    /// 
+    /// # Returns
+    /// The updates to send, each with the delay before sending it
+    fn apply_misbehavior(&self, cat_id: CATId, status: CATStatusLimited, constituent_chains: Vec<ChainId>) -> Vec<(Duration, CATStatusUpdate)> {
+        // A script stands in for a single run, its CATs belong to the first epoch
+        let mut update = CATStatusUpdate { cat_id, chain_id: self.chain_id.clone(), status, constituent_chains, epoch: 0, block_height: 0 };
+        let mut delay = Duration::ZERO;
+        let mut copies = 1;
+        for misbehavior in &self.misbehaviors {
+            match misbehavior {
+                Misbehavior::DropProposals => copies = 0,
+                Misbehavior::DuplicateProposals => copies *= 2,
+                Misbehavior::FlipStatus => {
+                    update.status = match update.status {
+                        CATStatusLimited::Success => CATStatusLimited::Failure,
+                        _ => CATStatusLimited::Success,
+                    };
+                }
+                Misbehavior::SpoofChainId(chain_id) => update.chain_id = chain_id.clone(),
+                Misbehavior::WrongConstituentChains(chains) => update.constituent_chains = chains.clone(),
+                Misbehavior::Delay(extra) => delay += *extra,
+            }
+        }
+        vec![(delay, update); copies]
+    }
+
+    /// Sets the status of a processed CAT from a status update transaction
+    fn handle_status_update(&mut self, tx: &Transaction) -> Result<TransactionStatus, anyhow::Error> {
+        let caps = STATUS_UPDATE_PATTERN.captures(&tx.data)
+            .ok_or_else(|| anyhow::anyhow!("Invalid status update format: {}", tx.data))?;
+        let status = match &caps[1] {
+            "Success" => TransactionStatus::Success,
+            "TimedOut" => TransactionStatus::TimedOut,
+            "Rejected" => TransactionStatus::Rejected,
+            _ => TransactionStatus::Failure,
+        };
+        let cat_id = &caps["cat_id"];
+        if let Some((_, cat_status)) = self.transactions.values_mut().find(|(cat_tx, _)| cat_tx.cl_id.0 == cat_id) {
+            *cat_status = status.clone();
+        }
+        Ok(status)
+    }
+}
+
+/// Sends an update to the HS after a delay and records it
+async fn deliver(sender: mpsc::Sender<CATStatusUpdate>, sent_proposals: Arc<Mutex<Vec<CATStatusUpdate>>>, delay: Duration, update: CATStatusUpdate) {
+    if !delay.is_zero() {
+        sleep(delay).await;
+    }
+    sent_proposals.lock().await.push(update.clone());
+    let _ = sender.send(update).await;
+}
+
+/// Returns an error for queries about state the mock does not keep
+fn unsupported(query: &str) -> HyperIGError {
+    HyperIGError::Internal(format!("{} is not supported by MockHyperIG", query))
+}
+
+#[async_trait]
+impl HyperIG for MockHyperIG {
+    async fn process_transaction(&mut self, transaction: Transaction) -> Result<TransactionStatus, anyhow::Error> {
+        if transaction.data.starts_with("STATUS_UPDATE") {
+            return self.handle_status_update(&transaction);
+        }
+        if !transaction.data.starts_with("CAT") {
+            self.transactions.insert(transaction.id.clone(), (transaction, TransactionStatus::Success));
+            return Ok(TransactionStatus::Success);
+        }
+
+        // CATs stay pending until their status update, the proposal is the canned response
+        let cat_id = CATId(transaction.cl_id.clone());
+        let status = self.responses.get(&cat_id).cloned().unwrap_or_else(|| self.default_proposal.clone());
+        let constituent_chains = transaction.constituent_chains.clone();
+        self.transactions.insert(transaction.id.clone(), (transaction, TransactionStatus::Pending));
+        self.send_cat_status_proposal(cat_id, status, constituent_chains).await?;
+        Ok(TransactionStatus::Pending)
+    }
+
+    async fn get_transaction_status(&self, transaction_id: TransactionId) -> Result<TransactionStatus, anyhow::Error> {
+        self.transactions.get(&transaction_id)
+            .map(|(_, status)| status.clone())
+            .ok_or_else(|| HyperIGError::TransactionNotFound(transaction_id).into())
+    }
+
+    async fn get_pending_transactions(&self) -> Result<Vec<TransactionId>, anyhow::Error> {
+        Ok(self.transactions.iter()
+            .filter(|(_, (_, status))| *status == TransactionStatus::Pending)
+            .map(|(tx_id, _)| tx_id.clone())
+            .collect())
+    }
+
+    async fn send_cat_status_proposal(&mut self, cat_id: CATId, status: CATStatusLimited, constituent_chains: Vec<ChainId>) -> Result<(), HyperIGError> {
+        for (delay, update) in self.apply_misbehavior(cat_id, status, constituent_chains) {
+            if delay.is_zero() {
+                deliver(self.sender_to_hs.clone(), self.sent_proposals.clone(), delay, update).await;
+            } else {
+                tokio::spawn(deliver(self.sender_to_hs.clone(), self.sent_proposals.clone(), delay, update));
+            }
+        }
+        Ok(())
+    }
+
+    async fn get_resolution_status(&self, id: TransactionId) -> Result<TransactionStatus, HyperIGError> {
+        self.transactions.get(&id)
+            .map(|(_, status)| status.clone())
+            .ok_or(HyperIGError::TransactionNotFound(id))
+    }
+
+    async fn process_subblock(&mut self, subblock: SubBlock) -> Result<(), HyperIGError> {
+        if subblock.chain_id != self.chain_id {
+            return Err(HyperIGError::WrongChainId { expected: self.chain_id.clone(), received: subblock.chain_id });
+        }
+        self.current_block_height = subblock.block_height;
+        for tx in subblock.transactions {
+            self.process_transaction(tx).await.map_err(|e| HyperIGError::Internal(e.to_string()))?;
+        }
+        Ok(())
+    }
+
+    async fn get_transaction_dependencies(&self, _transaction_id: TransactionId) -> Result<Vec<TransactionId>, HyperIGError> {
+        Ok(Vec::new())
+    }
+
+    async fn get_transaction_data(&self, tx_id: TransactionId) -> Result<String, anyhow::Error> {
+        self.transactions.get(&tx_id)
+            .map(|(tx, _)| tx.data.clone())
+            .ok_or_else(|| HyperIGError::TransactionNotFound(tx_id).into())
+    }
+
+    async fn get_chain_state(&self) -> Result<HashMap<String, i64>, anyhow::Error> {
+        Ok(HashMap::new())
+    }
+
+    async fn get_cat_max_lifetime(&self, _cat_id: CATId) -> Result<u64, HyperIGError> {
+        Err(unsupported("CAT lifetime"))
+    }
+
+    async fn get_current_block_height(&self) -> Result<u64, HyperIGError> {
+        Ok(self.current_block_height)
+    }
+
+    async fn get_cat_lifetime(&self) -> Result<u64, HyperIGError> {
+        Err(unsupported("CAT lifetime"))
+    }
+
+    async fn get_transaction_status_count(&self, status: TransactionStatus) -> Result<u64, HyperIGError> {
+        Ok(self.transactions.values().filter(|(_, tx_status)| *tx_status == status).count() as u64)
+    }
+
+    async fn get_transaction_status_counts_cats(&self) -> Result<(u64, u64, u64), HyperIGError> {
+        Ok(count_statuses(self.transactions.values().filter(|(tx, _)| tx.data.starts_with("CAT"))))
+    }
+
+    async fn get_transaction_status_counts_regular(&self) -> Result<(u64, u64, u64), HyperIGError> {
+        Ok(count_statuses(self.transactions.values().filter(|(tx, _)| !tx.data.starts_with("CAT"))))
+    }
+
+    async fn get_cat_pending_detailed_counts(&self) -> Result<(u64, u64), HyperIGError> {
+        // Mock CATs are never postponed
+        let (pending, _, _) = self.get_transaction_status_counts_cats().await?;
+        Ok((pending, 0))
+    }
+
+    async fn get_cat_failure_detailed_counts(&self) -> Result<(u64, u64), HyperIGError> {
+        Ok((0, 0))
+    }
+
+    async fn get_transaction_status_counts_cats_by_fanout(&self) -> Result<BTreeMap<usize, (u64, u64, u64)>, HyperIGError> {
+        let fanouts: BTreeMap<usize, ()> = self.transactions.values()
+            .filter(|(tx, _)| tx.data.starts_with("CAT"))
+            .map(|(tx, _)| (tx.constituent_chains.len(), ()))
+            .collect();
+        Ok(fanouts.into_keys()
+            .map(|fanout| (fanout, count_statuses(self.transactions.values()
+                .filter(|(tx, _)| tx.data.starts_with("CAT") && tx.constituent_chains.len() == fanout))))
+            .collect())
+    }
+
+    async fn get_transaction_status_counts_cats_by_blocked(&self) -> Result<((u64, u64, u64), (u64, u64, u64)), HyperIGError> {
+        // Mock transactions never take locks
+        Ok(((0, 0, 0), self.get_transaction_status_counts_cats().await?))
+    }
+
+    async fn get_transaction_status_counts_regular_by_blocked(&self) -> Result<((u64, u64, u64), (u64, u64, u64)), HyperIGError> {
+        Ok(((0, 0, 0), self.get_transaction_status_counts_regular().await?))
+    }
+
+    async fn get_status_transitions(&self, _block_height: u64) -> Result<HashMap<TransactionId, TransactionStatus>, HyperIGError> {
+        Err(unsupported("Status transitions"))
+    }
+
+    async fn simulate_transaction(&self, _transaction: Transaction) -> Result<TransactionPreview, HyperIGError> {
+        Err(unsupported("Transaction simulation"))
+    }
+
+    async fn get_account_balance(&self, _chain_id: ChainId, _account: u32, _with_proof: bool) -> Result<AccountBalance, HyperIGError> {
+        Err(unsupported("Account balances"))
+    }
+
+    async fn get_state_root(&self, block_height: u64) -> Result<StateRoot, HyperIGError> {
+        Err(HyperIGError::StateRootNotFound(block_height))
+    }
+
+    async fn get_subblock_timing(&self, _block_height: u64) -> Result<Option<SubblockTiming>, HyperIGError> {
+        Ok(None)
+    }
+
+    async fn get_hottest_keys(&self, _n: usize) -> Result<Vec<KeyLockWait>, HyperIGError> {
+        Ok(Vec::new())
+    }
+
+    async fn get_all_blocked_transactions(&self) -> Result<HashMap<TransactionId, BlockedTransaction>, HyperIGError> {
+        Ok(HashMap::new())
+    }
+}
+
+/// Counts (pending, success, failure) statuses, counting every other final status as failure
+fn count_statuses<'a>(transactions: impl Iterator<Item = &'a (Transaction, TransactionStatus)>) -> (u64, u64, u64) {
+    transactions.fold((0, 0, 0), |(pending, success, failure), (_, status)| match status {
+        TransactionStatus::Pending => (pending + 1, success, failure),
+        TransactionStatus::Success => (pending, success + 1, failure),
+        _ => (pending, success, failure + 1),
+    })
+}
@@ -1,8 +1,12 @@
-use crate::types::{TransactionId, TransactionStatus, Transaction, CATId, CATStatusLimited, SubBlock, ChainId};
+use crate::types::{TransactionId, TransactionStatus, Transaction, CATId, CATStatus, CATStatusLimited, SubBlock, ChainId, AccountBalance, StateRoot, ChannelHealth};
 use async_trait::async_trait;
 use thiserror::Error;
+use serde::{Deserialize, Serialize};
+use rand::Rng;
+use rand_distr::{Distribution, LogNormal, Normal};
 
 pub mod node;
+pub mod mock;
 pub use node::HyperIGNode;
 
 #[derive(Debug, Error)]
@@ -21,6 +25,339 @@ pub enum HyperIGError {
     InvalidCATConstituentChains(String),
     #[error("CAT depends on pending transaction: {0}")]
     CATDependsOnPendingTransaction(String),
+    #[error("Status update without HS-origin marker: {0}")]
+    UnverifiedStatusUpdate(TransactionId),
+    #[error("Protocol violation: {0}")]
+    ProtocolViolation(String),
+    #[error("No state root recorded for block {0}")]
+    StateRootNotFound(u64),
+    #[error("Proposal queue full, rejected proposal for CAT: {0}")]
+    ProposalQueueFull(CATId),
+    #[error("Duplicate transaction: {0}")]
+    DuplicateTransaction(TransactionId),
+    #[error("CAT {tx_id} reuses the id of CAT {cat_id}")]
+    CatIdCollision { cat_id: CATId, tx_id: TransactionId },
+    #[error("Invalid data for transaction {tx_id}: {reason}")]
+    InvalidTransactionData { tx_id: TransactionId, reason: String },
+}
+
+/// Policy for handling protocol violations, e.g. a Success status update
+/// arriving for a CAT that has already been marked as Failure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProtocolViolationPolicy {
+    /// Log and count the violation, keep the current status
+    #[default]
+    Ignore,
+    /// Count the violation and return an error so the caller can abort the run
+    FailRun,
+    /// Count the violation, log it as an error and record it for later inspection
+    Escalate,
+}
+
+/// Policy for transactions whose id was already received, either earlier in the same subblock
+/// or in a previous one. Duplicates indicate a CL bug, so they are always counted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DuplicateTransactionPolicy {
+    /// Skip the duplicate
+    #[default]
+    Skip,
+    /// Abort processing the subblock with DuplicateTransaction
+    Error,
+    /// Execute the duplicate again as a new transaction (CATs, and duplicates whose earlier
+    /// instance is still pending, are skipped)
+    ProcessAgain,
+}
+
+/// Policy for a CAT whose id is already used by another CAT the HIG received, e.g. when two CL
+/// transactions get the same timestamp-based id under load. Collisions are always counted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CatIdCollisionPolicy {
+    /// Skip the new CAT, so it is neither executed nor proposed to the HS
+    #[default]
+    RejectNew,
+    /// Abort processing the subblock with CatIdCollision
+    Error,
+}
+
+/// Policy for breaking a dependency cycle, detected when a new dependency would make a
+/// transaction wait (transitively) on itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DependencyCyclePolicy {
+    /// Fail the transaction whose dependency closes the cycle
+    #[default]
+    FailNewest,
+    /// Fail the transaction whose dependency closes the cycle and the transaction it would wait on
+    FailBoth,
+}
+
+/// Policy for a CAT status proposal that arrives while the pending-proposal queue is full.
+/// A dropped proposal never reaches the HS, so the CAT times out unless the HS decides without it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProposalQueueOverflowPolicy {
+    /// Drop the oldest queued proposal to make room for the new one
+    #[default]
+    DropOldest,
+    /// Drop the new proposal
+    DropNewest,
+    /// Return ProposalQueueFull to the caller
+    Reject,
+}
+
+/// Outcome of a dry run of a transaction, see [`HyperIG::simulate_transaction`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionPreview {
+    /// Keys the transaction would access
+    pub keys: Vec<String>,
+    /// Transaction that currently locks one of the keys, if the transaction would be blocked
+    pub blocked_by: Option<TransactionId>,
+    /// Whether executing the transaction against the current state would succeed
+    pub would_succeed: bool,
+    /// Status the transaction would have directly after being processed
+    pub expected_status: TransactionStatus,
+}
+
+/// Time the HIG spent on one subblock
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SubblockTiming {
+    /// Time the message loop waited for the node lock before processing the subblock
+    pub lock_wait: std::time::Duration,
+    /// Time spent executing the transactions of the subblock (excluding proposal queuing)
+    pub execution: std::time::Duration,
+    /// Time spent queuing CAT status proposals for the HS
+    pub proposal_queuing: std::time::Duration,
+}
+
+impl SubblockTiming {
+    /// Returns the total time spent on the subblock
+    pub fn total(&self) -> std::time::Duration {
+        self.lock_wait + self.execution + self.proposal_queuing
+    }
+}
+
+/// Points in time at which a transaction passed the HIG, to attribute its latency
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionTimeline {
+    /// Time the HIG first processed the transaction
+    pub received_at: std::time::Instant,
+    /// Total time the transaction waited for locked keys
+    pub lock_wait: std::time::Duration,
+    /// The keys the transaction waited for, sorted
+    pub blocked_keys: Vec<String>,
+    /// Time the HIG queued the status proposal of the CAT (CATs only)
+    pub proposed_at: Option<std::time::Instant>,
+    /// Time the transaction reached its final status
+    pub finalized_at: Option<std::time::Instant>,
+}
+
+/// A step in the resolution of a CAT on one HIG, see [`node::HyperIGNode::get_cat_audit_trail`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum CatAuditEvent {
+    /// The HIG received the CAT in a subblock
+    Received { tx_id: TransactionId },
+    /// The CAT was rejected (and Failure proposed) because it depends on a pending transaction
+    Rejected { blocked_by: TransactionId, key: String },
+    /// The CAT was postponed because a pending transaction locks one of its keys
+    Postponed { blocked_by: TransactionId, key: String },
+    /// The HIG determined its proposal: Success if executing the CAT on the current state would succeed
+    Proposed { status: CATStatus },
+    /// The proposal was sent to the HS
+    ProposalSent { status: CATStatusLimited, attempts: u32 },
+    /// The proposal was given up on after the maximum number of send attempts
+    ProposalDropped { status: CATStatusLimited, attempts: u32 },
+    /// A status update of the HS arrived; it is not applied if the CAT had already failed locally
+    StatusUpdate { status: CATStatusLimited, applied: bool },
+    /// The CAT timed out before a status update arrived
+    TimedOut { max_lifetime: u64 },
+}
+
+/// An event of the audit trail of a CAT
+#[derive(Debug, Clone, PartialEq)]
+pub struct CatAuditEntry {
+    /// Block height of the HIG when the event happened
+    pub block_height: u64,
+    /// Time of the event
+    pub at: std::time::Instant,
+    pub event: CatAuditEvent,
+}
+
+/// Retry policy for CAT status proposals whose send to the HS failed.
+///
+/// After the n-th failed attempt the proposal waits `min(initial_backoff * 2^(n-1), max_backoff)`,
+/// randomized by up to `jitter` (as a fraction) in either direction so that HIGs do not retry in lockstep.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProposalRetryPolicy {
+    /// Backoff after the first failed attempt
+    pub initial_backoff: std::time::Duration,
+    /// Upper bound of the backoff (before jitter)
+    pub max_backoff: std::time::Duration,
+    /// Maximum relative deviation of the randomized backoff (0.0 = no jitter)
+    pub jitter: f64,
+    /// Number of failed attempts after which the proposal is moved to the dead-letter list (None = retry forever)
+    pub max_attempts: Option<u32>,
+}
+
+impl Default for ProposalRetryPolicy {
+    fn default() -> Self {
+        Self {
+            initial_backoff: std::time::Duration::from_millis(100),
+            max_backoff: std::time::Duration::from_secs(5),
+            jitter: 0.2,
+            max_attempts: None,
+        }
+    }
+}
+
+impl ProposalRetryPolicy {
+    /// Returns the backoff after the given number of failed attempts, without jitter
+    pub fn base_backoff(&self, failed_attempts: u32) -> std::time::Duration {
+        let exponent = failed_attempts.saturating_sub(1).min(31);
+        self.initial_backoff.saturating_mul(1 << exponent).min(self.max_backoff)
+    }
+
+    /// Returns the randomized backoff after the given number of failed attempts
+    pub fn backoff(&self, failed_attempts: u32) -> std::time::Duration {
+        let base = self.base_backoff(failed_attempts);
+        if self.jitter <= 0.0 {
+            return base;
+        }
+        let factor = 1.0 + rand::thread_rng().gen_range(-self.jitter..=self.jitter);
+        base.mul_f64(factor.max(0.0))
+    }
+
+    /// Returns whether a proposal with the given number of failed attempts should be given up on
+    pub fn exhausted(&self, failed_attempts: u32) -> bool {
+        self.max_attempts.is_some_and(|max_attempts| failed_attempts >= max_attempts)
+    }
+}
+
+/// Distribution of the delay of the proposals a HIG sends to the HS.
+///
+/// The distribution is centered on the configured HS message delay: the normal and the
+/// log-normal distribution have it as their mean. A delay is drawn once per proposal, when the
+/// proposal reaches the front of the queue. Values are in seconds.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DelayDistribution {
+    /// Every proposal waits the configured delay
+    #[default]
+    Constant,
+    /// Delay drawn from a normal distribution with the given standard deviation, truncated at zero
+    Normal { std_dev: f64 },
+    /// Delay drawn from a log-normal distribution whose logarithm has the given standard deviation
+    #[serde(rename = "lognormal")]
+    LogNormal { sigma: f64 },
+    /// A fraction `tail_probability` of the proposals waits `tail_factor` times the configured delay,
+    /// the others wait the configured delay
+    Bimodal { tail_probability: f64, tail_factor: f64 },
+}
+
+impl DelayDistribution {
+    /// Draws a delay from the distribution.
+    ///
+    /// # Arguments
+    /// * `delay` - The configured delay the distribution is centered on
+    ///
+    /// # Returns
+    /// The sampled delay (the configured delay for invalid parameters)
+    pub fn sample(&self, delay: std::time::Duration) -> std::time::Duration {
+        let secs = delay.as_secs_f64();
+        let sampled = match *self {
+            DelayDistribution::Constant => secs,
+            DelayDistribution::Normal { std_dev } => match Normal::new(secs, std_dev) {
+                Ok(normal) => normal.sample(&mut rand::thread_rng()),
+                Err(_) => secs,
+            },
+            DelayDistribution::LogNormal { sigma } => {
+                // Choose the location so that the mean of the distribution is the configured delay
+                match LogNormal::new(secs.ln() - sigma * sigma / 2.0, sigma) {
+                    Ok(log_normal) if secs > 0.0 => log_normal.sample(&mut rand::thread_rng()),
+                    _ => secs,
+                }
+            }
+            DelayDistribution::Bimodal { tail_probability, tail_factor } => {
+                if rand::thread_rng().gen_bool(tail_probability.clamp(0.0, 1.0)) { secs * tail_factor } else { secs }
+            }
+        };
+        std::time::Duration::from_secs_f64(sampled.max(0.0))
+    }
+
+    /// Returns the distribution with its absolute parameters multiplied by `factor`.
+    /// Used e.g. by the simulator to convert a standard deviation given in blocks into seconds.
+    ///
+    /// # Arguments
+    /// * `factor` - The scaling factor
+    pub fn scaled(&self, factor: f64) -> Self {
+        match *self {
+            DelayDistribution::Normal { std_dev } => DelayDistribution::Normal { std_dev: std_dev * factor },
+            // The remaining parameters are relative to the configured delay
+            distribution => distribution,
+        }
+    }
+}
+
+/// CAT status proposal that could not be sent to the HS within the retry policy's maximum attempts
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeadLetterProposal {
+    /// The CAT the proposal is for
+    pub cat_id: CATId,
+    /// The proposed status
+    pub status: CATStatusLimited,
+    /// The constituent chains of the CAT
+    pub constituent_chains: Vec<ChainId>,
+    /// Number of failed send attempts
+    pub attempts: u32,
+    /// Error of the last send attempt
+    pub last_error: String,
+}
+
+/// Time transactions spent blocked behind the lock of one key
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct KeyLockWait {
+    /// The key
+    pub key: String,
+    /// Total time transactions waited for the key
+    pub total_wait: std::time::Duration,
+    /// Longest time a single transaction waited for the key
+    pub max_wait: std::time::Duration,
+    /// Number of transactions that waited for the key
+    pub blocked_count: u64,
+}
+
+/// What a blocked transaction is waiting on
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct BlockedTransaction {
+    /// The transactions holding the locks the transaction waits for, sorted by ID
+    pub blocking_txs: Vec<TransactionId>,
+    /// The keys the transaction waits to access, sorted
+    pub keys: Vec<String>,
+}
+
+/// Snapshot of the liveness of a HIG, to diagnose hangs
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct HyperIGHealth {
+    /// The chain of the HIG
+    pub chain_id: ChainId,
+    /// Whether the loop processing the subblocks of the CL is running
+    pub message_loop_running: bool,
+    /// Whether the loop sending the queued proposals to the HS is running
+    pub queue_processor_running: bool,
+    /// Whether the node is halted
+    pub halted: bool,
+    /// Height of the last processed subblock
+    pub current_block_height: u64,
+    /// Number of pending transactions
+    pub pending_transactions: usize,
+    /// Number of CAT status proposals waiting to be sent to the HS
+    pub proposal_queue_depth: usize,
+    /// The channel from the CL (None once the node no longer holds it)
+    pub cl_channel: Option<ChannelHealth>,
+    /// The channel to the HS (None once the node no longer holds it)
+    pub hs_channel: Option<ChannelHealth>,
 }
 
 /// The Hyper IG is responsible for executing transactions,
@@ -68,6 +405,7 @@ pub trait HyperIG: Send + Sync {
     async fn get_transaction_status_count(&self, status: TransactionStatus) -> Result<u64, HyperIGError>;
 
     /// Get counts of CAT transaction statuses (Pending, Success, Failure)
+    /// The failure count includes TimedOut and Rejected CATs
     async fn get_transaction_status_counts_cats(&self) -> Result<(u64, u64, u64), HyperIGError>;
 
     /// Get counts of regular transaction statuses (Pending, Success, Failure)
@@ -78,6 +416,59 @@ pub trait HyperIG: Send + Sync {
     /// - resolving: CATs with proposed status that are being actively processed
     /// - postponed: CATs waiting for dependencies (other pending transactions)
     async fn get_cat_pending_detailed_counts(&self) -> Result<(u64, u64), HyperIGError>;
+
+    /// Get detailed counts of failed CATs (TimedOut, Rejected)
+    /// Returns (timed_out_count, rejected_count) where:
+    /// - timed_out: CATs that did not receive a status update within their lifetime
+    /// - rejected: CATs rejected because they depend on a pending transaction
+    /// Both are included in the failure count of `get_transaction_status_counts_cats`.
+    async fn get_cat_failure_detailed_counts(&self) -> Result<(u64, u64), HyperIGError>;
+
+    /// Get counts of CAT transaction statuses (Pending, Success, Failure) per CAT fanout
+    /// Returns a map from the number of constituent chains to (pending_count, success_count, failure_count).
+    /// The counts of all fanouts add up to `get_transaction_status_counts_cats`.
+    async fn get_transaction_status_counts_cats_by_fanout(&self) -> Result<std::collections::BTreeMap<usize, (u64, u64, u64)>, HyperIGError>;
+
+    /// Get counts of CAT transaction statuses (Pending, Success, Failure) split by lock contention
+    /// Returns (ever_blocked, never_blocked), where ever_blocked counts the CATs that found at least one
+    /// of their keys locked by a pending transaction (whether they were postponed or rejected for it).
+    async fn get_transaction_status_counts_cats_by_blocked(&self) -> Result<((u64, u64, u64), (u64, u64, u64)), HyperIGError>;
+
+    /// Get counts of regular transaction statuses (Pending, Success, Failure) split by lock contention
+    /// Returns (ever_blocked, never_blocked), where ever_blocked counts the regular transactions that
+    /// waited on a key locked by another transaction at least once.
+    async fn get_transaction_status_counts_regular_by_blocked(&self) -> Result<((u64, u64, u64), (u64, u64, u64)), HyperIGError>;
+
+    /// Get the transactions whose status changed while processing the subblock at the given height
+    /// Returns a map from transaction ID to the status reached in that block. A transaction that
+    /// changed status several times within the block (e.g. Pending then Success) appears once with its last status.
+    async fn get_status_transitions(&self, block_height: u64) -> Result<std::collections::HashMap<TransactionId, TransactionStatus>, HyperIGError>;
+
+    /// Dry-run a transaction against the current state
+    /// Parses the transaction, extracts its keys and checks whether it would be blocked and whether it
+    /// would succeed, without changing any state (no status, lock, dependency or VM change).
+    async fn simulate_transaction(&self, transaction: Transaction) -> Result<TransactionPreview, HyperIGError>;
+
+    /// Get the balance of an account on the given chain
+    /// If `with_proof` is set and the account exists, an inclusion proof against the current state root is attached.
+    /// Returns WrongChainId if the chain is not the chain of this HIG.
+    async fn get_account_balance(&self, chain_id: ChainId, account: u32, with_proof: bool) -> Result<AccountBalance, HyperIGError>;
+
+    /// Get the state root after processing the subblock at the given block height
+    /// The root is deterministic in the account balances, so identical runs produce identical roots.
+    async fn get_state_root(&self, block_height: u64) -> Result<StateRoot, HyperIGError>;
+
+    /// Get the time spent on the subblock at the given block height
+    /// Returns None if no subblock was processed at that height.
+    async fn get_subblock_timing(&self, block_height: u64) -> Result<Option<SubblockTiming>, HyperIGError>;
+
+    /// Get the keys transactions waited for the longest in total, hottest first
+    /// Only waits that have ended are included.
+    async fn get_hottest_keys(&self, n: usize) -> Result<Vec<KeyLockWait>, HyperIGError>;
+
+    /// Get every blocked transaction together with the transactions and keys it waits on
+    /// Unlike calling get_transaction_dependencies per transaction, this is a consistent snapshot.
+    async fn get_all_blocked_transactions(&self) -> Result<std::collections::HashMap<TransactionId, BlockedTransaction>, HyperIGError>;
 }
 
 #[cfg(test)]
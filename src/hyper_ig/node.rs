@@ -1,15 +1,17 @@
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use crate::types::{Transaction, TransactionId, TransactionStatus, CATStatusLimited, CATStatus, CATId, SubBlock, CATStatusUpdate, CLTransactionId};
-use super::{HyperIG, HyperIGError};
+use super::{HyperIG, HyperIGError, HyperIGHealth, ProtocolViolationPolicy, DuplicateTransactionPolicy, CatIdCollisionPolicy, DependencyCyclePolicy, ProposalQueueOverflowPolicy, ProposalRetryPolicy, DelayDistribution, DeadLetterProposal, TransactionPreview, SubblockTiming, KeyLockWait, BlockedTransaction, TransactionTimeline, CatAuditEvent, CatAuditEntry};
 use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use async_trait::async_trait;
 use std::time::Duration;
-use crate::types::ChainId;
-use crate::types::communication::cl_to_hig::{STATUS_UPDATE_PATTERN};
+use crate::types::{ChainId, ChannelHealth, Clock, ClockSkew, SystemClock, BlockHeightSource, SubblockHeight, AccountBalance, StateRoot, StateTree};
+use crate::types::communication::cl_to_hig::{STATUS_UPDATE_PATTERN, TransactionData, has_hs_origin_marker};
 use crate::utils::logging::log;
 use crate::mock_vm::MockVM;
+use crate::confirmation_layer::ConfirmationLayer;
 use x_chain_vm::transaction::Transaction as VMTransaction;
 use x_chain_vm::transaction::TxSet1;
 
@@ -24,7 +26,13 @@ struct QueuedCATProposal {
     cat_id: CATId,
     status: CATStatusLimited,
     constituent_chains: Vec<ChainId>,
-    queue_entry_time: std::time::Instant,
+    /// HS epoch of the CAT
+    epoch: u64,
+    /// Time the proposal entered the queue, on the clock of the node
+    queue_entry_time: Duration,
+    /// Delay drawn for the proposal once it reached the front of the queue
+    delay: Option<Duration>,
+    failed_attempts: u32,
 }
 
 /// The internal state of the HyperIGNode
@@ -54,14 +62,34 @@ struct HyperIGState {
     tx_depends_on_txs: HashMap<TransactionId, HashSet<TransactionId>>,
     /// Map of transaction IDs to the keys they depend on (reverse index for O(1) key cleanup)
     tx_depends_on_keys: HashMap<TransactionId, HashSet<String>>,
+    /// Map of blocked transaction IDs to when they were blocked and the locked keys that blocked them
+    tx_blocked_since: HashMap<TransactionId, (std::time::Instant, Vec<String>)>,
+    /// Transactions that were blocked on a lock at least once
+    ever_blocked_txs: HashSet<TransactionId>,
+    /// Time transactions spent blocked behind the lock of each key
+    key_lock_waits: HashMap<String, KeyLockWait>,
     /// Map of transaction IDs to when they entered pending state (for timing metrics)
     tx_pending_start_time: HashMap<TransactionId, std::time::Instant>,
+    /// Map of transaction IDs to the points in time they passed the HIG
+    tx_timelines: HashMap<TransactionId, TransactionTimeline>,
+    /// Map of CAT IDs to the steps of their resolution on this HIG
+    cat_audit_trails: HashMap<CATId, Vec<CatAuditEntry>>,
     /// my chain id
     my_chain_id: ChainId,
     /// Mock VM for transaction execution
     vm: MockVM,
     /// Queue for pending CAT status proposals
     pending_proposals: VecDeque<QueuedCATProposal>,
+    /// Maximum length of the proposal queue (None = unbounded)
+    proposal_queue_max_len: Option<usize>,
+    /// What to do with a proposal that arrives while the queue is full
+    proposal_queue_overflow_policy: ProposalQueueOverflowPolicy,
+    /// Number of proposals dropped or rejected because the queue was full
+    count_dropped_proposals: u64,
+    /// Proposals given up on after the maximum number of send attempts
+    dead_letter_proposals: Vec<DeadLetterProposal>,
+    /// Time each sent proposal spent in the queue with its CAT, in the order they were sent (local clock)
+    realized_hs_message_delays: Vec<(CATId, Duration)>,
     /// Map of CAT IDs to their maximum lifetime block height
     cat_max_lifetime: HashMap<CATId, u64>,
     /// Default lifetime for CATs in blocks
@@ -70,10 +98,27 @@ struct HyperIGState {
     current_block_height: u64,
     /// Flag to control whether CATs can depend on pending transactions
     allow_cat_pending_dependencies: bool,
+    /// Whether the node is halted, i.e. neither processes subblocks nor sends proposals
+    halted: bool,
+    /// HS epoch new CATs are assigned to
+    epoch: u64,
+    /// Map of CAT IDs to the HS epoch they were received in
+    cat_epochs: HashMap<CATId, u64>,
+    /// Whether the halted node crashed, i.e. loses the subblocks delivered until it restarts
+    crashed: bool,
+    /// Number of subblocks lost while the node was crashed
+    count_lost_subblocks: u64,
+    /// Number of subblocks replayed from the CL when the node restarted
+    count_replayed_subblocks: u64,
+    /// Last block height replayed from the CL; live subblocks up to it are skipped
+    replayed_through_height: Option<u64>,
     /// CAT transaction counters
     count_cat_pending: u64,
     count_cat_success: u64,
     count_cat_failure: u64,
+    /// CAT failure detailed counters (subsets of count_cat_failure)
+    count_cat_timed_out: u64,
+    count_cat_rejected: u64,
     /// CAT pending detailed counters
     count_cat_pending_postponed: u64,
     count_cat_pending_resolving: u64,
@@ -81,13 +126,67 @@ struct HyperIGState {
     count_regular_pending: u64,
     count_regular_success: u64,
     count_regular_failure: u64,
+    /// Regular transactions that expired while pending (subset of count_regular_failure)
+    count_regular_expired: u64,
     /// Regular transaction timing metrics
     latency_regular_tx_finalized: f64, // Average latency in milliseconds
     max_latency_regular_tx_finalized: f64, // Maximum latency in milliseconds
     count_regular_tx_finalized: u64,   // Number of finalized regular transactions
+    /// Number of status updates rejected because they lacked the HS-origin marker
+    count_rejected_status_updates: u64,
+    /// Number of malformed transactions skipped in subblocks
+    count_malformed_txs: u64,
+    /// Policy for handling protocol violations
+    protocol_violation_policy: ProtocolViolationPolicy,
+    /// Policy for handling transactions whose id was already received
+    duplicate_transaction_policy: DuplicateTransactionPolicy,
+    /// Number of duplicate transactions received
+    count_duplicate_txs: u64,
+    /// Policy for handling CATs whose id is already used by another CAT
+    cat_id_collision_policy: CatIdCollisionPolicy,
+    /// Number of CATs received whose id was already used by another CAT
+    count_cat_id_collisions: u64,
+    /// Policy for breaking dependency cycles
+    dependency_cycle_policy: DependencyCyclePolicy,
+    /// Detected dependency cycles, each starting with the transaction whose dependency closed it
+    dependency_cycles: Vec<Vec<TransactionId>>,
+    /// Transactions to fail to break the detected dependency cycles
+    cycle_victims: Vec<TransactionId>,
+    /// Whether a CAT releases its key locks as soon as it proposes Failure
+    release_locks_on_failure_proposal: bool,
+    /// Number of CATs that released their locks on a Failure proposal
+    count_early_lock_releases: u64,
+    /// Whether status updates are processed before the other transactions of a subblock
+    prioritize_status_updates: bool,
+    /// Number of protocol violations observed (e.g. Success after Failure)
+    count_protocol_violations: u64,
+    /// Escalated protocol violations as (CAT ID, block height)
+    escalated_protocol_violations: Vec<(CATId, u64)>,
+    /// Status transitions per block height: block height -> (transaction ID -> new status)
+    status_transitions: HashMap<u64, HashMap<TransactionId, TransactionStatus>>,
+    /// State root after processing the subblock at each block height
+    state_roots: HashMap<u64, StateRoot>,
+    /// Time spent on the subblock at each block height
+    subblock_timings: HashMap<u64, SubblockTiming>,
+    /// Time spent queuing CAT status proposals while processing the current subblock
+    proposal_queuing_in_subblock: Duration,
+    /// Skew of the local clock relative to the CL (affects timeout checks and timestamping)
+    clock_skew: ClockSkew,
 }
 
 impl HyperIGState {
+    /// Counts the statuses (Pending, Success, Failure) of the received transactions matching the filter,
+    /// classifying them the same way as the status counters
+    fn count_statuses_where(&self, filter: impl Fn(&TransactionId, &Transaction) -> bool) -> (u64, u64, u64) {
+        self.transaction_statuses.iter()
+            .filter_map(|(tx_id, status)| self.received_txs.get(tx_id).filter(|tx| filter(tx_id, tx)).map(|_| status))
+            .fold((0, 0, 0), |(pending, success, failure), status| match status {
+                TransactionStatus::Pending => (pending + 1, success, failure),
+                TransactionStatus::Success => (pending, success + 1, failure),
+                _ => (pending, success, failure + 1),
+            })
+    }
+
     /// Increments the appropriate pending counter based on transaction type
     fn increment_count_pending(&mut self, tx_id: &TransactionId) {
         if let Some(tx) = self.received_txs.get(tx_id) {
@@ -149,9 +248,17 @@ impl HyperIGState {
                         TransactionStatus::Success => {
                             self.count_cat_success += 1;
                         }
-                        TransactionStatus::Failure => {
+                        TransactionStatus::Failure | TransactionStatus::Expired => {
                             self.count_cat_failure += 1;
                         }
+                        TransactionStatus::TimedOut => {
+                            self.count_cat_failure += 1;
+                            self.count_cat_timed_out += 1;
+                        }
+                        TransactionStatus::Rejected => {
+                            self.count_cat_failure += 1;
+                            self.count_cat_rejected += 1;
+                        }
                         TransactionStatus::Pending => {
                             // Should not happen here, do not handle gracefully. Panic.
                             panic!("BUG: Transaction {} is pending when incrementing final counter", tx_id.0);
@@ -165,8 +272,12 @@ impl HyperIGState {
                         TransactionStatus::Success => {
                             self.count_regular_success += 1;
                         }
-                        TransactionStatus::Failure => {
+                        TransactionStatus::Failure | TransactionStatus::TimedOut | TransactionStatus::Rejected => {
+                            self.count_regular_failure += 1;
+                        }
+                        TransactionStatus::Expired => {
                             self.count_regular_failure += 1;
+                            self.count_regular_expired += 1;
                         }
                         TransactionStatus::Pending => {
                             // Should not happen here, do not handle gracefully. Panic.
@@ -186,20 +297,41 @@ impl HyperIGState {
         // Add to pending set first
         self.pending_transactions.insert(tx_id.clone());
         // Record when transaction entered pending state (for timing metrics)
-        self.tx_pending_start_time.insert(tx_id.clone(), std::time::Instant::now());
+        let now = std::time::Instant::now();
+        self.tx_pending_start_time.insert(tx_id.clone(), now);
+        self.tx_timelines.entry(tx_id.clone())
+            .or_insert_with(|| TransactionTimeline { received_at: now, lock_wait: std::time::Duration::ZERO, blocked_keys: Vec::new(), proposed_at: None, finalized_at: None });
         // Then increment the counter
         self.increment_count_pending(tx_id);
+        // Record the transition into Pending for the current block
+        self.record_status_transition(tx_id, TransactionStatus::Pending);
+    }
+
+    /// Appends an event to the audit trail of a CAT at the current block height
+    fn record_cat_audit(&mut self, cat_id: &CATId, event: CatAuditEvent) {
+        let entry = CatAuditEntry { block_height: self.current_block_height, at: std::time::Instant::now(), event };
+        self.cat_audit_trails.entry(cat_id.clone()).or_default().push(entry);
+    }
+
+    /// Records that a transaction changed its status while processing the current block.
+    /// If the transaction changes status several times within the same block, only the last status is kept.
+    fn record_status_transition(&mut self, tx_id: &TransactionId, status: TransactionStatus) {
+        self.status_transitions
+            .entry(self.current_block_height)
+            .or_default()
+            .insert(tx_id.clone(), status);
     }
 
     /// Updates a transaction to a final status and increments the appropriate counter
     /// This ensures that counter management is always consistent with the final status
     fn update_to_final_status_and_update_counter(&mut self, tx_id: &TransactionId, status: TransactionStatus) {
-        // if the status is not failure or success, we need to panic
-        if status != TransactionStatus::Failure && status != TransactionStatus::Success {
+        // if the status is not a final status, we need to panic
+        if !status.is_final() {
             panic!("BUG: Transaction {} is not being updated to a final status", tx_id.0);
         }
         // Update the transaction status first
-        self.transaction_statuses.insert(tx_id.clone(), status);
+        self.transaction_statuses.insert(tx_id.clone(), status.clone());
+        self.record_status_transition(tx_id, status);
         
         // Decrement the pending counter since the transaction is no longer pending
         self.decrement_count_pending(tx_id);
@@ -209,6 +341,9 @@ impl HyperIGState {
         
         // Remove from pending set since the transaction has reached a final status
         self.pending_transactions.remove(tx_id);
+        if let Some(timeline) = self.tx_timelines.get_mut(tx_id) {
+            timeline.finalized_at = Some(std::time::Instant::now());
+        }
         
         // CRITICAL: Calculate timing metrics for regular transactions
         if let Some(start_time) = self.tx_pending_start_time.remove(tx_id) {
@@ -216,7 +351,8 @@ impl HyperIGState {
             // A transaction is a CAT if it exists as a value in cat_to_tx_id
             let is_cat = self.cat_to_tx_id.values().any(|cat_tx_id| cat_tx_id == tx_id);
             if !is_cat {
-                let latency_ms = start_time.elapsed().as_millis() as f64;
+                // Latency is measured with the local clock
+                let latency_ms = self.clock_skew.local_duration(start_time.elapsed()).as_millis() as f64;
                 let n = self.count_regular_tx_finalized;
                 // Update running average: new_avg = (old_avg * n + new_value) / (n + 1)
                 self.latency_regular_tx_finalized = (self.latency_regular_tx_finalized * n as f64 + latency_ms) / (n as f64 + 1.0);
@@ -237,6 +373,131 @@ impl HyperIGState {
             }
         }
     }
+
+    /// Removes the dependencies of a transaction, so that it no longer waits on keys or other transactions
+    fn remove_transaction_dependencies(&mut self, tx_id: &TransactionId) {
+        // OPTIMIZED: Use reverse index for O(1) key cleanup instead of O(n) search
+        let keys_to_cleanup: Vec<String> = if let Some(keys) = self.tx_depends_on_keys.get(tx_id) {
+            keys.iter().cloned().collect()
+        } else {
+            Vec::new()
+        };
+        
+        // Clean up key dependencies
+        for key in keys_to_cleanup {
+            if let Some(tx_list) = self.key_causes_dependencies_for_txs.get_mut(&key) {
+                tx_list.retain(|tx| tx != tx_id);
+                if tx_list.is_empty() {
+                    self.key_causes_dependencies_for_txs.remove(&key);
+                }
+            }
+        }
+        
+        // Clean up the reverse index
+        self.tx_depends_on_keys.remove(tx_id);
+        
+        // Remove from transaction dependency tracking
+        self.tx_depends_on_txs.remove(tx_id);
+        self.end_lock_wait(tx_id);
+    }
+
+    /// Hands the key locks and consumers of a pending transaction that leaves early over to the
+    /// transactions it waits on, so its consumers stay blocked until those complete.
+    /// 
+    /// # Returns
+    /// False if the transaction waits on no other transaction, so its locks have to be released instead
+    fn hand_over_locks(&mut self, tx_id: &TransactionId) -> bool {
+        let blockers = self.tx_depends_on_txs.get(tx_id).cloned().unwrap_or_default();
+        if blockers.is_empty() {
+            return false;
+        }
+
+        // Keys last locked by the transaction go back to a blocker that locks them
+        for key in self.tx_locks_keys.remove(tx_id).unwrap_or_default() {
+            if self.key_last_locked_by_tx.get(&key) != Some(tx_id) {
+                continue;
+            }
+            let previous_locker = blockers.iter()
+                .find(|blocker| self.tx_locks_keys.get(*blocker).is_some_and(|keys| keys.contains(&key)))
+                .cloned();
+            match previous_locker {
+                Some(blocker) => { self.key_last_locked_by_tx.insert(key, blocker); }
+                None => { self.key_last_locked_by_tx.remove(&key); }
+            }
+        }
+
+        // Consumers wait on the blockers instead
+        for consumer in self.tx_locks_consumer.remove(tx_id).unwrap_or_default() {
+            if let Some(dependencies) = self.tx_depends_on_txs.get_mut(&consumer) {
+                dependencies.remove(tx_id);
+                dependencies.extend(blockers.iter().cloned());
+            }
+            for blocker in &blockers {
+                self.tx_locks_consumer.entry(blocker.clone()).or_default().insert(consumer.clone());
+            }
+        }
+        true
+    }
+
+    /// Ends the lock wait of a transaction and adds the time it waited to the keys that blocked it
+    fn end_lock_wait(&mut self, tx_id: &TransactionId) {
+        if let Some((blocked_since, keys)) = self.tx_blocked_since.remove(tx_id) {
+            let wait = blocked_since.elapsed();
+            if let Some(timeline) = self.tx_timelines.get_mut(tx_id) {
+                timeline.lock_wait += wait;
+                for key in &keys {
+                    if let Err(index) = timeline.blocked_keys.binary_search(key) {
+                        timeline.blocked_keys.insert(index, key.clone());
+                    }
+                }
+            }
+            for key in keys {
+                let key_lock_wait = self.key_lock_waits.entry(key.clone())
+                    .or_insert_with(|| KeyLockWait { key, ..Default::default() });
+                key_lock_wait.total_wait += wait;
+                key_lock_wait.max_wait = key_lock_wait.max_wait.max(wait);
+                key_lock_wait.blocked_count += 1;
+            }
+        }
+    }
+}
+
+/// Finds a chain of dependencies from one transaction to another.
+/// 
+/// # Arguments
+/// * `depends_on` - Map of transaction IDs to the transaction IDs they depend on
+/// * `from` - The transaction to start from
+/// * `to` - The transaction to reach
+/// 
+/// # Returns
+/// The transactions on the shortest chain, starting with `from` and ending with `to`, or None if
+/// `from` does not (transitively) depend on `to`
+pub(crate) fn find_dependency_path(
+    depends_on: &HashMap<TransactionId, HashSet<TransactionId>>,
+    from: &TransactionId,
+    to: &TransactionId,
+) -> Option<Vec<TransactionId>> {
+    let mut predecessors: HashMap<&TransactionId, &TransactionId> = HashMap::new();
+    let mut queue = VecDeque::from([from]);
+    while let Some(current) = queue.pop_front() {
+        if current == to {
+            let mut path = vec![current.clone()];
+            let mut node = current;
+            while let Some(&predecessor) = predecessors.get(node) {
+                path.push(predecessor.clone());
+                node = predecessor;
+            }
+            path.reverse();
+            return Some(path);
+        }
+        for dependency in depends_on.get(current).into_iter().flatten() {
+            if dependency != from && !predecessors.contains_key(dependency) {
+                predecessors.insert(dependency, current);
+                queue.push_back(dependency);
+            }
+        }
+    }
+    None
 }
 
 /// Node implementation of the Hyper Information Gateway
@@ -249,8 +510,20 @@ pub struct HyperIGNode {
     sender_hig_to_hs: Option<mpsc::Sender<CATStatusUpdate>>,
     /// Delay for sending messages to HS (in milliseconds)
     hs_message_delay: Duration,
+    /// Distribution of the delay of each proposal around `hs_message_delay`
+    hs_message_delay_distribution: DelayDistribution,
+    /// Backoff and maximum attempts for proposals whose send to the HS failed
+    proposal_retry_policy: ProposalRetryPolicy,
     /// Flag to control the background queue processor
     queue_processor_running: Arc<Mutex<bool>>,
+    /// Clock the HS message delay and the proposal retries elapse on
+    clock: Arc<dyn Clock>,
+    /// Block height CAT timeouts and transaction expiries are judged by
+    block_height_source: Arc<dyn BlockHeightSource>,
+    /// The message processing loop, once started
+    message_loop: Option<JoinHandle<()>>,
+    /// The proposal queue processor, once started
+    queue_processor: Option<JoinHandle<()>>,
 }
 
 //==============================================================================
@@ -287,13 +560,26 @@ impl HyperIGNode {
     /// # Returns
     /// A new HyperIGNode instance with preloaded accounts
     pub fn new_with_preloaded_accounts(receiver_cl_to_hig: mpsc::Receiver<SubBlock>, sender_hig_to_hs: mpsc::Sender<CATStatusUpdate>, my_chain_id: ChainId, cat_lifetime: u64, allow_cat_pending_dependencies: bool, num_accounts: u32, preload_value: u32) -> Self {
+        let balances: Vec<(u32, u32)> = (1..=num_accounts).map(|account_id| (account_id, preload_value)).collect();
+        Self::new_with_account_balances(receiver_cl_to_hig, sender_hig_to_hs, my_chain_id, cat_lifetime, allow_cat_pending_dependencies, &balances)
+    }
+
+    /// Creates a new HyperIGNode instance whose VM starts with the given account balances.
+    /// 
+    /// # Arguments
+    /// * `receiver_cl_to_hig` - Channel receiver for messages from Confirmation Layer
+    /// * `sender_hig_to_hs` - Channel sender for messages to Hyper Scheduler
+    /// * `my_chain_id` - The chain ID this node is responsible for
+    /// * `cat_lifetime` - The default lifetime for CATs in blocks
+    /// * `allow_cat_pending_dependencies` - Whether CATs can depend on pending transactions
+    /// * `balances` - The (account, balance) pairs to preload
+    /// 
+    /// # Returns
+    /// A new HyperIGNode instance with preloaded accounts
+    pub fn new_with_account_balances(receiver_cl_to_hig: mpsc::Receiver<SubBlock>, sender_hig_to_hs: mpsc::Sender<CATStatusUpdate>, my_chain_id: ChainId, cat_lifetime: u64, allow_cat_pending_dependencies: bool, balances: &[(u32, u32)]) -> Self {
         let mut vm = MockVM::new();
-        
-        // Preload accounts if specified
-        if num_accounts > 0 {
-            for account_id in 1..=num_accounts {
-                vm.preload_account(account_id, preload_value);
-            }
+        for &(account_id, balance) in balances {
+            vm.preload_account(account_id, balance);
         }
         
         Self {
@@ -310,31 +596,77 @@ impl HyperIGNode {
                 key_causes_dependencies_for_txs: HashMap::new(),
                 tx_depends_on_txs: HashMap::new(),
                 tx_depends_on_keys: HashMap::new(),
+                tx_blocked_since: HashMap::new(),
+                ever_blocked_txs: HashSet::new(),
+                key_lock_waits: HashMap::new(),
                 tx_pending_start_time: HashMap::new(),
+                tx_timelines: HashMap::new(),
+                cat_audit_trails: HashMap::new(),
                 received_txs: HashMap::new(),
                 my_chain_id: my_chain_id.clone(),
                 vm,
                 pending_proposals: VecDeque::new(),
+                proposal_queue_max_len: None,
+                proposal_queue_overflow_policy: ProposalQueueOverflowPolicy::default(),
+                count_dropped_proposals: 0,
+                dead_letter_proposals: Vec::new(),
+                realized_hs_message_delays: Vec::new(),
                 cat_max_lifetime: HashMap::new(),
                 cat_lifetime: cat_lifetime,
                 current_block_height: 0,
                 allow_cat_pending_dependencies,
+                halted: false,
+                epoch: 0,
+                cat_epochs: HashMap::new(),
+                crashed: false,
+                count_lost_subblocks: 0,
+                count_replayed_subblocks: 0,
+                replayed_through_height: None,
                 count_cat_pending: 0,
                 count_cat_success: 0,
                 count_cat_failure: 0,
+                count_cat_timed_out: 0,
+                count_cat_rejected: 0,
                 count_cat_pending_postponed: 0,
                 count_cat_pending_resolving: 0,
                 count_regular_pending: 0,
                 count_regular_success: 0,
                 count_regular_failure: 0,
+                count_regular_expired: 0,
                 latency_regular_tx_finalized: 0.0,
                 max_latency_regular_tx_finalized: 0.0,
                 count_regular_tx_finalized: 0,
+                count_rejected_status_updates: 0,
+                count_malformed_txs: 0,
+                protocol_violation_policy: ProtocolViolationPolicy::default(),
+                duplicate_transaction_policy: DuplicateTransactionPolicy::default(),
+                count_duplicate_txs: 0,
+                cat_id_collision_policy: CatIdCollisionPolicy::default(),
+                count_cat_id_collisions: 0,
+                dependency_cycle_policy: DependencyCyclePolicy::default(),
+                dependency_cycles: Vec::new(),
+                cycle_victims: Vec::new(),
+                release_locks_on_failure_proposal: false,
+                count_early_lock_releases: 0,
+                prioritize_status_updates: false,
+                count_protocol_violations: 0,
+                escalated_protocol_violations: Vec::new(),
+                status_transitions: HashMap::new(),
+                state_roots: HashMap::new(),
+                subblock_timings: HashMap::new(),
+                proposal_queuing_in_subblock: Duration::ZERO,
+                clock_skew: ClockSkew::default(),
             })),
             receiver_cl_to_hig: Some(receiver_cl_to_hig),
             sender_hig_to_hs: Some(sender_hig_to_hs),
             hs_message_delay: Duration::from_millis(0), // Default 0ms delay
+            hs_message_delay_distribution: DelayDistribution::Constant,
+            proposal_retry_policy: ProposalRetryPolicy::default(),
             queue_processor_running: Arc::new(Mutex::new(false)),
+            clock: Arc::new(SystemClock::new()),
+            block_height_source: Arc::new(SubblockHeight),
+            message_loop: None,
+            queue_processor: None,
         }
     }
 
@@ -354,6 +686,262 @@ impl HyperIGNode {
         self.state.lock().await.allow_cat_pending_dependencies = allow;
     }
 
+    /// Sets the lifetime in blocks of the CATs received from now on.
+    /// 
+    /// CATs that are already pending keep the maximum lifetime they were received with.
+    /// 
+    /// # Arguments
+    /// * `cat_lifetime` - The new lifetime for CATs in blocks
+    pub async fn set_cat_lifetime(&self, cat_lifetime: u64) {
+        self.state.lock().await.cat_lifetime = cat_lifetime;
+    }
+
+    /// Sets the balances of the given accounts in the VM, as if they had been preloaded.
+    /// 
+    /// Accounts that are not listed keep their balance. The keys of pending transactions stay
+    /// locked, so a pending transaction on a reloaded account executes on the new balance.
+    /// 
+    /// # Arguments
+    /// * `balances` - The (account, balance) pairs to set
+    pub async fn preload_account_balances(&self, balances: &[(u32, u32)]) {
+        let mut state = self.state.lock().await;
+        for &(account_id, balance) in balances {
+            state.vm.preload_account(account_id, balance);
+        }
+    }
+
+    /// Gets a snapshot of the liveness of the node: whether its loops are running, the last
+    /// processed block height, the pending transactions and proposals and the state of its channels.
+    pub async fn health(&self) -> HyperIGHealth {
+        let state = self.state.lock().await;
+        HyperIGHealth {
+            chain_id: state.my_chain_id.clone(),
+            message_loop_running: self.message_loop.as_ref().is_some_and(|task| !task.is_finished()),
+            queue_processor_running: self.queue_processor.as_ref().is_some_and(|task| !task.is_finished()),
+            halted: state.halted,
+            current_block_height: state.current_block_height,
+            pending_transactions: state.pending_transactions.len(),
+            proposal_queue_depth: state.pending_proposals.len(),
+            cl_channel: self.receiver_cl_to_hig.as_ref().map(ChannelHealth::of_receiver),
+            hs_channel: self.sender_hig_to_hs.as_ref().map(ChannelHealth::of_sender),
+        }
+    }
+
+    /// Gets whether the node is halted.
+    pub async fn is_halted(&self) -> bool {
+        self.state.lock().await.halted
+    }
+
+    /// Halts or resumes the node.
+    /// 
+    /// A halted node neither processes subblocks nor sends proposals to the HS, as if it had crashed.
+    /// The subblocks and proposals stay queued, so a resumed node continues from the state it had,
+    /// as after a restart from persisted state.
+    /// 
+    /// # Arguments
+    /// * `halted` - Whether the node is halted
+    pub async fn set_halted(&self, halted: bool) {
+        let mut state = self.state.lock().await;
+        state.halted = halted;
+        state.crashed = false;
+    }
+
+    /// Sets the HS epoch the CATs received from now on belong to.
+    /// 
+    /// Proposals carry the epoch of their CAT, so the HS keeps them apart from the CATs of
+    /// later runs that reuse the ID.
+    /// 
+    /// # Arguments
+    /// * `epoch` - The epoch, as returned by the HS when it advanced
+    pub async fn set_epoch(&self, epoch: u64) {
+        self.state.lock().await.epoch = epoch;
+    }
+
+    /// Gets the HS epoch the CATs received from now on belong to.
+    pub async fn get_epoch(&self) -> u64 {
+        self.state.lock().await.epoch
+    }
+
+    /// Crashes the node.
+    /// 
+    /// Unlike a halted node, a crashed node loses the subblocks delivered to it until it restarts,
+    /// as they would be lost with the process. Its state and queued proposals are kept and stand
+    /// in for the snapshot it restarts from.
+    pub async fn crash(&self) {
+        let mut state = self.state.lock().await;
+        state.halted = true;
+        state.crashed = true;
+    }
+
+    /// Restarts a halted or crashed node and catches up on the subblocks it missed.
+    /// 
+    /// Requests the subblocks after the last processed block height from the CL, replays them and
+    /// only then resumes live processing. From the moment of the request the live subblocks stay
+    /// queued, so none is missed; those already replayed are skipped when they are received.
+    /// 
+    /// # Arguments
+    /// * `hig_node` - The node to restart
+    /// * `cl` - The confirmation layer to request the missed subblocks from
+    /// 
+    /// # Returns
+    /// The number of replayed subblocks
+    pub async fn restart<C: ConfirmationLayer + ?Sized>(hig_node: &Arc<Mutex<Self>>, cl: &C) -> Result<u64, HyperIGError> {
+        let (chain_id, from_height) = {
+            let node = hig_node.lock().await;
+            let mut state = node.state.lock().await;
+            state.crashed = false;
+            (state.my_chain_id.clone(), state.current_block_height + 1)
+        };
+        // The HIG lock is not held while waiting for the CL, which may itself wait on the HIG's channel
+        let mut subblocks = cl.stream_subblocks(chain_id.clone(), from_height, u64::MAX).await
+            .map_err(|e| HyperIGError::Communication(format!("Failed to request the missed subblocks: {}", e)))?;
+
+        let mut replayed = 0;
+        let mut replayed_through_height = None;
+        while let Some(subblock) = subblocks.recv().await {
+            let block_height = subblock.block_height;
+            if let Err(e) = hig_node.lock().await.process_subblock(subblock).await {
+                log(&format!("HIG-{}", chain_id), &format!("Error replaying subblock {}: {}", block_height, e));
+            }
+            replayed += 1;
+            replayed_through_height = Some(block_height);
+        }
+
+        let node = hig_node.lock().await;
+        let mut state = node.state.lock().await;
+        if replayed_through_height.is_some() {
+            state.replayed_through_height = replayed_through_height;
+        }
+        state.count_replayed_subblocks += replayed;
+        state.halted = false;
+        log(&format!("HIG-{}", chain_id), &format!("Restarted after replaying {} missed subblocks from block {}", replayed, from_height));
+        Ok(replayed)
+    }
+
+    /// Gets the number of subblocks lost while the node was crashed.
+    pub async fn get_lost_subblock_count(&self) -> u64 {
+        self.state.lock().await.count_lost_subblocks
+    }
+
+    /// Gets the number of subblocks replayed from the CL when the node restarted.
+    pub async fn get_replayed_subblock_count(&self) -> u64 {
+        self.state.lock().await.count_replayed_subblocks
+    }
+
+    /// 
+    /// # Returns
+    /// The current protocol violation policy
+    pub async fn get_protocol_violation_policy(&self) -> ProtocolViolationPolicy {
+        self.state.lock().await.protocol_violation_policy
+    }
+
+    /// Sets the policy for handling protocol violations.
+    /// 
+    /// # Arguments
+    /// * `policy` - The new protocol violation policy
+    pub async fn set_protocol_violation_policy(&self, policy: ProtocolViolationPolicy) {
+        self.state.lock().await.protocol_violation_policy = policy;
+    }
+
+    /// Gets the policy for handling transactions whose id was already received.
+    pub async fn get_duplicate_transaction_policy(&self) -> DuplicateTransactionPolicy {
+        self.state.lock().await.duplicate_transaction_policy
+    }
+
+    /// Sets the policy for handling transactions whose id was already received.
+    /// 
+    /// # Arguments
+    /// * `policy` - The new duplicate transaction policy
+    pub async fn set_duplicate_transaction_policy(&self, policy: DuplicateTransactionPolicy) {
+        self.state.lock().await.duplicate_transaction_policy = policy;
+    }
+
+    /// Gets the policy for handling CATs whose id is already used by another CAT.
+    pub async fn get_cat_id_collision_policy(&self) -> CatIdCollisionPolicy {
+        self.state.lock().await.cat_id_collision_policy
+    }
+
+    /// Sets the policy for handling CATs whose id is already used by another CAT.
+    /// 
+    /// # Arguments
+    /// * `policy` - The new CAT id collision policy
+    pub async fn set_cat_id_collision_policy(&self, policy: CatIdCollisionPolicy) {
+        self.state.lock().await.cat_id_collision_policy = policy;
+    }
+
+    /// Gets the policy for breaking dependency cycles.
+    pub async fn get_dependency_cycle_policy(&self) -> DependencyCyclePolicy {
+        self.state.lock().await.dependency_cycle_policy
+    }
+
+    /// Sets the policy for breaking dependency cycles.
+    /// 
+    /// # Arguments
+    /// * `policy` - The new dependency cycle policy
+    pub async fn set_dependency_cycle_policy(&self, policy: DependencyCyclePolicy) {
+        self.state.lock().await.dependency_cycle_policy = policy;
+    }
+
+    /// Gets whether a CAT releases its key locks as soon as it proposes Failure.
+    pub async fn get_release_locks_on_failure_proposal(&self) -> bool {
+        self.state.lock().await.release_locks_on_failure_proposal
+    }
+
+    /// Sets whether a CAT releases its key locks as soon as it proposes Failure.
+    /// 
+    /// A Failure proposal guarantees that the HS decides Failure, so the CAT never writes the keys
+    /// and transactions waiting on them can proceed before the status update arrives.
+    /// 
+    /// # Arguments
+    /// * `release` - Whether to release the locks early
+    pub async fn set_release_locks_on_failure_proposal(&self, release: bool) {
+        self.state.lock().await.release_locks_on_failure_proposal = release;
+    }
+
+    /// Gets whether status updates are processed before the other transactions of a subblock.
+    pub async fn get_prioritize_status_updates(&self) -> bool {
+        self.state.lock().await.prioritize_status_updates
+    }
+
+    /// Sets whether status updates are processed before the other transactions of a subblock.
+    /// 
+    /// A status update releases the locks of its CAT, so processing it first lets the regular
+    /// transactions of the same subblock run against the released keys instead of waiting another block.
+    /// The relative order of the status updates and of the other transactions is kept.
+    /// 
+    /// # Arguments
+    /// * `prioritize` - Whether to process status updates first
+    pub async fn set_prioritize_status_updates(&self, prioritize: bool) {
+        self.state.lock().await.prioritize_status_updates = prioritize;
+    }
+
+    /// Sets the clock the HS message delay and the proposal retries elapse on.
+    /// 
+    /// # Arguments
+    /// * `clock` - The clock, e.g. a `SimulatedClock` for runs in virtual time
+    pub fn set_clock(&mut self, clock: Arc<dyn Clock>) {
+        self.clock = clock;
+    }
+
+    /// Sets the source of the block height CAT timeouts and transaction expiries are judged by.
+    /// 
+    /// # Arguments
+    /// * `source` - The block height source, e.g. a `ManualBlockHeight` to control timeouts in tests
+    pub fn set_block_height_source(&mut self, source: Arc<dyn BlockHeightSource>) {
+        self.block_height_source = source;
+    }
+
+    /// Checks CAT timeouts and transaction expiries at the height of the block height source,
+    /// without waiting for the next subblock.
+    pub async fn check_timeouts(&mut self) {
+        let local_block_height = {
+            let state = self.state.lock().await;
+            state.clock_skew.local_block_height(self.block_height_source.block_height(state.current_block_height))
+        };
+        self.check_cat_timeouts(local_block_height).await;
+        self.check_transaction_expiries(local_block_height).await;
+    }
+
     /// Updates the delay for sending messages to Hyper Scheduler.
     /// 
     /// # Arguments
@@ -370,10 +958,66 @@ impl HyperIGNode {
         self.hs_message_delay
     }
 
-    /// Checks for timed out CATs and updates their status to Failure.
+    /// Sets the distribution the delay of each proposal to the HS is drawn from.
     /// 
     /// # Arguments
-    /// * `current_block_height` - The current block height
+    /// * `distribution` - The distribution, centered on the HS message delay
+    pub fn set_hs_message_delay_distribution(&mut self, distribution: DelayDistribution) {
+        self.hs_message_delay_distribution = distribution;
+    }
+
+    /// Gets the distribution the delay of each proposal to the HS is drawn from.
+    pub fn get_hs_message_delay_distribution(&self) -> DelayDistribution {
+        self.hs_message_delay_distribution
+    }
+
+    /// Limits the length of the pending-proposal queue.
+    /// 
+    /// # Arguments
+    /// * `max_len` - The maximum number of queued proposals (None = unbounded)
+    /// * `policy` - What to do with a proposal that arrives while the queue is full
+    pub async fn set_proposal_queue_limit(&self, max_len: Option<usize>, policy: ProposalQueueOverflowPolicy) {
+        let mut state = self.state.lock().await;
+        state.proposal_queue_max_len = max_len;
+        state.proposal_queue_overflow_policy = policy;
+    }
+
+    /// Gets the maximum length and overflow policy of the pending-proposal queue.
+    pub async fn get_proposal_queue_limit(&self) -> (Option<usize>, ProposalQueueOverflowPolicy) {
+        let state = self.state.lock().await;
+        (state.proposal_queue_max_len, state.proposal_queue_overflow_policy)
+    }
+
+    /// Sets the retry policy for proposals whose send to the HS failed.
+    /// 
+    /// # Arguments
+    /// * `policy` - The backoff and maximum attempts
+    pub fn set_proposal_retry_policy(&mut self, policy: ProposalRetryPolicy) {
+        self.proposal_retry_policy = policy;
+    }
+
+    /// Gets the retry policy for proposals whose send to the HS failed.
+    pub fn get_proposal_retry_policy(&self) -> ProposalRetryPolicy {
+        self.proposal_retry_policy
+    }
+
+    /// Sets the skew of the local clock relative to the CL.
+    /// 
+    /// # Arguments
+    /// * `skew` - The clock offset (in blocks) and drift
+    pub async fn set_clock_skew(&self, skew: ClockSkew) {
+        self.state.lock().await.clock_skew = skew;
+    }
+
+    /// Gets the skew of the local clock relative to the CL.
+    pub async fn get_clock_skew(&self) -> ClockSkew {
+        self.state.lock().await.clock_skew
+    }
+
+    /// Checks for timed out CATs and updates their status to TimedOut.
+    /// 
+    /// # Arguments
+    /// * `current_block_height` - The current block height as perceived by the local clock
     async fn check_cat_timeouts(&mut self, current_block_height: u64) {
         let mut state = self.state.lock().await;
         let chain_id = state.my_chain_id.0.clone();
@@ -397,49 +1041,33 @@ impl HyperIGNode {
 
         // Update status for timed out CATs
         let mut timed_out_tx_ids: Vec<TransactionId> = Vec::new();
+        let mut unproposed_cats: Vec<(CATId, Vec<ChainId>)> = Vec::new();
         for (cat_id, tx_id) in timed_out_cats {
             let max_lifetime = state.cat_max_lifetime.get(&cat_id).unwrap_or(&state.cat_lifetime);
             let cat_creation_block = max_lifetime.saturating_sub(state.cat_lifetime);
             let blocks_since_creation = current_block_height.saturating_sub(cat_creation_block);
             log(&format!("HIG-{}", chain_id), &format!("⏰ TIMEOUT: CAT '{}' timed out at block height {} (created at block {}, lived for {} blocks, max_lifetime: {}, cat_lifetime: {})", 
                 cat_id.0, current_block_height, cat_creation_block, blocks_since_creation, max_lifetime, state.cat_lifetime));
+            let max_lifetime = *max_lifetime;
             
             // Ensure the transaction exists in received_txs before setting status
             if !state.received_txs.contains_key(&tx_id) {
                 panic!("TIMEOUT BUG: Transaction {} not found in received_txs when setting timeout failure status", tx_id.0);
             }
             
-            // Update transaction status to Failure and increment counter
-            state.update_to_final_status_and_update_counter(&tx_id, TransactionStatus::Failure);
+            // Update transaction status to TimedOut and increment counter
+            state.update_to_final_status_and_update_counter(&tx_id, TransactionStatus::TimedOut);
+            state.record_cat_audit(&cat_id, CatAuditEvent::TimedOut { max_lifetime });
             
             // Remove from last update tracking
             state.cat_max_lifetime.remove(&cat_id);
             
             // Remove from dependency tracking maps to prevent reprocessing
-            state.cat_proposed_statuses.remove(&tx_id);
-            
-            // OPTIMIZED: Use reverse index for O(1) key cleanup instead of O(n) search
-            let keys_to_cleanup: Vec<String> = if let Some(keys) = state.tx_depends_on_keys.get(&tx_id) {
-                keys.iter().cloned().collect()
-            } else {
-                Vec::new()
-            };
-            
-            // Clean up key dependencies
-            for key in keys_to_cleanup {
-                if let Some(tx_list) = state.key_causes_dependencies_for_txs.get_mut(&key) {
-                    tx_list.retain(|tx| tx != &tx_id);
-                    if tx_list.is_empty() {
-                        state.key_causes_dependencies_for_txs.remove(&key);
-                    }
-                }
+            // A CAT still postponed has not proposed yet, so it lets the HS know it timed out
+            if state.cat_proposed_statuses.remove(&tx_id) == Some(CATStatus::Pending) {
+                unproposed_cats.push((cat_id.clone(), state.received_txs[&tx_id].constituent_chains.clone()));
             }
-            
-            // Clean up the reverse index
-            state.tx_depends_on_keys.remove(&tx_id);
-            
-            // Remove from transaction dependency tracking
-            state.tx_depends_on_txs.remove(&tx_id);
+            state.remove_transaction_dependencies(&tx_id);
             
             // Collect the transaction ID for later processing
             timed_out_tx_ids.push(tx_id);
@@ -448,8 +1076,52 @@ impl HyperIGNode {
         // Process any transactions that were waiting on timed out CATs
         // This is done after ALL CATs have been timed out to avoid reprocessing
         drop(state); // Release lock before async call
+        for (cat_id, constituent_chains) in unproposed_cats {
+            self.send_cat_status_proposal(cat_id, CATStatusLimited::TimedOut, constituent_chains).await
+                .unwrap_or_else(|e| log(&format!("HIG-{}", chain_id), &format!("Error sending timeout proposal: {}", e)));
+        }
         for tx_id in timed_out_tx_ids {
-            self.process_pending_transactions(tx_id, TransactionStatus::Failure).await
+            self.process_pending_transactions(tx_id, TransactionStatus::TimedOut).await
+                .unwrap_or_else(|e| log(&format!("HIG-{}", chain_id), &format!("Error processing pending transactions: {}", e)));
+        }
+    }
+
+    /// Checks for pending regular transactions past their expiry and updates their status to Expired.
+    /// 
+    /// An expired transaction releases the keys it locked while blocked, so the transactions
+    /// waiting on it are processed.
+    /// 
+    /// # Arguments
+    /// * `current_block_height` - The current block height as perceived by the local clock
+    async fn check_transaction_expiries(&mut self, current_block_height: u64) {
+        let mut state = self.state.lock().await;
+        let chain_id = state.my_chain_id.0.clone();
+
+        let mut expired_tx_ids: Vec<TransactionId> = state.pending_transactions.iter()
+            .filter(|tx_id| !state.tx_to_cat_id.contains_key(*tx_id))
+            .filter(|tx_id| state.received_txs.get(*tx_id)
+                .and_then(|tx| tx.expiry)
+                .is_some_and(|expiry| current_block_height > expiry))
+            .cloned()
+            .collect();
+        // Process in a deterministic order
+        expired_tx_ids.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut released_tx_ids = Vec::new();
+        for tx_id in expired_tx_ids {
+            log(&format!("HIG-{}", chain_id), &format!("⏰ EXPIRED: Regular transaction '{}' expired at block height {}", tx_id.0, current_block_height));
+            state.update_to_final_status_and_update_counter(&tx_id, TransactionStatus::Expired);
+            // The transactions waiting on the expired one keep waiting on what blocked it
+            if !state.hand_over_locks(&tx_id) {
+                released_tx_ids.push(tx_id.clone());
+            }
+            state.remove_transaction_dependencies(&tx_id);
+        }
+
+        // Release the keys of expired transactions that no longer waited on anything
+        drop(state); // Release lock before async call
+        for tx_id in released_tx_ids {
+            self.process_pending_transactions(tx_id, TransactionStatus::Expired).await
                 .unwrap_or_else(|e| log(&format!("HIG-{}", chain_id), &format!("Error processing pending transactions: {}", e)));
         }
     }
@@ -478,10 +1150,15 @@ impl HyperIGNode {
         let node_for_queue = node.clone();
         
         // Start the message processing loop
-        tokio::spawn(async move { HyperIGNode::process_messages(node_for_messages).await.unwrap() });
+        let message_loop = tokio::spawn(async move { HyperIGNode::process_messages(node_for_messages).await.unwrap() });
         
         // Start the queue processor
-        tokio::spawn(async move { HyperIGNode::process_proposal_queue(node_for_queue).await.unwrap() });
+        let queue_processor = tokio::spawn(async move { HyperIGNode::process_proposal_queue(node_for_queue).await.unwrap() });
+        
+        // Keep the handles so the health of the loops can be checked
+        let mut node_guard = node.lock().await;
+        node_guard.message_loop = Some(message_loop);
+        node_guard.queue_processor = Some(queue_processor);
     }
 
     /// Shuts down the node by stopping background tasks and clearing state.
@@ -519,25 +1196,55 @@ impl HyperIGNode {
             state.pending_transactions.clear();
             state.cat_proposed_statuses.clear();
             state.cat_to_tx_id.clear();
+            state.cat_epochs.clear();
             state.tx_to_cat_id.clear();
             state.key_last_locked_by_tx.clear();
             state.tx_locks_keys.clear();
             state.key_causes_dependencies_for_txs.clear();
             state.tx_depends_on_txs.clear();
             state.tx_depends_on_keys.clear();
+            state.tx_blocked_since.clear();
+            state.ever_blocked_txs.clear();
+            state.key_lock_waits.clear();
+            state.tx_timelines.clear();
+            state.cat_audit_trails.clear();
             state.pending_proposals.clear();
+            state.count_dropped_proposals = 0;
+            state.dead_letter_proposals.clear();
+            state.realized_hs_message_delays.clear();
             state.cat_max_lifetime.clear();
             state.current_block_height = 0;
+            state.count_lost_subblocks = 0;
+            state.count_replayed_subblocks = 0;
+            state.replayed_through_height = None;
+            state.clock_skew = ClockSkew::default();
+            state.epoch = 0;
             
             // Reset CAT counters
             state.count_cat_pending = 0;
             state.count_cat_success = 0;
             state.count_cat_failure = 0;
+            state.count_cat_timed_out = 0;
+            state.count_cat_rejected = 0;
             state.count_cat_pending_postponed = 0;
             state.count_cat_pending_resolving = 0;
             state.count_regular_pending = 0;
             state.count_regular_success = 0;
             state.count_regular_failure = 0;
+            state.count_regular_expired = 0;
+            state.count_rejected_status_updates = 0;
+            state.count_malformed_txs = 0;
+            state.count_duplicate_txs = 0;
+            state.count_cat_id_collisions = 0;
+            state.dependency_cycles.clear();
+            state.cycle_victims.clear();
+            state.count_early_lock_releases = 0;
+            state.count_protocol_violations = 0;
+            state.escalated_protocol_violations.clear();
+            state.status_transitions.clear();
+            state.state_roots.clear();
+            state.subblock_timings.clear();
+            state.proposal_queuing_in_subblock = Duration::ZERO;
             
             // Reset VM state
             state.vm = MockVM::new();
@@ -585,36 +1292,38 @@ impl HyperIGNode {
                 break;
             }
             
-            // Get the next proposal from the queue
+            // Get the next proposal from the queue, unless the node is halted
             let proposal = {
                 let node = hig_node.lock().await;
                 let mut state = node.state.lock().await;
-                state.pending_proposals.pop_front()
+                if state.halted { None } else { state.pending_proposals.pop_front() }
             };
             
-            if let Some(proposal) = proposal {
-                // Get the delay duration
-                let delay = {
+            if let Some(mut proposal) = proposal {
+                // Get the delay of the proposal (drawn once it reaches the front of the queue), the local clock skew and the clock
+                let (delay, clock_skew, clock) = {
                     let node = hig_node.lock().await;
-                    node.hs_message_delay
+                    let clock_skew = node.state.lock().await.clock_skew;
+                    let delay = *proposal.delay.get_or_insert_with(|| node.hs_message_delay_distribution.sample(node.hs_message_delay));
+                    (delay, clock_skew, node.clock.clone())
                 };
                 
-                // Get the chain ID for the status update
-                let chain_id = {
+                // Get the chain ID and the processed block height for the status update
+                let (chain_id, block_height) = {
                     let node = hig_node.lock().await;
                     let state = node.state.lock().await;
-                    state.my_chain_id.clone()
+                    (state.my_chain_id.clone(), state.current_block_height)
                 };
                 
-                // Check if enough time has passed since the proposal entered the queue
-                let elapsed_since_queue_entry = proposal.queue_entry_time.elapsed();
+                // Check if enough time has passed since the proposal entered the queue (measured with the local clock)
+                let elapsed_since_queue_entry = clock_skew.local_duration(clock.now().saturating_sub(proposal.queue_entry_time));
                 if elapsed_since_queue_entry < delay {
                     // Not enough time has passed, put the proposal back at the front of the queue
                     let node = hig_node.lock().await;
                     let mut state = node.state.lock().await;
                     state.pending_proposals.push_front(proposal);
                     // Wait a bit before checking again
-                    tokio::time::sleep(Duration::from_millis(10)).await;
+                    clock.sleep(Duration::from_millis(10)).await;
                     continue;
                 }
                 
@@ -624,6 +1333,8 @@ impl HyperIGNode {
                     chain_id: chain_id.clone(),
                     status: proposal.status.clone(),
                     constituent_chains: proposal.constituent_chains.clone(),
+                    epoch: proposal.epoch,
+                    block_height,
                 };
                 
                 // Send the status update (delay already satisfied)
@@ -637,15 +1348,49 @@ impl HyperIGNode {
                 };
                 
                 if let Err(e) = send_result {
-                    log(&format!("HIG-{}", chain_id), &format!("Error sending status update: {}", e));
-                    // Put the proposal back in the queue
+                    proposal.failed_attempts += 1;
+                    let backoff = {
+                        let node = hig_node.lock().await;
+                        let policy = node.proposal_retry_policy;
+                        let mut state = node.state.lock().await;
+                        if policy.exhausted(proposal.failed_attempts) {
+                            // Give up on the proposal and keep it for diagnostics
+                            log(&format!("HIG-{}", chain_id), &format!("[WARNING] Giving up on status proposal for cat-id='{}' after {} attempts: {}", 
+                                proposal.cat_id.0, proposal.failed_attempts, e));
+                            state.record_cat_audit(&proposal.cat_id, CatAuditEvent::ProposalDropped { status: proposal.status.clone(), attempts: proposal.failed_attempts });
+                            state.dead_letter_proposals.push(DeadLetterProposal {
+                                cat_id: proposal.cat_id,
+                                status: proposal.status,
+                                constituent_chains: proposal.constituent_chains,
+                                attempts: proposal.failed_attempts,
+                                last_error: e.to_string(),
+                            });
+                            None
+                        } else {
+                            // Put the proposal back in the queue and back off before retrying
+                            let backoff = policy.backoff(proposal.failed_attempts);
+                            log(&format!("HIG-{}", chain_id), &format!("Error sending status update (attempt {}): {}, retrying in {:?}", 
+                                proposal.failed_attempts, e, backoff));
+                            state.pending_proposals.push_front(proposal);
+                            Some(backoff)
+                        }
+                    };
+                    if let Some(backoff) = backoff {
+                        clock.sleep(backoff).await;
+                    }
+                } else {
                     let node = hig_node.lock().await;
                     let mut state = node.state.lock().await;
-                    state.pending_proposals.push_front(proposal);
+                    state.realized_hs_message_delays.push((proposal.cat_id.clone(), elapsed_since_queue_entry));
+                    state.record_cat_audit(&proposal.cat_id, CatAuditEvent::ProposalSent {
+                        status: proposal.status.clone(),
+                        attempts: proposal.failed_attempts + 1,
+                    });
                 }
             } else {
                 // No proposals to process, wait a bit
-                tokio::time::sleep(Duration::from_millis(100)).await;
+                let clock = hig_node.lock().await.clock.clone();
+                clock.sleep(Duration::from_millis(100)).await;
             }
         }
         
@@ -673,7 +1418,29 @@ impl HyperIGNode {
         };
         log(&format!("HIG-{}", chain_id), "Starting message processing loop");
         loop {
+            let lock_start = std::time::Instant::now();
             let mut node = hig_node.lock().await;
+            let lock_wait = lock_start.elapsed();
+
+            // A halted node leaves the subblocks in the channel, a crashed one loses them
+            let (halted, crashed) = {
+                let state = node.state.lock().await;
+                (state.halted, state.crashed)
+            };
+            if halted {
+                if crashed {
+                    let mut lost = 0;
+                    if let Some(receiver) = &mut node.receiver_cl_to_hig {
+                        while receiver.try_recv().is_ok() {
+                            lost += 1;
+                        }
+                    }
+                    node.state.lock().await.count_lost_subblocks += lost;
+                }
+                drop(node);
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                continue;
+            }
 
             // Get the receiver from the node
             let receiver = if let Some(receiver) = &mut node.receiver_cl_to_hig {
@@ -686,10 +1453,21 @@ impl HyperIGNode {
             // Try to receive a message
             match receiver.try_recv() {
                 Ok(subblock) => {                    
+                    // Skip the subblocks that were already replayed after a restart
+                    let block_height = subblock.block_height;
+                    if node.state.lock().await.replayed_through_height.is_some_and(|height| block_height <= height) {
+                        log(&format!("HIG-{}", chain_id), &format!("Skipping subblock {}, already replayed", block_height));
+                        continue;
+                    }
+
                     // Process the subblock
                     if let Err(e) = node.process_subblock(subblock).await {
                         log(&format!("HIG-{}", chain_id), &format!("Error processing subblock: {}", e));
                     }
+                    // Attribute the wait for the node lock to the subblock
+                    if let Some(timing) = node.state.lock().await.subblock_timings.get_mut(&block_height) {
+                        timing.lock_wait = lock_wait;
+                    }
                 }
                 Err(mpsc::error::TryRecvError::Empty) => {
                     // No message available, release the lock and wait a bit
@@ -715,6 +1493,11 @@ impl HyperIGNode {
     /// # Returns
     /// Result containing a vector of keys accessed by the transaction
     async fn get_transaction_keys(&self, command: &str) -> Result<Vec<String>, anyhow::Error> {
+        // Debits are handled by the mock VM, not by x-chain-vm
+        if let Some((account, _)) = MockVM::parse_debit(command) {
+            return Ok(vec![account.to_string()]);
+        }
+
         // Parse the transaction using x-chain-vm's parse_input
         let vm_tx = x_chain_vm::parse_input(command)
             .map_err(|e| anyhow::anyhow!("Failed to parse transaction: {}", e))?;
@@ -765,6 +1548,16 @@ impl HyperIGNode {
             })
             .collect();
         
+        // Start the lock wait of the transaction (a transaction reprocessed while still blocked keeps waiting)
+        let blocking_keys: Vec<String> = locking_tx_ids.iter()
+            .filter(|(_, locking_tx_id)| *locking_tx_id != tx_id)
+            .map(|(key, _)| key.clone())
+            .collect();
+        if !blocking_keys.is_empty() {
+            state.ever_blocked_txs.insert(tx_id.clone());
+            state.tx_blocked_since.entry(tx_id.clone()).or_insert_with(|| (std::time::Instant::now(), blocking_keys));
+        }
+        
         // Add transaction to the dependency list for each key
         for key in keys {
             state.key_causes_dependencies_for_txs
@@ -783,6 +1576,27 @@ impl HyperIGNode {
         
         // Add the locking transactions as dependencies
         for (key, locking_tx_id) in locking_tx_ids {
+            // Do not wait on a transaction that (transitively) waits on this one, as neither could ever proceed
+            if locking_tx_id != tx_id {
+                if let Some(path) = find_dependency_path(&state.tx_depends_on_txs, &locking_tx_id, &tx_id) {
+                    let mut cycle = vec![tx_id.clone()];
+                    cycle.extend(path[..path.len() - 1].iter().cloned());
+                    log(&format!("HIG-{}", chain_id), &format!("[WARNING] Dependency cycle detected: tx-id='{}' would wait on tx-id='{}' (key '{}'), cycle: {:?}", 
+                        tx_id_clone.0, locking_tx_id.0, key, cycle.iter().map(|id| id.0.as_str()).collect::<Vec<_>>()));
+                    let mut victims = vec![tx_id.clone()];
+                    if state.dependency_cycle_policy == DependencyCyclePolicy::FailBoth {
+                        victims.push(locking_tx_id.clone());
+                    }
+                    for victim in victims {
+                        if !state.cycle_victims.contains(&victim) {
+                            state.cycle_victims.push(victim);
+                        }
+                    }
+                    state.dependency_cycles.push(cycle);
+                    continue;
+                }
+            }
+            
             state.tx_depends_on_txs
                 .entry(tx_id.clone())
                 .or_insert_with(HashSet::new)
@@ -811,14 +1625,8 @@ impl HyperIGNode {
     /// # Returns
     /// Result containing whether the transaction would succeed
     async fn check_transaction_execution(&self, command: &str) -> Result<bool, anyhow::Error> {
-        // Parse and execute the transaction using x-chain-vm's parse_input
-        let vm_tx = x_chain_vm::parse_input(command)
-            .map_err(|e| anyhow::anyhow!("Failed to parse transaction: {}", e))?;
-        
-        // Execute the transaction to check if it would succeed
-        let execution = vm_tx.execute(&self.state.lock().await.vm.get_state());
-        
-        Ok(execution.is_success())
+        // Dry-run the transaction against the current state
+        self.state.lock().await.vm.would_succeed(command)
     }
 }
 
@@ -870,6 +1678,9 @@ impl HyperIGNode {
                     panic!("BUG: CAT '{}' is already in cat_max_lifetime. This indicates the CAT is being processed twice, which should never happen.", cat_id.0);
                 }
                 state.cat_max_lifetime.insert(cat_id.clone(), current_height + cat_lifetime);
+                let epoch = state.epoch;
+                state.cat_epochs.insert(cat_id.clone(), epoch);
+                state.record_cat_audit(&cat_id, CatAuditEvent::Received { tx_id: tx.id.clone() });
             }
             
             // Check if CAT is blocked by any pending transaction
@@ -885,6 +1696,10 @@ impl HyperIGNode {
                 }
             }
             
+            if is_blocked {
+                state.ever_blocked_txs.insert(tx.id.clone());
+            }
+
             // Determine if we should fail immediately based on configuration
             let should_fail = is_blocked && !allow_pending_deps;
             
@@ -893,25 +1708,31 @@ impl HyperIGNode {
 
         if should_fail {
             // Configuration says CATs cannot depend on pending transactions - fail immediately
-            if let Some((locking_tx_id, key)) = blocking_info {
+            if let Some((locking_tx_id, key)) = &blocking_info {
                 log(&chain_id_str, &format!("CAT transaction '{}' depends on pending transaction '{}' (key '{}'), but allow_cat_pending_dependencies is false", tx.id.0, locking_tx_id.0, key));
             }
             // OPTIMIZATION: Single lock for failure handling
             {
                 let mut state = self.state.lock().await;
-                state.update_to_final_status_and_update_counter(&tx.id, TransactionStatus::Failure);
+                if let Some((blocked_by, key)) = blocking_info {
+                    state.record_cat_audit(&cat_id, CatAuditEvent::Rejected { blocked_by, key });
+                }
+                state.update_to_final_status_and_update_counter(&tx.id, TransactionStatus::Rejected);
                 state.cat_proposed_statuses.insert(tx.id.clone(), CATStatus::Failure);
             }
-            return Ok(TransactionStatus::Failure);
+            return Ok(TransactionStatus::Rejected);
         } else if is_blocked {
             // Configuration allows CATs to depend on pending transactions - postpone the CAT
-            if let Some((locking_tx_id, key)) = blocking_info {
+            if let Some((locking_tx_id, key)) = &blocking_info {
                 log(&chain_id_str, &format!("CAT transaction '{}' is POSTPONED (blocked by transaction '{}' on key '{}')", tx.id.0, locking_tx_id.0, key));
             }
             
             // OPTIMIZATION: Single lock for postponement handling
             {
                 let mut state = self.state.lock().await;
+                if let Some((blocked_by, key)) = blocking_info {
+                    state.record_cat_audit(&cat_id, CatAuditEvent::Postponed { blocked_by, key });
+                }
                 state.cat_proposed_statuses.insert(tx.id.clone(), CATStatus::Pending);
             }
             
@@ -947,6 +1768,21 @@ impl HyperIGNode {
             CATStatus::Failure
         };
         
+        // Optionally release the locks right away, the CAT will not write the keys after proposing Failure
+        if proposed_status == CATStatus::Failure {
+            let mut state = self.state.lock().await;
+            if state.release_locks_on_failure_proposal {
+                let locked_keys = state.tx_locks_keys.remove(&tx.id).unwrap_or_default();
+                for key in &locked_keys {
+                    if state.key_last_locked_by_tx.get(key) == Some(&tx.id) {
+                        state.key_last_locked_by_tx.remove(key);
+                    }
+                }
+                state.count_early_lock_releases += 1;
+                log(&chain_id_str, &format!("Released locks {:?} of CAT tx-id='{}' on its Failure proposal", locked_keys, tx.id.0));
+            }
+        }
+        
         // OPTIMIZATION: Single lock for final status updates
         let existing_proposed_status = {
             let state = self.state.lock().await;
@@ -961,6 +1797,7 @@ impl HyperIGNode {
                 // OPTIMIZATION: Single lock for status update and counter transition
                 {
                     let mut state = self.state.lock().await;
+                    state.record_cat_audit(&cat_id, CatAuditEvent::Proposed { status: proposed_status.clone() });
                     state.cat_proposed_statuses.insert(tx.id.clone(), proposed_status);
                     state.transition_count_postponed_to_resolving(&tx.id);
                 }
@@ -975,6 +1812,7 @@ impl HyperIGNode {
         // OPTIMIZATION: Single lock for final status setup
         {
             let mut state = self.state.lock().await;
+            state.record_cat_audit(&cat_id, CatAuditEvent::Proposed { status: proposed_status.clone() });
             state.cat_proposed_statuses.insert(tx.id.clone(), proposed_status);
             state.transition_count_postponed_to_resolving(&tx.id);
         }
@@ -1023,21 +1861,51 @@ impl HyperIGNode {
         let chain_id_str = format!("HIG-{}", chain_id);
         log(&chain_id_str, &format!("Handling status update tx-id='{}' : data='{}'", tx.id.0, tx.data));
         
-        if current_status == TransactionStatus::Failure {
+        // Has format STATUS_UPDATE:<Status>.CAT_ID:<cat_id>
+        let status_part = tx.data.split(".").collect::<Vec<&str>>()[0];
+        let status_part = status_part.split(":").collect::<Vec<&str>>()[1];
+        log(&chain_id_str, &format!("... Extracted status update='{}'", status_part));
+        let decided = match status_part {
+            "Success" => CATStatusLimited::Success,
+            "Failure" => CATStatusLimited::Failure,
+            "TimedOut" => CATStatusLimited::TimedOut,
+            "Rejected" => CATStatusLimited::Rejected,
+            _ => return Err(anyhow::anyhow!("Invalid status in update: {}", status_part)),
+        };
+
+        if current_status.is_failure() {
             // CRITICAL: Check if the incoming status update is Success - this should never happen!
-            // Has format STATUS_UPDATE:<Status>.CAT_ID:<cat_id>
-            let status_part = tx.data.split(".").collect::<Vec<&str>>()[0];
-            let status_part = status_part.split(":").collect::<Vec<&str>>()[1];
-            
-            if status_part == "Success" {
-                log(&chain_id_str, &format!("⚠️  WARNING: Ignoring Success status update for CAT tx-id='{}' that is already marked as Failed. Current status: {:?}, Incoming status: Success. This can happen due to slow HS processing, network delays, or race conditions.", 
-                    tx_id.0, current_status));
+            if decided == CATStatusLimited::Success {
+                // Protocol violation: the HS decided Success but this chain already failed the CAT
+                let (policy, current_block_height) = {
+                    let mut state = self.state.lock().await;
+                    state.record_cat_audit(&cat_id, CatAuditEvent::StatusUpdate { status: CATStatusLimited::Success, applied: false });
+                    state.count_protocol_violations += 1;
+                    (state.protocol_violation_policy, state.current_block_height)
+                };
+                match policy {
+                    ProtocolViolationPolicy::Ignore => {
+                        log(&chain_id_str, &format!("⚠️  WARNING: Ignoring Success status update for CAT tx-id='{}' that is already marked as Failed. Current status: {:?}, Incoming status: Success. This can happen due to slow HS processing, network delays, or race conditions.", 
+                            tx_id.0, current_status));
+                    }
+                    ProtocolViolationPolicy::FailRun => {
+                        log(&chain_id_str, &format!("[ERROR] Success status update for failed CAT tx-id='{}', failing run", tx_id.0));
+                        return Err(HyperIGError::ProtocolViolation(
+                            format!("Success status update for failed CAT '{}' at block height {}", cat_id.0, current_block_height)
+                        ).into());
+                    }
+                    ProtocolViolationPolicy::Escalate => {
+                        log(&chain_id_str, &format!("[ERROR] Escalating Success status update for failed CAT tx-id='{}' at block height {}", tx_id.0, current_block_height));
+                        self.state.lock().await.escalated_protocol_violations.push((cat_id.clone(), current_block_height));
+                    }
+                }
                 return Ok(current_status);
             }
             
             // OPTIMIZATION: Single lock for timeout information
             let (cat_lifetime, max_lifetime, current_block_height) = {
-                let state = self.state.lock().await;
+                let mut state = self.state.lock().await;
+                state.record_cat_audit(&cat_id, CatAuditEvent::StatusUpdate { status: decided, applied: false });
                 let cat_lifetime = state.cat_lifetime;
                 let max_lifetime = state.cat_max_lifetime.get(&cat_id).unwrap_or(&cat_lifetime);
                 let current_block_height = state.current_block_height;
@@ -1050,22 +1918,14 @@ impl HyperIGNode {
             return Ok(current_status);
         }
         
-        // Has format STATUS_UPDATE:<Status>.CAT_ID:<cat_id>
-        let status_part = tx.data.split(".").collect::<Vec<&str>>()[0];
-        let status_part = status_part.split(":").collect::<Vec<&str>>()[1];
-        log(&chain_id_str, &format!("... Extracted status update='{}'", status_part));
-        let status = if status_part == "Success" {
-            TransactionStatus::Success
-        } else if status_part == "Failure" {
-            TransactionStatus::Failure
-        } else {
-            return Err(anyhow::anyhow!("Invalid status in update: {}", status_part));
-        };
+        // A CAT that timed out or was rejected on another chain keeps that terminal status here
+        let status = TransactionStatus::from(decided.clone());
         
         // OPTIMIZATION: Single lock for status update and counter management
         {
             let mut state = self.state.lock().await;
             log(&chain_id_str, &format!("... (Before) status of tx-id='{}': {:?}", tx_id.0, state.transaction_statuses.get(&tx_id)));
+            state.record_cat_audit(&cat_id, CatAuditEvent::StatusUpdate { status: decided, applied: true });
             state.update_to_final_status_and_update_counter(&tx_id, status.clone());
         }
         
@@ -1085,7 +1945,7 @@ impl HyperIGNode {
             
             // Execute the transaction
             let mut state = self.state.lock().await;
-            state.vm.execute_transaction(&command)?;
+            state.vm.execute_command(&command)?;
             log(&chain_id_str, &format!("Executed CAT transaction tx-id='{}'", tx_id.0));
         }
         
@@ -1098,6 +1958,45 @@ impl HyperIGNode {
         Ok(status)
     }
 
+    /// Verifies that a status update received via a subblock originates from the HS.
+    /// 
+    /// Status updates may only enter the HIG through CL-produced subblocks, and only the HS
+    /// submits them to the CL. Anything else carrying STATUS_UPDATE data is treated as spoofed.
+    /// 
+    /// # Arguments
+    /// * `tx` - The status update transaction to verify
+    /// 
+    /// # Returns
+    /// Ok if the transaction carries the HS-origin marker, an error otherwise
+    fn verify_status_update_origin(&self, tx: &Transaction) -> Result<(), HyperIGError> {
+        if has_hs_origin_marker(tx) {
+            Ok(())
+        } else {
+            Err(HyperIGError::UnverifiedStatusUpdate(tx.id.clone()))
+        }
+    }
+
+    /// Verifies that a transaction of a subblock is well-formed before it is processed.
+    /// 
+    /// # Arguments
+    /// * `tx` - The transaction to verify (not a status update)
+    /// * `my_chain_id` - The chain of this HIG
+    /// 
+    /// # Returns
+    /// Ok if the data is valid and the constituent chains include this chain (and another one for
+    /// CATs), an error otherwise
+    fn verify_well_formed(&self, tx: &Transaction, my_chain_id: &ChainId) -> Result<(), HyperIGError> {
+        TransactionData::validate(&tx.data)
+            .map_err(|reason| HyperIGError::InvalidTransactionData { tx_id: tx.id.clone(), reason })?;
+        if !tx.constituent_chains.contains(my_chain_id) {
+            return Err(HyperIGError::InvalidCATConstituentChains(format!("Own chain '{}' is not part of constituent chains", my_chain_id.0)));
+        }
+        if tx.data.starts_with("CAT") && tx.constituent_chains.len() <= 1 {
+            return Err(HyperIGError::InvalidCATConstituentChains("CAT must have more than one constituent chain".to_string()));
+        }
+        Ok(())
+    }
+
     /// Processes pending transactions that were waiting on a resolved transaction (CAT or regular).
     /// 
     /// # Arguments
@@ -1158,6 +2057,7 @@ impl HyperIGNode {
                     let deps = dependencies.clone();
                     if is_empty {
                         state.tx_depends_on_txs.remove(&tx_id);
+                        state.end_lock_wait(&tx_id);
                     }
                     (is_empty, deps)
                 } else {
@@ -1177,7 +2077,7 @@ impl HyperIGNode {
                 };
                 
                 if let Some(status) = current_status {
-                    if matches!(status, TransactionStatus::Success | TransactionStatus::Failure) {
+                    if status.is_final() {
                         log(&format!("HIG-{}", chain_id), &format!("Transaction tx-id='{}' has already reached final status {:?}, skipping reprocessing", tx_id.0, status));
                         continue;
                     }
@@ -1256,6 +2156,7 @@ impl HyperIGNode {
                                     let deps = dependencies.clone();
                                     if is_empty {
                                         state.tx_depends_on_txs.remove(&consumer_tx_id);
+                                        state.end_lock_wait(&consumer_tx_id);
                                     }
                                     (is_empty, deps)
                                 } else {
@@ -1325,13 +2226,58 @@ impl HyperIGNode {
                         }
                     }
                 }
-                log(&format!("HIG-{}", chain_id), &format!("Finished processing tx-id='{}'", tx_id.0));
+                log(&format!("HIG-{}", chain_id), &format!("Finished processing tx-id='{}'", tx_id.0));
+            } else {
+                log(&format!("HIG-{}", chain_id), &format!("Transaction tx-id='{}' still has remaining dependencies", tx_id.0));
+            }
+        }
+
+        log(&format!("HIG-{}", chain_id), "Finished processing all pending transactions");
+        Ok(())
+    }
+
+    /// Fails the transactions chosen to break the detected dependency cycles.
+    /// 
+    /// A regular transaction fails directly, which releases its locks and lets its consumers proceed.
+    /// A CAT cannot fail locally, so a postponed CAT proposes Failure to the HS instead. A CAT that
+    /// has already proposed a status is left to the HS.
+    async fn fail_cycle_victims(&mut self) -> Result<(), anyhow::Error> {
+        let (chain_id, victims) = {
+            let mut state = self.state.lock().await;
+            (state.my_chain_id.0.clone(), std::mem::take(&mut state.cycle_victims))
+        };
+        
+        for tx_id in victims {
+            let (tx, postponed) = {
+                let mut state = self.state.lock().await;
+                let tx = match state.received_txs.get(&tx_id) {
+                    Some(tx) if state.pending_transactions.contains(&tx_id) => tx.clone(),
+                    _ => continue,
+                };
+                // Stop waiting for the dependencies of the transaction
+                state.remove_transaction_dependencies(&tx_id);
+                let postponed = state.cat_proposed_statuses.get(&tx_id) == Some(&CATStatus::Pending);
+                (tx, postponed)
+            };
+            
+            if tx.data.starts_with("CAT") {
+                if !postponed {
+                    log(&format!("HIG-{}", chain_id), &format!("CAT tx-id='{}' on a dependency cycle has already proposed a status, leaving it to the HS", tx_id.0));
+                    continue;
+                }
+                {
+                    let mut state = self.state.lock().await;
+                    state.cat_proposed_statuses.insert(tx_id.clone(), CATStatus::Failure);
+                    state.transition_count_postponed_to_resolving(&tx_id);
+                }
+                log(&format!("HIG-{}", chain_id), &format!("Proposing Failure for CAT tx-id='{}' to break a dependency cycle", tx_id.0));
+                self.send_cat_status_proposal(CATId(tx.cl_id.clone()), CATStatusLimited::Failure, tx.constituent_chains.clone()).await?;
             } else {
-                log(&format!("HIG-{}", chain_id), &format!("Transaction tx-id='{}' still has remaining dependencies", tx_id.0));
+                self.state.lock().await.update_to_final_status_and_update_counter(&tx_id, TransactionStatus::Failure);
+                log(&format!("HIG-{}", chain_id), &format!("Failed tx-id='{}' to break a dependency cycle", tx_id.0));
+                self.process_pending_transactions(tx_id, TransactionStatus::Failure).await?;
             }
         }
-
-        log(&format!("HIG-{}", chain_id), "Finished processing all pending transactions");
         Ok(())
     }
 
@@ -1397,6 +2343,147 @@ impl HyperIGNode {
         self.state.lock().await.count_regular_tx_finalized
     }
 
+    /// Gets the count of protocol violations observed by this node.
+    /// 
+    /// # Returns
+    /// The number of protocol violations (e.g. Success status updates for failed CATs)
+    pub async fn get_protocol_violation_count(&self) -> u64 {
+        self.state.lock().await.count_protocol_violations
+    }
+
+    /// Gets the protocol violations that were escalated under the Escalate policy.
+    /// 
+    /// # Returns
+    /// A vector of (CAT ID, block height) pairs
+    pub async fn get_escalated_protocol_violations(&self) -> Vec<(CATId, u64)> {
+        self.state.lock().await.escalated_protocol_violations.clone()
+    }
+
+    /// Gets the count of status updates rejected for lacking the HS-origin marker.
+    /// 
+    /// # Returns
+    /// The number of rejected (potentially spoofed) status updates
+    pub async fn get_rejected_status_update_count(&self) -> u64 {
+        self.state.lock().await.count_rejected_status_updates
+    }
+
+    /// Gets the points in time at which a transaction passed the HIG.
+    /// 
+    /// # Returns
+    /// The timeline, or None if the HIG has not processed the transaction
+    pub async fn get_transaction_timeline(&self, tx_id: &TransactionId) -> Option<TransactionTimeline> {
+        self.state.lock().await.tx_timelines.get(tx_id).cloned()
+    }
+
+    /// Gets the steps of the resolution of a CAT on this HIG, in the order they happened.
+    /// 
+    /// # Returns
+    /// The audit trail, empty if the HIG has not received the CAT
+    pub async fn get_cat_audit_trail(&self, cat_id: &CATId) -> Vec<CatAuditEntry> {
+        self.state.lock().await.cat_audit_trails.get(cat_id).cloned().unwrap_or_default()
+    }
+
+    /// Gets the number of malformed transactions skipped in subblocks.
+    /// 
+    /// # Returns
+    /// The number of transactions with invalid data or constituent chains
+    pub async fn get_malformed_transaction_count(&self) -> u64 {
+        self.state.lock().await.count_malformed_txs
+    }
+
+    /// Gets the number of received transactions whose id was already received.
+    /// 
+    /// # Returns
+    /// The number of duplicate transactions, regardless of the duplicate policy
+    pub async fn get_duplicate_transaction_count(&self) -> u64 {
+        self.state.lock().await.count_duplicate_txs
+    }
+
+    /// Gets the number of received CATs whose id was already used by another CAT.
+    /// 
+    /// # Returns
+    /// The number of CAT id collisions, regardless of the collision policy
+    pub async fn get_cat_id_collision_count(&self) -> u64 {
+        self.state.lock().await.count_cat_id_collisions
+    }
+
+    /// Gets the number of regular transactions that expired while pending.
+    pub async fn get_expired_transaction_count(&self) -> u64 {
+        self.state.lock().await.count_regular_expired
+    }
+
+    /// Gets the number of CATs that released their locks on a Failure proposal.
+    pub async fn get_early_lock_release_count(&self) -> u64 {
+        self.state.lock().await.count_early_lock_releases
+    }
+
+    /// Gets the number of detected dependency cycles.
+    pub async fn get_dependency_cycle_count(&self) -> u64 {
+        self.state.lock().await.dependency_cycles.len() as u64
+    }
+
+    /// Gets the detected dependency cycles.
+    /// 
+    /// # Returns
+    /// The transactions on each cycle, starting with the transaction whose dependency closed it
+    pub async fn get_dependency_cycles(&self) -> Vec<Vec<TransactionId>> {
+        self.state.lock().await.dependency_cycles.clone()
+    }
+
+    /// Gets the histogram of the ages of the pending CATs, i.e. the number of blocks since each
+    /// CAT was received, at the height of the last processed subblock.
+    /// 
+    /// # Returns
+    /// The number of pending CATs per age in blocks
+    pub async fn get_pending_cat_ages(&self) -> BTreeMap<u64, u64> {
+        let state = self.state.lock().await;
+        let mut ages = BTreeMap::new();
+        // Only CATs that are still pending have a max lifetime
+        for max_lifetime in state.cat_max_lifetime.values() {
+            let cat_creation_block = max_lifetime.saturating_sub(state.cat_lifetime);
+            *ages.entry(state.current_block_height.saturating_sub(cat_creation_block)).or_insert(0) += 1;
+        }
+        ages
+    }
+
+    /// Gets the number of CAT status proposals waiting to be sent to the HS.
+    pub async fn get_proposal_queue_depth(&self) -> u64 {
+        self.state.lock().await.pending_proposals.len() as u64
+    }
+
+    /// Gets how long the oldest queued CAT status proposal has been waiting (zero if the queue is empty).
+    pub async fn get_proposal_queue_max_age(&self) -> Duration {
+        let now = self.clock.now();
+        self.state.lock().await.pending_proposals.iter()
+            .map(|proposal| now.saturating_sub(proposal.queue_entry_time))
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// Gets the number of CAT status proposals dropped or rejected because the queue was full.
+    pub async fn get_dropped_proposal_count(&self) -> u64 {
+        self.state.lock().await.count_dropped_proposals
+    }
+
+    /// Gets the CAT status proposals given up on after the maximum number of send attempts.
+    pub async fn get_dead_letter_proposals(&self) -> Vec<DeadLetterProposal> {
+        self.state.lock().await.dead_letter_proposals.clone()
+    }
+
+    /// Gets the time each sent proposal spent in the queue before it was sent to the HS, in send order.
+    ///
+    /// With a delay distribution, these are the realized delays of the proposals (measured on the local clock).
+    pub async fn get_realized_hs_message_delays(&self) -> Vec<Duration> {
+        self.state.lock().await.realized_hs_message_delays.iter().map(|(_, delay)| *delay).collect()
+    }
+
+    /// Gets the time the proposal of each CAT spent in the queue before it was sent to the HS.
+    ///
+    /// CATs whose proposal was not sent (yet) are missing.
+    pub async fn get_realized_hs_message_delays_by_cat(&self) -> HashMap<CATId, Duration> {
+        self.state.lock().await.realized_hs_message_delays.iter().cloned().collect()
+    }
+
 
 }
 
@@ -1473,7 +2560,7 @@ impl HyperIGNode {
         if would_succeed {
             let mut state = self.state.lock().await;
             log(&format!("HIG-{}", chain_id), "Executing transaction...");
-            state.vm.execute_transaction(command)?;
+            state.vm.execute_command(command)?;
             log(&format!("HIG-{}", chain_id), "Transaction executed successfully");
 
             // Get the balance for account 1 from the VM state, returns 0 if account doesn't exist
@@ -1551,6 +2638,11 @@ impl HyperIG for HyperIGNode {
         let status = if tx.data.starts_with("STATUS_UPDATE") {
             self.handle_status_update(tx.clone()).await?
         } else {
+            // Reject malformed data before the transaction enters the state, so it cannot be left pending
+            if let Err(reason) = TransactionData::validate(&tx.data) {
+                log(&format!("HIG-{}", chain_id), &format!("Rejecting tx-id='{}' with invalid data '{}': {}", tx.id, tx.data, reason));
+                return Err(HyperIGError::InvalidTransactionData { tx_id: tx.id.clone(), reason }.into());
+            }
             // now handle the case where it is any of the other transaction types
             // Check if transaction already exists in the system (e.g., being reprocessed after dependency resolution)
             let transaction_exists = {
@@ -1617,7 +2709,9 @@ impl HyperIG for HyperIGNode {
                     log(&format!("HIG-{}", chain_id), "Status proposal sent for CAT transaction.");
                 }
                 CATStatus::Failure => {
-                    self.send_cat_status_proposal(cat_id, CATStatusLimited::Failure, constituent_chains).await?;
+                    // A rejected CAT proposes Rejected so the other constituent chains record the rejection
+                    let proposal = if status == TransactionStatus::Rejected { CATStatusLimited::Rejected } else { CATStatusLimited::Failure };
+                    self.send_cat_status_proposal(cat_id, proposal, constituent_chains).await?;
                     log(&format!("HIG-{}", chain_id), "Status proposal sent for CAT transaction.");
                 }
                 CATStatus::Pending => {
@@ -1671,13 +2765,49 @@ impl HyperIG for HyperIGNode {
         // For high-throughput scenarios, we can batch multiple proposals with the same timestamp
         let now = std::time::Instant::now();
         
+        let mut state = self.state.lock().await;
+        let chain_id = state.my_chain_id.0.clone();
+        
+        // Apply the overflow policy if the queue is full
+        if state.proposal_queue_max_len.is_some_and(|max_len| state.pending_proposals.len() >= max_len) {
+            state.count_dropped_proposals += 1;
+            match state.proposal_queue_overflow_policy {
+                ProposalQueueOverflowPolicy::DropOldest => {
+                    if let Some(dropped) = state.pending_proposals.pop_front() {
+                        log(&format!("HIG-{}", chain_id), &format!("[WARNING] Proposal queue full, dropped oldest proposal for cat-id='{}'", dropped.cat_id.0));
+                    }
+                }
+                ProposalQueueOverflowPolicy::DropNewest => {
+                    log(&format!("HIG-{}", chain_id), &format!("[WARNING] Proposal queue full, dropped new proposal for cat-id='{}'", cat_id.0));
+                    state.proposal_queuing_in_subblock += now.elapsed();
+                    return Ok(());
+                }
+                ProposalQueueOverflowPolicy::Reject => {
+                    log(&format!("HIG-{}", chain_id), &format!("[WARNING] Proposal queue full, rejected proposal for cat-id='{}'", cat_id.0));
+                    state.proposal_queuing_in_subblock += now.elapsed();
+                    return Err(HyperIGError::ProposalQueueFull(cat_id));
+                }
+            }
+        }
+        
+        // Record when the first proposal of the CAT was queued
+        let state_ref = &mut *state;
+        if let Some(timeline) = state_ref.cat_to_tx_id.get(&cat_id).and_then(|tx_id| state_ref.tx_timelines.get_mut(tx_id)) {
+            timeline.proposed_at.get_or_insert(now);
+        }
+        
         // Add the proposal to the queue
-        self.state.lock().await.pending_proposals.push_back(QueuedCATProposal {
+        let epoch = state.cat_epochs.get(&cat_id).copied().unwrap_or(state.epoch);
+        state.pending_proposals.push_back(QueuedCATProposal {
             cat_id,
             status,
             constituent_chains,
-            queue_entry_time: now,
+            epoch,
+            queue_entry_time: self.clock.now(),
+            delay: None,
+            failed_attempts: 0,
         });
+        state.proposal_queuing_in_subblock += now.elapsed();
         
         Ok(())
     }
@@ -1720,6 +2850,10 @@ impl HyperIG for HyperIGNode {
             });
         }
 
+        // Start timing the subblock
+        let processing_start = std::time::Instant::now();
+        self.state.lock().await.proposal_queuing_in_subblock = Duration::ZERO;
+
         // Update current block height
         log(&format!("HIG-{}", chain_id), &format!("[DEBUG] Updating current block height from {} to {}", 
             self.state.lock().await.current_block_height, subblock.block_height));
@@ -1727,26 +2861,100 @@ impl HyperIG for HyperIGNode {
         log(&format!("HIG-{}", chain_id), &format!("[DEBUG] Current block height updated to {}", subblock.block_height));
 
         // Check for expired CATs at the beginning of subblock processing
+        // CAT lifetimes are anchored at CL block heights, but expiry is judged by the local clock
         log(&format!("HIG-{}", chain_id), "[DEBUG] Checking for expired CATs at beginning of subblock");
-        self.check_cat_timeouts(subblock.block_height).await;
+        let block_height = self.block_height_source.block_height(subblock.block_height);
+        let local_block_height = self.state.lock().await.clock_skew.local_block_height(block_height);
+        self.check_cat_timeouts(local_block_height).await;
         log(&format!("HIG-{}", chain_id), "[DEBUG] Finished checking expired CATs");
+        self.check_transaction_expiries(local_block_height).await;
+
+        // Optionally move the status updates to the front of the subblock
+        let transactions: Vec<&Transaction> = if self.state.lock().await.prioritize_status_updates {
+            let (status_updates, others): (Vec<&Transaction>, Vec<&Transaction>) = subblock.transactions.iter()
+                .partition(|tx| tx.data.starts_with("STATUS_UPDATE"));
+            log(&format!("HIG-{}", chain_id), &format!("[DEBUG] Processing {} status updates before {} other transactions", status_updates.len(), others.len()));
+            status_updates.into_iter().chain(others).collect()
+        } else {
+            subblock.transactions.iter().collect()
+        };
 
         // Track seen transaction IDs to skip duplicates
         let mut seen_tx_ids = HashSet::new();
         
-        for tx in &subblock.transactions {
+        for tx in transactions {
             log(&format!("HIG-{}", chain_id), &format!("[DEBUG] Processing tx-id='{}' : data='{}'", tx.id.0, tx.data));
             
-            // Skip if we've seen this transaction ID before in this subblock
-            if !seen_tx_ids.insert(tx.id.clone()) {
-                log(&format!("HIG-{}", chain_id), &format!("[DEBUG] Skipping duplicate transaction ID: {}", tx.id.0));
-                continue;
+            // Apply the duplicate policy if we've seen this transaction ID before in this subblock or it already exists in our state
+            log(&format!("HIG-{}", chain_id), &format!("[DEBUG] Checking if tx-id='{}' exists in state", tx.id.0));
+            let is_duplicate = !seen_tx_ids.insert(tx.id.clone()) || self.state.lock().await.received_txs.contains_key(&tx.id);
+            if is_duplicate {
+                let policy = {
+                    let mut state = self.state.lock().await;
+                    state.count_duplicate_txs += 1;
+                    state.duplicate_transaction_policy
+                };
+                match policy {
+                    DuplicateTransactionPolicy::Skip => {
+                        log(&format!("HIG-{}", chain_id), &format!("[DEBUG] Skipping duplicate tx-id='{}'", tx.id.0));
+                        continue;
+                    }
+                    DuplicateTransactionPolicy::Error => {
+                        log(&format!("HIG-{}", chain_id), &format!("[ERROR] Received duplicate tx-id='{}' in block {}", tx.id.0, subblock.block_height));
+                        return Err(HyperIGError::DuplicateTransaction(tx.id.clone()));
+                    }
+                    DuplicateTransactionPolicy::ProcessAgain if tx.data.starts_with("CAT") => {
+                        log(&format!("HIG-{}", chain_id), &format!("[WARNING] Skipping duplicate CAT tx-id='{}', a CAT cannot be processed twice", tx.id.0));
+                        continue;
+                    }
+                    DuplicateTransactionPolicy::ProcessAgain => {
+                        let mut state = self.state.lock().await;
+                        if state.transaction_statuses.get(&tx.id).is_some_and(|status| !status.is_final()) {
+                            log(&format!("HIG-{}", chain_id), &format!("[WARNING] Skipping duplicate tx-id='{}', the earlier instance is still pending", tx.id.0));
+                            continue;
+                        }
+                        // Forget the earlier instance so that the duplicate is set up like a new transaction
+                        state.received_txs.remove(&tx.id);
+                        log(&format!("HIG-{}", chain_id), &format!("[WARNING] Processing duplicate tx-id='{}' again", tx.id.0));
+                    }
+                }
             }
             
-            // Skip if transaction ID already exists in our state
-            log(&format!("HIG-{}", chain_id), &format!("[DEBUG] Checking if tx-id='{}' exists in state", tx.id.0));
-            if self.state.lock().await.received_txs.contains_key(&tx.id) {
-                log(&format!("HIG-{}", chain_id), &format!("[DEBUG] Skipping tx-id='{}' that already exists in state", tx.id.0));
+            // Apply the collision policy if another CAT already uses the id of this CAT
+            let collides_with = if tx.data.starts_with("CAT") {
+                self.state.lock().await.cat_to_tx_id.get(&CATId(tx.cl_id.clone())).filter(|cat_tx_id| **cat_tx_id != tx.id).cloned()
+            } else {
+                None
+            };
+            if let Some(cat_tx_id) = collides_with {
+                let policy = {
+                    let mut state = self.state.lock().await;
+                    state.count_cat_id_collisions += 1;
+                    state.cat_id_collision_policy
+                };
+                match policy {
+                    CatIdCollisionPolicy::RejectNew => {
+                        log(&format!("HIG-{}", chain_id), &format!("[WARNING] Rejecting CAT tx-id='{}', its cat-id='{}' is already used by tx-id='{}'", tx.id.0, tx.cl_id.0, cat_tx_id.0));
+                        continue;
+                    }
+                    CatIdCollisionPolicy::Error => {
+                        log(&format!("HIG-{}", chain_id), &format!("[ERROR] CAT tx-id='{}' in block {} reuses the cat-id='{}' of tx-id='{}'", tx.id.0, subblock.block_height, tx.cl_id.0, cat_tx_id.0));
+                        return Err(HyperIGError::CatIdCollision { cat_id: CATId(tx.cl_id.clone()), tx_id: tx.id.clone() });
+                    }
+                }
+            }
+            
+            // Reject status updates that do not originate from the HS
+            if tx.data.starts_with("STATUS_UPDATE") {
+                if let Err(e) = self.verify_status_update_origin(tx) {
+                    log(&format!("HIG-{}", chain_id), &format!("[WARNING] Rejecting status update tx-id='{}' with cl-id='{}': {}", tx.id.0, tx.cl_id.0, e));
+                    self.state.lock().await.count_rejected_status_updates += 1;
+                    continue;
+                }
+            } else if let Err(e) = self.verify_well_formed(tx, &subblock.chain_id) {
+                // Skip malformed transactions instead of aborting the rest of the subblock
+                log(&format!("HIG-{}", chain_id), &format!("[WARNING] Skipping malformed tx-id='{}' with data '{}': {}", tx.id.0, tx.data, e));
+                self.state.lock().await.count_malformed_txs += 1;
                 continue;
             }
             
@@ -1754,6 +2962,25 @@ impl HyperIG for HyperIGNode {
             log(&format!("HIG-{}", chain_id), &format!("[DEBUG] About to process tx-id='{}'", tx.id.0));
             HyperIG::process_transaction(self, tx.clone()).await.map_err(|e| HyperIGError::Internal(e.to_string()))?;
             log(&format!("HIG-{}", chain_id), &format!("[DEBUG] Finished processing tx-id='{}'", tx.id.0));
+            
+            // Break the dependency cycles detected while processing the transaction
+            self.fail_cycle_victims().await.map_err(|e| HyperIGError::Internal(e.to_string()))?;
+        }
+        
+        // Record the state root after this subblock
+        {
+            let mut state = self.state.lock().await;
+            let state_root = StateTree::from_state(state.vm.get_state()).root();
+            state.state_roots.insert(subblock.block_height, state_root);
+            log(&format!("HIG-{}", chain_id), &format!("State root at block {}: {}", subblock.block_height, state_root));
+
+            // Record the time spent on this subblock (the lock wait is filled in by the message loop)
+            let proposal_queuing = state.proposal_queuing_in_subblock;
+            state.subblock_timings.insert(subblock.block_height, SubblockTiming {
+                lock_wait: Duration::ZERO,
+                execution: processing_start.elapsed().saturating_sub(proposal_queuing),
+                proposal_queuing,
+            });
         }
         
         log(&format!("HIG-{}", chain_id), "[DEBUG] Finished processing subblock");
@@ -1896,6 +3123,206 @@ impl HyperIG for HyperIGNode {
         let state = self.state.lock().await;
         Ok((state.count_cat_pending_resolving, state.count_cat_pending_postponed))
     }
+
+    /// Gets detailed counts of failed CATs split by failure reason.
+    /// 
+    /// # Returns
+    /// A tuple containing (timed_out, rejected) where:
+    /// - timed_out: CATs that did not receive a status update within their lifetime
+    /// - rejected: CATs rejected because they depend on a pending transaction
+    async fn get_cat_failure_detailed_counts(&self) -> Result<(u64, u64), HyperIGError> {
+        let state = self.state.lock().await;
+        Ok((state.count_cat_timed_out, state.count_cat_rejected))
+    }
+
+    /// Gets counts of CAT transaction statuses (Pending, Success, Failure) per CAT fanout.
+    /// 
+    /// # Returns
+    /// A map from the number of constituent chains to (pending_count, success_count, failure_count)
+    async fn get_transaction_status_counts_cats_by_fanout(&self) -> Result<BTreeMap<usize, (u64, u64, u64)>, HyperIGError> {
+        let state = self.state.lock().await;
+        let fanouts: HashSet<usize> = state.received_txs.values()
+            .filter(|tx| tx.data.starts_with("CAT"))
+            .map(|tx| tx.constituent_chains.len())
+            .collect();
+        Ok(fanouts.into_iter()
+            .map(|fanout| (fanout, state.count_statuses_where(|_, tx| tx.data.starts_with("CAT") && tx.constituent_chains.len() == fanout)))
+            .collect())
+    }
+
+    /// Gets counts of CAT transaction statuses (Pending, Success, Failure) split by lock contention.
+    /// 
+    /// # Returns
+    /// A tuple of (ever_blocked, never_blocked) counts
+    async fn get_transaction_status_counts_cats_by_blocked(&self) -> Result<((u64, u64, u64), (u64, u64, u64)), HyperIGError> {
+        let state = self.state.lock().await;
+        Ok((
+            state.count_statuses_where(|tx_id, tx| tx.data.starts_with("CAT") && state.ever_blocked_txs.contains(tx_id)),
+            state.count_statuses_where(|tx_id, tx| tx.data.starts_with("CAT") && !state.ever_blocked_txs.contains(tx_id)),
+        ))
+    }
+
+    /// Gets counts of regular transaction statuses (Pending, Success, Failure) split by lock contention.
+    /// 
+    /// # Returns
+    /// A tuple of (ever_blocked, never_blocked) counts
+    async fn get_transaction_status_counts_regular_by_blocked(&self) -> Result<((u64, u64, u64), (u64, u64, u64)), HyperIGError> {
+        let state = self.state.lock().await;
+        Ok((
+            state.count_statuses_where(|tx_id, tx| !tx.data.starts_with("CAT") && state.ever_blocked_txs.contains(tx_id)),
+            state.count_statuses_where(|tx_id, tx| !tx.data.starts_with("CAT") && !state.ever_blocked_txs.contains(tx_id)),
+        ))
+    }
+
+    /// Gets the transactions whose status changed while processing the given block.
+    /// 
+    /// # Arguments
+    /// * `block_height` - The block height to query
+    /// 
+    /// # Returns
+    /// A map from transaction ID to the status it reached in that block (empty if nothing changed)
+    async fn get_status_transitions(&self, block_height: u64) -> Result<HashMap<TransactionId, TransactionStatus>, HyperIGError> {
+        let state = self.state.lock().await;
+        Ok(state.status_transitions.get(&block_height).cloned().unwrap_or_default())
+    }
+
+    /// Dry-runs a transaction against the current state.
+    /// 
+    /// Performs the same parsing, key extraction, lock check and execution check as
+    /// `process_transaction`, but does not record the transaction or change any state.
+    /// 
+    /// # Arguments
+    /// * `tx` - The transaction to simulate
+    /// 
+    /// # Returns
+    /// The keys, blocking transaction, execution result and expected status of the transaction
+    async fn simulate_transaction(&self, tx: Transaction) -> Result<TransactionPreview, HyperIGError> {
+        if tx.data.starts_with("STATUS_UPDATE") {
+            return Err(HyperIGError::ExecutionFailed(format!("Status updates cannot be simulated: {}", tx.id)));
+        }
+        let is_cat = tx.data.starts_with("CAT");
+
+        // Extract the command part between the dots
+        let command = tx.data.split('.').nth(1)
+            .ok_or_else(|| HyperIGError::ExecutionFailed("Invalid transaction format".to_string()))?;
+        let keys = self.get_transaction_keys(command).await
+            .map_err(|e| HyperIGError::ExecutionFailed(e.to_string()))?;
+        let would_succeed = self.check_transaction_execution(command).await
+            .map_err(|e| HyperIGError::ExecutionFailed(e.to_string()))?;
+
+        // A CAT is only blocked by pending transactions, a regular transaction by any lock held by another transaction
+        let (blocked_by, allow_pending_deps) = {
+            let state = self.state.lock().await;
+            let blocked_by = keys.iter()
+                .filter_map(|key| state.key_last_locked_by_tx.get(key))
+                .find(|locking_tx_id| if is_cat {
+                    state.pending_transactions.contains(*locking_tx_id)
+                } else {
+                    **locking_tx_id != tx.id
+                })
+                .cloned();
+            (blocked_by, state.allow_cat_pending_dependencies)
+        };
+
+        let expected_status = match (is_cat, blocked_by.is_some()) {
+            (true, true) if !allow_pending_deps => TransactionStatus::Rejected,
+            (true, _) | (false, true) => TransactionStatus::Pending,
+            (false, false) if would_succeed => TransactionStatus::Success,
+            (false, false) => TransactionStatus::Failure,
+        };
+
+        let chain_id = self.state.lock().await.my_chain_id.0.clone();
+        log(&format!("HIG-{}", chain_id), &format!("Simulated tx-id='{}': keys={:?}, blocked_by={:?}, would_succeed={}, expected_status={:?}",
+            tx.id, keys, blocked_by, would_succeed, expected_status));
+
+        Ok(TransactionPreview { keys, blocked_by, would_succeed, expected_status })
+    }
+
+    /// Gets the balance of an account, optionally with an inclusion proof.
+    /// 
+    /// # Arguments
+    /// * `chain_id` - The chain to query, must be the chain of this HIG
+    /// * `account` - The account to query
+    /// * `with_proof` - Whether to attach an inclusion proof against the current state root
+    /// 
+    /// # Returns
+    /// The balance (0 if the account does not exist), the state root and the proof if requested
+    async fn get_account_balance(&self, chain_id: ChainId, account: u32, with_proof: bool) -> Result<AccountBalance, HyperIGError> {
+        let state = self.state.lock().await;
+        if chain_id != state.my_chain_id {
+            return Err(HyperIGError::WrongChainId { expected: state.my_chain_id.clone(), received: chain_id });
+        }
+        let tree = StateTree::from_state(state.vm.get_state());
+        Ok(AccountBalance {
+            account,
+            balance: tree.balance(account).unwrap_or(0),
+            state_root: tree.root(),
+            proof: if with_proof { tree.proof(account) } else { None },
+        })
+    }
+
+    /// Gets the state root after processing the subblock at the given height.
+    /// 
+    /// # Arguments
+    /// * `block_height` - The block height to query
+    /// 
+    /// # Returns
+    /// The state root, or StateRootNotFound if no subblock was processed at that height
+    async fn get_state_root(&self, block_height: u64) -> Result<StateRoot, HyperIGError> {
+        let state = self.state.lock().await;
+        state.state_roots.get(&block_height)
+            .copied()
+            .ok_or(HyperIGError::StateRootNotFound(block_height))
+    }
+
+    /// Gets the time spent on the subblock at the given height.
+    /// 
+    /// # Arguments
+    /// * `block_height` - The block height to query
+    /// 
+    /// # Returns
+    /// The lock wait, execution and proposal queuing time, or None if no subblock was processed at that height
+    async fn get_subblock_timing(&self, block_height: u64) -> Result<Option<SubblockTiming>, HyperIGError> {
+        let state = self.state.lock().await;
+        Ok(state.subblock_timings.get(&block_height).copied())
+    }
+
+    /// Gets the keys transactions waited for the longest in total.
+    /// 
+    /// # Arguments
+    /// * `n` - The maximum number of keys to return
+    /// 
+    /// # Returns
+    /// The lock wait times of the hottest keys, sorted by total wait time (descending)
+    async fn get_hottest_keys(&self, n: usize) -> Result<Vec<KeyLockWait>, HyperIGError> {
+        let state = self.state.lock().await;
+        let mut keys: Vec<KeyLockWait> = state.key_lock_waits.values().cloned().collect();
+        keys.sort_by(|a, b| b.total_wait.cmp(&a.total_wait).then_with(|| a.key.cmp(&b.key)));
+        keys.truncate(n);
+        Ok(keys)
+    }
+
+    /// Gets every blocked transaction with the transactions and keys it waits on.
+    /// 
+    /// All dependencies are read under a single lock acquisition.
+    /// 
+    /// # Returns
+    /// Map from each blocked transaction to its blocking transactions and keys
+    async fn get_all_blocked_transactions(&self) -> Result<HashMap<TransactionId, BlockedTransaction>, HyperIGError> {
+        let state = self.state.lock().await;
+        Ok(state.tx_depends_on_txs.iter()
+            .filter(|(_, blocking_txs)| !blocking_txs.is_empty())
+            .map(|(tx_id, blocking_txs)| {
+                let mut blocking_txs: Vec<TransactionId> = blocking_txs.iter().cloned().collect();
+                blocking_txs.sort_by(|a, b| a.0.cmp(&b.0));
+                let mut keys: Vec<String> = state.tx_depends_on_keys.get(tx_id)
+                    .map(|keys| keys.iter().cloned().collect())
+                    .unwrap_or_default();
+                keys.sort();
+                (tx_id.clone(), BlockedTransaction { blocking_txs, keys })
+            })
+            .collect())
+    }
 }
 
 //==============================================================================
@@ -2056,4 +3483,97 @@ impl HyperIG for Arc<Mutex<HyperIGNode>> {
         let node = self.lock().await;
         node.get_cat_pending_detailed_counts().await
     }
-} 
\ No newline at end of file
+
+    /// Gets detailed counts of failed CATs split by failure reason.
+    /// 
+    /// # Returns
+    /// A tuple containing (timed_out, rejected) where:
+    /// - timed_out: CATs that did not receive a status update within their lifetime
+    /// - rejected: CATs rejected because they depend on a pending transaction
+    async fn get_cat_failure_detailed_counts(&self) -> Result<(u64, u64), HyperIGError> {
+        let node = self.lock().await;
+        node.get_cat_failure_detailed_counts().await
+    }
+
+    async fn get_transaction_status_counts_cats_by_fanout(&self) -> Result<BTreeMap<usize, (u64, u64, u64)>, HyperIGError> {
+        let node = self.lock().await;
+        node.get_transaction_status_counts_cats_by_fanout().await
+    }
+
+    async fn get_transaction_status_counts_cats_by_blocked(&self) -> Result<((u64, u64, u64), (u64, u64, u64)), HyperIGError> {
+        let node = self.lock().await;
+        node.get_transaction_status_counts_cats_by_blocked().await
+    }
+
+    async fn get_transaction_status_counts_regular_by_blocked(&self) -> Result<((u64, u64, u64), (u64, u64, u64)), HyperIGError> {
+        let node = self.lock().await;
+        node.get_transaction_status_counts_regular_by_blocked().await
+    }
+
+    /// Gets the transactions whose status changed while processing the given block.
+    /// 
+    /// # Arguments
+    /// * `block_height` - The block height to query
+    /// 
+    /// # Returns
+    /// A map from transaction ID to the status it reached in that block (empty if nothing changed)
+    async fn get_status_transitions(&self, block_height: u64) -> Result<HashMap<TransactionId, TransactionStatus>, HyperIGError> {
+        let node = self.lock().await;
+        node.get_status_transitions(block_height).await
+    }
+
+    /// Dry-runs a transaction against the current state.
+    /// 
+    /// # Arguments
+    /// * `transaction` - The transaction to simulate
+    /// 
+    /// # Returns
+    /// The keys, blocking transaction, execution result and expected status of the transaction
+    async fn simulate_transaction(&self, transaction: Transaction) -> Result<TransactionPreview, HyperIGError> {
+        let node = self.lock().await;
+        node.simulate_transaction(transaction).await
+    }
+
+    /// Gets the balance of an account, optionally with an inclusion proof.
+    /// 
+    /// # Arguments
+    /// * `chain_id` - The chain to query, must be the chain of this HIG
+    /// * `account` - The account to query
+    /// * `with_proof` - Whether to attach an inclusion proof against the current state root
+    async fn get_account_balance(&self, chain_id: ChainId, account: u32, with_proof: bool) -> Result<AccountBalance, HyperIGError> {
+        let node = self.lock().await;
+        node.get_account_balance(chain_id, account, with_proof).await
+    }
+
+    /// Gets the state root after processing the subblock at the given height.
+    /// 
+    /// # Arguments
+    /// * `block_height` - The block height to query
+    async fn get_state_root(&self, block_height: u64) -> Result<StateRoot, HyperIGError> {
+        let node = self.lock().await;
+        node.get_state_root(block_height).await
+    }
+
+    /// Gets the time spent on the subblock at the given height.
+    /// 
+    /// # Arguments
+    /// * `block_height` - The block height to query
+    async fn get_subblock_timing(&self, block_height: u64) -> Result<Option<SubblockTiming>, HyperIGError> {
+        let node = self.lock().await;
+        node.get_subblock_timing(block_height).await
+    }
+
+    /// Gets the keys transactions waited for the longest in total.
+    /// 
+    /// # Arguments
+    /// * `n` - The maximum number of keys to return
+    async fn get_hottest_keys(&self, n: usize) -> Result<Vec<KeyLockWait>, HyperIGError> {
+        let node = self.lock().await;
+        node.get_hottest_keys(n).await
+    }
+
+    async fn get_all_blocked_transactions(&self) -> Result<HashMap<TransactionId, BlockedTransaction>, HyperIGError> {
+        let node = self.lock().await;
+        node.get_all_blocked_transactions().await
+    }
+}
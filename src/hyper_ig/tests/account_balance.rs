@@ -0,0 +1,80 @@
+use crate::types::{ChainId, StateRoot, StateTree};
+use crate::types::NetworkSpec;
+use crate::utils::logging;
+use crate::hyper_ig::{HyperIG, HyperIGError};
+use crate::hyper_ig::tests::preloaded_accounts::setup_test_hig_node_with_preloaded_accounts;
+use std::collections::HashMap;
+
+/// Tests that account balances are returned with proofs that verify against the state root.
+/// 
+/// Test flow:
+/// 1. Creates a HIG node with 5 preloaded accounts (an odd number, so the tree has an unpaired node)
+/// 2. Queries every account with a proof and verifies the proof against the returned state root
+/// 3. Verifies that a tampered balance or a different root does not verify
+#[tokio::test]
+async fn test_account_balance_proofs_verify() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_account_balance_proofs_verify ===");
+
+    let hig_node = setup_test_hig_node_with_preloaded_accounts(5, 100).await;
+    let chain_id = ChainId("test-chain".to_string());
+
+    let mut state_root = None;
+    for account in 1..=5 {
+        let balance = hig_node.lock().await.get_account_balance(chain_id.clone(), account, true).await.unwrap();
+        assert_eq!(balance.balance, 100);
+        let proof = balance.proof.expect("Existing account should have a proof");
+        assert_eq!(proof.account, account);
+        assert!(proof.verify(&balance.state_root), "Proof of account {} should verify", account);
+
+        // All queries see the same state
+        assert_eq!(*state_root.get_or_insert(balance.state_root), balance.state_root);
+
+        let mut tampered = proof.clone();
+        tampered.balance = 1000;
+        assert!(!tampered.verify(&balance.state_root));
+        assert!(!proof.verify(&StateRoot([0; 32])));
+    }
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
+
+/// Tests account balance queries without proof, for missing accounts and for a foreign chain.
+#[tokio::test]
+async fn test_account_balance_queries() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_account_balance_queries ===");
+
+    let hig_node = setup_test_hig_node_with_preloaded_accounts(2, 50).await;
+    let chain_id = ChainId("test-chain".to_string());
+
+    // No proof unless requested
+    let balance = hig_node.lock().await.get_account_balance(chain_id.clone(), 1, false).await.unwrap();
+    assert_eq!(balance.balance, 50);
+    assert!(balance.proof.is_none());
+
+    // Missing accounts have balance 0 and no proof
+    let balance = hig_node.lock().await.get_account_balance(chain_id.clone(), 7, true).await.unwrap();
+    assert_eq!(balance.balance, 0);
+    assert!(balance.proof.is_none());
+
+    // Other chains are rejected
+    let result = hig_node.lock().await.get_account_balance(NetworkSpec::test_chain(2), 1, true).await;
+    assert!(matches!(result, Err(HyperIGError::WrongChainId { .. })));
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
+
+/// Tests that the state root only depends on the state, not on the insertion order.
+#[test]
+fn test_state_root_is_deterministic() {
+    let state_a: HashMap<u32, u32> = [(3, 30), (1, 10), (2, 20)].into_iter().collect();
+    let state_b: HashMap<u32, u32> = [(1, 10), (2, 20), (3, 30)].into_iter().collect();
+    assert_eq!(StateTree::from_state(&state_a).root(), StateTree::from_state(&state_b).root());
+
+    let state_c: HashMap<u32, u32> = [(1, 10), (2, 20), (3, 31)].into_iter().collect();
+    assert_ne!(StateTree::from_state(&state_a).root(), StateTree::from_state(&state_c).root());
+
+    // The empty state has a well-defined root
+    assert_eq!(StateTree::from_state(&HashMap::new()).root(), StateTree::from_state(&HashMap::new()).root());
+}
@@ -1,5 +1,5 @@
 use crate::{
-    types::{Transaction, TransactionId, TransactionStatus, CATStatusLimited, SubBlock, ChainId, CATId, constants, CLTransactionId, CATStatusUpdate},
+    types::{Transaction, TransactionId, TransactionStatus, CATStatusLimited, SubBlock, ChainId, CATId, NetworkSpec, CLTransactionId, CATStatusUpdate},
     hyper_ig::{HyperIG, node::HyperIGNode},
 };
 use std::sync::Arc;
@@ -14,7 +14,7 @@ pub async fn setup_test_hig_node(allow_cat_pending_dependencies: bool) -> (Arc<M
     let (_sender_cl_to_hig, receiver_cl_to_hig) = mpsc::channel(100);
     let (sender_hig_to_hs, receiver_hig_to_hs) = mpsc::channel(100);
     
-    let hig_node = HyperIGNode::new(receiver_cl_to_hig, sender_hig_to_hs, constants::chain_1(), 4, allow_cat_pending_dependencies);
+    let hig_node = HyperIGNode::new(receiver_cl_to_hig, sender_hig_to_hs, NetworkSpec::test_chain(1), 4, allow_cat_pending_dependencies);
     let hig_node = Arc::new(Mutex::new(hig_node));
     
     // Start the node
@@ -43,8 +43,8 @@ async fn run_test_regular_transaction_status(data: &str, expected_status: Transa
     let cl_id = CLTransactionId("cl-tx".to_string());
     let tx = Transaction::new(
         TransactionId(format!("{:?}:{}", cl_id, tx_id)),
-        constants::chain_1(),
-        vec![constants::chain_1()],
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1)],
         command.to_string(),
         cl_id.clone(),
     ).expect("Failed to create transaction");
@@ -90,8 +90,8 @@ async fn run_process_and_send_cat(data: &str, expected_status: crate::types::CAT
     let cl_id = CLTransactionId("cl-tx".to_string());
     let tx_chain_1 = Transaction::new(
         TransactionId(format!("{:?}:tx_chain_1", cl_id)),
-        constants::chain_1(),
-        vec![constants::chain_1(), constants::chain_2()],
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)],
         data.to_string(),
 
         cl_id.clone(),
@@ -144,7 +144,7 @@ async fn run_process_and_send_cat(data: &str, expected_status: crate::types::CAT
         crate::types::CATStatus::Failure => crate::types::CATStatusLimited::Failure,
         crate::types::CATStatus::Pending => panic!("Cannot send Pending status to HS"),
     };
-    hig_node.lock().await.send_cat_status_proposal(cat_id.clone(), status_limited, vec![constants::chain_1()])
+    hig_node.lock().await.send_cat_status_proposal(cat_id.clone(), status_limited, vec![NetworkSpec::test_chain(1)])
         .await
         .expect("Failed to send status proposal");
     logging::log("TEST", "Status proposal sent to HS");
@@ -194,8 +194,8 @@ async fn test_get_pending_transactions() {
     let cl_id_1 = CLTransactionId("cl-tx_cat".to_string());
     let tx_1 = Transaction::new(
         TransactionId(format!("{:?}:tx_1", cl_id_1)),
-        constants::chain_1(),
-        vec![constants::chain_1(), constants::chain_2()],
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)],
         "CAT.credit 1 100".to_string(),
         cl_id_1.clone(),
     ).expect("Failed to create transaction");
@@ -209,8 +209,8 @@ async fn test_get_pending_transactions() {
     let cl_id_2 = CLTransactionId("cl-tx_dependent".to_string());
     let tx_2 = Transaction::new(
         TransactionId(format!("{:?}:tx_2", cl_id_2)),
-        constants::chain_1(),
-        vec![constants::chain_1()],
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1)],
         "REGULAR.send 1 2 100".to_string(),
         cl_id_2.clone(),
     ).expect("Failed to create transaction");
@@ -336,8 +336,8 @@ async fn test_send_after_credit() {
     let cl_id_1 = CLTransactionId("cl-tx_1".to_string());
     let credit_tx = Transaction::new(
         TransactionId(format!("{:?}:credit-tx", cl_id_1)),
-        constants::chain_1(),
-        vec![constants::chain_1()],
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1)],
         "REGULAR.credit 1 100".to_string(),
         cl_id_1.clone(),
     ).expect("Failed to create credit transaction");
@@ -353,8 +353,8 @@ async fn test_send_after_credit() {
     let cl_id_2 = CLTransactionId("cl-tx_2".to_string());
     let send_tx = Transaction::new(
         TransactionId(format!("{:?}:send-tx", cl_id_2)),
-        constants::chain_1(),
-        vec![constants::chain_1()],
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1)],
         "REGULAR.send 1 2 50".to_string(),
         cl_id_2.clone(),
     ).expect("Failed to create send transaction");
@@ -399,8 +399,8 @@ async fn test_cat_send_no_funds() {
     let cl_id = CLTransactionId("cl-tx".to_string());
     let cat_send_tx = Transaction::new(
         TransactionId(format!("{:?}:cat-send-1", cl_id)),
-        constants::chain_1(),
-        vec![constants::chain_1(), constants::chain_2()],
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)],
         "CAT.send 1 2 50".to_string(),
         cl_id.clone(),
     ).expect("Failed to create CAT send transaction");
@@ -438,8 +438,8 @@ async fn test_cat_credit_pending() {
     let cl_id = CLTransactionId("cl-tx".to_string());
     let cat_credit_tx = Transaction::new(
         TransactionId(format!("{:?}:cat-credit-1", cl_id)),
-        constants::chain_1(),
-        vec![constants::chain_1(), constants::chain_2()],
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)],
         "CAT.credit 1 100".to_string(),
         cl_id.clone(),
     ).expect("Failed to create CAT credit transaction");
@@ -475,8 +475,8 @@ async fn test_cat_send_after_credit() {
     let cl_id_1 = CLTransactionId("cl-tx_1".to_string());
     let credit_tx = Transaction::new(
         TransactionId(format!("{:?}:credit-1", cl_id_1)),
-        constants::chain_1(),
-        vec![constants::chain_1()],
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1)],
         "REGULAR.credit 1 100".to_string(),
         cl_id_1.clone(),
     ).expect("Failed to create credit transaction");
@@ -488,8 +488,8 @@ async fn test_cat_send_after_credit() {
     let cl_id_2 = CLTransactionId("cl-tx_2".to_string());
     let cat_send_tx = Transaction::new(
         TransactionId(format!("{:?}:cat-send-1", cl_id_2)),
-        constants::chain_1(),
-        vec![constants::chain_1(), constants::chain_2()],
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)],
         "CAT.send 1 2 50".to_string(),
         cl_id_2.clone(),
     ).expect("Failed to create CAT send transaction");
@@ -532,8 +532,8 @@ async fn test_get_chain_state_after_transaction() {
     let cl_id = CLTransactionId("cl-tx".to_string());
     let tx = Transaction::new(
         TransactionId(format!("{:?}:test_tx", cl_id)),
-        constants::chain_1(),
-        vec![constants::chain_1()],
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1)],
         "REGULAR.credit 1 100".to_string(),
         cl_id.clone(),
     ).expect("Failed to create transaction");
@@ -560,15 +560,15 @@ async fn test_duplicate_transaction_id() {
     let cl_id = CLTransactionId("cl-tx".to_string());
     let tx = Transaction::new(
         TransactionId(format!("{:?}:test_tx", cl_id)),
-        constants::chain_1(),
-        vec![constants::chain_1()],
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1)],
         "REGULAR.credit 1 100".to_string(),
         cl_id.clone(),
     ).expect("Failed to create transaction");
     
     // Create a subblock with the transaction
     let subblock = SubBlock {
-        chain_id: constants::chain_1(),
+        chain_id: NetworkSpec::test_chain(1),
         block_height: 1,
         transactions: vec![tx.clone()],
     };
@@ -579,7 +579,7 @@ async fn test_duplicate_transaction_id() {
     
     // Create another subblock with the same transaction
     let subblock2 = SubBlock {
-        chain_id: constants::chain_1(),
+        chain_id: NetworkSpec::test_chain(1),
         block_height: 2,
         transactions: vec![tx],
     };
@@ -614,8 +614,8 @@ async fn test_hs_message_delay() {
     let cl_id = CLTransactionId("cl-tx_test".to_string());
     let tx = Transaction::new(
         TransactionId(format!("{:?}:tx", cl_id)),
-        constants::chain_1(),
-        vec![constants::chain_1(), constants::chain_2()],
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)],
         "CAT.credit 1 100".to_string(),
         cl_id.clone(),
     ).expect("Failed to create transaction");
@@ -649,7 +649,7 @@ async fn test_hs_message_delay() {
 /// Tests that CATs are rejected when they depend on pending transactions and allow_cat_pending_dependencies is false.
 /// This verifies that:
 /// 1. When allow_cat_pending_dependencies is false, CATs that depend on pending transactions are rejected
-/// 2. The rejected CAT is marked as failed
+/// 2. The rejected CAT is marked as Rejected
 /// 3. A failure status proposal is sent to HS
 /// 4. When allow_cat_pending_dependencies is true, CATs can depend on pending transactions
 #[tokio::test]
@@ -670,8 +670,8 @@ async fn test_cat_pending_dependency_restriction() {
         let cl_id_1 = CLTransactionId("cl-tx_cat_1".to_string());
         let cat_tx_1 = Transaction::new(
             TransactionId(format!("{:?}:cat_1", cl_id_1)),
-            constants::chain_1(),
-            vec![constants::chain_1(), constants::chain_2()],
+            NetworkSpec::test_chain(1),
+            vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)],
             "CAT.credit 1 100".to_string(),
             cl_id_1.clone(),
         ).expect("Failed to create first CAT transaction");
@@ -683,24 +683,28 @@ async fn test_cat_pending_dependency_restriction() {
         let cl_id_2 = CLTransactionId("cl-tx_cat_2".to_string());
         let cat_tx_2 = Transaction::new(
             TransactionId(format!("{:?}:cat_2", cl_id_2)),
-            constants::chain_1(),
-            vec![constants::chain_1(), constants::chain_2()],
+            NetworkSpec::test_chain(1),
+            vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)],
             "CAT.send 1 2 50".to_string(), // This depends on account 1 which is locked by the first CAT
             cl_id_2.clone(),
         ).expect("Failed to create second CAT transaction");
         
         let status = hig_node.lock().await.process_transaction(cat_tx_2.clone()).await.unwrap();
-        assert_eq!(status, TransactionStatus::Failure, "Second CAT should be rejected due to pending dependency");
+        assert_eq!(status, TransactionStatus::Rejected, "Second CAT should be rejected due to pending dependency");
         
-        // Verify the second CAT is marked as failed
+        // Verify the second CAT is marked as rejected
         let retrieved_status = hig_node.lock().await.get_transaction_status(cat_tx_2.id.clone()).await.unwrap();
-        assert_eq!(retrieved_status, TransactionStatus::Failure, "Rejected CAT should be marked as failed");
+        assert_eq!(retrieved_status, TransactionStatus::Rejected, "Rejected CAT should be marked as rejected");
+        
+        // Verify the rejection is counted separately from timeouts
+        let failure_detailed = hig_node.lock().await.get_cat_failure_detailed_counts().await.unwrap();
+        assert_eq!(failure_detailed, (0, 1), "CAT failure detailed counts should be (timed_out=0, rejected=1)");
         
         // Verify the second CAT is not in pending transactions
         let pending = hig_node.lock().await.get_pending_transactions().await.unwrap();
         assert!(!pending.contains(&cat_tx_2.id), "Rejected CAT should not be in pending transactions");
         
-        // Verify a failure status proposal is sent to HS for the second CAT
+        // Verify a rejection status proposal is sent to HS for the second CAT
         let cat_id_2 = CATId(cl_id_2.clone());
         let proposed_status = hig_node.lock().await.get_proposed_status(cat_tx_2.id.clone()).await.unwrap();
         assert_eq!(proposed_status, crate::types::CATStatus::Failure, "Rejected CAT should propose Failure status");
@@ -713,7 +717,7 @@ async fn test_cat_pending_dependency_restriction() {
         for _ in 0..2 {
             if let Ok(status_update) = receiver_hig_to_hs.try_recv() {
                 if status_update.cat_id == cat_id_2 {
-                    assert_eq!(status_update.status, CATStatusLimited::Failure, "Status update for second CAT should be Failure");
+                    assert_eq!(status_update.status, CATStatusLimited::Rejected, "Status update for second CAT should be Rejected");
                     found_second_cat_update = true;
                     break;
                 }
@@ -737,8 +741,8 @@ async fn test_cat_pending_dependency_restriction() {
         let cl_id_1 = CLTransactionId("cl-tx_cat_3".to_string());
         let cat_tx_1 = Transaction::new(
             TransactionId(format!("{:?}:cat_3", cl_id_1)),
-            constants::chain_1(),
-            vec![constants::chain_1(), constants::chain_2()],
+            NetworkSpec::test_chain(1),
+            vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)],
             "CAT.credit 1 100".to_string(),
             cl_id_1.clone(),
         ).expect("Failed to create first CAT transaction");
@@ -750,8 +754,8 @@ async fn test_cat_pending_dependency_restriction() {
         let cl_id_2 = CLTransactionId("cl-tx_cat_4".to_string());
         let cat_tx_2 = Transaction::new(
             TransactionId(format!("{:?}:cat_4", cl_id_2)),
-            constants::chain_1(),
-            vec![constants::chain_1(), constants::chain_2()],
+            NetworkSpec::test_chain(1),
+            vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)],
             "CAT.send 1 2 50".to_string(), // This depends on account 1 which is locked by the first CAT
             cl_id_2.clone(),
         ).expect("Failed to create second CAT transaction");
@@ -823,8 +827,8 @@ async fn test_proposal_queue_independent_delays() {
     let cl_id_1 = CLTransactionId("cl-tx_queue_1".to_string());
     let cat_tx_1 = Transaction::new(
         TransactionId(format!("{:?}:cat_1", cl_id_1)),
-        constants::chain_1(),
-        vec![constants::chain_1(), constants::chain_2()],
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)],
         "CAT.credit 1 100".to_string(),
         cl_id_1.clone(),
     ).expect("Failed to create first CAT transaction");
@@ -839,8 +843,8 @@ async fn test_proposal_queue_independent_delays() {
     let cl_id_2 = CLTransactionId("cl-tx_queue_2".to_string());
     let cat_tx_2 = Transaction::new(
         TransactionId(format!("{:?}:cat_2", cl_id_2)),
-        constants::chain_1(),
-        vec![constants::chain_1(), constants::chain_2()],
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)],
         "CAT.credit 2 100".to_string(),
         cl_id_2.clone(),
     ).expect("Failed to create second CAT transaction");
@@ -854,8 +858,8 @@ async fn test_proposal_queue_independent_delays() {
     let cl_id_3 = CLTransactionId("cl-tx_queue_3".to_string());
     let cat_tx_3 = Transaction::new(
         TransactionId(format!("{:?}:cat_3", cl_id_3)),
-        constants::chain_1(),
-        vec![constants::chain_1(), constants::chain_2()],
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)],
         "CAT.credit 3 100".to_string(),
         cl_id_3.clone(),
     ).expect("Failed to create third CAT transaction");
@@ -946,8 +950,8 @@ async fn test_cat_dependency_resolution(
     let cl_id_1 = CLTransactionId("cl-tx_cat_1".to_string());
     let cat_tx_1 = Transaction::new(
         TransactionId(format!("{:?}:cat_1", cl_id_1)),
-        constants::chain_1(),
-        vec![constants::chain_1(), constants::chain_2()],
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)],
         "CAT.credit 1 100".to_string(), // Fixed transaction data
         cl_id_1.clone(),
     ).expect("Failed to create first CAT transaction");
@@ -966,8 +970,8 @@ async fn test_cat_dependency_resolution(
     let cl_id_2 = CLTransactionId("cl-tx_cat_2".to_string());
     let cat_tx_2 = Transaction::new(
         TransactionId(format!("{:?}:cat_2", cl_id_2)),
-        constants::chain_1(),
-        vec![constants::chain_1(), constants::chain_2()],
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)],
         "CAT.send 1 2 50".to_string(), // This depends on account 1 which is locked by first CAT
         cl_id_2.clone(),
     ).expect("Failed to create second CAT transaction");
@@ -991,8 +995,8 @@ async fn test_cat_dependency_resolution(
     // Now resolve the first CAT with the specified status
     let status_update_tx = Transaction::new(
         TransactionId("status_update".to_string()),
-        constants::chain_1(),
-        vec![constants::chain_1()],
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1)],
         format!("STATUS_UPDATE:{}.CAT_ID:cl-tx_cat_1", first_cat_resolution_status),
         cl_id_1.clone(),
     ).expect("Failed to create status update transaction");
@@ -1042,8 +1046,8 @@ async fn test_regular_tx_dependency_resolution(
     let cl_id_1 = CLTransactionId("cl-tx_cat_1".to_string());
     let cat_tx_1 = Transaction::new(
         TransactionId(format!("{:?}:cat_1", cl_id_1)),
-        constants::chain_1(),
-        vec![constants::chain_1(), constants::chain_2()],
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)],
         "CAT.credit 1 100".to_string(), // Fixed transaction data
         cl_id_1.clone(),
     ).expect("Failed to create first CAT transaction");
@@ -1061,8 +1065,8 @@ async fn test_regular_tx_dependency_resolution(
     // Second transaction: Regular transaction that depends on the first CAT
     let regular_tx_2 = Transaction::new(
         TransactionId("regular_tx_2".to_string()),
-        constants::chain_1(),
-        vec![constants::chain_1()],
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1)],
         "REGULAR.send 1 2 50".to_string(), // This depends on account 1 which is locked by first CAT
         cl_id_1.clone(),
     ).expect("Failed to create second regular transaction");
@@ -1081,8 +1085,8 @@ async fn test_regular_tx_dependency_resolution(
     // Now resolve the first CAT with the specified status
     let status_update_tx = Transaction::new(
         TransactionId("status_update".to_string()),
-        constants::chain_1(),
-        vec![constants::chain_1()],
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1)],
         format!("STATUS_UPDATE:{}.CAT_ID:cl-tx_cat_1", first_cat_resolution_status),
         cl_id_1.clone(),
     ).expect("Failed to create status update transaction");
@@ -0,0 +1,72 @@
+use crate::types::{Transaction, TransactionId, CLTransactionId, CATId, CATStatus, CATStatusLimited, NetworkSpec};
+use crate::utils::logging;
+use crate::hyper_ig::{HyperIG, CatAuditEvent};
+use crate::hyper_ig::tests::basic::setup_test_hig_node;
+use std::time::Duration;
+
+/// Helper function to create a CAT between chain-1 and chain-2
+fn cat_transaction(cl_id: &str, data: &str) -> Transaction {
+    let cl_id = CLTransactionId(cl_id.to_string());
+    Transaction::new(
+        TransactionId(format!("{}:tx", cl_id.0)),
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)],
+        data.to_string(),
+        cl_id,
+    ).expect("Failed to create CAT transaction")
+}
+
+/// Tests that the HIG records the steps of the resolution of CATs.
+/// 
+/// Test flow:
+/// 1. Processes a CAT crediting account 1 and a CAT debiting account 1, which is postponed behind the first
+/// 2. Resolves the first CAT with a Success status update, which reprocesses the second
+/// 3. Verifies the audit trail of the first CAT covers reception, proposal, sending and the status update
+/// 4. Verifies the audit trail of the second CAT records what it was postponed behind and its later proposal
+#[tokio::test]
+async fn test_cat_audit_trail_recorded() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_cat_audit_trail_recorded ===");
+
+    let (hig_node, _rx) = setup_test_hig_node(true).await;
+    let credit = cat_transaction("cl-tx_credit", "CAT.credit 1 100");
+    let debit = cat_transaction("cl-tx_debit", "CAT.debit 1 50");
+    hig_node.lock().await.process_transaction(credit.clone()).await.unwrap();
+    hig_node.lock().await.process_transaction(debit.clone()).await.unwrap();
+
+    // Let the proposal queue send the proposal of the first CAT
+    tokio::time::sleep(Duration::from_millis(300)).await;
+
+    let status_update = Transaction::new(
+        TransactionId("cl-tx_credit:status".to_string()),
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1)],
+        "STATUS_UPDATE:Success.CAT_ID:cl-tx_credit".to_string(),
+        credit.cl_id.clone(),
+    ).expect("Failed to create status update");
+    hig_node.lock().await.process_transaction(status_update).await.unwrap();
+
+    let credit_trail = hig_node.lock().await.get_cat_audit_trail(&CATId(credit.cl_id.clone())).await;
+    let events: Vec<CatAuditEvent> = credit_trail.iter().map(|entry| entry.event.clone()).collect();
+    logging::log("TEST", &format!("Audit trail of the first CAT: {:?}", events));
+    assert_eq!(events, vec![
+        CatAuditEvent::Received { tx_id: credit.id.clone() },
+        CatAuditEvent::Proposed { status: CATStatus::Success },
+        CatAuditEvent::ProposalSent { status: CATStatusLimited::Success, attempts: 1 },
+        CatAuditEvent::StatusUpdate { status: CATStatusLimited::Success, applied: true },
+    ]);
+    assert!(credit_trail.windows(2).all(|pair| pair[0].at <= pair[1].at), "Events should be in the order they happened");
+
+    let debit_trail = hig_node.lock().await.get_cat_audit_trail(&CATId(debit.cl_id.clone())).await;
+    let events: Vec<CatAuditEvent> = debit_trail.iter().map(|entry| entry.event.clone()).collect();
+    logging::log("TEST", &format!("Audit trail of the second CAT: {:?}", events));
+    assert_eq!(events[..3], [
+        CatAuditEvent::Received { tx_id: debit.id.clone() },
+        CatAuditEvent::Postponed { blocked_by: credit.id.clone(), key: "1".to_string() },
+        CatAuditEvent::Proposed { status: CATStatus::Success },
+    ]);
+
+    assert!(hig_node.lock().await.get_cat_audit_trail(&CATId(CLTransactionId("unknown".to_string()))).await.is_empty());
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
@@ -0,0 +1,65 @@
+use crate::types::{Transaction, TransactionId, TransactionStatus, CLTransactionId, CATStatus, NetworkSpec};
+use crate::utils::logging;
+use crate::hyper_ig::tests::basic::setup_test_hig_node;
+use crate::hyper_ig::HyperIG;
+
+/// Creates a transaction on chain-1
+fn chain_1_tx(name: &str, data: &str, constituent_chains: Vec<crate::types::ChainId>) -> Transaction {
+    let cl_id = CLTransactionId(name.to_string());
+    Transaction::new(
+        TransactionId(format!("{}:tx", cl_id.0)),
+        NetworkSpec::test_chain(1),
+        constituent_chains,
+        data.to_string(),
+        cl_id,
+    ).expect("Failed to create transaction")
+}
+
+/// Tests that a HIG validates only its own chain's command of a CAT.
+/// 
+/// Test flow:
+/// 1. Processes the debit half of a transfer CAT on an account without balance
+/// 2. Verifies the HIG proposes Failure, regardless of the credit on the other chain
+/// 3. Credits the account and processes another debit, which proposes Success
+#[tokio::test]
+async fn test_cat_debit_validated_against_own_chain() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_cat_debit_validated_against_own_chain ===");
+
+    let (hig_node, _rx) = setup_test_hig_node(true).await;
+    let cat_chains = vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)];
+
+    let failing_debit = chain_1_tx("cl-tx_failing", "CAT.debit 1 50", cat_chains.clone());
+    hig_node.lock().await.process_transaction(failing_debit.clone()).await.unwrap();
+    assert_eq!(hig_node.lock().await.get_proposed_status(failing_debit.id).await.unwrap(), CATStatus::Failure);
+
+    let credit = chain_1_tx("cl-tx_credit", "REGULAR.credit 1 100", vec![NetworkSpec::test_chain(1)]);
+    assert_eq!(hig_node.lock().await.process_transaction(credit).await.unwrap(), TransactionStatus::Success);
+
+    let debit = chain_1_tx("cl-tx_debit", "CAT.debit 1 50", cat_chains);
+    hig_node.lock().await.process_transaction(debit.clone()).await.unwrap();
+    assert_eq!(hig_node.lock().await.get_proposed_status(debit.id).await.unwrap(), CATStatus::Success);
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
+
+/// Tests that a regular debit decreases the balance and fails on insufficient funds.
+#[tokio::test]
+async fn test_regular_debit() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_regular_debit ===");
+
+    let (hig_node, _rx) = setup_test_hig_node(true).await;
+    let credit = chain_1_tx("cl-tx_credit", "REGULAR.credit 1 100", vec![NetworkSpec::test_chain(1)]);
+    hig_node.lock().await.process_transaction(credit).await.unwrap();
+
+    let debit = chain_1_tx("cl-tx_debit", "REGULAR.debit 1 60", vec![NetworkSpec::test_chain(1)]);
+    assert_eq!(hig_node.lock().await.process_transaction(debit).await.unwrap(), TransactionStatus::Success);
+    let overdraft = chain_1_tx("cl-tx_overdraft", "REGULAR.debit 1 60", vec![NetworkSpec::test_chain(1)]);
+    assert_eq!(hig_node.lock().await.process_transaction(overdraft).await.unwrap(), TransactionStatus::Failure);
+
+    let balance = hig_node.lock().await.get_account_balance(NetworkSpec::test_chain(1), 1, false).await.unwrap();
+    assert_eq!(balance.balance, 40);
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
@@ -0,0 +1,120 @@
+use crate::types::{Transaction, TransactionId, TransactionStatus, SubBlock, CLTransactionId, CATId, NetworkSpec};
+use crate::utils::logging;
+use crate::hyper_ig::{HyperIG, HyperIGError, CatIdCollisionPolicy, CatAuditEvent, node::HyperIGNode};
+use crate::hyper_ig::tests::basic::setup_test_hig_node;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{mpsc, Mutex};
+
+/// Helper function: creates a CAT between chain-1 and chain-2 with the given transaction id and CAT id
+fn cat_tx(tx_id: &str, cl_id: &str, data: &str) -> Transaction {
+    Transaction::new(
+        TransactionId(tx_id.to_string()),
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)],
+        data.to_string(),
+        CLTransactionId(cl_id.to_string()),
+    ).expect("Failed to create CAT transaction")
+}
+
+/// Helper function: creates a subblock on chain 1
+fn subblock(block_height: u64, transactions: Vec<Transaction>) -> SubBlock {
+    SubBlock {
+        chain_id: NetworkSpec::test_chain(1),
+        block_height,
+        transactions,
+    }
+}
+
+/// Tests that a CAT reusing the id of an earlier CAT is rejected and counted by default.
+/// 
+/// Test flow:
+/// 1. Processes a CAT, then a second CAT with a different transaction id but the same CAT id
+/// 2. Verifies the second CAT was not processed and the collision was counted
+/// 3. Verifies the first CAT is still pending under its id
+#[tokio::test]
+async fn test_cat_id_collision_rejects_new() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_cat_id_collision_rejects_new ===");
+
+    let (hig_node, _rx) = setup_test_hig_node(true).await;
+    assert_eq!(hig_node.lock().await.get_cat_id_collision_policy().await, CatIdCollisionPolicy::RejectNew);
+
+    let first = cat_tx("cl-tx_cat:tx-1", "cl-tx_cat", "CAT.credit 1 100");
+    let second = cat_tx("cl-tx_cat:tx-2", "cl-tx_cat", "CAT.credit 2 100");
+    hig_node.lock().await.process_subblock(subblock(1, vec![first.clone()])).await.unwrap();
+    hig_node.lock().await.process_subblock(subblock(2, vec![second.clone()])).await.unwrap();
+
+    assert_eq!(hig_node.lock().await.get_cat_id_collision_count().await, 1);
+    assert!(hig_node.lock().await.get_transaction_status(second.id.clone()).await.is_err(), "The colliding CAT should not be processed");
+    assert_eq!(hig_node.lock().await.get_transaction_status(first.id.clone()).await.unwrap(), TransactionStatus::Pending);
+    let trail = hig_node.lock().await.get_cat_audit_trail(&CATId(first.cl_id.clone())).await;
+    assert!(trail.iter().all(|entry| entry.event != CatAuditEvent::Received { tx_id: second.id.clone() }), "The colliding CAT should not enter the audit trail");
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
+
+/// Tests that a collision seen by only one chain does not make the chains process the CAT under different ids.
+/// 
+/// Test flow:
+/// 1. Chain-1 processes an earlier CAT that chain-2 never received
+/// 2. Both chains process a new CAT with the same CAT id
+/// 3. Verifies chain-1 rejected the new CAT without proposing it, while chain-2 proposed it under its own id
+/// 4. Verifies neither chain proposed a CAT under a synthesized id
+#[tokio::test]
+async fn test_cat_id_collision_seen_by_one_chain() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_cat_id_collision_seen_by_one_chain ===");
+
+    let (hig_node_1, mut receiver_1) = setup_test_hig_node(true).await;
+    let (_sender_cl_to_hig, receiver_cl_to_hig) = mpsc::channel(100);
+    let (sender_hig_to_hs, mut receiver_2) = mpsc::channel(100);
+    let hig_node_2 = Arc::new(Mutex::new(HyperIGNode::new(receiver_cl_to_hig, sender_hig_to_hs, NetworkSpec::test_chain(2), 4, true)));
+    HyperIGNode::start(hig_node_2.clone()).await;
+
+    let earlier = cat_tx("cl-tx_cat:tx-1", "cl-tx_cat", "CAT.credit 1 100");
+    hig_node_1.lock().await.process_subblock(subblock(1, vec![earlier.clone()])).await.unwrap();
+
+    let new_1 = cat_tx("cl-tx_cat:tx-2", "cl-tx_cat", "CAT.credit 2 100");
+    let new_2 = Transaction { id: TransactionId("cl-tx_cat:tx-2.chain-2".to_string()), chain_id: NetworkSpec::test_chain(2), ..new_1.clone() };
+    hig_node_1.lock().await.process_subblock(subblock(2, vec![new_1.clone()])).await.unwrap();
+    hig_node_2.lock().await.process_subblock(SubBlock { chain_id: NetworkSpec::test_chain(2), block_height: 2, transactions: vec![new_2.clone()] }).await.unwrap();
+
+    assert_eq!(hig_node_1.lock().await.get_cat_id_collision_count().await, 1);
+    assert_eq!(hig_node_2.lock().await.get_cat_id_collision_count().await, 0);
+    assert!(hig_node_1.lock().await.get_transaction_status(new_1.id.clone()).await.is_err(), "Chain-1 should not process the colliding CAT");
+    assert_eq!(hig_node_2.lock().await.get_transaction_status(new_2.id.clone()).await.unwrap(), TransactionStatus::Pending);
+
+    // Only the earlier CAT on chain-1 and the new CAT on chain-2 are proposed, both under the original id
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    for (receiver, chain) in [(&mut receiver_1, "chain-1"), (&mut receiver_2, "chain-2")] {
+        let proposal = receiver.try_recv().unwrap_or_else(|_| panic!("{} should propose one CAT", chain));
+        assert_eq!(proposal.cat_id, CATId(CLTransactionId("cl-tx_cat".to_string())));
+        assert!(receiver.try_recv().is_err(), "{} should not propose another CAT", chain);
+    }
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
+
+/// Tests that the Error policy aborts the subblock at the colliding CAT.
+#[tokio::test]
+async fn test_cat_id_collision_error() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_cat_id_collision_error ===");
+
+    let (hig_node, _rx) = setup_test_hig_node(true).await;
+    hig_node.lock().await.set_cat_id_collision_policy(CatIdCollisionPolicy::Error).await;
+
+    let first = cat_tx("cl-tx_cat:tx-1", "cl-tx_cat", "CAT.credit 1 100");
+    let second = cat_tx("cl-tx_cat:tx-2", "cl-tx_cat", "CAT.credit 2 100");
+    hig_node.lock().await.process_subblock(subblock(1, vec![first.clone()])).await.unwrap();
+    let result = hig_node.lock().await.process_subblock(subblock(2, vec![second.clone()])).await;
+
+    assert!(
+        matches!(result, Err(HyperIGError::CatIdCollision { ref cat_id, ref tx_id }) if *cat_id == CATId(first.cl_id.clone()) && *tx_id == second.id),
+        "Expected CatIdCollision, got {:?}", result
+    );
+    assert_eq!(hig_node.lock().await.get_cat_id_collision_count().await, 1);
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
@@ -1,6 +1,6 @@
 use crate::hyper_ig::{HyperIGNode, HyperIG};
 use crate::types::{Transaction, TransactionId, CLTransactionId, TransactionStatus, SubBlock};
-use crate::types::constants;
+use crate::types::NetworkSpec;
 use crate::utils::logging;
 use std::sync::Arc;
 use tokio::sync::Mutex;
@@ -26,8 +26,8 @@ async fn test_cat_status_update_with_credit_amount(
     let cl_id = CLTransactionId(format!("cl-tx_cat_{}", cat_credit_amount));
     let cat_tx = Transaction::new(
         TransactionId(format!("{:?}:cat_{}", cl_id, cat_credit_amount)),
-        constants::chain_1(),
-        vec![constants::chain_1(), constants::chain_2()],
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)],
         format!("CAT.credit 1 {}", cat_credit_amount),
         cl_id.clone(),
     ).expect("Failed to create CAT transaction");
@@ -35,8 +35,8 @@ async fn test_cat_status_update_with_credit_amount(
     // Create a regular transaction that will succeed or fail based on the CAT's credit amount
     let regular_tx = Transaction::new(
         TransactionId(format!("regular_tx_{}", cat_credit_amount)),
-        constants::chain_1(),
-        vec![constants::chain_1()],
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1)],
         "REGULAR.send 1 2 1000".to_string(), // This will succeed if CAT credits 1000, fail if CAT credits 100
         CLTransactionId(format!("cl-tx_regular_{}", cat_credit_amount)),
     ).expect("Failed to create regular transaction");
@@ -70,16 +70,16 @@ async fn test_cat_status_update_with_credit_amount(
     logging::log("TEST", "Updating CAT status to success...");
     let status_update = Transaction::new(
         TransactionId(format!("{}:status_update", cl_id.0)),
-        constants::chain_1(),
-        vec![constants::chain_1()],
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1)],
         format!("STATUS_UPDATE:Success.CAT_ID:{}", cl_id.0),
-        cl_id.clone(),
+        CLTransactionId(format!("{}.UPDATE", cl_id.0)),
     ).expect("Failed to create status update");
     
     // Process the status update in a subblock
     let subblock = SubBlock {
         block_height: 2,
-        chain_id: constants::chain_1(),
+        chain_id: NetworkSpec::test_chain(1),
         transactions: vec![status_update],
     };
     hig_node.lock().await.process_subblock(subblock).await.unwrap();
@@ -126,7 +126,7 @@ async fn setup_test_hig_node(allow_cat_pending_dependencies: bool) -> (Arc<Mutex
     let hig_node = HyperIGNode::new(
         receiver_cl_to_hig,
         sender_hig_to_hs,
-        constants::chain_1(),
+        NetworkSpec::test_chain(1),
         10, // cat_lifetime
         allow_cat_pending_dependencies,
     );
@@ -149,8 +149,8 @@ async fn test_add_to_pending_and_increment_counter() {
     let cl_id = CLTransactionId("cl-tx_cat_1".to_string());
     let cat_tx = Transaction::new(
         TransactionId(format!("{:?}:cat_1", cl_id)),
-        constants::chain_1(),
-        vec![constants::chain_1(), constants::chain_2()],
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)],
         "CAT.credit 1 100".to_string(),
         cl_id.clone(),
     ).expect("Failed to create CAT transaction");
@@ -197,8 +197,8 @@ async fn test_update_to_final_status_and_update_counter() {
     // Create a regular transaction that will fail
     let regular_tx = Transaction::new(
         TransactionId("regular_tx_2".to_string()),
-        constants::chain_1(),
-        vec![constants::chain_1()],
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1)],
         "REGULAR.send 1 2 1000".to_string(), // This will fail due to insufficient funds
         CLTransactionId("cl-tx_regular_2".to_string()),
     ).expect("Failed to create regular transaction");
@@ -239,8 +239,8 @@ async fn test_counter_consistency_on_reprocessing() {
     let cl_id = CLTransactionId("cl-tx_cat_2".to_string());
     let cat_tx = Transaction::new(
         TransactionId(format!("{:?}:cat_2", cl_id)),
-        constants::chain_1(),
-        vec![constants::chain_1(), constants::chain_2()],
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)],
         "CAT.credit 1 100".to_string(),
         cl_id.clone(),
     ).expect("Failed to create CAT transaction");
@@ -307,8 +307,8 @@ async fn test_cat_timeout_counter_management() {
     let cl_id = CLTransactionId("cl-tx_timeout_test".to_string());
     let cat_tx = Transaction::new(
         TransactionId(format!("{:?}:timeout_test", cl_id)),
-        constants::chain_1(),
-        vec![constants::chain_1(), constants::chain_2()],
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)],
         "CAT.credit 1 100".to_string(),
         cl_id.clone(),
     ).expect("Failed to create CAT transaction");
@@ -316,7 +316,7 @@ async fn test_cat_timeout_counter_management() {
     // Process the CAT transaction in block 1
     let subblock = SubBlock {
         block_height: 1,
-        chain_id: constants::chain_1(),
+        chain_id: NetworkSpec::test_chain(1),
         transactions: vec![cat_tx.clone()],
     };
     hig_node.lock().await.process_subblock(subblock).await.unwrap();
@@ -348,7 +348,7 @@ async fn test_cat_timeout_counter_management() {
     let timeout_block = max_lifetime + 1;
     let subblock = SubBlock {
         block_height: timeout_block,
-        chain_id: constants::chain_1(),
+        chain_id: NetworkSpec::test_chain(1),
         transactions: vec![],
     };
     hig_node.lock().await.process_subblock(subblock).await.unwrap();
@@ -356,7 +356,7 @@ async fn test_cat_timeout_counter_management() {
     
     // Verify CAT is now failed
     let status_after = hig_node.lock().await.get_transaction_status(cat_tx.id.clone()).await.unwrap();
-    assert_eq!(status_after, TransactionStatus::Failure, "CAT should be failed after timeout");
+    assert_eq!(status_after, TransactionStatus::TimedOut, "CAT should be timed out after timeout");
     
     // Get counts after timeout
     let cat_counts_after = hig_node.lock().await.get_transaction_status_counts_cats().await.unwrap();
@@ -373,6 +373,10 @@ async fn test_cat_timeout_counter_management() {
     assert_eq!(pending_detailed_after.0, 0, "CAT resolving count should be 0 after timeout");
     assert_eq!(pending_detailed_after.1, 0, "CAT postponed count should be 0 after timeout");
     
+    // Verify the failure is counted as a timeout
+    let failure_detailed_after = hig_node.lock().await.get_cat_failure_detailed_counts().await.unwrap();
+    assert_eq!(failure_detailed_after, (1, 0), "CAT failure detailed counts should be (timed_out=1, rejected=0)");
+    
     // Verify CAT is removed from pending set
     let pending_txs_after = hig_node.lock().await.get_pending_transactions().await.unwrap();
     assert!(!pending_txs_after.contains(&cat_tx.id), "CAT should not be in pending set after timeout");
@@ -408,8 +412,8 @@ async fn test_cat_accumulation_management() {
         let cl_id = CLTransactionId(format!("cl-tx_accumulation_{}", i));
         let cat_tx = Transaction::new(
             TransactionId(format!("{:?}:accumulation_{}", cl_id, i)),
-            constants::chain_1(),
-            vec![constants::chain_1(), constants::chain_2()],
+            NetworkSpec::test_chain(1),
+            vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)],
             "CAT.credit 1 100".to_string(),
             cl_id.clone(),
         ).expect("Failed to create CAT transaction");
@@ -419,7 +423,7 @@ async fn test_cat_accumulation_management() {
         // Process CAT in block i+1
         let subblock = SubBlock {
             block_height: (i + 1) as u64,
-            chain_id: constants::chain_1(),
+            chain_id: NetworkSpec::test_chain(1),
             transactions: vec![cat_tx],
         };
         hig_node.lock().await.process_subblock(subblock).await.unwrap();
@@ -441,7 +445,7 @@ async fn test_cat_accumulation_management() {
     let timeout_block = cat_lifetime + 2; // Block after max_lifetime for first CAT
     let subblock = SubBlock {
         block_height: timeout_block,
-        chain_id: constants::chain_1(),
+        chain_id: NetworkSpec::test_chain(1),
         transactions: vec![],
     };
     hig_node.lock().await.process_subblock(subblock).await.unwrap();
@@ -471,7 +475,7 @@ async fn test_cat_accumulation_management() {
     let final_timeout_block = cat_lifetime + num_cats as u64 + 1;
     let subblock = SubBlock {
         block_height: final_timeout_block,
-        chain_id: constants::chain_1(),
+        chain_id: NetworkSpec::test_chain(1),
         transactions: vec![],
     };
     hig_node.lock().await.process_subblock(subblock).await.unwrap();
@@ -511,8 +515,8 @@ async fn test_postponed_to_resolving_transition() {
     let cl_id_1 = CLTransactionId("cl-tx_cat_1".to_string());
     let cat_tx_1 = Transaction::new(
         TransactionId(format!("{:?}:cat_1", cl_id_1)),
-        constants::chain_1(),
-        vec![constants::chain_1(), constants::chain_2()],
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)],
         "CAT.credit 1 100".to_string(),
         cl_id_1.clone(),
     ).expect("Failed to create first CAT transaction");
@@ -525,8 +529,8 @@ async fn test_postponed_to_resolving_transition() {
     let cl_id_2 = CLTransactionId("cl-tx_cat_2".to_string());
     let cat_tx_2 = Transaction::new(
         TransactionId(format!("{:?}:cat_2", cl_id_2)),
-        constants::chain_1(),
-        vec![constants::chain_1(), constants::chain_2()],
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)],
         "CAT.credit 1 100".to_string(),
         cl_id_2.clone(),
     ).expect("Failed to create second CAT transaction");
@@ -566,8 +570,8 @@ async fn test_postponed_to_resolving_transition() {
     // Now resolve the first CAT to trigger reprocessing of the second CAT
     let status_update_tx = Transaction::new(
         TransactionId("status_update".to_string()),
-        constants::chain_1(),
-        vec![constants::chain_1()],
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1)],
         "STATUS_UPDATE:Success.CAT_ID:cl-tx_cat_1".to_string(),
         cl_id_1.clone(),
     ).expect("Failed to create status update transaction");
@@ -631,8 +635,8 @@ async fn test_regular_transaction_timing_metrics() {
     // Create a regular transaction that will succeed
     let regular_tx = Transaction::new(
         TransactionId("regular_tx_timing".to_string()),
-        constants::chain_1(),
-        vec![constants::chain_1()],
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1)],
         "REGULAR.credit 1 100".to_string(),
         CLTransactionId("cl-tx_timing".to_string()),
     ).expect("Failed to create regular transaction");
@@ -680,8 +684,8 @@ async fn test_regular_tx_multiple_dependencies() {
         let cl_id = CLTransactionId(format!("cl-cat-{}", i));
         let cat_tx = Transaction::new(
             TransactionId(format!("{:?}:cat-credit-tx-{}", cl_id, i)),
-            constants::chain_1(),
-            vec![constants::chain_1(), constants::chain_2()],
+            NetworkSpec::test_chain(1),
+            vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)],
             format!("CAT.credit {} 100", i),
             cl_id.clone(),
         ).expect("Failed to create CAT transaction");
@@ -698,8 +702,8 @@ async fn test_regular_tx_multiple_dependencies() {
         let cl_id = CLTransactionId(format!("cl-reg-{}", i));
         let regular_tx = Transaction::new(
             TransactionId(format!("{:?}:regular-send-tx-{}", cl_id, i)),
-            constants::chain_1(),
-            vec![constants::chain_1()],
+            NetworkSpec::test_chain(1),
+            vec![NetworkSpec::test_chain(1)],
             format!("REGULAR.send {} {} 10", i, i + 1),
             cl_id.clone(),
         ).expect("Failed to create regular transaction");
@@ -725,8 +729,8 @@ async fn test_regular_tx_multiple_dependencies() {
     // Resolve the first CAT
     let status_update = Transaction::new(
         TransactionId("status_update_1".to_string()),
-        constants::chain_1(),
-        vec![constants::chain_1()],
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1)],
         "STATUS_UPDATE:Success.CAT_ID:cl-cat-1".to_string(),
         CLTransactionId("cl-cat-1".to_string()),
     ).expect("Failed to create status update");
@@ -754,8 +758,8 @@ async fn test_regular_tx_multiple_dependencies() {
     // Resolve the second CAT
     let status_update_2 = Transaction::new(
         TransactionId("status_update_2".to_string()),
-        constants::chain_1(),
-        vec![constants::chain_1()],
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1)],
         "STATUS_UPDATE:Success.CAT_ID:cl-cat-2".to_string(),
         CLTransactionId("cl-cat-2".to_string()),
     ).expect("Failed to create status update");
@@ -773,4 +777,50 @@ async fn test_regular_tx_multiple_dependencies() {
         cat_pending_final, regular_pending_final, regular_success_final, locked_keys_final));
     
     logging::log("TEST", "=== test_regular_tx_multiple_dependencies completed ===\n");
-} 
\ No newline at end of file
+} 
+/// Tests the breakdowns of the status counts:
+/// - CATs are counted per number of constituent chains
+/// - Transactions that found a key locked are counted as ever blocked, the others as never blocked
+/// - Each breakdown adds up to the plain status counts
+#[tokio::test]
+async fn test_status_counts_breakdowns() {
+    logging::init_logging();
+    logging::log("TEST", "=== Starting test_status_counts_breakdowns ===");
+
+    let (hig_node, _receiver_hig_to_hs) = setup_test_hig_node(true).await;
+    let transaction = |id: &str, chains: Vec<crate::types::ChainId>, data: &str| Transaction::new(
+        TransactionId(format!("{}:tx", id)),
+        NetworkSpec::test_chain(1),
+        chains,
+        data.to_string(),
+        CLTransactionId(id.to_string()),
+    ).expect("Failed to create transaction");
+    let two_chains = vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)];
+    let three_chains = vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2), NetworkSpec::test_chain(3)];
+
+    // cat-c is postponed behind cat-a, regular-blocked waits on the lock of cat-a
+    for tx in [
+        transaction("cat-a", two_chains.clone(), "CAT.credit 1 100"),
+        transaction("cat-b", three_chains, "CAT.credit 2 100"),
+        transaction("cat-c", two_chains, "CAT.credit 1 100"),
+        transaction("regular-blocked", vec![NetworkSpec::test_chain(1)], "REGULAR.credit 1 100"),
+        transaction("regular-free", vec![NetworkSpec::test_chain(1)], "REGULAR.credit 5 100"),
+    ] {
+        hig_node.lock().await.process_transaction(tx).await.unwrap();
+    }
+
+    let by_fanout = hig_node.lock().await.get_transaction_status_counts_cats_by_fanout().await.unwrap();
+    assert_eq!(by_fanout.into_iter().collect::<Vec<_>>(), vec![(2, (2, 0, 0)), (3, (1, 0, 0))]);
+
+    let (cats_ever_blocked, cats_never_blocked) = hig_node.lock().await.get_transaction_status_counts_cats_by_blocked().await.unwrap();
+    assert_eq!(cats_ever_blocked, (1, 0, 0), "Only cat-c was postponed");
+    assert_eq!(cats_never_blocked, (2, 0, 0));
+    assert_eq!(hig_node.lock().await.get_transaction_status_counts_cats().await.unwrap(), (3, 0, 0));
+
+    let (regular_ever_blocked, regular_never_blocked) = hig_node.lock().await.get_transaction_status_counts_regular_by_blocked().await.unwrap();
+    assert_eq!(regular_ever_blocked, (1, 0, 0), "regular-blocked waits on the lock of cat-a");
+    assert_eq!(regular_never_blocked, (0, 1, 0));
+    assert_eq!(hig_node.lock().await.get_transaction_status_counts_regular().await.unwrap(), (1, 1, 0));
+
+    logging::log("TEST", "=== test_status_counts_breakdowns completed ===\n");
+}
@@ -1,6 +1,6 @@
 use crate::hyper_ig::node::HyperIGNode;
 use crate::types::{Transaction, TransactionId, TransactionStatus, ChainId, CLTransactionId};
-use crate::types::constants;
+use crate::types::NetworkSpec;
 use crate::utils::logging;
 use crate::hyper_ig::HyperIG;
 use crate::hyper_ig::tests::basic::setup_test_hig_node;
@@ -641,8 +641,8 @@ async fn test_regular_tx_dependency_chain_resolution() {
     let cl_id_cat = CLTransactionId("cl-cat-credit-1".to_string());
     let cat_tx = Transaction::new(
         TransactionId(format!("{}:cat-credit-tx", cl_id_cat.0)),
-        constants::chain_1(),
-        vec![constants::chain_1(), constants::chain_2()],
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)],
         "CAT.credit 1 100".to_string(),
         cl_id_cat.clone(),
     ).expect("Failed to create CAT transaction");
@@ -656,8 +656,8 @@ async fn test_regular_tx_dependency_chain_resolution() {
     let cl_id_tx1 = CLTransactionId("cl-tx-1".to_string());
     let tx_1 = Transaction::new(
         TransactionId(format!("{}:regular-credit-tx", cl_id_tx1.0)),
-        constants::chain_1(),
-        vec![constants::chain_1()],
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1)],
         "REGULAR.credit 1 50".to_string(),
         cl_id_tx1.clone(),
     ).expect("Failed to create tx-1");
@@ -671,8 +671,8 @@ async fn test_regular_tx_dependency_chain_resolution() {
     let cl_id_tx2 = CLTransactionId("cl-tx-2".to_string());
     let tx_2 = Transaction::new(
         TransactionId(format!("{}:regular-credit-tx", cl_id_tx2.0)),
-        constants::chain_1(),
-        vec![constants::chain_1()],
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1)],
         "REGULAR.credit 1 30".to_string(),
         cl_id_tx2.clone(),
     ).expect("Failed to create tx-2");
@@ -694,8 +694,8 @@ async fn test_regular_tx_dependency_chain_resolution() {
     // Step 5: Resolve CAT with success
     let status_update = Transaction::new(
         TransactionId("status_update_cat".to_string()),
-        constants::chain_1(),
-        vec![constants::chain_1()],
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1)],
         format!("STATUS_UPDATE:Success.CAT_ID:{}", cl_id_cat.0),
         cl_id_cat.clone(),
     ).expect("Failed to create status update");
@@ -745,3 +745,51 @@ async fn test_regular_tx_dependency_chain_resolution() {
     
     logging::log("TEST", "=== test_regular_tx_dependency_chain_resolution completed ===\n");
 }
+
+/// Tests listing all blocked transactions at once.
+/// 
+/// Test flow:
+/// 1. Creates a CAT that locks key "1"
+/// 2. Creates a regular transaction from key "1" to key "2", blocked by the CAT
+/// 3. Creates a regular transaction on key "3", which is not blocked
+/// 4. Verifies only the blocked transaction is listed, with the CAT and its keys
+/// 5. Resolves the CAT and verifies nothing is blocked anymore
+#[tokio::test]
+async fn test_get_all_blocked_transactions() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_get_all_blocked_transactions ===");
+
+    let (hig_node, _receiver_hig_to_hs) = setup_test_hig_node(true).await;
+    assert!(hig_node.get_all_blocked_transactions().await.unwrap().is_empty());
+
+    let new_tx = |name: &str, data: &str, constituent_chains: Vec<ChainId>| {
+        let cl_id = CLTransactionId(name.to_string());
+        Transaction::new(
+            TransactionId(format!("{}:tx", cl_id.0)),
+            NetworkSpec::test_chain(1),
+            constituent_chains,
+            data.to_string(),
+            cl_id,
+        ).expect("Failed to create transaction")
+    };
+    let cat_tx = new_tx("cl-tx_cat", "CAT.credit 1 100", vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)]);
+    let blocked_tx = new_tx("cl-tx_blocked", "REGULAR.send 1 2 50", vec![NetworkSpec::test_chain(1)]);
+    let free_tx = new_tx("cl-tx_free", "REGULAR.credit 3 10", vec![NetworkSpec::test_chain(1)]);
+    for tx in [cat_tx.clone(), blocked_tx.clone(), free_tx] {
+        hig_node.lock().await.process_transaction(tx).await.unwrap();
+    }
+
+    let blocked = hig_node.get_all_blocked_transactions().await.unwrap();
+    assert_eq!(blocked.len(), 1, "Only the send should be blocked");
+    let waits_on = blocked.get(&blocked_tx.id).expect("The send should be blocked");
+    assert_eq!(waits_on.blocking_txs, vec![cat_tx.id.clone()]);
+    assert_eq!(waits_on.keys, vec!["1".to_string(), "2".to_string()]);
+
+    // Resolve the CAT, which unblocks the send
+    let status_update = new_tx("cl-tx_cat.UPDATE", "STATUS_UPDATE:Success.CAT_ID:cl-tx_cat", vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)]);
+    hig_node.lock().await.process_transaction(status_update).await.unwrap();
+    assert!(hig_node.get_all_blocked_transactions().await.unwrap().is_empty());
+    assert_eq!(hig_node.get_transaction_status(blocked_tx.id).await.unwrap(), TransactionStatus::Success);
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
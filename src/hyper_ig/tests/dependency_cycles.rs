@@ -0,0 +1,182 @@
+use crate::hyper_ig::node::{find_dependency_path, HyperIGNode};
+use crate::types::{Transaction, TransactionId, TransactionStatus, SubBlock, CLTransactionId, ChainId};
+use crate::types::NetworkSpec;
+use crate::utils::logging;
+use crate::hyper_ig::{HyperIG, DependencyCyclePolicy, DuplicateTransactionPolicy};
+use crate::hyper_ig::tests::basic::setup_test_hig_node;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Helper function: builds a dependency map from (transaction, dependency) edges
+fn dependency_map(edges: &[(&str, &str)]) -> HashMap<TransactionId, HashSet<TransactionId>> {
+    let mut depends_on: HashMap<TransactionId, HashSet<TransactionId>> = HashMap::new();
+    for (tx, dependency) in edges {
+        depends_on.entry(TransactionId(tx.to_string())).or_default().insert(TransactionId(dependency.to_string()));
+    }
+    depends_on
+}
+
+/// Tests the search for dependency chains that cycle detection is based on.
+#[tokio::test]
+async fn test_find_dependency_path() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_find_dependency_path ===");
+
+    let id = |name: &str| TransactionId(name.to_string());
+    let depends_on = dependency_map(&[("c", "b"), ("b", "a"), ("d", "a"), ("a", "root")]);
+
+    assert_eq!(find_dependency_path(&depends_on, &id("c"), &id("root")), Some(vec![id("c"), id("b"), id("a"), id("root")]));
+    assert_eq!(find_dependency_path(&depends_on, &id("d"), &id("a")), Some(vec![id("d"), id("a")]));
+    assert_eq!(find_dependency_path(&depends_on, &id("a"), &id("c")), None, "Dependencies are directed");
+    assert_eq!(find_dependency_path(&depends_on, &id("d"), &id("b")), None);
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
+
+/// Tests that an ordinary dependency chain is not reported as a cycle.
+/// 
+/// Test flow:
+/// 1. Processes a pending CAT and two regular transactions that wait on it one after the other
+/// 2. Verifies that no cycle is detected and the regular transactions stay pending
+#[tokio::test]
+async fn test_dependency_chain_is_not_a_cycle() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_dependency_chain_is_not_a_cycle ===");
+
+    let (hig_node, _rx) = setup_test_hig_node(true).await;
+    assert_eq!(hig_node.lock().await.get_dependency_cycle_policy().await, DependencyCyclePolicy::FailNewest);
+
+    let tx = |name: &str, constituent_chains: Vec<ChainId>, data: &str| {
+        let cl_id = CLTransactionId(name.to_string());
+        Transaction::new(TransactionId(format!("{}:tx", cl_id.0)), NetworkSpec::test_chain(1), constituent_chains, data.to_string(), cl_id)
+            .expect("Failed to create transaction")
+    };
+    let transactions = vec![
+        tx("cl-tx_cat", vec![NetworkSpec::test_chain(1), ChainId("chain-2".to_string())], "CAT.credit 1 100"),
+        tx("cl-tx_send_1", vec![NetworkSpec::test_chain(1)], "REGULAR.send 1 2 50"),
+        tx("cl-tx_send_2", vec![NetworkSpec::test_chain(1)], "REGULAR.send 2 3 20"),
+    ];
+    hig_node.lock().await.process_subblock(SubBlock {
+        chain_id: NetworkSpec::test_chain(1),
+        block_height: 1,
+        transactions: transactions.clone(),
+    }).await.unwrap();
+
+    assert_eq!(hig_node.lock().await.get_dependency_cycle_count().await, 0);
+    assert!(hig_node.lock().await.get_dependency_cycles().await.is_empty());
+    let pending = hig_node.lock().await.get_pending_transactions().await.unwrap();
+    for tx in &transactions {
+        assert!(pending.contains(&tx.id), "tx-id='{}' should still be pending", tx.id.0);
+    }
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
+
+/// Helper function: creates a transaction on chain 1
+fn chain_1_tx(name: &str, constituent_chains: Vec<ChainId>, data: &str) -> Transaction {
+    let cl_id = CLTransactionId(name.to_string());
+    Transaction::new(TransactionId(format!("{}:tx", cl_id.0)), NetworkSpec::test_chain(1), constituent_chains, data.to_string(), cl_id)
+        .expect("Failed to create transaction")
+}
+
+/// Helper function: runs the subblocks that close a dependency cycle under the given policy
+/// 
+/// 1. A CAT locks account 1; `send 1 2` waits on it and locks accounts 1 and 2; `send 2 3` waits on `send 1 2`
+/// 2. The CAT succeeds, `send 1 2` is reprocessed and fails on the balance, while `send 2 3` still waits on it
+/// 3. `send 1 2` is delivered again and processed again (`ProcessAgain`), so it would wait on `send 2 3`
+/// 
+/// # Returns
+/// The HIG node, the regular transaction that closed the cycle and the one it would have waited on
+async fn run_dependency_cycle(policy: DependencyCyclePolicy) -> (Arc<Mutex<HyperIGNode>>, Transaction, Transaction) {
+    let (hig_node, _rx) = setup_test_hig_node(true).await;
+    hig_node.lock().await.set_dependency_cycle_policy(policy).await;
+    hig_node.lock().await.set_duplicate_transaction_policy(DuplicateTransactionPolicy::ProcessAgain).await;
+
+    let cat = chain_1_tx("cl-tx_cat", vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)], "CAT.credit 1 100");
+    let send_1_2 = chain_1_tx("cl-tx_send_1_2", vec![NetworkSpec::test_chain(1)], "REGULAR.send 1 2 500");
+    let send_2_3 = chain_1_tx("cl-tx_send_2_3", vec![NetworkSpec::test_chain(1)], "REGULAR.send 2 3 10");
+    hig_node.lock().await.process_subblock(SubBlock {
+        chain_id: NetworkSpec::test_chain(1),
+        block_height: 1,
+        transactions: vec![cat.clone(), send_1_2.clone(), send_2_3.clone()],
+    }).await.unwrap();
+
+    let status_update = Transaction::new(
+        TransactionId("cl-tx_cat.UPDATE:tx".to_string()),
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)],
+        "STATUS_UPDATE:Success.CAT_ID:cl-tx_cat".to_string(),
+        CLTransactionId("cl-tx_cat.UPDATE".to_string()),
+    ).expect("Failed to create status update");
+    hig_node.lock().await.process_subblock(SubBlock {
+        chain_id: NetworkSpec::test_chain(1),
+        block_height: 2,
+        transactions: vec![status_update],
+    }).await.unwrap();
+    assert_eq!(hig_node.lock().await.get_transaction_status(send_1_2.id.clone()).await.unwrap(), TransactionStatus::Failure);
+    assert_eq!(hig_node.lock().await.get_transaction_status(send_2_3.id.clone()).await.unwrap(), TransactionStatus::Pending);
+    assert_eq!(hig_node.lock().await.get_dependency_cycle_count().await, 0);
+
+    hig_node.lock().await.process_subblock(SubBlock {
+        chain_id: NetworkSpec::test_chain(1),
+        block_height: 3,
+        transactions: vec![send_1_2.clone()],
+    }).await.unwrap();
+
+    assert_eq!(hig_node.lock().await.get_dependency_cycle_count().await, 1);
+    assert_eq!(hig_node.lock().await.get_dependency_cycles().await, vec![vec![send_1_2.id.clone(), send_2_3.id.clone()]]);
+    (hig_node, send_1_2, send_2_3)
+}
+
+/// Tests that the FailNewest policy fails only the transaction whose dependency closes a cycle.
+/// 
+/// Test flow:
+/// 1. Closes a dependency cycle between two regular transactions through subblocks (see `run_dependency_cycle`)
+/// 2. Verifies the cycle was counted and recorded, starting with the transaction that closed it
+/// 3. Verifies that transaction failed and the one it would have waited on is left pending
+#[tokio::test]
+async fn test_dependency_cycle_fail_newest() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_dependency_cycle_fail_newest ===");
+
+    let (hig_node, newest, other) = run_dependency_cycle(DependencyCyclePolicy::FailNewest).await;
+
+    assert_eq!(hig_node.lock().await.get_transaction_status(newest.id.clone()).await.unwrap(), TransactionStatus::Failure);
+    assert_eq!(hig_node.lock().await.get_transaction_status(other.id.clone()).await.unwrap(), TransactionStatus::Pending);
+    let pending = hig_node.lock().await.get_pending_transactions().await.unwrap();
+    assert!(!pending.contains(&newest.id), "The failed transaction should not be pending");
+    assert!(pending.contains(&other.id), "FailNewest should not fail the other transaction on the cycle");
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
+
+/// Tests that the FailBoth policy also fails the transaction the newest one would have waited on.
+/// 
+/// Test flow:
+/// 1. Closes a dependency cycle between two regular transactions through subblocks (see `run_dependency_cycle`)
+/// 2. Verifies the cycle was counted and recorded
+/// 3. Verifies both transactions on the cycle failed and none is left pending
+/// 4. Verifies a later transaction on the keys of the cycle is no longer blocked
+#[tokio::test]
+async fn test_dependency_cycle_fail_both() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_dependency_cycle_fail_both ===");
+
+    let (hig_node, newest, other) = run_dependency_cycle(DependencyCyclePolicy::FailBoth).await;
+
+    assert_eq!(hig_node.lock().await.get_transaction_status(newest.id.clone()).await.unwrap(), TransactionStatus::Failure);
+    assert_eq!(hig_node.lock().await.get_transaction_status(other.id.clone()).await.unwrap(), TransactionStatus::Failure);
+    assert!(hig_node.lock().await.get_pending_transactions().await.unwrap().is_empty());
+
+    let credit = chain_1_tx("cl-tx_credit", vec![NetworkSpec::test_chain(1)], "REGULAR.credit 2 10");
+    hig_node.lock().await.process_subblock(SubBlock {
+        chain_id: NetworkSpec::test_chain(1),
+        block_height: 4,
+        transactions: vec![credit.clone()],
+    }).await.unwrap();
+    assert_eq!(hig_node.lock().await.get_transaction_status(credit.id.clone()).await.unwrap(), TransactionStatus::Success);
+    assert_eq!(hig_node.lock().await.get_dependency_cycle_count().await, 1);
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
@@ -0,0 +1,90 @@
+use crate::types::{Transaction, TransactionId, SubBlock, CLTransactionId};
+use crate::types::NetworkSpec;
+use crate::utils::logging;
+use crate::hyper_ig::{HyperIG, HyperIGError, DuplicateTransactionPolicy};
+use crate::hyper_ig::tests::basic::setup_test_hig_node;
+
+/// Helper function: creates a regular credit transaction on chain 1
+fn credit_tx(name: &str) -> Transaction {
+    let cl_id = CLTransactionId(name.to_string());
+    Transaction::new(
+        TransactionId(format!("{}:tx", cl_id.0)),
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1)],
+        "REGULAR.credit 1 100".to_string(),
+        cl_id,
+    ).expect("Failed to create regular transaction")
+}
+
+/// Helper function: creates a subblock on chain 1
+fn subblock(block_height: u64, transactions: Vec<Transaction>) -> SubBlock {
+    SubBlock {
+        chain_id: NetworkSpec::test_chain(1),
+        block_height,
+        transactions,
+    }
+}
+
+/// Tests that duplicates are skipped and counted by default.
+/// 
+/// Test flow:
+/// 1. Processes a subblock containing the same transaction twice
+/// 2. Processes a later subblock containing the transaction again
+/// 3. Verifies the transaction was executed once and both duplicates were counted
+#[tokio::test]
+async fn test_duplicate_transactions_skipped() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_duplicate_transactions_skipped ===");
+
+    let (hig_node, _rx) = setup_test_hig_node(true).await;
+    assert_eq!(hig_node.lock().await.get_duplicate_transaction_policy().await, DuplicateTransactionPolicy::Skip);
+
+    let tx = credit_tx("cl-tx_dup");
+    hig_node.lock().await.process_subblock(subblock(1, vec![tx.clone(), tx.clone()])).await.unwrap();
+    hig_node.lock().await.process_subblock(subblock(2, vec![tx])).await.unwrap();
+
+    let balance = hig_node.lock().await.get_account_balance(NetworkSpec::test_chain(1), 1, false).await.unwrap();
+    assert_eq!(balance.balance, 100, "The duplicates should not be executed");
+    assert_eq!(hig_node.lock().await.get_duplicate_transaction_count().await, 2);
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
+
+/// Tests that the Error policy aborts the subblock at the duplicate.
+#[tokio::test]
+async fn test_duplicate_transactions_error() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_duplicate_transactions_error ===");
+
+    let (hig_node, _rx) = setup_test_hig_node(true).await;
+    hig_node.lock().await.set_duplicate_transaction_policy(DuplicateTransactionPolicy::Error).await;
+
+    let tx = credit_tx("cl-tx_dup");
+    hig_node.lock().await.process_subblock(subblock(1, vec![tx.clone()])).await.unwrap();
+    let result = hig_node.lock().await.process_subblock(subblock(2, vec![tx.clone()])).await;
+
+    assert!(matches!(result, Err(HyperIGError::DuplicateTransaction(ref id)) if *id == tx.id), "Expected DuplicateTransaction, got {:?}", result);
+    assert_eq!(hig_node.lock().await.get_duplicate_transaction_count().await, 1);
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
+
+/// Tests that the ProcessAgain policy executes a duplicate regular transaction again.
+#[tokio::test]
+async fn test_duplicate_transactions_process_again() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_duplicate_transactions_process_again ===");
+
+    let (hig_node, _rx) = setup_test_hig_node(true).await;
+    hig_node.lock().await.set_duplicate_transaction_policy(DuplicateTransactionPolicy::ProcessAgain).await;
+
+    let tx = credit_tx("cl-tx_dup");
+    hig_node.lock().await.process_subblock(subblock(1, vec![tx.clone()])).await.unwrap();
+    hig_node.lock().await.process_subblock(subblock(2, vec![tx])).await.unwrap();
+
+    let balance = hig_node.lock().await.get_account_balance(NetworkSpec::test_chain(1), 1, false).await.unwrap();
+    assert_eq!(balance.balance, 200, "The duplicate should be executed again");
+    assert_eq!(hig_node.lock().await.get_duplicate_transaction_count().await, 1);
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
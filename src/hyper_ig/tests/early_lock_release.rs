@@ -0,0 +1,78 @@
+use crate::types::{Transaction, TransactionId, TransactionStatus, CLTransactionId};
+use crate::types::NetworkSpec;
+use crate::utils::logging;
+use crate::hyper_ig::HyperIG;
+use crate::hyper_ig::tests::basic::setup_test_hig_node;
+
+/// Helper function: creates a transaction on chain 1
+fn chain_1_tx(name: &str, data: &str, constituent_chains: Vec<crate::types::ChainId>) -> Transaction {
+    let cl_id = CLTransactionId(name.to_string());
+    Transaction::new(
+        TransactionId(format!("{}:tx", cl_id.0)),
+        NetworkSpec::test_chain(1),
+        constituent_chains,
+        data.to_string(),
+        cl_id,
+    ).expect("Failed to create transaction")
+}
+
+/// Processes a CAT that proposes Failure followed by a regular transaction on the same keys.
+/// 
+/// # Returns
+/// The keys still locked by the CAT, the status of the regular transaction and the number of early releases
+async fn run_failing_cat_then_dependent(release_locks: bool) -> (Vec<String>, TransactionStatus, u64) {
+    let (hig_node, _rx) = setup_test_hig_node(true).await;
+    hig_node.lock().await.set_release_locks_on_failure_proposal(release_locks).await;
+    assert_eq!(hig_node.lock().await.get_release_locks_on_failure_proposal().await, release_locks);
+
+    // Account 1 has no balance, so the CAT proposes Failure
+    let cat_tx = chain_1_tx("cl-tx_cat", "CAT.send 1 2 1000", vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)]);
+    hig_node.lock().await.process_transaction(cat_tx.clone()).await.unwrap();
+    assert_eq!(hig_node.lock().await.get_proposed_status(cat_tx.id.clone()).await.unwrap(), crate::types::CATStatus::Failure);
+
+    let dependent_tx = chain_1_tx("cl-tx_dependent", "REGULAR.credit 1 100", vec![NetworkSpec::test_chain(1)]);
+    let status = hig_node.lock().await.process_transaction(dependent_tx).await.unwrap();
+
+    let locked_keys = hig_node.lock().await.get_locked_keys_by_transaction(cat_tx.id).await;
+    let releases = hig_node.lock().await.get_early_lock_release_count().await;
+    (locked_keys, status, releases)
+}
+
+/// Tests that by default a CAT holds its locks after proposing Failure.
+/// 
+/// Test flow:
+/// 1. Processes a CAT that proposes Failure
+/// 2. Processes a regular transaction on the same key
+/// 3. Verifies the regular transaction is blocked by the CAT
+#[tokio::test]
+async fn test_failure_proposal_keeps_locks_by_default() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_failure_proposal_keeps_locks_by_default ===");
+
+    let (locked_keys, status, releases) = run_failing_cat_then_dependent(false).await;
+    assert!(!locked_keys.is_empty(), "The CAT should still hold its locks");
+    assert_eq!(status, TransactionStatus::Pending);
+    assert_eq!(releases, 0);
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
+
+/// Tests that a CAT releases its locks on a Failure proposal when enabled.
+/// 
+/// Test flow:
+/// 1. Enables the early lock release
+/// 2. Processes a CAT that proposes Failure
+/// 3. Processes a regular transaction on the same key
+/// 4. Verifies the regular transaction executes immediately
+#[tokio::test]
+async fn test_failure_proposal_releases_locks() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_failure_proposal_releases_locks ===");
+
+    let (locked_keys, status, releases) = run_failing_cat_then_dependent(true).await;
+    assert!(locked_keys.is_empty(), "The CAT should have released its locks");
+    assert_eq!(status, TransactionStatus::Success);
+    assert_eq!(releases, 1);
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
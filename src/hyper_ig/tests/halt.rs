@@ -0,0 +1,92 @@
+use crate::types::{NetworkSpec, CATStatusUpdate, CLTransaction, CLTransactionId, SubBlock, Transaction, TransactionId, TransactionStatus};
+use crate::utils::logging;
+use crate::hyper_ig::HyperIG;
+use crate::hyper_ig::node::HyperIGNode;
+use crate::confirmation_layer::{ConfirmationLayer, node::ConfirmationLayerNode};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{Mutex, mpsc};
+
+/// Tests that a halted node leaves its subblocks queued and processes them once resumed:
+/// - No subblock is processed while the node is halted
+/// - The queued subblock is processed after the node is resumed
+#[tokio::test]
+async fn test_halted_node_resumes_processing() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_halted_node_resumes_processing ===");
+
+    let (sender_cl_to_hig, receiver_cl_to_hig) = mpsc::channel::<SubBlock>(100);
+    let (sender_hig_to_hs, _receiver_hig_to_hs) = mpsc::channel::<CATStatusUpdate>(100);
+    let hig_node = Arc::new(Mutex::new(HyperIGNode::new(receiver_cl_to_hig, sender_hig_to_hs, NetworkSpec::test_chain(1), 10, true)));
+    HyperIGNode::start(hig_node.clone()).await;
+
+    // Halt the node and send it a subblock
+    hig_node.lock().await.set_halted(true).await;
+    assert!(hig_node.lock().await.is_halted().await);
+    sender_cl_to_hig.send(SubBlock { block_height: 1, chain_id: NetworkSpec::test_chain(1), transactions: vec![] }).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    assert_eq!(hig_node.get_current_block_height().await.unwrap(), 0, "A halted node should not process subblocks");
+
+    // Resume the node
+    hig_node.lock().await.set_halted(false).await;
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    assert_eq!(hig_node.get_current_block_height().await.unwrap(), 1, "A resumed node should process the queued subblock");
+
+    HyperIGNode::shutdown(hig_node).await;
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
+
+/// Tests that a crashed node loses its subblocks and catches up from the CL when it restarts:
+/// - The subblocks delivered while the node is crashed are lost
+/// - On restart, the missed subblocks are replayed from the CL, including a transaction submitted meanwhile
+/// - Live processing resumes afterwards without processing a replayed subblock twice
+#[tokio::test]
+async fn test_crashed_node_catches_up_on_restart() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_crashed_node_catches_up_on_restart ===");
+
+    let (_sender_hs_to_cl, receiver_hs_to_cl) = mpsc::channel::<CLTransaction>(100);
+    let cl_node = Arc::new(Mutex::new(ConfirmationLayerNode::new_with_block_interval(receiver_hs_to_cl, Duration::from_millis(20))
+        .expect("Failed to create CL node")));
+    let (sender_cl_to_hig, receiver_cl_to_hig) = mpsc::channel::<SubBlock>(100);
+    cl_node.lock().await.register_chain(NetworkSpec::test_chain(1), sender_cl_to_hig).await.expect("Failed to register chain-1");
+    let (sender_hig_to_hs, _receiver_hig_to_hs) = mpsc::channel::<CATStatusUpdate>(100);
+    let hig_node = Arc::new(Mutex::new(HyperIGNode::new(receiver_cl_to_hig, sender_hig_to_hs, NetworkSpec::test_chain(1), 10, true)));
+
+    // Crash the node before the CL starts, so it misses the first blocks
+    hig_node.lock().await.crash().await;
+    HyperIGNode::start(hig_node.clone()).await;
+    ConfirmationLayerNode::start(cl_node.clone()).await;
+
+    let cl_id = CLTransactionId("cl-tx".to_string());
+    let tx = Transaction::new(
+        TransactionId(format!("{:?}:credit-tx", cl_id)),
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1)],
+        "REGULAR.credit 1 100".to_string(),
+        cl_id.clone(),
+    ).expect("Failed to create transaction");
+    let cl_tx = CLTransaction::new(cl_id, vec![NetworkSpec::test_chain(1)], vec![tx.clone()]).expect("Failed to create CL transaction");
+    cl_node.lock().await.submit_transaction(cl_tx).await.expect("Failed to submit transaction");
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    assert_eq!(hig_node.get_current_block_height().await.unwrap(), 0, "A crashed node should not process subblocks");
+    assert!(hig_node.lock().await.get_lost_subblock_count().await > 0, "The subblocks delivered while crashed should be lost");
+
+    // Restart the node and check that it caught up
+    let replayed = HyperIGNode::restart(&hig_node, &cl_node).await.expect("Failed to restart");
+    assert!(replayed > 0, "The missed subblocks should be replayed");
+    assert!(!hig_node.lock().await.is_halted().await, "A restarted node should resume");
+    assert_eq!(hig_node.get_current_block_height().await.unwrap(), replayed, "The replay should cover every missed block");
+    assert_eq!(hig_node.lock().await.get_transaction_status(tx.id.clone()).await.unwrap(), TransactionStatus::Success,
+        "The transaction submitted while crashed should be processed");
+
+    // Live processing continues after the replayed blocks
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    let current_block = cl_node.lock().await.get_current_block().await.unwrap();
+    let hig_block = hig_node.get_current_block_height().await.unwrap();
+    assert!(hig_block > replayed && hig_block + 2 >= current_block, "The node should follow the CL again (HIG at {}, CL at {})", hig_block, current_block);
+
+    HyperIGNode::shutdown(hig_node).await;
+    ConfirmationLayerNode::shutdown(cl_node).await;
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
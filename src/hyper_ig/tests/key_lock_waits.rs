@@ -0,0 +1,66 @@
+use crate::types::{Transaction, TransactionId, CLTransactionId, ChainId};
+use crate::types::NetworkSpec;
+use crate::utils::logging;
+use crate::hyper_ig::HyperIG;
+use crate::hyper_ig::tests::basic::setup_test_hig_node;
+use std::time::Duration;
+
+/// Tests that the time transactions wait for a locked key is recorded per key.
+/// 
+/// Test flow:
+/// 1. Processes a CAT crediting key "1" and two transactions sending from key "1" (blocked by the CAT)
+/// 2. Verifies that no wait is reported while the transactions are still blocked
+/// 3. Resolves the CAT after a delay
+/// 4. Verifies key "1" is the hottest key with both waits recorded
+#[tokio::test]
+async fn test_key_lock_wait_recorded() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_key_lock_wait_recorded ===");
+
+    let (hig_node, _rx) = setup_test_hig_node(true).await;
+
+    let cat_cl_id = CLTransactionId("cl-tx_cat".to_string());
+    let cat_tx = Transaction::new(
+        TransactionId(format!("{}:tx", cat_cl_id.0)),
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1), ChainId("chain-2".to_string())],
+        "CAT.credit 1 100".to_string(),
+        cat_cl_id.clone(),
+    ).expect("Failed to create CAT transaction");
+    hig_node.lock().await.process_transaction(cat_tx).await.unwrap();
+
+    for (name, data) in [("cl-tx_send_1", "REGULAR.send 1 2 10"), ("cl-tx_send_2", "REGULAR.send 1 3 10")] {
+        let cl_id = CLTransactionId(name.to_string());
+        let tx = Transaction::new(
+            TransactionId(format!("{}:tx", cl_id.0)),
+            NetworkSpec::test_chain(1),
+            vec![NetworkSpec::test_chain(1)],
+            data.to_string(),
+            cl_id,
+        ).expect("Failed to create regular transaction");
+        hig_node.lock().await.process_transaction(tx).await.unwrap();
+    }
+    assert!(hig_node.lock().await.get_hottest_keys(10).await.unwrap().is_empty(), "Ongoing waits should not be reported");
+
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    let status_update = Transaction::new(
+        TransactionId(format!("{}:status", cat_cl_id.0)),
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1)],
+        format!("STATUS_UPDATE:Success.CAT_ID:{}", cat_cl_id.0),
+        cat_cl_id,
+    ).expect("Failed to create status update");
+    hig_node.lock().await.process_transaction(status_update).await.unwrap();
+
+    let hottest_keys = hig_node.lock().await.get_hottest_keys(10).await.unwrap();
+    logging::log("TEST", &format!("Hottest keys: {:?}", hottest_keys));
+    let key_1 = hottest_keys.first().expect("Key 1 should have a lock wait");
+    assert_eq!(key_1.key, "1");
+    assert!(key_1.blocked_count >= 1);
+    assert!(key_1.max_wait >= Duration::from_millis(50));
+    assert!(key_1.total_wait >= key_1.max_wait);
+    assert!(hottest_keys.windows(2).all(|pair| pair[0].total_wait >= pair[1].total_wait), "Keys should be sorted by total wait");
+    assert!(hig_node.lock().await.get_hottest_keys(0).await.unwrap().is_empty());
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
@@ -0,0 +1,94 @@
+use proptest::prelude::*;
+use crate::types::{Transaction, TransactionId, CLTransactionId, ChainId, SubBlock, NetworkSpec};
+use crate::types::communication::cl_to_hig::TransactionData;
+use crate::types::tests::strategies;
+use crate::hyper_ig::{HyperIG, HyperIGError};
+use crate::hyper_ig::tests::basic::setup_test_hig_node;
+use crate::utils::logging;
+
+/// Processes a transaction with the given data on a fresh chain-1 HIG and returns the result
+/// and whether the transaction was left in the pending set.
+async fn process_data(data: String) -> (Result<(), anyhow::Error>, bool) {
+    let (hig_node, _rx) = setup_test_hig_node(true).await;
+    let tx = Transaction {
+        id: TransactionId("cl-tx_fuzz:tx".to_string()),
+        chain_id: NetworkSpec::test_chain(1),
+        constituent_chains: vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)],
+        data,
+        cl_id: CLTransactionId("cl-tx_fuzz".to_string()),
+        expiry: None,
+    };
+    let result = hig_node.lock().await.process_transaction(tx.clone()).await.map(|_| ());
+    let pending = hig_node.lock().await.get_pending_transactions().await.unwrap().contains(&tx.id);
+    (result, pending)
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    /// Tests that the HIG handles arbitrary transaction data deterministically:
+    /// - Data that fails validation is rejected with InvalidTransactionData and never enters the pending set
+    /// - Valid data is never rejected as invalid
+    #[test]
+    fn test_hig_rejects_malformed_data(data in strategies::transaction_data()) {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let (result, pending) = runtime.block_on(process_data(data.clone()));
+        if data.starts_with("STATUS_UPDATE") {
+            // Status updates are checked against the HS-origin marker instead
+            prop_assert!(!pending);
+        } else if TransactionData::validate(&data).is_err() {
+            let error = result.expect_err("Malformed data should be rejected");
+            prop_assert!(
+                matches!(error.downcast_ref::<HyperIGError>(), Some(HyperIGError::InvalidTransactionData { .. })),
+                "Unexpected error for '{}': {}", data, error
+            );
+            prop_assert!(!pending, "Rejected transaction '{}' should not be pending", data);
+        } else if let Err(error) = result {
+            prop_assert!(
+                !matches!(error.downcast_ref::<HyperIGError>(), Some(HyperIGError::InvalidTransactionData { .. })),
+                "Valid data '{}' was rejected: {}", data, error
+            );
+        }
+    }
+}
+
+/// Helper function: creates a chain-1 transaction without validating its data or constituent chains
+fn unchecked_tx(name: &str, constituent_chains: Vec<ChainId>, data: &str) -> Transaction {
+    Transaction {
+        id: TransactionId(format!("{}:tx", name)),
+        chain_id: NetworkSpec::test_chain(1),
+        constituent_chains,
+        data: data.to_string(),
+        cl_id: CLTransactionId(name.to_string()),
+        expiry: None,
+    }
+}
+
+/// Tests that malformed transactions in a subblock are skipped and counted without affecting the
+/// other transactions of the subblock.
+#[tokio::test]
+async fn test_malformed_transactions_skipped_in_subblock() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_malformed_transactions_skipped_in_subblock ===");
+
+    let (hig_node, _rx) = setup_test_hig_node(true).await;
+    let subblock = SubBlock {
+        chain_id: NetworkSpec::test_chain(1),
+        block_height: 1,
+        transactions: vec![
+            unchecked_tx("cl-tx_prefix", vec![NetworkSpec::test_chain(1)], "FOO.credit 1 100"),
+            unchecked_tx("cl-tx_command", vec![NetworkSpec::test_chain(1)], "REGULAR.mint 1 100"),
+            unchecked_tx("cl-tx_single_chain_cat", vec![NetworkSpec::test_chain(1)], "CAT.credit 1 100"),
+            unchecked_tx("cl-tx_foreign", vec![NetworkSpec::test_chain(2)], "REGULAR.credit 1 100"),
+            unchecked_tx("cl-tx_valid", vec![NetworkSpec::test_chain(1)], "REGULAR.credit 1 100"),
+        ],
+    };
+    hig_node.lock().await.process_subblock(subblock).await.expect("Malformed transactions should not abort the subblock");
+
+    assert_eq!(hig_node.lock().await.get_malformed_transaction_count().await, 4);
+    assert!(hig_node.lock().await.get_pending_transactions().await.unwrap().is_empty(), "Malformed transactions should not be pending");
+    let balance = hig_node.lock().await.get_account_balance(NetworkSpec::test_chain(1), 1, false).await.unwrap();
+    assert_eq!(balance.balance, 100, "Only the valid transaction should be executed");
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
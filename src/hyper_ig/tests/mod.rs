@@ -5,3 +5,22 @@ mod dependencies;
 mod timeouts;
 mod preloaded_accounts;
 mod shutdown;
+mod status_update_origin;
+mod status_transitions;
+mod simulate;
+mod account_balance;
+mod state_root;
+mod subblock_timing;
+mod proposal_queue;
+mod duplicate_transactions;
+mod dependency_cycles;
+mod key_lock_waits;
+mod early_lock_release;
+mod status_update_priority;
+mod transaction_expiry;
+mod cat_commands;
+mod malformed_data;
+mod halt;
+mod transaction_timeline;
+mod cat_audit_trail;
+mod cat_id_collisions;
@@ -302,4 +302,56 @@ async fn test_simulation_with_preloaded_accounts() {
     
     logging::log("TEST", "✓ Simulation with preloaded accounts completed successfully");
     logging::log("TEST", "=== Test completed successfully ===\n");
-} 
\ No newline at end of file
+} 
+/// Tests that a HyperIG node can be created with explicit per-account balances.
+/// 
+/// Test flow:
+/// 1. Creates a HyperIG node with accounts 1 and 7 preloaded with different balances
+/// 2. Verifies the chain state contains exactly these accounts and balances
+#[tokio::test]
+async fn test_account_balances() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_account_balances ===");
+
+    let (_sender_cl_to_hig, receiver_cl_to_hig) = mpsc::channel(100);
+    let (sender_hig_to_hs, _receiver_hig_to_hs) = mpsc::channel(100);
+    let hig_node = HyperIGNode::new_with_account_balances(
+        receiver_cl_to_hig,
+        sender_hig_to_hs,
+        ChainId("test-chain".to_string()),
+        4,
+        true,
+        &[(1, 500), (7, 20)],
+    );
+
+    let state = hig_node.get_chain_state().await.unwrap();
+    assert_eq!(state.len(), 2, "Should have exactly 2 accounts");
+    assert_eq!(state.get("1").copied(), Some(500));
+    assert_eq!(state.get("7").copied(), Some(20));
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
+
+/// Tests that the balances of a running HyperIG node can be reloaded.
+/// 
+/// Test flow:
+/// 1. Creates a HyperIG node with 3 accounts preloaded with 100 tokens each
+/// 2. Sets the balance of account 2 and of the new account 9
+/// 3. Verifies the listed accounts have the new balances and the others are unchanged
+#[tokio::test]
+async fn test_preload_account_balances() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_preload_account_balances ===");
+
+    let hig_node = setup_test_hig_node_with_preloaded_accounts(3, 100).await;
+    hig_node.lock().await.preload_account_balances(&[(2, 500), (9, 7)]).await;
+
+    let state = hig_node.lock().await.get_chain_state().await.unwrap();
+    assert_eq!(state.len(), 4, "Should have the 3 preloaded accounts and account 9");
+    assert_eq!(state.get("1").copied(), Some(100), "Account 1 should keep its balance");
+    assert_eq!(state.get("2").copied(), Some(500), "Account 2 should have the new balance");
+    assert_eq!(state.get("3").copied(), Some(100), "Account 3 should keep its balance");
+    assert_eq!(state.get("9").copied(), Some(7), "Account 9 should have been created");
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
@@ -0,0 +1,224 @@
+use crate::types::{CATId, CATStatusLimited, CLTransactionId, ChainId};
+use crate::types::NetworkSpec;
+use crate::utils::logging;
+use crate::hyper_ig::{HyperIG, HyperIGError, ProposalQueueOverflowPolicy, ProposalRetryPolicy, DelayDistribution};
+use crate::hyper_ig::tests::basic::setup_test_hig_node;
+use crate::hyper_ig::node::HyperIGNode;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+/// Helper function: creates a HIG node without starting it, so that queued proposals stay in the queue
+fn setup_unstarted_hig_node() -> HyperIGNode {
+    let (_sender_cl_to_hig, receiver_cl_to_hig) = mpsc::channel(100);
+    let (sender_hig_to_hs, _receiver_hig_to_hs) = mpsc::channel(100);
+    HyperIGNode::new(receiver_cl_to_hig, sender_hig_to_hs, NetworkSpec::test_chain(1), 4, true)
+}
+
+/// Helper function: queues a Success proposal for the CAT with the given name
+async fn queue_proposal(hig_node: &mut HyperIGNode, name: &str) -> Result<(), HyperIGError> {
+    hig_node.send_cat_status_proposal(
+        CATId(CLTransactionId(name.to_string())),
+        CATStatusLimited::Success,
+        vec![NetworkSpec::test_chain(1), ChainId("chain-2".to_string())],
+    ).await
+}
+
+/// Tests that the proposal queue is unbounded by default and reports its depth and age.
+/// 
+/// Test flow:
+/// 1. Verifies that an empty queue has depth 0 and age 0
+/// 2. Queues several proposals
+/// 3. Verifies the depth, a non-zero age and that nothing was dropped
+#[tokio::test]
+async fn test_proposal_queue_gauges() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_proposal_queue_gauges ===");
+
+    let mut hig_node = setup_unstarted_hig_node();
+    assert_eq!(hig_node.get_proposal_queue_limit().await, (None, ProposalQueueOverflowPolicy::DropOldest));
+    assert_eq!(hig_node.get_proposal_queue_depth().await, 0);
+    assert_eq!(hig_node.get_proposal_queue_max_age().await, Duration::ZERO);
+
+    for i in 0..5 {
+        queue_proposal(&mut hig_node, &format!("cat-{}", i)).await.unwrap();
+    }
+    tokio::time::sleep(Duration::from_millis(10)).await;
+
+    assert_eq!(hig_node.get_proposal_queue_depth().await, 5);
+    assert!(hig_node.get_proposal_queue_max_age().await >= Duration::from_millis(10));
+    assert_eq!(hig_node.get_dropped_proposal_count().await, 0);
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
+
+/// Tests the overflow policies of a bounded proposal queue.
+/// 
+/// Test flow:
+/// 1. Limits the queue to 2 entries for each policy
+/// 2. Queues 3 proposals
+/// 3. Verifies the depth stays at the limit, the dropped count and the result of the third call
+#[tokio::test]
+async fn test_proposal_queue_overflow_policies() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_proposal_queue_overflow_policies ===");
+
+    for policy in [ProposalQueueOverflowPolicy::DropOldest, ProposalQueueOverflowPolicy::DropNewest, ProposalQueueOverflowPolicy::Reject] {
+        logging::log("TEST", &format!("Testing policy {:?}", policy));
+        let mut hig_node = setup_unstarted_hig_node();
+        hig_node.set_proposal_queue_limit(Some(2), policy).await;
+
+        queue_proposal(&mut hig_node, "cat-0").await.unwrap();
+        queue_proposal(&mut hig_node, "cat-1").await.unwrap();
+        let result = queue_proposal(&mut hig_node, "cat-2").await;
+
+        match policy {
+            ProposalQueueOverflowPolicy::Reject => assert!(
+                matches!(result, Err(HyperIGError::ProposalQueueFull(ref cat_id)) if cat_id.0.0 == "cat-2"),
+                "Expected ProposalQueueFull, got {:?}", result
+            ),
+            _ => assert!(result.is_ok(), "Expected the proposal to be accepted, got {:?}", result),
+        }
+        assert_eq!(hig_node.get_proposal_queue_depth().await, 2);
+        assert_eq!(hig_node.get_dropped_proposal_count().await, 1);
+    }
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
+
+/// Tests the exponential growth and the cap of the retry backoff.
+#[tokio::test]
+async fn test_proposal_retry_backoff() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_proposal_retry_backoff ===");
+
+    let policy = ProposalRetryPolicy {
+        initial_backoff: Duration::from_millis(100),
+        max_backoff: Duration::from_millis(500),
+        jitter: 0.5,
+        max_attempts: Some(3),
+    };
+    assert_eq!(policy.base_backoff(1), Duration::from_millis(100));
+    assert_eq!(policy.base_backoff(2), Duration::from_millis(200));
+    assert_eq!(policy.base_backoff(3), Duration::from_millis(400));
+    assert_eq!(policy.base_backoff(4), Duration::from_millis(500));
+    assert_eq!(policy.base_backoff(u32::MAX), Duration::from_millis(500));
+
+    // The jittered backoff stays within the configured deviation
+    for _ in 0..100 {
+        let backoff = policy.backoff(2);
+        assert!(backoff >= Duration::from_millis(100) && backoff <= Duration::from_millis(300), "Backoff out of range: {:?}", backoff);
+    }
+
+    assert!(!policy.exhausted(2));
+    assert!(policy.exhausted(3));
+    assert!(!ProposalRetryPolicy::default().exhausted(u32::MAX));
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
+
+/// Tests that a proposal that cannot be sent is moved to the dead-letter list after the maximum attempts.
+/// 
+/// Test flow:
+/// 1. Starts a HIG node whose HS receiver is dropped, so every send fails
+/// 2. Queues a proposal with a retry policy of 3 attempts and a short backoff
+/// 3. Verifies the proposal ends up in the dead-letter list and the queue is empty
+#[tokio::test]
+async fn test_proposal_dead_letter() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_proposal_dead_letter ===");
+
+    let (hig_node, receiver_hig_to_hs) = setup_test_hig_node(true).await;
+    drop(receiver_hig_to_hs);
+    hig_node.lock().await.set_proposal_retry_policy(ProposalRetryPolicy {
+        initial_backoff: Duration::from_millis(10),
+        max_backoff: Duration::from_millis(20),
+        jitter: 0.0,
+        max_attempts: Some(3),
+    });
+
+    queue_proposal(&mut *hig_node.lock().await, "cat-dead").await.unwrap();
+    tokio::time::sleep(Duration::from_millis(500)).await;
+
+    let dead_letters = hig_node.lock().await.get_dead_letter_proposals().await;
+    assert_eq!(dead_letters.len(), 1);
+    assert_eq!(dead_letters[0].cat_id, CATId(CLTransactionId("cat-dead".to_string())));
+    assert_eq!(dead_letters[0].attempts, 3);
+    assert_eq!(hig_node.lock().await.get_proposal_queue_depth().await, 0);
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
+
+/// Tests the delays drawn from the HS message delay distributions.
+/// 
+/// Test flow:
+/// 1. Verifies the degenerate distributions return the configured delay (or its tail)
+/// 2. Verifies the mean of the log-normal distribution is the configured delay
+/// 3. Verifies only the absolute parameters are scaled
+#[tokio::test]
+async fn test_delay_distribution_sampling() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_delay_distribution_sampling ===");
+
+    let delay = Duration::from_millis(200);
+    assert_eq!(DelayDistribution::Constant.sample(delay), delay);
+    assert_eq!(DelayDistribution::Normal { std_dev: 0.0 }.sample(delay), delay);
+    assert_eq!(DelayDistribution::Bimodal { tail_probability: 0.0, tail_factor: 5.0 }.sample(delay), delay);
+    assert_eq!(DelayDistribution::Bimodal { tail_probability: 1.0, tail_factor: 5.0 }.sample(delay), Duration::from_secs(1));
+    assert_eq!(DelayDistribution::LogNormal { sigma: 1.0 }.sample(Duration::ZERO), Duration::ZERO);
+
+    // Negative draws of a wide normal distribution are truncated at zero
+    for _ in 0..100 {
+        assert!(DelayDistribution::Normal { std_dev: 1.0 }.sample(delay) >= Duration::ZERO);
+    }
+
+    let samples = 10_000;
+    let total: f64 = (0..samples).map(|_| DelayDistribution::LogNormal { sigma: 0.5 }.sample(delay).as_secs_f64()).sum();
+    let mean = total / samples as f64;
+    assert!((mean - 0.2).abs() < 0.01, "Mean of the log-normal delays should be close to 0.2s, got {}", mean);
+
+    assert_eq!(DelayDistribution::Normal { std_dev: 1.5 }.scaled(2.0), DelayDistribution::Normal { std_dev: 3.0 });
+    assert_eq!(DelayDistribution::LogNormal { sigma: 0.5 }.scaled(2.0), DelayDistribution::LogNormal { sigma: 0.5 });
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
+
+/// Tests that the realized delay of every sent proposal is recorded.
+/// 
+/// Test flow:
+/// 1. Starts a HIG node with a 50ms delay and a bimodal distribution whose tail always applies
+/// 2. Queues two proposals
+/// 3. Verifies both were sent after at least the tail delay and their delays were recorded
+/// 4. Verifies the delays are also recorded by CAT
+#[tokio::test]
+async fn test_realized_hs_message_delays() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_realized_hs_message_delays ===");
+
+    let (hig_node, mut receiver_hig_to_hs) = setup_test_hig_node(true).await;
+    {
+        let mut node = hig_node.lock().await;
+        node.set_hs_message_delay(Duration::from_millis(50));
+        node.set_hs_message_delay_distribution(DelayDistribution::Bimodal { tail_probability: 1.0, tail_factor: 2.0 });
+    }
+
+    queue_proposal(&mut *hig_node.lock().await, "cat-0").await.unwrap();
+    queue_proposal(&mut *hig_node.lock().await, "cat-1").await.unwrap();
+    for _ in 0..2 {
+        tokio::time::timeout(Duration::from_secs(1), receiver_hig_to_hs.recv()).await
+            .expect("Proposal should be sent within 1s")
+            .expect("Channel should be open");
+    }
+
+    let delays = hig_node.lock().await.get_realized_hs_message_delays().await;
+    assert_eq!(delays.len(), 2);
+    assert!(delays.iter().all(|delay| *delay >= Duration::from_millis(100)), "Delays should include the tail: {:?}", delays);
+
+    let delays_by_cat = hig_node.lock().await.get_realized_hs_message_delays_by_cat().await;
+    assert_eq!(delays_by_cat.len(), 2);
+    for name in ["cat-0", "cat-1"] {
+        let delay = delays_by_cat.get(&CATId(CLTransactionId(name.to_string()))).expect("Delay should be recorded for each CAT");
+        assert!(delays.contains(delay), "Delay of {} should be one of the realized delays", name);
+    }
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
@@ -2,7 +2,7 @@ use std::sync::Arc;
 use tokio::sync::Mutex;
 use tokio::sync::mpsc;
 use hyperplane::hyper_ig::node::HyperIGNode;
-use hyperplane::types::{constants, cat::CATStatusUpdate, SubBlock};
+use hyperplane::types::{NetworkSpec, cat::CATStatusUpdate, SubBlock};
 use hyperplane::utils::logging;
 
 /// Tests that the HIG node shutdown functionality works correctly:
@@ -23,7 +23,7 @@ async fn test_hig_node_shutdown() {
     let node = Arc::new(Mutex::new(HyperIGNode::new(
         receiver_cl_to_hig,
         sender_hig_to_hs,
-        constants::chain_1(),
+        NetworkSpec::test_chain(1),
         10, // cat_lifetime
         true, // allow_cat_pending_dependencies
     )));
@@ -58,7 +58,7 @@ async fn test_hig_node_shutdown_multiple_times() {
     let node = Arc::new(Mutex::new(HyperIGNode::new(
         receiver_cl_to_hig,
         sender_hig_to_hs,
-        constants::chain_1(),
+        NetworkSpec::test_chain(1),
         10, // cat_lifetime
         true, // allow_cat_pending_dependencies
     )));
@@ -96,7 +96,7 @@ async fn test_hig_node_restart_after_shutdown() {
     let node = Arc::new(Mutex::new(HyperIGNode::new(
         receiver_cl_to_hig,
         sender_hig_to_hs,
-        constants::chain_1(),
+        NetworkSpec::test_chain(1),
         10, // cat_lifetime
         true, // allow_cat_pending_dependencies
     )));
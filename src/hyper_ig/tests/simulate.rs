@@ -0,0 +1,112 @@
+use crate::types::{Transaction, TransactionId, TransactionStatus, ChainId, CLTransactionId};
+use crate::types::NetworkSpec;
+use crate::utils::logging;
+use crate::hyper_ig::HyperIG;
+use crate::hyper_ig::tests::basic::setup_test_hig_node;
+
+/// Helper function: Creates a transaction on chain-1 with the given data
+fn create_transaction(cl_id: &str, data: &str) -> Transaction {
+    let cl_id = CLTransactionId(cl_id.to_string());
+    let constituent_chains = if data.starts_with("CAT") {
+        vec![NetworkSpec::test_chain(1), ChainId("chain-2".to_string())]
+    } else {
+        vec![NetworkSpec::test_chain(1)]
+    };
+    Transaction::new(
+        TransactionId(format!("{}:tx", cl_id.0)),
+        NetworkSpec::test_chain(1),
+        constituent_chains,
+        data.to_string(),
+        cl_id,
+    ).expect("Failed to create transaction")
+}
+
+/// Tests that simulating a regular transaction reports its outcome without changing any state.
+/// 
+/// Test flow:
+/// 1. Simulates a credit (would succeed) and a send from an empty account (would fail)
+/// 2. Verifies keys, execution result and expected status of both
+/// 3. Verifies that neither transaction was recorded and the chain state is unchanged
+#[tokio::test]
+async fn test_simulate_regular_transaction_does_not_change_state() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_simulate_regular_transaction_does_not_change_state ===");
+
+    let (hig_node, _rx) = setup_test_hig_node(true).await;
+
+    let credit_tx = create_transaction("cl-tx_credit", "REGULAR.credit 1 100");
+    let preview = hig_node.lock().await.simulate_transaction(credit_tx.clone()).await.unwrap();
+    assert_eq!(preview.keys, vec!["1".to_string()]);
+    assert_eq!(preview.blocked_by, None);
+    assert!(preview.would_succeed);
+    assert_eq!(preview.expected_status, TransactionStatus::Success);
+
+    // The credit was not applied, so a send from account 1 would fail
+    let send_tx = create_transaction("cl-tx_send", "REGULAR.send 1 2 50");
+    let preview = hig_node.lock().await.simulate_transaction(send_tx.clone()).await.unwrap();
+    assert_eq!(preview.keys, vec!["1".to_string(), "2".to_string()]);
+    assert!(!preview.would_succeed);
+    assert_eq!(preview.expected_status, TransactionStatus::Failure);
+
+    // Nothing was recorded
+    assert!(hig_node.lock().await.get_transaction_status(credit_tx.id.clone()).await.is_err());
+    assert!(hig_node.lock().await.get_transaction_status(send_tx.id.clone()).await.is_err());
+    assert!(hig_node.lock().await.get_pending_transactions().await.unwrap().is_empty());
+    assert!(hig_node.lock().await.get_chain_state().await.unwrap().is_empty());
+    assert_eq!(hig_node.lock().await.get_total_locked_keys_count().await, 0);
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
+
+/// Tests that simulating transactions on keys locked by a pending CAT reports the blocking CAT.
+/// 
+/// Test flow:
+/// 1. Processes a CAT that credits key "1" and stays pending
+/// 2. Simulates a regular send from key "1" and verifies it would be blocked (Pending)
+/// 3. Simulates a CAT send from key "1" on a node that disallows pending dependencies (Rejected)
+/// 4. Verifies that the simulations added no pending transactions or dependencies
+#[tokio::test]
+async fn test_simulate_reports_blocking_transaction() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_simulate_reports_blocking_transaction ===");
+
+    for allow_cat_pending_dependencies in [true, false] {
+        let (hig_node, _rx) = setup_test_hig_node(allow_cat_pending_dependencies).await;
+
+        let cat_tx = create_transaction("cl-tx_cat", "CAT.credit 1 100");
+        let status = hig_node.lock().await.process_transaction(cat_tx.clone()).await.unwrap();
+        assert_eq!(status, TransactionStatus::Pending);
+
+        let send_tx = create_transaction("cl-tx_send", "REGULAR.send 1 2 50");
+        let preview = hig_node.lock().await.simulate_transaction(send_tx.clone()).await.unwrap();
+        assert_eq!(preview.blocked_by, Some(cat_tx.id.clone()));
+        assert_eq!(preview.expected_status, TransactionStatus::Pending);
+
+        let cat_send_tx = create_transaction("cl-tx_cat_send", "CAT.send 1 2 50");
+        let preview = hig_node.lock().await.simulate_transaction(cat_send_tx.clone()).await.unwrap();
+        assert_eq!(preview.blocked_by, Some(cat_tx.id.clone()));
+        let expected_status = if allow_cat_pending_dependencies { TransactionStatus::Pending } else { TransactionStatus::Rejected };
+        assert_eq!(preview.expected_status, expected_status);
+
+        // Only the processed CAT is pending, and the simulated transactions have no dependencies
+        assert_eq!(hig_node.lock().await.get_pending_transactions().await.unwrap(), vec![cat_tx.id.clone()]);
+        assert!(hig_node.lock().await.get_transaction_dependencies(send_tx.id.clone()).await.unwrap_or_default().is_empty());
+        assert!(hig_node.lock().await.get_transaction_dependencies(cat_send_tx.id.clone()).await.unwrap_or_default().is_empty());
+    }
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
+
+/// Tests that status updates cannot be simulated.
+#[tokio::test]
+async fn test_simulate_rejects_status_update() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_simulate_rejects_status_update ===");
+
+    let (hig_node, _rx) = setup_test_hig_node(true).await;
+
+    let status_update = create_transaction("cl-tx_update", "STATUS_UPDATE:Success.CAT_ID:cl-tx_cat");
+    assert!(hig_node.lock().await.simulate_transaction(status_update).await.is_err());
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
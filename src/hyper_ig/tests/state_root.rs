@@ -0,0 +1,63 @@
+use crate::types::{Transaction, TransactionId, SubBlock, CLTransactionId};
+use crate::types::NetworkSpec;
+use crate::utils::logging;
+use crate::hyper_ig::{HyperIG, HyperIGError};
+use crate::hyper_ig::tests::basic::setup_test_hig_node;
+
+/// Helper function: Creates a subblock for chain-1 with one regular transaction
+fn create_subblock(block_height: u64, data: &str) -> SubBlock {
+    let cl_id = CLTransactionId(format!("cl-tx_{}", block_height));
+    let tx = Transaction::new(
+        TransactionId(format!("{}:tx", cl_id.0)),
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1)],
+        data.to_string(),
+        cl_id,
+    ).expect("Failed to create transaction");
+    SubBlock {
+        chain_id: NetworkSpec::test_chain(1),
+        block_height,
+        transactions: vec![tx],
+    }
+}
+
+/// Tests that the HIG records a state root per processed subblock.
+/// 
+/// Test flow:
+/// 1. Processes two subblocks that change the state on two HIG nodes
+/// 2. Verifies that the roots change with the state and match between the identical nodes
+/// 3. Verifies that the latest root matches the root returned with account balances
+/// 4. Verifies that heights without a processed subblock have no root
+#[tokio::test]
+async fn test_state_root_per_block() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_state_root_per_block ===");
+
+    let (hig_node_a, _rx_a) = setup_test_hig_node(true).await;
+    let (hig_node_b, _rx_b) = setup_test_hig_node(true).await;
+
+    for hig_node in [&hig_node_a, &hig_node_b] {
+        hig_node.lock().await.process_subblock(create_subblock(1, "REGULAR.credit 1 100")).await.unwrap();
+        hig_node.lock().await.process_subblock(create_subblock(2, "REGULAR.send 1 2 40")).await.unwrap();
+    }
+
+    let root_1 = hig_node_a.lock().await.get_state_root(1).await.unwrap();
+    let root_2 = hig_node_a.lock().await.get_state_root(2).await.unwrap();
+    assert_ne!(root_1, root_2, "State root should change with the state");
+
+    // Identical inputs produce identical roots
+    assert_eq!(hig_node_b.lock().await.get_state_root(1).await.unwrap(), root_1);
+    assert_eq!(hig_node_b.lock().await.get_state_root(2).await.unwrap(), root_2);
+
+    // The latest root is the root the account proofs are issued against
+    let balance = hig_node_a.lock().await.get_account_balance(NetworkSpec::test_chain(1), 2, true).await.unwrap();
+    assert_eq!(balance.balance, 40);
+    assert_eq!(balance.state_root, root_2);
+    assert!(balance.proof.unwrap().verify(&root_2));
+
+    // No subblock was processed at height 3
+    let result = hig_node_a.lock().await.get_state_root(3).await;
+    assert!(matches!(result, Err(HyperIGError::StateRootNotFound(3))));
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
@@ -0,0 +1,118 @@
+use crate::types::{Transaction, TransactionId, SubBlock, TransactionStatus, CLTransactionId, NetworkSpec};
+use crate::utils::logging;
+use crate::hyper_ig::tests::basic::setup_test_hig_node;
+use crate::hyper_ig::HyperIG;
+
+/// Tests that status transitions are reported per block.
+/// 
+/// Test flow:
+/// 1. Block 1 contains a regular transaction (succeeds immediately) and a CAT (stays pending)
+/// 2. Block 2 contains the HS status update resolving the CAT
+/// 3. Block 3 is empty
+#[tokio::test]
+async fn test_status_transitions_per_block() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_status_transitions_per_block ===");
+
+    let (mut hig_node, _receiver_hig_to_hs) = setup_test_hig_node(true).await;
+
+    let regular_cl_id = CLTransactionId("cl-regular".to_string());
+    let regular_tx = Transaction::new(
+        TransactionId(format!("{}:tx", regular_cl_id.0)),
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1)],
+        "REGULAR.credit 2 50".to_string(),
+        regular_cl_id,
+    ).expect("Failed to create regular transaction");
+
+    let cat_cl_id = CLTransactionId("cl-cat".to_string());
+    let cat_tx = Transaction::new(
+        TransactionId(format!("{}:tx", cat_cl_id.0)),
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)],
+        "CAT.credit 1 100".to_string(),
+        cat_cl_id.clone(),
+    ).expect("Failed to create CAT transaction");
+
+    hig_node.process_subblock(SubBlock {
+        block_height: 1,
+        chain_id: NetworkSpec::test_chain(1),
+        transactions: vec![regular_tx.clone(), cat_tx.clone()],
+    }).await.unwrap();
+
+    // Block 1: the regular transaction finalized, the CAT entered Pending
+    let transitions = hig_node.get_status_transitions(1).await.unwrap();
+    logging::log("TEST", &format!("Transitions at block 1: {:?}", transitions));
+    assert_eq!(transitions.len(), 2, "Two transactions should change status in block 1");
+    assert_eq!(transitions.get(&regular_tx.id), Some(&TransactionStatus::Success));
+    assert_eq!(transitions.get(&cat_tx.id), Some(&TransactionStatus::Pending));
+
+    // Block 2: the HS resolves the CAT
+    let update_cl_id = CLTransactionId(format!("{}.UPDATE", cat_cl_id.0));
+    let status_update = Transaction::new(
+        TransactionId(format!("{}.{}", update_cl_id.0, NetworkSpec::test_chain(1).0)),
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)],
+        format!("STATUS_UPDATE:Success.CAT_ID:{}", cat_cl_id.0),
+        update_cl_id,
+    ).expect("Failed to create status update");
+    hig_node.process_subblock(SubBlock {
+        block_height: 2,
+        chain_id: NetworkSpec::test_chain(1),
+        transactions: vec![status_update],
+    }).await.unwrap();
+
+    let transitions = hig_node.get_status_transitions(2).await.unwrap();
+    logging::log("TEST", &format!("Transitions at block 2: {:?}", transitions));
+    assert_eq!(transitions.len(), 1, "Only the CAT should change status in block 2");
+    assert_eq!(transitions.get(&cat_tx.id), Some(&TransactionStatus::Success));
+
+    // Block 3: nothing changes
+    hig_node.process_subblock(SubBlock {
+        block_height: 3,
+        chain_id: NetworkSpec::test_chain(1),
+        transactions: vec![],
+    }).await.unwrap();
+    let transitions = hig_node.get_status_transitions(3).await.unwrap();
+    assert!(transitions.is_empty(), "No transaction should change status in block 3");
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
+
+/// Tests that a CAT timeout is reported as a transition in the block in which it times out.
+#[tokio::test]
+async fn test_status_transitions_cat_timeout() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_status_transitions_cat_timeout ===");
+
+    let (mut hig_node, _receiver_hig_to_hs) = setup_test_hig_node(true).await;
+
+    let cl_id = CLTransactionId("cl-tx".to_string());
+    let cat_tx = Transaction::new(
+        TransactionId(format!("{}:tx", cl_id.0)),
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)],
+        "CAT.credit 1 100".to_string(),
+        cl_id,
+    ).expect("Failed to create CAT transaction");
+
+    hig_node.process_subblock(SubBlock {
+        block_height: 1,
+        chain_id: NetworkSpec::test_chain(1),
+        transactions: vec![cat_tx.clone()],
+    }).await.unwrap();
+
+    // Block 6 is after the CAT's max lifetime
+    hig_node.process_subblock(SubBlock {
+        block_height: 6,
+        chain_id: NetworkSpec::test_chain(1),
+        transactions: vec![],
+    }).await.unwrap();
+
+    let transitions = hig_node.get_status_transitions(6).await.unwrap();
+    logging::log("TEST", &format!("Transitions at block 6: {:?}", transitions));
+    assert_eq!(transitions.len(), 1);
+    assert_eq!(transitions.get(&cat_tx.id), Some(&TransactionStatus::TimedOut));
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
@@ -0,0 +1,135 @@
+use crate::types::{Transaction, TransactionId, SubBlock, TransactionStatus, CLTransactionId, NetworkSpec};
+use crate::utils::logging;
+use crate::hyper_ig::tests::basic::setup_test_hig_node;
+use crate::hyper_ig::HyperIG;
+use crate::hyper_ig::node::HyperIGNode;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Helper function to create a pending CAT in block 1 and return the node together with the CAT's cl-id and tx-id
+async fn setup_pending_cat() -> (Arc<Mutex<HyperIGNode>>, CLTransactionId, TransactionId) {
+    let (mut hig_node, _receiver_hig_to_hs) = setup_test_hig_node(true).await;
+
+    let cl_id = CLTransactionId("cl-tx".to_string());
+    let tx_id = TransactionId(format!("{}:tx", cl_id.0));
+    let cat_tx = Transaction::new(
+        tx_id.clone(),
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)],
+        "CAT.credit 1 100".to_string(),
+        cl_id.clone(),
+    ).expect("Failed to create transaction");
+
+    let subblock = SubBlock {
+        block_height: 1,
+        chain_id: NetworkSpec::test_chain(1),
+        transactions: vec![cat_tx],
+    };
+    hig_node.process_subblock(subblock).await.unwrap();
+
+    let status = hig_node.get_transaction_status(tx_id.clone()).await.unwrap();
+    assert_eq!(status, TransactionStatus::Pending, "CAT should be pending");
+
+    (hig_node, cl_id, tx_id)
+}
+
+/// Tests that a status update injected as a regular user transaction is rejected.
+///
+/// The spoofed update references the CAT but does not carry the HS-origin marker
+/// in its CL ID, so the HIG must ignore it and leave the CAT pending.
+#[tokio::test]
+async fn test_spoofed_status_update_is_rejected() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_spoofed_status_update_is_rejected ===");
+
+    let (mut hig_node, cl_id, tx_id) = setup_pending_cat().await;
+
+    // Inject a status update under an attacker-chosen CL ID
+    let attacker_cl_id = CLTransactionId("attacker-tx".to_string());
+    let spoofed_update = Transaction::new(
+        TransactionId(format!("{}:tx", attacker_cl_id.0)),
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1)],
+        format!("STATUS_UPDATE:Success.CAT_ID:{}", cl_id.0),
+        attacker_cl_id,
+    ).expect("Failed to create status update");
+    let subblock = SubBlock {
+        block_height: 2,
+        chain_id: NetworkSpec::test_chain(1),
+        transactions: vec![spoofed_update],
+    };
+    hig_node.process_subblock(subblock).await.unwrap();
+
+    // Verify the CAT was not resolved and the rejection was counted
+    let status = hig_node.get_transaction_status(tx_id).await.unwrap();
+    assert_eq!(status, TransactionStatus::Pending, "CAT should remain pending after spoofed status update");
+    let rejected = hig_node.lock().await.get_rejected_status_update_count().await;
+    assert_eq!(rejected, 1, "Spoofed status update should be counted as rejected");
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
+
+/// Tests that a status update reusing the CAT's own CL ID is rejected.
+///
+/// Only the HS produces status updates under `<cat_id>.UPDATE`; a transaction bundled
+/// under the CAT's CL ID itself must not be able to resolve the CAT.
+#[tokio::test]
+async fn test_status_update_with_cat_cl_id_is_rejected() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_status_update_with_cat_cl_id_is_rejected ===");
+
+    let (mut hig_node, cl_id, tx_id) = setup_pending_cat().await;
+
+    let spoofed_update = Transaction::new(
+        TransactionId(format!("{}:status_update", cl_id.0)),
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1)],
+        format!("STATUS_UPDATE:Failure.CAT_ID:{}", cl_id.0),
+        cl_id.clone(),
+    ).expect("Failed to create status update");
+    let subblock = SubBlock {
+        block_height: 2,
+        chain_id: NetworkSpec::test_chain(1),
+        transactions: vec![spoofed_update],
+    };
+    hig_node.process_subblock(subblock).await.unwrap();
+
+    let status = hig_node.get_transaction_status(tx_id).await.unwrap();
+    assert_eq!(status, TransactionStatus::Pending, "CAT should remain pending after spoofed status update");
+    let rejected = hig_node.lock().await.get_rejected_status_update_count().await;
+    assert_eq!(rejected, 1, "Spoofed status update should be counted as rejected");
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
+
+/// Tests that a status update carrying the HS-origin marker is accepted via the subblock path.
+#[tokio::test]
+async fn test_hs_originated_status_update_is_accepted() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_hs_originated_status_update_is_accepted ===");
+
+    let (mut hig_node, cl_id, tx_id) = setup_pending_cat().await;
+
+    // Build the status update the same way the HS does
+    let update_cl_id = CLTransactionId(format!("{}.UPDATE", cl_id.0));
+    let status_update = Transaction::new(
+        TransactionId(format!("{}.{}", update_cl_id.0, NetworkSpec::test_chain(1).0)),
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)],
+        format!("STATUS_UPDATE:Success.CAT_ID:{}", cl_id.0),
+        update_cl_id,
+    ).expect("Failed to create status update");
+    let subblock = SubBlock {
+        block_height: 2,
+        chain_id: NetworkSpec::test_chain(1),
+        transactions: vec![status_update],
+    };
+    hig_node.process_subblock(subblock).await.unwrap();
+
+    let status = hig_node.get_transaction_status(tx_id).await.unwrap();
+    assert_eq!(status, TransactionStatus::Success, "CAT should be resolved by the HS-originated status update");
+    let rejected = hig_node.lock().await.get_rejected_status_update_count().await;
+    assert_eq!(rejected, 0, "No status update should be rejected");
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
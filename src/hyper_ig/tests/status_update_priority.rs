@@ -0,0 +1,95 @@
+use crate::types::{Transaction, TransactionId, SubBlock, TransactionStatus, CLTransactionId, NetworkSpec};
+use crate::utils::logging;
+use crate::hyper_ig::tests::basic::setup_test_hig_node;
+use crate::hyper_ig::HyperIG;
+
+/// Processes a subblock with a regular transaction blocked by a CAT followed by the CAT's status update.
+/// 
+/// # Returns
+/// The final status of the regular transaction and the number of times a transaction waited for a locked key
+async fn run_dependent_tx_before_status_update(prioritize: bool) -> (TransactionStatus, u64) {
+    let (mut hig_node, _receiver_hig_to_hs) = setup_test_hig_node(true).await;
+    hig_node.lock().await.set_prioritize_status_updates(prioritize).await;
+    assert_eq!(hig_node.lock().await.get_prioritize_status_updates().await, prioritize);
+
+    // Block 1: a CAT locking account 1
+    let cat_cl_id = CLTransactionId("cl-tx_cat".to_string());
+    let cat_tx = Transaction::new(
+        TransactionId(format!("{}:tx", cat_cl_id.0)),
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)],
+        "CAT.credit 1 100".to_string(),
+        cat_cl_id.clone(),
+    ).expect("Failed to create transaction");
+    hig_node.process_subblock(SubBlock {
+        block_height: 1,
+        chain_id: NetworkSpec::test_chain(1),
+        transactions: vec![cat_tx],
+    }).await.unwrap();
+
+    // Block 2: a regular transaction on account 1, then the status update of the CAT
+    let regular_tx = Transaction::new(
+        TransactionId("cl-tx_regular:tx".to_string()),
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1)],
+        "REGULAR.credit 1 50".to_string(),
+        CLTransactionId("cl-tx_regular".to_string()),
+    ).expect("Failed to create transaction");
+    let update_cl_id = CLTransactionId(format!("{}.UPDATE", cat_cl_id.0));
+    let status_update = Transaction::new(
+        TransactionId(format!("{}.{}", update_cl_id.0, NetworkSpec::test_chain(1).0)),
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)],
+        format!("STATUS_UPDATE:Success.CAT_ID:{}", cat_cl_id.0),
+        update_cl_id,
+    ).expect("Failed to create status update");
+    hig_node.process_subblock(SubBlock {
+        block_height: 2,
+        chain_id: NetworkSpec::test_chain(1),
+        transactions: vec![regular_tx.clone(), status_update],
+    }).await.unwrap();
+
+    let balance = hig_node.lock().await.get_account_balance(NetworkSpec::test_chain(1), 1, false).await.unwrap();
+    assert_eq!(balance.balance, 150, "Both the CAT and the regular transaction should be executed");
+
+    let status = hig_node.get_transaction_status(regular_tx.id).await.unwrap();
+    let waited = hig_node.get_hottest_keys(usize::MAX).await.unwrap().iter().map(|key| key.blocked_count).sum();
+    (status, waited)
+}
+
+/// Tests that by default the transactions of a subblock are processed in order.
+/// 
+/// Test flow:
+/// 1. Processes a CAT in block 1
+/// 2. Processes a dependent regular transaction and the CAT's status update in block 2
+/// 3. Verifies the regular transaction waited on the CAT before succeeding
+#[tokio::test]
+async fn test_status_updates_processed_in_order_by_default() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_status_updates_processed_in_order_by_default ===");
+
+    let (status, waited) = run_dependent_tx_before_status_update(false).await;
+    assert_eq!(status, TransactionStatus::Success);
+    assert_eq!(waited, 1, "The regular transaction should have waited on the CAT");
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
+
+/// Tests that status updates are processed first when prioritized.
+/// 
+/// Test flow:
+/// 1. Enables the status update priority
+/// 2. Processes a CAT in block 1
+/// 3. Processes a dependent regular transaction and the CAT's status update in block 2
+/// 4. Verifies the regular transaction succeeded without waiting
+#[tokio::test]
+async fn test_status_updates_prioritized() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_status_updates_prioritized ===");
+
+    let (status, waited) = run_dependent_tx_before_status_update(true).await;
+    assert_eq!(status, TransactionStatus::Success);
+    assert_eq!(waited, 0, "The regular transaction should not have waited on the CAT");
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
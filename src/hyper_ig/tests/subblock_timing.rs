@@ -0,0 +1,65 @@
+use crate::types::{Transaction, TransactionId, SubBlock, CLTransactionId, ChainId};
+use crate::types::NetworkSpec;
+use crate::utils::logging;
+use crate::hyper_ig::HyperIG;
+use crate::hyper_ig::tests::basic::setup_test_hig_node;
+use std::time::Duration;
+
+/// Tests that the HIG records the time spent on each processed subblock.
+/// 
+/// Test flow:
+/// 1. Processes a subblock with a regular transaction and one with a CAT (which queues a proposal)
+/// 2. Verifies that a timing is recorded for both heights and the parts add up to the total
+/// 3. Verifies that subblocks processed outside the message loop have no lock wait
+/// 4. Verifies that heights without a processed subblock have no timing
+#[tokio::test]
+async fn test_subblock_timing_recorded() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_subblock_timing_recorded ===");
+
+    let (hig_node, _rx) = setup_test_hig_node(true).await;
+
+    let regular_cl_id = CLTransactionId("cl-tx_regular".to_string());
+    let regular_tx = Transaction::new(
+        TransactionId(format!("{}:tx", regular_cl_id.0)),
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1)],
+        "REGULAR.credit 1 100".to_string(),
+        regular_cl_id,
+    ).expect("Failed to create regular transaction");
+    hig_node.lock().await.process_subblock(SubBlock {
+        chain_id: NetworkSpec::test_chain(1),
+        block_height: 1,
+        transactions: vec![regular_tx],
+    }).await.unwrap();
+
+    let cat_cl_id = CLTransactionId("cl-tx_cat".to_string());
+    let cat_tx = Transaction::new(
+        TransactionId(format!("{}:tx", cat_cl_id.0)),
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1), ChainId("chain-2".to_string())],
+        "CAT.credit 2 100".to_string(),
+        cat_cl_id,
+    ).expect("Failed to create CAT transaction");
+    hig_node.lock().await.process_subblock(SubBlock {
+        chain_id: NetworkSpec::test_chain(1),
+        block_height: 2,
+        transactions: vec![cat_tx],
+    }).await.unwrap();
+
+    for block_height in [1, 2] {
+        let timing = hig_node.lock().await.get_subblock_timing(block_height).await.unwrap()
+            .expect("Timing should be recorded for a processed subblock");
+        logging::log("TEST", &format!("Timing of block {}: {:?}", block_height, timing));
+        assert_eq!(timing.lock_wait, Duration::ZERO, "Subblocks processed directly do not wait for the message loop");
+        assert_eq!(timing.total(), timing.lock_wait + timing.execution + timing.proposal_queuing);
+    }
+
+    // The regular transaction queues no proposal
+    let timing = hig_node.lock().await.get_subblock_timing(1).await.unwrap().unwrap();
+    assert_eq!(timing.proposal_queuing, Duration::ZERO);
+
+    assert_eq!(hig_node.lock().await.get_subblock_timing(3).await.unwrap(), None);
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
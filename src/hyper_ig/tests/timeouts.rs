@@ -1,7 +1,7 @@
-use crate::types::{Transaction, TransactionId, CATId, SubBlock, TransactionStatus, CLTransactionId, constants};
+use crate::types::{Transaction, TransactionId, CATId, SubBlock, TransactionStatus, CLTransactionId, ClockSkew, ManualBlockHeight, NetworkSpec};
 use crate::utils::logging;
 use crate::hyper_ig::tests::basic::setup_test_hig_node;
-use crate::hyper_ig::HyperIG;
+use crate::hyper_ig::{HyperIG, ProtocolViolationPolicy};
 use crate::hyper_ig::node::HyperIGNode;
 use crate::types::CATStatusUpdate;
 use std::sync::Arc;
@@ -9,12 +9,18 @@ use tokio::sync::{Mutex, mpsc};
 
 /// Helper function to run a CAT timeout test with specific parameters
 async fn run_cat_timeout_test(second_block_height: u64, expected_status: TransactionStatus) -> (Arc<Mutex<HyperIGNode>>, mpsc::Receiver<CATStatusUpdate>) {
+    run_cat_timeout_test_with_clock_skew(second_block_height, ClockSkew::default(), expected_status).await
+}
+
+/// Helper function to run a CAT timeout test on a node with a skewed local clock
+async fn run_cat_timeout_test_with_clock_skew(second_block_height: u64, clock_skew: ClockSkew, expected_status: TransactionStatus) -> (Arc<Mutex<HyperIGNode>>, mpsc::Receiver<CATStatusUpdate>) {
     logging::init_logging();
-    logging::log("TEST", &format!("\n=== Starting CAT timeout test with block height {} and expected status {:?} ===", 
-        second_block_height, expected_status));
+    logging::log("TEST", &format!("\n=== Starting CAT timeout test with block height {}, clock skew {:?} and expected status {:?} ===", 
+        second_block_height, clock_skew, expected_status));
     
     // Create node
     let (mut hig_node, receiver_hig_to_hs) = setup_test_hig_node(true).await;
+    hig_node.lock().await.set_clock_skew(clock_skew).await;
     
     // Create a CAT transaction
     let cl_id = CLTransactionId("cl-tx".to_string());
@@ -23,8 +29,8 @@ async fn run_cat_timeout_test(second_block_height: u64, expected_status: Transac
     logging::log("TEST", &format!("Created tx-id='{}'", tx_id.0));
     let cat_tx = Transaction::new(
         tx_id,
-        constants::chain_1(),
-        vec![constants::chain_1(), constants::chain_2()],
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)],
         "CAT.credit 1 100".to_string(),
         cl_id.clone(),
     ).expect("Failed to create transaction");
@@ -32,7 +38,7 @@ async fn run_cat_timeout_test(second_block_height: u64, expected_status: Transac
     // Process the CAT in block 1
     let subblock = SubBlock {
         block_height: 1,
-        chain_id: constants::chain_1(),
+        chain_id: NetworkSpec::test_chain(1),
         transactions: vec![cat_tx.clone()],
     };
     hig_node.process_subblock(subblock).await.unwrap();
@@ -50,7 +56,7 @@ async fn run_cat_timeout_test(second_block_height: u64, expected_status: Transac
     // Process the second block at the specified height
     let subblock = SubBlock {
         block_height: second_block_height,
-        chain_id: constants::chain_1(),
+        chain_id: NetworkSpec::test_chain(1),
         transactions: vec![],
     };
     hig_node.process_subblock(subblock).await.unwrap();
@@ -82,7 +88,7 @@ async fn run_cat_timeout_test(second_block_height: u64, expected_status: Transac
 #[tokio::test]
 async fn test_cat_timeout() {
     // Create a CAT in block 1, then process block 6 (which is after max lifetime)
-    run_cat_timeout_test(6, TransactionStatus::Failure).await;
+    run_cat_timeout_test(6, TransactionStatus::TimedOut).await;
 }
 
 /// Tests that a CAT transaction remains pending for a block height less than its expiration.
@@ -101,6 +107,30 @@ async fn test_cat_timeout_not() {
     run_cat_timeout_test(5, TransactionStatus::Pending).await;
 }
 
+/// Tests that a HIG whose clock runs ahead of the CL times a CAT out earlier.
+/// 
+/// Test flow:
+/// 1. Sets a clock offset of +1 block on the HIG
+/// 2. Creates a CAT transaction in block 1 (max lifetime 5)
+/// 3. Processes block 5, which the HIG perceives as block 6
+/// 4. Verifies the CAT is timed out
+#[tokio::test]
+async fn test_cat_timeout_clock_ahead() {
+    run_cat_timeout_test_with_clock_skew(5, ClockSkew { offset: 1.0, drift: 0.0 }, TransactionStatus::TimedOut).await;
+}
+
+/// Tests that a HIG whose clock runs slow keeps a CAT pending past its lifetime in CL blocks.
+/// 
+/// Test flow:
+/// 1. Sets a clock drift of -20% on the HIG
+/// 2. Creates a CAT transaction in block 1 (max lifetime 5)
+/// 3. Processes block 6, which the HIG perceives as block 4
+/// 4. Verifies the CAT is still pending
+#[tokio::test]
+async fn test_cat_timeout_clock_slow() {
+    run_cat_timeout_test_with_clock_skew(6, ClockSkew { offset: 0.0, drift: -0.2 }, TransactionStatus::Pending).await;
+}
+
 /// Tests that a timed-out CAT cannot be updated to success.
 /// 
 /// This test verifies that once a CAT transaction is marked as failed due to timeout,
@@ -129,18 +159,18 @@ async fn test_cat_timeout_irreversible() {
     let tx_id = TransactionId(format!("{}:tx", cl_id.0));
     
     // Use run_cat_timeout_test to set up and trigger timeout
-    let (mut hig_node, _receiver_hig_to_hs) = run_cat_timeout_test(6, TransactionStatus::Failure).await;
+    let (mut hig_node, _receiver_hig_to_hs) = run_cat_timeout_test(6, TransactionStatus::TimedOut).await;
     
     // ensure the cat is marked as failed
     let status = hig_node.get_transaction_status(tx_id.clone()).await.unwrap();
-    assert_eq!(status, TransactionStatus::Failure, "CAT should be marked as failed");
+    assert_eq!(status, TransactionStatus::TimedOut, "CAT should be marked as timed out");
     
     // Try to update the CAT to success
     let cl_id_2 = CLTransactionId("cl-tx.UPDATE".to_string());
     let status_update = Transaction::new(
         TransactionId(format!("{}:tx", cl_id_2.0)),
-        constants::chain_1(),
-        vec![constants::chain_1()],
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1)],
         format!("STATUS_UPDATE:Success.CAT_ID:{}", cl_id.0),
         cl_id.clone(),
     ).expect("Failed to create status update");
@@ -150,11 +180,66 @@ async fn test_cat_timeout_irreversible() {
     
     // Verify CAT is still failed
     let status = hig_node.get_transaction_status(tx_id).await.unwrap();
-    assert_eq!(status, TransactionStatus::Failure, "CAT should remain failed even after status update");
+    assert_eq!(status, TransactionStatus::TimedOut, "CAT should remain timed out even after status update");
     
     logging::log("TEST", "=== Test completed successfully ===\n");
 }
 
+/// Helper function: times out a CAT under the given policy, then delivers a late Success status update.
+/// Returns the node and the result of processing the status update.
+async fn run_success_after_failure_test(policy: ProtocolViolationPolicy) -> (Arc<Mutex<HyperIGNode>>, Result<TransactionStatus, anyhow::Error>) {
+    let cl_id = CLTransactionId("cl-tx".to_string());
+    let tx_id = TransactionId(format!("{}:tx", cl_id.0));
+
+    let (mut hig_node, _receiver_hig_to_hs) = run_cat_timeout_test(6, TransactionStatus::TimedOut).await;
+    hig_node.lock().await.set_protocol_violation_policy(policy).await;
+
+    let status_update = Transaction::new(
+        TransactionId(format!("{}.UPDATE:tx", cl_id.0)),
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1)],
+        format!("STATUS_UPDATE:Success.CAT_ID:{}", cl_id.0),
+        CLTransactionId(format!("{}.UPDATE", cl_id.0)),
+    ).expect("Failed to create status update");
+    let result = hig_node.process_transaction(status_update).await;
+
+    // The CAT must remain failed regardless of the policy
+    let status = hig_node.get_transaction_status(tx_id).await.unwrap();
+    assert_eq!(status, TransactionStatus::TimedOut, "CAT should remain timed out after late Success update");
+
+    (hig_node, result)
+}
+
+/// Tests that a Success update for a timed-out CAT is counted as a protocol violation and ignored by default.
+#[tokio::test]
+async fn test_success_after_failure_ignore_policy() {
+    logging::init_logging();
+    let (hig_node, result) = run_success_after_failure_test(ProtocolViolationPolicy::Ignore).await;
+    assert_eq!(result.unwrap(), TransactionStatus::TimedOut);
+    assert_eq!(hig_node.lock().await.get_protocol_violation_count().await, 1);
+    assert!(hig_node.lock().await.get_escalated_protocol_violations().await.is_empty());
+}
+
+/// Tests that the FailRun policy surfaces a Success update for a timed-out CAT as an error instead of panicking.
+#[tokio::test]
+async fn test_success_after_failure_fail_run_policy() {
+    logging::init_logging();
+    let (hig_node, result) = run_success_after_failure_test(ProtocolViolationPolicy::FailRun).await;
+    assert!(result.is_err(), "FailRun policy should return an error");
+    assert_eq!(hig_node.lock().await.get_protocol_violation_count().await, 1);
+}
+
+/// Tests that the Escalate policy records a Success update for a timed-out CAT.
+#[tokio::test]
+async fn test_success_after_failure_escalate_policy() {
+    logging::init_logging();
+    let (hig_node, result) = run_success_after_failure_test(ProtocolViolationPolicy::Escalate).await;
+    assert_eq!(result.unwrap(), TransactionStatus::TimedOut);
+    assert_eq!(hig_node.lock().await.get_protocol_violation_count().await, 1);
+    let escalated = hig_node.lock().await.get_escalated_protocol_violations().await;
+    assert_eq!(escalated, vec![(CATId(CLTransactionId("cl-tx".to_string())), 6)]);
+}
+
 /// Tests that a CAT transaction that succeeds should not be timed out.
 /// 
 /// This test verifies that if a CAT transaction receives a success status update,
@@ -181,15 +266,15 @@ async fn test_cat_success_should_not_timeout() {
     // Create a CAT transaction
     let cat_tx = Transaction::new(
         tx_id.clone(),
-        constants::chain_1(),
-        vec![constants::chain_1(), constants::chain_2()],
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)],
         "CAT.credit 1 100".to_string(),
         cl_id.clone(),
     ).expect("Failed to create transaction");
     // Process the CAT in block 1
     let subblock = SubBlock {
         block_height: 1,
-        chain_id: constants::chain_1(),
+        chain_id: NetworkSpec::test_chain(1),
         transactions: vec![cat_tx.clone()],
     };
     hig_node.process_subblock(subblock).await.unwrap();
@@ -202,15 +287,15 @@ async fn test_cat_success_should_not_timeout() {
     // Create a subblock that provides a status update with success
     let status_update = Transaction::new(
         TransactionId(format!("{}:status_update", cl_id.0)),
-        constants::chain_1(),
-        vec![constants::chain_1()],
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1)],
         format!("STATUS_UPDATE:Success.CAT_ID:{}", cl_id.0),
-        cl_id.clone(),
+        CLTransactionId(format!("{}.UPDATE", cl_id.0)),
     ).expect("Failed to create status update");
     // Process the status update in block 2
     let subblock = SubBlock {
         block_height: 2,
-        chain_id: constants::chain_1(),
+        chain_id: NetworkSpec::test_chain(1),
         transactions: vec![status_update],
     };
     hig_node.process_subblock(subblock).await.unwrap();
@@ -227,7 +312,7 @@ async fn test_cat_success_should_not_timeout() {
     // Process block after max lifetime
     let subblock = SubBlock {
         block_height: max_lifetime + 2,
-        chain_id: constants::chain_1(),
+        chain_id: NetworkSpec::test_chain(1),
         transactions: vec![],
     };
     hig_node.process_subblock(subblock).await.unwrap();
@@ -267,8 +352,8 @@ async fn test_status_update_before_timeout_should_process() {
     // Create a CAT transaction
     let cat_tx = Transaction::new(
         tx_id.clone(),
-        constants::chain_1(),
-        vec![constants::chain_1(), constants::chain_2()],
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)],
         "CAT.credit 1 100".to_string(),
         cl_id.clone(),
     ).expect("Failed to create transaction");
@@ -276,7 +361,7 @@ async fn test_status_update_before_timeout_should_process() {
     // Process the CAT in block 1
     let subblock = SubBlock {
         block_height: 1,
-        chain_id: constants::chain_1(),
+        chain_id: NetworkSpec::test_chain(1),
         transactions: vec![cat_tx.clone()],
     };
     hig_node.process_subblock(subblock).await.unwrap();
@@ -295,16 +380,16 @@ async fn test_status_update_before_timeout_should_process() {
     // Create a status update transaction
     let status_update = Transaction::new(
         TransactionId(format!("{}:status_update", cl_id.0)),
-        constants::chain_1(),
-        vec![constants::chain_1()],
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1)],
         format!("STATUS_UPDATE:Success.CAT_ID:{}", cl_id.0),
-        cl_id.clone(),
+        CLTransactionId(format!("{}.UPDATE", cl_id.0)),
     ).expect("Failed to create status update");
     
     // Process the status update in block 2 (before timeout)
     let subblock = SubBlock {
         block_height: 2,
-        chain_id: constants::chain_1(),
+        chain_id: NetworkSpec::test_chain(1),
         transactions: vec![status_update],
     };
     hig_node.process_subblock(subblock).await.unwrap();
@@ -317,7 +402,7 @@ async fn test_status_update_before_timeout_should_process() {
     // Process block 3 to trigger timeout check
     let subblock = SubBlock {
         block_height: 3,
-        chain_id: constants::chain_1(),
+        chain_id: NetworkSpec::test_chain(1),
         transactions: vec![],
     };
     hig_node.process_subblock(subblock).await.unwrap();
@@ -355,8 +440,8 @@ async fn test_status_update_at_timeout_boundary_should_process() {
     // Create a CAT transaction
     let cat_tx = Transaction::new(
         tx_id.clone(),
-        constants::chain_1(),
-        vec![constants::chain_1(), constants::chain_2()],
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)],
         "CAT.credit 1 100".to_string(),
         cl_id.clone(),
     ).expect("Failed to create transaction");
@@ -364,7 +449,7 @@ async fn test_status_update_at_timeout_boundary_should_process() {
     // Process the CAT in block 1
     let subblock = SubBlock {
         block_height: 1,
-        chain_id: constants::chain_1(),
+        chain_id: NetworkSpec::test_chain(1),
         transactions: vec![cat_tx.clone()],
     };
     hig_node.process_subblock(subblock).await.unwrap();
@@ -379,16 +464,16 @@ async fn test_status_update_at_timeout_boundary_should_process() {
     // Create a status update transaction
     let status_update = Transaction::new(
         TransactionId(format!("{}:status_update", cl_id.0)),
-        constants::chain_1(),
-        vec![constants::chain_1()],
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1)],
         format!("STATUS_UPDATE:Success.CAT_ID:{}", cl_id.0),
-        cl_id.clone(),
+        CLTransactionId(format!("{}.UPDATE", cl_id.0)),
     ).expect("Failed to create status update");
     
     // Process the status update in the exact block where timeout would occur
     let subblock = SubBlock {
         block_height: max_lifetime,
-        chain_id: constants::chain_1(),
+        chain_id: NetworkSpec::test_chain(1),
         transactions: vec![status_update],
     };
     hig_node.process_subblock(subblock).await.unwrap();
@@ -401,7 +486,7 @@ async fn test_status_update_at_timeout_boundary_should_process() {
     // Process the next block to trigger timeout check
     let subblock = SubBlock {
         block_height: max_lifetime + 1,
-        chain_id: constants::chain_1(),
+        chain_id: NetworkSpec::test_chain(1),
         transactions: vec![],
     };
     hig_node.process_subblock(subblock).await.unwrap();
@@ -437,8 +522,8 @@ async fn test_regular_tx_released_on_cat_timeout() {
     let cl_id_cat = CLTransactionId("cl-cat-timeout".to_string());
     let cat_tx = Transaction::new(
         TransactionId(format!("{}:cat-credit-tx", cl_id_cat.0)),
-        constants::chain_1(),
-        vec![constants::chain_1(), constants::chain_2()],
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)],
         "CAT.credit 1 100".to_string(),
         cl_id_cat.clone(),
     ).expect("Failed to create CAT transaction");
@@ -447,8 +532,8 @@ async fn test_regular_tx_released_on_cat_timeout() {
     let cl_id_reg = CLTransactionId("cl-reg-depends".to_string());
     let regular_tx = Transaction::new(
         TransactionId(format!("{}:regular-send-tx", cl_id_reg.0)),
-        constants::chain_1(),
-        vec![constants::chain_1()],
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1)],
         "REGULAR.send 1 2 50".to_string(),
         cl_id_reg.clone(),
     ).expect("Failed to create regular transaction");
@@ -456,7 +541,7 @@ async fn test_regular_tx_released_on_cat_timeout() {
     // Process both transactions in block 1
     let subblock = SubBlock {
         block_height: 1,
-        chain_id: constants::chain_1(),
+        chain_id: NetworkSpec::test_chain(1),
         transactions: vec![cat_tx.clone(), regular_tx.clone()],
     };
     hig_node.process_subblock(subblock).await.unwrap();
@@ -489,7 +574,7 @@ async fn test_regular_tx_released_on_cat_timeout() {
     let timeout_block = max_lifetime + 1;
     let subblock = SubBlock {
         block_height: timeout_block,
-        chain_id: constants::chain_1(),
+        chain_id: NetworkSpec::test_chain(1),
         transactions: vec![],
     };
     hig_node.process_subblock(subblock).await.unwrap();
@@ -497,7 +582,7 @@ async fn test_regular_tx_released_on_cat_timeout() {
     
     // Verify CAT is now failed due to timeout
     let cat_status = hig_node.get_transaction_status(cat_tx.id.clone()).await.unwrap();
-    assert_eq!(cat_status, TransactionStatus::Failure, "CAT should be failed due to timeout");
+    assert_eq!(cat_status, TransactionStatus::TimedOut, "CAT should be timed out");
     
     // Verify regular transaction is now processed (should fail due to insufficient balance)
     let reg_status = hig_node.get_transaction_status(regular_tx.id.clone()).await.unwrap();
@@ -524,3 +609,110 @@ async fn test_regular_tx_released_on_cat_timeout() {
     logging::log("TEST", "=== Test completed successfully ===\n");
 }
 
+
+/// Tests that the ages of the pending CATs are the blocks since each CAT was received:
+/// - CATs received at different heights have different ages
+/// - CATs leave the histogram once they time out
+#[tokio::test]
+async fn test_pending_cat_ages() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_pending_cat_ages ===");
+
+    let (mut hig_node, _receiver_hig_to_hs) = setup_test_hig_node(true).await;
+    let cat_tx = |id: &str, account: u32| Transaction::new(
+        TransactionId(format!("{}:tx", id)),
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)],
+        format!("CAT.credit {} 100", account),
+        CLTransactionId(id.to_string()),
+    ).expect("Failed to create transaction");
+
+    // Receive one CAT at block 1 and one at block 3
+    for (block_height, transactions) in [(1, vec![cat_tx("cat-1", 1)]), (2, vec![]), (3, vec![cat_tx("cat-2", 2)]), (4, vec![])] {
+        hig_node.process_subblock(SubBlock { block_height, chain_id: NetworkSpec::test_chain(1), transactions }).await.unwrap();
+    }
+    let ages = hig_node.lock().await.get_pending_cat_ages().await;
+    assert_eq!(ages.into_iter().collect::<Vec<_>>(), vec![(1, 1), (3, 1)]);
+
+    // The first CAT times out after its lifetime of 4 blocks
+    hig_node.process_subblock(SubBlock { block_height: 6, chain_id: NetworkSpec::test_chain(1), transactions: vec![] }).await.unwrap();
+    let ages = hig_node.lock().await.get_pending_cat_ages().await;
+    assert_eq!(ages.into_iter().collect::<Vec<_>>(), vec![(3, 1)]);
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
+
+/// Tests that changing the CAT lifetime at runtime only affects the CATs received afterwards:
+/// - The CAT received before the change keeps its lifetime and times out
+/// - The CAT received after the change gets the new lifetime and stays pending
+#[tokio::test]
+async fn test_cat_lifetime_runtime_change() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_cat_lifetime_runtime_change ===");
+
+    let (mut hig_node, _receiver_hig_to_hs) = setup_test_hig_node(true).await;
+    let cat_tx = |id: &str, account: u32| Transaction::new(
+        TransactionId(format!("{}:tx", id)),
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)],
+        format!("CAT.credit {} 100", account),
+        CLTransactionId(id.to_string()),
+    ).expect("Failed to create transaction");
+
+    // Receive one CAT with the initial lifetime, then extend the lifetime and receive another
+    hig_node.process_subblock(SubBlock { block_height: 1, chain_id: NetworkSpec::test_chain(1), transactions: vec![cat_tx("cat-1", 1)] }).await.unwrap();
+    hig_node.lock().await.set_cat_lifetime(10).await;
+    assert_eq!(hig_node.get_cat_lifetime().await.unwrap(), 10);
+    hig_node.process_subblock(SubBlock { block_height: 2, chain_id: NetworkSpec::test_chain(1), transactions: vec![cat_tx("cat-2", 2)] }).await.unwrap();
+    assert_eq!(hig_node.get_cat_max_lifetime(CATId(CLTransactionId("cat-2".to_string()))).await.unwrap(), 12);
+
+    // Only the first CAT times out
+    hig_node.process_subblock(SubBlock { block_height: 6, chain_id: NetworkSpec::test_chain(1), transactions: vec![] }).await.unwrap();
+    assert_eq!(hig_node.get_transaction_status(TransactionId("cat-1:tx".to_string())).await.unwrap(), TransactionStatus::TimedOut);
+    assert_eq!(hig_node.get_transaction_status(TransactionId("cat-2:tx".to_string())).await.unwrap(), TransactionStatus::Pending);
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
+
+/// Tests that a manual block height source decides CAT timeouts independently of the subblocks.
+/// 
+/// Test flow:
+/// 1. Injects a manual block height source at height 1
+/// 2. Creates a CAT transaction in block 1 (max lifetime 5)
+/// 3. Processes block 10, which does not move the manual height, and verifies the CAT is pending
+/// 4. Advances the manual height to 5 and checks the timeouts, the CAT is still pending
+/// 5. Advances the manual height to 6 and checks the timeouts, the CAT is timed out
+#[tokio::test]
+async fn test_cat_timeout_manual_block_height() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_cat_timeout_manual_block_height ===");
+
+    let (mut hig_node, _receiver_hig_to_hs) = setup_test_hig_node(true).await;
+    let block_height = Arc::new(ManualBlockHeight::new(1));
+    hig_node.lock().await.set_block_height_source(block_height.clone());
+
+    let cl_id = CLTransactionId("cl-tx".to_string());
+    let cat_tx = Transaction::new(
+        TransactionId(format!("{}:tx", cl_id.0)),
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)],
+        "CAT.credit 1 100".to_string(),
+        cl_id.clone(),
+    ).expect("Failed to create transaction");
+    hig_node.process_subblock(SubBlock { block_height: 1, chain_id: NetworkSpec::test_chain(1), transactions: vec![cat_tx.clone()] }).await.unwrap();
+    assert_eq!(hig_node.get_cat_max_lifetime(CATId(cl_id)).await.unwrap(), 5);
+
+    // Subblocks no longer move the height timeouts are judged by
+    hig_node.process_subblock(SubBlock { block_height: 10, chain_id: NetworkSpec::test_chain(1), transactions: vec![] }).await.unwrap();
+    assert_eq!(hig_node.get_transaction_status(cat_tx.id.clone()).await.unwrap(), TransactionStatus::Pending);
+
+    block_height.advance(4);
+    hig_node.lock().await.check_timeouts().await;
+    assert_eq!(hig_node.get_transaction_status(cat_tx.id.clone()).await.unwrap(), TransactionStatus::Pending, "The CAT lives until block 5");
+
+    block_height.advance(1);
+    hig_node.lock().await.check_timeouts().await;
+    assert_eq!(hig_node.get_transaction_status(cat_tx.id).await.unwrap(), TransactionStatus::TimedOut);
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
@@ -0,0 +1,123 @@
+use crate::types::{Transaction, TransactionId, SubBlock, TransactionStatus, CLTransactionId, NetworkSpec};
+use crate::utils::logging;
+use crate::hyper_ig::tests::basic::setup_test_hig_node;
+use crate::hyper_ig::HyperIG;
+
+/// Creates a regular transaction on chain-1
+fn regular_tx(name: &str, data: &str) -> Transaction {
+    let cl_id = CLTransactionId(name.to_string());
+    Transaction::new(
+        TransactionId(format!("{}:tx", cl_id.0)),
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1)],
+        data.to_string(),
+        cl_id,
+    ).expect("Failed to create transaction")
+}
+
+/// Tests that a regular transaction blocked behind a CAT expires once its expiry height passes.
+/// 
+/// Test flow:
+/// 1. Processes a CAT locking account 1 in block 1
+/// 2. Processes two regular transactions on account 1 in block 2, the first expiring after block 2
+/// 3. Verifies the first transaction expires in block 3 while the second keeps waiting on the CAT
+/// 4. Processes the CAT's status update in block 4 and verifies the second transaction succeeds
+#[tokio::test]
+async fn test_blocked_regular_transaction_expires() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_blocked_regular_transaction_expires ===");
+
+    let (mut hig_node, _receiver_hig_to_hs) = setup_test_hig_node(true).await;
+
+    // Block 1: a CAT locking account 1
+    let cat_cl_id = CLTransactionId("cl-tx_cat".to_string());
+    let cat_tx = Transaction::new(
+        TransactionId(format!("{}:tx", cat_cl_id.0)),
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)],
+        "CAT.credit 1 100".to_string(),
+        cat_cl_id.clone(),
+    ).expect("Failed to create transaction");
+    hig_node.process_subblock(SubBlock {
+        block_height: 1,
+        chain_id: NetworkSpec::test_chain(1),
+        transactions: vec![cat_tx],
+    }).await.unwrap();
+
+    // Block 2: two regular transactions blocked behind the CAT, only the first one expires
+    let expiring_tx = regular_tx("cl-tx_expiring", "REGULAR.credit 1 50").with_expiry(2);
+    let waiting_tx = regular_tx("cl-tx_waiting", "REGULAR.credit 1 7");
+    hig_node.process_subblock(SubBlock {
+        block_height: 2,
+        chain_id: NetworkSpec::test_chain(1),
+        transactions: vec![expiring_tx.clone(), waiting_tx.clone()],
+    }).await.unwrap();
+    assert_eq!(hig_node.get_transaction_status(expiring_tx.id.clone()).await.unwrap(), TransactionStatus::Pending);
+
+    // Block 3: the expiry height has passed
+    hig_node.process_subblock(SubBlock {
+        block_height: 3,
+        chain_id: NetworkSpec::test_chain(1),
+        transactions: vec![],
+    }).await.unwrap();
+    assert_eq!(hig_node.get_transaction_status(expiring_tx.id.clone()).await.unwrap(), TransactionStatus::Expired);
+    assert_eq!(hig_node.get_transaction_status(waiting_tx.id.clone()).await.unwrap(), TransactionStatus::Pending,
+        "The second transaction should still wait on the CAT");
+    assert_eq!(hig_node.lock().await.get_expired_transaction_count().await, 1);
+
+    // Block 4: the CAT's status update releases the second transaction
+    let update_cl_id = CLTransactionId(format!("{}.UPDATE", cat_cl_id.0));
+    let status_update = Transaction::new(
+        TransactionId(format!("{}.{}", update_cl_id.0, NetworkSpec::test_chain(1).0)),
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)],
+        format!("STATUS_UPDATE:Success.CAT_ID:{}", cat_cl_id.0),
+        update_cl_id,
+    ).expect("Failed to create status update");
+    hig_node.process_subblock(SubBlock {
+        block_height: 4,
+        chain_id: NetworkSpec::test_chain(1),
+        transactions: vec![status_update],
+    }).await.unwrap();
+    assert_eq!(hig_node.get_transaction_status(waiting_tx.id).await.unwrap(), TransactionStatus::Success);
+
+    let balance = hig_node.lock().await.get_account_balance(NetworkSpec::test_chain(1), 1, false).await.unwrap();
+    assert_eq!(balance.balance, 107, "The expired transaction should not be executed");
+    let (pending, success, failure) = hig_node.get_transaction_status_counts_regular().await.unwrap();
+    assert_eq!((pending, success, failure), (0, 1, 1));
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
+
+/// Tests that a pending regular transaction without an expiry never expires.
+#[tokio::test]
+async fn test_regular_transaction_without_expiry_keeps_waiting() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_regular_transaction_without_expiry_keeps_waiting ===");
+
+    let (mut hig_node, _receiver_hig_to_hs) = setup_test_hig_node(true).await;
+    let cat_cl_id = CLTransactionId("cl-tx_cat".to_string());
+    let cat_tx = Transaction::new(
+        TransactionId(format!("{}:tx", cat_cl_id.0)),
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)],
+        "CAT.credit 1 100".to_string(),
+        cat_cl_id,
+    ).expect("Failed to create transaction");
+    let waiting_tx = regular_tx("cl-tx_waiting", "REGULAR.credit 1 7");
+    hig_node.process_subblock(SubBlock {
+        block_height: 1,
+        chain_id: NetworkSpec::test_chain(1),
+        transactions: vec![cat_tx, waiting_tx.clone()],
+    }).await.unwrap();
+    hig_node.process_subblock(SubBlock {
+        block_height: 3,
+        chain_id: NetworkSpec::test_chain(1),
+        transactions: vec![],
+    }).await.unwrap();
+
+    assert_eq!(hig_node.get_transaction_status(waiting_tx.id).await.unwrap(), TransactionStatus::Pending);
+    assert_eq!(hig_node.lock().await.get_expired_transaction_count().await, 0);
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
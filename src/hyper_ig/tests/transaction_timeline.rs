@@ -0,0 +1,70 @@
+use crate::types::{Transaction, TransactionId, CLTransactionId, ChainId};
+use crate::types::NetworkSpec;
+use crate::utils::logging;
+use crate::hyper_ig::HyperIG;
+use crate::hyper_ig::tests::basic::setup_test_hig_node;
+use std::time::Duration;
+
+/// Tests that the HIG records when a transaction was received, proposed and finalized, and how long it waited for locks.
+/// 
+/// Test flow:
+/// 1. Processes a CAT crediting key "1" and a transaction sending from key "1" (blocked by the CAT)
+/// 2. Verifies the CAT was proposed but neither transaction is final
+/// 3. Resolves the CAT after a delay
+/// 4. Verifies both transactions are final and the lock wait of the blocked one covers the delay
+#[tokio::test]
+async fn test_transaction_timeline_recorded() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_transaction_timeline_recorded ===");
+
+    let (hig_node, _rx) = setup_test_hig_node(true).await;
+
+    let cat_cl_id = CLTransactionId("cl-tx_cat".to_string());
+    let cat_tx = Transaction::new(
+        TransactionId(format!("{}:tx", cat_cl_id.0)),
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1), ChainId("chain-2".to_string())],
+        "CAT.credit 1 100".to_string(),
+        cat_cl_id.clone(),
+    ).expect("Failed to create CAT transaction");
+    hig_node.lock().await.process_transaction(cat_tx.clone()).await.unwrap();
+
+    let send_cl_id = CLTransactionId("cl-tx_send".to_string());
+    let send_tx = Transaction::new(
+        TransactionId(format!("{}:tx", send_cl_id.0)),
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1)],
+        "REGULAR.send 1 2 10".to_string(),
+        send_cl_id,
+    ).expect("Failed to create regular transaction");
+    hig_node.lock().await.process_transaction(send_tx.clone()).await.unwrap();
+
+    let cat_timeline = hig_node.lock().await.get_transaction_timeline(&cat_tx.id).await.expect("The CAT should have a timeline");
+    assert!(cat_timeline.proposed_at.is_some_and(|at| at >= cat_timeline.received_at), "The CAT should be proposed after it was received");
+    assert!(cat_timeline.finalized_at.is_none(), "The CAT should not be final before its status update");
+    assert!(hig_node.lock().await.get_transaction_timeline(&send_tx.id).await.unwrap().finalized_at.is_none());
+
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    let status_update = Transaction::new(
+        TransactionId(format!("{}:status", cat_cl_id.0)),
+        NetworkSpec::test_chain(1),
+        vec![NetworkSpec::test_chain(1)],
+        format!("STATUS_UPDATE:Success.CAT_ID:{}", cat_cl_id.0),
+        cat_cl_id,
+    ).expect("Failed to create status update");
+    hig_node.lock().await.process_transaction(status_update).await.unwrap();
+
+    let cat_timeline = hig_node.lock().await.get_transaction_timeline(&cat_tx.id).await.unwrap();
+    assert!(cat_timeline.finalized_at.is_some_and(|at| at >= cat_timeline.proposed_at.unwrap()));
+    assert_eq!(cat_timeline.lock_wait, Duration::ZERO, "The CAT never waited for a lock");
+    assert!(cat_timeline.blocked_keys.is_empty());
+
+    let send_timeline = hig_node.lock().await.get_transaction_timeline(&send_tx.id).await.unwrap();
+    logging::log("TEST", &format!("Timeline of the blocked transaction: {:?}", send_timeline));
+    assert!(send_timeline.lock_wait >= Duration::from_millis(50), "The lock wait should cover the time the CAT was pending");
+    assert_eq!(send_timeline.blocked_keys, vec!["1".to_string()], "The transaction waited for the key locked by the CAT");
+    assert!(send_timeline.finalized_at.is_some_and(|at| at >= send_timeline.received_at + send_timeline.lock_wait));
+    assert!(send_timeline.proposed_at.is_none(), "Regular transactions are not proposed");
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
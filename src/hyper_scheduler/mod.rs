@@ -1,6 +1,10 @@
-use crate::types::{CATId, TransactionId, CATStatusLimited, ChainId, CATStatus};
+use crate::types::{CATId, TransactionId, CATStatusLimited, ChainId, CATStatus, ChannelHealth};
 use async_trait::async_trait;
 use thiserror::Error;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use rand::Rng;
+use rand_distr::{Distribution, Exp};
 
 pub mod node;
 pub use node::HyperSchedulerNode;
@@ -19,6 +23,136 @@ pub enum HyperSchedulerError {
     InvalidCATProposal(String),
     #[error("Constituent chains mismatch: expected {expected:?}, got {received:?}")]
     ConstituentChainsMismatch { expected: Vec<ChainId>, received: Vec<ChainId> },
+    #[error("Proposal for CAT {cat_id} is from epoch {epoch}, ahead of the current epoch {current}")]
+    FutureEpoch { cat_id: CATId, epoch: u64, current: u64 },
+}
+
+/// Decision latency of the Hyper Scheduler, i.e. the time between receiving the final
+/// proposal for a CAT and emitting its status update. Values are in seconds.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DecisionLatency {
+    /// Status updates are emitted immediately
+    #[default]
+    Zero,
+    /// Every decision takes the same time
+    Constant { value: f64 },
+    /// Decision time is drawn uniformly from [min, max]
+    Uniform { min: f64, max: f64 },
+    /// Decision time is drawn from an exponential distribution with the given mean
+    Exponential { mean: f64 },
+}
+
+impl DecisionLatency {
+    /// Draws a decision latency from the distribution.
+    /// 
+    /// # Returns
+    /// The sampled latency (zero for invalid or non-positive parameters)
+    pub fn sample(&self) -> Duration {
+        let secs = match *self {
+            DecisionLatency::Zero => 0.0,
+            DecisionLatency::Constant { value } => value,
+            DecisionLatency::Uniform { min, max } => {
+                if max > min { rand::thread_rng().gen_range(min..=max) } else { min }
+            }
+            DecisionLatency::Exponential { mean } => {
+                match Exp::new(1.0 / mean) {
+                    Ok(exp) if mean > 0.0 => exp.sample(&mut rand::thread_rng()),
+                    _ => 0.0,
+                }
+            }
+        };
+        Duration::from_secs_f64(secs.max(0.0))
+    }
+
+    /// Returns the distribution with all parameters multiplied by `factor`.
+    /// Used e.g. by the simulator to convert a latency given in blocks into seconds.
+    /// 
+    /// # Arguments
+    /// * `factor` - The scaling factor
+    pub fn scaled(&self, factor: f64) -> Self {
+        match *self {
+            DecisionLatency::Zero => DecisionLatency::Zero,
+            DecisionLatency::Constant { value } => DecisionLatency::Constant { value: value * factor },
+            DecisionLatency::Uniform { min, max } => DecisionLatency::Uniform { min: min * factor, max: max * factor },
+            DecisionLatency::Exponential { mean } => DecisionLatency::Exponential { mean: mean * factor },
+        }
+    }
+}
+
+/// A CAT ID scoped by the epoch its CAT was proposed in
+/// 
+/// Runs against the same HS may reuse CAT IDs, so the HS keeps the CATs of different epochs
+/// apart and routes every proposal to the CAT of the epoch it carries.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EpochCATId {
+    /// The epoch of the CAT
+    pub epoch: u64,
+    /// The ID of the CAT
+    pub cat_id: CATId,
+}
+
+impl EpochCATId {
+    /// Creates the ID of a CAT in the given epoch
+    pub fn new(epoch: u64, cat_id: CATId) -> Self {
+        Self { epoch, cat_id }
+    }
+}
+
+/// A CAT the HS is still waiting on a chain's proposal for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingCAT {
+    /// The ID of the CAT
+    pub cat_id: CATId,
+    /// The constituent chains of the CAT
+    pub constituent_chains: Vec<ChainId>,
+    /// The chains that already proposed a status
+    pub proposed_by: Vec<ChainId>,
+    /// Time since the first proposal for the CAT arrived
+    pub waiting: Duration,
+}
+
+/// A status proposal the HS received for a CAT
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReceivedProposal {
+    /// The chain that proposed
+    pub chain_id: ChainId,
+    /// The proposed status
+    pub status: CATStatusLimited,
+    /// Time the proposal was accepted
+    pub received_at: std::time::Instant,
+}
+
+/// Snapshot of the internal processing metrics of the Hyper Scheduler
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct HyperSchedulerMetrics {
+    /// Number of proposals received from each chain
+    pub proposals_received: std::collections::HashMap<ChainId, u64>,
+    /// Number of CATs decided (Success or Failure)
+    pub decisions: u64,
+    /// Number of CATs decided at each CL block height (as reported by the HIGs with their proposals)
+    pub decisions_per_block: std::collections::BTreeMap<u64, u64>,
+    /// Time from the first to the last proposal of each CAT all constituent chains proposed for
+    pub proposal_spreads: Vec<Duration>,
+    /// Time since the first proposal of each pending CAT
+    pub pending_cat_ages: Vec<Duration>,
+}
+
+/// Snapshot of the liveness of the Hyper Scheduler, to diagnose hangs
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct HyperSchedulerHealth {
+    /// Whether the loop receiving the status proposals of the HIGs is running
+    pub running: bool,
+    /// Number of registered chains
+    pub registered_chains: usize,
+    /// Number of CATs still waiting for proposals or a decision
+    pub pending_cats: usize,
+    /// Number of proposals received from all chains
+    pub proposals_received: u64,
+    /// Number of CATs decided (Success or Failure)
+    pub decisions: u64,
+    /// The channel to the CL (None once the node no longer holds it)
+    pub cl_channel: Option<ChannelHealth>,
 }
 
 #[async_trait]
@@ -29,6 +163,9 @@ pub trait HyperScheduler: Send + Sync {
     /// Get all pending CAT IDs
     async fn get_pending_cats(&self) -> Result<Vec<CATId>, HyperSchedulerError>;
 
+    /// Get the pending CATs that still await a proposal from the given chain, longest waiting first
+    async fn get_pending_cats_for_chain(&self, chain_id: ChainId) -> Result<Vec<PendingCAT>, HyperSchedulerError>;
+
     /// Get all registered chains
     async fn get_registered_chains(&self) -> Result<Vec<ChainId>, HyperSchedulerError>;
 
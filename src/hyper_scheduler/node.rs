@@ -1,25 +1,85 @@
-use crate::types::{CATId, TransactionId, CATStatusLimited, CLTransaction, ChainId, CATStatusUpdate, CATStatus, Transaction, CLTransactionId};
-use super::{HyperScheduler, HyperSchedulerError};
-use std::collections::{HashMap, HashSet};
-use tokio::sync::mpsc;
+use crate::types::{CATId, TransactionId, CATStatusLimited, CLTransaction, ChainId, CATStatusUpdate, CATStatus, Transaction, CLTransactionId, ClockSkew, ChainRegistrationEvent, CHAIN_EVENTS_CAPACITY, ChannelHealth, Clock, SystemClock};
+use super::{HyperScheduler, HyperSchedulerError, DecisionLatency, EpochCATId, PendingCAT, HyperSchedulerHealth, HyperSchedulerMetrics, ReceivedProposal};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::time::{Duration, Instant};
+use std::pin::Pin;
+use tokio::sync::{broadcast, mpsc};
 use async_trait::async_trait;
+use futures::stream::{self, SelectAll, Stream, StreamExt};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use tokio;
 use crate::utils::logging::log;
+use crate::types::communication::cl_to_hig::STATUS_UPDATE_CL_ID_SUFFIX;
 
 /// The internal state of the HyperSchedulerNode
 pub struct HyperSchedulerState {
     /// The chain IDs of valid chains
     pub registered_chains: HashSet<ChainId>,
-    /// Map of CAT IDs to their constituent chains
-    pub constituent_chains: HashMap<CATId, Vec<ChainId>>,
-    /// Map of CAT IDs to their status (this is the result of the cat_chainwise_statuses)
-    pub cat_statuses: HashMap<CATId, CATStatus>,
-    /// Map of CAT IDs to their status per constituent chain
-    pub cat_chainwise_statuses: HashMap<CATId, HashMap<ChainId, CATStatusLimited>>,
+    /// Map of CATs to their constituent chains
+    pub constituent_chains: HashMap<EpochCATId, Vec<ChainId>>,
+    /// Map of CATs to their status (this is the result of the cat_chainwise_statuses)
+    pub cat_statuses: HashMap<EpochCATId, CATStatus>,
+    /// Map of CATs to their status per constituent chain
+    pub cat_chainwise_statuses: HashMap<EpochCATId, HashMap<ChainId, CATStatusLimited>>,
+    /// Map of CATs to when their first proposal arrived, until all constituent chains proposed
+    pub cat_first_proposal_at: HashMap<EpochCATId, Instant>,
+    /// Map of CATs to the proposals received for them, in order of arrival
+    pub cat_proposals: HashMap<EpochCATId, Vec<ReceivedProposal>>,
+    /// Number of proposals received from each chain
+    pub count_proposals_received: HashMap<ChainId, u64>,
+    /// Number of CATs decided (Success or Failure)
+    pub count_decisions: u64,
+    /// Number of CATs decided at each CL block height
+    pub decisions_per_block: BTreeMap<u64, u64>,
+    /// Highest CL block height the HIGs reported with their proposals, the block decisions are attributed to
+    pub latest_block_height: u64,
+    /// Map of CATs to when they were decided (Success or Failure)
+    pub cat_decided_at: HashMap<EpochCATId, Instant>,
+    /// Map of CATs to when their status update was handed to the CL (before any per-chain status update delay)
+    pub cat_status_update_submitted_at: HashMap<EpochCATId, Instant>,
+    /// Time from the first to the last proposal of each CAT all constituent chains proposed for
+    pub proposal_spreads: Vec<Duration>,
+    /// Time between receiving the final proposal for a CAT and emitting its status update
+    pub decision_latency: DecisionLatency,
+    /// Skew of the local clock relative to the CL (only the drift applies, the HS does not track block heights)
+    pub clock_skew: ClockSkew,
+    /// Delay before the status update for each chain is submitted to the CL (chains without an entry are not delayed)
+    pub status_update_delays: HashMap<ChainId, Duration>,
+    /// The current epoch; a new run starts a new epoch so it can reuse CAT IDs of earlier runs
+    pub epoch: u64,
+    /// Map of CAT IDs to the latest epoch a CAT with the ID was proposed in
+    pub cat_epochs: HashMap<CATId, u64>,
 }
 
+impl HyperSchedulerState {
+    /// The CAT with the given ID in the latest epoch the ID was proposed in
+    fn latest_cat(&self, cat_id: &CATId) -> EpochCATId {
+        EpochCATId::new(self.cat_epochs.get(cat_id).copied().unwrap_or(self.epoch), cat_id.clone())
+    }
+
+    /// The status update to send for a failed CAT: the first non-Success proposal it received, so
+    /// the constituent chains learn whether it failed, timed out or was rejected
+    fn failure_status(&self, cat: &EpochCATId) -> CATStatusLimited {
+        self.cat_proposals.get(cat)
+            .and_then(|proposals| proposals.iter().find(|p| p.status != CATStatusLimited::Success))
+            .map(|p| p.status.clone())
+            .unwrap_or(CATStatusLimited::Failure)
+    }
+
+    /// Counts a decision, attributed to the latest block height the HIGs reported
+    fn record_decision(&mut self) {
+        self.count_decisions += 1;
+        *self.decisions_per_block.entry(self.latest_block_height).or_insert(0) += 1;
+    }
+}
+
+/// A chain receiver handed to the receive loop
+type ChainReceiver = (ChainId, mpsc::Receiver<CATStatusUpdate>);
+
+/// The status updates of one chain, tagged with the chain ID
+type ChainUpdateStream = Pin<Box<dyn Stream<Item = (ChainId, CATStatusUpdate)> + Send>>;
+
 /// A node that implements the HyperScheduler trait
 pub struct HyperSchedulerNode {
     /// The internal state of the node
@@ -28,6 +88,12 @@ pub struct HyperSchedulerNode {
     pub receivers_from_hig: HashMap<String, mpsc::Receiver<CATStatusUpdate>>,
     /// Sender for messages to CL
     pub sender_to_cl: Option<mpsc::Sender<CLTransaction>>,
+    /// Sender of chain registration events
+    chain_events: broadcast::Sender<ChainRegistrationEvent>,
+    /// Hands receivers of newly registered chains to the running receive loop, if any
+    receive_loop: Arc<Mutex<Option<mpsc::UnboundedSender<ChainReceiver>>>>,
+    /// Clock the decision latency and the status update delays elapse on
+    clock: Arc<dyn Clock>,
 }
 
 impl Clone for HyperSchedulerNode {
@@ -36,6 +102,9 @@ impl Clone for HyperSchedulerNode {
             state: self.state.clone(),
             receivers_from_hig: HashMap::new(), // Can't clone receivers
             sender_to_cl: self.sender_to_cl.clone(),
+            chain_events: self.chain_events.clone(),
+            receive_loop: self.receive_loop.clone(),
+            clock: self.clock.clone(),
         }
     }
 }
@@ -49,12 +118,198 @@ impl HyperSchedulerNode {
                 registered_chains: HashSet::new(),
                 constituent_chains: HashMap::new(),
                 cat_chainwise_statuses: HashMap::new(),
+                cat_first_proposal_at: HashMap::new(),
+                cat_proposals: HashMap::new(),
+                count_proposals_received: HashMap::new(),
+                count_decisions: 0,
+                decisions_per_block: BTreeMap::new(),
+                latest_block_height: 0,
+                cat_decided_at: HashMap::new(),
+                cat_status_update_submitted_at: HashMap::new(),
+                proposal_spreads: Vec::new(),
+                decision_latency: DecisionLatency::default(),
+                clock_skew: ClockSkew::default(),
+                status_update_delays: HashMap::new(),
+                epoch: 0,
+                cat_epochs: HashMap::new(),
             })),
             receivers_from_hig: HashMap::new(),
             sender_to_cl: Some(sender_to_cl),
+            chain_events: broadcast::channel(CHAIN_EVENTS_CAPACITY).0,
+            receive_loop: Arc::new(Mutex::new(None)),
+            clock: Arc::new(SystemClock::new()),
+        }
+    }
+
+    /// Sets the clock the decision latency and the status update delays elapse on.
+    /// 
+    /// # Arguments
+    /// * `clock` - The clock, e.g. a `SimulatedClock` for runs in virtual time
+    pub fn set_clock(&mut self, clock: Arc<dyn Clock>) {
+        self.clock = clock;
+    }
+
+    /// Sets the decision latency of the HS.
+    /// 
+    /// # Arguments
+    /// * `latency` - The decision latency distribution (in seconds)
+    pub async fn set_decision_latency(&self, latency: DecisionLatency) {
+        self.state.lock().await.decision_latency = latency;
+    }
+
+    /// Gets the decision latency of the HS.
+    pub async fn get_decision_latency(&self) -> DecisionLatency {
+        self.state.lock().await.decision_latency
+    }
+
+    /// Sets the skew of the local clock of the HS.
+    /// 
+    /// # Arguments
+    /// * `skew` - The clock skew (the offset is ignored, the drift scales the decision latency)
+    pub async fn set_clock_skew(&self, skew: ClockSkew) {
+        self.state.lock().await.clock_skew = skew;
+    }
+
+    /// Gets the skew of the local clock of the HS.
+    pub async fn get_clock_skew(&self) -> ClockSkew {
+        self.state.lock().await.clock_skew
+    }
+
+    /// Sets the delay before the status updates for a chain are submitted to the CL.
+    /// 
+    /// Models settlement paths of different length per chain. The status update parts for chains
+    /// with different delays are submitted separately, as parts of the same CL transaction.
+    /// 
+    /// # Arguments
+    /// * `chain_id` - The chain the status updates are for
+    /// * `delay` - The delay (zero removes it)
+    pub async fn set_status_update_delay(&self, chain_id: ChainId, delay: Duration) {
+        let mut state = self.state.lock().await;
+        if delay.is_zero() {
+            state.status_update_delays.remove(&chain_id);
+        } else {
+            state.status_update_delays.insert(chain_id, delay);
+        }
+    }
+
+    /// Gets the delay before the status updates for a chain are submitted to the CL.
+    pub async fn get_status_update_delay(&self, chain_id: &ChainId) -> Duration {
+        self.state.lock().await.status_update_delays.get(chain_id).copied().unwrap_or_default()
+    }
+
+    /// Gets a snapshot of the metrics of the HS.
+    /// 
+    /// # Returns
+    /// The proposal and decision counters, the proposal spreads of all fully proposed CATs and the ages of the pending CATs
+    pub async fn get_metrics(&self) -> HyperSchedulerMetrics {
+        let state = self.state.lock().await;
+        HyperSchedulerMetrics {
+            proposals_received: state.count_proposals_received.clone(),
+            decisions: state.count_decisions,
+            decisions_per_block: state.decisions_per_block.clone(),
+            proposal_spreads: state.proposal_spreads.clone(),
+            pending_cat_ages: state.cat_statuses.iter()
+                .filter(|(_, status)| **status == CATStatus::Pending)
+                .filter_map(|(cat, _)| state.cat_first_proposal_at.get(cat).map(|at| at.elapsed()))
+                .collect(),
+        }
+    }
+
+    /// Gets a snapshot of the liveness of the HS: whether the receive loop is running, the
+    /// pending CATs, the progress counters and the state of the channel to the CL.
+    pub async fn health(&self) -> HyperSchedulerHealth {
+        // The registrations of a retired receive loop are closed
+        let running = self.receive_loop.lock().await.as_ref().is_some_and(|registrations| !registrations.is_closed());
+        let state = self.state.lock().await;
+        HyperSchedulerHealth {
+            running,
+            registered_chains: state.registered_chains.len(),
+            pending_cats: state.cat_statuses.values().filter(|status| **status == CATStatus::Pending).count(),
+            proposals_received: state.count_proposals_received.values().sum(),
+            decisions: state.count_decisions,
+            cl_channel: self.sender_to_cl.as_ref().map(ChannelHealth::of_sender),
         }
     }
 
+    /// Starts a new epoch, e.g. for a new run against the same HS.
+    /// 
+    /// CATs are scoped by the epoch their proposals carry: a proposal reusing the ID of a CAT
+    /// from an earlier epoch starts a new CAT, while a late proposal of the earlier run still
+    /// reaches the CAT of its own epoch. Getters taking only a CAT ID refer to the latest epoch
+    /// the ID was proposed in.
+    /// 
+    /// # Returns
+    /// The new epoch
+    pub async fn advance_epoch(&self) -> u64 {
+        let mut state = self.state.lock().await;
+        state.epoch += 1;
+        log("HS", &format!("Advanced to epoch {}", state.epoch));
+        state.epoch
+    }
+
+    /// Gets the current epoch of the HS.
+    pub async fn get_epoch(&self) -> u64 {
+        self.state.lock().await.epoch
+    }
+
+    /// Gets the latest epoch a CAT with the given ID was proposed in.
+    /// 
+    /// # Arguments
+    /// * `cat_id` - The ID of the CAT
+    /// 
+    /// # Returns
+    /// The epoch, or None if the HS has not received a proposal for the CAT
+    pub async fn get_cat_epoch(&self, cat_id: &CATId) -> Option<u64> {
+        self.state.lock().await.cat_epochs.get(cat_id).copied()
+    }
+
+    /// Gets the status of the CAT with the given ID in the given epoch.
+    /// 
+    /// # Arguments
+    /// * `cat_id` - The ID of the CAT
+    /// * `epoch` - The epoch of the CAT
+    pub async fn get_cat_status_in_epoch(&self, cat_id: &CATId, epoch: u64) -> Result<CATStatus, HyperSchedulerError> {
+        self.state.lock().await.cat_statuses.get(&EpochCATId::new(epoch, cat_id.clone())).cloned()
+            .ok_or_else(|| HyperSchedulerError::CATNotFound(cat_id.clone()))
+    }
+
+    /// Gets the time a CAT was decided.
+    /// 
+    /// # Arguments
+    /// * `cat_id` - The ID of the CAT
+    /// 
+    /// # Returns
+    /// The time the status of the CAT was set to Success or Failure, or None if it is not decided
+    pub async fn get_cat_decision_time(&self, cat_id: &CATId) -> Option<Instant> {
+        let state = self.state.lock().await;
+        state.cat_decided_at.get(&state.latest_cat(cat_id)).copied()
+    }
+
+    /// Gets the time the status update of a CAT was submitted to the CL.
+    /// 
+    /// # Arguments
+    /// * `cat_id` - The ID of the CAT
+    /// 
+    /// # Returns
+    /// The time the status update was handed to the CL, not counting the status update delays
+    /// of individual chains, or None if no status update was sent for the CAT
+    pub async fn get_status_update_submission_time(&self, cat_id: &CATId) -> Option<Instant> {
+        let state = self.state.lock().await;
+        state.cat_status_update_submitted_at.get(&state.latest_cat(cat_id)).copied()
+    }
+
+    /// Gets the status proposals received for a CAT.
+    /// 
+    /// # Arguments
+    /// * `cat_id` - The ID of the CAT
+    /// 
+    /// # Returns
+    /// The accepted proposals in order of arrival (duplicates and mismatching proposals are not included)
+    pub async fn get_cat_proposals(&self, cat_id: &CATId) -> Vec<ReceivedProposal> {
+        let state = self.state.lock().await;
+        state.cat_proposals.get(&state.latest_cat(cat_id)).cloned().unwrap_or_default()
+    }
+
     /// Get a clone of the sender to the confirmation layer
     pub async fn get_sender_to_cl(&self) -> mpsc::Sender<CLTransaction> {
         self.sender_to_cl.as_ref().expect("Sender to CL not set").clone()
@@ -73,166 +328,203 @@ impl HyperSchedulerNode {
         log("HS", &format!("Chain {} registered successfully", chain_id.0));
         
         // Start message processing for this chain
-        log("HS", &format!("Starting message processing loop for chain '{}'", chain_id.0));
+        self.add_receiver(chain_id.clone(), receiver).await;
+        
+        // Sending only fails if there are no subscribers
+        let _ = self.chain_events.send(ChainRegistrationEvent::Registered(chain_id));
+        
+        Ok(())
+    }
+
+    /// Deregister a chain
+    /// 
+    /// Proposals for CATs that include the chain are rejected afterwards. The receive loop
+    /// stops polling the chain's receiver once its HIG drops the sender.
+    pub async fn deregister_chain(&mut self, chain_id: ChainId) -> Result<(), HyperSchedulerError> {
+        let mut state = self.state.lock().await;
+        if !state.registered_chains.remove(&chain_id) {
+            return Err(HyperSchedulerError::Internal(format!("Chain {} is not registered", chain_id.0)));
+        }
+        log("HS", &format!("Chain {} deregistered", chain_id.0));
+
+        // Sending only fails if there are no subscribers
+        let _ = self.chain_events.send(ChainRegistrationEvent::Deregistered(chain_id));
+
+        Ok(())
+    }
+
+    /// Subscribe to chain registration and deregistration events
+    pub fn subscribe_chain_events(&self) -> broadcast::Receiver<ChainRegistrationEvent> {
+        self.chain_events.subscribe()
+    }
+
+    /// Hands a chain receiver to the receive loop, starting the loop if none is running
+    /// 
+    /// All chains share a single loop that merges their receivers, so the number of
+    /// tasks polling for status updates does not grow with the number of chains.
+    async fn add_receiver(&self, chain_id: ChainId, receiver: mpsc::Receiver<CATStatusUpdate>) {
+        let mut receive_loop = self.receive_loop.lock().await;
+        let receiver = match receive_loop.as_ref() {
+            Some(registrations) => match registrations.send((chain_id.clone(), receiver)) {
+                Ok(()) => {
+                    log("HS", &format!("Added chain '{}' to the running receive loop", chain_id.0));
+                    return;
+                }
+                // The loop has retired, start a new one
+                Err(mpsc::error::SendError((_, receiver))) => receiver,
+            },
+            None => receiver,
+        };
+
+        log("HS", &format!("Starting receive loop with chain '{}'", chain_id.0));
+        let (registrations, registrations_receiver) = mpsc::unbounded_channel();
+        registrations.send((chain_id, receiver)).expect("Receive loop registrations should be open");
+        *receive_loop = Some(registrations);
+
         let node = Arc::new(Mutex::new(self.clone()));
-        let chain_id_str = chain_id.0.clone();
+        let receive_loop = self.receive_loop.clone();
         tokio::spawn(async move {
-            HyperSchedulerNode::process_messages_with_receiver(node, chain_id_str, receiver).await;
+            HyperSchedulerNode::run_receive_loop(node, receive_loop, registrations_receiver).await;
         });
-        log("HS", &format!("Message processing loop for chain '{}' should be started", chain_id.0));
-        
-        Ok(())
     }
 
-    /// Process messages for a specific chain with a given receiver
-    async fn process_messages_with_receiver(node: Arc<Mutex<Self>>, chain_id: String, mut receiver: mpsc::Receiver<CATStatusUpdate>) {
-        // Process messages
-        log("HS", &format!("Starting message processing loop for chain {}", chain_id));
-        while let Some(status_update) = receiver.recv().await {
-            log("HS", &format!("Received status update from chain {}: {:?}", chain_id, status_update));
-            let mut node_guard = node.lock().await;
-            
-            // Process the CAT status proposal
-            if let Err(e) = node_guard.process_cat_status_proposal(
-                status_update.cat_id.clone(),
-                ChainId(chain_id.clone()),
-                status_update.constituent_chains.clone(),
-                status_update.status.clone(),
-            ).await {
-                log("HS", &format!("Failed to process status proposal: {:?}", e));
-                continue;
+    /// Wraps a chain receiver into a stream of status updates tagged with the chain ID
+    fn chain_update_stream(chain_id: ChainId, receiver: mpsc::Receiver<CATStatusUpdate>) -> ChainUpdateStream {
+        Box::pin(stream::unfold((chain_id, receiver), |(chain_id, mut receiver)| async move {
+            match receiver.recv().await {
+                Some(status_update) => Some(((chain_id.clone(), status_update), (chain_id, receiver))),
+                None => {
+                    log("HS", &format!("Message processing ended for chain {}", chain_id.0));
+                    None
+                }
             }
+        }))
+    }
 
-            // Get the current status of the CAT
-            let cat_status = node_guard.get_cat_status(status_update.cat_id.clone()).await;
-            if let Ok(status) = cat_status {
-                // Only send status update if we have a final status (Success or Failure)
-                if status != CATStatus::Pending {
-                    // Send status update to CL
-                    if let Err(e) = node_guard.send_cat_status_update(
-                        status_update.cat_id.clone(),
-                        status_update.constituent_chains.clone(),
-                        match status {
-                            CATStatus::Success => CATStatusLimited::Success,
-                            CATStatus::Failure => CATStatusLimited::Failure,
-                            _ => continue, // Skip if not a final status
-                        },
-                    ).await {
-                        log("HS", &format!("Failed to send status update: {:?}", e));
+    /// Processes the status updates of all chains in a single loop
+    /// 
+    /// The loop merges the receivers of all chains and picks up the receivers of chains
+    /// registered while it runs. It retires once the HIGs of all chains have dropped their
+    /// senders; the registrations are closed under the `receive_loop` lock, so a chain
+    /// registered at the same time starts a new loop instead of being lost.
+    async fn run_receive_loop(
+        node: Arc<Mutex<Self>>,
+        receive_loop: Arc<Mutex<Option<mpsc::UnboundedSender<ChainReceiver>>>>,
+        mut registrations: mpsc::UnboundedReceiver<ChainReceiver>,
+    ) {
+        let mut streams: SelectAll<ChainUpdateStream> = SelectAll::new();
+        let mut registrations_open = true;
+        loop {
+            if streams.is_empty() {
+                let registration = {
+                    let _receive_loop = receive_loop.lock().await;
+                    let registration = registrations.try_recv().ok();
+                    if registration.is_none() {
+                        registrations.close();
                     }
+                    registration
+                };
+                match registration {
+                    Some((chain_id, receiver)) => streams.push(Self::chain_update_stream(chain_id, receiver)),
+                    None => break,
                 }
             }
-        }
-        log("HS", &format!("Message processing loop exiting for chain {}", chain_id));
-    }
-
-    /// Start the message processing loop
-    pub async fn start(node: Arc<Mutex<Self>>) {
-        log("HS", "Starting message processing loop");
-        let chain_ids = {
-            let node_guard = node.lock().await;
-            node_guard.receivers_from_hig.keys().cloned().collect::<Vec<_>>()
-        };
-
-        log("HS", &format!("Starting message processing loop for {} chains: {:?}", chain_ids.len(), chain_ids));
 
-        // Spawn tasks for each chain
-        for chain_id in chain_ids {
-            let node_clone = node.clone();
-            let receiver = {
-                let mut node_guard: tokio::sync::MutexGuard<'_, HyperSchedulerNode> = node_clone.lock().await;
-                node_guard.receivers_from_hig.remove(&chain_id).expect("Receiver not found")
-            };
-            log("HS", &format!("Starting message processing loop for chain '{}'", chain_id));
-            tokio::spawn(async move {
-                Self::process_messages_with_receiver(node_clone, chain_id, receiver).await;
-            });
+            tokio::select! {
+                registration = registrations.recv(), if registrations_open => match registration {
+                    Some((chain_id, receiver)) => {
+                        log("HS", &format!("Starting message processing for chain {}", chain_id.0));
+                        streams.push(Self::chain_update_stream(chain_id, receiver));
+                    }
+                    None => registrations_open = false,
+                },
+                Some((chain_id, status_update)) = streams.next() => {
+                    Self::handle_status_update(&node, chain_id, status_update).await;
+                }
+                else => {}
+            }
         }
+        log("HS", "Receive loop exiting");
     }
 
-    /// Shuts down the node by stopping background tasks and clearing state.
-    /// 
-    /// This method stops the background processing tasks and clears all state
-    /// to prevent state persistence between simulation runs.
-    /// 
-    /// # Arguments
-    /// * `node` - An Arc<Mutex<HyperSchedulerNode>> containing the node instance
-    pub async fn shutdown(node: Arc<Mutex<Self>>) {
-        log("HS", "Shutting down HyperScheduler node");
-        
-        // Clear all state
+    /// Process a status update received from a chain
+    async fn handle_status_update(node: &Arc<Mutex<Self>>, chain_id: ChainId, status_update: CATStatusUpdate) {
+        log("HS", &format!("Received status update from chain {}: {:?}", chain_id.0, status_update));
+        let mut node_guard = node.lock().await;
         {
-            let node_guard = node.lock().await;
             let mut state = node_guard.state.lock().await;
-            
-            // Clear all state
-            state.registered_chains.clear();
-            state.constituent_chains.clear();
-            state.cat_statuses.clear();
-            state.cat_chainwise_statuses.clear();
-        }
-        
-        // Clear receivers separately to avoid borrowing conflict
-        {
-            let mut node_guard = node.lock().await;
-            node_guard.receivers_from_hig.clear();
+            state.latest_block_height = state.latest_block_height.max(status_update.block_height);
         }
         
-        log("HS", "HyperScheduler node shutdown complete");
-    }
-
-    /// Submit a transaction to the confirmation layer
-    pub async fn submit_transaction_to_cl(&mut self, tx: CLTransaction) -> Result<(), String> {
-        log("HS", &format!("submit_transaction called for tx-id={}, transactions={:?}, chain_ids={:?}", 
-            tx.id.0, 
-            tx.transactions.iter().map(|t| t.data.clone()).collect::<Vec<_>>(),
-            tx.constituent_chains.iter().map(|c| c.0.clone()).collect::<Vec<_>>()));
-        if let Some(sender) = &self.sender_to_cl {
-            sender.send(tx).await.map_err(|e| e.to_string())
-        } else {
-            Err("No sender to CL set".to_string())
+        // Process the CAT status proposal in the epoch it was made in
+        let cat = EpochCATId::new(status_update.epoch, status_update.cat_id.clone());
+        if let Err(e) = node_guard.process_epoch_cat_status_proposal(
+            cat.clone(),
+            chain_id,
+            status_update.constituent_chains.clone(),
+            status_update.status.clone(),
+        ).await {
+            log("HS", &format!("Failed to process status proposal: {:?}", e));
+            return;
         }
-    }
-}
 
-#[async_trait]
-impl HyperScheduler for HyperSchedulerNode {
-    async fn get_cat_status(&self, id: CATId) -> Result<CATStatus, HyperSchedulerError> {
-        log("HS", &format!("get_cat_status called for cat-id='{}'", id.0));
-        let state = self.state.lock().await;
-        let result = state.cat_statuses.get(&id).cloned();
-        
-        if let Some(ref status) = result {
-            log("HS", &format!("get_cat_status found status for tx-id='{}': {:?}", id.0, status));
-        } else {
-            log("HS", &format!("get_cat_status did not find status for tx-id='{}'", id.0));
+        // Get the current status of the CAT
+        let cat_status = node_guard.get_cat_status_in_epoch(&cat.cat_id, cat.epoch).await;
+        if let Ok(status) = cat_status {
+            // Only send status update if we have a final status (Success or Failure)
+            let final_status = match status {
+                CATStatus::Success => CATStatusLimited::Success,
+                CATStatus::Failure => node_guard.state.lock().await.failure_status(&cat),
+                _ => return, // Skip if not a final status
+            };
+            // The decision latency elapses on the local clock
+            let latency = {
+                let state = node_guard.state.lock().await;
+                state.clock_skew.real_duration(state.decision_latency.sample())
+            };
+            if latency.is_zero() {
+                // Send status update to CL
+                if let Err(e) = node_guard.send_epoch_cat_status_update(
+                    cat,
+                    status_update.constituent_chains.clone(),
+                    final_status,
+                ).await {
+                    log("HS", &format!("Failed to send status update: {:?}", e));
+                }
+            } else {
+                // Emit the status update after the decision latency without blocking the receive loop
+                log("HS", &format!("Delaying status update for cat-id='{}' by {:?}", status_update.cat_id.0, latency));
+                let mut delayed_node = node_guard.clone();
+                tokio::spawn(async move {
+                    delayed_node.clock.sleep(latency).await;
+                    if let Err(e) = delayed_node.send_epoch_cat_status_update(
+                        cat,
+                        status_update.constituent_chains.clone(),
+                        final_status,
+                    ).await {
+                        log("HS", &format!("Failed to send delayed status update: {:?}", e));
+                    }
+                });
+            }
         }
-        
-        result.ok_or_else(|| HyperSchedulerError::CATNotFound(id))
-    }
-
-    async fn get_pending_cats(&self) -> Result<Vec<CATId>, HyperSchedulerError> {
-        Ok(self.state.lock().await.cat_statuses.keys().cloned().collect())
-    }
-
-    async fn get_registered_chains(&self) -> Result<Vec<ChainId>, HyperSchedulerError> {
-        let state = self.state.lock().await;
-        Ok(state.registered_chains.iter().cloned().collect())
     }
 
-    /// Process a status proposal for a CAT
+    /// Process a status proposal for the CAT of an epoch
     /// 
-    /// cat_id: the ID of the CAT
+    /// cat: the ID of the CAT and the epoch the proposal was made in
     /// this_chain_id: the ID of the chain that is proposing the status
     /// constituent_chains: the IDs of the chains that are part of the CAT
     /// status: the status that the proposing chain is proposing
-    async fn process_cat_status_proposal(
+    pub async fn process_epoch_cat_status_proposal(
         &mut self,
-        cat_id: CATId,
+        cat: EpochCATId,
         this_chain_id: ChainId,
         constituent_chains: Vec<ChainId>,
         status: CATStatusLimited,
     ) -> Result<(), HyperSchedulerError> {
+        let cat_id = cat.cat_id.clone();
+        *self.state.lock().await.count_proposals_received.entry(this_chain_id.clone()).or_insert(0) += 1;
+
         // Validate constituent chains
         if constituent_chains.len() <= 1 {
             return Err(HyperSchedulerError::InvalidCATProposal(
@@ -261,9 +553,17 @@ impl HyperScheduler for HyperSchedulerNode {
 
         log("HS", &format!("process_cat_status_proposal called for cat-id='{}' by chain-id='{}' with status {:?}", cat_id.0, this_chain_id.0, status));
         let mut state = self.state.lock().await;
+
+        // Proposals can only be late, an epoch the HS has not reached yet is a bug of the proposer
+        if cat.epoch > state.epoch {
+            log("HS", &format!("Proposal for cat-id='{}' is from epoch {}, ahead of epoch {}", cat_id.0, cat.epoch, state.epoch));
+            return Err(HyperSchedulerError::FutureEpoch { cat_id, epoch: cat.epoch, current: state.epoch });
+        }
+        let latest_epoch = state.cat_epochs.entry(cat_id.clone()).or_insert(cat.epoch);
+        *latest_epoch = (*latest_epoch).max(cat.epoch);
         
         // Check if this chain has already submitted a proposal
-        if let Some(chain_statuses) = state.cat_chainwise_statuses.get(&cat_id) {
+        if let Some(chain_statuses) = state.cat_chainwise_statuses.get(&cat) {
             if chain_statuses.contains_key(&this_chain_id) {
                 log("HS", &format!("Chain {} has already submitted a proposal for CAT {}, rejecting duplicate", this_chain_id.0, cat_id.0));
                 return Err(HyperSchedulerError::DuplicateProposal(cat_id));
@@ -271,8 +571,8 @@ impl HyperScheduler for HyperSchedulerNode {
         }
 
         // If this is not the first proposal, validate that constituent chains match
-        if state.constituent_chains.contains_key(&cat_id) {
-            let existing_chains = state.constituent_chains.get(&cat_id).unwrap();
+        if state.constituent_chains.contains_key(&cat) {
+            let existing_chains = state.constituent_chains.get(&cat).unwrap();
             if existing_chains != &constituent_chains {
                 log("HS", &format!("Constituent chains mismatch for CAT {}: expected {:?}, got {:?}. Aborting message.", 
                     cat_id.0, existing_chains, constituent_chains));
@@ -284,48 +584,63 @@ impl HyperScheduler for HyperSchedulerNode {
         }
         
         // Store the status proposal - this should never fail as the map is initialized in new()
-        state.cat_chainwise_statuses.entry(cat_id.clone()).or_insert_with(HashMap::new).insert(this_chain_id.clone(), status.clone());
+        state.cat_chainwise_statuses.entry(cat.clone()).or_insert_with(HashMap::new).insert(this_chain_id.clone(), status.clone());
+        let first_proposal_at = *state.cat_first_proposal_at.entry(cat.clone()).or_insert_with(Instant::now);
+        state.cat_proposals.entry(cat.clone()).or_default().push(ReceivedProposal {
+            chain_id: this_chain_id.clone(),
+            status: status.clone(),
+            received_at: Instant::now(),
+        });
+        // Once every constituent chain proposed, record the time from the first to the last proposal
+        let all_proposed = state.cat_chainwise_statuses.get(&cat)
+            .is_some_and(|statuses| constituent_chains.iter().all(|chain_id| statuses.contains_key(chain_id)));
+        if all_proposed {
+            state.cat_first_proposal_at.remove(&cat);
+            state.proposal_spreads.push(first_proposal_at.elapsed());
+        }
         log("HS", &format!("Proposal for {} from {} set to {:?}", cat_id.0, this_chain_id.0, status));
 
         // when reaching this point the cat should not be set to success. this is a severe bug so we should return an error
-        if matches!(state.cat_statuses.get(&cat_id), Some(CATStatus::Success)) {
+        if matches!(state.cat_statuses.get(&cat), Some(CATStatus::Success)) {
             log("HS", &format!("Cat status is already set to success for CAT {}", cat_id.0));
             return Err(HyperSchedulerError::Internal(format!("Cat status is already set to success for CAT {}", cat_id.0)));
         }
 
         // if the cat is already set to failure, we don't need to do anything
-        if matches!(state.cat_statuses.get(&cat_id), Some(CATStatus::Failure)) {
+        if matches!(state.cat_statuses.get(&cat), Some(CATStatus::Failure)) {
             log("HS", &format!("CAT {} is already set to failure, skipping", cat_id.0));
             return Ok(());
-        // if the proposal is failure, we set the status of the cat itself to failure
-        } else if status == CATStatusLimited::Failure {
-            state.cat_statuses.insert(cat_id.clone(), CATStatus::Failure);
+        // if the proposal is failure (or a timeout or rejection), we set the status of the cat itself to failure
+        } else if status != CATStatusLimited::Success {
+            state.cat_statuses.insert(cat.clone(), CATStatus::Failure);
+            state.record_decision();
+            state.cat_decided_at.insert(cat.clone(), Instant::now());
             log("HS", &format!("Status for {} set to {:?}", cat_id.0, CATStatus::Failure));
-            state.constituent_chains.insert(cat_id.clone(), constituent_chains.clone());
+            state.constituent_chains.insert(cat.clone(), constituent_chains.clone());
             log("HS", &format!("Constituent chains for {} set to {:?}", cat_id.0, constituent_chains));
         // if the cat does not exist in cat_statuses, we need to add it
-        } else if !state.cat_statuses.contains_key(&cat_id) {
+        } else if !state.cat_statuses.contains_key(&cat) {
             // since this cat is new, and we need two chains to be successful, we set the status to Pending
-            state.cat_statuses.insert(cat_id.clone(), CATStatus::Pending);
+            state.cat_statuses.insert(cat.clone(), CATStatus::Pending);
             log("HS", &format!("Status for {} set to {:?}", cat_id.0, CATStatus::Pending));
-            state.constituent_chains.insert(cat_id.clone(), constituent_chains.clone());
+            state.constituent_chains.insert(cat.clone(), constituent_chains.clone());
             log("HS", &format!("Constituent chains for {} set to {:?}", cat_id.0, constituent_chains));
         // if the cat proposal already exists, we need to check if all chains have submitted their status
         } else {
             // the cat status should be pending at this point
-            if !matches!(state.cat_statuses.get(&cat_id), Some(CATStatus::Pending)) {
+            if !matches!(state.cat_statuses.get(&cat), Some(CATStatus::Pending)) {
                 return Err(HyperSchedulerError::Internal("Cat status is not pending".to_string()));
             }
             
             // we need to check if the proposed statuses in cat_chainwise_statuses are all present and set to success for all constituent chains
             log("HS", &format!("Checking chain statuses for cat-id='{}'", cat_id.0));
             log("HS", &format!("Constituent chains: {:?}", constituent_chains));
-            log("HS", &format!("Current chain statuses: {:?}", state.cat_chainwise_statuses.get(&cat_id)));
+            log("HS", &format!("Current chain statuses: {:?}", state.cat_chainwise_statuses.get(&cat)));
             
             let mut all_success = true;
             for chain_id in &constituent_chains {
                 log("HS", &format!("Checking status for chain-id='{}'", chain_id.0));
-                let chain_status = state.cat_chainwise_statuses.get(&cat_id).unwrap().get(chain_id);
+                let chain_status = state.cat_chainwise_statuses.get(&cat).unwrap().get(chain_id);
                 log("HS", &format!("Chain status: {:?}", chain_status));
                 if !matches!(chain_status, Some(&CATStatusLimited::Success)) {
                     log("HS", &format!("Chain '{}' is not Success, breaking", chain_id.0));
@@ -337,7 +652,9 @@ impl HyperScheduler for HyperSchedulerNode {
             log("HS", &format!("All chains success: {}", all_success));
             if all_success {
                 // all is well and complete. Set the status of the cat to success
-                state.cat_statuses.insert(cat_id.clone(), CATStatus::Success);
+                state.cat_statuses.insert(cat.clone(), CATStatus::Success);
+                state.record_decision();
+                state.cat_decided_at.insert(cat.clone(), Instant::now());
                 log("HS", &format!("Status for {} set to {:?}", cat_id.0, CATStatus::Success));
             } else {
                 log("HS", "Not all chains are Success, keeping status as Pending");
@@ -347,19 +664,24 @@ impl HyperScheduler for HyperSchedulerNode {
         Ok(())
     }
 
-    async fn send_cat_status_update(&mut self, cat_id: CATId, constituent_chains: Vec<ChainId>, status: CATStatusLimited) -> Result<(), HyperSchedulerError> {
+    /// Send the status update of the CAT of an epoch to the CL
+    pub async fn send_epoch_cat_status_update(&mut self, cat: EpochCATId, constituent_chains: Vec<ChainId>, status: CATStatusLimited) -> Result<(), HyperSchedulerError> {
+        let cat_id = cat.cat_id.clone();
         log("HS", &format!("send_cat_status_update called for CAT {}", cat_id.0));
 
         let data = match status {
             CATStatusLimited::Success => format!("STATUS_UPDATE:Success.CAT_ID:{}", cat_id.0),
             CATStatusLimited::Failure => format!("STATUS_UPDATE:Failure.CAT_ID:{}", cat_id.0),
+            CATStatusLimited::TimedOut => format!("STATUS_UPDATE:TimedOut.CAT_ID:{}", cat_id.0),
+            CATStatusLimited::Rejected => format!("STATUS_UPDATE:Rejected.CAT_ID:{}", cat_id.0),
         };
 
         // Send the status update to the confirmation layer
         if let Some(sender) = &self.sender_to_cl {
             // Create a transaction for each constituent chain
-            let transactions: Vec<Transaction> = constituent_chains.iter().map(|chain_id| {
-                let cl_id = format!("{}.UPDATE", cat_id.0);
+            // The CL ID carries the HS-origin marker that the HIGs verify
+            let transactions = constituent_chains.iter().map(|chain_id| {
+                let cl_id = format!("{}{}", cat_id.0, STATUS_UPDATE_CL_ID_SUFFIX);
                 let tx_id = format!("{}.{}", cl_id, chain_id.0);
                 Transaction::new(
                     TransactionId(tx_id),
@@ -367,19 +689,46 @@ impl HyperScheduler for HyperSchedulerNode {
                     constituent_chains.clone(),
                     data.clone(),
                     CLTransactionId(cl_id),
-                ).expect("Failed to create transaction")
-            }).collect();
-
-            let cl_tx = CLTransaction::new(
-                CLTransactionId(format!("{}.UPDATE", cat_id.0)),
-                constituent_chains.clone(),
-                transactions,
-            ).expect("Failed to create CL transaction");
-            log("HS", &format!("Submitting status update transaction to CL: id={}, chain_ids={:?}", 
-                cl_tx.id.0, cl_tx.constituent_chains.iter().map(|c| c.0.clone()).collect::<Vec<_>>()));
-            sender.send(cl_tx)
-                .await
-                .map_err(|e| HyperSchedulerError::Internal(e.to_string()))?;
+                )
+            }).collect::<Result<Vec<Transaction>, String>>().map_err(HyperSchedulerError::Internal)?;
+
+            // Group the transactions by the delay of their chain, each group is submitted as a part of the CL transaction
+            let mut parts: BTreeMap<Duration, Vec<Transaction>> = BTreeMap::new();
+            {
+                let mut state = self.state.lock().await;
+                state.cat_status_update_submitted_at.entry(cat).or_insert_with(Instant::now);
+                for tx in transactions {
+                    let delay = state.status_update_delays.get(&tx.chain_id).copied().unwrap_or_default();
+                    parts.entry(delay).or_default().push(tx);
+                }
+            }
+
+            for (delay, transactions) in parts {
+                let cl_tx = CLTransaction::new(
+                    CLTransactionId(format!("{}{}", cat_id.0, STATUS_UPDATE_CL_ID_SUFFIX)),
+                    constituent_chains.clone(),
+                    transactions,
+                ).map_err(HyperSchedulerError::Internal)?;
+                if delay.is_zero() {
+                    log("HS", &format!("Submitting status update transaction to CL: id={}, chain_ids={:?}", 
+                        cl_tx.id.0, cl_tx.transactions.iter().map(|tx| tx.chain_id.0.clone()).collect::<Vec<_>>()));
+                    sender.send(cl_tx)
+                        .await
+                        .map_err(|e| HyperSchedulerError::Internal(e.to_string()))?;
+                } else {
+                    // Submit the part after the delay of its chains without blocking the other parts
+                    log("HS", &format!("Delaying status update transaction id={} for chain_ids={:?} by {:?}", 
+                        cl_tx.id.0, cl_tx.transactions.iter().map(|tx| tx.chain_id.0.clone()).collect::<Vec<_>>(), delay));
+                    let sender = sender.clone();
+                    let clock = self.clock.clone();
+                    tokio::spawn(async move {
+                        clock.sleep(delay).await;
+                        if let Err(e) = sender.send(cl_tx).await {
+                            log("HS", &format!("Failed to send delayed status update part: {}", e));
+                        }
+                    });
+                }
+            }
         } else {
             log("HS", "No sender to CL set, cannot send status update");
             return Err(HyperSchedulerError::Internal("No sender to CL set".to_string()));
@@ -387,4 +736,148 @@ impl HyperScheduler for HyperSchedulerNode {
 
         Ok(())
     }
+
+    /// Start the message processing loop
+    pub async fn start(node: Arc<Mutex<Self>>) {
+        log("HS", "Starting message processing loop");
+        let mut node_guard = node.lock().await;
+        let receivers = node_guard.receivers_from_hig.drain().collect::<Vec<_>>();
+
+        log("HS", &format!("Starting message processing loop for {} chains: {:?}", receivers.len(), receivers.iter().map(|(chain_id, _)| chain_id).collect::<Vec<_>>()));
+
+        // Hand the receivers of all chains to the receive loop
+        for (chain_id, receiver) in receivers {
+            node_guard.add_receiver(ChainId(chain_id), receiver).await;
+        }
+    }
+
+    /// Shuts down the node by stopping background tasks and clearing state.
+    /// 
+    /// This method stops the background processing tasks and clears all state
+    /// to prevent state persistence between simulation runs.
+    /// 
+    /// # Arguments
+    /// * `node` - An Arc<Mutex<HyperSchedulerNode>> containing the node instance
+    pub async fn shutdown(node: Arc<Mutex<Self>>) {
+        log("HS", "Shutting down HyperScheduler node");
+        
+        // Clear all state
+        {
+            let node_guard = node.lock().await;
+            let mut state = node_guard.state.lock().await;
+            
+            // Clear all state
+            state.registered_chains.clear();
+            state.constituent_chains.clear();
+            state.cat_statuses.clear();
+            state.cat_chainwise_statuses.clear();
+            state.cat_first_proposal_at.clear();
+            state.cat_proposals.clear();
+            state.count_proposals_received.clear();
+            state.count_decisions = 0;
+            state.decisions_per_block.clear();
+            state.latest_block_height = 0;
+            state.cat_decided_at.clear();
+            state.cat_status_update_submitted_at.clear();
+            state.proposal_spreads.clear();
+            state.decision_latency = DecisionLatency::default();
+            state.clock_skew = ClockSkew::default();
+            state.status_update_delays.clear();
+            state.epoch = 0;
+            state.cat_epochs.clear();
+        }
+        
+        // Clear receivers separately to avoid borrowing conflict
+        {
+            let mut node_guard = node.lock().await;
+            node_guard.receivers_from_hig.clear();
+            // The running receive loop retires once its chains are gone
+            *node_guard.receive_loop.lock().await = None;
+        }
+        
+        log("HS", "HyperScheduler node shutdown complete");
+    }
+
+    /// Submit a transaction to the confirmation layer
+    pub async fn submit_transaction_to_cl(&mut self, tx: CLTransaction) -> Result<(), String> {
+        log("HS", &format!("submit_transaction called for tx-id={}, transactions={:?}, chain_ids={:?}", 
+            tx.id.0, 
+            tx.transactions.iter().map(|t| t.data.clone()).collect::<Vec<_>>(),
+            tx.constituent_chains.iter().map(|c| c.0.clone()).collect::<Vec<_>>()));
+        if let Some(sender) = &self.sender_to_cl {
+            sender.send(tx).await.map_err(|e| e.to_string())
+        } else {
+            Err("No sender to CL set".to_string())
+        }
+    }
+}
+
+#[async_trait]
+impl HyperScheduler for HyperSchedulerNode {
+    async fn get_cat_status(&self, id: CATId) -> Result<CATStatus, HyperSchedulerError> {
+        log("HS", &format!("get_cat_status called for cat-id='{}'", id.0));
+        let state = self.state.lock().await;
+        let result = state.cat_statuses.get(&state.latest_cat(&id)).cloned();
+        
+        if let Some(ref status) = result {
+            log("HS", &format!("get_cat_status found status for tx-id='{}': {:?}", id.0, status));
+        } else {
+            log("HS", &format!("get_cat_status did not find status for tx-id='{}'", id.0));
+        }
+        
+        result.ok_or_else(|| HyperSchedulerError::CATNotFound(id))
+    }
+
+    async fn get_pending_cats(&self) -> Result<Vec<CATId>, HyperSchedulerError> {
+        Ok(self.state.lock().await.cat_statuses.keys().map(|cat| cat.cat_id.clone()).collect())
+    }
+
+    async fn get_pending_cats_for_chain(&self, chain_id: ChainId) -> Result<Vec<PendingCAT>, HyperSchedulerError> {
+        let state = self.state.lock().await;
+        if !state.registered_chains.contains(&chain_id) {
+            return Err(HyperSchedulerError::Internal(format!("Chain {} is not registered", chain_id.0)));
+        }
+        let mut pending_cats: Vec<PendingCAT> = state.cat_statuses.iter()
+            .filter(|(_, status)| **status == CATStatus::Pending)
+            .filter_map(|(cat, _)| {
+                let constituent_chains = state.constituent_chains.get(cat)?;
+                let chain_statuses = state.cat_chainwise_statuses.get(cat);
+                if !constituent_chains.contains(&chain_id) || chain_statuses.is_some_and(|statuses| statuses.contains_key(&chain_id)) {
+                    return None;
+                }
+                Some(PendingCAT {
+                    cat_id: cat.cat_id.clone(),
+                    constituent_chains: constituent_chains.clone(),
+                    proposed_by: constituent_chains.iter()
+                        .filter(|chain| chain_statuses.is_some_and(|statuses| statuses.contains_key(*chain)))
+                        .cloned()
+                        .collect(),
+                    waiting: state.cat_first_proposal_at.get(cat).map(|at| at.elapsed()).unwrap_or_default(),
+                })
+            })
+            .collect();
+        pending_cats.sort_by(|a, b| b.waiting.cmp(&a.waiting).then_with(|| a.cat_id.0.0.cmp(&b.cat_id.0.0)));
+        Ok(pending_cats)
+    }
+
+    async fn get_registered_chains(&self) -> Result<Vec<ChainId>, HyperSchedulerError> {
+        let state = self.state.lock().await;
+        Ok(state.registered_chains.iter().cloned().collect())
+    }
+
+    async fn process_cat_status_proposal(
+        &mut self,
+        cat_id: CATId,
+        this_chain_id: ChainId,
+        constituent_chains: Vec<ChainId>,
+        status: CATStatusLimited,
+    ) -> Result<(), HyperSchedulerError> {
+        let epoch = self.state.lock().await.epoch;
+        self.process_epoch_cat_status_proposal(EpochCATId::new(epoch, cat_id), this_chain_id, constituent_chains, status).await
+    }
+
+    async fn send_cat_status_update(&mut self, cat_id: CATId, constituent_chains: Vec<ChainId>, status: CATStatusLimited) -> Result<(), HyperSchedulerError> {
+        let cat = self.state.lock().await.latest_cat(&cat_id);
+        self.send_epoch_cat_status_update(cat, constituent_chains, status).await
+    }
 } 
\ No newline at end of file
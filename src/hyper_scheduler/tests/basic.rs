@@ -1,5 +1,5 @@
 use crate::{
-    hyper_scheduler::{node::HyperSchedulerNode, HyperScheduler, HyperSchedulerError}, types::{constants, CATId, CATStatus, CATStatusLimited, CATStatusUpdate, CLTransactionId}
+    hyper_scheduler::{node::HyperSchedulerNode, DecisionLatency, EpochCATId, HyperScheduler, HyperSchedulerError}, types::{NetworkSpec, CATId, CATStatus, CATStatusLimited, CATStatusUpdate, ChainId, ChainRegistrationEvent, CLTransactionId}
 };
 use tokio::sync::mpsc;
 use hyperplane::utils::logging;
@@ -18,13 +18,13 @@ async fn setup_hs_node_with_chains() -> (HyperSchedulerNode, mpsc::Sender<CATSta
     // Register both chains
     let (sender_1, receiver_1) = mpsc::channel(1);
     let (sender_2, receiver_2) = mpsc::channel(1);
-    hs_node.register_chain(constants::chain_1(), receiver_1).await.expect("Failed to register chain-1");
-    hs_node.register_chain(constants::chain_2(), receiver_2).await.expect("Failed to register chain-2");
+    hs_node.register_chain(NetworkSpec::test_chain(1), receiver_1).await.expect("Failed to register chain-1");
+    hs_node.register_chain(NetworkSpec::test_chain(2), receiver_2).await.expect("Failed to register chain-2");
 
     // Verify both chains are registered
     let registered_chains = hs_node.get_registered_chains().await.expect("Failed to get registered chains");
-    assert!(registered_chains.contains(&constants::chain_1()));
-    assert!(registered_chains.contains(&constants::chain_2()));
+    assert!(registered_chains.contains(&NetworkSpec::test_chain(1)));
+    assert!(registered_chains.contains(&NetworkSpec::test_chain(2)));
 
     (hs_node, sender_1, sender_2)
 }
@@ -39,19 +39,19 @@ async fn test_receive_cat_for_unregistered_chain() {
 
     // Register chain-1
     let (_sender_1, receiver_1) = mpsc::channel(100);
-    hs_node.register_chain(constants::chain_1(), receiver_1).await.expect("Failed to register chain-1");
+    hs_node.register_chain(NetworkSpec::test_chain(1), receiver_1).await.expect("Failed to register chain-1");
 
     // Create a CAT ID and status update
     let cat_id = CATId(CLTransactionId("test-cat".to_string()));
     let status_proposed = CATStatusLimited::Success;
-    let constituent_chains = vec![constants::chain_1(), constants::chain_2()];
+    let constituent_chains = vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)];
     logging::log("TEST", &format!("Created cat-id='{}' with status: {:?}", cat_id.0, status_proposed));
 
     // Try to process the status proposal directly
     logging::log("TEST", "Processing CAT status proposal...");
     let result = hs_node.process_cat_status_proposal(
         cat_id.clone(),
-        constants::chain_1(),
+        NetworkSpec::test_chain(1),
         constituent_chains.clone(),
         status_proposed.clone()
     ).await;
@@ -79,14 +79,14 @@ async fn test_receive_success_proposal_first_message() {
     // Create a CAT ID and status update
     let cat_id = CATId(CLTransactionId("test-cat".to_string()));
     let status_proposal = CATStatusLimited::Success;
-    let constituent_chains = vec![constants::chain_1(), constants::chain_2()];
+    let constituent_chains = vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)];
     logging::log("TEST", &format!("Created cat-id='{}' with status: {:?}", cat_id.0, status_proposal));
 
     // Process the status proposal directly
     logging::log("TEST", "Processing CAT status proposal...");
     hs_node.process_cat_status_proposal(
         cat_id.clone(),
-        constants::chain_1(),
+        NetworkSpec::test_chain(1),
         constituent_chains.clone(),
         status_proposal.clone()
     ).await.expect("Failed to process status proposal");
@@ -113,14 +113,14 @@ async fn test_receive_failure_proposal_first_message() {
     // Create a CAT ID and status update
     let cat_id = CATId(CLTransactionId("test-cat".to_string()));
     let status_proposed = CATStatusLimited::Failure;
-    let constituent_chains = vec![constants::chain_1(), constants::chain_2()];
+    let constituent_chains = vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)];
     logging::log("TEST", &format!("Created cat-id='{}' with status: {:?}", cat_id.0, status_proposed));
 
     // Process the status proposal directly
     logging::log("TEST", "Processing CAT status proposal...");
     hs_node.process_cat_status_proposal(
         cat_id.clone(),
-        constants::chain_1(),
+        NetworkSpec::test_chain(1),
         constituent_chains.clone(),
         status_proposed.clone()
     ).await.expect("Failed to process status proposal");
@@ -156,12 +156,12 @@ async fn test_duplicate_rejection() {
     // Test proposal behavior
     let cat_id = CATId(CLTransactionId("test-cat".to_string()));
     let status = CATStatusLimited::Success;
-    let constituent_chains = vec![constants::chain_1(), constants::chain_2()];
+    let constituent_chains = vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)];
     
     // First proposal should create a record
     hs_node.process_cat_status_proposal(
         cat_id.clone(),
-        constants::chain_1(),
+        NetworkSpec::test_chain(1),
         constituent_chains.clone(),
         status.clone()
     ).await.expect("Failed to process first proposal");
@@ -169,7 +169,7 @@ async fn test_duplicate_rejection() {
     // Second proposal should be rejected
     let result = hs_node.process_cat_status_proposal(
         cat_id.clone(),
-        constants::chain_1(),
+        NetworkSpec::test_chain(1),
         constituent_chains.clone(),
         status.clone()
     ).await;
@@ -192,14 +192,14 @@ async fn test_process_proposals_for_two_chain_cat() {
     // Create a CAT ID and status update
     let cat_id = CATId(CLTransactionId("test-cat".to_string()));
     let status = CATStatusLimited::Success;
-    let constituent_chains = vec![constants::chain_1(), constants::chain_2()];
+    let constituent_chains = vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)];
     logging::log("TEST", &format!("Created cat-id='{}' with status: {:?}", cat_id.0, status));
 
     // Process status proposal from first chain
     logging::log("TEST", "Processing CAT status proposal from first chain...");
     hs_node.process_cat_status_proposal(
         cat_id.clone(),
-        constants::chain_1(),
+        NetworkSpec::test_chain(1),
         constituent_chains.clone(),
         status.clone()
     ).await.expect("Failed to process first proposal");
@@ -208,7 +208,7 @@ async fn test_process_proposals_for_two_chain_cat() {
     logging::log("TEST", "Processing CAT status proposal from second chain...");
     hs_node.process_cat_status_proposal(
         cat_id.clone(),
-        constants::chain_2(),
+        NetworkSpec::test_chain(2),
         constituent_chains.clone(),
         status.clone()
     ).await.expect("Failed to process second proposal");
@@ -233,7 +233,7 @@ async fn test_cannot_set_success_if_constituent_chains_dont_match() {
     logging::log("TEST", "\n=== Starting test_cannot_set_success_if_constituent_chains_dont_match ===");
     
     let (mut hs_node, _sender_1, _sender_2) = setup_hs_node_with_chains().await;
-    let chain_id_3 = constants::chain_3();
+    let chain_id_3 = NetworkSpec::test_chain(3);
     let cat_id = CATId(CLTransactionId("test-cat".to_string()));
 
     // register also chain-3
@@ -241,19 +241,19 @@ async fn test_cannot_set_success_if_constituent_chains_dont_match() {
     hs_node.register_chain(chain_id_3.clone(), receiver_3).await.expect("Failed to register chain-3");
     
     // First proposal with chains 1 and 2
-    let constituent_chains_1 = vec![constants::chain_1(), constants::chain_2()];
+    let constituent_chains_1 = vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)];
     hs_node.process_cat_status_proposal(
         cat_id.clone(),
-        constants::chain_1(),
+        NetworkSpec::test_chain(1),
         constituent_chains_1.clone(),
         CATStatusLimited::Success
     ).await.expect("Failed to process first proposal");
 
     // Try to set Success with different constituent chains
-    let constituent_chains_2 = vec![constants::chain_2(), chain_id_3.clone()];
+    let constituent_chains_2 = vec![NetworkSpec::test_chain(2), chain_id_3.clone()];
     let result = hs_node.process_cat_status_proposal(
         cat_id.clone(),
-        constants::chain_2(),
+        NetworkSpec::test_chain(2),
         constituent_chains_2,
         CATStatusLimited::Success
     ).await;
@@ -261,8 +261,399 @@ async fn test_cannot_set_success_if_constituent_chains_dont_match() {
     assert!(result.is_err(), "Should not be able to set Success with different constituent chains");
     if let Err(HyperSchedulerError::ConstituentChainsMismatch { expected, received }) = result {
         assert_eq!(expected, constituent_chains_1, "Expected first set of constituent chains");
-        assert_eq!(received, vec![constants::chain_2(), chain_id_3.clone()], "Expected second set of constituent chains");
+        assert_eq!(received, vec![NetworkSpec::test_chain(2), chain_id_3.clone()], "Expected second set of constituent chains");
     } else {
         panic!("Expected ConstituentChainsMismatch error");
     }
-}
\ No newline at end of file
+}
+
+/// Test that the HS emits the status update only after the configured decision latency
+#[tokio::test]
+async fn test_decision_latency_delays_status_update() {
+    logging::log("TEST", "=== Starting test_decision_latency_delays_status_update ===");
+
+    let (sender_to_cl, mut receiver_from_hs) = mpsc::channel(100);
+    let mut hs_node = HyperSchedulerNode::new(sender_to_cl);
+    hs_node.set_decision_latency(DecisionLatency::Constant { value: 0.3 }).await;
+    assert_eq!(hs_node.get_decision_latency().await, DecisionLatency::Constant { value: 0.3 });
+
+    let (sender_1, receiver_1) = mpsc::channel(10);
+    let (sender_2, receiver_2) = mpsc::channel(10);
+    hs_node.register_chain(NetworkSpec::test_chain(1), receiver_1).await.expect("Failed to register chain-1");
+    hs_node.register_chain(NetworkSpec::test_chain(2), receiver_2).await.expect("Failed to register chain-2");
+
+    // Both chains propose Success, so the CAT is decided on the second proposal
+    let cat_id = CATId(CLTransactionId("test-cat".to_string()));
+    let constituent_chains = vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)];
+    for (sender, chain_id) in [(&sender_1, NetworkSpec::test_chain(1)), (&sender_2, NetworkSpec::test_chain(2))] {
+        sender.send(CATStatusUpdate {
+            cat_id: cat_id.clone(),
+            chain_id,
+            status: CATStatusLimited::Success,
+            constituent_chains: constituent_chains.clone(),
+            epoch: 0,
+            block_height: 0,
+        }).await.expect("Failed to send proposal");
+    }
+
+    // The decision is made but the status update is held back
+    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+    assert_eq!(hs_node.get_cat_status(cat_id.clone()).await.unwrap(), CATStatus::Success);
+    assert!(receiver_from_hs.try_recv().is_err(), "Status update should not be sent before the decision latency");
+
+    // After the latency has passed the status update is emitted
+    tokio::time::sleep(tokio::time::Duration::from_millis(400)).await;
+    let cl_tx = receiver_from_hs.try_recv().expect("Status update should be sent after the decision latency");
+    assert_eq!(cl_tx.transactions[0].data, format!("STATUS_UPDATE:Success.CAT_ID:{}", cat_id.0));
+}
+
+/// Test that scaling a decision latency multiplies all of its parameters
+#[test]
+fn test_decision_latency_scaled() {
+    assert_eq!(DecisionLatency::Zero.scaled(2.0), DecisionLatency::Zero);
+    assert_eq!(DecisionLatency::Constant { value: 1.5 }.scaled(2.0), DecisionLatency::Constant { value: 3.0 });
+    assert_eq!(DecisionLatency::Uniform { min: 1.0, max: 2.0 }.scaled(0.5), DecisionLatency::Uniform { min: 0.5, max: 1.0 });
+    assert_eq!(DecisionLatency::Exponential { mean: 4.0 }.scaled(0.25), DecisionLatency::Exponential { mean: 1.0 });
+    assert_eq!(DecisionLatency::Constant { value: 0.5 }.sample(), std::time::Duration::from_millis(500));
+}
+
+/// Test that chain registration and deregistration are broadcast to subscribers
+#[tokio::test]
+async fn test_chain_registration_events() {
+    logging::log("TEST", "=== Starting test_chain_registration_events ===");
+
+    let mut hs_node = setup_hs_node();
+    let mut events = hs_node.subscribe_chain_events();
+
+    // Register and deregister chain-1
+    let (_sender_1, receiver_1) = mpsc::channel(100);
+    hs_node.register_chain(NetworkSpec::test_chain(1), receiver_1).await.expect("Failed to register chain-1");
+    assert_eq!(events.try_recv().unwrap(), ChainRegistrationEvent::Registered(NetworkSpec::test_chain(1)));
+    hs_node.deregister_chain(NetworkSpec::test_chain(1)).await.expect("Failed to deregister chain-1");
+    assert_eq!(events.try_recv().unwrap(), ChainRegistrationEvent::Deregistered(NetworkSpec::test_chain(1)));
+    assert!(hs_node.get_registered_chains().await.unwrap().is_empty(), "chain-1 should no longer be registered");
+
+    // Deregistering an unknown chain fails without an event
+    assert!(hs_node.deregister_chain(NetworkSpec::test_chain(2)).await.is_err(), "Deregistering an unknown chain should fail");
+    assert!(events.try_recv().is_err(), "No event should be emitted for a failed deregistration");
+
+    logging::log("TEST", "=== Test completed successfully ===");
+}
+
+/// Test listing the pending CATs that await a chain's proposal
+/// - A CAT proposed by chain-1 awaits chain-2 only
+/// - Once chain-2 proposed, no CAT awaits either chain
+#[tokio::test]
+async fn test_get_pending_cats_for_chain() {
+    logging::log("TEST", "\n=== Starting test_get_pending_cats_for_chain ===");
+
+    let (mut hs_node, _sender_1, _sender_2) = setup_hs_node_with_chains().await;
+    let cat_id = CATId(CLTransactionId("test-cat".to_string()));
+    let constituent_chains = vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)];
+
+    hs_node.process_cat_status_proposal(cat_id.clone(), NetworkSpec::test_chain(1), constituent_chains.clone(), CATStatusLimited::Success)
+        .await.expect("Failed to process status proposal");
+    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+
+    assert!(hs_node.get_pending_cats_for_chain(NetworkSpec::test_chain(1)).await.unwrap().is_empty(), "chain-1 already proposed");
+    let awaiting_chain_2 = hs_node.get_pending_cats_for_chain(NetworkSpec::test_chain(2)).await.unwrap();
+    assert_eq!(awaiting_chain_2.len(), 1);
+    assert_eq!(awaiting_chain_2[0].cat_id, cat_id);
+    assert_eq!(awaiting_chain_2[0].proposed_by, vec![NetworkSpec::test_chain(1)]);
+    assert!(awaiting_chain_2[0].waiting >= std::time::Duration::from_millis(10), "The wait should start at the first proposal");
+
+    hs_node.process_cat_status_proposal(cat_id.clone(), NetworkSpec::test_chain(2), constituent_chains, CATStatusLimited::Success)
+        .await.expect("Failed to process status proposal");
+    assert!(hs_node.get_pending_cats_for_chain(NetworkSpec::test_chain(2)).await.unwrap().is_empty(), "The CAT is decided");
+
+    // Unregistered chains cannot be queried
+    assert!(hs_node.get_pending_cats_for_chain(NetworkSpec::test_chain(3)).await.is_err());
+
+    logging::log("TEST", "=== Test completed successfully ===");
+}
+
+/// Test that the status update for a delayed chain is submitted as a separate, later part
+#[tokio::test]
+async fn test_status_update_delay_per_chain() {
+    logging::log("TEST", "=== Starting test_status_update_delay_per_chain ===");
+
+    let (sender_to_cl, mut receiver_from_hs) = mpsc::channel(100);
+    let mut hs_node = HyperSchedulerNode::new(sender_to_cl);
+    hs_node.set_status_update_delay(NetworkSpec::test_chain(2), std::time::Duration::from_millis(200)).await;
+    assert_eq!(hs_node.get_status_update_delay(&NetworkSpec::test_chain(1)).await, std::time::Duration::ZERO);
+    assert_eq!(hs_node.get_status_update_delay(&NetworkSpec::test_chain(2)).await, std::time::Duration::from_millis(200));
+
+    let cat_id = CATId(CLTransactionId("test-cat".to_string()));
+    let constituent_chains = vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)];
+    hs_node.send_cat_status_update(cat_id.clone(), constituent_chains.clone(), CATStatusLimited::Success)
+        .await.expect("Failed to send status update");
+
+    // The part for chain-1 is submitted right away
+    let first_part = receiver_from_hs.try_recv().expect("The status update for chain-1 should be sent immediately");
+    assert_eq!(first_part.constituent_chains, constituent_chains);
+    assert_eq!(first_part.transactions.len(), 1);
+    assert_eq!(first_part.transactions[0].chain_id, NetworkSpec::test_chain(1));
+    assert!(receiver_from_hs.try_recv().is_err(), "The status update for chain-2 should be delayed");
+
+    // The part for chain-2 follows after its delay, under the same CL ID
+    tokio::time::sleep(tokio::time::Duration::from_millis(300)).await;
+    let second_part = receiver_from_hs.try_recv().expect("The status update for chain-2 should be sent after its delay");
+    assert_eq!(second_part.id, first_part.id);
+    assert_eq!(second_part.transactions.len(), 1);
+    assert_eq!(second_part.transactions[0].chain_id, NetworkSpec::test_chain(2));
+
+    logging::log("TEST", "=== Test completed successfully ===");
+}
+
+/// Test that the HS records when it submitted the status update of a decided CAT
+/// - No submission time is known before the status update is sent
+/// - The submission time is taken when the update is handed to the CL, not after per-chain status update delays
+#[tokio::test]
+async fn test_status_update_submission_time() {
+    logging::log("TEST", "=== Starting test_status_update_submission_time ===");
+
+    let (sender_to_cl, _receiver_from_hs) = mpsc::channel(100);
+    let mut hs_node = HyperSchedulerNode::new(sender_to_cl);
+    hs_node.set_status_update_delay(NetworkSpec::test_chain(2), std::time::Duration::from_secs(10)).await;
+
+    let cat_id = CATId(CLTransactionId("test-cat".to_string()));
+    let constituent_chains = vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)];
+    assert_eq!(hs_node.get_status_update_submission_time(&cat_id).await, None);
+
+    let before = std::time::Instant::now();
+    hs_node.send_cat_status_update(cat_id.clone(), constituent_chains.clone(), CATStatusLimited::Success)
+        .await.expect("Failed to send status update");
+    let submitted_at = hs_node.get_status_update_submission_time(&cat_id).await.expect("The submission time should be recorded");
+    assert!(submitted_at >= before);
+    assert!(submitted_at.duration_since(before) < std::time::Duration::from_secs(10), "The delay of chain-2 should not count towards the submission time");
+
+    logging::log("TEST", "=== Test completed successfully ===");
+}
+
+/// Test the internal processing metrics of the HS
+/// - Proposals are counted per chain
+/// - A decided CAT counts as a decision and records the time between its first and last proposal
+/// - A CAT still waiting on a chain shows up in the pending CAT ages
+#[tokio::test]
+async fn test_hs_metrics() {
+    logging::log("TEST", "\n=== Starting test_hs_metrics ===");
+
+    let (mut hs_node, _sender_1, _sender_2) = setup_hs_node_with_chains().await;
+    assert_eq!(hs_node.get_metrics().await, crate::hyper_scheduler::HyperSchedulerMetrics::default());
+
+    let constituent_chains = vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)];
+    let decided_cat = CATId(CLTransactionId("decided-cat".to_string()));
+    let pending_cat = CATId(CLTransactionId("pending-cat".to_string()));
+
+    hs_node.process_cat_status_proposal(decided_cat.clone(), NetworkSpec::test_chain(1), constituent_chains.clone(), CATStatusLimited::Success)
+        .await.expect("Failed to process status proposal");
+    hs_node.process_cat_status_proposal(pending_cat.clone(), NetworkSpec::test_chain(1), constituent_chains.clone(), CATStatusLimited::Success)
+        .await.expect("Failed to process status proposal");
+    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    hs_node.process_cat_status_proposal(decided_cat.clone(), NetworkSpec::test_chain(2), constituent_chains.clone(), CATStatusLimited::Success)
+        .await.expect("Failed to process status proposal");
+
+    let metrics = hs_node.get_metrics().await;
+    assert_eq!(metrics.proposals_received.get(&NetworkSpec::test_chain(1)), Some(&2));
+    assert_eq!(metrics.proposals_received.get(&NetworkSpec::test_chain(2)), Some(&1));
+    assert_eq!(metrics.decisions, 1);
+    assert_eq!(metrics.proposal_spreads.len(), 1);
+    assert!(metrics.proposal_spreads[0] >= std::time::Duration::from_millis(10), "The spread should cover the time between both proposals");
+    assert_eq!(metrics.pending_cat_ages.len(), 1, "Only the pending CAT should have an age");
+
+    logging::log("TEST", "=== Test completed successfully ===");
+}
+
+/// Test that the receive loop processes proposals from many chains
+/// - Every chain's receiver is merged into the same loop
+/// - A chain registered after the first proposals arrived is picked up by the running loop
+/// - A new loop starts once the HIGs of all chains have dropped their senders
+#[tokio::test]
+async fn test_receive_loop_with_many_chains() {
+    logging::log("TEST", "\n=== Starting test_receive_loop_with_many_chains ===");
+
+    let mut hs_node = setup_hs_node();
+    let chains: Vec<ChainId> = (1..=16).map(|i| ChainId(format!("chain-{}", i))).collect();
+    let mut senders = Vec::new();
+    for chain_id in &chains[..15] {
+        let (sender, receiver) = mpsc::channel(10);
+        hs_node.register_chain(chain_id.clone(), receiver).await.expect("Failed to register chain");
+        senders.push((chain_id.clone(), sender));
+    }
+
+    // All but the last chain propose before the last one is registered
+    let cat_id = CATId(CLTransactionId("many-chains-cat".to_string()));
+    for (chain_id, sender) in &senders {
+        sender.send(CATStatusUpdate {
+            cat_id: cat_id.clone(),
+            chain_id: chain_id.clone(),
+            status: CATStatusLimited::Success,
+            constituent_chains: chains.clone(),
+            epoch: 0,
+            block_height: 0,
+        }).await.expect("Failed to send proposal");
+    }
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    assert_eq!(hs_node.get_cat_status(cat_id.clone()).await.unwrap(), CATStatus::Pending);
+    assert_eq!(hs_node.get_metrics().await.proposals_received.values().sum::<u64>(), 15);
+
+    let (sender_16, receiver_16) = mpsc::channel(10);
+    hs_node.register_chain(chains[15].clone(), receiver_16).await.expect("Failed to register chain");
+    sender_16.send(CATStatusUpdate {
+        cat_id: cat_id.clone(),
+        chain_id: chains[15].clone(),
+        status: CATStatusLimited::Success,
+        constituent_chains: chains.clone(),
+        epoch: 0,
+        block_height: 0,
+    }).await.expect("Failed to send proposal");
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    assert_eq!(hs_node.get_cat_status(cat_id.clone()).await.unwrap(), CATStatus::Success);
+
+    // Once all senders are gone the loop retires, and a newly registered chain starts a new one
+    drop(senders);
+    drop(sender_16);
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    let late_chain = ChainId("chain-17".to_string());
+    let (sender_17, receiver_17) = mpsc::channel(10);
+    hs_node.register_chain(late_chain.clone(), receiver_17).await.expect("Failed to register chain");
+    sender_17.send(CATStatusUpdate {
+        cat_id: CATId(CLTransactionId("late-cat".to_string())),
+        chain_id: late_chain.clone(),
+        status: CATStatusLimited::Failure,
+        constituent_chains: vec![chains[0].clone(), late_chain.clone()],
+        epoch: 0,
+        block_height: 0,
+    }).await.expect("Failed to send proposal");
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    assert_eq!(hs_node.get_metrics().await.proposals_received.get(&late_chain), Some(&1));
+
+    logging::log("TEST", "=== Test completed successfully ===");
+}
+
+/// Test that CAT IDs are scoped by epoch
+/// - A CAT ID can be reused in a later epoch without inheriting the status of the earlier CAT
+/// - A late proposal of the earlier epoch resolves the CAT of its own epoch, also through the receive loop
+/// - A late proposal for a decided CAT of the earlier epoch does not start a new CAT
+/// - A proposal from an epoch the HS has not reached is rejected
+#[tokio::test]
+async fn test_cat_id_reuse_across_epochs() {
+    logging::log("TEST", "\n=== Starting test_cat_id_reuse_across_epochs ===");
+
+    let (mut hs_node, _sender_1, sender_2) = setup_hs_node_with_chains().await;
+    assert_eq!(hs_node.get_epoch().await, 0);
+
+    let constituent_chains = vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)];
+    let decided_cat = CATId(CLTransactionId("decided-cat".to_string()));
+    let pending_cat = CATId(CLTransactionId("pending-cat".to_string()));
+    hs_node.process_cat_status_proposal(decided_cat.clone(), NetworkSpec::test_chain(1), constituent_chains.clone(), CATStatusLimited::Failure)
+        .await.expect("Failed to process status proposal");
+    hs_node.process_cat_status_proposal(pending_cat.clone(), NetworkSpec::test_chain(1), constituent_chains.clone(), CATStatusLimited::Success)
+        .await.expect("Failed to process status proposal");
+    assert_eq!(hs_node.get_cat_status(decided_cat.clone()).await.unwrap(), CATStatus::Failure);
+
+    assert_eq!(hs_node.advance_epoch().await, 1);
+
+    // Both IDs start new CATs in the new epoch
+    for cat_id in [&decided_cat, &pending_cat] {
+        hs_node.process_cat_status_proposal(cat_id.clone(), NetworkSpec::test_chain(1), constituent_chains.clone(), CATStatusLimited::Success)
+            .await.expect("Reusing a CAT ID in a new epoch should be accepted");
+        assert_eq!(hs_node.get_cat_status(cat_id.clone()).await.unwrap(), CATStatus::Pending);
+        assert_eq!(hs_node.get_cat_epoch(cat_id).await, Some(1));
+    }
+    assert_eq!(hs_node.get_cat_status_in_epoch(&decided_cat, 0).await.unwrap(), CATStatus::Failure);
+
+    // The late proposal of the earlier run reaches the pending CAT of its epoch through the receive loop
+    sender_2.send(CATStatusUpdate {
+        cat_id: pending_cat.clone(),
+        chain_id: NetworkSpec::test_chain(2),
+        status: CATStatusLimited::Success,
+        constituent_chains: constituent_chains.clone(),
+        epoch: 0,
+        block_height: 0,
+    }).await.expect("Failed to send proposal");
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    assert_eq!(hs_node.get_cat_status_in_epoch(&pending_cat, 0).await.unwrap(), CATStatus::Success);
+    assert_eq!(hs_node.get_cat_status(pending_cat.clone()).await.unwrap(), CATStatus::Pending);
+
+    // A late proposal for the decided CAT of the earlier epoch leaves the new CAT alone
+    hs_node.process_epoch_cat_status_proposal(EpochCATId::new(0, decided_cat.clone()), NetworkSpec::test_chain(2), constituent_chains.clone(), CATStatusLimited::Success)
+        .await.expect("Failed to process late status proposal");
+    assert_eq!(hs_node.get_cat_status_in_epoch(&decided_cat, 0).await.unwrap(), CATStatus::Failure);
+    assert_eq!(hs_node.get_cat_status(decided_cat.clone()).await.unwrap(), CATStatus::Pending);
+    assert_eq!(hs_node.get_cat_proposals(&decided_cat).await.len(), 1);
+
+    // An epoch ahead of the HS is rejected
+    let result = hs_node.process_epoch_cat_status_proposal(EpochCATId::new(2, pending_cat.clone()), NetworkSpec::test_chain(2), constituent_chains.clone(), CATStatusLimited::Success).await;
+    assert!(matches!(result, Err(HyperSchedulerError::FutureEpoch { epoch: 2, current: 1, .. })), "Expected FutureEpoch, got {:?}", result);
+    assert_eq!(hs_node.get_cat_status(pending_cat.clone()).await.unwrap(), CATStatus::Pending);
+
+    logging::log("TEST", "=== Test completed successfully ===");
+}
+
+/// Test that the HS records the proposals it accepted for a CAT
+/// - The proposals are listed per chain in order of arrival
+/// - A rejected duplicate proposal is not recorded
+#[tokio::test]
+async fn test_cat_proposals_recorded() {
+    logging::log("TEST", "=== Starting test_cat_proposals_recorded ===");
+
+    let (mut hs_node, _sender_1, _sender_2) = setup_hs_node_with_chains().await;
+    let cat_id = CATId(CLTransactionId("test-cat".to_string()));
+    let constituent_chains = vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)];
+    assert!(hs_node.get_cat_proposals(&cat_id).await.is_empty());
+
+    hs_node.process_cat_status_proposal(cat_id.clone(), NetworkSpec::test_chain(2), constituent_chains.clone(), CATStatusLimited::Success)
+        .await.expect("Failed to process first proposal");
+    let result = hs_node.process_cat_status_proposal(cat_id.clone(), NetworkSpec::test_chain(2), constituent_chains.clone(), CATStatusLimited::Failure).await;
+    assert!(matches!(result, Err(HyperSchedulerError::DuplicateProposal(_))));
+    hs_node.process_cat_status_proposal(cat_id.clone(), NetworkSpec::test_chain(1), constituent_chains.clone(), CATStatusLimited::Failure)
+        .await.expect("Failed to process second proposal");
+
+    let proposals = hs_node.get_cat_proposals(&cat_id).await;
+    let summary: Vec<(ChainId, CATStatusLimited)> = proposals.iter().map(|p| (p.chain_id.clone(), p.status.clone())).collect();
+    assert_eq!(summary, vec![
+        (NetworkSpec::test_chain(2), CATStatusLimited::Success),
+        (NetworkSpec::test_chain(1), CATStatusLimited::Failure),
+    ]);
+    assert!(proposals[1].received_at >= proposals[0].received_at);
+
+    logging::log("TEST", "=== Test completed successfully ===");
+}
+
+/// Test that the HS counts its decisions per CL block height
+/// - A decision is attributed to the highest block height the HIGs reported so far
+/// - A proposal from a chain that lags behind does not move the decisions back to an earlier block
+#[tokio::test]
+async fn test_hs_decisions_per_block() {
+    logging::log("TEST", "\n=== Starting test_hs_decisions_per_block ===");
+
+    let (hs_node, sender_1, sender_2) = setup_hs_node_with_chains().await;
+    let constituent_chains = vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)];
+
+    // Each proposal is sent with the block height its chain had processed
+    let proposals = [
+        ("cat-a", &sender_1, NetworkSpec::test_chain(1), CATStatusLimited::Success, 3),
+        ("cat-a", &sender_2, NetworkSpec::test_chain(2), CATStatusLimited::Success, 4),
+        ("cat-b", &sender_1, NetworkSpec::test_chain(1), CATStatusLimited::Failure, 6),
+        ("cat-c", &sender_1, NetworkSpec::test_chain(1), CATStatusLimited::Success, 6),
+        ("cat-c", &sender_2, NetworkSpec::test_chain(2), CATStatusLimited::Success, 5),
+    ];
+    for (cat_id, sender, chain_id, status, block_height) in proposals {
+        sender.send(CATStatusUpdate {
+            cat_id: CATId(CLTransactionId(cat_id.to_string())),
+            chain_id,
+            status,
+            constituent_chains: constituent_chains.clone(),
+            epoch: 0,
+            block_height,
+        }).await.expect("Failed to send proposal");
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    }
+
+    let metrics = hs_node.get_metrics().await;
+    assert_eq!(metrics.decisions, 3);
+    assert_eq!(metrics.decisions_per_block, [(4, 1), (6, 2)].into_iter().collect());
+
+    logging::log("TEST", "=== Test completed successfully ===");
+}
@@ -0,0 +1,95 @@
+use std::sync::Arc;
+use tokio::sync::{mpsc, Mutex};
+use tokio::time::{sleep, Duration};
+use crate::hyper_ig::HyperIG;
+use crate::hyper_ig::mock::{Misbehavior, MockHyperIG};
+use crate::hyper_scheduler::{node::HyperSchedulerNode, HyperScheduler};
+use crate::types::{NetworkSpec, CATId, CATStatus, CATStatusLimited, CLTransaction, CLTransactionId, Transaction, TransactionId};
+use hyperplane::utils::logging;
+
+/// Starts a HS node with a mock HIG for chain-1 and chain-2
+/// 
+/// # Returns
+/// The HS node, the mock HIGs and the receiver of the HS's status updates to the CL
+async fn setup_hs_with_mock_higs(mock_2_misbehavior: Option<Misbehavior>) -> (Arc<Mutex<HyperSchedulerNode>>, MockHyperIG, MockHyperIG, mpsc::Receiver<CLTransaction>) {
+    let (sender_to_cl, receiver_from_hs) = mpsc::channel(100);
+    let mut hs_node = HyperSchedulerNode::new(sender_to_cl);
+
+    let (sender_1, receiver_1) = mpsc::channel(100);
+    let (sender_2, receiver_2) = mpsc::channel(100);
+    hs_node.register_chain(NetworkSpec::test_chain(1), receiver_1).await.expect("Failed to register chain-1");
+    hs_node.register_chain(NetworkSpec::test_chain(2), receiver_2).await.expect("Failed to register chain-2");
+    let hs_node = Arc::new(Mutex::new(hs_node));
+    HyperSchedulerNode::start(hs_node.clone()).await;
+
+    let mock_1 = MockHyperIG::new(NetworkSpec::test_chain(1), sender_1);
+    let mut mock_2 = MockHyperIG::new(NetworkSpec::test_chain(2), sender_2);
+    if let Some(misbehavior) = mock_2_misbehavior {
+        mock_2 = mock_2.with_misbehavior(misbehavior);
+    }
+    (hs_node, mock_1, mock_2, receiver_from_hs)
+}
+
+/// Creates the part of a CAT destined for the given chain
+fn cat_transaction(cat_id: &str, chain_index: usize) -> Transaction {
+    let chain_id = if chain_index == 1 { NetworkSpec::test_chain(1) } else { NetworkSpec::test_chain(2) };
+    Transaction::new(
+        TransactionId(format!("{}:tx", cat_id)),
+        chain_id,
+        vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)],
+        "CAT.credit 1 100".to_string(),
+        CLTransactionId(cat_id.to_string()),
+    ).expect("Failed to create transaction")
+}
+
+/// Tests that the HS decides Success when both mock HIGs propose Success for a processed CAT
+#[tokio::test]
+async fn test_mock_higs_cat_success() {
+    logging::log("TEST", "=== Starting test_mock_higs_cat_success ===");
+    let (hs_node, mut mock_1, mut mock_2, _receiver_from_hs) = setup_hs_with_mock_higs(None).await;
+
+    mock_1.process_transaction(cat_transaction("cat-1", 1)).await.expect("Failed to process CAT on chain-1");
+    mock_2.process_transaction(cat_transaction("cat-1", 2)).await.expect("Failed to process CAT on chain-2");
+    sleep(Duration::from_millis(100)).await;
+
+    let status = hs_node.lock().await.get_cat_status(CATId(CLTransactionId("cat-1".to_string()))).await.expect("Failed to get CAT status");
+    assert_eq!(status, CATStatus::Success);
+    assert_eq!(mock_1.get_sent_proposals().await.len(), 1);
+    assert_eq!(mock_2.get_sent_proposals().await.len(), 1);
+}
+
+/// Tests that the HS decides Failure when a mock HIG flips its scripted Success proposal
+#[tokio::test]
+async fn test_mock_higs_scripted_flipped_status() {
+    logging::log("TEST", "=== Starting test_mock_higs_scripted_flipped_status ===");
+    let (hs_node, mock_1, mock_2, _receiver_from_hs) = setup_hs_with_mock_higs(Some(Misbehavior::FlipStatus)).await;
+    let cat_id = CATId(CLTransactionId("cat-1".to_string()));
+    let chains = vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)];
+
+    let mock_1 = mock_1.propose_at(Duration::from_millis(10), cat_id.clone(), CATStatusLimited::Success, chains.clone());
+    let mock_2 = mock_2.propose_at(Duration::from_millis(50), cat_id.clone(), CATStatusLimited::Success, chains);
+    let script_1 = mock_1.run_script();
+    let script_2 = mock_2.run_script();
+    script_1.await.expect("Script of chain-1 failed");
+    script_2.await.expect("Script of chain-2 failed");
+    sleep(Duration::from_millis(100)).await;
+
+    let status = hs_node.lock().await.get_cat_status(cat_id).await.expect("Failed to get CAT status");
+    assert_eq!(status, CATStatus::Failure);
+    assert_eq!(mock_2.get_sent_proposals().await[0].status, CATStatusLimited::Failure);
+}
+
+/// Tests that the HS keeps a CAT pending when a mock HIG drops its proposals
+#[tokio::test]
+async fn test_mock_higs_dropped_proposal() {
+    logging::log("TEST", "=== Starting test_mock_higs_dropped_proposal ===");
+    let (hs_node, mut mock_1, mut mock_2, _receiver_from_hs) = setup_hs_with_mock_higs(Some(Misbehavior::DropProposals)).await;
+
+    mock_1.process_transaction(cat_transaction("cat-1", 1)).await.expect("Failed to process CAT on chain-1");
+    mock_2.process_transaction(cat_transaction("cat-1", 2)).await.expect("Failed to process CAT on chain-2");
+    sleep(Duration::from_millis(100)).await;
+
+    let status = hs_node.lock().await.get_cat_status(CATId(CLTransactionId("cat-1".to_string()))).await.expect("Failed to get CAT status");
+    assert_eq!(status, CATStatus::Pending);
+    assert!(mock_2.get_sent_proposals().await.is_empty());
+}
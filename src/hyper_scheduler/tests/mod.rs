@@ -1,3 +1,4 @@
 #[cfg(test)]
 mod basic;
 mod shutdown;
+mod mock_hig;
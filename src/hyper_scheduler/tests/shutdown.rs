@@ -1,8 +1,8 @@
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use tokio::sync::mpsc;
-use hyperplane::hyper_scheduler::node::HyperSchedulerNode;
-use hyperplane::types::{CATId, CLTransaction, CLTransactionId, constants, cat::CATStatus};
+use hyperplane::hyper_scheduler::{node::HyperSchedulerNode, EpochCATId};
+use hyperplane::types::{CATId, CLTransaction, CLTransactionId, NetworkSpec, cat::CATStatus};
 use hyperplane::utils::logging;
 
 /// Tests that the HS node shutdown functionality works correctly:
@@ -31,18 +31,18 @@ async fn test_hs_node_shutdown() {
         let node_guard = node.lock().await;
         let mut state = node_guard.state.lock().await;
         
-        // Add some test data using constants
-        state.registered_chains.insert(constants::chain_1());
-        state.registered_chains.insert(constants::chain_2());
+        // Add some test data on the test chains
+        state.registered_chains.insert(NetworkSpec::test_chain(1));
+        state.registered_chains.insert(NetworkSpec::test_chain(2));
         
-        let cat_id = CATId(CLTransactionId("test_cat_1".to_string()));
-        state.constituent_chains.insert(cat_id.clone(), vec![constants::chain_1(), constants::chain_2()]);
+        let cat_id = EpochCATId::new(0, CATId(CLTransactionId("test_cat_1".to_string())));
+        state.constituent_chains.insert(cat_id.clone(), vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)]);
         state.cat_statuses.insert(cat_id.clone(), CATStatus::Pending);
         state.cat_chainwise_statuses.insert(cat_id.clone(), std::collections::HashMap::new());
         
         // Verify state exists
-        assert!(state.registered_chains.contains(&constants::chain_1()));
-        assert!(state.registered_chains.contains(&constants::chain_2()));
+        assert!(state.registered_chains.contains(&NetworkSpec::test_chain(1)));
+        assert!(state.registered_chains.contains(&NetworkSpec::test_chain(2)));
         assert!(state.constituent_chains.contains_key(&cat_id));
         assert!(state.cat_statuses.contains_key(&cat_id));
         assert!(state.cat_chainwise_statuses.contains_key(&cat_id));
@@ -95,8 +95,8 @@ async fn test_hs_node_shutdown_multiple_times() {
         logging::log("TEST", "Adding test state...");
         let node_guard = node.lock().await;
         let mut state = node_guard.state.lock().await;
-        state.registered_chains.insert(constants::chain_1());
-        state.registered_chains.insert(constants::chain_2());
+        state.registered_chains.insert(NetworkSpec::test_chain(1));
+        state.registered_chains.insert(NetworkSpec::test_chain(2));
         logging::log("TEST", "✓ Test state added");
     }
     
@@ -148,10 +148,10 @@ async fn test_hs_node_restart_after_shutdown() {
         logging::log("TEST", "Adding initial test state...");
         let node_guard = node.lock().await;
         let mut state = node_guard.state.lock().await;
-        state.registered_chains.insert(constants::chain_1());
+        state.registered_chains.insert(NetworkSpec::test_chain(1));
         
-        let cat_id = CATId(CLTransactionId("test_cat_1".to_string()));
-        state.constituent_chains.insert(cat_id.clone(), vec![constants::chain_1()]);
+        let cat_id = EpochCATId::new(0, CATId(CLTransactionId("test_cat_1".to_string())));
+        state.constituent_chains.insert(cat_id.clone(), vec![NetworkSpec::test_chain(1)]);
         state.cat_statuses.insert(cat_id.clone(), CATStatus::Pending);
         logging::log("TEST", "✓ Initial state added");
     }
@@ -180,10 +180,10 @@ async fn test_hs_node_restart_after_shutdown() {
         logging::log("TEST", "Adding new test state after restart...");
         let node_guard = node.lock().await;
         let mut state = node_guard.state.lock().await;
-        state.registered_chains.insert(constants::chain_2());
+        state.registered_chains.insert(NetworkSpec::test_chain(2));
         
-        let cat_id = CATId(CLTransactionId("test_cat_2".to_string()));
-        state.constituent_chains.insert(cat_id.clone(), vec![constants::chain_2()]);
+        let cat_id = EpochCATId::new(0, CATId(CLTransactionId("test_cat_2".to_string())));
+        state.constituent_chains.insert(cat_id.clone(), vec![NetworkSpec::test_chain(2)]);
         state.cat_statuses.insert(cat_id.clone(), CATStatus::Success);
         logging::log("TEST", "✓ New state added after restart");
     }
@@ -195,15 +195,15 @@ async fn test_hs_node_restart_after_shutdown() {
         let state = node_guard.state.lock().await;
         
         // Verify new state exists
-        assert!(state.registered_chains.contains(&constants::chain_2()));
-        assert!(state.constituent_chains.contains_key(&CATId(CLTransactionId("test_cat_2".to_string()))));
-        assert!(state.cat_statuses.contains_key(&CATId(CLTransactionId("test_cat_2".to_string()))));
+        assert!(state.registered_chains.contains(&NetworkSpec::test_chain(2)));
+        assert!(state.constituent_chains.contains_key(&EpochCATId::new(0, CATId(CLTransactionId("test_cat_2".to_string())))));
+        assert!(state.cat_statuses.contains_key(&EpochCATId::new(0, CATId(CLTransactionId("test_cat_2".to_string())))));
         logging::log("TEST", "✓ New state exists");
         
         // Verify old state doesn't persist
-        assert!(!state.registered_chains.contains(&constants::chain_1()));
-        assert!(!state.constituent_chains.contains_key(&CATId(CLTransactionId("test_cat_1".to_string()))));
-        assert!(!state.cat_statuses.contains_key(&CATId(CLTransactionId("test_cat_1".to_string()))));
+        assert!(!state.registered_chains.contains(&NetworkSpec::test_chain(1)));
+        assert!(!state.constituent_chains.contains_key(&EpochCATId::new(0, CATId(CLTransactionId("test_cat_1".to_string())))));
+        assert!(!state.cat_statuses.contains_key(&EpochCATId::new(0, CATId(CLTransactionId("test_cat_1".to_string())))));
         logging::log("TEST", "✓ Old state doesn't persist");
     }
     
@@ -4,6 +4,8 @@ pub mod hyper_scheduler;
 pub mod hyper_ig;
 pub mod utils;
 pub mod mock_vm;
+#[cfg(feature = "harness")]
+pub mod harness;
 
 pub use confirmation_layer::ConfirmationLayer;
 pub use hyper_scheduler::HyperScheduler;
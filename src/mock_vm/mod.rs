@@ -46,6 +46,64 @@ impl MockVM {
         Ok(execution)
     }
 
+    /// Parses a debit command (`debit <account> <amount>`).
+    /// 
+    /// x-chain-vm has no debit, so the mock VM handles it itself. Together with a credit on
+    /// another chain it forms the two halves of a cross-chain transfer.
+    /// 
+    /// # Arguments
+    /// * `transaction` - A string containing the transaction command
+    /// 
+    /// # Returns
+    /// `Some((account, amount))` if the command is a debit, `None` otherwise
+    pub fn parse_debit(transaction: &str) -> Option<(u32, u32)> {
+        let mut parts = transaction.split_whitespace();
+        if parts.next() != Some("debit") {
+            return None;
+        }
+        let account = parts.next()?.parse().ok()?;
+        let amount = parts.next()?.parse().ok()?;
+        if parts.next().is_some() {
+            return None;
+        }
+        Some((account, amount))
+    }
+
+    /// Checks whether a transaction would succeed without changing the state
+    /// 
+    /// # Arguments
+    /// * `transaction` - A string containing the transaction command
+    /// 
+    /// # Returns
+    /// True if the transaction would succeed
+    pub fn would_succeed(&self, transaction: &str) -> Result<bool, anyhow::Error> {
+        if let Some((account, amount)) = Self::parse_debit(transaction) {
+            return Ok(self.state.get(&account).copied().unwrap_or(0) >= amount);
+        }
+        let tx = parse_input(transaction)
+            .map_err(|e| anyhow::anyhow!("Failed to parse transaction: {}", e))?;
+        Ok(tx.execute(&self.state).is_success())
+    }
+
+    /// Execute a transaction, including debits, and update the state if it succeeds
+    /// 
+    /// # Arguments
+    /// * `transaction` - A string containing the transaction command
+    /// 
+    /// # Returns
+    /// True if the transaction succeeded
+    pub fn execute_command(&mut self, transaction: &str) -> Result<bool, anyhow::Error> {
+        if let Some((account, amount)) = Self::parse_debit(transaction) {
+            let balance = self.state.get(&account).copied().unwrap_or(0);
+            if balance < amount {
+                return Ok(false);
+            }
+            self.state.insert(account, balance - amount);
+            return Ok(true);
+        }
+        Ok(self.execute_transaction(transaction)?.is_success())
+    }
+
     /// Get the current state
     /// 
     /// # Returns
@@ -121,4 +179,28 @@ mod tests {
         // Verify sender's balance is unchanged after failed transaction
         assert_eq!(vm.get_state().get(&1), Some(&50));
     }
+
+    /// Test debit transactions
+    /// 
+    /// This test verifies that:
+    /// 1. A debit transaction decreases the balance of an account
+    /// 2. A debit transaction fails if the account has insufficient funds
+    /// 3. Malformed debits are not recognized as debits
+    #[test]
+    fn test_debit_transaction() {
+        let mut vm = MockVM::new();
+        vm.execute_command("credit 1 100").unwrap();
+
+        assert!(vm.would_succeed("debit 1 60").unwrap());
+        assert!(vm.execute_command("debit 1 60").unwrap());
+        assert_eq!(vm.get_state().get(&1), Some(&40));
+
+        assert!(!vm.would_succeed("debit 1 60").unwrap());
+        assert!(!vm.execute_command("debit 1 60").unwrap());
+        assert_eq!(vm.get_state().get(&1), Some(&40));
+
+        assert_eq!(MockVM::parse_debit("debit 1"), None);
+        assert_eq!(MockVM::parse_debit("debit 1 2 3"), None);
+        assert_eq!(MockVM::parse_debit("credit 1 2"), None);
+    }
 } 
\ No newline at end of file
@@ -2,9 +2,9 @@ use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::convert::TryFrom;
 use thiserror::Error;
-use crate::types::CLTransactionId;
+use crate::types::{CLTransaction, CLTransactionId};
 
-use super::{TransactionId, ChainId};
+use super::{TransactionId, TransactionStatus, ChainId};
 
 #[derive(Debug, Error)]
 pub enum CATStatusConversionError {
@@ -24,6 +24,17 @@ impl TryFrom<CATStatus> for CATStatusLimited {
     }
 }
 
+impl From<CATStatusLimited> for TransactionStatus {
+    fn from(status: CATStatusLimited) -> Self {
+        match status {
+            CATStatusLimited::Success => TransactionStatus::Success,
+            CATStatusLimited::Failure => TransactionStatus::Failure,
+            CATStatusLimited::TimedOut => TransactionStatus::TimedOut,
+            CATStatusLimited::Rejected => TransactionStatus::Rejected,
+        }
+    }
+}
+
 /// Unique identifier for a Crosschain Atomic Transaction (CAT)
 #[derive(Debug, Clone, Hash, Eq, PartialEq, Serialize, Deserialize)]
 pub struct CATId(pub CLTransactionId);
@@ -48,6 +59,10 @@ pub enum CATStatusLimited {
     Success,
     /// CAT is failed
     Failure,
+    /// CAT did not receive a status update within its lifetime
+    TimedOut,
+    /// CAT was rejected because it depends on a pending transaction
+    Rejected,
 }
 
 /// A status update for a CAT
@@ -61,6 +76,12 @@ pub struct CATStatusUpdate {
     pub status: CATStatusLimited,
     /// The set of all chains involved in this CAT (including the chain_id that sent this update)
     pub constituent_chains: Vec<ChainId>,
+    /// The HS epoch the CAT belongs to, so a late proposal reaches its own CAT when a later run reuses the ID
+    #[serde(default)]
+    pub epoch: u64,
+    /// The CL block height the proposing HIG had processed when it sent the update, so the HS can attribute its decisions to blocks
+    #[serde(default)]
+    pub block_height: u64,
 }
 
 /// A Crosschain Atomic Transaction (CAT)
@@ -78,4 +99,55 @@ impl fmt::Display for CATId {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.0)
     }
-} 
\ No newline at end of file
+} 
+
+/// Builds CATs with a fixed cross-chain semantics, so the shell and the simulator construct
+/// the same per-chain commands for the same operation.
+#[derive(Debug, Clone)]
+pub struct CatBuilder {
+    /// The command for each constituent chain, in the order of the chains
+    commands: Vec<(ChainId, String)>,
+}
+
+impl CatBuilder {
+    /// Creates a transfer of `amount` from `from_acct` on `from_chain` to `to_acct` on `to_chain`.
+    /// The CAT debits `from_acct` on `from_chain` and credits `to_acct` on `to_chain`, so it only
+    /// succeeds if `from_acct` holds at least `amount` on `from_chain`.
+    /// 
+    /// # Arguments
+    /// * `from_chain` - The chain the amount is taken from
+    /// * `to_chain` - The chain the amount is moved to
+    /// * `from_acct` - The account that is debited on `from_chain`
+    /// * `to_acct` - The account that is credited on `to_chain`
+    /// * `amount` - The amount to transfer
+    pub fn transfer(from_chain: ChainId, to_chain: ChainId, from_acct: u32, to_acct: u32, amount: u32) -> Self {
+        CatBuilder {
+            commands: vec![
+                (from_chain, format!("CAT.debit {} {}", from_acct, amount)),
+                (to_chain, format!("CAT.credit {} {}", to_acct, amount)),
+            ],
+        }
+    }
+
+    /// Returns the constituent chains of the CAT
+    pub fn constituent_chains(&self) -> Vec<ChainId> {
+        self.commands.iter().map(|(chain_id, _)| chain_id.clone()).collect()
+    }
+
+    /// Builds the CL transaction of the CAT
+    /// 
+    /// # Arguments
+    /// * `id` - The ID of the CL transaction, which is also the ID of the CAT
+    /// 
+    /// # Returns
+    /// An error if the constituent chains are not distinct or a command is invalid
+    pub fn build(self, id: CLTransactionId) -> Result<CLTransaction, String> {
+        let chains = self.constituent_chains();
+        if chains.iter().enumerate().any(|(i, chain_id)| chains[..i].contains(chain_id)) {
+            return Err(format!("CAT {} must span distinct chains", id.0));
+        }
+        self.commands.into_iter()
+            .fold(CLTransaction::builder(id, chains), |builder, (chain_id, data)| builder.command(chain_id, data))
+            .build()
+    }
+}
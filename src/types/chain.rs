@@ -21,6 +21,18 @@ pub struct ChainRegistration {
     pub active: bool,
 }
 
+/// Change of the set of registered chains, broadcast by the CL and the HS
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ChainRegistrationEvent {
+    /// The chain was registered
+    Registered(ChainId),
+    /// The chain was deregistered
+    Deregistered(ChainId),
+}
+
+/// Capacity of the chain registration event channels; slower subscribers miss the oldest events
+pub const CHAIN_EVENTS_CAPACITY: usize = 64;
+
 impl Default for ChainRegistration {
     fn default() -> Self {
         Self {
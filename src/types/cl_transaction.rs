@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 use std::fmt;
-use super::{ChainId, Transaction};
+use super::{ChainId, Transaction, TransactionId};
 use crate::types::communication::cl_to_hig::TransactionData;
 
 /// Unique identifier for a CL transaction
@@ -33,4 +33,89 @@ impl CLTransaction {
         }
         Ok(CLTransaction { id, constituent_chains, transactions })
     }
+
+    /// Starts building a CL transaction whose transactions may carry a different command per chain
+    /// 
+    /// # Arguments
+    /// * `id` - The ID of the CL transaction
+    /// * `constituent_chains` - The chains the CL transaction is destined to
+    pub fn builder(id: CLTransactionId, constituent_chains: Vec<ChainId>) -> CLTransactionBuilder {
+        CLTransactionBuilder { id, constituent_chains, commands: Vec::new() }
+    }
+
+    /// Size of the serialized CL transaction in bytes, as accounted by the CL for size limits
+    /// and byte throughput
+    pub fn serialized_size(&self) -> u64 {
+        serde_json::to_vec(self).map_or(0, |bytes| bytes.len() as u64)
+    }
+}
+
+/// Builder for a CL transaction with one command per constituent chain, e.g. a CAT that
+/// debits an account on one chain and credits it on another.
+#[derive(Debug, Clone)]
+pub struct CLTransactionBuilder {
+    id: CLTransactionId,
+    constituent_chains: Vec<ChainId>,
+    commands: Vec<(ChainId, String)>,
+}
+
+impl CLTransactionBuilder {
+    /// Sets the transaction data for a chain
+    /// 
+    /// # Arguments
+    /// * `chain_id` - The constituent chain the command is destined to
+    /// * `data` - The transaction data, e.g. `CAT.credit 1 100`
+    pub fn command(mut self, chain_id: ChainId, data: impl Into<String>) -> Self {
+        self.commands.push((chain_id, data.into()));
+        self
+    }
+
+    /// Sets the same transaction data for all constituent chains
+    /// 
+    /// # Arguments
+    /// * `data` - The transaction data
+    pub fn command_for_all(mut self, data: impl Into<String>) -> Self {
+        let data = data.into();
+        for chain_id in self.constituent_chains.clone() {
+            self.commands.push((chain_id, data.clone()));
+        }
+        self
+    }
+
+    /// Builds the CL transaction with one transaction per constituent chain, in the order of the chains
+    /// 
+    /// # Returns
+    /// An error if a chain has no or several commands, a command targets a chain outside the
+    /// constituent chains, a command is malformed, or a transaction spanning several chains is not a CAT
+    pub fn build(self) -> Result<CLTransaction, String> {
+        if self.constituent_chains.is_empty() {
+            return Err("CL transaction must have at least one constituent chain".to_string());
+        }
+        for (chain_id, _) in &self.commands {
+            if !self.constituent_chains.contains(chain_id) {
+                return Err(format!("Chain {} is not a constituent chain", chain_id.0));
+            }
+        }
+        let tx_id = TransactionId(format!("{}:tx", self.id.0));
+        let mut transactions = Vec::new();
+        for chain_id in &self.constituent_chains {
+            let mut commands = self.commands.iter().filter(|(cid, _)| cid == chain_id);
+            let data = match (commands.next(), commands.next()) {
+                (Some((_, data)), None) => data.clone(),
+                (None, _) => return Err(format!("No command for chain {}", chain_id.0)),
+                (Some(_), Some(_)) => return Err(format!("Several commands for chain {}", chain_id.0)),
+            };
+            if self.constituent_chains.len() > 1 && !data.starts_with("CAT.") {
+                return Err(format!("Command '{}' for chain {} must be a CAT", data, chain_id.0));
+            }
+            transactions.push(Transaction::new(
+                tx_id.clone(),
+                chain_id.clone(),
+                self.constituent_chains.clone(),
+                data,
+                self.id.clone(),
+            )?);
+        }
+        CLTransaction::new(self.id, self.constituent_chains, transactions)
+    }
 } 
\ No newline at end of file
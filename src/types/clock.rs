@@ -0,0 +1,326 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::{Arc, Mutex as StdMutex};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use async_trait::async_trait;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+
+/// Skew of a node's local clock relative to the confirmation layer, which acts as the reference clock.
+/// 
+/// The offset shifts the block height a node perceives, the drift makes its clock run fast (positive)
+/// or slow (negative). Local time is used for timeout checks and timestamping, so a skewed clock
+/// changes when CATs time out and how long local delays take.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct ClockSkew {
+    /// Constant offset of the local clock in blocks (positive = ahead of the CL)
+    #[serde(default)]
+    pub offset: f64,
+    /// Relative rate error of the local clock (e.g. 0.01 = runs 1% fast, must be greater than -1)
+    #[serde(default)]
+    pub drift: f64,
+}
+
+impl ClockSkew {
+    /// Returns true if the local clock matches the reference clock
+    pub fn is_zero(&self) -> bool {
+        self.offset == 0.0 && self.drift == 0.0
+    }
+
+    /// Returns the block height perceived by the local clock at the given CL block height.
+    /// 
+    /// # Arguments
+    /// * `block_height` - The block height of the CL
+    pub fn local_block_height(&self, block_height: u64) -> u64 {
+        (block_height as f64 * (1.0 + self.drift) + self.offset).max(0.0).floor() as u64
+    }
+
+    /// Converts a duration that passed in real time into the duration measured by the local clock.
+    /// 
+    /// # Arguments
+    /// * `real` - The duration in real time
+    pub fn local_duration(&self, real: Duration) -> Duration {
+        real.mul_f64((1.0 + self.drift).max(0.0))
+    }
+
+    /// Converts a duration measured by the local clock into real time.
+    /// 
+    /// # Arguments
+    /// * `local` - The duration on the local clock
+    pub fn real_duration(&self, local: Duration) -> Duration {
+        let rate = 1.0 + self.drift;
+        if rate <= 0.0 {
+            return local;
+        }
+        local.div_f64(rate)
+    }
+}
+
+/// Source of time for the nodes.
+/// 
+/// Block production of the CL, the proposal delays of the HIG and the decision latency of the HS
+/// wait on a clock, so the same node code runs in real time (`SystemClock`, e.g. in the shell) and
+/// in virtual time (`SimulatedClock`, e.g. in fast sweeps). Times are measured from the start of the clock.
+#[async_trait]
+pub trait Clock: Send + Sync + fmt::Debug {
+    /// Returns the time elapsed since the start of the clock
+    fn now(&self) -> Duration;
+
+    /// Waits until the clock reaches the given time (returns immediately if it already passed)
+    /// 
+    /// # Arguments
+    /// * `deadline` - The time since the start of the clock to wait for
+    async fn sleep_until(&self, deadline: Duration);
+
+    /// Waits for the given duration
+    /// 
+    /// # Arguments
+    /// * `duration` - The duration to wait for
+    async fn sleep(&self, duration: Duration) {
+        self.sleep_until(self.now() + duration).await
+    }
+
+    /// Returns the instant at which the clock showed the given time, counting back from now in
+    /// real time. Lines up clock times with the instants recorded by nodes that do not read the
+    /// clock (exact for a `SystemClock`, an approximation for a `SimulatedClock`).
+    /// 
+    /// # Arguments
+    /// * `time` - The time since the start of the clock
+    fn instant_at(&self, time: Duration) -> std::time::Instant {
+        let now = std::time::Instant::now();
+        now.checked_sub(self.now().saturating_sub(time)).unwrap_or(now)
+    }
+}
+
+/// Clock that follows the time of the tokio runtime
+#[derive(Debug, Clone, Copy)]
+pub struct SystemClock {
+    /// Start of the clock
+    start: Instant,
+}
+
+impl SystemClock {
+    /// Creates a clock that starts now
+    pub fn new() -> Self {
+        Self { start: Instant::now() }
+    }
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    async fn sleep_until(&self, deadline: Duration) {
+        tokio::time::sleep_until(self.start + deadline).await
+    }
+}
+
+/// Clock whose time only moves when it is advanced.
+/// 
+/// The clock keeps track of the times its sleepers wait for, so a driver can jump straight to
+/// the next one instead of waiting in real time (see `spawn_auto_advance`).
+#[derive(Debug)]
+pub struct SimulatedClock {
+    /// Current time, watched by the sleepers
+    now: watch::Sender<Duration>,
+    /// Times the sleepers wait for, with the number of sleepers waiting for each
+    deadlines: Arc<StdMutex<BTreeMap<Duration, usize>>>,
+}
+
+/// Removes the deadline of a sleeper when it wakes up or is dropped
+struct DeadlineGuard {
+    deadlines: Arc<StdMutex<BTreeMap<Duration, usize>>>,
+    deadline: Duration,
+}
+
+impl Drop for DeadlineGuard {
+    fn drop(&mut self) {
+        let mut deadlines = self.deadlines.lock().unwrap();
+        if let Some(count) = deadlines.get_mut(&self.deadline) {
+            *count -= 1;
+            if *count == 0 {
+                deadlines.remove(&self.deadline);
+            }
+        }
+    }
+}
+
+impl SimulatedClock {
+    /// Number of times the auto-advance driver yields to other tasks before it advances the clock
+    const AUTO_ADVANCE_YIELDS: usize = 16;
+
+    /// Creates a clock at time zero
+    pub fn new() -> Self {
+        Self {
+            now: watch::channel(Duration::ZERO).0,
+            deadlines: Arc::new(StdMutex::new(BTreeMap::new())),
+        }
+    }
+
+    /// Moves the clock forward and wakes the sleepers whose deadline passed
+    /// 
+    /// # Arguments
+    /// * `duration` - The duration to advance the clock by
+    pub fn advance(&self, duration: Duration) {
+        self.now.send_modify(|now| *now += duration);
+    }
+
+    /// Moves the clock to the given time (does nothing if the time already passed)
+    /// 
+    /// # Arguments
+    /// * `time` - The time since the start of the clock to advance to
+    pub fn advance_to(&self, time: Duration) {
+        self.now.send_if_modified(|now| {
+            if time > *now {
+                *now = time;
+                true
+            } else {
+                false
+            }
+        });
+    }
+
+    /// Returns the earliest time a sleeper waits for, if any
+    pub fn next_deadline(&self) -> Option<Duration> {
+        self.deadlines.lock().unwrap().keys().next().copied()
+    }
+
+    /// Moves the clock to the earliest time a sleeper waits for
+    /// 
+    /// # Returns
+    /// True if the clock was advanced, false if no sleeper is waiting
+    pub fn advance_to_next_deadline(&self) -> bool {
+        match self.next_deadline() {
+            Some(deadline) => {
+                self.advance_to(deadline);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Spawns a task that advances the clock to the next deadline whenever the other tasks had a
+    /// chance to run, so virtual time passes as fast as the nodes can keep up.
+    /// 
+    /// # Returns
+    /// The handle of the task, which runs until it is aborted or the clock is dropped
+    pub fn spawn_auto_advance(self: &Arc<Self>) -> JoinHandle<()> {
+        let clock = Arc::downgrade(self);
+        tokio::spawn(async move {
+            loop {
+                for _ in 0..Self::AUTO_ADVANCE_YIELDS {
+                    tokio::task::yield_now().await;
+                }
+                let Some(clock) = clock.upgrade() else { break };
+                if !clock.advance_to_next_deadline() {
+                    // Nothing waits on the clock, give the other tasks real time to make progress
+                    tokio::time::sleep(Duration::from_millis(1)).await;
+                }
+            }
+        })
+    }
+}
+
+impl Default for SimulatedClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Clock for SimulatedClock {
+    fn now(&self) -> Duration {
+        *self.now.borrow()
+    }
+
+    async fn sleep_until(&self, deadline: Duration) {
+        let mut receiver = self.now.subscribe();
+        if *receiver.borrow_and_update() >= deadline {
+            return;
+        }
+        *self.deadlines.lock().unwrap().entry(deadline).or_insert(0) += 1;
+        let _guard = DeadlineGuard { deadlines: self.deadlines.clone(), deadline };
+        while *receiver.borrow_and_update() < deadline {
+            if receiver.changed().await.is_err() {
+                return;
+            }
+        }
+    }
+}
+
+/// Source of the block height a HIG judges CAT timeouts and transaction expiries by.
+/// 
+/// By default the HIG follows the heights of the subblocks it receives (`SubblockHeight`), so
+/// timeouts depend on the cadence of the CL. Tests inject a `ManualBlockHeight` to move the height
+/// independently of the subblocks and check the timeouts right away.
+pub trait BlockHeightSource: Send + Sync + fmt::Debug {
+    /// Returns the block height to judge timeouts by
+    /// 
+    /// # Arguments
+    /// * `subblock_height` - The height of the last subblock the HIG received
+    fn block_height(&self, subblock_height: u64) -> u64;
+}
+
+/// Block height source that follows the received subblocks
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SubblockHeight;
+
+impl BlockHeightSource for SubblockHeight {
+    fn block_height(&self, subblock_height: u64) -> u64 {
+        subblock_height
+    }
+}
+
+/// Block height source that only moves when it is set or advanced, regardless of the subblocks
+#[derive(Debug, Default)]
+pub struct ManualBlockHeight {
+    height: AtomicU64,
+}
+
+impl ManualBlockHeight {
+    /// Creates a source at the given height
+    /// 
+    /// # Arguments
+    /// * `height` - The initial block height
+    pub fn new(height: u64) -> Self {
+        Self { height: AtomicU64::new(height) }
+    }
+
+    /// Returns the current height
+    pub fn get(&self) -> u64 {
+        self.height.load(Ordering::SeqCst)
+    }
+
+    /// Sets the height
+    /// 
+    /// # Arguments
+    /// * `height` - The new block height
+    pub fn set(&self, height: u64) {
+        self.height.store(height, Ordering::SeqCst);
+    }
+
+    /// Moves the height forward
+    /// 
+    /// # Arguments
+    /// * `blocks` - The number of blocks to advance by
+    pub fn advance(&self, blocks: u64) {
+        self.height.fetch_add(blocks, Ordering::SeqCst);
+    }
+}
+
+impl BlockHeightSource for ManualBlockHeight {
+    fn block_height(&self, _subblock_height: u64) -> u64 {
+        self.get()
+    }
+}
@@ -1,4 +1,4 @@
-use crate::types::{SubBlock};
+use crate::types::{SubBlock, Transaction};
 use serde::{Deserialize, Serialize};
 use regex::Regex;
 use lazy_static::lazy_static;
@@ -24,10 +24,12 @@ impl TransactionData {
     /// Validates if a transaction data string matches the expected format
     pub fn validate(data: &str) -> Result<TransactionData, String> {
         if CAT_PATTERN.is_match(data) {
+            check_arguments_fit_u32(data)?;
             Ok(TransactionData::CAT(data.to_string()))
         } else if STATUS_UPDATE_PATTERN.is_match(data) {
             Ok(TransactionData::StatusUpdate(data.to_string()))
         } else if REGULAR_PATTERN.is_match(data) {
+            check_arguments_fit_u32(data)?;
             Ok(TransactionData::Regular(data.to_string()))
         } else {
             Err("Invalid transaction data format".to_string())
@@ -35,17 +37,49 @@ impl TransactionData {
     }
 }
 
+/// Checks that the accounts and amounts of a command fit the VM's u32 values,
+/// since the patterns accept numbers of any length
+fn check_arguments_fit_u32(data: &str) -> Result<(), String> {
+    match data.split_whitespace().skip(1).find(|argument| argument.parse::<u32>().is_err()) {
+        Some(argument) => Err(format!("Transaction argument out of range: {}", argument)),
+        None => Ok(()),
+    }
+}
+
+/// Suffix appended by the HS to a CAT ID to form the CL transaction ID of its status update.
+/// Together with the CL only accepting status updates from the HS channel, this acts as the HS-origin marker.
+pub const STATUS_UPDATE_CL_ID_SUFFIX: &str = ".UPDATE";
+
+/// Checks whether a status update transaction carries the HS-origin marker.
+/// 
+/// A status update is considered to originate from the HS if its CL transaction ID is
+/// `<cat_id>.UPDATE`, where `<cat_id>` is the CAT referenced in the transaction data.
+/// 
+/// # Arguments
+/// * `tx` - The transaction to check
+/// 
+/// # Returns
+/// True if the transaction is a well-formed status update with the HS-origin marker, false otherwise
+pub fn has_hs_origin_marker(tx: &Transaction) -> bool {
+    STATUS_UPDATE_PATTERN.captures(&tx.data)
+        .and_then(|caps| caps.name("cat_id"))
+        .map(|cat_id| tx.cl_id.0 == format!("{}{}", cat_id.as_str(), STATUS_UPDATE_CL_ID_SUFFIX))
+        .unwrap_or(false)
+}
+
 lazy_static! {
     pub static ref CAT_ID_SUFFIX: &'static str = r"\.CAT_ID:(?P<cat_id>[a-zA-Z0-9_-]+)";
 
     // Expected formats of the data field of a transaction:
     // REGULAR.credit <receiver> <amount>
+    // REGULAR.debit <account> <amount>
     // REGULAR.send <sender> <receiver> <amount>
     // CAT.credit <receiver> <amount>
+    // CAT.debit <account> <amount>
     // CAT.send <sender> <receiver> <amount>
     // STATUS_UPDATE:<StatusLimited>.CAT_ID:<ID>
-    pub static ref REGULAR_PATTERN: Regex = Regex::new(r"^REGULAR\.(credit \d+ \d+|send \d+ \d+ \d+)$").unwrap();
-    pub static ref CAT_PATTERN: Regex = Regex::new(&format!(r"^CAT\.(credit \d+ \d+|send \d+ \d+ \d+)$")).unwrap();
-    pub static ref STATUS_UPDATE_PATTERN: Regex = Regex::new(&format!(r"^STATUS_UPDATE:(Success|Failure){}$", *CAT_ID_SUFFIX)).unwrap();
+    pub static ref REGULAR_PATTERN: Regex = Regex::new(r"^REGULAR\.(credit \d+ \d+|debit \d+ \d+|send \d+ \d+ \d+)$").unwrap();
+    pub static ref CAT_PATTERN: Regex = Regex::new(&format!(r"^CAT\.(credit \d+ \d+|debit \d+ \d+|send \d+ \d+ \d+)$")).unwrap();
+    pub static ref STATUS_UPDATE_PATTERN: Regex = Regex::new(&format!(r"^STATUS_UPDATE:(Success|Failure|TimedOut|Rejected){}$", *CAT_ID_SUFFIX)).unwrap();
 }
 
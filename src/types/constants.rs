@@ -1,20 +1,32 @@
-use crate::types::ChainId;
+//! Fixed chain IDs of the test networks.
+//!
+//! Deprecated: these IDs are fixed at compile time. New code should take its chains from a
+//! runtime [`NetworkSpec`](crate::types::NetworkSpec); the test fixtures use the chains of
+//! `NetworkSpec::test_network()`.
 
+use crate::types::{ChainId, NetworkSpec};
+
+#[deprecated(note = "use NetworkSpec")]
 pub const CHAIN_1: &str = "chain-1";
+#[deprecated(note = "use NetworkSpec")]
 pub const CHAIN_2: &str = "chain-2";
+#[deprecated(note = "use NetworkSpec")]
 pub const CHAIN_3: &str = "chain-3";
 
 /// Chain ID for the first test chain
+#[deprecated(note = "use NetworkSpec")]
 pub fn chain_1() -> ChainId {
-    ChainId(CHAIN_1.to_string())
+    NetworkSpec::chain_id(1)
 }
 
 /// Chain ID for the second test chain
+#[deprecated(note = "use NetworkSpec")]
 pub fn chain_2() -> ChainId {
-    ChainId(CHAIN_2.to_string())
+    NetworkSpec::chain_id(2)
 }
 
 /// Chain ID for the third test chain
+#[deprecated(note = "use NetworkSpec")]
 pub fn chain_3() -> ChainId {
-    ChainId(CHAIN_3.to_string())
-} 
\ No newline at end of file
+    NetworkSpec::chain_id(3)
+}
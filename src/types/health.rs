@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+
+/// Liveness of a channel between two nodes, seen from one of its ends
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChannelHealth {
+    /// Whether the other end of the channel still exists
+    pub open: bool,
+    /// Number of messages waiting in the channel
+    pub queued: usize,
+}
+
+impl ChannelHealth {
+    /// Health of a channel seen from its sending end (closed once the receiver is dropped)
+    pub fn of_sender<T>(sender: &mpsc::Sender<T>) -> Self {
+        Self {
+            open: !sender.is_closed(),
+            queued: sender.max_capacity() - sender.capacity(),
+        }
+    }
+
+    /// Health of a channel seen from its receiving end (closed once all senders are dropped)
+    pub fn of_receiver<T>(receiver: &mpsc::Receiver<T>) -> Self {
+        Self {
+            open: !receiver.is_closed(),
+            queued: receiver.len(),
+        }
+    }
+}
@@ -3,8 +3,16 @@ mod cl_transaction;
 pub mod cat;
 mod block;
 mod chain;
+mod clock;
+mod state_proof;
+mod state_diff;
+mod network_spec;
+mod shell_command;
+mod health;
 pub mod communication;
 pub mod constants;
+#[cfg(test)]
+pub(crate) mod tests;
 
 // Re-export all types
 pub use transaction::*;
@@ -12,4 +20,10 @@ pub use cl_transaction::*;
 pub use cat::*;
 pub use block::*;
 pub use chain::*;
+pub use clock::*;
+pub use state_proof::*;
+pub use state_diff::*;
+pub use network_spec::*;
+pub use shell_command::*;
+pub use health::*;
 pub use communication::*; 
\ No newline at end of file
@@ -0,0 +1,124 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+use super::ChainId;
+
+/// Chain count of the default network (chain-1 and chain-2)
+pub const DEFAULT_CHAIN_COUNT: usize = 2;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum NetworkSpecError {
+    #[error("A network needs at least one chain")]
+    NoChains,
+    #[error("Chain IDs must not be empty")]
+    EmptyChainId,
+    #[error("Chain {0} is listed more than once")]
+    DuplicateChain(String),
+}
+
+/// The chains of a network, decided at runtime
+///
+/// The shell, the harness and the simulator take the chains they run from a `NetworkSpec`, so a
+/// different topology does not need a rebuild.
+/// Chains are numbered from 1 in the order of the spec.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NetworkSpec {
+    chains: Vec<ChainId>,
+}
+
+impl NetworkSpec {
+    /// Creates a spec of the given chains, which must be non-empty and distinct
+    pub fn new(chains: Vec<ChainId>) -> Result<Self, NetworkSpecError> {
+        if chains.is_empty() {
+            return Err(NetworkSpecError::NoChains);
+        }
+        let mut seen = HashSet::new();
+        for chain_id in &chains {
+            if chain_id.0.is_empty() {
+                return Err(NetworkSpecError::EmptyChainId);
+            }
+            if !seen.insert(chain_id) {
+                return Err(NetworkSpecError::DuplicateChain(chain_id.0.clone()));
+            }
+        }
+        Ok(Self { chains })
+    }
+
+    /// Creates a spec of `count` chains with the conventional IDs chain-1 to chain-`count`
+    pub fn with_chain_count(count: usize) -> Result<Self, NetworkSpecError> {
+        Self::new((1..=count).map(Self::chain_id).collect())
+    }
+
+    /// The conventional ID of the chain with the given number (starting at 1)
+    pub fn chain_id(number: usize) -> ChainId {
+        ChainId(format!("chain-{}", number))
+    }
+
+    /// The chains, in order
+    pub fn chain_ids(&self) -> &[ChainId] {
+        &self.chains
+    }
+
+    /// The number of chains
+    pub fn chain_count(&self) -> usize {
+        self.chains.len()
+    }
+
+    /// The chain with the given number (starting at 1)
+    pub fn chain(&self, number: usize) -> Option<&ChainId> {
+        number.checked_sub(1).and_then(|index| self.chains.get(index))
+    }
+
+    /// The number (starting at 1) of the given chain
+    pub fn number_of(&self, chain_id: &ChainId) -> Option<usize> {
+        self.chains.iter().position(|chain| chain == chain_id).map(|index| index + 1)
+    }
+
+    /// Whether the network contains the given chain
+    pub fn contains(&self, chain_id: &ChainId) -> bool {
+        self.chains.contains(chain_id)
+    }
+}
+
+#[cfg(any(test, feature = "test"))]
+impl NetworkSpec {
+    /// The network the unit and integration test fixtures are written against: chain-1 to chain-3
+    pub fn test_network() -> Self {
+        Self::with_chain_count(3).expect("Three chains form a valid network")
+    }
+
+    /// The chain with the given number (1 to 3) of the test network
+    pub fn test_chain(number: usize) -> ChainId {
+        Self::test_network().chain(number).cloned().expect("The test network has chains 1 to 3")
+    }
+}
+
+impl Default for NetworkSpec {
+    fn default() -> Self {
+        Self { chains: (1..=DEFAULT_CHAIN_COUNT).map(Self::chain_id).collect() }
+    }
+}
+
+/// Parses either a chain count (`3` → chain-1, chain-2, chain-3) or a comma-separated list of
+/// chain IDs (`chain-a,chain-b`)
+impl FromStr for NetworkSpec {
+    type Err = NetworkSpecError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if let Ok(count) = s.parse::<usize>() {
+            return Self::with_chain_count(count);
+        }
+        Self::new(s.split(',').map(|chain| ChainId(chain.trim().to_string())).collect())
+    }
+}
+
+impl fmt::Display for NetworkSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let chains: Vec<&str> = self.chains.iter().map(|chain| chain.0.as_str()).collect();
+        write!(f, "{}", chains.join(","))
+    }
+}
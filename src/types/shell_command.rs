@@ -0,0 +1,313 @@
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+use super::{CATId, CLTransactionId, ChainId};
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ShellCommandError {
+    #[error("Empty command")]
+    Empty,
+    #[error("Unknown command '{0}' (type 'help' for commands)")]
+    UnknownCommand(String),
+    #[error("Unterminated {0} quote")]
+    UnterminatedQuote(char),
+    #[error("Missing {missing}. Usage: {usage}")]
+    MissingArgument { missing: &'static str, usage: &'static str },
+    #[error("Unexpected argument '{argument}'. Usage: {usage}")]
+    UnexpectedArgument { argument: String, usage: &'static str },
+    #[error("Invalid {name} '{value}': {reason}. Usage: {usage}")]
+    InvalidArgument { name: &'static str, value: String, reason: String, usage: &'static str },
+}
+
+/// A command of the shell
+///
+/// The interactive shell, scripts piped into `hyperplane-ctl` and the daemon's control socket all
+/// parse their lines into a `ShellCommand`, so they accept the same grammar: arguments are
+/// separated by any amount of whitespace and may be quoted with `"` or `'` to contain spaces. The
+/// transaction data of `send-tx`, `send-cat` and `preview` is the rest of the line. In the chain
+/// list of `send-cat`, a backslash makes the next character part of the chain ID, so `\,` is a
+/// comma within a chain ID. Blank lines and lines starting with `#` parse to `ShellCommandError::Empty`.
+///
+/// `Display` writes the canonical form of a command, which parses back to the same command as
+/// long as its chain IDs are not empty (the parser rejects empty chain IDs).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShellCommand {
+    Help,
+    /// `exit` or `quit`
+    Exit,
+    Shutdown,
+    Status,
+    Health,
+    AddChain { chain_id: ChainId },
+    SendTx { chain_id: ChainId, data: String },
+    /// `data` is either one command for all chains or one command per chain, separated by `;`
+    SendCat { chain_ids: Vec<ChainId>, data: String },
+    Preview { chain_id: ChainId, data: String },
+    Balance { chain_id: ChainId, account: u32, with_proof: bool },
+    Blocked { chain_id: ChainId },
+    ExplainCat { cat_id: CATId },
+    DumpState { chain_id: ChainId, file: String },
+    LoadBalances { chain_id: ChainId, file: String },
+    SetDelay { chain_id: ChainId, milliseconds: u64 },
+    SetBlockInterval { milliseconds: u64 },
+    Pause,
+    Resume,
+    Step,
+    NewEpoch,
+}
+
+impl ShellCommand {
+    /// The usage line of a command, or None for an unknown command
+    pub fn usage(name: &str) -> Option<&'static str> {
+        Some(match name {
+            "help" => "help",
+            "exit" | "quit" => "exit",
+            "shutdown" => "shutdown",
+            "status" => "status",
+            "health" => "health",
+            "add-chain" => "add-chain <chain_id>",
+            "send-tx" => "send-tx <chain_id> <data>",
+            "send-cat" => "send-cat <chain_id1,chain_id2,...> <data>",
+            "preview" => "preview <chain_id> <data>",
+            "balance" => "balance <chain_id> <account> [--proof]",
+            "blocked" => "blocked <chain_id>",
+            "explain-cat" => "explain-cat <cat_id>",
+            "dump-state" => "dump-state <chain_id> <file>",
+            "load-balances" => "load-balances <chain_id> <file>",
+            "set-delay" => "set-delay <chain_id> <milliseconds>",
+            "set-block-interval" => "set-block-interval <milliseconds>",
+            "pause" => "pause",
+            "resume" => "resume",
+            "step" => "step",
+            "new-epoch" => "new-epoch",
+            _ => return None,
+        })
+    }
+}
+
+/// Splits a line into arguments, keeping quoted text together without its quotes
+fn tokenize(input: &str) -> Result<Vec<String>, ShellCommandError> {
+    let mut tokens = Vec::new();
+    let mut current: Option<String> = None;
+    let mut chars = input.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' | '\'' => {
+                // A quote continues the current argument, so `a"b c"` is the single argument `ab c`
+                let token = current.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some(next) if next == c => break,
+                        Some(next) => token.push(next),
+                        None => return Err(ShellCommandError::UnterminatedQuote(c)),
+                    }
+                }
+            }
+            c if c.is_whitespace() => tokens.extend(current.take()),
+            c => current.get_or_insert_with(String::new).push(c),
+        }
+    }
+    tokens.extend(current);
+    Ok(tokens)
+}
+
+/// The arguments of a command, consumed in order
+struct Arguments {
+    usage: &'static str,
+    tokens: std::vec::IntoIter<String>,
+}
+
+impl Arguments {
+    fn required(&mut self, missing: &'static str) -> Result<String, ShellCommandError> {
+        self.tokens.next().ok_or(ShellCommandError::MissingArgument { missing, usage: self.usage })
+    }
+
+    fn chain_id(&mut self) -> Result<ChainId, ShellCommandError> {
+        let chain_id = self.required("chain ID")?;
+        if chain_id.is_empty() {
+            return Err(self.invalid("chain ID", chain_id, "chain IDs must not be empty"));
+        }
+        Ok(ChainId(chain_id))
+    }
+
+    fn number<T: FromStr>(&mut self, name: &'static str) -> Result<T, ShellCommandError> {
+        let value = self.required(name)?;
+        value.parse().map_err(|_| self.invalid(name, value, "expected a non-negative whole number"))
+    }
+
+    /// The remaining arguments joined by single spaces
+    fn rest(&mut self, missing: &'static str) -> Result<String, ShellCommandError> {
+        let rest: Vec<String> = self.tokens.by_ref().collect();
+        if rest.is_empty() {
+            return Err(ShellCommandError::MissingArgument { missing, usage: self.usage });
+        }
+        Ok(rest.join(" "))
+    }
+
+    fn invalid(&self, name: &'static str, value: String, reason: &str) -> ShellCommandError {
+        ShellCommandError::InvalidArgument { name, value, reason: reason.to_string(), usage: self.usage }
+    }
+
+    /// Rejects arguments that are left over
+    fn finish<T>(mut self, command: T) -> Result<T, ShellCommandError> {
+        match self.tokens.next() {
+            Some(argument) => Err(ShellCommandError::UnexpectedArgument { argument, usage: self.usage }),
+            None => Ok(command),
+        }
+    }
+}
+
+impl FromStr for ShellCommand {
+    type Err = ShellCommandError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.starts_with('#') {
+            return Err(ShellCommandError::Empty);
+        }
+        let mut tokens = tokenize(s)?.into_iter();
+        let name = tokens.next().ok_or(ShellCommandError::Empty)?;
+        let usage = Self::usage(&name).ok_or_else(|| ShellCommandError::UnknownCommand(name.clone()))?;
+        let mut args = Arguments { usage, tokens };
+
+        let command = match name.as_str() {
+            "help" => Self::Help,
+            "exit" | "quit" => Self::Exit,
+            "shutdown" => Self::Shutdown,
+            "status" => Self::Status,
+            "health" => Self::Health,
+            "pause" => Self::Pause,
+            "resume" => Self::Resume,
+            "step" => Self::Step,
+            "new-epoch" => Self::NewEpoch,
+            "add-chain" => Self::AddChain { chain_id: args.chain_id()? },
+            "send-tx" => Self::SendTx { chain_id: args.chain_id()?, data: args.rest("transaction data")? },
+            "send-cat" => {
+                let chains = args.required("chain IDs")?;
+                let chain_ids = split_chain_list(&chains);
+                if chain_ids.iter().any(|chain_id| chain_id.0.is_empty()) {
+                    return Err(args.invalid("chain IDs", chains, "expected a comma-separated list of chain IDs"));
+                }
+                Self::SendCat { chain_ids, data: args.rest("transaction data")? }
+            }
+            "preview" => Self::Preview { chain_id: args.chain_id()?, data: args.rest("transaction data")? },
+            "balance" => {
+                let chain_id = args.chain_id()?;
+                let account = args.number("account")?;
+                let with_proof = match args.tokens.next() {
+                    Some(flag) if flag == "--proof" => true,
+                    Some(flag) => return Err(args.invalid("flag", flag, "the only flag is --proof")),
+                    None => false,
+                };
+                Self::Balance { chain_id, account, with_proof }
+            }
+            "blocked" => Self::Blocked { chain_id: args.chain_id()? },
+            "explain-cat" => Self::ExplainCat { cat_id: CATId(CLTransactionId(args.required("CAT ID")?)) },
+            "dump-state" => Self::DumpState { chain_id: args.chain_id()?, file: args.required("file")? },
+            "load-balances" => Self::LoadBalances { chain_id: args.chain_id()?, file: args.required("file")? },
+            "set-delay" => Self::SetDelay { chain_id: args.chain_id()?, milliseconds: args.number("milliseconds")? },
+            "set-block-interval" => Self::SetBlockInterval { milliseconds: args.number("milliseconds")? },
+            _ => unreachable!("every command with a usage line is parsed"),
+        };
+        args.finish(command)
+    }
+}
+
+/// Splits the chain list of `send-cat` at its commas, trimming the whitespace around each chain ID.
+/// A backslash makes the next character part of the chain ID, even a comma or whitespace.
+fn split_chain_list(list: &str) -> Vec<ChainId> {
+    let mut chain_ids = Vec::new();
+    // Characters of the current chain ID, with whether they were escaped
+    let mut chain: Vec<(char, bool)> = Vec::new();
+    let mut chars = list.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => chain.push((chars.next().unwrap_or('\\'), true)),
+            ',' => chain_ids.push(trimmed_chain_id(&std::mem::take(&mut chain))),
+            c => chain.push((c, false)),
+        }
+    }
+    chain_ids.push(trimmed_chain_id(&chain));
+    chain_ids
+}
+
+/// Builds a chain ID from its characters, dropping the unescaped whitespace at either end
+fn trimmed_chain_id(chain: &[(char, bool)]) -> ChainId {
+    let kept = |&(c, escaped): &(char, bool)| escaped || !c.is_whitespace();
+    let start = chain.iter().position(kept).unwrap_or(chain.len());
+    let end = chain.iter().rposition(kept).map_or(start, |end| end + 1);
+    ChainId(chain[start..end].iter().map(|&(c, _)| c).collect())
+}
+
+/// Joins chain IDs into the chain list of `send-cat`, escaping the backslashes, commas and
+/// whitespace within them
+fn chain_list(chain_ids: &[ChainId]) -> String {
+    let escaped: Vec<String> = chain_ids.iter()
+        .map(|chain_id| chain_id.0.chars().fold(String::new(), |mut escaped, c| {
+            if c == '\\' || c == ',' || c.is_whitespace() {
+                escaped.push('\\');
+            }
+            escaped.push(c);
+            escaped
+        }))
+        .collect();
+    escaped.join(",")
+}
+
+/// Quotes an argument if it would otherwise not parse back as a single argument. An argument with
+/// both kinds of quotes is written as adjacent quoted runs, each quoted with the kind it does not
+/// contain, which the tokenizer joins back into one argument (`a"b'c` becomes `'a"b'"'c"`).
+fn quoted(argument: &str) -> String {
+    if !argument.is_empty() && !argument.contains(|c: char| c.is_whitespace() || c == '"' || c == '\'') {
+        return argument.to_string();
+    }
+    let mut quoted = String::new();
+    let mut run = String::new();
+    // Quote of the current run, decided by the first quote character it contains
+    let mut quote = None;
+    for c in argument.chars() {
+        if c == '"' || c == '\'' {
+            if quote == Some(c) {
+                quoted.push_str(&format!("{c}{run}{c}"));
+                run.clear();
+            }
+            quote = Some(if c == '"' { '\'' } else { '"' });
+        }
+        run.push(c);
+    }
+    let quote = quote.unwrap_or('"');
+    quoted.push_str(&format!("{quote}{run}{quote}"));
+    quoted
+}
+
+impl fmt::Display for ShellCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Help => write!(f, "help"),
+            Self::Exit => write!(f, "exit"),
+            Self::Shutdown => write!(f, "shutdown"),
+            Self::Status => write!(f, "status"),
+            Self::Health => write!(f, "health"),
+            Self::AddChain { chain_id } => write!(f, "add-chain {}", quoted(&chain_id.0)),
+            Self::SendTx { chain_id, data } => write!(f, "send-tx {} {}", quoted(&chain_id.0), quoted(data)),
+            Self::SendCat { chain_ids, data } => {
+                write!(f, "send-cat {} {}", quoted(&chain_list(chain_ids)), quoted(data))
+            }
+            Self::Preview { chain_id, data } => write!(f, "preview {} {}", quoted(&chain_id.0), quoted(data)),
+            Self::Balance { chain_id, account, with_proof } => {
+                write!(f, "balance {} {}{}", quoted(&chain_id.0), account, if *with_proof { " --proof" } else { "" })
+            }
+            Self::Blocked { chain_id } => write!(f, "blocked {}", quoted(&chain_id.0)),
+            Self::ExplainCat { cat_id } => write!(f, "explain-cat {}", quoted(&cat_id.0.0)),
+            Self::DumpState { chain_id, file } => write!(f, "dump-state {} {}", quoted(&chain_id.0), quoted(file)),
+            Self::LoadBalances { chain_id, file } => write!(f, "load-balances {} {}", quoted(&chain_id.0), quoted(file)),
+            Self::SetDelay { chain_id, milliseconds } => write!(f, "set-delay {} {}", quoted(&chain_id.0), milliseconds),
+            Self::SetBlockInterval { milliseconds } => write!(f, "set-block-interval {}", milliseconds),
+            Self::Pause => write!(f, "pause"),
+            Self::Resume => write!(f, "resume"),
+            Self::Step => write!(f, "step"),
+            Self::NewEpoch => write!(f, "new-epoch"),
+        }
+    }
+}
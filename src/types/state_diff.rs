@@ -0,0 +1,82 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+
+/// Difference of one account between two chain-state snapshots
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountDiff {
+    /// The account
+    pub account: String,
+    /// Balance in the first snapshot (None if the account does not exist there)
+    pub before: Option<i64>,
+    /// Balance in the second snapshot (None if the account does not exist there)
+    pub after: Option<i64>,
+}
+
+impl AccountDiff {
+    /// Returns the change of the balance, treating a missing account as a balance of 0
+    pub fn delta(&self) -> i64 {
+        self.after.unwrap_or(0) - self.before.unwrap_or(0)
+    }
+}
+
+impl fmt::Display for AccountDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.before, self.after) {
+            (Some(before), Some(after)) => write!(f, "account {}: {} -> {} ({:+})", self.account, before, after, self.delta()),
+            (None, Some(after)) => write!(f, "account {}: only in second snapshot ({})", self.account, after),
+            (Some(before), None) => write!(f, "account {}: only in first snapshot ({})", self.account, before),
+            (None, None) => write!(f, "account {}: missing in both snapshots", self.account),
+        }
+    }
+}
+
+/// Per-account differences between two chain-state snapshots (as returned by `get_chain_state`)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateDiff {
+    /// Accounts whose balances differ, ordered by account (numerically where possible)
+    pub accounts: Vec<AccountDiff>,
+}
+
+impl StateDiff {
+    /// Compares two chain-state snapshots account by account.
+    ///
+    /// # Arguments
+    /// * `before` - The first snapshot
+    /// * `after` - The second snapshot
+    pub fn between(before: &HashMap<String, i64>, after: &HashMap<String, i64>) -> Self {
+        let accounts: BTreeSet<(Option<u64>, &String)> = before.keys().chain(after.keys())
+            .map(|account| (account.parse::<u64>().ok(), account))
+            .collect();
+        let accounts = accounts.into_iter()
+            .filter_map(|(_, account)| {
+                let (before, after) = (before.get(account).copied(), after.get(account).copied());
+                (before != after).then(|| AccountDiff { account: account.clone(), before, after })
+            })
+            .collect();
+        Self { accounts }
+    }
+
+    /// Returns true if both snapshots are identical
+    pub fn is_empty(&self) -> bool {
+        self.accounts.is_empty()
+    }
+
+    /// Returns the sum of the balance changes over all accounts
+    pub fn net_delta(&self) -> i64 {
+        self.accounts.iter().map(AccountDiff::delta).sum()
+    }
+}
+
+impl fmt::Display for StateDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return write!(f, "states are identical");
+        }
+        write!(f, "{} accounts differ (net {:+})", self.accounts.len(), self.net_delta())?;
+        for account in &self.accounts {
+            write!(f, "\n  {}", account)?;
+        }
+        Ok(())
+    }
+}
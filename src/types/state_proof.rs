@@ -0,0 +1,161 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Domain separation prefix of a leaf hash
+const LEAF_PREFIX: u8 = 0x00;
+/// Domain separation prefix of an inner node hash
+const NODE_PREFIX: u8 = 0x01;
+
+/// Root of the Merkle tree over the account balances of a chain.
+///
+/// The leaves are `sha256(0x00 || account || balance)` (big-endian u32s) sorted by account, inner
+/// nodes are `sha256(0x01 || left || right)`. A node without a sibling is carried up unchanged.
+/// The root of an empty state is `sha256("")`.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateRoot(pub [u8; 32]);
+
+impl fmt::Display for StateRoot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in self.0 {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+/// Sibling hash on the path from a leaf to the root
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProofStep {
+    /// Hash of the sibling node
+    pub sibling: [u8; 32],
+    /// Whether the sibling is the left child
+    pub sibling_is_left: bool,
+}
+
+/// Inclusion proof of an account balance against a [`StateRoot`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountProof {
+    /// The account
+    pub account: u32,
+    /// The balance of the account
+    pub balance: u32,
+    /// Sibling hashes from the leaf up to the root
+    pub path: Vec<ProofStep>,
+}
+
+impl AccountProof {
+    /// Checks the proof against a state root.
+    ///
+    /// # Arguments
+    /// * `root` - The state root the proof was issued against
+    ///
+    /// # Returns
+    /// True if the account balance is included in the state with the given root
+    pub fn verify(&self, root: &StateRoot) -> bool {
+        let hash = self.path.iter().fold(leaf_hash(self.account, self.balance), |hash, step| {
+            if step.sibling_is_left {
+                node_hash(&step.sibling, &hash)
+            } else {
+                node_hash(&hash, &step.sibling)
+            }
+        });
+        hash == root.0
+    }
+}
+
+/// Balance of an account on a chain, optionally with an inclusion proof
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountBalance {
+    /// The account
+    pub account: u32,
+    /// The balance of the account (0 if the account does not exist)
+    pub balance: u32,
+    /// The state root of the chain at the time of the query
+    pub state_root: StateRoot,
+    /// Inclusion proof of the balance, if requested and the account exists
+    pub proof: Option<AccountProof>,
+}
+
+/// Merkle tree over the account balances of a chain
+#[derive(Debug, Clone)]
+pub struct StateTree {
+    /// Accounts in leaf order
+    accounts: Vec<(u32, u32)>,
+    /// Levels of the tree, from the leaves (index 0) to the root
+    levels: Vec<Vec<[u8; 32]>>,
+}
+
+impl StateTree {
+    /// Builds the tree over a VM state.
+    ///
+    /// # Arguments
+    /// * `state` - Map from account to balance
+    pub fn from_state(state: &HashMap<u32, u32>) -> Self {
+        let mut accounts: Vec<(u32, u32)> = state.iter().map(|(&account, &balance)| (account, balance)).collect();
+        accounts.sort_unstable();
+
+        let mut levels = vec![accounts.iter().map(|&(account, balance)| leaf_hash(account, balance)).collect::<Vec<_>>()];
+        while levels.last().unwrap().len() > 1 {
+            let next = levels.last().unwrap()
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => node_hash(left, right),
+                    [single] => *single,
+                    _ => unreachable!(),
+                })
+                .collect();
+            levels.push(next);
+        }
+        Self { accounts, levels }
+    }
+
+    /// Returns the root of the tree
+    pub fn root(&self) -> StateRoot {
+        match self.levels.last().and_then(|level| level.first()) {
+            Some(root) => StateRoot(*root),
+            None => StateRoot(Sha256::digest(b"").into()),
+        }
+    }
+
+    /// Returns the balance of an account, or None if the account does not exist
+    pub fn balance(&self, account: u32) -> Option<u32> {
+        self.accounts.binary_search_by_key(&account, |&(account, _)| account).ok()
+            .map(|index| self.accounts[index].1)
+    }
+
+    /// Builds the inclusion proof of an account, or None if the account does not exist
+    pub fn proof(&self, account: u32) -> Option<AccountProof> {
+        let mut index = self.accounts.binary_search_by_key(&account, |&(account, _)| account).ok()?;
+        let balance = self.accounts[index].1;
+
+        let mut path = Vec::new();
+        for level in &self.levels[..self.levels.len() - 1] {
+            let sibling_index = index ^ 1;
+            if let Some(sibling) = level.get(sibling_index) {
+                path.push(ProofStep { sibling: *sibling, sibling_is_left: sibling_index < index });
+            }
+            index /= 2;
+        }
+        Some(AccountProof { account, balance, path })
+    }
+}
+
+/// Hashes a leaf of the state tree
+fn leaf_hash(account: u32, balance: u32) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update([LEAF_PREFIX]);
+    hasher.update(account.to_be_bytes());
+    hasher.update(balance.to_be_bytes());
+    hasher.finalize().into()
+}
+
+/// Hashes an inner node of the state tree
+fn node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update([NODE_PREFIX]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
@@ -0,0 +1,47 @@
+use std::sync::Arc;
+use tokio::time::{sleep, timeout, Duration};
+use crate::types::{Clock, SimulatedClock};
+
+/// Tests that a sleeper on a simulated clock wakes up exactly when the clock is advanced past its deadline
+#[tokio::test]
+async fn test_simulated_clock_wakes_sleepers_on_advance() {
+    let clock = Arc::new(SimulatedClock::new());
+    let sleeper = {
+        let clock = clock.clone();
+        tokio::spawn(async move { clock.sleep(Duration::from_secs(10)).await })
+    };
+    sleep(Duration::from_millis(20)).await;
+    assert_eq!(clock.next_deadline(), Some(Duration::from_secs(10)));
+    assert!(!sleeper.is_finished(), "Time does not pass on its own");
+
+    clock.advance(Duration::from_secs(5));
+    sleep(Duration::from_millis(20)).await;
+    assert!(!sleeper.is_finished(), "The deadline has not been reached yet");
+
+    assert!(clock.advance_to_next_deadline());
+    timeout(Duration::from_secs(1), sleeper).await.expect("Sleeper did not wake up").unwrap();
+    assert_eq!(clock.now(), Duration::from_secs(10));
+    assert_eq!(clock.next_deadline(), None, "The deadline is removed once the sleeper woke up");
+    assert!(!clock.advance_to_next_deadline());
+}
+
+/// Tests that the deadline of a sleeper that is dropped before waking up is removed
+#[tokio::test]
+async fn test_simulated_clock_drops_cancelled_deadlines() {
+    let clock = SimulatedClock::new();
+    let result = timeout(Duration::from_millis(20), clock.sleep(Duration::from_secs(1))).await;
+    assert!(result.is_err(), "The sleeper only wakes up on virtual time");
+    assert_eq!(clock.next_deadline(), None);
+}
+
+/// Tests that the auto-advance driver lets virtual time pass without waiting in real time
+#[tokio::test]
+async fn test_simulated_clock_auto_advance() {
+    let clock = Arc::new(SimulatedClock::new());
+    let driver = clock.spawn_auto_advance();
+    timeout(Duration::from_secs(1), clock.sleep(Duration::from_secs(3600)))
+        .await
+        .expect("An hour of virtual time should pass instantly");
+    assert!(clock.now() >= Duration::from_secs(3600));
+    driver.abort();
+}
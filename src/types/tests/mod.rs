@@ -0,0 +1,7 @@
+#[cfg(test)]
+pub(crate) mod strategies;
+mod properties;
+mod clock;
+mod state_diff;
+mod network_spec;
+mod shell_command;
@@ -0,0 +1,44 @@
+use crate::types::{ChainId, NetworkSpec, NetworkSpecError};
+
+/// Helper function: the chain with the given ID
+fn chain(id: &str) -> ChainId {
+    ChainId(id.to_string())
+}
+
+/// Tests that the default spec and chain counts use the conventional chain IDs
+#[test]
+fn test_network_spec_chain_count() {
+    assert_eq!(NetworkSpec::default().chain_ids(), &[chain("chain-1"), chain("chain-2")]);
+
+    let spec = NetworkSpec::with_chain_count(3).unwrap();
+    assert_eq!(spec.chain_ids(), &[chain("chain-1"), chain("chain-2"), chain("chain-3")]);
+    assert_eq!(spec.chain_count(), 3);
+    assert_eq!(spec.chain(1), Some(&chain("chain-1")));
+    assert_eq!(spec.chain(0), None);
+    assert_eq!(spec.chain(4), None);
+    assert_eq!(spec.number_of(&chain("chain-3")), Some(3));
+    assert!(!spec.contains(&chain("chain-4")));
+}
+
+/// Tests that specs are parsed from a chain count or a list of chain IDs
+#[test]
+fn test_network_spec_from_str() {
+    let spec: NetworkSpec = "4".parse().unwrap();
+    assert_eq!(spec.chain_count(), 4);
+    assert_eq!(spec.to_string(), "chain-1,chain-2,chain-3,chain-4");
+
+    let spec: NetworkSpec = "rollup-a, rollup-b".parse().unwrap();
+    assert_eq!(spec.chain_ids(), &[ChainId("rollup-a".to_string()), ChainId("rollup-b".to_string())]);
+    assert_eq!(spec.number_of(&ChainId("rollup-b".to_string())), Some(2));
+}
+
+/// Tests that empty networks, empty chain IDs and duplicate chains are rejected
+#[test]
+fn test_network_spec_rejects_invalid_chains() {
+    assert_eq!(NetworkSpec::with_chain_count(0), Err(NetworkSpecError::NoChains));
+    assert_eq!("chain-1,,chain-2".parse::<NetworkSpec>(), Err(NetworkSpecError::EmptyChainId));
+    assert_eq!(
+        "chain-1,chain-2,chain-1".parse::<NetworkSpec>(),
+        Err(NetworkSpecError::DuplicateChain("chain-1".to_string()))
+    );
+}
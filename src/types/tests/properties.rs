@@ -0,0 +1,80 @@
+use proptest::prelude::*;
+use crate::types::{Transaction, TransactionId, CLTransaction, CLTransactionId, SubBlock, CATStatusUpdate};
+use crate::types::communication::cl_to_hig::{TransactionData, CAT_PATTERN, REGULAR_PATTERN};
+use super::strategies;
+
+proptest! {
+    /// Well-formed data is always accepted with the type given by its prefix
+    #[test]
+    fn valid_data_is_accepted(data in strategies::valid_transaction_data()) {
+        match TransactionData::validate(&data) {
+            Ok(TransactionData::CAT(_)) => prop_assert!(data.starts_with("CAT.")),
+            Ok(TransactionData::Regular(_)) => prop_assert!(data.starts_with("REGULAR.")),
+            other => return Err(TestCaseError::fail(format!("Unexpected validation result for '{}': {:?}", data, other.err()))),
+        }
+    }
+
+    /// Malformed data is rejected with an error instead of a panic
+    #[test]
+    fn malformed_data_is_rejected(data in strategies::malformed_transaction_data()) {
+        prop_assert!(TransactionData::validate(&data).is_err(), "'{}' should be rejected", data);
+    }
+
+    /// Validation agrees with the patterns and the u32 range of the arguments
+    #[test]
+    fn validation_matches_patterns(data in strategies::transaction_data()) {
+        let fits_u32 = data.split_whitespace().skip(1).all(|argument| argument.parse::<u32>().is_ok());
+        if (REGULAR_PATTERN.is_match(&data) || CAT_PATTERN.is_match(&data)) && !fits_u32 {
+            prop_assert!(TransactionData::validate(&data).is_err());
+        }
+        if TransactionData::validate(&data).is_ok() && !data.starts_with("STATUS_UPDATE") {
+            prop_assert!(fits_u32);
+        }
+    }
+
+    /// Transaction::new accepts exactly the transactions with a valid chain list and data
+    #[test]
+    fn transaction_new_validates(tx in strategies::transaction()) {
+        let result = Transaction::new(tx.id.clone(), tx.chain_id.clone(), tx.constituent_chains.clone(), tx.data.clone(), tx.cl_id.clone());
+        let valid = !tx.constituent_chains.is_empty()
+            && tx.constituent_chains.contains(&tx.chain_id)
+            && TransactionData::validate(&tx.data).is_ok();
+        prop_assert_eq!(result.is_ok(), valid);
+    }
+
+    /// Valid transactions survive a serialization round trip
+    #[test]
+    fn transaction_roundtrip(tx in strategies::valid_transaction()) {
+        let json = serde_json::to_string(&tx).unwrap();
+        let decoded: Transaction = serde_json::from_str(&json).unwrap();
+        prop_assert_eq!(decoded, tx);
+    }
+
+    /// A CL transaction holds one transaction per constituent chain under a single transaction ID
+    #[test]
+    fn cl_transaction_has_one_transaction_per_chain(cl_tx in strategies::valid_cl_transaction()) {
+        prop_assert_eq!(cl_tx.transactions.len(), cl_tx.constituent_chains.len());
+        let chains: Vec<_> = cl_tx.transactions.iter().map(|tx| tx.chain_id.clone()).collect();
+        prop_assert_eq!(&chains, &cl_tx.constituent_chains);
+        prop_assert!(cl_tx.transactions.iter().all(|tx| tx.id == TransactionId(format!("{}:tx", cl_tx.id.0))));
+        prop_assert!(CLTransaction::new(cl_tx.id.clone(), cl_tx.constituent_chains.clone(), cl_tx.transactions.clone()).is_ok());
+    }
+
+    /// A CL transaction is rejected if any of its transactions carries malformed data
+    #[test]
+    fn cl_transaction_rejects_malformed_data(tx in strategies::valid_transaction(), data in strategies::malformed_transaction_data()) {
+        let malformed = Transaction { data, ..tx.clone() };
+        let result = CLTransaction::new(CLTransactionId("cl-tx".to_string()), tx.constituent_chains.clone(), vec![tx, malformed]);
+        prop_assert!(result.is_err());
+    }
+
+    /// Subblocks and status updates with arbitrary content survive a serialization round trip
+    #[test]
+    fn subblock_and_status_update_roundtrip(subblock in strategies::subblock(), update in strategies::cat_status_update()) {
+        let decoded: SubBlock = serde_json::from_str(&serde_json::to_string(&subblock).unwrap()).unwrap();
+        prop_assert_eq!(decoded, subblock);
+        let json = serde_json::to_string(&update).unwrap();
+        let decoded: CATStatusUpdate = serde_json::from_str(&json).unwrap();
+        prop_assert_eq!(serde_json::to_string(&decoded).unwrap(), json);
+    }
+}
@@ -0,0 +1,87 @@
+use crate::types::{CATId, CLTransactionId, ChainId, ShellCommand, ShellCommandError};
+use crate::types::NetworkSpec;
+
+/// Tests that quoted data and extra whitespace parse to the same command
+#[test]
+fn test_shell_command_quotes_and_whitespace() {
+    let expected = ShellCommand::SendTx { chain_id: NetworkSpec::test_chain(1), data: "credit 1 100".to_string() };
+    assert_eq!("send-tx chain-1 credit 1 100".parse(), Ok(expected.clone()));
+    assert_eq!("  send-tx   chain-1\t credit  1   100  ".parse(), Ok(expected.clone()));
+    assert_eq!("send-tx chain-1 \"credit 1 100\"".parse(), Ok(expected.clone()));
+    assert_eq!("send-tx 'chain-1' 'credit 1 100'".parse(), Ok(expected));
+
+    // Data that only looks like the chain ID is not cut at the chain ID
+    assert_eq!(
+        "send-cat chain-1,chain-2 CAT.debit 1 50;CAT.credit 2 50".parse(),
+        Ok(ShellCommand::SendCat { chain_ids: vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)], data: "CAT.debit 1 50;CAT.credit 2 50".to_string() })
+    );
+    assert_eq!(
+        "dump-state chain-1 \"my state.json\"".parse(),
+        Ok(ShellCommand::DumpState { chain_id: NetworkSpec::test_chain(1), file: "my state.json".to_string() })
+    );
+    assert_eq!(
+        "balance chain-1 7 --proof".parse(),
+        Ok(ShellCommand::Balance { chain_id: NetworkSpec::test_chain(1), account: 7, with_proof: true })
+    );
+    assert_eq!(
+        "explain-cat cl-tx_cat_1".parse(),
+        Ok(ShellCommand::ExplainCat { cat_id: CATId(CLTransactionId("cl-tx_cat_1".to_string())) })
+    );
+    assert_eq!("quit".parse(), Ok(ShellCommand::Exit));
+}
+
+/// Tests that invalid lines are rejected with the usage of the command
+#[test]
+fn test_shell_command_errors() {
+    assert_eq!("".parse::<ShellCommand>(), Err(ShellCommandError::Empty));
+    assert_eq!("   # a comment".parse::<ShellCommand>(), Err(ShellCommandError::Empty));
+    assert_eq!("sned-tx chain-1".parse::<ShellCommand>(), Err(ShellCommandError::UnknownCommand("sned-tx".to_string())));
+    assert_eq!("send-tx chain-1 \"credit 1".parse::<ShellCommand>(), Err(ShellCommandError::UnterminatedQuote('"')));
+    assert_eq!(
+        "send-tx chain-1".parse::<ShellCommand>(),
+        Err(ShellCommandError::MissingArgument { missing: "transaction data", usage: "send-tx <chain_id> <data>" })
+    );
+    assert_eq!(
+        "status now".parse::<ShellCommand>(),
+        Err(ShellCommandError::UnexpectedArgument { argument: "now".to_string(), usage: "status" })
+    );
+    assert!(matches!(
+        "set-delay chain-1 fast".parse::<ShellCommand>(),
+        Err(ShellCommandError::InvalidArgument { name: "milliseconds", .. })
+    ));
+    assert!(matches!(
+        "send-cat chain-1,,chain-2 CAT.credit 1 1".parse::<ShellCommand>(),
+        Err(ShellCommandError::InvalidArgument { name: "chain IDs", .. })
+    ));
+    assert!(matches!(
+        "balance chain-1 1 --prof".parse::<ShellCommand>(),
+        Err(ShellCommandError::InvalidArgument { name: "flag", .. })
+    ));
+
+    let message = "balance chain-1 -1".parse::<ShellCommand>().unwrap_err().to_string();
+    assert!(message.contains("'-1'") && message.contains("Usage: balance <chain_id> <account> [--proof]"), "Unhelpful error: {}", message);
+}
+
+/// Tests that the canonical form of a command parses back to the same command
+#[test]
+fn test_shell_command_display_round_trip() {
+    let commands = vec![
+        ShellCommand::SendTx { chain_id: NetworkSpec::test_chain(1), data: "send 1 2 50".to_string() },
+        ShellCommand::SendCat { chain_ids: vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)], data: "CAT.debit 1 50;CAT.credit 2 50".to_string() },
+        ShellCommand::Preview { chain_id: ChainId("rollup a".to_string()), data: "credit 1 1".to_string() },
+        ShellCommand::Balance { chain_id: NetworkSpec::test_chain(2), account: 3, with_proof: false },
+        ShellCommand::LoadBalances { chain_id: NetworkSpec::test_chain(2), file: "balances \"v2\".json".to_string() },
+        ShellCommand::SetDelay { chain_id: NetworkSpec::test_chain(1), milliseconds: 200 },
+        ShellCommand::SetBlockInterval { milliseconds: 500 },
+        ShellCommand::NewEpoch,
+        // Arguments with both kinds of quotes
+        ShellCommand::SendTx { chain_id: NetworkSpec::test_chain(1), data: "a\"b'c".to_string() },
+        ShellCommand::LoadBalances { chain_id: NetworkSpec::test_chain(1), file: "it's \"v2\".json".to_string() },
+        // Chain IDs with commas, backslashes and whitespace
+        ShellCommand::SendCat { chain_ids: vec![ChainId("chain,1".to_string()), NetworkSpec::test_chain(2)], data: "CAT.credit 1 1".to_string() },
+        ShellCommand::SendCat { chain_ids: vec![ChainId(" a\\b ".to_string()), ChainId("c\\".to_string())], data: "CAT.credit 1 1".to_string() },
+    ];
+    for command in commands {
+        assert_eq!(command.to_string().parse(), Ok(command.clone()), "{} should parse back", command);
+    }
+}
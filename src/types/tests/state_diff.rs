@@ -0,0 +1,39 @@
+use std::collections::HashMap;
+use crate::types::{AccountDiff, StateDiff};
+
+/// Builds a chain-state snapshot from (account, balance) pairs
+fn snapshot(balances: &[(&str, i64)]) -> HashMap<String, i64> {
+    balances.iter().map(|(account, balance)| (account.to_string(), *balance)).collect()
+}
+
+/// Tests that identical snapshots have no differences
+#[test]
+fn test_state_diff_identical_snapshots() {
+    let state = snapshot(&[("1", 100), ("2", 50)]);
+    let diff = StateDiff::between(&state, &state.clone());
+    assert!(diff.is_empty());
+    assert_eq!(diff.to_string(), "states are identical");
+}
+
+/// Tests that changed, added and removed accounts are reported in numeric account order
+#[test]
+fn test_state_diff_reports_per_account_differences() {
+    let before = snapshot(&[("1", 100), ("2", 50), ("10", 7), ("3", 0)]);
+    let after = snapshot(&[("1", 80), ("2", 50), ("10", 9), ("4", 20)]);
+    let diff = StateDiff::between(&before, &after);
+
+    assert_eq!(diff.accounts, vec![
+        AccountDiff { account: "1".to_string(), before: Some(100), after: Some(80) },
+        AccountDiff { account: "3".to_string(), before: Some(0), after: None },
+        AccountDiff { account: "4".to_string(), before: None, after: Some(20) },
+        AccountDiff { account: "10".to_string(), before: Some(7), after: Some(9) },
+    ]);
+    assert_eq!(diff.net_delta(), 2);
+    assert_eq!(diff.to_string(), [
+        "4 accounts differ (net +2)",
+        "  account 1: 100 -> 80 (-20)",
+        "  account 3: only in first snapshot (0)",
+        "  account 4: only in second snapshot (20)",
+        "  account 10: 7 -> 9 (+2)",
+    ].join("\n"));
+}
@@ -0,0 +1,153 @@
+//! Proptest strategies for the core types.
+//!
+//! The `valid_*` strategies only produce values the constructors accept, the other strategies
+//! also produce malformed values (bad prefixes, out-of-range numbers, empty chain lists, odd IDs).
+
+use proptest::prelude::*;
+use crate::types::{
+    CATId, CATStatusLimited, CATStatusUpdate, ChainId, CLTransaction, CLTransactionId, SubBlock,
+    Transaction, TransactionId,
+};
+use crate::types::communication::cl_to_hig::STATUS_UPDATE_PATTERN;
+
+/// A chain ID of the form `chain-<n>`
+pub fn chain_id() -> impl Strategy<Value = ChainId> {
+    (1u32..=5).prop_map(|n| ChainId(format!("chain-{}", n)))
+}
+
+/// A list of distinct chain IDs with the given number of chains
+pub fn distinct_chain_ids(size: std::ops::RangeInclusive<usize>) -> impl Strategy<Value = Vec<ChainId>> {
+    prop::sample::subsequence((1u32..=5).collect::<Vec<_>>(), size)
+        .prop_map(|ns| ns.into_iter().map(|n| ChainId(format!("chain-{}", n))).collect())
+}
+
+/// An ID made of any printable characters, including the separators used in derived IDs
+pub fn odd_id() -> impl Strategy<Value = String> {
+    prop_oneof![
+        "[a-zA-Z0-9_-]{1,16}",
+        "[ -~]{0,24}",
+        Just(String::new()),
+        Just(":tx".to_string()),
+        Just(".UPDATE".to_string()),
+    ]
+}
+
+/// A well-formed command without its `REGULAR.` or `CAT.` prefix
+pub fn valid_command() -> impl Strategy<Value = String> {
+    prop_oneof![
+        (any::<u32>(), any::<u32>()).prop_map(|(receiver, amount)| format!("credit {} {}", receiver, amount)),
+        (any::<u32>(), any::<u32>()).prop_map(|(account, amount)| format!("debit {} {}", account, amount)),
+        (any::<u32>(), any::<u32>(), any::<u32>()).prop_map(|(sender, receiver, amount)| format!("send {} {} {}", sender, receiver, amount)),
+    ]
+}
+
+/// Well-formed regular or CAT transaction data
+pub fn valid_transaction_data() -> impl Strategy<Value = String> {
+    (prop_oneof![Just("REGULAR"), Just("CAT")], valid_command())
+        .prop_map(|(prefix, command)| format!("{}.{}", prefix, command))
+}
+
+/// Transaction data that the patterns reject or whose numbers do not fit the VM
+pub fn malformed_transaction_data() -> impl Strategy<Value = String> {
+    prop_oneof![
+        // Unknown or misspelled prefixes
+        ("[A-Za-z_]{0,10}", valid_command())
+            .prop_filter("Valid prefix", |(prefix, _)| prefix != "REGULAR" && prefix != "CAT")
+            .prop_map(|(prefix, command)| format!("{}.{}", prefix, command)),
+        // Unknown operations and wrong argument counts
+        (prop_oneof![Just("REGULAR"), Just("CAT")], "[a-z]{1,10}( [0-9]{1,3}){0,4}")
+            .prop_filter("Valid command", |(_, command)| !command.starts_with("credit ") && !command.starts_with("debit ") && !command.starts_with("send "))
+            .prop_map(|(prefix, command)| format!("{}.{}", prefix, command)),
+        // Numbers that match the patterns but overflow u32
+        (prop_oneof![Just("REGULAR"), Just("CAT")], 4_294_967_296u64..=u64::MAX, any::<u32>())
+            .prop_map(|(prefix, account, amount)| format!("{}.credit {} {}", prefix, account, amount)),
+        // Surrounding whitespace and missing separators
+        valid_transaction_data().prop_map(|data| format!(" {}", data)),
+        valid_transaction_data().prop_map(|data| data.replacen('.', "", 1)),
+        // Malformed status updates
+        "STATUS_UPDATE:[A-Za-z]{0,8}(\\.CAT_ID:[ -~]{0,12})?"
+            .prop_filter("Valid status update", |data| !STATUS_UPDATE_PATTERN.is_match(data)),
+    ]
+}
+
+/// Any transaction data, well-formed or not
+pub fn transaction_data() -> impl Strategy<Value = String> {
+    prop_oneof![
+        3 => valid_transaction_data(),
+        3 => malformed_transaction_data(),
+        1 => "[ -~]{0,40}",
+    ]
+}
+
+/// A transaction that `Transaction::new` accepts
+pub fn valid_transaction() -> impl Strategy<Value = Transaction> {
+    (odd_id(), distinct_chain_ids(1..=3), valid_command(), any::<prop::sample::Index>())
+        .prop_map(|(id, chains, command, target)| {
+            let prefix = if chains.len() > 1 { "CAT" } else { "REGULAR" };
+            let chain_id = target.get(&chains).clone();
+            Transaction::new(
+                TransactionId(format!("{}:tx", id)),
+                chain_id,
+                chains,
+                format!("{}.{}", prefix, command),
+                CLTransactionId(id),
+            ).expect("Strategy produced an invalid transaction")
+        })
+}
+
+/// A transaction built without validation: any data, possibly empty chain lists and a target chain
+/// outside the constituent chains
+pub fn transaction() -> impl Strategy<Value = Transaction> {
+    (odd_id(), odd_id(), chain_id(), prop::collection::vec(chain_id(), 0..=3), transaction_data(), prop::option::of(any::<u64>()))
+        .prop_map(|(id, cl_id, chain_id, constituent_chains, data, expiry)| Transaction {
+            id: TransactionId(id),
+            chain_id,
+            constituent_chains,
+            data,
+            cl_id: CLTransactionId(cl_id),
+            expiry,
+        })
+}
+
+/// A CL transaction with one well-formed command per constituent chain
+pub fn valid_cl_transaction() -> impl Strategy<Value = CLTransaction> {
+    (odd_id(), distinct_chain_ids(1..=3))
+        .prop_flat_map(|(id, chains)| {
+            let commands = prop::collection::vec(valid_command(), chains.len());
+            (Just(id), Just(chains), commands)
+        })
+        .prop_map(|(id, chains, commands)| {
+            let prefix = if chains.len() > 1 { "CAT" } else { "REGULAR" };
+            chains.iter().zip(commands)
+                .fold(CLTransaction::builder(CLTransactionId(id), chains.clone()), |builder, (chain_id, command)| {
+                    builder.command(chain_id.clone(), format!("{}.{}", prefix, command))
+                })
+                .build()
+                .expect("Strategy produced an invalid CL transaction")
+        })
+}
+
+/// A subblock of unvalidated transactions
+pub fn subblock() -> impl Strategy<Value = SubBlock> {
+    (any::<u64>(), chain_id(), prop::collection::vec(transaction(), 0..8))
+        .prop_map(|(block_height, chain_id, transactions)| SubBlock { block_height, chain_id, transactions })
+}
+
+/// A CAT status update, possibly from a chain outside its constituent chains
+pub fn cat_status_update() -> impl Strategy<Value = CATStatusUpdate> {
+    let status = prop_oneof![
+        Just(CATStatusLimited::Success),
+        Just(CATStatusLimited::Failure),
+        Just(CATStatusLimited::TimedOut),
+        Just(CATStatusLimited::Rejected),
+    ];
+    (odd_id(), chain_id(), status, prop::collection::vec(chain_id(), 0..=3))
+        .prop_map(|(cat_id, chain_id, status, constituent_chains)| CATStatusUpdate {
+            cat_id: CATId(CLTransactionId(cat_id)),
+            chain_id,
+            status,
+            constituent_chains,
+            epoch: 0,
+            block_height: 0,
+        })
+}
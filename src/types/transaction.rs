@@ -15,9 +15,26 @@ pub enum TransactionStatus {
     Pending,
     /// Transaction is successful and accepted
     Success,
-    /// Transaction failed
-    /// NOTE: we distinguish not between failed due to execution or due to dependency
+    /// Transaction failed (execution failure or Failure decision by the HS)
     Failure,
+    /// CAT did not receive a status update within its lifetime
+    TimedOut,
+    /// CAT was rejected because it depends on a pending transaction
+    Rejected,
+    /// Regular transaction was still pending when its expiry height passed
+    Expired,
+}
+
+impl TransactionStatus {
+    /// Returns true if the status is terminal, i.e. the transaction is no longer pending
+    pub fn is_final(&self) -> bool {
+        !matches!(self, TransactionStatus::Pending)
+    }
+
+    /// Returns true if the status is a failure of any kind (Failure, TimedOut, Rejected, Expired)
+    pub fn is_failure(&self) -> bool {
+        matches!(self, TransactionStatus::Failure | TransactionStatus::TimedOut | TransactionStatus::Rejected | TransactionStatus::Expired)
+    }
 }
 
 /// A simple transaction type for testing destined to be included in a subblock and the respective chain
@@ -33,6 +50,9 @@ pub struct Transaction {
     pub data: String,
     /// The ID of the CL transaction this transaction belongs to
     pub cl_id: CLTransactionId,
+    /// Block height after which the transaction expires if it is still pending (regular transactions only)
+    #[serde(default)]
+    pub expiry: Option<u64>,
 }
 
 impl Transaction {
@@ -54,7 +74,24 @@ impl Transaction {
         }
         // Use TransactionData's validation logic
         TransactionData::validate(&data)?;
-        Ok(Self {id,chain_id,constituent_chains,data,cl_id})
+        Ok(Self {id,chain_id,constituent_chains,data,cl_id,expiry: None})
+    }
+
+    /// Sets the block height after which the transaction expires if it is still pending.
+    ///
+    /// Only regular transactions expire; CATs time out after their lifetime instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `expiry` - The last block height at which the transaction may still be pending
+    pub fn with_expiry(mut self, expiry: u64) -> Self {
+        self.expiry = Some(expiry);
+        self
+    }
+
+    /// Size of the serialized transaction in bytes, as accounted by the CL for byte throughput
+    pub fn serialized_size(&self) -> u64 {
+        serde_json::to_vec(self).map_or(0, |bytes| bytes.len() as u64)
     }
 }
 
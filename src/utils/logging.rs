@@ -1,5 +1,7 @@
+use std::collections::HashMap;
 use std::fs::OpenOptions;
 use std::io::Write;
+use std::path::{Path, PathBuf};
 use std::sync::Mutex;
 use once_cell::sync::Lazy;
 use std::env;
@@ -7,6 +9,29 @@ use std::env;
 static LOG_FILE: Lazy<Mutex<Option<std::fs::File>>> = Lazy::new(|| Mutex::new(None));
 static ENABLE_LOGGING: Lazy<Mutex<bool>> = Lazy::new(|| Mutex::new(true));
 static LOG_TO_FILE: Lazy<Mutex<bool>> = Lazy::new(|| Mutex::new(true));
+static CAPTURE: Lazy<Mutex<Option<LogCapture>>> = Lazy::new(|| Mutex::new(None));
+
+/// Log files of a captured run, one per component
+struct LogCapture {
+    /// Directory the log files are written to
+    dir: PathBuf,
+    /// Log files by their name (None if the file could not be created)
+    files: HashMap<String, Option<std::fs::File>>,
+    /// Whether logging was enabled before the capture started
+    was_enabled: bool,
+}
+
+/// Ends the log capture it was returned for when dropped
+#[must_use = "the capture ends when the guard is dropped"]
+pub struct LogCaptureGuard {
+    _private: (),
+}
+
+impl Drop for LogCaptureGuard {
+    fn drop(&mut self) {
+        stop_capture();
+    }
+}
 
 /// Initializes logging by opening the log file.
 pub fn init_logging() {
@@ -75,6 +100,41 @@ pub fn reset_logging() {
     *LOG_TO_FILE.lock().unwrap() = true;
 }
 
+/// Routes all log messages into `dir`, one file per component, until the returned guard is dropped.
+///
+/// The component of a message is its prefix, so `log("HIG-chain-1", ...)` writes to
+/// `hig-chain-1.log`. While the capture is active, messages are neither printed nor written to
+/// the log file, and logging is enabled even if it was disabled before.
+///
+/// # Arguments
+/// * `dir` - Directory for the log files, created if it does not exist; files in it are replaced
+pub fn capture_to_directory(dir: impl AsRef<Path>) -> std::io::Result<LogCaptureGuard> {
+    std::fs::create_dir_all(dir.as_ref())?;
+    let mut enabled = ENABLE_LOGGING.lock().unwrap();
+    let mut capture = CAPTURE.lock().unwrap();
+    // A capture that is still active keeps the state from before it
+    let was_enabled = capture.as_ref().map_or(*enabled, |capture| capture.was_enabled);
+    *capture = Some(LogCapture { dir: dir.as_ref().to_path_buf(), files: HashMap::new(), was_enabled });
+    *enabled = true;
+    Ok(LogCaptureGuard { _private: () })
+}
+
+/// Ends the log capture, closing its files and restoring whether logging is enabled
+pub fn stop_capture() {
+    let capture = CAPTURE.lock().unwrap().take();
+    if let Some(capture) = capture {
+        *ENABLE_LOGGING.lock().unwrap() = capture.was_enabled;
+    }
+}
+
+/// Name of the file the messages of a component are captured in
+pub fn component_log_file_name(prefix: &str) -> String {
+    let component: String = prefix.trim().chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' { c.to_ascii_lowercase() } else { '_' })
+        .collect();
+    format!("{}.log", if component.is_empty() { "log" } else { &component })
+}
+
 pub fn log(prefix: &str, message: &str) {
     // Check if logging is enabled first - avoid unnecessary work
     let enabled = *ENABLE_LOGGING.lock().unwrap();
@@ -84,6 +144,17 @@ pub fn log(prefix: &str, message: &str) {
 
     // Only do string formatting and further operations if logging is enabled
     let log_message = format!("  [{}]   {}\n", prefix, message);
+
+    if let Some(capture) = &mut *CAPTURE.lock().unwrap() {
+        let dir = &capture.dir;
+        // Messages of a component whose file cannot be created are dropped
+        let file = capture.files.entry(component_log_file_name(prefix))
+            .or_insert_with_key(|name| std::fs::File::create(dir.join(name)).ok());
+        if let Some(file) = file {
+            let _ = file.write_all(log_message.as_bytes());
+        }
+        return;
+    }
     
     let log_to_file = *LOG_TO_FILE.lock().unwrap();
     if log_to_file {
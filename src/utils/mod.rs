@@ -1 +1,4 @@
-pub mod logging; 
\ No newline at end of file
+pub mod logging;
+
+#[cfg(test)]
+mod tests;
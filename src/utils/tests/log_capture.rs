@@ -0,0 +1,37 @@
+use std::fs;
+use crate::utils::logging;
+
+/// Tests the capture of the log messages of a run:
+/// - Messages are written to one file per component, named after the prefix
+/// - Messages logged after the capture ended do not reach the files
+#[test]
+fn test_capture_to_directory() {
+    let dir = std::env::temp_dir().join(format!("hyperplane-log-capture-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+
+    let guard = logging::capture_to_directory(&dir).expect("Failed to start the capture");
+    logging::log("HIG-chain-1", "first message of chain-1");
+    logging::log("NODES SETUP", "nodes are set up");
+    logging::log("HIG-chain-1", "second message of chain-1");
+    drop(guard);
+    logging::log("HIG-chain-1", "message after the capture");
+
+    let hig_log = fs::read_to_string(dir.join("hig-chain-1.log")).expect("The HIG messages should be captured");
+    assert!(hig_log.contains("[HIG-chain-1]   first message of chain-1"));
+    assert!(hig_log.contains("second message of chain-1"));
+    assert!(!hig_log.contains("message after the capture"));
+    let setup_log = fs::read_to_string(dir.join("nodes_setup.log")).expect("The setup messages should be captured");
+    assert!(setup_log.contains("nodes are set up"));
+    assert!(!setup_log.contains("chain-1"));
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+/// Tests the file names of the components
+#[test]
+fn test_component_log_file_name() {
+    assert_eq!(logging::component_log_file_name("CL"), "cl.log");
+    assert_eq!(logging::component_log_file_name("HIG-chain-2"), "hig-chain-2.log");
+    assert_eq!(logging::component_log_file_name("ACCOUNT_SELECTOR"), "account_selector.log");
+    assert_eq!(logging::component_log_file_name(""), "log.log");
+}
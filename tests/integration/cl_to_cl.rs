@@ -6,7 +6,7 @@ use hyperplane::{
     utils::logging,
 };
 use crate::integration::common::{testnodes, submit_transactions};
-use hyperplane::types::constants;
+use hyperplane::types::NetworkSpec;
 use tokio::time::Duration;
 
 /// Helper function to run a two chain CAT test
@@ -22,7 +22,7 @@ async fn run_two_chain_cat_test(transaction_data: &str, expected_status: CATStat
     let (_hs_node, cl_node, _hig_node, _, start_block_height) = testnodes::setup_test_nodes(Duration::from_millis(100)).await;
     logging::log("TEST", "Test nodes initialized successfully");
 
-    logging::log("TEST", &format!("Using chains: {} and {}", constants::CHAIN_1, constants::CHAIN_2));
+    logging::log("TEST", &format!("Using chains: {} and {}", NetworkSpec::test_chain(1).0, NetworkSpec::test_chain(2).0));
 
     // Submit the CAT transaction
     let _cl_tx = submit_transactions::create_and_submit_cat_transaction(
@@ -45,7 +45,7 @@ async fn run_two_chain_cat_test(transaction_data: &str, expected_status: CATStat
     for i in 0..20 {
         let subblock = {
             let node = cl_node.lock().await;
-            node.get_subblock(constants::chain_2(), start_block_height+1+i).await.expect("Failed to get subblock")
+            node.get_subblock(NetworkSpec::test_chain(2), start_block_height+1+i).await.expect("Failed to get subblock")
         };
         let tx_count = subblock.transactions.len();
         // Find our transaction in the subblock
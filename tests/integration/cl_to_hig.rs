@@ -6,7 +6,7 @@ use hyperplane::{
     hyper_ig::HyperIG,
 };
 use crate::integration::common::{testnodes, submit_transactions};
-use hyperplane::types::constants;
+use hyperplane::types::NetworkSpec;
 use tokio::time::Duration;
 use hyperplane::utils::logging;
 
@@ -29,7 +29,7 @@ async fn run_process_subblock_regular_tx(
     // Submit regular transaction using helper function
     let cl_tx = submit_transactions::create_and_submit_regular_transaction(
         &cl_node,
-        &constants::chain_1(),
+        &NetworkSpec::test_chain(1),
         transaction_data,
         "test-tx"
     ).await.expect("Failed to submit transaction");
@@ -5,7 +5,7 @@ use hyperplane::{
     confirmation_layer::ConfirmationLayer,
     HyperScheduler,
     utils::logging,
-    types::constants,
+    types::NetworkSpec,
 };
 use crate::integration::common::{testnodes, submit_transactions};
 use tokio::time::Duration;
@@ -116,9 +116,9 @@ async fn test_hig_delays() {
         let hs_state = node_guard.state.lock().await;
         (
             hs_state.cat_chainwise_statuses.get(&cat_id)
-                .and_then(|statuses| statuses.get(&constants::chain_1())).cloned(),
+                .and_then(|statuses| statuses.get(&NetworkSpec::test_chain(1))).cloned(),
             hs_state.cat_chainwise_statuses.get(&cat_id)
-                .and_then(|statuses| statuses.get(&constants::chain_2())).cloned(),
+                .and_then(|statuses| statuses.get(&NetworkSpec::test_chain(2))).cloned(),
             hs_state.cat_statuses.get(&cat_id).cloned()
         )
     };
@@ -151,7 +151,7 @@ async fn test_hig_delays() {
         let hs_state = node_guard.state.lock().await;
         (
             hs_state.cat_chainwise_statuses.get(&cat_id)
-                .and_then(|statuses| statuses.get(&constants::chain_2())).cloned(),
+                .and_then(|statuses| statuses.get(&NetworkSpec::test_chain(2))).cloned(),
             hs_state.cat_statuses.get(&cat_id).cloned()
         )
     };
@@ -3,7 +3,7 @@ use hyperplane::{
     confirmation_layer::node::ConfirmationLayerNode,
     confirmation_layer::ConfirmationLayer,
     utils::logging,
-    types::constants,
+    types::NetworkSpec,
 };
 use std::sync::Arc;
 use tokio::sync::Mutex;
@@ -22,8 +22,8 @@ pub async fn create_and_submit_cat_transaction(
     transaction_data: &str,
     cat_id: &str,
 ) -> Result<CLTransaction, anyhow::Error> {
-    let chain_id_1 = constants::chain_1();
-    let chain_id_2 = constants::chain_2();
+    let chain_id_1 = NetworkSpec::test_chain(1);
+    let chain_id_2 = NetworkSpec::test_chain(2);
 
     // Create a transaction for each chain
     let tx_chain_1 = Transaction::new(
@@ -4,10 +4,11 @@ use hyperplane::{
     types::{CATStatusLimited, TransactionStatus, CATId, CATStatus},
     confirmation_layer::ConfirmationLayer,
     hyper_ig::HyperIG,
+    hyper_scheduler::HyperScheduler,
     utils::logging,
 };
 use crate::integration::common::{testnodes, submit_transactions};
-use hyperplane::types::constants;
+use hyperplane::types::NetworkSpec;
 use tokio::time::{Duration, timeout};
 
 // Helper function to run a two chain CAT test
@@ -58,10 +59,7 @@ async fn run_two_chain_cat_test(transaction_data: &str, expected_status: CATStat
     logging::log("TEST", &format!("Transaction status in HIG-chain-1: {:?}", status));
     
     // The status should match the expected status from the CAT transaction
-    let expected_tx_status = match expected_status {
-        CATStatusLimited::Success => TransactionStatus::Success,
-        CATStatusLimited::Failure => TransactionStatus::Failure,
-    };
+    let expected_tx_status = TransactionStatus::from(expected_status);
     assert_eq!(status, expected_tx_status, "Transaction status should match the expected status from CAT transaction");
     
     logging::log("TEST", "=== Test completed successfully ===");
@@ -91,10 +89,10 @@ async fn run_two_chain_cat_test_with_credits(
     // Credit chains if needed
     logging::log("TEST", "Crediting chains...");
     if chain_1_credit {
-        submit_transactions::credit_account(&cl_node, &constants::chain_1(), "1").await.expect("Failed to submit credit transaction for chain-1");
+        submit_transactions::credit_account(&cl_node, &NetworkSpec::test_chain(1), "1").await.expect("Failed to submit credit transaction for chain-1");
     }
     if chain_2_credit {
-        submit_transactions::credit_account(&cl_node, &constants::chain_2(), "1").await.expect("Failed to submit credit transaction for chain-2");
+        submit_transactions::credit_account(&cl_node, &NetworkSpec::test_chain(2), "1").await.expect("Failed to submit credit transaction for chain-2");
     }
 
     // Wait for block production
@@ -121,10 +119,7 @@ async fn run_two_chain_cat_test_with_credits(
     logging::log("TEST", &format!("Transaction status in HIG of chain-1: {:?}", status));
     
     // The status should match the expected status from the CAT transaction
-    let expected_tx_status = match expected_status {
-        CATStatusLimited::Success => TransactionStatus::Success,
-        CATStatusLimited::Failure => TransactionStatus::Failure,
-    };
+    let expected_tx_status = TransactionStatus::from(expected_status);
     assert_eq!(status, expected_tx_status, "Transaction status should match the expected status from CAT transaction. Should: {:?}, Got: {:?}", expected_tx_status, status);
     
     logging::log("TEST", "=== Test completed successfully ===");
@@ -203,7 +198,7 @@ async fn test_cat_credit_then_send() {
     logging::log("TEST", "Submitting regular send transaction...");
     let cl_tx_send = submit_transactions::create_and_submit_regular_transaction(
         &cl_node,
-        &constants::chain_1(),
+        &NetworkSpec::test_chain(1),
         "send 1 2 50",
         "send1"
     ).await.expect("Failed to submit regular send transaction");
@@ -282,9 +277,9 @@ async fn test_hig_delays() {
         let hs_state = node_guard.state.lock().await;
         (
             hs_state.cat_chainwise_statuses.get(&cat_id)
-                .and_then(|statuses| statuses.get(&constants::chain_1())).cloned(),
+                .and_then(|statuses| statuses.get(&NetworkSpec::test_chain(1))).cloned(),
             hs_state.cat_chainwise_statuses.get(&cat_id)
-                .and_then(|statuses| statuses.get(&constants::chain_2())).cloned(),
+                .and_then(|statuses| statuses.get(&NetworkSpec::test_chain(2))).cloned(),
             hs_state.cat_statuses.get(&cat_id).cloned()
         )
     };
@@ -316,7 +311,7 @@ async fn test_hig_delays() {
         let hs_state = node_guard.state.lock().await;
         (
             hs_state.cat_chainwise_statuses.get(&cat_id)
-                .and_then(|statuses| statuses.get(&constants::chain_1())).cloned(),
+                .and_then(|statuses| statuses.get(&NetworkSpec::test_chain(1))).cloned(),
             hs_state.cat_statuses.get(&cat_id).cloned()
         )
     };
@@ -334,3 +329,42 @@ async fn test_hig_delays() {
     logging::log("TEST", "=== Test completed successfully ===\n");
 }
 
+/// Tests that a rejection on one chain reaches the other chain through the HS and the CL:
+/// - Chain-1 rejects CATs that depend on pending transactions, chain-2 postpones them
+/// - Submit a CAT that locks account 1 and, in the same block, a CAT that depends on it
+/// - Verify that the HS failed the second CAT
+/// - Verify that both HIGs record the second CAT as Rejected, not as a plain Failure
+#[tokio::test]
+async fn test_rejected_status_reaches_other_chain() {
+    logging::init_logging();
+    logging::log("TEST", "\n=== Starting test_rejected_status_reaches_other_chain ===");
+
+    let (hs_node, cl_node, hig_node_1, hig_node_2, _start_block_height) = testnodes::setup_test_nodes(Duration::from_millis(100)).await;
+    hig_node_1.lock().await.set_allow_cat_pending_dependencies(false).await;
+    hig_node_2.lock().await.set_allow_cat_pending_dependencies(true).await;
+
+    let _first = submit_transactions::create_and_submit_cat_transaction(
+        &cl_node,
+        "credit 1 100",
+        "cat-lock"
+    ).await.expect("Failed to submit CAT transaction");
+    let second = submit_transactions::create_and_submit_cat_transaction(
+        &cl_node,
+        "send 1 2 50",
+        "cat-dependent"
+    ).await.expect("Failed to submit dependent CAT transaction");
+
+    // Wait for the CATs to be included, proposed, decided and their status updates to be included
+    tokio::time::sleep(Duration::from_millis(500)).await;
+
+    let cat_id = CATId(second.id.clone());
+    assert_eq!(hs_node.lock().await.get_cat_status(cat_id).await.ok(), Some(CATStatus::Failure), "The dependent CAT should fail in HS");
+
+    for (hig_node, chain_id) in [(&hig_node_1, NetworkSpec::test_chain(1)), (&hig_node_2, NetworkSpec::test_chain(2))] {
+        let tx_id = second.transactions.iter().find(|tx| tx.chain_id == chain_id).expect("Missing transaction for chain").id.clone();
+        let status = hig_node.lock().await.get_transaction_status(tx_id).await.expect("Failed to get transaction status");
+        assert_eq!(status, TransactionStatus::Rejected, "The dependent CAT should be rejected on chain '{}'", chain_id.0);
+    }
+
+    logging::log("TEST", "=== Test completed successfully ===\n");
+}
@@ -7,7 +7,7 @@ use hyperplane::{
     utils::logging,
 };
 use crate::integration::common::testnodes;
-use hyperplane::types::constants;
+use hyperplane::types::NetworkSpec;
 use tokio::time::Duration;
 
 /// Tests that the HIG waits for all proposals before sending a status update:
@@ -24,7 +24,7 @@ async fn test_hs_waits_for_all_proposals() {
 
     // Create a CAT transaction
     let cat_id = CATId(CLTransactionId("test-cat".to_string()));
-    let constituent_chains = vec![constants::chain_1(), constants::chain_2()];
+    let constituent_chains = vec![NetworkSpec::test_chain(1), NetworkSpec::test_chain(2)];
     logging::log("TEST", &format!("Created CAT '{}' with chains {:?}", cat_id.0, constituent_chains));
 
     // Send proposals from both HIGs
@@ -60,7 +60,7 @@ async fn test_hs_waits_for_all_proposals() {
     for block_id in start_block_height + 1..=start_block_height + 6 {
         let subblock = {
             let node = cl_node.lock().await;
-            node.get_subblock(constants::chain_1(), block_id)
+            node.get_subblock(NetworkSpec::test_chain(1), block_id)
                 .await
                 .expect("Failed to get subblock")
         };
@@ -7,7 +7,7 @@ use hyperplane::{
     utils::logging,
 };
 use crate::integration::common::testnodes;
-use hyperplane::types::constants;
+use hyperplane::types::NetworkSpec;
 use tokio::time::Duration;
 
 /// Tests that a single-chain CAT status update is properly included in a block:
@@ -25,7 +25,7 @@ async fn test_single_chain_cat_status_update() {
     logging::log("TEST", &format!("Sending CAT status update for '{}'...", cat_id.0));
     {
         let mut node = hs_node.lock().await;
-        node.send_cat_status_update(cat_id.clone(), vec![constants::chain_1()], CATStatusLimited::Success)
+        node.send_cat_status_update(cat_id.clone(), vec![NetworkSpec::test_chain(1)], CATStatusLimited::Success)
             .await
             .expect("Failed to send status update");
     }
@@ -46,10 +46,10 @@ async fn test_single_chain_cat_status_update() {
     assert!(current_block >= start_block_height + 3 && current_block <= start_block_height + 6, "Current block not in correct range {}", current_block);
 
     // Get subblock and verify transaction
-    logging::log("TEST", &format!("Getting subblock for chain {}...", constants::CHAIN_1));
+    logging::log("TEST", &format!("Getting subblock for chain {}...", NetworkSpec::test_chain(1).0));
     let subblock = {
         let node = cl_node.lock().await;
-        node.get_subblock(constants::chain_1(), start_block_height + 1)
+        node.get_subblock(NetworkSpec::test_chain(1), start_block_height + 1)
             .await
             .expect("Failed to get subblock")
     };
@@ -85,7 +85,7 @@ async fn test_several_single_chain_cat_status_updates() {
             i + 1, updates.len(), cat_id.0, status));
         {
             let mut node = hs_node.lock().await;
-            node.send_cat_status_update(cat_id.clone(), vec![constants::chain_1()], status.clone())
+            node.send_cat_status_update(cat_id.clone(), vec![NetworkSpec::test_chain(1)], status.clone())
                 .await
                 .expect("Failed to send status update");
         }
@@ -102,7 +102,7 @@ async fn test_several_single_chain_cat_status_updates() {
         for block_id in start_block_height + 1..=start_block_height + 9 {
             let subblock = {
                 let node = cl_node.lock().await;
-                node.get_subblock(constants::chain_1(), block_id)
+                node.get_subblock(NetworkSpec::test_chain(1), block_id)
                     .await
                     .expect("Failed to get subblock")
             };